@@ -1,14 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use anyhow::{Context, Result};
-
+use crate::config::AuthorAliases;
 use crate::date_utils;
+use crate::deadline::{date_from_timestamp, parse_deadline, Deadline};
+use crate::error::{Error, Result};
 use crate::git::git_command;
-use crate::model::{BlameEntry, BlameInfo, BlameResult, ScanResult, TodoItem};
+use crate::model::{
+    AgeBucket, BlameAgeSummary, BlameAuthorSummary, BlameEntry, BlameInfo, BlameResult, DiffResult,
+    DiffStatus, ScanResult, TodoItem,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RawBlameData {
     pub author: String,
     pub email: String,
@@ -66,6 +72,84 @@ pub fn blame_file(file_path: &str, root: &Path) -> Result<HashMap<usize, RawBlam
     Ok(parse_porcelain_blame(&output))
 }
 
+/// Upper bound on concurrent `git blame` processes.
+const MAX_BLAME_THREADS: usize = 8;
+
+/// Blame each file once, spreading the `git blame` processes over a bounded
+/// pool of worker threads. Results line up with `files`; a file git cannot
+/// blame (e.g. untracked) yields `None`.
+pub fn blame_files(files: &[&str], root: &Path) -> Vec<Option<HashMap<usize, RawBlameData>>> {
+    let workers = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_BLAME_THREADS)
+        .min(files.len());
+    if workers <= 1 {
+        return files.iter().map(|f| blame_file(f, root).ok()).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; files.len()]);
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(file) = files.get(i) else { break };
+                let blame = blame_file(file, root).ok();
+                results.lock().unwrap()[i] = blame;
+            });
+        }
+    });
+    results.into_inner().unwrap()
+}
+
+/// Run `git blame --porcelain` on a file as of `git_ref`.
+pub fn blame_file_at_ref(
+    file_path: &str,
+    git_ref: &str,
+    root: &Path,
+) -> Result<HashMap<usize, RawBlameData>> {
+    let output = git_command(&["blame", "--porcelain", git_ref, "--", file_path], root)?;
+    Ok(parse_porcelain_blame(&output))
+}
+
+/// Blame author of each diff entry, parallel to `diff.entries`.
+///
+/// Added items are blamed at the head ref (or the working tree), removed
+/// items at the base ref. `None` marks entries whose file or line could
+/// not be blamed. Authors are mapped through `aliases`.
+pub fn diff_blame_authors(
+    diff: &DiffResult,
+    root: &Path,
+    aliases: &AuthorAliases,
+) -> Vec<Option<String>> {
+    let mut blames: HashMap<(DiffStatus, &str), Option<HashMap<usize, RawBlameData>>> =
+        HashMap::new();
+    diff.entries
+        .iter()
+        .map(|entry| {
+            let file = entry.item.file.as_str();
+            let blame = blames.entry((entry.status, file)).or_insert_with(|| {
+                let git_ref = match entry.status {
+                    DiffStatus::Added => diff.head_ref.as_deref(),
+                    DiffStatus::Removed => Some(diff.base_ref.as_str()),
+                };
+                match git_ref {
+                    Some(git_ref) => blame_file_at_ref(file, git_ref, root).ok(),
+                    None => blame_file(file, root).ok(),
+                }
+            });
+            blame
+                .as_ref()
+                .and_then(|b| b.get(&entry.item.line))
+                .map(|raw| {
+                    aliases
+                        .canonical_identity(&raw.author, &raw.email)
+                        .to_string()
+                })
+        })
+        .collect()
+}
+
 /// Convert a unix timestamp to a "YYYY-MM-DD" date string.
 pub fn timestamp_to_date_string(timestamp: i64) -> String {
     // Manual conversion without external date library
@@ -93,36 +177,139 @@ pub fn parse_duration_days(s: &str) -> Result<u64> {
     let numeric = s.strip_suffix('d').unwrap_or(s);
     numeric
         .parse::<u64>()
-        .with_context(|| format!("invalid duration: {}", s))
+        .map_err(|_| Error::Invalid(format!("invalid duration: {}", s)))
+}
+
+/// Parse a `list --since-date` window into the earliest date it keeps: a
+/// duration like "30d" counted back from `today`, or a `YYYY-MM-DD` date.
+pub fn parse_since_date(s: &str, today: &Deadline) -> Result<Deadline> {
+    if let Ok(days) = parse_duration_days(s) {
+        return Ok(today.days_before(days));
+    }
+    match parse_deadline(s) {
+        Some(date) if !s.to_ascii_uppercase().contains('Q') => Ok(date),
+        _ => Err(Error::Invalid(format!(
+            "invalid --since-date: {} (expected a duration like 30d or a date like 2025-01-31)",
+            s.trim()
+        ))),
+    }
+}
+
+/// Default `age` bucket cut points in days: 0-7d, 8-30d, 31-90d, 91-365d, 366d+.
+pub const DEFAULT_AGE_BUCKETS: &[u64] = &[7, 30, 90, 365];
+
+/// Parse comma-separated bucket cut points like "7d,30d,90d" into days.
+/// Cut points must be strictly increasing.
+pub fn parse_age_buckets(s: &str) -> Result<Vec<u64>> {
+    let cuts = s
+        .split(',')
+        .map(parse_duration_days)
+        .collect::<Result<Vec<u64>>>()?;
+    if cuts.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(Error::Invalid(format!(
+            "bucket cut points must be strictly increasing: {}",
+            s
+        )));
+    }
+    Ok(cuts)
+}
+
+/// Bin blamed TODOs by age. Each cut point closes a bucket (`0-7d`, `8-30d`,
+/// ...), followed by an open-ended bucket and an `unknown` bucket for items
+/// without a blame date: untracked files and uncommitted lines.
+pub fn compute_age_histogram(
+    blame: &BlameResult,
+    total_items: usize,
+    cuts: &[u64],
+) -> Vec<AgeBucket> {
+    let mut counts = vec![0usize; cuts.len() + 1];
+    let mut dated = 0;
+    for entry in blame.entries.iter().filter(|e| !e.blame.date.is_empty()) {
+        let idx = cuts.partition_point(|&cut| cut < entry.blame.age_days);
+        counts[idx] += 1;
+        dated += 1;
+    }
+
+    let mut labels: Vec<String> = Vec::with_capacity(cuts.len() + 2);
+    let mut low = 0;
+    for &cut in cuts {
+        labels.push(format!("{}-{}d", low, cut));
+        low = cut + 1;
+    }
+    labels.push(format!("{}d+", low));
+
+    let mut buckets: Vec<AgeBucket> = labels
+        .into_iter()
+        .zip(counts)
+        .map(|(label, count)| AgeBucket { label, count })
+        .collect();
+    buckets.push(AgeBucket {
+        label: "unknown".to_string(),
+        count: total_items.saturating_sub(dated),
+    });
+    buckets
+}
+
+/// Resolve relative deadlines (e.g. `+14d`) against the git blame date of
+/// each TODO line. Items in untracked files keep their today-based deadline.
+pub fn resolve_relative_deadlines(items: &mut [TodoItem], root: &Path) {
+    let mut by_file: BTreeMap<String, Vec<&mut TodoItem>> = BTreeMap::new();
+    for item in items.iter_mut().filter(|i| i.relative_deadline.is_some()) {
+        by_file.entry(item.file.clone()).or_default().push(item);
+    }
+
+    let files: Vec<&str> = by_file.keys().map(String::as_str).collect();
+    let blames = blame_files(&files, root);
+    for (items, blame_data) in by_file.into_values().zip(blames) {
+        let Some(blame_data) = blame_data else {
+            continue;
+        };
+        for item in items {
+            if let Some(raw) = blame_data.get(&item.line) {
+                item.resolve_relative_deadline(&date_from_timestamp(raw.timestamp));
+            }
+        }
+    }
 }
 
 /// Build blame entries for all TODO items in a scan result.
+///
+/// Blame authors are mapped through `aliases` (`[authors.aliases]`).
 pub fn compute_blame(
     scan: &ScanResult,
     root: &Path,
     stale_threshold_days: u64,
+    aliases: &AuthorAliases,
 ) -> Result<BlameResult> {
-    // Group items by file
-    let mut by_file: HashMap<&str, Vec<&TodoItem>> = HashMap::new();
+    // Group items by file; each file is blamed once
+    let mut by_file: BTreeMap<&str, Vec<&TodoItem>> = BTreeMap::new();
     for item in &scan.items {
         by_file.entry(&item.file).or_default().push(item);
     }
 
+    let files: Vec<&str> = by_file.keys().copied().collect();
+    let blames = blame_files(&files, root);
+
     let mut entries: Vec<BlameEntry> = Vec::new();
 
-    for (file, items) in &by_file {
-        let blame_data = match blame_file(file, root) {
-            Ok(data) => data,
-            Err(_) => continue, // Skip files not tracked by git
+    for (items, blame_data) in by_file.values().zip(blames) {
+        let Some(blame_data) = blame_data else {
+            continue; // Skip files not tracked by git
         };
 
         for item in items {
             let raw = blame_data.get(&item.line);
+            let mut item = (*item).clone();
+            if let Some(raw) = raw {
+                item.resolve_relative_deadline(&date_from_timestamp(raw.timestamp));
+            }
             let blame_info = match raw {
                 Some(raw) => {
                     let age_days = compute_age_days(raw.timestamp);
                     BlameInfo {
-                        author: raw.author.clone(),
+                        author: aliases
+                            .canonical_identity(&raw.author, &raw.email)
+                            .to_string(),
                         email: raw.email.clone(),
                         date: timestamp_to_date_string(raw.timestamp),
                         age_days,
@@ -141,7 +328,7 @@ pub fn compute_blame(
             let stale = blame_info.age_days >= stale_threshold_days;
 
             entries.push(BlameEntry {
-                item: (*item).clone(),
+                item,
                 blame: blame_info,
                 stale,
             });
@@ -156,6 +343,14 @@ pub fn compute_blame(
             .then(a.item.line.cmp(&b.item.line))
     });
 
+    Ok(summarize_blame(entries, stale_threshold_days))
+}
+
+/// Wrap blame entries in a `BlameResult`, computing the summary fields.
+///
+/// Entries without a blame date (lines not yet committed) are left out of
+/// the `oldest`/`newest` pair.
+pub fn summarize_blame(entries: Vec<BlameEntry>, stale_threshold_days: u64) -> BlameResult {
     let total = entries.len();
     let stale_count = entries.iter().filter(|e| e.stale).count();
     let avg_age_days = if total > 0 {
@@ -164,18 +359,247 @@ pub fn compute_blame(
         0
     };
 
-    Ok(BlameResult {
+    // Ties on date resolve to the earliest file/line so the pair does not
+    // depend on the caller's sort order.
+    let location = |e: &&BlameEntry| (e.item.file.clone(), e.item.line);
+    let dated = || entries.iter().filter(|e| !e.blame.date.is_empty());
+    let oldest = dated()
+        .min_by(|a, b| {
+            a.blame
+                .date
+                .cmp(&b.blame.date)
+                .then(location(a).cmp(&location(b)))
+        })
+        .map(age_summary);
+    let newest = dated()
+        .max_by(|a, b| {
+            a.blame
+                .date
+                .cmp(&b.blame.date)
+                .then(location(b).cmp(&location(a)))
+        })
+        .map(age_summary);
+
+    BlameResult {
         entries,
         total,
         avg_age_days,
         stale_count,
         stale_threshold_days,
-    })
+        oldest,
+        newest,
+        authors: None,
+    }
+}
+
+/// Roll blame entries up per author: TODO count, average age, and stale
+/// count. Sorted by count, largest first, then by author name.
+pub fn summarize_by_author(entries: &[BlameEntry]) -> Vec<BlameAuthorSummary> {
+    let mut by_author: BTreeMap<&str, (usize, u64, usize)> = BTreeMap::new();
+    for entry in entries {
+        let (count, age_sum, stale) = by_author.entry(&entry.blame.author).or_default();
+        *count += 1;
+        *age_sum += entry.blame.age_days;
+        *stale += entry.stale as usize;
+    }
+    let mut authors: Vec<BlameAuthorSummary> = by_author
+        .into_iter()
+        .map(
+            |(author, (count, age_sum, stale_count))| BlameAuthorSummary {
+                author: author.to_string(),
+                count,
+                avg_age_days: age_sum / count as u64,
+                stale_count,
+            },
+        )
+        .collect();
+    authors.sort_by_key(|a| std::cmp::Reverse(a.count));
+    authors
+}
+
+fn age_summary(entry: &BlameEntry) -> BlameAgeSummary {
+    BlameAgeSummary {
+        file: entry.item.file.clone(),
+        line: entry.item.line,
+        date: entry.blame.date.clone(),
+        age_days: entry.blame.age_days,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{Priority, Tag};
+    use crate::test_helpers::helpers::git;
+
+    fn make_entry(file: &str, line: usize, date: &str, age_days: u64) -> BlameEntry {
+        BlameEntry {
+            item: TodoItem {
+                file: file.to_string(),
+                line,
+                column: 1,
+                tag: Tag::Todo,
+                message: "task".to_string(),
+                author: None,
+                issue_ref: None,
+                priority: Priority::Normal,
+                deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
+            },
+            blame: BlameInfo {
+                author: "tester".to_string(),
+                email: "tester@test.com".to_string(),
+                date: date.to_string(),
+                age_days,
+                commit: "abc12345".to_string(),
+            },
+            stale: age_days >= 365,
+        }
+    }
+
+    #[test]
+    fn test_summarize_blame_oldest_and_newest() {
+        let result = summarize_blame(
+            vec![
+                make_entry("b.rs", 3, "2023-05-01", 500),
+                make_entry("a.rs", 1, "2021-01-01", 1200),
+                make_entry("c.rs", 7, "2024-06-01", 100),
+            ],
+            365,
+        );
+        assert_eq!(result.total, 3);
+        assert_eq!(result.stale_count, 2);
+        assert_eq!(result.avg_age_days, 600);
+        let oldest = result.oldest.unwrap();
+        assert_eq!((oldest.file.as_str(), oldest.line), ("a.rs", 1));
+        assert_eq!(oldest.age_days, 1200);
+        let newest = result.newest.unwrap();
+        assert_eq!(
+            (newest.file.as_str(), newest.date.as_str()),
+            ("c.rs", "2024-06-01")
+        );
+    }
+
+    #[test]
+    fn test_summarize_blame_skips_uncommitted_and_breaks_ties_by_location() {
+        let result = summarize_blame(
+            vec![
+                make_entry("z.rs", 1, "2024-01-01", 10),
+                make_entry("a.rs", 9, "2024-01-01", 10),
+                make_entry("new.rs", 1, "", 0),
+            ],
+            365,
+        );
+        assert_eq!(result.oldest.unwrap().file, "a.rs");
+        assert_eq!(result.newest.unwrap().file, "a.rs");
+    }
+
+    #[test]
+    fn test_summarize_by_author() {
+        let with_author = |author: &str, age_days: u64| {
+            let mut entry = make_entry("a.rs", 1, "2024-01-01", age_days);
+            entry.blame.author = author.to_string();
+            entry
+        };
+        let authors = summarize_by_author(&[
+            with_author("bob", 10),
+            with_author("alice", 400),
+            with_author("carol", 20),
+            with_author("alice", 100),
+            with_author("carol", 30),
+        ]);
+
+        assert_eq!(
+            authors,
+            vec![
+                BlameAuthorSummary {
+                    author: "alice".to_string(),
+                    count: 2,
+                    avg_age_days: 250,
+                    stale_count: 1,
+                },
+                BlameAuthorSummary {
+                    author: "carol".to_string(),
+                    count: 2,
+                    avg_age_days: 25,
+                    stale_count: 0,
+                },
+                BlameAuthorSummary {
+                    author: "bob".to_string(),
+                    count: 1,
+                    avg_age_days: 10,
+                    stale_count: 0,
+                },
+            ]
+        );
+        assert!(summarize_by_author(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_age_buckets() {
+        assert_eq!(parse_age_buckets("7d,30d,90d").unwrap(), vec![7, 30, 90]);
+        assert_eq!(parse_age_buckets("1, 14").unwrap(), vec![1, 14]);
+        assert!(parse_age_buckets("30d,7d").is_err());
+        assert!(parse_age_buckets("7d,7d").is_err());
+        assert!(parse_age_buckets("7d,soon").is_err());
+        assert!(parse_age_buckets("").is_err());
+    }
+
+    #[test]
+    fn test_compute_age_histogram_default_buckets() {
+        let blame = summarize_blame(
+            vec![
+                make_entry("a.rs", 1, "2024-01-01", 0),
+                make_entry("a.rs", 2, "2024-01-01", 7),
+                make_entry("a.rs", 3, "2024-01-01", 8),
+                make_entry("a.rs", 4, "2024-01-01", 365),
+                make_entry("a.rs", 5, "2024-01-01", 366),
+                make_entry("a.rs", 6, "", 0),
+            ],
+            365,
+        );
+        // One more item lives in a file git does not track
+        let histogram = compute_age_histogram(&blame, 7, DEFAULT_AGE_BUCKETS);
+
+        let pairs: Vec<(&str, usize)> = histogram
+            .iter()
+            .map(|b| (b.label.as_str(), b.count))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("0-7d", 2),
+                ("8-30d", 1),
+                ("31-90d", 0),
+                ("91-365d", 1),
+                ("366d+", 1),
+                ("unknown", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compute_age_histogram_custom_buckets() {
+        let blame = summarize_blame(vec![make_entry("a.rs", 1, "2024-01-01", 20)], 365);
+        let histogram = compute_age_histogram(&blame, 1, &[14]);
+        let labels: Vec<&str> = histogram.iter().map(|b| b.label.as_str()).collect();
+        assert_eq!(labels, vec!["0-14d", "15d+", "unknown"]);
+        assert_eq!(histogram[1].count, 1);
+        assert_eq!(histogram[2].count, 0);
+    }
+
+    #[test]
+    fn test_summarize_blame_empty() {
+        let result = summarize_blame(vec![], 90);
+        assert_eq!(result.total, 0);
+        assert_eq!(result.avg_age_days, 0);
+        assert_eq!(result.stale_threshold_days, 90);
+        assert!(result.oldest.is_none());
+        assert!(result.newest.is_none());
+    }
 
     #[test]
     fn test_parse_duration_days_with_suffix() {
@@ -187,6 +611,22 @@ mod tests {
         assert_eq!(parse_duration_days("365").unwrap(), 365);
     }
 
+    #[test]
+    fn test_parse_since_date() {
+        let today = parse_deadline("2025-03-10").unwrap();
+        assert_eq!(
+            parse_since_date("30d", &today).unwrap().to_string(),
+            "2025-02-08"
+        );
+        assert_eq!(parse_since_date("0", &today).unwrap(), today);
+        assert_eq!(
+            parse_since_date("2024-12-31", &today).unwrap().to_string(),
+            "2024-12-31"
+        );
+        assert!(parse_since_date("2025-Q1", &today).is_err());
+        assert!(parse_since_date("last week", &today).is_err());
+    }
+
     #[test]
     fn test_parse_duration_days_invalid() {
         assert!(parse_duration_days("abc").is_err());
@@ -384,4 +824,82 @@ filename test.rs
         // Unix epoch: timestamp 0 should produce 1970-01-01
         assert_eq!(timestamp_to_date_string(0), "1970-01-01");
     }
+
+    // ---- blame_files / compute_blame against a real repository ----
+
+    /// Repo with 20 committed files (two commits, so blame data differs per
+    /// line) plus one untracked file.
+    fn setup_many_files() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path();
+        git(cwd, &["init"]);
+        git(cwd, &["config", "user.email", "a@example.com"]);
+        git(cwd, &["config", "user.name", "Alice"]);
+        for i in 0..20 {
+            std::fs::write(cwd.join(format!("f{i:02}.rs")), "// TODO: first\n").unwrap();
+        }
+        git(cwd, &["add", "-A"]);
+        git(cwd, &["commit", "-m", "one"]);
+        git(cwd, &["config", "user.name", "Bob"]);
+        for i in (0..20).step_by(3) {
+            std::fs::write(
+                cwd.join(format!("f{i:02}.rs")),
+                "// TODO: first\n// FIXME: second\n",
+            )
+            .unwrap();
+        }
+        git(cwd, &["commit", "-am", "two"]);
+        std::fs::write(cwd.join("untracked.rs"), "// TODO: new\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_blame_files_matches_serial_blame() {
+        let dir = setup_many_files();
+        let mut files: Vec<String> = (0..20).map(|i| format!("f{i:02}.rs")).collect();
+        files.push("untracked.rs".to_string());
+        let files: Vec<&str> = files.iter().map(String::as_str).collect();
+
+        let parallel = blame_files(&files, dir.path());
+        let serial: Vec<_> = files
+            .iter()
+            .map(|f| blame_file(f, dir.path()).ok())
+            .collect();
+
+        assert_eq!(parallel, serial);
+        assert!(parallel[..20].iter().all(Option::is_some));
+        assert!(parallel[20].is_none());
+        assert_eq!(parallel[3].as_ref().unwrap()[&2].author, "Bob");
+    }
+
+    #[test]
+    fn test_compute_blame_is_deterministic_across_files() {
+        let dir = setup_many_files();
+        let scan =
+            crate::scanner::scan_directory(dir.path(), &crate::config::Config::default()).unwrap();
+
+        let first = compute_blame(&scan, dir.path(), 365, &AuthorAliases::default()).unwrap();
+        let second = compute_blame(&scan, dir.path(), 365, &AuthorAliases::default()).unwrap();
+
+        // Untracked files are skipped; every committed TODO is blamed once
+        assert_eq!(first.entries.len(), 27);
+        let key = |r: &BlameResult| -> Vec<(String, usize, String, String)> {
+            r.entries
+                .iter()
+                .map(|e| {
+                    (
+                        e.item.file.clone(),
+                        e.item.line,
+                        e.blame.author.clone(),
+                        e.blame.commit.clone(),
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(key(&first), key(&second));
+        let mut sorted = key(&first);
+        sorted.sort();
+        assert_eq!(key(&first), sorted);
+        assert_eq!(first.entries[1].blame.author, "Bob");
+    }
 }
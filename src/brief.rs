@@ -2,7 +2,13 @@ use std::collections::HashSet;
 
 use crate::model::*;
 
-pub fn compute_brief(scan: &ScanResult, diff: Option<&DiffResult>) -> BriefResult {
+/// Summarize a scan. `tag_styles` breaks ties between equally urgent items
+/// by tag severity.
+pub fn compute_brief(
+    scan: &ScanResult,
+    diff: Option<&DiffResult>,
+    tag_styles: &TagStyles,
+) -> BriefResult {
     let total_items = scan.items.len();
 
     let total_files = scan
@@ -28,9 +34,11 @@ pub fn compute_brief(scan: &ScanResult, diff: Option<&DiffResult>) -> BriefResul
         .iter()
         .filter(|i| i.priority != Priority::Normal)
         .max_by(|a, b| {
-            a.priority
-                .cmp(&b.priority)
-                .then_with(|| a.tag.severity().cmp(&b.tag.severity()))
+            a.priority.cmp(&b.priority).then_with(|| {
+                tag_styles
+                    .severity(&a.tag)
+                    .cmp(&tag_styles.severity(&b.tag))
+            })
         })
         .cloned();
 
@@ -69,10 +77,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         assert_eq!(result.total_items, 3);
         assert_eq!(result.total_files, 2);
         assert_eq!(result.priority_counts.normal, 1);
@@ -93,10 +102,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 3,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         let top = result.top_urgent.expect("should have a top urgent item");
         assert_eq!(top.file, "b.rs");
         assert_eq!(top.line, 5);
@@ -114,10 +124,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         assert!(result.top_urgent.is_none());
     }
 
@@ -126,6 +137,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let diff = DiffResult {
@@ -133,9 +145,13 @@ mod tests {
             added_count: 5,
             removed_count: 2,
             base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
         };
 
-        let result = compute_brief(&scan, Some(&diff));
+        let result = compute_brief(&scan, Some(&diff), &TagStyles::default());
         let trend = result.trend.expect("should have trend info");
         assert_eq!(trend.added, 5);
         assert_eq!(trend.removed, 2);
@@ -155,10 +171,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         let top = result.top_urgent.expect("should have top urgent");
         assert_eq!(top.tag, Tag::Bug);
     }
@@ -175,10 +192,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         let top = result.top_urgent.expect("should have top urgent");
         assert_eq!(top.priority, Priority::Urgent);
     }
@@ -194,10 +212,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         assert_eq!(result.total_items, 3);
         assert_eq!(result.total_files, 1);
     }
@@ -216,10 +235,11 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 3,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         assert_eq!(result.priority_counts.normal, 2);
         assert_eq!(result.priority_counts.high, 1);
         assert_eq!(result.priority_counts.urgent, 1);
@@ -231,10 +251,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_brief(&scan, None);
+        let result = compute_brief(&scan, None, &TagStyles::default());
         assert_eq!(result.total_items, 0);
         assert_eq!(result.total_files, 0);
         assert_eq!(result.priority_counts.normal, 0);
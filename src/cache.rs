@@ -53,16 +53,34 @@ impl ScanCache {
         bincode::deserialize(data).ok()
     }
 
-    /// Load cache from disk. Returns None if missing, oversized, or corrupt.
-    pub fn load(repo_root: &Path) -> Option<Self> {
-        let path = cache_path(repo_root)?;
+    /// Load cache from disk, from `cache_dir` or else the OS cache
+    /// directory. Returns None if missing, oversized, or corrupt.
+    pub fn load(repo_root: &Path, cache_dir: Option<&Path>) -> Option<Self> {
+        let path = cache_path(repo_root, cache_dir)?;
         let data = fs::read(&path).ok()?;
         Self::deserialize_with_limit(&data, MAX_CACHE_SIZE)
     }
 
+    /// Load the cache for `repo_root` if it was built with the same
+    /// scan-affecting config, otherwise start from an empty cache. A newly
+    /// configured `[cache] path` starts from the default location's cache.
+    pub fn load_for(repo_root: &Path, config: &Config) -> Self {
+        let config_hash = Self::config_hash(config);
+        let cache_dir = config.cache_dir(repo_root);
+        Self::load(repo_root, cache_dir.as_deref())
+            .or_else(|| cache_dir.and_then(|_| Self::load(repo_root, None)))
+            .filter(|c| c.config_hash == config_hash)
+            .unwrap_or_else(|| Self::new(config_hash))
+    }
+
+    /// Remove the entry for a single file, e.g. after it was deleted.
+    pub fn remove(&mut self, path: &Path) {
+        self.entries.remove(path);
+    }
+
     /// Save cache to disk with atomic write (write tmp, then rename).
-    pub fn save(&self, repo_root: &Path) -> Result<()> {
-        let path = match cache_path(repo_root) {
+    pub fn save(&self, repo_root: &Path, cache_dir: Option<&Path>) -> Result<()> {
+        let path = match cache_path(repo_root, cache_dir) {
             Some(p) => p,
             None => anyhow::bail!("cannot determine cache directory"),
         };
@@ -81,7 +99,7 @@ impl ScanCache {
     /// Compute a deterministic hash of the config fields that affect scanning.
     pub fn config_hash(config: &Config) -> [u8; 32] {
         let mut hasher = blake3::Hasher::new();
-        for tag in &config.tags {
+        for tag in config.all_tags() {
             hasher.update(tag.as_bytes());
             hasher.update(b"\0");
         }
@@ -95,6 +113,56 @@ impl ScanCache {
             hasher.update(pat.as_bytes());
             hasher.update(b"\0");
         }
+        hasher.update(b"\x01");
+        hasher.update(&[config.multiline() as u8]);
+        hasher.update(b"\x01");
+        for keyword in &config.priority.high {
+            hasher.update(keyword.as_bytes());
+            hasher.update(b"\0");
+        }
+        hasher.update(b"\x01");
+        for keyword in &config.priority.urgent {
+            hasher.update(keyword.as_bytes());
+            hasher.update(b"\0");
+        }
+        hasher.update(b"\x01");
+        for pattern in &config.issue.patterns {
+            hasher.update(pattern.as_bytes());
+            hasher.update(b"\0");
+        }
+        hasher.update(b"\x01");
+        hasher.update(&config.max_file_size().to_le_bytes());
+        hasher.update(&[config.respect_gitignore() as u8]);
+        hasher.update(&[config.at_tags() as u8]);
+        hasher.update(&[config.escalation() as u8]);
+        hasher.update(&[config.mentions() as u8]);
+        hasher.update(&[config.utf16_columns() as u8]);
+        hasher.update(b"\x01");
+        // Unset and empty prefix lists scan differently
+        for prefixes in [
+            &config.scan.comment_prefixes,
+            &config.scan.line_start_prefixes,
+        ] {
+            hasher.update(&[prefixes.is_some() as u8]);
+            for prefix in prefixes.iter().flatten() {
+                hasher.update(prefix.as_bytes());
+                hasher.update(b"\0");
+            }
+            hasher.update(b"\x01");
+        }
+        for (canonical, aliases) in &config.authors.aliases {
+            hasher.update(canonical.as_bytes());
+            hasher.update(b"=");
+            for alias in aliases {
+                hasher.update(alias.as_bytes());
+                hasher.update(b"\0");
+            }
+            hasher.update(b"\x02");
+        }
+        hasher.update(b"\x01");
+        hasher.update(crate::scanner::IGNORE_FILENAME.as_bytes());
+        hasher.update(b"\x01");
+        hasher.update(&crate::scanner::SCAN_RULES_VERSION.to_le_bytes());
         *hasher.finalize().as_bytes()
     }
 
@@ -165,17 +233,17 @@ fn system_time_to_parts(time: SystemTime) -> (u64, u32) {
 }
 
 /// Compute the cache file path for a given repo root.
-/// Returns `~/.cache/todo-scan/<repo-hash>/scan-cache.bin` (or platform equivalent).
-fn cache_path(repo_root: &Path) -> Option<PathBuf> {
-    let cache_dir = dirs::cache_dir()?;
+/// Returns `<cache_dir>/<repo-hash>/scan-cache.bin`, where `cache_dir`
+/// defaults to `~/.cache/todo-scan` (or platform equivalent). The repo hash
+/// keeps projects sharing a cache directory apart.
+fn cache_path(repo_root: &Path, cache_dir: Option<&Path>) -> Option<PathBuf> {
+    let cache_dir = match cache_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => dirs::cache_dir()?.join("todo-scan"),
+    };
     let repo_hash = blake3::hash(repo_root.to_string_lossy().as_bytes());
     let hex = format!("{}", repo_hash.to_hex());
-    Some(
-        cache_dir
-            .join("todo-scan")
-            .join(&hex[..16])
-            .join("scan-cache.bin"),
-    )
+    Some(cache_dir.join(&hex[..16]).join("scan-cache.bin"))
 }
 
 #[cfg(test)]
@@ -189,6 +257,7 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line: 1,
+            column: 1,
             tag: Tag::Todo,
             message: msg.to_string(),
             author: Some("alice".to_string()),
@@ -199,6 +268,10 @@ mod tests {
                 month: 6,
                 day: 1,
             }),
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
@@ -243,6 +316,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_hash_changes_with_issue_patterns() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.issue.patterns.push(r"GH-\d+".to_string());
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_max_file_size() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.scan.max_file_size = Some("1KiB".to_string());
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_respect_gitignore() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.scan.respect_gitignore = Some(false);
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_at_tags() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.scan.at_tags = Some(true);
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_escalation() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.scan.escalation = Some(true);
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_comment_prefixes() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.scan.comment_prefixes = Some(vec!["REM ".to_string()]);
+        let mut config3 = Config::default();
+        config3.scan.line_start_prefixes = Some(vec![]);
+        let hashes = [&config1, &config2, &config3].map(ScanCache::config_hash);
+        assert_ne!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+        assert_ne!(hashes[1], hashes[2]);
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_utf16_columns() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.scan.utf16_columns = Some(true);
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_mentions() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2.scan.mentions = Some(true);
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
+    #[test]
+    fn test_config_hash_changes_with_author_aliases() {
+        let config1 = Config::default();
+        let mut config2 = Config::default();
+        config2
+            .authors
+            .aliases
+            .insert("Alice".to_string(), vec!["alice@corp.com".to_string()]);
+        assert_ne!(
+            ScanCache::config_hash(&config1),
+            ScanCache::config_hash(&config2)
+        );
+    }
+
     #[test]
     fn test_save_load_roundtrip() {
         let dir = tempfile::tempdir().unwrap();
@@ -262,8 +439,8 @@ mod tests {
             mtime,
         );
 
-        cache.save(repo_root).unwrap();
-        let loaded = ScanCache::load(repo_root).unwrap();
+        cache.save(repo_root, None).unwrap();
+        let loaded = ScanCache::load(repo_root, None).unwrap();
 
         assert_eq!(loaded.config_hash, config_hash);
         assert_eq!(loaded.entries.len(), 1);
@@ -291,8 +468,8 @@ mod tests {
             mtime,
         );
 
-        cache.save(repo_root).unwrap();
-        let loaded = ScanCache::load(repo_root).unwrap();
+        cache.save(repo_root, None).unwrap();
+        let loaded = ScanCache::load(repo_root, None).unwrap();
 
         let entry = loaded.entries.get(Path::new("src/lib.rs")).unwrap();
         assert_eq!(entry.items[0].author.as_deref(), Some("alice"));
@@ -303,10 +480,31 @@ mod tests {
         assert_eq!(d.day, 1);
     }
 
+    #[test]
+    fn test_save_load_custom_cache_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = ScanCache::new(ScanCache::config_hash(&Config::default()));
+
+        cache.save(dir.path(), Some(cache_dir.path())).unwrap();
+        let path = cache_path(dir.path(), Some(cache_dir.path())).unwrap();
+        assert!(path.starts_with(cache_dir.path()));
+        assert!(path.is_file());
+        assert!(ScanCache::load(dir.path(), Some(cache_dir.path())).is_some());
+
+        // Another project sharing the directory gets its own file
+        let other = tempfile::tempdir().unwrap();
+        assert_ne!(
+            cache_path(other.path(), Some(cache_dir.path())).unwrap(),
+            path
+        );
+        assert!(ScanCache::load(other.path(), Some(cache_dir.path())).is_none());
+    }
+
     #[test]
     fn test_load_missing_file_returns_none() {
         let dir = tempfile::tempdir().unwrap();
-        assert!(ScanCache::load(dir.path()).is_none());
+        assert!(ScanCache::load(dir.path(), None).is_none());
     }
 
     #[test]
@@ -354,13 +552,13 @@ mod tests {
         // Save a valid cache first to find the path
         let config_hash = ScanCache::config_hash(&Config::default());
         let cache = ScanCache::new(config_hash);
-        cache.save(repo_root).unwrap();
+        cache.save(repo_root, None).unwrap();
 
         // Now corrupt it
-        let path = cache_path(repo_root).unwrap();
+        let path = cache_path(repo_root, None).unwrap();
         fs::write(&path, b"not valid bincode data").unwrap();
 
-        assert!(ScanCache::load(repo_root).is_none());
+        assert!(ScanCache::load(repo_root, None).is_none());
     }
 
     #[test]
@@ -1,24 +1,41 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
+use globset::{Glob, GlobMatcher};
+
+use crate::blame::parse_duration_days;
 use crate::config::Config;
 use crate::deadline::Deadline;
 use crate::model::*;
 
+#[derive(Debug, Default)]
 pub struct CheckOverrides {
     pub max: Option<usize>,
     pub block_tags: Vec<String>,
     pub max_new: Option<usize>,
     pub expired: bool,
+    /// Days a TODO may live per git blame; needs the `blame` passed to `run_check`
+    pub max_age: Option<u64>,
+    /// Days ahead to warn about deadlines; overrides `[check] due_soon`
+    pub due_soon: Option<u64>,
+    pub due_soon_fails: bool,
+    pub require_deadline: Vec<String>,
+    pub block_priority: Option<Priority>,
 }
 
+/// Evaluate the check rules against a scan. `diff` feeds `max_new` and
+/// `blame` feeds `max_age`; each rule is skipped when its input is missing.
 pub fn run_check(
     scan: &ScanResult,
     diff: Option<&DiffResult>,
+    blame: Option<&BlameResult>,
     config: &Config,
     overrides: &CheckOverrides,
     today: &Deadline,
 ) -> CheckResult {
     let mut violations: Vec<CheckViolation> = Vec::new();
+    // Rules configured for this run, whether or not they found anything
+    let mut rules: Vec<String> = Vec::new();
+    let mut evaluate = |rule: &str| rules.push(rule.to_string());
 
     // Step 1: block_tags check
     let blocked: HashSet<String> = overrides
@@ -27,6 +44,9 @@ pub fn run_check(
         .chain(config.check.block_tags.iter())
         .map(|t| t.to_uppercase())
         .collect();
+    if !blocked.is_empty() {
+        evaluate("block_tags");
+    }
 
     for item in &scan.items {
         let item_tag = item.tag.as_str().to_uppercase();
@@ -44,6 +64,7 @@ pub fn run_check(
     // Step 2: max total check
     let max = overrides.max.or(config.check.max);
     if let Some(max) = max {
+        evaluate("max");
         let total = scan.items.len();
         if total > max {
             violations.push(CheckViolation {
@@ -57,6 +78,7 @@ pub fn run_check(
     let max_new = overrides.max_new.or(config.check.max_new);
     if let Some(max_new) = max_new {
         if let Some(diff) = diff {
+            evaluate("max_new");
             if diff.added_count > max_new {
                 violations.push(CheckViolation {
                     rule: "max_new".to_string(),
@@ -72,6 +94,7 @@ pub fn run_check(
     // Step 4: expired deadline check
     let check_expired = overrides.expired || config.check.expired.unwrap_or(false);
     if check_expired {
+        evaluate("expired");
         for item in &scan.items {
             if let Some(ref deadline) = item.deadline {
                 if deadline.is_expired(today) {
@@ -87,6 +110,76 @@ pub fn run_check(
         }
     }
 
+    // Step 5: per-file limits
+    if !config.check.file_limits.is_empty() || config.check.file_max.is_some() {
+        evaluate("file_max");
+    }
+    violations.extend(check_file_limits(&scan.items, config));
+
+    // Step 6: tags that must carry a deadline
+    let require_deadline: HashSet<String> = overrides
+        .require_deadline
+        .iter()
+        .chain(config.check.require_deadline.iter())
+        .map(|t| t.to_uppercase())
+        .collect();
+    if !require_deadline.is_empty() {
+        evaluate("require_deadline");
+    }
+    for item in &scan.items {
+        let has_deadline = item.deadline.is_some() || item.relative_deadline.is_some();
+        if !has_deadline && require_deadline.contains(&item.tag.as_str().to_uppercase()) {
+            violations.push(CheckViolation {
+                rule: "require_deadline".to_string(),
+                message: format!(
+                    "{} in {}:{} has no deadline (use {}(author, YYYY-MM-DD): ...)",
+                    item.tag, item.file, item.line, item.tag
+                ),
+            });
+        }
+    }
+
+    // Step 7: items at or above the blocked priority
+    if let Some(min) = overrides.block_priority.or(config.check.block_priority) {
+        evaluate("block_priority");
+        for item in scan.items.iter().filter(|item| item.priority >= min) {
+            violations.push(CheckViolation {
+                rule: "block_priority".to_string(),
+                message: format!(
+                    "Blocked {} priority {} found in {}:{}",
+                    priority_label(item.priority),
+                    item.tag,
+                    item.file,
+                    item.line
+                ),
+            });
+        }
+    }
+
+    // Step 8: TODOs older than max_age
+    if let (Some(max_age_days), Some(blame)) = (overrides.max_age, blame) {
+        evaluate("max_age");
+        violations.extend(check_max_age(blame, max_age_days));
+    }
+
+    // Step 9: deadlines within the due_soon window warn, or fail when
+    // due_soon_fails is set. An invalid config value is rejected by
+    // `Config::parse`.
+    let mut warnings = Vec::new();
+    let due_soon = overrides.due_soon.or_else(|| {
+        let window = config.check.due_soon.as_deref()?;
+        parse_duration_days(window).ok()
+    });
+    if let Some(lead_days) = due_soon {
+        evaluate("due_soon");
+        let due = check_due_soon(&scan.items, today, lead_days);
+        if overrides.due_soon_fails || config.check.due_soon_fails.unwrap_or(false) {
+            violations.extend(due);
+        } else {
+            warnings.extend(due);
+        }
+    }
+
     let passed = violations.is_empty();
     let total = scan.items.len();
 
@@ -94,9 +187,120 @@ pub fn run_check(
         passed,
         total,
         violations,
+        warnings,
+        rules,
     }
 }
 
+fn priority_label(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Normal => "normal",
+        Priority::High => "high",
+        Priority::Urgent => "urgent",
+    }
+}
+
+/// Flag every file holding more TODOs than its limit: the most specific
+/// matching `[[check.file_limits]]` glob, else `file_max`.
+fn check_file_limits(items: &[TodoItem], config: &Config) -> Vec<CheckViolation> {
+    let limits: Vec<(GlobMatcher, usize, usize)> = config
+        .check
+        .file_limits
+        .iter()
+        .filter_map(|limit| {
+            let matcher = Glob::new(&limit.path).ok()?.compile_matcher();
+            Some((matcher, glob_specificity(&limit.path), limit.max))
+        })
+        .collect();
+    if limits.is_empty() && config.check.file_max.is_none() {
+        return Vec::new();
+    }
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for item in items {
+        *counts.entry(item.file.as_str()).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter_map(|(file, count)| {
+            // max_by_key returns the last maximum; reverse so the first
+            // declared glob wins ties
+            let max = limits
+                .iter()
+                .rev()
+                .filter(|(matcher, _, _)| matcher.is_match(file))
+                .max_by_key(|(_, specificity, _)| *specificity)
+                .map(|(_, _, max)| *max)
+                .or(config.check.file_max)?;
+            (count > max).then(|| CheckViolation {
+                rule: "file_max".to_string(),
+                message: format!("{} has {} TODOs (max: {})", file, count, max),
+            })
+        })
+        .collect()
+}
+
+/// Number of literal (non-wildcard) characters in a glob; more means more
+/// specific.
+fn glob_specificity(pattern: &str) -> usize {
+    pattern
+        .chars()
+        .filter(|c| !matches!(c, '*' | '?' | '[' | ']' | '{' | '}'))
+        .count()
+}
+
+/// Flag every TODO whose deadline falls within `lead_days` of `today`.
+///
+/// Already-expired deadlines are left to the `expired` rule.
+pub fn check_due_soon(items: &[TodoItem], today: &Deadline, lead_days: u64) -> Vec<CheckViolation> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let deadline = item.deadline.as_ref()?;
+            let remaining = deadline.days_until(today);
+            if remaining < 0 || remaining > lead_days as i64 {
+                return None;
+            }
+            let when = match remaining {
+                0 => "today".to_string(),
+                1 => "in 1 day".to_string(),
+                n => format!("in {} days", n),
+            };
+            Some(CheckViolation {
+                rule: "due_soon".to_string(),
+                message: format!(
+                    "Deadline {} in {}:{} is due {}",
+                    deadline, item.file, item.line, when
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Flag every blamed TODO older than `max_age_days`.
+///
+/// Items missing from `blame` (files not tracked by git) are treated as new.
+pub fn check_max_age(blame: &BlameResult, max_age_days: u64) -> Vec<CheckViolation> {
+    blame
+        .entries
+        .iter()
+        .filter(|e| e.blame.age_days > max_age_days)
+        .map(|e| CheckViolation {
+            rule: "max_age".to_string(),
+            message: format!(
+                "{} in {}:{} by {} is {} days old (max age: {} days)",
+                e.item.tag,
+                e.item.file,
+                e.item.line,
+                e.blame.author,
+                e.blame.age_days,
+                max_age_days
+            ),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,6 +313,11 @@ mod tests {
             block_tags: vec![],
             max_new: None,
             expired: false,
+            max_age: None,
+            due_soon: None,
+            due_soon_fails: false,
+            require_deadline: vec![],
+            block_priority: None,
         }
     }
 
@@ -125,6 +334,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "do something")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -133,7 +343,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
         assert!(result.violations.is_empty());
         assert_eq!(result.total, 1);
@@ -147,6 +357,7 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -155,7 +366,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations.len(), 1);
         assert_eq!(result.violations[0].rule, "max");
@@ -171,6 +382,7 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "normal todo"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -179,7 +391,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations.len(), 1);
         assert_eq!(result.violations[0].rule, "block_tags");
@@ -192,6 +404,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "new todo")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let diff = DiffResult {
@@ -202,6 +415,10 @@ mod tests {
             added_count: 5,
             removed_count: 0,
             base_ref: "HEAD~1".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -209,7 +426,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, Some(&diff), &config, &overrides, &test_today());
+        let result = run_check(&scan, Some(&diff), None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations.len(), 1);
         assert_eq!(result.violations[0].rule, "max_new");
@@ -225,12 +442,13 @@ mod tests {
                 make_item("b.rs", 2, Tag::Note, "just a note"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
         let overrides = default_overrides();
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
         assert!(result.violations.is_empty());
         assert_eq!(result.total, 2);
@@ -247,6 +465,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -255,13 +474,168 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations.len(), 1);
         assert_eq!(result.violations[0].rule, "expired");
         assert!(result.violations[0].message.contains("2025-01-01"));
     }
 
+    #[test]
+    fn test_block_priority_flags_items_at_or_above() {
+        let mut high = make_item("a.rs", 1, Tag::Todo, "soon");
+        high.priority = Priority::High;
+        let mut urgent = make_item("b.rs", 3, Tag::Fixme, "now");
+        urgent.priority = Priority::Urgent;
+        let scan = ScanResult {
+            items: vec![high, urgent, make_item("c.rs", 5, Tag::Todo, "later")],
+            files_scanned: 3,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+
+        let urgent_only = CheckOverrides {
+            block_priority: Some(Priority::Urgent),
+            ..default_overrides()
+        };
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &Config::default(),
+            &urgent_only,
+            &test_today(),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule, "block_priority");
+        assert_eq!(
+            result.violations[0].message,
+            "Blocked urgent priority FIXME found in b.rs:3"
+        );
+
+        let high_and_up = CheckOverrides {
+            block_priority: Some(Priority::High),
+            ..default_overrides()
+        };
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &Config::default(),
+            &high_and_up,
+            &test_today(),
+        );
+        assert_eq!(result.violations.len(), 2);
+    }
+
+    #[test]
+    fn test_block_priority_passes_without_matching_items() {
+        let scan = ScanResult {
+            items: vec![make_item("a.rs", 1, Tag::Todo, "later")],
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let mut config = Config::default();
+        config.check.block_priority = Some(Priority::Urgent);
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_block_priority_override_wins_over_config() {
+        let mut high = make_item("a.rs", 1, Tag::Todo, "soon");
+        high.priority = Priority::High;
+        let scan = ScanResult {
+            items: vec![high],
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let mut config = Config::default();
+        config.check.block_priority = Some(Priority::High);
+        let overrides = CheckOverrides {
+            block_priority: Some(Priority::Urgent),
+            ..default_overrides()
+        };
+
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_require_deadline_flags_tags_without_deadline() {
+        let mut with_deadline = make_item("a.rs", 1, Tag::Fixme, "has a date");
+        with_deadline.deadline = Some(Deadline {
+            year: 2026,
+            month: 6,
+            day: 1,
+        });
+        let scan = ScanResult {
+            items: vec![
+                with_deadline,
+                make_item("b.rs", 7, Tag::Fixme, "no date"),
+                make_item("c.rs", 2, Tag::Todo, "not required"),
+            ],
+            files_scanned: 3,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let overrides = CheckOverrides {
+            require_deadline: vec!["fixme".to_string()],
+            ..default_overrides()
+        };
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &Config::default(),
+            &overrides,
+            &test_today(),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule, "require_deadline");
+        assert_eq!(
+            result.violations[0].message,
+            "FIXME in b.rs:7 has no deadline (use FIXME(author, YYYY-MM-DD): ...)"
+        );
+    }
+
+    #[test]
+    fn test_require_deadline_from_config_composes_with_block_tags() {
+        let mut relative = make_item("a.rs", 1, Tag::Bug, "relative date");
+        relative.relative_deadline = Some(crate::deadline::RelativeDeadline::Days(14));
+        let scan = ScanResult {
+            items: vec![relative, make_item("b.rs", 3, Tag::Hack, "hack")],
+            files_scanned: 2,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let mut config = Config::default();
+        config.check.require_deadline = vec!["BUG".to_string(), "HACK".to_string()];
+        let overrides = CheckOverrides {
+            block_tags: vec!["HACK".to_string()],
+            ..default_overrides()
+        };
+
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
+        let rules: Vec<&str> = result.violations.iter().map(|v| v.rule.as_str()).collect();
+        // A relative deadline satisfies the rule
+        assert_eq!(rules, vec!["block_tags", "require_deadline"]);
+        assert!(result.violations[1].message.contains("b.rs:3"));
+    }
+
     #[test]
     fn test_future_deadline_passes() {
         let mut item = make_item("a.rs", 1, Tag::Todo, "future task");
@@ -273,6 +647,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -281,7 +656,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
         assert!(result.violations.is_empty());
     }
@@ -297,12 +672,13 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
         let overrides = default_overrides(); // expired: false
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
         assert!(result.violations.is_empty());
     }
@@ -315,13 +691,14 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
         config.check.max = Some(5);
         let overrides = default_overrides(); // max: None
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations[0].rule, "max");
     }
@@ -334,6 +711,7 @@ mod tests {
                 make_item("b.rs", 2, Tag::Hack, "hack"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -343,7 +721,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(!result.passed);
         // Both BUG (from config) and HACK (from overrides) should be blocked
         assert_eq!(result.violations.len(), 2);
@@ -354,6 +732,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let diff = DiffResult {
@@ -361,12 +740,16 @@ mod tests {
             added_count: 5,
             removed_count: 0,
             base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
         };
         let mut config = Config::default();
         config.check.max_new = Some(2);
         let overrides = default_overrides();
 
-        let result = run_check(&scan, Some(&diff), &config, &overrides, &test_today());
+        let result = run_check(&scan, Some(&diff), None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations[0].rule, "max_new");
     }
@@ -376,6 +759,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -385,7 +769,7 @@ mod tests {
         };
 
         // No diff provided, so max_new check is skipped
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
     }
 
@@ -400,13 +784,14 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
         config.check.expired = Some(true);
         let overrides = default_overrides(); // expired: false
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations[0].rule, "expired");
     }
@@ -428,6 +813,7 @@ mod tests {
         let scan = ScanResult {
             items: all_items,
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let diff = DiffResult {
@@ -435,6 +821,10 @@ mod tests {
             added_count: 8,
             removed_count: 0,
             base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -442,9 +832,14 @@ mod tests {
             block_tags: vec!["BUG".to_string()],
             max_new: Some(3),
             expired: true,
+            max_age: None,
+            due_soon: None,
+            due_soon_fails: false,
+            require_deadline: vec![],
+            block_priority: None,
         };
 
-        let result = run_check(&scan, Some(&diff), &config, &overrides, &test_today());
+        let result = run_check(&scan, Some(&diff), None, &config, &overrides, &test_today());
         assert!(!result.passed);
         // Should have: block_tags (BUG), max (11 > 5), max_new (8 > 3), expired
         assert!(result.violations.len() >= 4);
@@ -460,6 +855,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Hack, "workaround")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -468,7 +864,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(!result.passed);
         assert_eq!(result.violations[0].rule, "block_tags");
     }
@@ -478,6 +874,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let diff = DiffResult {
@@ -485,6 +882,10 @@ mod tests {
             added_count: 2,
             removed_count: 0,
             base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
         };
         let config = Config::default();
         let overrides = CheckOverrides {
@@ -492,7 +893,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, Some(&diff), &config, &overrides, &test_today());
+        let result = run_check(&scan, Some(&diff), None, &config, &overrides, &test_today());
         assert!(result.passed);
     }
 
@@ -507,6 +908,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -515,7 +917,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
     }
 
@@ -524,6 +926,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no deadline")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -532,7 +935,7 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
     }
 
@@ -541,6 +944,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -550,8 +954,354 @@ mod tests {
             ..default_overrides()
         };
 
-        let result = run_check(&scan, None, &config, &overrides, &test_today());
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
         assert!(result.passed);
         assert_eq!(result.total, 0);
     }
+
+    fn blame_entry(file: &str, line: usize, author: &str, age_days: u64) -> BlameEntry {
+        BlameEntry {
+            item: make_item(file, line, Tag::Todo, "old work"),
+            blame: BlameInfo {
+                author: author.to_string(),
+                email: String::new(),
+                date: "2024-01-01".to_string(),
+                age_days,
+                commit: "abc12345".to_string(),
+            },
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn test_max_age_flags_only_older_items() {
+        let blame = crate::blame::summarize_blame(
+            vec![
+                blame_entry("old.rs", 3, "Alice", 400),
+                blame_entry("edge.rs", 1, "Bob", 180),
+                blame_entry("new.rs", 7, "Carol", 10),
+            ],
+            180,
+        );
+
+        let violations = check_max_age(&blame, 180);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, "max_age");
+        assert_eq!(
+            violations[0].message,
+            "TODO in old.rs:3 by Alice is 400 days old (max age: 180 days)"
+        );
+    }
+
+    fn make_scan(items: Vec<TodoItem>) -> ScanResult {
+        ScanResult {
+            files_scanned: items.len(),
+            items,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        }
+    }
+
+    #[test]
+    fn test_run_check_applies_max_age_with_blame() {
+        let blame = crate::blame::summarize_blame(
+            vec![
+                blame_entry("old.rs", 3, "Alice", 400),
+                blame_entry("new.rs", 7, "Carol", 10),
+            ],
+            180,
+        );
+        let scan = make_scan(vec![]);
+        let overrides = CheckOverrides {
+            max_age: Some(180),
+            ..default_overrides()
+        };
+        let config = Config::default();
+
+        let result = run_check(
+            &scan,
+            None,
+            Some(&blame),
+            &config,
+            &overrides,
+            &test_today(),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule, "max_age");
+
+        // Without blame the rule has nothing to evaluate
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_max_age_empty_blame_passes() {
+        let blame = crate::blame::summarize_blame(vec![], 30);
+        assert!(check_max_age(&blame, 30).is_empty());
+    }
+
+    fn item_due(file: &str, deadline: &str) -> TodoItem {
+        let mut item = make_item(file, 1, Tag::Todo, "task");
+        item.deadline = crate::deadline::parse_deadline(deadline);
+        item
+    }
+
+    #[test]
+    fn test_due_soon_flags_deadlines_within_window() {
+        let items = vec![
+            item_due("today.rs", "2025-06-15"),
+            item_due("tomorrow.rs", "2025-06-16"),
+            item_due("week.rs", "2025-06-22"),
+        ];
+
+        let violations = check_due_soon(&items, &test_today(), 7);
+        assert_eq!(violations.len(), 3);
+        assert!(violations.iter().all(|v| v.rule == "due_soon"));
+        assert_eq!(
+            violations[0].message,
+            "Deadline 2025-06-15 in today.rs:1 is due today"
+        );
+        assert!(violations[1].message.ends_with("is due in 1 day"));
+        assert!(violations[2].message.ends_with("is due in 7 days"));
+    }
+
+    #[test]
+    fn test_run_check_due_soon_warns_unless_it_fails() {
+        let scan = make_scan(vec![item_due("soon.rs", "2025-06-17")]);
+        let config = Config::default();
+        let overrides = CheckOverrides {
+            due_soon: Some(7),
+            ..default_overrides()
+        };
+        let result = run_check(&scan, None, None, &config, &overrides, &test_today());
+        assert!(result.passed);
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].rule, "due_soon");
+
+        let fails = CheckOverrides {
+            due_soon_fails: true,
+            ..overrides
+        };
+        let result = run_check(&scan, None, None, &config, &fails, &test_today());
+        assert!(!result.passed);
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.violations[0].rule, "due_soon");
+    }
+
+    #[test]
+    fn test_run_check_records_evaluated_rules() {
+        let scan = make_scan(vec![make_item("a.rs", 1, Tag::Todo, "task")]);
+        let overrides = CheckOverrides {
+            max: Some(5),
+            expired: true,
+            due_soon: Some(7),
+            ..default_overrides()
+        };
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &Config::default(),
+            &overrides,
+            &test_today(),
+        );
+        assert!(result.passed);
+        assert_eq!(result.rules, vec!["max", "expired", "due_soon"]);
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &Config::default(),
+            &default_overrides(),
+            &test_today(),
+        );
+        assert!(result.rules.is_empty());
+    }
+
+    #[test]
+    fn test_run_check_due_soon_from_config() {
+        let scan = make_scan(vec![item_due("soon.rs", "2025-06-17")]);
+        let mut config = Config::default();
+        config.check.due_soon = Some("3d".to_string());
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert_eq!(result.warnings.len(), 1);
+
+        config.check.due_soon = Some("1d".to_string());
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_due_soon_skips_expired_deadlines() {
+        let items = vec![item_due("old.rs", "2025-06-14")];
+        assert!(check_due_soon(&items, &test_today(), 7).is_empty());
+    }
+
+    #[test]
+    fn test_due_soon_skips_far_future_deadlines() {
+        let items = vec![
+            item_due("later.rs", "2025-06-23"),
+            item_due("next_year.rs", "2026-01-01"),
+            make_item("none.rs", 1, Tag::Todo, "no deadline"),
+        ];
+        assert!(check_due_soon(&items, &test_today(), 7).is_empty());
+    }
+
+    #[test]
+    fn test_due_soon_quarter_deadline_resolves_to_quarter_end() {
+        let items = vec![item_due("q.rs", "2025-Q2")];
+
+        let violations = check_due_soon(&items, &test_today(), 30);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0]
+            .message
+            .contains("Deadline 2025-06-30 in q.rs:1 is due in 15 days"));
+        assert!(check_due_soon(&items, &test_today(), 14).is_empty());
+    }
+
+    fn scan_with_counts(counts: &[(&str, usize)]) -> ScanResult {
+        let items = counts
+            .iter()
+            .flat_map(|(file, n)| (0..*n).map(move |i| make_item(file, i + 1, Tag::Todo, "task")))
+            .collect();
+        ScanResult {
+            items,
+            files_scanned: counts.len(),
+            scanned_files: vec![],
+            ignored_items: vec![],
+        }
+    }
+
+    fn limit(path: &str, max: usize) -> crate::config::FileLimit {
+        crate::config::FileLimit {
+            path: path.to_string(),
+            max,
+        }
+    }
+
+    #[test]
+    fn test_file_max_global_default() {
+        let scan = scan_with_counts(&[("a.rs", 4), ("b.rs", 2)]);
+        let mut config = Config::default();
+        config.check.file_max = Some(3);
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule, "file_max");
+        assert_eq!(result.violations[0].message, "a.rs has 4 TODOs (max: 3)");
+    }
+
+    #[test]
+    fn test_file_max_exactly_at_limit_passes() {
+        let scan = scan_with_counts(&[("a.rs", 3)]);
+        let mut config = Config::default();
+        config.check.file_max = Some(3);
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_file_limits_glob_override() {
+        let scan = scan_with_counts(&[("src/legacy/old.rs", 10), ("src/new.rs", 4)]);
+        let mut config = Config::default();
+        config.check.file_max = Some(3);
+        config.check.file_limits = vec![limit("src/legacy/**", 50)];
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(
+            result.violations[0].message,
+            "src/new.rs has 4 TODOs (max: 3)"
+        );
+    }
+
+    #[test]
+    fn test_file_limits_without_file_max_only_checks_matching_files() {
+        let scan = scan_with_counts(&[("src/legacy/old.rs", 3), ("src/new.rs", 100)]);
+        let mut config = Config::default();
+        config.check.file_limits = vec![limit("src/legacy/**", 2)];
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert_eq!(result.violations.len(), 1);
+        assert!(result.violations[0]
+            .message
+            .starts_with("src/legacy/old.rs has 3"));
+    }
+
+    #[test]
+    fn test_file_limits_most_specific_glob_wins() {
+        let scan = scan_with_counts(&[("src/legacy/parser.rs", 8), ("src/legacy/lexer.rs", 8)]);
+        let mut config = Config::default();
+        config.check.file_limits = vec![
+            limit("src/legacy/parser.rs", 5),
+            limit("src/**", 10),
+            limit("src/legacy/**", 20),
+        ];
+
+        let result = run_check(
+            &scan,
+            None,
+            None,
+            &config,
+            &default_overrides(),
+            &test_today(),
+        );
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(
+            result.violations[0].message,
+            "src/legacy/parser.rs has 8 TODOs (max: 5)"
+        );
+    }
+
+    #[test]
+    fn test_glob_specificity() {
+        assert!(glob_specificity("src/legacy/**") > glob_specificity("src/**"));
+        assert!(glob_specificity("src/a.rs") > glob_specificity("src/*.rs"));
+    }
 }
@@ -1,13 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::LazyLock;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use regex::Regex;
 
 use crate::blame::parse_duration_days;
 use crate::config::Config;
 use crate::date_utils;
 use crate::model::{CleanResult, CleanViolation, ScanResult, TodoItem};
+use crate::scanner;
 
 static ISO8601_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(\d{4})-(\d{2})-(\d{2})T(\d{2}):(\d{2}):(\d{2})").unwrap());
@@ -84,6 +86,75 @@ impl IssueChecker for GhIssueChecker {
     }
 }
 
+/// Checks GitLab issues through `glab api`. glab handles authentication,
+/// including a token from `GITLAB_TOKEN`.
+pub struct GlIssueChecker {
+    /// URL-encoded project path or ID, or glab's `:fullpath` placeholder for
+    /// the project of the current git remote.
+    project: String,
+    cache: std::cell::RefCell<HashMap<u32, Option<IssueState>>>,
+}
+
+impl GlIssueChecker {
+    pub fn new(project: Option<&str>) -> Option<Self> {
+        // Check if glab CLI is available
+        let output = std::process::Command::new("glab")
+            .arg("--version")
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(Self {
+            project: gitlab_project_id(project),
+            cache: std::cell::RefCell::new(HashMap::new()),
+        })
+    }
+}
+
+impl IssueChecker for GlIssueChecker {
+    fn check_issue(&self, issue_number: u32) -> Result<Option<IssueState>> {
+        if let Some(cached) = self.cache.borrow().get(&issue_number) {
+            return Ok(cached.clone());
+        }
+
+        let endpoint = format!("projects/{}/issues/{}", self.project, issue_number);
+        let output = std::process::Command::new("glab")
+            .args(["api", &endpoint])
+            .output();
+
+        let result = match output {
+            Ok(out) if out.status.success() => parse_gitlab_issue(&out.stdout),
+            // glab failed (auth issue, network, unknown issue) — skip this issue
+            _ => None,
+        };
+
+        self.cache.borrow_mut().insert(issue_number, result.clone());
+        Ok(result)
+    }
+}
+
+/// The project segment of a GitLab API path: numeric IDs are used as is and
+/// paths are URL-encoded.
+fn gitlab_project_id(project: Option<&str>) -> String {
+    match project.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(p) => p.replace('/', "%2F"),
+        None => ":fullpath".to_string(),
+    }
+}
+
+/// Parse a GitLab issue API response (`state` is "opened" or "closed").
+fn parse_gitlab_issue(body: &[u8]) -> Option<IssueState> {
+    let json: serde_json::Value = serde_json::from_slice(body).ok()?;
+    match json["state"].as_str()? {
+        "closed" => {
+            let closed_at = json["closed_at"].as_str().and_then(parse_iso8601_timestamp);
+            Some(IssueState::Closed { closed_at })
+        }
+        _ => Some(IssueState::Open),
+    }
+}
+
 /// Parse an ISO 8601 timestamp string into a Unix timestamp.
 fn parse_iso8601_timestamp(s: &str) -> Option<i64> {
     let caps = ISO8601_RE.captures(s)?;
@@ -101,7 +172,7 @@ fn parse_iso8601_timestamp(s: &str) -> Option<i64> {
 }
 
 /// Normalize a TODO message for duplicate comparison.
-fn normalize_message(msg: &str) -> String {
+pub(crate) fn normalize_message(msg: &str) -> String {
     msg.trim()
         .to_lowercase()
         .split_whitespace()
@@ -252,6 +323,212 @@ fn detect_duplicates(items: &[TodoItem], violations: &mut Vec<CleanViolation>) {
     }
 }
 
+/// Block comment delimiters. A removable line may end with a closer, but
+/// code after one means the line holds more than the TODO, and an opener
+/// left unclosed means the comment runs on past the line.
+const BLOCK_COMMENTS: &[(&str, &str)] =
+    &[("/*", "*/"), ("<!--", "-->"), ("{-", "-}"), ("(*", "*)")];
+
+/// Edits `apply_fixes` made, or would make, to one file.
+#[derive(Debug)]
+pub struct FileFix {
+    pub file: String,
+    /// 1-based numbers of the removed lines, ascending.
+    pub removed: Vec<usize>,
+    /// Unified diff of the change.
+    pub diff: String,
+}
+
+/// A stale TODO `apply_fixes` left in place, and why.
+#[derive(Debug)]
+pub struct SkippedFix {
+    pub file: String,
+    pub line: usize,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct FixOutcome {
+    pub files: Vec<FileFix>,
+    pub skipped: Vec<SkippedFix>,
+}
+
+impl FixOutcome {
+    pub fn removed_count(&self) -> usize {
+        self.files.iter().map(|f| f.removed.len()).sum()
+    }
+}
+
+/// Delete the lines of `stale_issue` violations from their files. Duplicates
+/// are left alone, since either copy may be the one worth keeping.
+///
+/// A line is only removed when it still holds the reported TODO and nothing
+/// but the comment, so code sharing the line is never lost. Continuation
+/// lines of the comment go with it, so no orphaned tail is left. Other lines,
+/// including their `\n` or `\r\n` endings, are written back byte for byte.
+/// File paths are resolved against `base_dir`. With `dry_run`, files are left
+/// untouched and the outcome only describes the change.
+pub fn apply_fixes(
+    violations: &[CleanViolation],
+    items: &[TodoItem],
+    base_dir: &Path,
+    dry_run: bool,
+) -> Result<FixOutcome> {
+    let mut by_file: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for v in violations.iter().filter(|v| v.rule == "stale_issue") {
+        by_file.entry(v.file.as_str()).or_default().push(v.line);
+    }
+
+    let mut outcome = FixOutcome::default();
+    for (file, mut lines) in by_file {
+        lines.sort_unstable();
+        lines.dedup();
+        let path = base_dir.join(file);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        let old: Vec<&str> = content.split_inclusive('\n').collect();
+
+        let mut removed = Vec::new();
+        for line in lines {
+            let item = items.iter().find(|i| i.file == file && i.line == line);
+            match removable(old.get(line - 1).copied(), item) {
+                Ok(tag_pos) => {
+                    removed.push(line);
+                    removed.extend(continuation_lines(&old, line, tag_pos, file, items));
+                }
+                Err(reason) => outcome.skipped.push(SkippedFix {
+                    file: file.to_string(),
+                    line,
+                    reason: reason.to_string(),
+                }),
+            }
+        }
+        if removed.is_empty() {
+            continue;
+        }
+        removed.sort_unstable();
+        removed.dedup();
+
+        if !dry_run {
+            let kept: String = old
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| removed.binary_search(&(i + 1)).is_err())
+                .map(|(_, l)| *l)
+                .collect();
+            std::fs::write(&path, kept)
+                .with_context(|| format!("cannot write {}", path.display()))?;
+        }
+        outcome.files.push(FileFix {
+            file: file.to_string(),
+            diff: unified_diff(file, &old, &removed),
+            removed,
+        });
+    }
+    Ok(outcome)
+}
+
+/// Whether `line` can be deleted as the comment of `item`; on success, the
+/// byte offset of the tag.
+fn removable(
+    line: Option<&str>,
+    item: Option<&TodoItem>,
+) -> std::result::Result<usize, &'static str> {
+    let (Some(line), Some(item)) = (line, item) else {
+        return Err("the line no longer holds the TODO");
+    };
+    let upper = line.to_ascii_uppercase();
+    let Some(pos) = upper.find(item.tag.as_str()) else {
+        return Err("the line no longer holds the TODO");
+    };
+    // Only comment punctuation may precede the tag
+    if line[..pos].chars().any(|c| c.is_alphanumeric()) {
+        return Err("code shares the line");
+    }
+    let closed_early = BLOCK_COMMENTS.iter().any(|(_, closer)| {
+        line.find(closer)
+            .is_some_and(|at| !line[at + closer.len()..].trim().is_empty())
+    });
+    if closed_early {
+        return Err("code shares the line");
+    }
+    let left_open = BLOCK_COMMENTS
+        .iter()
+        .any(|(opener, closer)| line[..pos].contains(opener) && !line[pos..].contains(closer));
+    if left_open {
+        return Err("the block comment continues past the line");
+    }
+    Ok(pos)
+}
+
+/// 1-based numbers of the lines after `line` that continue its comment: the
+/// same line-comment prefix with the text indented past the tag, up to the
+/// next scanned TODO.
+fn continuation_lines(
+    old: &[&str],
+    line: usize,
+    tag_pos: usize,
+    file: &str,
+    items: &[TodoItem],
+) -> Vec<usize> {
+    let text = old[line - 1];
+    let prefix = text[..tag_pos].trim();
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let prefix_end = text[..tag_pos].trim_end().len();
+    let tag_indent = tag_pos - prefix_end;
+    (line + 1..=old.len())
+        .take_while(|&next| {
+            !items.iter().any(|i| i.file == file && i.line == next)
+                && scanner::continuation_text(old[next - 1], prefix, tag_indent).is_some()
+        })
+        .collect()
+}
+
+/// A unified diff removing `removed` (1-based, ascending) from `old`, with
+/// three lines of context.
+fn unified_diff(file: &str, old: &[&str], removed: &[usize]) -> String {
+    const CONTEXT: usize = 3;
+    let mut out = format!("--- a/{file}\n+++ b/{file}\n");
+    // Group removals whose context overlaps into one hunk
+    let mut hunks: Vec<Vec<usize>> = Vec::new();
+    for &line in removed {
+        match hunks.last_mut() {
+            Some(hunk) if line - hunk[hunk.len() - 1] <= 2 * CONTEXT + 1 => hunk.push(line),
+            _ => hunks.push(vec![line]),
+        }
+    }
+
+    // Lines removed by earlier hunks shift the new file's numbering
+    let mut shift = 0;
+    for hunk in hunks {
+        let start = hunk[0].saturating_sub(CONTEXT).max(1);
+        let end = (hunk[hunk.len() - 1] + CONTEXT).min(old.len());
+        let old_len = end - start + 1;
+        let new_len = old_len - hunk.len();
+        // An empty range names the line before it
+        let new_start = if new_len == 0 { start - 1 } else { start };
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            start,
+            old_len,
+            new_start - shift,
+            new_len
+        ));
+        for (i, text) in old.iter().enumerate().take(end).skip(start - 1) {
+            let marker = if hunk.contains(&(i + 1)) { '-' } else { ' ' };
+            let text = text.trim_end_matches('\n').trim_end_matches('\r');
+            out.push_str(&format!("{marker}{text}\n"));
+        }
+        if end == old.len() && !old[end - 1].ends_with('\n') {
+            out.push_str("\\ No newline at end of file\n");
+        }
+        shift += hunk.len();
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,12 +564,17 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            column: 1,
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: Some(issue_ref.to_string()),
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
@@ -313,6 +595,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker =
@@ -335,6 +618,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![(42, Some(IssueState::Open))]);
@@ -362,6 +646,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![(
@@ -396,6 +681,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![(
@@ -419,6 +705,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![]);
@@ -438,6 +725,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -455,6 +743,7 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -471,6 +760,7 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -486,6 +776,7 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -501,6 +792,7 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, "implement feature B"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -516,6 +808,7 @@ mod tests {
                 make_item("b.rs", 5, Tag::Todo, ""),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -528,6 +821,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "unique message")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -545,6 +839,42 @@ mod tests {
         assert_eq!(normalize_message("a\tb"), "a b");
     }
 
+    // --- GitLab ---
+
+    #[test]
+    fn test_gitlab_project_id() {
+        assert_eq!(gitlab_project_id(None), ":fullpath");
+        assert_eq!(gitlab_project_id(Some("  ")), ":fullpath");
+        assert_eq!(
+            gitlab_project_id(Some("group/sub/repo")),
+            "group%2Fsub%2Frepo"
+        );
+        assert_eq!(gitlab_project_id(Some("4242")), "4242");
+    }
+
+    #[test]
+    fn test_parse_gitlab_issue_closed() {
+        let body = br#"{"iid": 7, "state": "closed", "closed_at": "2024-01-01T00:00:00.000Z"}"#;
+        assert_eq!(
+            parse_gitlab_issue(body),
+            Some(IssueState::Closed {
+                closed_at: Some(1704067200)
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_gitlab_issue_opened() {
+        let body = br#"{"iid": 7, "state": "opened", "closed_at": null}"#;
+        assert_eq!(parse_gitlab_issue(body), Some(IssueState::Open));
+    }
+
+    #[test]
+    fn test_parse_gitlab_issue_invalid_response() {
+        assert_eq!(parse_gitlab_issue(b"not json"), None);
+        assert_eq!(parse_gitlab_issue(br#"{"message": "404 Not found"}"#), None);
+    }
+
     #[test]
     fn test_extract_issue_number() {
         assert_eq!(extract_issue_number("#123"), Some(123));
@@ -566,6 +896,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item_with_issue("a.rs", 1, Tag::Todo, "fix #42", "#42")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker =
@@ -584,6 +915,7 @@ mod tests {
                 make_item("b.rs", 2, Tag::Todo, "same message"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = default_config();
@@ -625,6 +957,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker =
@@ -658,6 +991,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![(
@@ -694,6 +1028,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![(
@@ -721,6 +1056,7 @@ mod tests {
                 make_item_with_issue("b.rs", 10, Tag::Todo, "second ref to #42", "#42"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker =
@@ -753,6 +1089,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![(
@@ -779,6 +1116,7 @@ mod tests {
                 make_item("c.rs", 3, Tag::Todo, "same msg"),
             ],
             files_scanned: 3,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = run_clean(&scan, &default_config(), None, None);
@@ -847,6 +1185,7 @@ mod tests {
                 make_item_with_issue("a.rs", 5, Tag::Todo, "fix #3", "#3"),
             ],
             files_scanned: 3,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = MockIssueChecker::new(vec![
@@ -875,6 +1214,7 @@ mod tests {
                 make_item("c.rs", 3, Tag::Todo, "same message"),
             ],
             files_scanned: 3,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker =
@@ -907,6 +1247,7 @@ mod tests {
                 "#42",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let checker = ErrorIssueChecker;
@@ -916,4 +1257,134 @@ mod tests {
         assert!(result.passed);
         assert_eq!(result.stale_count, 0);
     }
+
+    // --- Fixes ---
+
+    fn stale(file: &str, line: usize) -> CleanViolation {
+        CleanViolation {
+            rule: "stale_issue".to_string(),
+            message: "Issue #42 is closed".to_string(),
+            file: file.to_string(),
+            line,
+            issue_ref: Some("#42".to_string()),
+            duplicate_of: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_fixes_removes_only_reported_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "fn a() {}\r\n// TODO: retry #42\r\nfn b() {}\r\n    # FIXME: old #42";
+        std::fs::write(dir.path().join("a.rs"), content).unwrap();
+        let items = vec![
+            make_item_with_issue("a.rs", 2, Tag::Todo, "retry #42", "#42"),
+            make_item_with_issue("a.rs", 4, Tag::Fixme, "old #42", "#42"),
+        ];
+        let violations = vec![stale("a.rs", 2), stale("a.rs", 4)];
+
+        let outcome = apply_fixes(&violations, &items, dir.path(), false).unwrap();
+        assert_eq!(outcome.removed_count(), 2);
+        assert!(outcome.skipped.is_empty());
+        let fixed = std::fs::read_to_string(dir.path().join("a.rs")).unwrap();
+        assert_eq!(fixed, "fn a() {}\r\nfn b() {}\r\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_keeps_lines_shared_with_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "let x = 1; // TODO: retry #42\n/* TODO: later #42 */ call();\n";
+        std::fs::write(dir.path().join("a.rs"), content).unwrap();
+        let items = vec![
+            make_item_with_issue("a.rs", 1, Tag::Todo, "retry #42", "#42"),
+            make_item_with_issue("a.rs", 2, Tag::Todo, "later #42", "#42"),
+        ];
+        let violations = vec![stale("a.rs", 1), stale("a.rs", 2)];
+
+        let outcome = apply_fixes(&violations, &items, dir.path(), false).unwrap();
+        assert!(outcome.files.is_empty());
+        assert_eq!(outcome.skipped.len(), 2);
+        assert_eq!(outcome.skipped[0].reason, "code shares the line");
+        let unchanged = std::fs::read_to_string(dir.path().join("a.rs")).unwrap();
+        assert_eq!(unchanged, content);
+    }
+
+    #[test]
+    fn test_apply_fixes_keeps_unclosed_block_comment_opener() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "int a;\n/* TODO(#12): drop this\n * once done\n */\nint b;\n";
+        std::fs::write(dir.path().join("a.c"), content).unwrap();
+        let items = vec![make_item_with_issue(
+            "a.c",
+            2,
+            Tag::Todo,
+            "drop this",
+            "#12",
+        )];
+
+        let outcome = apply_fixes(&[stale("a.c", 2)], &items, dir.path(), false).unwrap();
+        assert!(outcome.files.is_empty());
+        assert_eq!(
+            outcome.skipped[0].reason,
+            "the block comment continues past the line"
+        );
+        let unchanged = std::fs::read_to_string(dir.path().join("a.c")).unwrap();
+        assert_eq!(unchanged, content);
+    }
+
+    #[test]
+    fn test_apply_fixes_removes_continuation_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "fn a() {}\n// TODO(#12): drop this\n//   once done\n// TODO: keep\n//   its tail\nfn b() {}\n";
+        std::fs::write(dir.path().join("a.rs"), content).unwrap();
+        let items = vec![
+            make_item_with_issue("a.rs", 2, Tag::Todo, "drop this", "#12"),
+            make_item("a.rs", 4, Tag::Todo, "keep"),
+        ];
+
+        let outcome = apply_fixes(&[stale("a.rs", 2)], &items, dir.path(), false).unwrap();
+        assert_eq!(outcome.files[0].removed, vec![2, 3]);
+        let fixed = std::fs::read_to_string(dir.path().join("a.rs")).unwrap();
+        assert_eq!(
+            fixed,
+            "fn a() {}\n// TODO: keep\n//   its tail\nfn b() {}\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_fixes_dry_run_and_duplicates_leave_files_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "// TODO: retry #42\n// TODO: same\n";
+        std::fs::write(dir.path().join("a.rs"), content).unwrap();
+        let items = vec![
+            make_item_with_issue("a.rs", 1, Tag::Todo, "retry #42", "#42"),
+            make_item("a.rs", 2, Tag::Todo, "same"),
+        ];
+        let mut duplicate = stale("a.rs", 2);
+        duplicate.rule = "duplicate".to_string();
+        let violations = vec![stale("a.rs", 1), duplicate];
+
+        let outcome = apply_fixes(&violations, &items, dir.path(), true).unwrap();
+        assert_eq!(outcome.files[0].removed, vec![1]);
+        let unchanged = std::fs::read_to_string(dir.path().join("a.rs")).unwrap();
+        assert_eq!(unchanged, content);
+    }
+
+    #[test]
+    fn test_unified_diff_hunks() {
+        let old: Vec<&str> = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n"
+            .split_inclusive('\n')
+            .collect();
+        assert_eq!(
+            unified_diff("a.rs", &old, &[2, 12]),
+            "--- a/a.rs\n+++ b/a.rs\n\
+             @@ -1,5 +1,4 @@\n 1\n-2\n 3\n 4\n 5\n\
+             @@ -9,4 +8,3 @@\n 9\n 10\n 11\n-12\n"
+        );
+
+        let old = vec!["only"];
+        assert_eq!(
+            unified_diff("a.rs", &old, &[1]),
+            "--- a/a.rs\n+++ b/a.rs\n@@ -1,1 +0,0 @@\n-only\n\\ No newline at end of file\n"
+        );
+    }
 }
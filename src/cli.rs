@@ -1,9 +1,21 @@
+use clap::builder::FalseyValueParser;
 use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use std::path::PathBuf;
 
+use crate::context;
 use crate::model;
 
+/// Extended `--help` text for `--template`, listing every variable.
+const TEMPLATE_HELP: &str =
+    "Render each item with a minijinja template instead of the default text \
+output, e.g. \"{{file}}:{{line}} {{tag}} {{message}}\\n\". \\n and \\t are turned into a \
+newline and a tab; each item's output ends with a newline.
+
+Variables: file, line, column, tag, message, author, issue_ref, priority, deadline, \
+relative_deadline, checked, escalate_to, mentions, id, stable_id, age_days (git blame age; list and search), \
+status (added or removed; diff). Missing values render as empty.";
+
 #[derive(Parser)]
 #[command(
     name = "todo-scan",
@@ -11,31 +23,142 @@ use crate::model;
     about = "Track TODO/FIXME/HACK comments in your codebase"
 )]
 pub struct Cli {
-    #[arg(long, global = true, value_enum, default_value = "text")]
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "text",
+        env = "TODO_SCAN_FORMAT"
+    )]
     pub format: Format,
 
-    #[arg(long, global = true)]
+    #[arg(long, global = true, env = "TODO_SCAN_ROOT")]
     pub root: Option<PathBuf>,
 
-    #[arg(long, global = true)]
+    #[arg(long, global = true, env = "TODO_SCAN_CONFIG")]
     pub config: Option<PathBuf>,
 
     /// Disable scan result caching
-    #[arg(long, global = true)]
+    #[arg(long, global = true, env = "TODO_SCAN_NO_CACHE", value_parser = FalseyValueParser::new())]
     pub no_cache: bool,
 
+    /// Directory for scan caches (default: the OS cache directory); overrides [cache] path
+    #[arg(long, global = true, value_name = "DIR", env = "TODO_SCAN_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Also scan files excluded by .gitignore, .ignore, and git exclude files
+    #[arg(long, global = true)]
+    pub no_ignore: bool,
+
+    /// Drop items in files matching this glob from the results (repeatable);
+    /// applied after scanning, on top of the config's exclusions
+    #[arg(long, global = true, value_name = "GLOB")]
+    pub exclude_path: Vec<String>,
+
+    /// Count JSON `column` values in UTF-16 code units (as LSP expects) instead of bytes
+    #[arg(long, global = true)]
+    pub utf16_columns: bool,
+
+    /// Worker threads for scanning (0 = automatic); overrides [scan] threads
+    #[arg(long, global = true, value_name = "N", env = "TODO_SCAN_THREADS")]
+    pub threads: Option<usize>,
+
+    /// Make file paths relative to the scan root (default) or the git top-level
+    #[arg(long, global = true, value_enum)]
+    pub path_base: Option<PathBase>,
+
     /// Control output detail level: minimal (compact), normal (default), full (enriched)
-    #[arg(long, global = true, value_enum, default_value = "normal")]
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "normal",
+        env = "TODO_SCAN_DETAIL"
+    )]
     pub detail: DetailLevel,
 
+    /// How context lines are chosen: lines (-C N above and below) or function
+    /// (the enclosing or following function/block; falls back to lines)
+    #[arg(long, global = true, value_enum, default_value = "lines")]
+    pub context_mode: ContextMode,
+
     /// Show items suppressed by todo-scan:ignore markers
     #[arg(long, global = true)]
     pub show_ignored: bool,
 
+    /// When to use colors: auto (terminal without NO_COLOR), always, never
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Disable colored output (same as --color never)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// Fit text-mode item lines to N columns (default: terminal width; 0 disables).
+    /// Lines are truncated with an ellipsis, or wrapped with --detail full
+    #[arg(long, global = true, value_name = "N")]
+    pub width: Option<usize>,
+
+    /// Wrap JSON output in {todo_scan_version, generated_at, root, command, data}
+    #[arg(long, global = true)]
+    pub envelope: bool,
+
+    /// Suppress summary lines and non-error warnings
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Print time spent in each phase (scan, cache, blame, diff, context, output) to stderr
+    #[arg(long, global = true)]
+    pub profile: bool,
+
+    /// When to show a scan progress spinner on stderr: auto (text output on a
+    /// terminal), always, never
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pub progress: ProgressChoice,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
+#[derive(Clone, ValueEnum)]
+pub enum SchemaTarget {
+    List,
+    Search,
+    Diff,
+    Blame,
+    Stats,
+    Check,
+    Lint,
+    Clean,
+    Export,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ProgressChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PathBase {
+    Root,
+    GitRoot,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ContextMode {
+    Lines,
+    Function,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
 pub enum DetailLevel {
     Minimal,
@@ -48,14 +171,28 @@ pub enum Format {
     Text,
     Json,
     GithubActions,
+    AzureDevops,
+    Xcode,
     Sarif,
     Markdown,
+    Csv,
+    Dot,
+    GhIssue,
+    Html,
+    Junit,
 }
 
 #[derive(Subcommand)]
 pub enum Command {
     #[command(alias = "ls")]
     List {
+        /// Scan only these files or directories instead of the whole root
+        paths: Vec<PathBuf>,
+
+        /// Also read paths to scan from stdin, one per line
+        #[arg(long)]
+        files: bool,
+
         #[arg(long)]
         tag: Vec<String>,
 
@@ -68,11 +205,20 @@ pub enum Command {
         #[arg(long, value_enum)]
         priority: Vec<PriorityFilter>,
 
+        /// Only items at this priority or more severe (normal < high < urgent)
+        #[arg(long, value_enum, value_name = "PRIORITY", conflicts_with = "priority")]
+        priority_min: Option<PriorityFilter>,
+
         #[arg(long)]
         author: Option<String>,
 
-        #[arg(long)]
-        path: Option<String>,
+        /// Only items whose message mentions @HANDLE (turns on [scan] mentions)
+        #[arg(long, value_name = "HANDLE")]
+        mention: Option<String>,
+
+        /// Keep items in files matching this glob; repeat to match any of several
+        #[arg(long, value_name = "GLOB")]
+        path: Vec<String>,
 
         #[arg(long)]
         limit: Option<usize>,
@@ -84,9 +230,71 @@ pub enum Command {
         /// Scope scan to a single workspace package
         #[arg(long)]
         package: Option<String>,
+
+        /// Print only the total, per-tag, and file counts
+        #[arg(long)]
+        count_only: bool,
+
+        /// List scanned files that contain no matching TODOs instead of the TODOs
+        #[arg(long, visible_alias = "no-todos", conflicts_with = "count_only")]
+        invert_match: bool,
+
+        /// Collapse TODOs with the same tag and message into one row with all locations
+        #[arg(long, conflicts_with_all = ["count_only", "invert_match"])]
+        dedupe: bool,
+
+        /// Only TODOs in tracked files with staged or unstaged changes (git status)
+        #[arg(long, conflicts_with = "invert_match")]
+        modified_only: bool,
+
+        /// Only TODOs whose line git blame dates within this window: a duration
+        /// (e.g. "30d") or a date (YYYY-MM-DD); untracked files are excluded
+        #[arg(long, value_name = "WINDOW", conflicts_with = "invert_match")]
+        since_date: Option<String>,
+
+        /// Columns to show in text output, in order (comma-separated, e.g. "file,line,tag,message")
+        #[arg(long, value_enum, value_delimiter = ',')]
+        fields: Vec<ListField>,
+
+        /// Render each TODO with a template (see --help for variables)
+        #[arg(
+            long,
+            long_help = TEMPLATE_HELP,
+            conflicts_with_all = ["count_only", "invert_match", "dedupe", "fields"]
+        )]
+        template: Option<String>,
+
+        /// Show items as one flat table with aligned tag, priority, location
+        /// and message columns (text output)
+        #[arg(
+            long,
+            conflicts_with_all = ["count_only", "invert_match", "dedupe", "fields", "template", "context", "group_by"]
+        )]
+        table: bool,
+
+        /// Open each matching TODO at its line in $EDITOR instead of listing it
+        #[arg(
+            long,
+            group = "open_mode",
+            conflicts_with_all = ["count_only", "invert_match", "dedupe", "template"]
+        )]
+        open: bool,
+
+        /// Choose which matching TODOs to open from an interactive menu
+        #[arg(
+            long,
+            group = "open_mode",
+            conflicts_with_all = ["count_only", "invert_match", "dedupe", "template"]
+        )]
+        pick: bool,
+
+        /// Editor command for --open and --pick (default: $EDITOR)
+        #[arg(long, requires = "open_mode")]
+        editor: Option<String>,
     },
 
     Diff {
+        /// Git ref to compare the working tree against, or BASE..HEAD to compare two refs
         git_ref: String,
 
         #[arg(long)]
@@ -96,9 +304,21 @@ pub enum Command {
         #[arg(short = 'C', long)]
         context: Option<usize>,
 
+        /// Show only added or only removed TODOs
+        #[arg(long, value_enum)]
+        only: Option<DiffOnly>,
+
         /// Scope scan to a single workspace package
         #[arg(long)]
         package: Option<String>,
+
+        /// Render each added or removed TODO with a template (see --help for variables)
+        #[arg(long, long_help = TEMPLATE_HELP)]
+        template: Option<String>,
+
+        /// Print added and removed counts per file instead of each TODO
+        #[arg(long, conflicts_with_all = ["template", "context"])]
+        stat: bool,
     },
 
     /// Show code context around a TODO at FILE:LINE
@@ -109,6 +329,10 @@ pub enum Command {
         /// Number of context lines (default: 5)
         #[arg(short = 'C', long, default_value = "5")]
         context: usize,
+
+        /// Syntax-highlight surrounding lines (ignored when stdout is not a terminal)
+        #[arg(long)]
+        highlight: bool,
     },
 
     /// Generate a .todo-scan.toml configuration file
@@ -116,6 +340,34 @@ pub enum Command {
         /// Accept defaults without interactive prompts
         #[arg(long, short = 'y')]
         yes: bool,
+
+        /// Tags to track, skipping the tag prompt (comma-separated, e.g. "TODO,FIXME")
+        #[arg(long, value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Directories to exclude, skipping the detected suggestions (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        exclude: Vec<String>,
+
+        /// Maximum TODO count for CI checks, written to [check] max
+        #[arg(long)]
+        max: Option<usize>,
+
+        /// Overwrite an existing .todo-scan.toml without asking
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Manage the git pre-commit hook
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+
+    /// Inspect the .todo-scan.toml configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
     },
 
     /// Generate shell completions
@@ -125,6 +377,14 @@ pub enum Command {
         shell: Shell,
     },
 
+    /// Print the JSON Schema of a command's `--format json` output
+    #[command(hide = true)]
+    Schema {
+        /// Command whose output schema to print
+        #[arg(value_enum)]
+        command: SchemaTarget,
+    },
+
     /// Show git blame metadata for TODO comments
     Blame {
         #[arg(long, value_enum, default_value = "file")]
@@ -143,13 +403,50 @@ pub enum Command {
         #[arg(long)]
         tag: Vec<String>,
 
+        /// Keep items in files matching this glob; repeat to match any of several
+        #[arg(long, value_name = "GLOB")]
+        path: Vec<String>,
+
+        /// Summarize per author instead of listing each TODO
+        #[arg(long, value_enum)]
+        group_by: Option<BlameGroupBy>,
+
+        /// Show only the N oldest TODOs (shortcut for --sort age --limit N)
+        #[arg(long, value_name = "N", conflicts_with_all = ["sort", "limit"])]
+        oldest: Option<usize>,
+
+        /// Show at most N TODOs; the summary still covers all of them
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+    },
+
+    /// Histogram of TODO ages from git blame
+    Age {
+        /// Bucket cut points in days, ascending (default: "7d,30d,90d,365d")
         #[arg(long)]
-        path: Option<String>,
+        buckets: Option<String>,
     },
 
     Stats {
+        /// Compare the full breakdown at two refs side by side (BASE..HEAD)
+        #[arg(value_name = "BASE..HEAD", conflicts_with = "since")]
+        range: Option<String>,
+
         #[arg(long)]
         since: Option<String>,
+
+        /// Break down changes since --since by git blame author
+        #[arg(long, value_enum, requires = "since")]
+        by: Option<StatsBy>,
+
+        /// Show only the N largest author and hotspot entries (0 shows all)
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+
+        /// Sample N commits from git history and draw the TODO count as a
+        /// sparkline (0 = off; each sample scans a whole commit)
+        #[arg(long, value_name = "N", default_value_t = 0, conflicts_with = "range")]
+        history: usize,
     },
 
     /// Compressed summary of TODO landscape (2-4 lines)
@@ -180,17 +477,38 @@ pub enum Command {
         #[arg(long)]
         author: Option<String>,
 
+        /// Only items whose message mentions @HANDLE (turns on [scan] mentions)
+        #[arg(long, value_name = "HANDLE")]
+        mention: Option<String>,
+
+        /// Only matches at this priority or more severe (normal < high < urgent)
+        #[arg(long, value_enum, value_name = "PRIORITY")]
+        priority_min: Option<PriorityFilter>,
+
         #[arg(long)]
         tag: Vec<String>,
 
-        #[arg(long)]
-        path: Option<String>,
+        /// Keep items in files matching this glob; repeat to match any of several
+        #[arg(long, value_name = "GLOB")]
+        path: Vec<String>,
 
         #[arg(long, value_enum, default_value = "file")]
         sort: SortBy,
 
         #[arg(long, value_enum, default_value = "file")]
         group_by: GroupBy,
+
+        /// Search TODOs in every commit's files instead of the working tree (slow)
+        #[arg(long, conflicts_with = "context")]
+        history: bool,
+
+        /// Number of most recent commits to read with --history
+        #[arg(long, requires = "history", default_value_t = 1000)]
+        max_commits: usize,
+
+        /// Render each match with a template (see --help for variables)
+        #[arg(long, long_help = TEMPLATE_HELP, conflicts_with = "history")]
+        template: Option<String>,
     },
 
     Check {
@@ -206,9 +524,34 @@ pub enum Command {
         #[arg(long)]
         since: Option<String>,
 
+        /// Check the staged content instead of the working tree; new TODOs
+        /// are counted against HEAD (for pre-commit hooks)
+        #[arg(long, conflicts_with_all = ["since", "package", "workspace"])]
+        staged: bool,
+
         #[arg(long)]
         expired: bool,
 
+        /// Fail if any TODO is older than this per git blame (e.g., "180d")
+        #[arg(long)]
+        max_age: Option<String>,
+
+        /// Warn about deadlines due within this window (e.g., "7d")
+        #[arg(long)]
+        due_soon: Option<String>,
+
+        /// Fail instead of warn on deadlines within the --due-soon window
+        #[arg(long)]
+        due_soon_fails: bool,
+
+        /// Tags that must carry a deadline (comma-separated, e.g. "BUG,FIXME")
+        #[arg(long, value_delimiter = ',')]
+        require_deadline: Vec<String>,
+
+        /// Fail on any TODO at this priority or above (e.g., "urgent" for `!!`)
+        #[arg(long, value_enum, value_name = "PRIORITY")]
+        block_priority: Option<PriorityFilter>,
+
         /// Scope scan to a single workspace package
         #[arg(long)]
         package: Option<String>,
@@ -216,6 +559,10 @@ pub enum Command {
         /// Run check across all workspace packages with per-package thresholds
         #[arg(long)]
         workspace: bool,
+
+        /// Report violations but exit with code 0
+        #[arg(long)]
+        exit_zero: bool,
     },
 
     /// Watch filesystem for TODO changes in real-time
@@ -230,6 +577,14 @@ pub enum Command {
         /// Debounce interval in milliseconds
         #[arg(long, default_value = "300")]
         debounce: u64,
+
+        /// Shell command to run after each change, with the event JSON on stdin
+        #[arg(long)]
+        run: Option<String>,
+
+        /// Redraw a full-screen summary on each change instead of streaming events
+        #[arg(long, visible_alias = "tui")]
+        clear: bool,
     },
 
     /// Find stale issue references and duplicate TODOs
@@ -241,6 +596,22 @@ pub enum Command {
         /// Only flag issues closed longer than this duration (e.g., "30d")
         #[arg(long)]
         since: Option<String>,
+
+        /// Delete the comment lines of TODOs whose issue is closed
+        #[arg(long)]
+        fix: bool,
+
+        /// With --fix, print a unified diff of the removals instead of writing them
+        #[arg(long, requires = "fix")]
+        dry_run: bool,
+
+        /// With --fix, edit files even if the git working tree has uncommitted changes
+        #[arg(long, requires = "fix")]
+        allow_dirty: bool,
+
+        /// Report violations but exit with code 0
+        #[arg(long)]
+        exit_zero: bool,
     },
 
     /// Generate an HTML technical debt dashboard report
@@ -256,6 +627,15 @@ pub enum Command {
         /// Days threshold for marking TODOs as stale (default: 365)
         #[arg(long)]
         stale_threshold: Option<String>,
+
+        /// Append a snapshot to this JSON Lines file and chart its history
+        #[arg(long)]
+        history_file: Option<std::path::PathBuf>,
+
+        /// List TODOs present at a sampled --history commit but gone now,
+        /// with a resolution rate
+        #[arg(long)]
+        include_resolved: bool,
     },
 
     /// Export TODOs as Claude Code Tasks (Claude Code-specific; not compatible with other coding agents)
@@ -284,13 +664,34 @@ pub enum Command {
         #[arg(long, value_enum)]
         priority: Vec<PriorityFilter>,
 
+        /// Only TODOs at this priority or more severe (normal < high < urgent)
+        #[arg(long, value_enum, value_name = "PRIORITY", conflicts_with = "priority")]
+        priority_min: Option<PriorityFilter>,
+
         /// Filter by author
         #[arg(long)]
         author: Option<String>,
 
-        /// Filter by file glob
+        /// Keep items in files matching this glob; repeat to match any of several
+        #[arg(long, value_name = "GLOB")]
+        path: Vec<String>,
+    },
+
+    /// Stream TODOs as JSON Lines, one object per item
+    Export {
         #[arg(long)]
-        path: Option<String>,
+        tag: Vec<String>,
+
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Keep items in files matching this glob; repeat to match any of several
+        #[arg(long, value_name = "GLOB")]
+        path: Vec<String>,
+
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
     },
 
     /// Manage and inspect workspace packages
@@ -306,8 +707,9 @@ pub enum Command {
         #[arg(long)]
         cluster: bool,
 
-        /// Show TODOs related to a specific item (FILE:LINE)
-        #[arg(long, value_name = "LOCATION")]
+        /// Show TODOs related to one item, given as its stable_id (or a unique
+        /// prefix of at least 4 characters), its id, or FILE:LINE
+        #[arg(long, value_name = "ID|LOCATION")]
         r#for: Option<String>,
 
         /// Minimum relationship score (0.0-1.0)
@@ -317,6 +719,10 @@ pub enum Command {
         /// Line proximity threshold for same-file detection
         #[arg(long, default_value = "10")]
         proximity: usize,
+
+        /// Drop clusters with fewer members than this (1 keeps single items)
+        #[arg(long, requires = "cluster", default_value_t = 2)]
+        min_cluster_size: usize,
     },
 
     /// Lint TODO comment formatting against configurable rules
@@ -344,6 +750,23 @@ pub enum Command {
         /// Enforce colon after tag
         #[arg(long)]
         require_colon: bool,
+
+        /// Flag files where more than this fraction of lines are TODOs (e.g., 0.05)
+        #[arg(long, value_name = "RATIO")]
+        max_density: Option<f64>,
+
+        /// Flag TODOs past their deadline that were not escalated per their
+        /// `TODO->FIXME` marker
+        #[arg(long)]
+        escalate_overdue: bool,
+
+        /// Flag TODOs whose message repeats an earlier one in the same file
+        #[arg(long)]
+        no_local_duplicates: bool,
+
+        /// Report violations but exit with code 0
+        #[arg(long)]
+        exit_zero: bool,
     },
 }
 
@@ -352,6 +775,14 @@ pub enum SortBy {
     File,
     Tag,
     Priority,
+    /// Oldest first per git blame; untracked files last
+    Age,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DiffOnly {
+    Added,
+    Removed,
 }
 
 #[derive(Clone, ValueEnum)]
@@ -361,6 +792,20 @@ pub enum GroupBy {
     Priority,
     Author,
     Dir,
+    Issue,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListField {
+    File,
+    Line,
+    Tag,
+    Priority,
+    Author,
+    #[value(name = "issue_ref")]
+    IssueRef,
+    Deadline,
+    Message,
 }
 
 #[derive(Clone, ValueEnum)]
@@ -378,6 +823,36 @@ pub enum BlameSortBy {
     Tag,
 }
 
+#[derive(Clone, ValueEnum)]
+pub enum BlameGroupBy {
+    Author,
+}
+
+#[derive(Clone, ValueEnum)]
+pub enum StatsBy {
+    Author,
+}
+
+#[derive(Subcommand)]
+pub enum HooksAction {
+    /// Install a pre-commit hook that rejects commits adding new TODOs
+    Install {
+        /// Append to an existing pre-commit hook not written by todo-scan
+        #[arg(long)]
+        force: bool,
+
+        /// Remove the todo-scan section from the pre-commit hook instead
+        #[arg(long, conflicts_with = "force")]
+        uninstall: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Check the config file for unknown keys and invalid values without scanning
+    Validate,
+}
+
 #[derive(Subcommand)]
 pub enum WorkspaceAction {
     /// List detected workspace packages and their TODO counts
@@ -385,6 +860,36 @@ pub enum WorkspaceAction {
     List,
 }
 
+impl Format {
+    /// Subcommands that can render this format, for formats only some
+    /// commands support. Every command accepts the others.
+    pub fn supported_commands(&self) -> Option<&'static [&'static str]> {
+        match self {
+            Format::Dot => Some(&["relate"]),
+            Format::GhIssue => Some(&["tasks"]),
+            Format::Html => Some(&["stats"]),
+            Format::Junit => Some(&["check", "lint"]),
+            _ => None,
+        }
+    }
+
+    /// The `--format` value naming this format.
+    pub fn name(&self) -> String {
+        self.to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl ContextMode {
+    pub fn to_context_mode(self) -> context::ContextMode {
+        match self {
+            ContextMode::Lines => context::ContextMode::Lines,
+            ContextMode::Function => context::ContextMode::Function,
+        }
+    }
+}
+
 impl PriorityFilter {
     pub fn to_priority(&self) -> model::Priority {
         match self {
@@ -0,0 +1,38 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::blame::{compute_age_histogram, compute_blame, parse_age_buckets, DEFAULT_AGE_BUCKETS};
+use crate::cli::Format;
+use crate::config::Config;
+use crate::output::print_age;
+use crate::profile;
+
+use super::{do_scan, file_base};
+
+pub fn cmd_age(
+    root: &Path,
+    config: &Config,
+    format: &Format,
+    buckets: Option<&str>,
+    no_cache: bool,
+) -> Result<()> {
+    let cuts = match buckets {
+        Some(s) => parse_age_buckets(s)?,
+        None => DEFAULT_AGE_BUCKETS.to_vec(),
+    };
+
+    let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
+    // Staleness is not reported here, so any threshold will do
+    let blame = profile::time("blame", || {
+        compute_blame(&scan, &base_dir, u64::MAX, &config.author_aliases())
+    })?;
+
+    let _output = profile::phase("output");
+    print_age(
+        &compute_age_histogram(&blame, scan.items.len(), &cuts),
+        format,
+    );
+    Ok(())
+}
@@ -1,14 +1,16 @@
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 
-use crate::blame::{compute_blame, parse_duration_days};
-use crate::cli::{BlameSortBy, Format};
+use crate::blame::{compute_blame, parse_duration_days, summarize_blame, summarize_by_author};
+use crate::cli::{BlameGroupBy, BlameSortBy, Format};
 use crate::config::Config;
 use crate::model::Tag;
 use crate::output::print_blame;
+use crate::profile;
 
-use super::do_scan;
+use super::filter::path_matcher;
+use super::{do_scan, file_base};
 
 pub struct BlameOptions {
     pub sort: BlameSortBy,
@@ -16,7 +18,10 @@ pub struct BlameOptions {
     pub min_age: Option<String>,
     pub stale_threshold: Option<String>,
     pub tag: Vec<String>,
-    pub path: Option<String>,
+    pub path: Vec<String>,
+    pub group_by: Option<BlameGroupBy>,
+    pub oldest: Option<usize>,
+    pub limit: Option<usize>,
 }
 
 pub fn cmd_blame(
@@ -27,6 +32,7 @@ pub fn cmd_blame(
     no_cache: bool,
 ) -> Result<()> {
     let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
 
     // Resolve stale threshold: CLI > config > default (365d)
     let threshold_str = opts
@@ -35,14 +41,17 @@ pub fn cmd_blame(
         .unwrap_or_else(|| "365d".to_string());
     let stale_threshold = parse_duration_days(&threshold_str)?;
 
-    let mut result = compute_blame(&scan, root, stale_threshold)?;
+    let mut result = profile::time("blame", || {
+        compute_blame(&scan, &base_dir, stale_threshold, &config.author_aliases())
+    })?;
 
     // Apply tag filter
+    let tag_styles = config.tag_styles();
     if !opts.tag.is_empty() {
         let filter_tags: Vec<Tag> = opts
             .tag
             .iter()
-            .filter_map(|s| s.parse::<Tag>().ok())
+            .filter_map(|s| tag_styles.parse(s))
             .collect();
         result.entries.retain(|e| filter_tags.contains(&e.item.tag));
     }
@@ -62,15 +71,17 @@ pub fn cmd_blame(
     }
 
     // Apply path filter
-    if let Some(ref pattern) = opts.path {
-        let glob = globset::Glob::new(pattern)
-            .context("invalid glob pattern")?
-            .compile_matcher();
-        result.entries.retain(|e| glob.is_match(&e.item.file));
+    if let Some(globs) = path_matcher(&opts.path)? {
+        result.entries.retain(|e| globs.is_match(&e.item.file));
     }
 
-    // Apply sort
-    match opts.sort {
+    // Apply sort; --oldest always ranks by age
+    let sort = if opts.oldest.is_some() {
+        BlameSortBy::Age
+    } else {
+        opts.sort
+    };
+    match sort {
         BlameSortBy::File => result.entries.sort_by(|a, b| {
             a.item
                 .file
@@ -79,24 +90,26 @@ pub fn cmd_blame(
         }),
         BlameSortBy::Age => result
             .entries
-            .sort_by(|a, b| b.blame.age_days.cmp(&a.blame.age_days)),
+            .sort_by_key(|e| std::cmp::Reverse(e.blame.age_days)),
         BlameSortBy::Author => result
             .entries
             .sort_by(|a, b| a.blame.author.cmp(&b.blame.author)),
         BlameSortBy::Tag => result
             .entries
-            .sort_by(|a, b| a.item.tag.severity().cmp(&b.item.tag.severity()).reverse()),
+            .sort_by_key(|e| std::cmp::Reverse(tag_styles.severity(&e.item.tag))),
     }
 
-    // Recompute summary after filtering
-    result.total = result.entries.len();
-    result.stale_count = result.entries.iter().filter(|e| e.stale).count();
-    result.avg_age_days = if result.total > 0 {
-        result.entries.iter().map(|e| e.blame.age_days).sum::<u64>() / result.total as u64
-    } else {
-        0
-    };
+    // Recompute summary after filtering, before truncating, so the totals
+    // cover every matching TODO
+    let mut result = summarize_blame(result.entries, result.stale_threshold_days);
+    if let Some(BlameGroupBy::Author) = opts.group_by {
+        result.authors = Some(summarize_by_author(&result.entries));
+    }
+    if let Some(n) = opts.oldest.or(opts.limit) {
+        result.entries.truncate(n);
+    }
 
-    print_blame(&result, format);
+    let _output = profile::phase("output");
+    print_blame(&result, format, opts.oldest.is_some());
     Ok(())
 }
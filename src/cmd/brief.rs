@@ -5,10 +5,10 @@ use anyhow::Result;
 use crate::brief::compute_brief;
 use crate::cli::Format;
 use crate::config::Config;
-use crate::diff::compute_diff;
 use crate::output::print_brief;
+use crate::profile;
 
-use super::do_scan;
+use super::{diff_since, do_scan, file_base};
 
 pub fn cmd_brief(
     root: &Path,
@@ -19,14 +19,16 @@ pub fn cmd_brief(
     no_cache: bool,
 ) -> Result<()> {
     let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
 
     let diff = if let Some(ref base_ref) = since {
-        Some(compute_diff(&scan, base_ref, root, config)?)
+        Some(diff_since(&scan, base_ref, &base_dir, config)?)
     } else {
         None
     };
 
-    let result = compute_brief(&scan, diff.as_ref());
+    let result = compute_brief(&scan, diff.as_ref(), &config.tag_styles());
+    let _output = profile::phase("output");
     print_brief(&result, format, budget);
     Ok(())
 }
@@ -1,18 +1,20 @@
 use std::path::Path;
-use std::process;
 
 use anyhow::Result;
 
+use crate::blame::{compute_blame, resolve_relative_deadlines};
 use crate::check::{run_check, CheckOverrides};
 use crate::cli::Format;
 use crate::config::Config;
 use crate::deadline;
-use crate::diff::compute_diff;
+use crate::diff::{compute_staged_diff, scan_index};
+use crate::git::default_branch;
 use crate::model;
 use crate::output::print_check;
+use crate::profile;
 use crate::workspace;
 
-use super::do_scan;
+use super::{diff_since, do_scan, drop_excluded_entries, drop_excluded_items, file_base};
 
 pub fn cmd_check(
     root: &Path,
@@ -20,27 +22,63 @@ pub fn cmd_check(
     format: &Format,
     overrides: CheckOverrides,
     since: Option<String>,
+    staged: bool,
     no_cache: bool,
-) -> Result<()> {
-    let scan = do_scan(root, config, no_cache)?;
+) -> Result<bool> {
+    let base_dir = file_base(root, config)?;
+    let mut scan = if staged {
+        let mut scan = profile::time("walk+scan", || scan_index(&base_dir, config))?;
+        drop_excluded_items(&mut scan);
+        scan
+    } else {
+        do_scan(root, config, no_cache)?
+    };
+
+    let due_soon = overrides.due_soon.is_some() || config.check.due_soon.is_some();
+    if overrides.expired || config.check.expired.unwrap_or(false) || due_soon {
+        resolve_relative_deadlines(&mut scan.items, &base_dir);
+    }
+
+    // `--max-new` alone compares against the remote default branch
+    let since = match since {
+        None if overrides.max_new.is_some() && !staged => Some(default_branch(&base_dir)?),
+        since => since,
+    };
 
-    let diff = if let Some(ref base_ref) = since {
-        Some(compute_diff(&scan, base_ref, root, config)?)
+    let diff = if staged {
+        let mut diff = profile::time("diff", || compute_staged_diff(&scan, &base_dir, config))?;
+        drop_excluded_entries(&mut diff);
+        Some(diff)
+    } else if let Some(ref base_ref) = since {
+        Some(diff_since(&scan, base_ref, &base_dir, config)?)
     } else {
         None
     };
 
-    let today = deadline::today();
-    let result = run_check(&scan, diff.as_ref(), config, &overrides, &today);
-    let passed = result.passed;
+    // Blame is expensive, so only run it when an age limit is requested
+    let blame = overrides
+        .max_age
+        .map(|max_age_days| {
+            profile::time("blame", || {
+                compute_blame(&scan, &base_dir, max_age_days, &config.author_aliases())
+            })
+        })
+        .transpose()?;
 
+    let today = deadline::today();
+    let result = run_check(
+        &scan,
+        diff.as_ref(),
+        blame.as_ref(),
+        config,
+        &overrides,
+        &today,
+    );
+
+    let _output = profile::phase("output");
     print_check(&result, format);
 
-    if !passed {
-        process::exit(1);
-    }
-
-    Ok(())
+    Ok(result.passed)
 }
 
 pub fn cmd_workspace_check(
@@ -48,35 +86,50 @@ pub fn cmd_workspace_check(
     config: &Config,
     format: &Format,
     no_cache: bool,
-) -> Result<()> {
+) -> Result<bool> {
     let ws = workspace::detect_workspace(root, config)?
         .ok_or_else(|| anyhow::anyhow!("no workspace detected"))?;
 
     let mut all_passed = true;
     let mut violations = Vec::new();
+    let mut rules: Vec<String> = Vec::new();
+    let mut evaluate = |rule: &str| {
+        if !rules.iter().any(|r| r == rule) {
+            rules.push(rule.to_string());
+        }
+    };
+    let mut workspace_total = 0;
 
     for pkg in &ws.packages {
         let pkg_root = root.join(&pkg.path);
         let scan = do_scan(&pkg_root, config, no_cache)?;
         let todo_count = scan.items.len();
+        workspace_total += todo_count;
 
         let pkg_config = config.workspace.packages.get(&pkg.name);
 
-        if let Some(pc) = pkg_config {
-            if let Some(max) = pc.max {
-                if todo_count > max {
-                    all_passed = false;
-                    violations.push(model::CheckViolation {
-                        rule: "workspace/max".to_string(),
-                        message: format!(
-                            "package '{}' has {} TODOs (max: {})",
-                            pkg.name, todo_count, max
-                        ),
-                    });
-                }
+        // A package's own `max` wins over `[workspace] default_max`
+        let max = match pkg_config.and_then(|pc| pc.max) {
+            Some(max) => Some((max, "max")),
+            None => config.workspace.default_max.map(|max| (max, "default max")),
+        };
+        if let Some((max, label)) = max {
+            evaluate("workspace/max");
+            if todo_count > max {
+                all_passed = false;
+                violations.push(model::CheckViolation {
+                    rule: "workspace/max".to_string(),
+                    message: format!(
+                        "package '{}' has {} TODOs ({}: {})",
+                        pkg.name, todo_count, label, max
+                    ),
+                });
             }
+        }
 
+        if let Some(pc) = pkg_config {
             if !pc.block_tags.is_empty() {
+                evaluate("workspace/block-tag");
                 for item in &scan.items {
                     if pc
                         .block_tags
@@ -97,17 +150,32 @@ pub fn cmd_workspace_check(
         }
     }
 
+    if let Some(total_max) = config.workspace.total_max {
+        evaluate("workspace/total-max");
+        if workspace_total > total_max {
+            all_passed = false;
+            violations.push(model::CheckViolation {
+                rule: "workspace/total-max".to_string(),
+                message: format!(
+                    "workspace has {} TODOs across {} packages (total max: {})",
+                    workspace_total,
+                    ws.packages.len(),
+                    total_max
+                ),
+            });
+        }
+    }
+
     let result = model::CheckResult {
         passed: all_passed,
         total: violations.len(),
         violations,
+        warnings: vec![],
+        rules,
     };
 
+    let _output = profile::phase("output");
     print_check(&result, format);
 
-    if !all_passed {
-        process::exit(1);
-    }
-
-    Ok(())
+    Ok(all_passed)
 }
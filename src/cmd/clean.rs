@@ -1,44 +1,109 @@
 use std::path::Path;
-use std::process;
 
-use anyhow::Result;
+use anyhow::{ensure, Context, Result};
 
 use crate::clean;
 use crate::cli::Format;
-use crate::config::Config;
-use crate::output::print_clean;
+use crate::config::{Config, IssueProvider};
+use crate::git::modified_files;
+use crate::output::{self, print_clean};
+use crate::profile;
 
-use super::do_scan;
+use super::{do_scan, file_base};
+
+pub struct CleanOptions {
+    pub check: bool,
+    pub since: Option<String>,
+    /// Set with `--fix`
+    pub fix: Option<FixMode>,
+}
+
+pub struct FixMode {
+    pub dry_run: bool,
+    pub allow_dirty: bool,
+}
 
 pub fn cmd_clean(
     root: &Path,
     config: &Config,
     format: &Format,
-    check_mode: bool,
-    since: Option<String>,
+    opts: CleanOptions,
     no_cache: bool,
-) -> Result<()> {
+) -> Result<bool> {
+    // Refuse before doing any work, so uncommitted edits cannot be mixed
+    // with the removals
+    if let Some(FixMode {
+        dry_run: false,
+        allow_dirty: false,
+    }) = opts.fix
+    {
+        let modified = modified_files(root).context(
+            "clean --fix requires a git repository; pass --allow-dirty to skip this check",
+        )?;
+        ensure!(
+            modified.is_empty(),
+            "the working tree has uncommitted changes; commit or stash them first, or pass --allow-dirty"
+        );
+    }
+
     let scan = do_scan(root, config, no_cache)?;
 
-    // Try to create GhIssueChecker; warn if gh is unavailable
-    let gh_checker = clean::GhIssueChecker::new();
-    if gh_checker.is_none() && config.clean.stale_issues.unwrap_or(true) {
-        eprintln!("warning: gh CLI not found, skipping stale issue detection");
+    // Try to create the provider's checker; warn if its CLI is unavailable
+    let (checker, cli_name): (Option<Box<dyn clean::IssueChecker>>, &str) =
+        match config.clean.provider.unwrap_or_default() {
+            IssueProvider::Github => (clean::GhIssueChecker::new().map(|c| Box::new(c) as _), "gh"),
+            IssueProvider::Gitlab => (
+                clean::GlIssueChecker::new(config.clean.project.as_deref())
+                    .map(|c| Box::new(c) as _),
+                "glab",
+            ),
+        };
+    if checker.is_none() && config.clean.stale_issues.unwrap_or(true) && !output::is_quiet() {
+        eprintln!(
+            "warning: {} CLI not found, skipping stale issue detection",
+            cli_name
+        );
     }
 
-    let result = clean::run_clean(
-        &scan,
-        config,
-        gh_checker.as_ref().map(|c| c as &dyn clean::IssueChecker),
-        since.as_deref(),
-    );
-    let has_violations = !result.passed;
+    let result = clean::run_clean(&scan, config, checker.as_deref(), opts.since.as_deref());
+    // Violations only fail the run in --check mode
+    let passed = result.passed || !opts.check;
 
-    print_clean(&result, format);
+    let Some(fix) = opts.fix else {
+        let _output = profile::phase("output");
+        print_clean(&result, format);
+        return Ok(passed);
+    };
+
+    let base_dir = file_base(root, config)?;
+    let outcome = clean::apply_fixes(&result.violations, &scan.items, &base_dir, fix.dry_run)?;
+    if !output::is_quiet() {
+        for skipped in &outcome.skipped {
+            eprintln!(
+                "warning: {}:{}: left in place: {}",
+                skipped.file, skipped.line, skipped.reason
+            );
+        }
+    }
 
-    if check_mode && has_violations {
-        process::exit(1);
+    let _output = profile::phase("output");
+    if fix.dry_run {
+        for file in &outcome.files {
+            print!("{}", file.diff);
+        }
+        if outcome.files.is_empty() && !output::is_quiet() {
+            eprintln!("No stale TODO lines to remove");
+        }
+        return Ok(passed);
     }
 
-    Ok(())
+    print_clean(&result, format);
+    if !output::is_quiet() {
+        eprintln!(
+            "Removed {} stale TODO line(s) from {} file(s)",
+            outcome.removed_count(),
+            outcome.files.len()
+        );
+    }
+    Ok(passed)
 }
@@ -4,28 +4,39 @@ use anyhow::Result;
 
 use crate::cli::Format;
 use crate::config::Config;
-use crate::context::{build_rich_context, resolve_location};
+use crate::context::{build_rich_context, resolve_location, ContextMode};
 use crate::model;
 use crate::output::print_context;
+use crate::profile;
 
-use super::do_scan;
+use super::{do_scan, file_base};
 
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_context(
     root: &Path,
     config: &Config,
     format: &Format,
     location: &str,
     n: usize,
+    mode: ContextMode,
+    highlight: bool,
     no_cache: bool,
 ) -> Result<()> {
     // Scan first so we have items available for ID-based resolution
     let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
     let (file, line) = resolve_location(location, &scan.items)?;
 
     let todos_in_file: Vec<&model::TodoItem> =
         scan.items.iter().filter(|i| i.file == file).collect();
 
-    let rich = build_rich_context(root, &file, line, n, &todos_in_file)?;
-    print_context(&rich, format);
+    let rich = profile::time("context", || {
+        build_rich_context(&base_dir, &file, line, n, mode, &todos_in_file)
+    })?;
+    // `colored` already accounts for --color, NO_COLOR and whether stdout
+    // is a terminal
+    let highlight = highlight && colored::control::SHOULD_COLORIZE.should_colorize();
+    let _output = profile::phase("output");
+    print_context(&rich, format, highlight);
     Ok(())
 }
@@ -3,20 +3,29 @@ use std::path::Path;
 
 use anyhow::Result;
 
-use crate::cli::{DetailLevel, Format};
+use crate::cli::{DetailLevel, DiffOnly, Format};
 use crate::config::Config;
-use crate::context::collect_context_map;
-use crate::diff::compute_diff;
+use crate::context::{collect_context_map, ContextMode};
+use crate::diff::{compute_ref_diff, diff_stat};
 use crate::model::{DiffStatus, Tag};
-use crate::output::print_diff;
+use crate::output::template::ItemExtras;
+use crate::output::{print_diff, print_diff_stat};
+use crate::profile;
 
-use super::do_scan;
+use super::{
+    diff_since, do_scan, drop_excluded_entries, file_base, parse_template, print_templated,
+};
 
 pub struct DiffOptions {
     pub git_ref: String,
     pub tag: Vec<String>,
     pub context: Option<usize>,
+    pub only: Option<DiffOnly>,
     pub detail: DetailLevel,
+    pub context_mode: ContextMode,
+    pub template: Option<String>,
+    /// Print per-file counts instead of the items
+    pub stat: bool,
 }
 
 pub fn cmd_diff(
@@ -26,40 +35,100 @@ pub fn cmd_diff(
     opts: DiffOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let current = do_scan(root, config, no_cache)?;
-    let mut diff_result = compute_diff(&current, &opts.git_ref, root, config)?;
+    let template = parse_template(opts.template.as_deref(), format)?;
+    let base_dir = file_base(root, config)?;
+
+    // `A..B` compares two refs; a single ref compares against the working tree
+    let range = opts.git_ref.split_once("..");
+    let mut diff_result = match range {
+        Some((base, head)) => {
+            anyhow::ensure!(
+                !head.starts_with('.'),
+                "three-dot ranges are not supported; use BASE..HEAD"
+            );
+            anyhow::ensure!(
+                !base.is_empty() && !head.is_empty(),
+                "invalid range '{}': expected BASE..HEAD",
+                opts.git_ref
+            );
+            anyhow::ensure!(
+                opts.context.is_none(),
+                "--context is not supported when comparing two refs"
+            );
+            let mut diff =
+                profile::time("diff", || compute_ref_diff(base, head, &base_dir, config))?;
+            drop_excluded_entries(&mut diff);
+            diff
+        }
+        None => {
+            let current = do_scan(root, config, no_cache)?;
+            diff_since(&current, &opts.git_ref, &base_dir, config)?
+        }
+    };
 
     // Apply tag filter
-    if !opts.tag.is_empty() {
-        let filter_tags: Vec<Tag> = opts
-            .tag
-            .iter()
-            .filter_map(|s| s.parse::<Tag>().ok())
-            .collect();
+    let tag_styles = config.tag_styles();
+    let filter_tags: Vec<Tag> = opts
+        .tag
+        .iter()
+        .filter_map(|s| tag_styles.parse(s))
+        .collect();
+    if !filter_tags.is_empty() {
         diff_result
             .entries
             .retain(|entry| filter_tags.contains(&entry.item.tag));
-        diff_result.added_count = diff_result
-            .entries
-            .iter()
-            .filter(|e| matches!(e.status, DiffStatus::Added))
-            .count();
-        diff_result.removed_count = diff_result
-            .entries
-            .iter()
-            .filter(|e| matches!(e.status, DiffStatus::Removed))
-            .count();
     }
 
+    // Apply --only filter
+    if let Some(only) = opts.only {
+        diff_result.entries.retain(|entry| match only {
+            DiffOnly::Added => matches!(entry.status, DiffStatus::Added),
+            DiffOnly::Removed => matches!(entry.status, DiffStatus::Removed),
+        });
+    }
+
+    diff_result.recount(&filter_tags);
+
+    if opts.stat {
+        let _output = profile::phase("output");
+        print_diff_stat(&diff_stat(&diff_result), format);
+        return Ok(());
+    }
+
+    if let Some(template) = template {
+        return print_templated(
+            &template,
+            diff_result.entries.iter().map(|entry| {
+                let status = match entry.status {
+                    DiffStatus::Added => "added",
+                    DiffStatus::Removed => "removed",
+                };
+                let extras = ItemExtras {
+                    age_days: None,
+                    status: Some(status),
+                };
+                (&entry.item, extras)
+            }),
+        );
+    }
+
+    // Context is read from the working tree, so it only applies to ref-vs-tree diffs
     let items: Vec<_> = diff_result.entries.iter().map(|e| e.item.clone()).collect();
-    let context_map = if let Some(n) = opts.context {
-        collect_context_map(root, &items, n)
-    } else if opts.detail == DetailLevel::Full {
-        collect_context_map(root, &items, 3)
+    let context_map = if range.is_some() {
+        HashMap::new()
+    } else if let Some(n) = opts.context {
+        profile::time("context", || {
+            collect_context_map(&base_dir, &items, n, opts.context_mode)
+        })
+    } else if opts.detail == DetailLevel::Full || opts.context_mode == ContextMode::Function {
+        profile::time("context", || {
+            collect_context_map(&base_dir, &items, 3, opts.context_mode)
+        })
     } else {
         HashMap::new()
     };
 
+    let _output = profile::phase("output");
     print_diff(&diff_result, format, &context_map, &opts.detail);
     Ok(())
 }
@@ -0,0 +1,73 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+use crate::output::write_jsonl;
+use crate::{profile, progress, scanner};
+
+use super::filter::{FilterOptions, ItemFilter};
+use super::{is_path_excluded, path_prefix};
+
+pub struct ExportOptions {
+    pub tag: Vec<String>,
+    pub author: Option<String>,
+    pub path: Vec<String>,
+    pub output: Option<PathBuf>,
+}
+
+/// Write every TODO as a JSON line while the tree is walked: each file's
+/// items go out as soon as the file is scanned, so memory does not grow
+/// with the repository. Bypasses the scan cache, which would hold every
+/// item at once.
+pub fn cmd_export(root: &Path, config: &Config, opts: ExportOptions) -> Result<()> {
+    let filters = FilterOptions {
+        tags: opts.tag,
+        author: opts.author,
+        mention: None,
+        path: opts.path,
+        priority: vec![],
+        priority_min: None,
+    };
+    let tag_styles = config.tag_styles();
+    let filter = ItemFilter::new(&filters, &tag_styles)?;
+
+    match opts.output {
+        Some(path) => {
+            let file = File::create(&path)
+                .with_context(|| format!("cannot create output file: {}", path.display()))?;
+            let write_error = format!("cannot write output file: {}", path.display());
+            stream_items(root, config, &filter, BufWriter::new(file), &write_error)
+        }
+        None => {
+            let stdout = io::stdout();
+            let writer = BufWriter::new(stdout.lock());
+            stream_items(root, config, &filter, writer, "cannot write to stdout")
+        }
+    }
+}
+
+/// Scan `root` and write the items `filter` keeps to `out`, one file at a
+/// time in walk order.
+fn stream_items(
+    root: &Path,
+    config: &Config,
+    filter: &ItemFilter,
+    mut out: impl Write,
+    write_error: &str,
+) -> Result<()> {
+    let prefix = path_prefix(root, config)?;
+    let _scan = profile::phase("walk+scan+output");
+    progress::track(|| {
+        scanner::scan_directory_each(root, config, |mut items| -> Result<()> {
+            for item in &mut items {
+                item.file = format!("{}{}", prefix, item.file);
+            }
+            items.retain(|item| !is_path_excluded(&item.file) && filter.matches(item));
+            write_jsonl(&items, &mut out).with_context(|| write_error.to_string())
+        })
+    })?;
+    out.flush().with_context(|| write_error.to_string())
+}
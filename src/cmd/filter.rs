@@ -1,49 +1,205 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
 use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 
-use crate::cli::PriorityFilter;
-use crate::model::{self, Tag, TodoItem};
+use crate::blame::compute_blame;
+use crate::cli::{PriorityFilter, SortBy};
+use crate::config::AuthorAliases;
+use crate::deadline::Deadline;
+use crate::model::{self, Tag, TagStyles, TodoItem};
+use crate::output;
+use crate::profile;
 
 pub struct FilterOptions {
     pub tags: Vec<String>,
     pub author: Option<String>,
-    pub path: Option<String>,
+    /// Handle that must appear in `mentions`, with or without the `@`
+    pub mention: Option<String>,
+    pub path: Vec<String>,
     pub priority: Vec<PriorityFilter>,
+    /// Least severe priority to keep; excludes `priority`
+    pub priority_min: Option<PriorityFilter>,
 }
 
-pub fn apply_filters(items: &mut Vec<TodoItem>, filters: &FilterOptions) -> Result<()> {
-    // Apply tag filter
-    if !filters.tags.is_empty() {
-        let filter_tags: Vec<Tag> = filters
-            .tags
-            .iter()
-            .filter_map(|s| s.parse::<Tag>().ok())
-            .collect();
-        items.retain(|item| filter_tags.contains(&item.tag));
+/// Apply `filters` to `items`. Tag names resolve through `tag_styles`, so
+/// `[custom_tags]` names are accepted.
+pub fn apply_filters(
+    items: &mut Vec<TodoItem>,
+    filters: &FilterOptions,
+    tag_styles: &TagStyles,
+) -> Result<()> {
+    let filter = ItemFilter::new(filters, tag_styles)?;
+    items.retain(|item| filter.matches(item));
+    Ok(())
+}
+
+/// `FilterOptions` resolved once, to test items one at a time (e.g. while
+/// streaming) without recompiling the path globs.
+pub struct ItemFilter<'a> {
+    tags: Option<Vec<Tag>>,
+    priorities: Option<Vec<model::Priority>>,
+    priority_min: Option<model::Priority>,
+    author: Option<&'a str>,
+    mention: Option<&'a str>,
+    paths: Option<GlobSet>,
+}
+
+impl<'a> ItemFilter<'a> {
+    pub fn new(filters: &'a FilterOptions, tag_styles: &TagStyles) -> Result<Self> {
+        Ok(ItemFilter {
+            tags: (!filters.tags.is_empty()).then(|| {
+                filters
+                    .tags
+                    .iter()
+                    .filter_map(|s| tag_styles.parse(s))
+                    .collect()
+            }),
+            priorities: (!filters.priority.is_empty())
+                .then(|| filters.priority.iter().map(|p| p.to_priority()).collect()),
+            priority_min: filters.priority_min.as_ref().map(|p| p.to_priority()),
+            author: filters.author.as_deref(),
+            // Handles compare case-insensitively
+            mention: filters
+                .mention
+                .as_deref()
+                .map(|m| m.strip_prefix('@').unwrap_or(m)),
+            paths: path_matcher(&filters.path)?,
+        })
     }
 
-    // Apply priority filter
-    if !filters.priority.is_empty() {
-        let priorities: Vec<model::Priority> =
-            filters.priority.iter().map(|p| p.to_priority()).collect();
-        items.retain(|item| priorities.contains(&item.priority));
+    pub fn matches(&self, item: &TodoItem) -> bool {
+        self.tags
+            .as_ref()
+            .is_none_or(|tags| tags.contains(&item.tag))
+            && self
+                .priorities
+                .as_ref()
+                .is_none_or(|priorities| priorities.contains(&item.priority))
+            && self.priority_min.is_none_or(|min| item.priority >= min)
+            && self
+                .author
+                .is_none_or(|author| item.author.as_deref() == Some(author))
+            && self
+                .mention
+                .is_none_or(|handle| item.mentions.iter().any(|m| m.eq_ignore_ascii_case(handle)))
+            && self
+                .paths
+                .as_ref()
+                .is_none_or(|globs| globs.is_match(&item.file))
     }
+}
 
-    // Apply author filter
-    if let Some(ref author) = filters.author {
-        items.retain(|item| item.author.as_deref() == Some(author.as_str()));
+/// Compile repeated `--path` globs into one set that matches a file when any
+/// glob does. `None` when no globs were given, meaning every file matches.
+pub fn path_matcher(patterns: &[String]) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
     }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            Glob::new(pattern).with_context(|| format!("invalid glob pattern '{}'", pattern))?,
+        );
+    }
+    Ok(Some(builder.build().context("invalid glob pattern")?))
+}
 
-    // Apply path filter
-    if let Some(ref pattern) = filters.path {
-        let glob = globset::Glob::new(pattern)
-            .context("invalid glob pattern")?
-            .compile_matcher();
-        items.retain(|item| glob.is_match(&item.file));
+/// Sort items for `list` and `search`. `SortBy::Age` runs git blame on the
+/// items' files; see `sort_by_age`.
+pub fn apply_sort(
+    items: &mut [TodoItem],
+    sort: &SortBy,
+    root: &Path,
+    tag_styles: &TagStyles,
+) -> Result<()> {
+    match sort {
+        SortBy::File => items.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line))),
+        SortBy::Tag => items.sort_by(|a, b| {
+            tag_styles
+                .severity(&a.tag)
+                .cmp(&tag_styles.severity(&b.tag))
+                .reverse()
+                .then(a.file.cmp(&b.file))
+                .then(a.line.cmp(&b.line))
+        }),
+        SortBy::Priority => items.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.file.cmp(&b.file))
+                .then(a.line.cmp(&b.line))
+        }),
+        SortBy::Age => {
+            if !output::is_quiet() {
+                eprintln!("note: running git blame to sort by age");
+            }
+            let ages = blame_ages(items, root)?;
+            sort_by_age(items, |item| {
+                ages.get(&(item.file.clone(), item.line)).copied()
+            });
+        }
     }
+    Ok(())
+}
+
+/// Git blame age in days of each item's line, keyed by file and line. Items
+/// git cannot blame are missing from the map.
+pub fn blame_ages(items: &[TodoItem], root: &Path) -> Result<HashMap<(String, usize), u64>> {
+    let scan = model::ScanResult {
+        items: items.to_vec(),
+        ignored_items: vec![],
+        files_scanned: 0,
+        scanned_files: vec![],
+    };
+    let blame = profile::time("blame", || {
+        compute_blame(&scan, root, u64::MAX, &AuthorAliases::default())
+    })?;
+    Ok(blame
+        .entries
+        .into_iter()
+        .map(|e| ((e.item.file, e.item.line), e.blame.age_days))
+        .collect())
+}
 
+/// Keep items whose line git blame dates on or after `since`
+/// (`list --since-date`). Items git cannot blame, such as those in
+/// untracked files, are dropped.
+pub fn retain_blamed_since(items: &mut Vec<TodoItem>, since: &Deadline, root: &Path) -> Result<()> {
+    let scan = model::ScanResult {
+        items: items.clone(),
+        ignored_items: vec![],
+        files_scanned: 0,
+        scanned_files: vec![],
+    };
+    let blame = profile::time("blame", || {
+        compute_blame(&scan, root, u64::MAX, &AuthorAliases::default())
+    })?;
+    let since = since.to_string();
+    let recent: HashSet<(&str, usize)> = blame
+        .entries
+        .iter()
+        .filter(|e| !e.blame.date.is_empty() && e.blame.date >= since)
+        .map(|e| (e.item.file.as_str(), e.item.line))
+        .collect();
+    items.retain(|item| recent.contains(&(item.file.as_str(), item.line)));
     Ok(())
 }
 
+/// Oldest first. Items without an age (files not tracked by git) go last,
+/// in file/line order.
+fn sort_by_age(items: &mut [TodoItem], age_of: impl Fn(&TodoItem) -> Option<u64>) {
+    items.sort_by(|a, b| {
+        let (age_a, age_b) = (age_of(a), age_of(b));
+        age_b
+            .is_some()
+            .cmp(&age_a.is_some())
+            .then(age_b.cmp(&age_a))
+            .then(a.file.cmp(&b.file))
+            .then(a.line.cmp(&b.line))
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,10 +228,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec!["TODO".to_string()],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].tag, Tag::Todo);
     }
@@ -90,15 +248,37 @@ mod tests {
         let filters = FilterOptions {
             tags: vec!["TODO".to_string(), "HACK".to_string()],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 2);
         assert_eq!(items[0].tag, Tag::Todo);
         assert_eq!(items[1].tag, Tag::Hack);
     }
 
+    #[test]
+    fn filter_by_mention_ignores_at_and_case() {
+        let mut items = vec![
+            make_filter_item("a.rs", Tag::Todo, Priority::Normal, None),
+            make_filter_item("b.rs", Tag::Todo, Priority::Normal, Some("bob")),
+        ];
+        items[0].mentions = vec!["Bob".to_string()];
+        let filters = FilterOptions {
+            tags: vec![],
+            author: None,
+            mention: Some("@bob".to_string()),
+            path: vec![],
+            priority: vec![],
+            priority_min: None,
+        };
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].file, "a.rs");
+    }
+
     #[test]
     fn filter_by_priority() {
         let mut items = vec![
@@ -109,10 +289,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![PriorityFilter::High],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].priority, Priority::High);
     }
@@ -127,10 +309,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: Some("alice".to_string()),
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].author.as_deref(), Some("alice"));
     }
@@ -145,14 +329,41 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: None,
-            path: Some("src/*.rs".to_string()),
+            mention: None,
+            path: vec!["src/*.rs".to_string()],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 2);
         assert!(items.iter().all(|i| i.file.starts_with("src/")));
     }
 
+    #[test]
+    fn filter_by_several_paths_keeps_union() {
+        let mut items = vec![
+            make_filter_item("src/main.rs", Tag::Todo, Priority::Normal, None),
+            make_filter_item("lib/util.rs", Tag::Todo, Priority::Normal, None),
+            make_filter_item("tests/test.rs", Tag::Todo, Priority::Normal, None),
+        ];
+        let filters = FilterOptions {
+            tags: vec![],
+            author: None,
+            mention: None,
+            path: vec!["src/**".to_string(), "lib/**".to_string()],
+            priority: vec![],
+            priority_min: None,
+        };
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
+        let files: Vec<&str> = items.iter().map(|i| i.file.as_str()).collect();
+        assert_eq!(files, vec!["src/main.rs", "lib/util.rs"]);
+    }
+
+    #[test]
+    fn path_matcher_empty_matches_everything() {
+        assert!(path_matcher(&[]).unwrap().is_none());
+    }
+
     #[test]
     fn filter_combined() {
         let mut items = vec![
@@ -164,10 +375,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec!["TODO".to_string()],
             author: Some("alice".to_string()),
-            path: Some("src/**".to_string()),
+            mention: None,
+            path: vec!["src/**".to_string()],
             priority: vec![PriorityFilter::High],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].file, "src/main.rs");
     }
@@ -181,10 +394,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 2);
     }
 
@@ -198,10 +413,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![PriorityFilter::Normal],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].priority, Priority::Normal);
     }
@@ -212,10 +429,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: None,
-            path: Some("[invalid".to_string()),
+            mention: None,
+            path: vec!["[invalid".to_string()],
             priority: vec![],
+            priority_min: None,
         };
-        assert!(apply_filters(&mut items, &filters).is_err());
+        assert!(apply_filters(&mut items, &filters, &TagStyles::default()).is_err());
     }
 
     #[test]
@@ -228,14 +447,36 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![PriorityFilter::High, PriorityFilter::Urgent],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 2);
         assert!(items.iter().all(|i| i.priority != Priority::Normal));
     }
 
+    #[test]
+    fn filter_by_priority_min_keeps_more_severe() {
+        let mut items = vec![
+            make_filter_item("a.rs", Tag::Todo, Priority::Normal, None),
+            make_filter_item("b.rs", Tag::Todo, Priority::High, None),
+            make_filter_item("c.rs", Tag::Todo, Priority::Urgent, None),
+        ];
+        let filters = FilterOptions {
+            tags: vec![],
+            author: None,
+            mention: None,
+            path: vec![],
+            priority: vec![],
+            priority_min: Some(PriorityFilter::High),
+        };
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
+        let files: Vec<&str> = items.iter().map(|i| i.file.as_str()).collect();
+        assert_eq!(files, vec!["b.rs", "c.rs"]);
+    }
+
     #[test]
     fn filter_by_path_no_matches() {
         let mut items = vec![
@@ -245,10 +486,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: None,
-            path: Some("tests/**".to_string()),
+            mention: None,
+            path: vec!["tests/**".to_string()],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert!(items.is_empty());
     }
 
@@ -261,10 +504,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec!["INVALID".to_string()],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert!(items.is_empty());
     }
 
@@ -277,10 +522,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec![],
             author: Some("charlie".to_string()),
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert!(items.is_empty());
     }
 
@@ -294,10 +541,12 @@ mod tests {
         let filters = FilterOptions {
             tags: vec!["TODO".to_string()],
             author: None,
-            path: None,
+            mention: None,
+            path: vec![],
             priority: vec![],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 3);
         assert_eq!(items[0].file, "c.rs");
         assert_eq!(items[1].file, "a.rs");
@@ -315,10 +564,43 @@ mod tests {
         let filters = FilterOptions {
             tags: vec!["BUG".to_string()],
             author: Some("alice".to_string()),
-            path: Some("src/**".to_string()),
+            mention: None,
+            path: vec!["src/**".to_string()],
             priority: vec![PriorityFilter::Urgent],
+            priority_min: None,
         };
-        apply_filters(&mut items, &filters).unwrap();
+        apply_filters(&mut items, &filters, &TagStyles::default()).unwrap();
         assert_eq!(items.len(), 1);
     }
+
+    #[test]
+    fn sort_by_age_oldest_first_untracked_last() {
+        let mut items = vec![
+            make_item("untracked.rs", 1, Tag::Todo, "x"),
+            make_item("a.rs", 2, Tag::Todo, "new"),
+            make_item("a.rs", 1, Tag::Todo, "old"),
+            make_item("b.rs", 1, Tag::Todo, "middle"),
+        ];
+        sort_by_age(&mut items, |item| match item.message.as_str() {
+            "old" => Some(400),
+            "middle" => Some(30),
+            "new" => Some(0),
+            _ => None,
+        });
+        let order: Vec<&str> = items.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(order, vec!["old", "middle", "new", "x"]);
+    }
+
+    #[test]
+    fn sort_by_age_outside_git_keeps_file_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut items = vec![
+            make_item("b.rs", 1, Tag::Todo, "b"),
+            make_item("a.rs", 5, Tag::Todo, "a5"),
+            make_item("a.rs", 2, Tag::Todo, "a2"),
+        ];
+        apply_sort(&mut items, &SortBy::Age, dir.path(), &TagStyles::default()).unwrap();
+        let order: Vec<&str> = items.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(order, vec!["a2", "a5", "b"]);
+    }
 }
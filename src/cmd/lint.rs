@@ -1,14 +1,14 @@
 use std::path::Path;
-use std::process;
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 
 use crate::cli::Format;
 use crate::config::Config;
 use crate::lint::{run_lint, LintOverrides};
 use crate::output::print_lint;
+use crate::profile;
 
-use super::do_scan;
+use super::{do_scan, file_base};
 
 pub fn cmd_lint(
     root: &Path,
@@ -16,16 +16,20 @@ pub fn cmd_lint(
     format: &Format,
     overrides: LintOverrides,
     no_cache: bool,
-) -> Result<()> {
+) -> Result<bool> {
+    if let Some(density) = overrides.max_density {
+        ensure!(
+            (0.0..=1.0).contains(&density),
+            "--max-density must be a fraction between 0 and 1, got {}",
+            density
+        );
+    }
     let scan = do_scan(root, config, no_cache)?;
-    let result = run_lint(&scan, config, &overrides, root);
-    let passed = result.passed;
+    let base_dir = file_base(root, config)?;
+    let result = run_lint(&scan, config, &overrides, &base_dir);
 
+    let _output = profile::phase("output");
     print_lint(&result, format);
 
-    if !passed {
-        process::exit(1);
-    }
-
-    Ok(())
+    Ok(result.passed)
 }
@@ -1,27 +1,58 @@
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
-use crate::cli::{DetailLevel, Format, GroupBy, PriorityFilter, SortBy};
+use crate::blame::parse_since_date;
+use crate::cli::{DetailLevel, Format, GroupBy, ListField, PriorityFilter, SortBy};
 use crate::config::Config;
-use crate::context::collect_context_map;
-use crate::output::print_list;
+use crate::context::{collect_context_map, ContextMode};
+use crate::deadline;
+use crate::editor;
+use crate::git::modified_files;
+use crate::model::{DedupeResult, FilesWithoutTodosResult};
+use crate::output::{
+    self, print_count, print_dedupe, print_files_without_todos, print_list, print_list_table,
+};
+use crate::profile;
+use crate::stats::{compute_counts, dedupe_items, files_without_todos};
 
-use super::do_scan;
-use super::filter::{apply_filters, FilterOptions};
+use crate::output::template::ItemExtras;
+
+use super::filter::{
+    apply_filters, apply_sort, blame_ages, path_matcher, retain_blamed_since, FilterOptions,
+};
+use super::{do_scan, file_base, parse_template, path_prefix, print_templated, scan_given_paths};
 
 pub struct ListOptions {
+    pub paths: Vec<PathBuf>,
     pub tag: Vec<String>,
     pub sort: SortBy,
     pub group_by: GroupBy,
     pub priority: Vec<PriorityFilter>,
+    pub priority_min: Option<PriorityFilter>,
     pub author: Option<String>,
-    pub path: Option<String>,
+    pub mention: Option<String>,
+    pub path: Vec<String>,
     pub limit: Option<usize>,
     pub context: Option<usize>,
     pub show_ignored: bool,
     pub detail: DetailLevel,
+    pub count_only: bool,
+    pub invert_match: bool,
+    pub dedupe: bool,
+    pub modified_only: bool,
+    pub since_date: Option<String>,
+    pub fields: Vec<ListField>,
+    pub template: Option<String>,
+    /// Print a flat aligned table instead of grouped lines
+    pub table: bool,
+    /// Open the matching TODOs in an editor instead of listing them
+    pub open: bool,
+    /// Like `open`, after choosing TODOs from a menu
+    pub pick: bool,
+    pub editor: Option<String>,
+    pub context_mode: ContextMode,
 }
 
 pub fn cmd_list(
@@ -31,7 +62,32 @@ pub fn cmd_list(
     opts: ListOptions,
     no_cache: bool,
 ) -> Result<()> {
-    let mut result = do_scan(root, config, no_cache)?;
+    let since_date = opts
+        .since_date
+        .as_deref()
+        .map(|window| parse_since_date(window, &deadline::today()))
+        .transpose()?;
+    let template = parse_template(opts.template.as_deref(), format)?;
+    anyhow::ensure!(
+        !opts.table || matches!(format, Format::Text),
+        "--table is a text layout and cannot be combined with --format"
+    );
+    let editor = if opts.open || opts.pick {
+        let editor = editor::resolve_editor(opts.editor);
+        if editor.is_none() && !output::is_quiet() {
+            eprintln!("warning: $EDITOR is not set and no --editor was given; listing instead");
+        }
+        editor
+    } else {
+        None
+    };
+
+    let mut result = if opts.paths.is_empty() {
+        do_scan(root, config, no_cache)?
+    } else {
+        scan_given_paths(root, config, &opts.paths)?
+    };
+    let base_dir = file_base(root, config)?;
 
     let ignored_count = result.ignored_items.len();
 
@@ -40,30 +96,72 @@ pub fn cmd_list(
         &FilterOptions {
             tags: opts.tag,
             author: opts.author,
-            path: opts.path,
+            mention: opts.mention,
+            path: opts.path.clone(),
             priority: opts.priority,
+            priority_min: opts.priority_min,
         },
+        &config.tag_styles(),
+    )?;
+
+    if opts.modified_only {
+        let prefix = path_prefix(root, config)?;
+        let modified = modified_files(root).context("--modified-only requires a git repository")?;
+        result.items.retain(|item| {
+            modified.contains(item.file.strip_prefix(&prefix).unwrap_or(&item.file))
+        });
+    }
+
+    if let Some(since) = since_date {
+        retain_blamed_since(&mut result.items, &since, &base_dir)?;
+    }
+
+    if opts.count_only {
+        let _output = profile::phase("output");
+        print_count(&compute_counts(&result.items), format);
+        return Ok(());
+    }
+
+    if opts.invert_match {
+        let mut files = files_without_todos(&result.scanned_files, &result.items);
+        if let Some(globs) = path_matcher(&opts.path)? {
+            files.retain(|f| globs.is_match(f));
+        }
+        if let Some(n) = opts.limit {
+            files.truncate(n);
+        }
+        let _output = profile::phase("output");
+        print_files_without_todos(
+            &FilesWithoutTodosResult {
+                files_without_todos: files,
+            },
+            format,
+        );
+        return Ok(());
+    }
+
+    apply_sort(
+        &mut result.items,
+        &opts.sort,
+        &base_dir,
+        &config.tag_styles(),
     )?;
 
-    // Apply sort
-    match opts.sort {
-        SortBy::File => result
-            .items
-            .sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line))),
-        SortBy::Tag => result.items.sort_by(|a, b| {
-            a.tag
-                .severity()
-                .cmp(&b.tag.severity())
-                .reverse()
-                .then(a.file.cmp(&b.file))
-                .then(a.line.cmp(&b.line))
-        }),
-        SortBy::Priority => result.items.sort_by(|a, b| {
-            b.priority
-                .cmp(&a.priority)
-                .then(a.file.cmp(&b.file))
-                .then(a.line.cmp(&b.line))
-        }),
+    if opts.dedupe {
+        let mut items = dedupe_items(&result.items);
+        if let Some(n) = opts.limit {
+            items.truncate(n);
+        }
+        let _output = profile::phase("output");
+        print_dedupe(
+            &DedupeResult {
+                total_items: result.items.len(),
+                items,
+            },
+            format,
+            &opts.detail,
+        );
+        return Ok(());
     }
 
     // Apply limit
@@ -71,14 +169,52 @@ pub fn cmd_list(
         result.items.truncate(n);
     }
 
+    if let Some(editor) = editor {
+        let items = if opts.pick {
+            editor::pick_items(&result.items)?
+        } else {
+            result.items.iter().collect()
+        };
+        return editor::open_items(&editor, &items, &base_dir);
+    }
+
+    if let Some(template) = template {
+        let ages = if template.uses_age_days() {
+            blame_ages(&result.items, &base_dir)?
+        } else {
+            HashMap::new()
+        };
+        return print_templated(
+            &template,
+            result.items.iter().map(|item| {
+                let extras = ItemExtras {
+                    age_days: ages.get(&(item.file.clone(), item.line)).copied(),
+                    status: None,
+                };
+                (item, extras)
+            }),
+        );
+    }
+
+    if opts.table {
+        let _output = profile::phase("output");
+        print_list_table(&result, ignored_count, &opts.detail);
+        return Ok(());
+    }
+
     let context_map = if let Some(n) = opts.context {
-        collect_context_map(root, &result.items, n)
-    } else if opts.detail == DetailLevel::Full {
-        collect_context_map(root, &result.items, 3)
+        profile::time("context", || {
+            collect_context_map(&base_dir, &result.items, n, opts.context_mode)
+        })
+    } else if opts.detail == DetailLevel::Full || opts.context_mode == ContextMode::Function {
+        profile::time("context", || {
+            collect_context_map(&base_dir, &result.items, 3, opts.context_mode)
+        })
     } else {
         HashMap::new()
     };
 
+    let _output = profile::phase("output");
     print_list(
         &result,
         format,
@@ -87,6 +223,7 @@ pub fn cmd_list(
         ignored_count,
         opts.show_ignored,
         &opts.detail,
+        &opts.fields,
     );
     Ok(())
 }
@@ -1,9 +1,11 @@
+mod age;
 mod blame;
 mod brief;
 mod check;
 mod clean;
 mod context;
 mod diff;
+mod export;
 mod filter;
 mod lint;
 mod list;
@@ -14,48 +16,217 @@ mod stats;
 mod tasks;
 mod workspace;
 
+pub use self::age::cmd_age;
 pub use self::blame::{cmd_blame, BlameOptions};
 pub use self::brief::cmd_brief;
 pub use self::check::{cmd_check, cmd_workspace_check};
-pub use self::clean::cmd_clean;
+pub use self::clean::{cmd_clean, CleanOptions, FixMode};
 pub use self::context::cmd_context;
 pub use self::diff::{cmd_diff, DiffOptions};
+pub use self::export::{cmd_export, ExportOptions};
 pub use self::lint::cmd_lint;
 pub use self::list::{cmd_list, ListOptions};
 pub use self::relate::{cmd_relate, RelateOptions};
 pub use self::report::cmd_report;
 pub use self::search::{cmd_search, SearchOptions};
-pub use self::stats::cmd_stats;
+pub use self::stats::{cmd_stats, cmd_stats_compare};
 pub use self::tasks::{cmd_tasks, TasksOptions};
 pub use self::workspace::cmd_workspace_list;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use globset::GlobSet;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use crate::cache;
-use crate::config::Config;
+use crate::cli::Format;
+use crate::config::{Config, PathBase};
+use crate::deadline;
+use crate::diff::compute_diff;
+use crate::git::git_command;
 use crate::model;
+use crate::output;
+use crate::output::template::{ItemExtras, ItemTemplate};
+use crate::profile;
+use crate::progress;
 use crate::scanner;
 use crate::workspace as ws;
 
+static EXCLUDE_PATHS: OnceLock<GlobSet> = OnceLock::new();
+
+/// Apply `--exclude-path`: later scans and diffs drop items in files matching
+/// any of `patterns`. Only results are filtered, so the scan cache stays
+/// valid across runs with different exclusions.
+pub fn set_exclude_paths(patterns: &[String]) -> Result<()> {
+    if let Some(globs) = filter::path_matcher(patterns).context("invalid --exclude-path")? {
+        let _ = EXCLUDE_PATHS.set(globs);
+    }
+    Ok(())
+}
+
+pub(crate) fn is_path_excluded(file: &str) -> bool {
+    EXCLUDE_PATHS
+        .get()
+        .is_some_and(|globs| globs.is_match(file))
+}
+
+pub(crate) fn drop_excluded_items(result: &mut model::ScanResult) {
+    if EXCLUDE_PATHS.get().is_some() {
+        result.items.retain(|item| !is_path_excluded(&item.file));
+        result
+            .ignored_items
+            .retain(|item| !is_path_excluded(&item.file));
+    }
+}
+
+/// Drop `--exclude-path` matches from a diff, on the base ref's side too,
+/// so excluded files don't show up as removed TODOs.
+pub(crate) fn drop_excluded_entries(diff: &mut model::DiffResult) {
+    if EXCLUDE_PATHS.get().is_some() {
+        diff.drop_entries(|item| is_path_excluded(&item.file));
+        diff.recount(&[]);
+    }
+}
+
+/// Diff a `do_scan` result against `base_ref`, minus `--exclude-path` matches.
+pub(crate) fn diff_since(
+    scan: &model::ScanResult,
+    base_ref: &str,
+    base_dir: &Path,
+    config: &Config,
+) -> Result<model::DiffResult> {
+    let mut diff = profile::time("diff", || compute_diff(scan, base_ref, base_dir, config))?;
+    drop_excluded_entries(&mut diff);
+    Ok(diff)
+}
+
 /// Perform a directory scan, optionally using cache for performance.
+///
+/// File paths in the result are relative to `file_base(root, config)`.
 pub(crate) fn do_scan(root: &Path, config: &Config, no_cache: bool) -> Result<model::ScanResult> {
-    if no_cache {
-        return scanner::scan_directory(root, config);
+    let mut result = scan_root_relative(root, config, no_cache)?;
+    scanner::rebase_paths(&mut result, &path_prefix(root, config)?);
+    drop_excluded_items(&mut result);
+    Ok(result)
+}
+
+/// Scan only `paths` (relative to the current directory), warning about and
+/// skipping those that do not exist. Bypasses the cache; file paths are
+/// re-based onto `file_base` like `do_scan`.
+pub(crate) fn scan_given_paths(
+    root: &Path,
+    config: &Config,
+    paths: &[PathBuf],
+) -> Result<model::ScanResult> {
+    let (existing, missing): (Vec<PathBuf>, Vec<PathBuf>) =
+        paths.iter().cloned().partition(|p| p.exists());
+    if !output::is_quiet() {
+        for path in &missing {
+            eprintln!(
+                "warning: {}: no such file or directory, skipping",
+                path.display()
+            );
+        }
     }
+    let mut result = profile::time("walk+scan", || {
+        progress::track(|| scanner::scan_paths(root, &existing, config))
+    })?;
+    scanner::rebase_paths(&mut result, &path_prefix(root, config)?);
+    drop_excluded_items(&mut result);
+    Ok(result)
+}
 
-    let config_hash = cache::ScanCache::config_hash(config);
+/// Prefix that re-bases scan-root-relative paths onto `file_base`: empty
+/// unless `path_base = "git-root"` and `root` is below the git top-level.
+pub(crate) fn path_prefix(root: &Path, config: &Config) -> Result<String> {
+    match config.path_base() {
+        PathBase::Root => Ok(String::new()),
+        PathBase::GitRoot => {
+            let prefix = git_command(&["rev-parse", "--show-prefix"], root)
+                .context("path_base = \"git-root\" requires a git repository")?;
+            Ok(prefix.trim_end_matches('\n').to_string())
+        }
+    }
+}
+
+/// Directory that item file paths from `do_scan` are relative to: the scan
+/// root, or the git top-level with `path_base = "git-root"`. Blame, context
+/// and diff lookups must resolve item paths against this directory.
+pub(crate) fn file_base(root: &Path, config: &Config) -> Result<PathBuf> {
+    match config.path_base() {
+        PathBase::Root => Ok(root.to_path_buf()),
+        PathBase::GitRoot => {
+            let toplevel = git_command(&["rev-parse", "--show-toplevel"], root)
+                .context("path_base = \"git-root\" requires a git repository")?;
+            Ok(PathBuf::from(toplevel.trim_end_matches('\n')))
+        }
+    }
+}
 
-    let mut scan_cache = cache::ScanCache::load(root)
-        .filter(|c| c.config_hash == config_hash)
-        .unwrap_or_else(|| cache::ScanCache::new(config_hash));
+/// Compile a `--template` before any scanning so mistakes fail fast.
+pub(crate) fn parse_template(
+    source: Option<&str>,
+    format: &Format,
+) -> Result<Option<ItemTemplate>> {
+    let Some(source) = source else {
+        return Ok(None);
+    };
+    anyhow::ensure!(
+        matches!(format, Format::Text),
+        "--template replaces text output and cannot be combined with --format"
+    );
+    ItemTemplate::parse(source).map(Some)
+}
 
-    let cached_result = scanner::scan_directory_cached(root, config, &mut scan_cache)?;
+/// Render every item with `template` and print the results in order.
+pub(crate) fn print_templated<'a>(
+    template: &ItemTemplate,
+    items: impl IntoIterator<Item = (&'a model::TodoItem, ItemExtras)>,
+) -> Result<()> {
+    let _output = profile::phase("output");
+    let mut out = String::new();
+    for (item, extras) in items {
+        out.push_str(&template.render(item, &extras)?);
+    }
+    print!("{out}");
+    Ok(())
+}
+
+/// `do_scan` without re-basing: file paths stay relative to `root`.
+pub(crate) fn scan_root_relative(
+    root: &Path,
+    config: &Config,
+    no_cache: bool,
+) -> Result<model::ScanResult> {
+    if no_cache {
+        return Ok(profile::time("walk+scan", || {
+            progress::track(|| scanner::scan_directory(root, config))
+        })?);
+    }
+
+    let mut scan_cache = profile::time("cache load", || cache::ScanCache::load_for(root, config));
+
+    let cached_result = profile::time("walk+scan", || {
+        progress::track(|| scanner::scan_directory_cached(root, config, &mut scan_cache))
+    })?;
 
     // Best-effort save; don't fail the scan if cache write fails
-    let _ = scan_cache.save(root);
+    let _ = profile::time("cache save", || {
+        scan_cache.save(root, config.cache_dir(root).as_deref())
+    });
+
+    // Cached relative deadlines were resolved on the day they were scanned
+    let mut result = cached_result.result;
+    let today = deadline::today();
+    for item in result
+        .items
+        .iter_mut()
+        .chain(result.ignored_items.iter_mut())
+    {
+        item.resolve_relative_deadline(&today);
+    }
 
-    Ok(cached_result.result)
+    Ok(result)
 }
 
 /// Resolve a `--package` flag to an absolute scan root path via workspace detection.
@@ -4,8 +4,8 @@ use anyhow::Result;
 
 use crate::cli::Format;
 use crate::config::Config;
-use crate::context::parse_location;
 use crate::output::print_relate;
+use crate::profile;
 use crate::relate;
 
 use super::do_scan;
@@ -15,6 +15,7 @@ pub struct RelateOptions {
     pub for_item: Option<String>,
     pub min_score: f64,
     pub proximity: usize,
+    pub min_cluster_size: usize,
 }
 
 pub fn cmd_relate(
@@ -28,15 +29,23 @@ pub fn cmd_relate(
     let mut result = relate::compute_relations(&scan, opts.min_score, opts.proximity);
 
     if let Some(ref location) = opts.for_item {
-        let (file, line) = parse_location(location)?;
+        let (file, line) = relate::resolve_target(&scan.items, location)?;
         result = relate::filter_for_item(result, &file, line);
     }
 
     if opts.cluster {
-        let clusters = relate::build_clusters(&result.relationships, &scan.items);
+        let clusters = relate::build_clusters(
+            &result.relationships,
+            &scan.items,
+            opts.min_cluster_size,
+            &config.tag_styles(),
+        );
+        let grouped: usize = clusters.iter().map(|c| c.members.len()).sum();
+        result.ungrouped_items = Some(scan.items.len() - grouped);
         result.clusters = Some(clusters);
     }
 
+    let _output = profile::phase("output");
     print_relate(&result, format);
     Ok(())
 }
@@ -5,26 +5,47 @@ use anyhow::Result;
 use crate::blame;
 use crate::config::Config;
 use crate::output::print_report;
+use crate::profile;
 use crate::report;
 
-use super::do_scan;
+use super::{do_scan, file_base};
 
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_report(
     root: &Path,
     config: &Config,
     output_path: &str,
     history_count: usize,
     stale_threshold_cli: Option<String>,
+    history_file: Option<&Path>,
+    include_resolved: bool,
     no_cache: bool,
 ) -> Result<()> {
     let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
 
     let threshold_str = stale_threshold_cli
         .or_else(|| config.blame.stale_threshold.clone())
         .unwrap_or_else(|| "365d".to_string());
     let stale_threshold = blame::parse_duration_days(&threshold_str)?;
 
-    let result = report::compute_report(&scan, root, config, history_count, stale_threshold)?;
+    let mut result = report::compute_report(
+        &scan,
+        &base_dir,
+        config,
+        history_count,
+        stale_threshold,
+        include_resolved,
+    )?;
+
+    if let Some(path) = history_file {
+        report::append_history(path, &report::HistorySnapshot::from_report(&result))?;
+        let snapshots = report::load_history(path)?;
+        let git_history = std::mem::take(&mut result.history);
+        result.history = report::merge_history(git_history, &snapshots);
+    }
+
+    let _output = profile::phase("output");
     print_report(&result, output_path)?;
     Ok(())
 }
@@ -3,25 +3,37 @@ use std::path::Path;
 
 use anyhow::Result;
 
-use crate::cli::{DetailLevel, Format, GroupBy, SortBy};
+use crate::cli::{DetailLevel, Format, GroupBy, PriorityFilter, SortBy};
 use crate::config::Config;
-use crate::context::collect_context_map;
-use crate::output::print_search;
-use crate::search::search_items;
+use crate::context::{collect_context_map, ContextMode};
+use crate::output::template::ItemExtras;
+use crate::output::{print_search, print_search_history};
+use crate::profile;
+use crate::search::{search_history, search_items};
 
-use super::do_scan;
-use super::filter::{apply_filters, FilterOptions};
+use super::filter::{apply_filters, apply_sort, blame_ages, FilterOptions};
+use super::{
+    do_scan, file_base, is_path_excluded, parse_template, path_prefix, print_templated,
+    scan_root_relative,
+};
 
 pub struct SearchOptions {
     pub query: String,
     pub exact: bool,
     pub context: Option<usize>,
     pub author: Option<String>,
+    pub mention: Option<String>,
+    pub priority_min: Option<PriorityFilter>,
     pub tag: Vec<String>,
-    pub path: Option<String>,
+    pub path: Vec<String>,
     pub sort: SortBy,
     pub group_by: GroupBy,
     pub detail: DetailLevel,
+    pub context_mode: ContextMode,
+    /// Search git history instead of the working tree, reading at most this
+    /// many commits
+    pub history: Option<usize>,
+    pub template: Option<String>,
 }
 
 pub fn cmd_search(
@@ -31,7 +43,13 @@ pub fn cmd_search(
     opts: SearchOptions,
     no_cache: bool,
 ) -> Result<()> {
+    if let Some(max_commits) = opts.history {
+        return cmd_search_history(root, config, format, opts, max_commits, no_cache);
+    }
+    let template = parse_template(opts.template.as_deref(), format)?;
+
     let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
     let mut result = search_items(&scan, &opts.query, opts.exact);
 
     apply_filters(
@@ -39,30 +57,37 @@ pub fn cmd_search(
         &FilterOptions {
             tags: opts.tag,
             author: opts.author,
+            mention: opts.mention,
             path: opts.path,
             priority: vec![],
+            priority_min: opts.priority_min,
         },
+        &config.tag_styles(),
     )?;
 
-    // Apply sort
-    match opts.sort {
-        SortBy::File => result
-            .items
-            .sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line))),
-        SortBy::Tag => result.items.sort_by(|a, b| {
-            a.tag
-                .severity()
-                .cmp(&b.tag.severity())
-                .reverse()
-                .then(a.file.cmp(&b.file))
-                .then(a.line.cmp(&b.line))
-        }),
-        SortBy::Priority => result.items.sort_by(|a, b| {
-            b.priority
-                .cmp(&a.priority)
-                .then(a.file.cmp(&b.file))
-                .then(a.line.cmp(&b.line))
-        }),
+    apply_sort(
+        &mut result.items,
+        &opts.sort,
+        &base_dir,
+        &config.tag_styles(),
+    )?;
+
+    if let Some(template) = template {
+        let ages = if template.uses_age_days() {
+            blame_ages(&result.items, &base_dir)?
+        } else {
+            HashMap::new()
+        };
+        return print_templated(
+            &template,
+            result.items.iter().map(|item| {
+                let extras = ItemExtras {
+                    age_days: ages.get(&(item.file.clone(), item.line)).copied(),
+                    status: None,
+                };
+                (item, extras)
+            }),
+        );
     }
 
     // Recompute counts after filtering
@@ -75,13 +100,60 @@ pub fn cmd_search(
         .len();
 
     let context_map = if let Some(n) = opts.context {
-        collect_context_map(root, &result.items, n)
-    } else if opts.detail == DetailLevel::Full {
-        collect_context_map(root, &result.items, 3)
+        profile::time("context", || {
+            collect_context_map(&base_dir, &result.items, n, opts.context_mode)
+        })
+    } else if opts.detail == DetailLevel::Full || opts.context_mode == ContextMode::Function {
+        profile::time("context", || {
+            collect_context_map(&base_dir, &result.items, 3, opts.context_mode)
+        })
     } else {
         HashMap::new()
     };
 
+    let _output = profile::phase("output");
     print_search(&result, format, &opts.group_by, &context_map, &opts.detail);
     Ok(())
 }
+
+fn cmd_search_history(
+    root: &Path,
+    config: &Config,
+    format: &Format,
+    opts: SearchOptions,
+    max_commits: usize,
+    no_cache: bool,
+) -> Result<()> {
+    // Git paths are read relative to `root`, so compare against an un-rebased scan
+    let current = scan_root_relative(root, config, no_cache)?;
+    let mut result = search_history(root, config, &current, &opts.query, opts.exact, max_commits)?;
+
+    let prefix = path_prefix(root, config)?;
+    for m in &mut result.matches {
+        m.item.file = format!("{}{}", prefix, m.item.file);
+    }
+    result.matches.retain(|m| !is_path_excluded(&m.item.file));
+
+    let mut items: Vec<_> = result.matches.iter().map(|m| m.item.clone()).collect();
+    apply_filters(
+        &mut items,
+        &FilterOptions {
+            tags: opts.tag,
+            author: opts.author,
+            mention: opts.mention,
+            path: opts.path,
+            priority: vec![],
+            priority_min: opts.priority_min,
+        },
+        &config.tag_styles(),
+    )?;
+    let kept: HashSet<String> = items.iter().map(|i| i.stable_id()).collect();
+    result
+        .matches
+        .retain(|m| kept.contains(&m.item.stable_id()));
+    result.match_count = result.matches.len();
+
+    let _output = profile::phase("output");
+    print_search_history(&result, format, &opts.detail);
+    Ok(())
+}
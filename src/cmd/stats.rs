@@ -1,31 +1,98 @@
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
-use crate::cli::Format;
+use crate::blame::diff_blame_authors;
+use crate::cli::{Format, StatsBy};
 use crate::config::Config;
-use crate::diff::compute_diff;
-use crate::output::print_stats;
-use crate::stats::compute_stats;
+use crate::diff::scan_ref;
+use crate::output::{print_stats, print_stats_comparison};
+use crate::profile;
+use crate::report::compute_history;
+use crate::stats::{compare_stats, compute_author_churn, compute_stats, truncate_stats};
 
-use super::do_scan;
+use super::{diff_since, do_scan, file_base};
 
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_stats(
     root: &Path,
     config: &Config,
     format: &Format,
     since: Option<String>,
+    by: Option<StatsBy>,
+    top: usize,
+    history: usize,
     no_cache: bool,
 ) -> Result<()> {
     let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
 
     let diff = if let Some(ref base_ref) = since {
-        Some(compute_diff(&scan, base_ref, root, config)?)
+        Some(diff_since(&scan, base_ref, &base_dir, config)?)
     } else {
         None
     };
 
-    let result = compute_stats(&scan, diff.as_ref());
+    let mut result = compute_stats(&scan, diff.as_ref());
+    if top > 0 {
+        truncate_stats(&mut result, top);
+    }
+
+    if let (Some(StatsBy::Author), Some(diff)) = (by, diff.as_ref()) {
+        let authors = profile::time("blame", || {
+            diff_blame_authors(diff, &base_dir, &config.author_aliases())
+        });
+        result.author_churn = Some(compute_author_churn(diff, &authors));
+    }
+    if history > 0 {
+        let points = profile::time("history", || compute_history(root, config, history))
+            .context("cannot read git history for --history")?;
+        result.history = Some(points);
+    }
+    let _output = profile::phase("output");
     print_stats(&result, format);
     Ok(())
 }
+
+/// `stats BASE..HEAD`: scan both refs' trees and compare their breakdowns.
+pub fn cmd_stats_compare(
+    root: &Path,
+    config: &Config,
+    format: &Format,
+    range: &str,
+    top: usize,
+) -> Result<()> {
+    let (base_ref, head_ref) = match range.split_once("..") {
+        Some((base, head)) if !base.is_empty() && !head.is_empty() => {
+            anyhow::ensure!(
+                !head.starts_with('.'),
+                "three-dot ranges are not supported; use BASE..HEAD"
+            );
+            (base, head)
+        }
+        _ => anyhow::bail!("invalid range '{}': expected BASE..HEAD", range),
+    };
+    anyhow::ensure!(
+        !matches!(format, Format::Html),
+        "--format html is not supported when comparing BASE..HEAD"
+    );
+    let base_dir = file_base(root, config)?;
+
+    let base = compute_stats(
+        &profile::time("diff", || scan_ref(base_ref, &base_dir, config))?,
+        None,
+    );
+    let head = compute_stats(
+        &profile::time("diff", || scan_ref(head_ref, &base_dir, config))?,
+        None,
+    );
+    let mut result = compare_stats(base, head, base_ref, head_ref);
+    if top > 0 {
+        result.authors.truncate(top);
+        truncate_stats(&mut result.base, top);
+        truncate_stats(&mut result.head, top);
+    }
+    let _output = profile::phase("output");
+    print_stats_comparison(&result, format);
+    Ok(())
+}
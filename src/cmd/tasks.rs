@@ -4,14 +4,14 @@ use anyhow::{Context, Result};
 
 use crate::cli::{Format, PriorityFilter};
 use crate::config::Config;
-use crate::context::collect_context_map;
-use crate::diff::compute_diff;
+use crate::context::{collect_context_map, ContextMode};
 use crate::model;
-use crate::output::print_tasks;
+use crate::output::{gh_issue, print_tasks};
+use crate::profile;
 use crate::tasks;
 
-use super::do_scan;
 use super::filter::{apply_filters, FilterOptions};
+use super::{diff_since, do_scan, file_base};
 
 pub struct TasksOptions {
     pub tag: Vec<String>,
@@ -20,8 +20,10 @@ pub struct TasksOptions {
     pub dry_run: bool,
     pub since: Option<String>,
     pub priority: Vec<PriorityFilter>,
+    pub priority_min: Option<PriorityFilter>,
     pub author: Option<String>,
-    pub path: Option<String>,
+    pub path: Vec<String>,
+    pub context_mode: ContextMode,
 }
 
 pub fn cmd_tasks(
@@ -32,10 +34,11 @@ pub fn cmd_tasks(
     no_cache: bool,
 ) -> Result<()> {
     let scan = do_scan(root, config, no_cache)?;
+    let base_dir = file_base(root, config)?;
 
     let mut items = if let Some(ref base_ref) = opts.since {
         // Only TODOs added since the git ref
-        let diff = compute_diff(&scan, base_ref, root, config)?;
+        let diff = diff_since(&scan, base_ref, &base_dir, config)?;
         diff.entries
             .into_iter()
             .filter(|e| matches!(e.status, model::DiffStatus::Added))
@@ -50,16 +53,21 @@ pub fn cmd_tasks(
         &FilterOptions {
             tags: opts.tag,
             author: opts.author,
+            mention: None,
             path: opts.path,
             priority: opts.priority,
+            priority_min: opts.priority_min,
         },
+        &config.tag_styles(),
     )?;
 
     // Sort by priority
-    tasks::sort_by_priority(&mut items);
+    tasks::sort_by_priority(&mut items, &config.tag_styles());
 
     // Collect context
-    let context_map = collect_context_map(root, &items, opts.context);
+    let context_map = profile::time("context", || {
+        collect_context_map(&base_dir, &items, opts.context, opts.context_mode)
+    });
 
     // Build tasks
     let claude_tasks = tasks::build_tasks(&items, &context_map);
@@ -72,11 +80,19 @@ pub fn cmd_tasks(
                 .with_context(|| format!("cannot create output directory: {}", dir.display()))?;
 
             for (i, task) in claude_tasks.iter().enumerate() {
-                let filename = format!("task-{:04}.json", i + 1);
+                let (filename, contents) = if matches!(format, Format::GhIssue) {
+                    let issue = gh_issue::issue_from_task(task);
+                    (
+                        format!("task-{:04}.md", i + 1),
+                        gh_issue::format_issue_file(&issue),
+                    )
+                } else {
+                    let json =
+                        serde_json::to_string_pretty(task).context("failed to serialize task")?;
+                    (format!("task-{:04}.json", i + 1), json)
+                };
                 let path = dir.join(&filename);
-                let json =
-                    serde_json::to_string_pretty(task).context("failed to serialize task")?;
-                std::fs::write(&path, json)
+                std::fs::write(&path, contents)
                     .with_context(|| format!("cannot write task file: {}", path.display()))?;
             }
 
@@ -85,6 +101,7 @@ pub fn cmd_tasks(
                 total,
                 output_dir: Some(dir.to_string_lossy().to_string()),
             };
+            let _output = profile::phase("output");
             print_tasks(&result, format);
         }
         _ => {
@@ -93,6 +110,7 @@ pub fn cmd_tasks(
                 total,
                 output_dir: None,
             };
+            let _output = profile::phase("output");
             print_tasks(&result, format);
         }
     }
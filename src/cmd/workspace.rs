@@ -6,6 +6,7 @@ use crate::cli::Format;
 use crate::config::Config;
 use crate::model;
 use crate::output::print_workspace_list;
+use crate::profile;
 use crate::workspace;
 
 use super::do_scan;
@@ -51,6 +52,7 @@ pub fn cmd_workspace_list(
         total_todos,
     };
 
+    let _output = profile::phase("output");
     print_workspace_list(&result, format, &ws.kind);
     Ok(())
 }
@@ -1,19 +1,29 @@
-use anyhow::{Context, Result};
 use schemars::JsonSchema;
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
+use crate::error::{Error, Result};
+use crate::model::{Priority, TagStyle, TagStyles};
+use crate::scanner::MAX_FILE_SIZE;
+
 /// Configuration for todo-scan TODO tracking tool
 #[derive(Debug, Deserialize, JsonSchema)]
-#[serde(default)]
-#[schemars(deny_unknown_fields, title = "todo-scan Configuration")]
+#[serde(default, deny_unknown_fields)]
+#[schemars(title = "todo-scan Configuration")]
 pub struct Config {
     /// Tags to scan for (e.g., TODO, FIXME, HACK)
     pub tags: Vec<String>,
-    /// Directory names to skip during scanning
+    /// Project-specific tags (e.g., OPTIMIZE, SECURITY) with severity and color
+    pub custom_tags: BTreeMap<String, CustomTagConfig>,
+    /// Directories to skip during scanning: names or globs (e.g., "vendor", "build-*", "**/generated")
     pub exclude_dirs: Vec<String>,
     /// Regex patterns; matching file paths are excluded
     pub exclude_patterns: Vec<String>,
+    /// Keywords that raise a TODO's priority when no `!`/`!!` marker is present
+    pub priority: PriorityConfig,
+    /// Issue reference extraction settings
+    pub issue: IssueConfig,
     /// CI gate check settings
     pub check: CheckConfig,
     /// Git blame analysis settings
@@ -24,12 +34,123 @@ pub struct Config {
     pub clean: CleanConfig,
     /// Workspace/monorepo settings
     pub workspace: WorkspaceConfig,
+    /// File walking settings
+    pub scan: ScanConfig,
+    /// Author identity settings
+    pub authors: AuthorsConfig,
+    /// Scan cache settings
+    pub cache: CacheConfig,
+}
+
+/// Author identity settings
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthorsConfig {
+    /// Canonical author names mapped to the other names and emails they
+    /// appear under in `TODO(...)` and git blame (matched case-insensitively)
+    pub aliases: BTreeMap<String, Vec<String>>,
+}
+
+/// Scan cache settings
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Directory holding scan caches, one subdirectory per project; relative
+    /// paths are resolved against the scan root (default: the OS cache
+    /// directory, e.g. ~/.cache/todo-scan)
+    pub path: Option<String>,
+}
+
+/// Case-insensitive lookup from an author alias to its canonical name,
+/// built from `[authors.aliases]`.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorAliases(HashMap<String, String>);
+
+impl AuthorAliases {
+    /// Canonical name for `name`, or `name` itself when it is not an alias.
+    pub fn canonical<'a>(&'a self, name: &'a str) -> &'a str {
+        self.0
+            .get(&name.trim().to_lowercase())
+            .map_or(name, String::as_str)
+    }
+
+    /// Canonical name for a blame identity: the author name, else the email.
+    pub fn canonical_identity<'a>(&'a self, name: &'a str, email: &str) -> &'a str {
+        self.0
+            .get(&name.trim().to_lowercase())
+            .or_else(|| self.0.get(&email.trim().to_lowercase()))
+            .map_or(name, String::as_str)
+    }
+
+    /// Owned variant of `canonical`, for author filters given on the command line.
+    pub fn canonicalize(&self, name: String) -> String {
+        match self.0.get(&name.trim().to_lowercase()) {
+            Some(canonical) => canonical.clone(),
+            None => name,
+        }
+    }
+}
+
+/// File walking settings
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanConfig {
+    /// Skip files larger than this size (e.g., "10MiB", "512KiB", "1GiB";
+    /// default: 10MiB)
+    pub max_file_size: Option<String>,
+    /// Honor .gitignore, .ignore, and git exclude files (default: true)
+    pub respect_gitignore: Option<bool>,
+    /// Directory that emitted file paths are relative to (default: "root")
+    pub path_base: Option<PathBase>,
+    /// Also recognize Javadoc/JSDoc style tags such as `@todo` (default: false)
+    pub at_tags: Option<bool>,
+    /// Worker threads used to walk and read files; 0 picks a count from the
+    /// available CPUs (default: 0)
+    pub threads: Option<usize>,
+    /// Parse escalation markers such as `TODO->FIXME: ...`, recording the
+    /// tag after the arrow (default: false)
+    pub escalation: Option<bool>,
+    /// Record `@handle` mentions in TODO messages, such as `@bob` in
+    /// `TODO: @bob please review` (default: false)
+    pub mentions: Option<bool>,
+    /// Count the `column` of each TODO in UTF-16 code units, as LSP clients
+    /// expect, instead of bytes (default: false)
+    pub utf16_columns: Option<bool>,
+    /// Append indented comment continuation lines to the TODO message
+    /// (default: false)
+    pub multiline: Option<bool>,
+    /// Comment prefixes recognized anywhere before a tag, replacing the
+    /// built-in lists of every file type (e.g., ["//", "REM "])
+    pub comment_prefixes: Option<Vec<String>>,
+    /// Prefixes recognized only at line start, replacing the built-in lists
+    /// of every file type (default for unrecognized files: ["*"])
+    pub line_start_prefixes: Option<Vec<String>>,
+}
+
+/// Directory that emitted file paths are relative to
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum PathBase {
+    /// The scan root (`--root`, the current directory, or `--package`)
+    #[default]
+    Root,
+    /// The git top-level, so paths and IDs match wherever todo-scan runs
+    GitRoot,
+}
+
+/// Definition of a project-specific tag
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct CustomTagConfig {
+    /// Sort severity, where NOTE=0 and BUG=5 (default: 1, same as TODO)
+    pub severity: Option<u8>,
+    /// Terminal color name (e.g., "cyan", "bright magenta")
+    pub color: Option<String>,
 }
 
 /// CI gate check settings
 #[derive(Debug, Default, Deserialize, JsonSchema)]
-#[serde(default)]
-#[schemars(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct CheckConfig {
     /// Maximum total TODOs allowed
     pub max: Option<usize>,
@@ -39,12 +160,53 @@ pub struct CheckConfig {
     pub block_tags: Vec<String>,
     /// Fail if any TODOs have expired deadlines
     pub expired: Option<bool>,
+    /// Warn about deadlines falling within this window (e.g., "7d")
+    pub due_soon: Option<String>,
+    /// Fail instead of warn on deadlines within the due_soon window
+    pub due_soon_fails: Option<bool>,
+    /// Maximum TODOs allowed in any single file
+    pub file_max: Option<usize>,
+    /// Per-glob overrides of file_max; the most specific matching glob wins
+    pub file_limits: Vec<FileLimit>,
+    /// Tags whose TODOs must carry a deadline (e.g., ["BUG", "FIXME"])
+    pub require_deadline: Vec<String>,
+    /// Fail on any TODO at this priority or above ("high" or "urgent")
+    pub block_priority: Option<Priority>,
+}
+
+/// A `[[check.file_limits]]` entry
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FileLimit {
+    /// Glob matched against file paths relative to the root
+    pub path: String,
+    /// Maximum TODOs allowed in each matching file
+    pub max: usize,
+}
+
+/// Keyword-based priority inference
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct PriorityConfig {
+    /// Words (case-insensitive) in the message that imply high priority
+    pub high: Vec<String>,
+    /// Words (case-insensitive) in the message that imply urgent priority
+    pub urgent: Vec<String>,
+}
+
+/// Issue reference extraction
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct IssueConfig {
+    /// Regexes tried in order against the TODO message; the first capture group
+    /// (or the whole match) becomes the issue reference. Replaces the built-in
+    /// `#123` and `ABC-123` patterns when set
+    pub patterns: Vec<String>,
 }
 
 /// Git blame analysis settings
 #[derive(Debug, Default, Deserialize, JsonSchema)]
-#[serde(default)]
-#[schemars(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct BlameConfig {
     /// Duration threshold for marking TODOs as stale (e.g., "180d")
     pub stale_threshold: Option<String>,
@@ -52,8 +214,7 @@ pub struct BlameConfig {
 
 /// Lint rule settings for TODO comment formatting
 #[derive(Debug, Default, Deserialize, JsonSchema)]
-#[serde(default)]
-#[schemars(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct LintConfig {
     /// Reject TODOs with empty message (default: true)
     pub no_bare_tags: Option<bool>,
@@ -67,12 +228,19 @@ pub struct LintConfig {
     pub uppercase_tag: Option<bool>,
     /// Enforce colon after tag (default: true)
     pub require_colon: Option<bool>,
+    /// Flag files where more than this fraction of lines are TODOs (e.g., 0.05)
+    pub max_density: Option<f64>,
+    /// Flag TODOs past their deadline that still carry the tag before an
+    /// escalation marker (default: true)
+    pub escalate_overdue: Option<bool>,
+    /// Flag a TODO whose message repeats an earlier one in the same file
+    /// (default: false)
+    pub no_local_duplicates: Option<bool>,
 }
 
 /// Clean detection settings for stale issues and duplicates
 #[derive(Debug, Default, Deserialize, JsonSchema)]
-#[serde(default)]
-#[schemars(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct CleanConfig {
     /// Enable stale issue detection (default: true)
     pub stale_issues: Option<bool>,
@@ -80,23 +248,41 @@ pub struct CleanConfig {
     pub duplicates: Option<bool>,
     /// Only flag issues closed longer than this duration (e.g., "30d")
     pub since: Option<String>,
+    /// Issue tracker that `#123` refs point to (default: github)
+    pub provider: Option<IssueProvider>,
+    /// GitLab project path (e.g., "group/repo") or numeric ID
+    /// (default: the project of the current git remote)
+    pub project: Option<String>,
+}
+
+/// Issue tracker queried for stale issue detection
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueProvider {
+    /// GitHub issues via the `gh` CLI
+    #[default]
+    Github,
+    /// GitLab issues via the `glab` CLI
+    Gitlab,
 }
 
 /// Workspace/monorepo settings
 #[derive(Debug, Default, Deserialize, JsonSchema)]
-#[serde(default)]
-#[schemars(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct WorkspaceConfig {
     /// Enable automatic workspace detection (default: true)
     pub auto_detect: Option<bool>,
+    /// Maximum TODOs across all packages combined (`check --workspace`)
+    pub total_max: Option<usize>,
+    /// Maximum TODOs for each package whose own config sets no `max`
+    pub default_max: Option<usize>,
     /// Per-package check configuration
     pub packages: std::collections::HashMap<String, PackageCheckConfig>,
 }
 
 /// Per-package check configuration
 #[derive(Debug, Default, Deserialize, JsonSchema)]
-#[serde(default)]
-#[schemars(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct PackageCheckConfig {
     /// Maximum total TODOs allowed for this package
     pub max: Option<usize>,
@@ -115,53 +301,329 @@ impl Default for Config {
                 "BUG".into(),
                 "NOTE".into(),
             ],
+            custom_tags: BTreeMap::new(),
             exclude_dirs: vec![],
             exclude_patterns: vec![],
+            priority: PriorityConfig::default(),
+            issue: IssueConfig::default(),
             check: CheckConfig::default(),
             blame: BlameConfig::default(),
             lint: LintConfig::default(),
             clean: CleanConfig::default(),
             workspace: WorkspaceConfig::default(),
+            scan: ScanConfig::default(),
+            authors: AuthorsConfig::default(),
+            cache: CacheConfig::default(),
         }
     }
 }
 
 impl Config {
+    /// All tag names to scan for: `tags` followed by any `[custom_tags]`
+    /// entries not already listed.
+    pub fn all_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self.tags.iter().map(String::as_str).collect();
+        for name in self.custom_tags.keys() {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(name)) {
+                tags.push(name);
+            }
+        }
+        tags
+    }
+
     /// Build regex pattern from configured tags.
     /// Each tag is escaped to prevent regex injection from config values.
+    /// Groups: tag, escalation target after `->`, parenthesized content,
+    /// `!`/`!!` priority marker, message.
     pub fn tags_pattern(&self) -> String {
         let tags = self
-            .tags
-            .iter()
-            .map(|t| regex::escape(t))
+            .all_tags()
+            .into_iter()
+            .map(regex::escape)
             .collect::<Vec<_>>()
             .join("|");
-        format!(r"(?i)\b({tags})\b(?:\(([^)]+)\))?:?\s*(!{{1,2}})?\s*(.*)$")
+        format!(r"(?i)\b({tags})\b(?:->([a-z][\w-]*))?(?:\(([^)]+)\))?:?\s*(!{{1,2}})?\s*(.*)$")
+    }
+
+    /// Styles for `[custom_tags]`, used for tag parsing, sorting, and coloring.
+    pub fn tag_styles(&self) -> TagStyles {
+        let styles: HashMap<String, TagStyle> = self
+            .custom_tags
+            .iter()
+            .map(|(name, def)| {
+                let style = TagStyle {
+                    severity: def.severity.unwrap_or(1),
+                    color: def.color.clone(),
+                };
+                (name.clone(), style)
+            })
+            .collect();
+        TagStyles::new(styles)
+    }
+
+    /// Parse config TOML and check the values serde cannot, such as regexes.
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Config = toml::from_str(content).map_err(|e| {
+            unknown_key_error(content, &e).unwrap_or_else(|| Error::config("invalid TOML", e))
+        })?;
+        for pattern in &config.exclude_patterns {
+            regex::Regex::new(pattern).map_err(|e| {
+                Error::config("invalid exclude_patterns entry", Error::regex(pattern, e))
+            })?;
+        }
+        for pattern in &config.issue.patterns {
+            regex::Regex::new(pattern)
+                .map_err(|e| Error::config("invalid [issue] pattern", Error::regex(pattern, e)))?;
+        }
+        if let Some(size) = &config.scan.max_file_size {
+            parse_byte_size(size).map_err(|e| Error::config("invalid [scan] max_file_size", e))?;
+        }
+        if let Some(window) = &config.check.due_soon {
+            crate::blame::parse_duration_days(window)
+                .map_err(|e| Error::config("invalid [check] due_soon", e))?;
+        }
+        for (field, prefixes) in [
+            ("comment_prefixes", &config.scan.comment_prefixes),
+            ("line_start_prefixes", &config.scan.line_start_prefixes),
+        ] {
+            if prefixes.iter().flatten().any(|p| p.trim().is_empty()) {
+                return Err(invalid_config(format!(
+                    "invalid [scan] {}: prefixes must not be empty",
+                    field
+                )));
+            }
+        }
+        if let Some(density) = config.lint.max_density {
+            if !(0.0..=1.0).contains(&density) {
+                return Err(invalid_config(format!(
+                    "invalid [lint] max_density: {} (expected a fraction between 0 and 1)",
+                    density
+                )));
+            }
+        }
+        let mut seen_aliases: HashMap<String, &str> = HashMap::new();
+        for (canonical, aliases) in &config.authors.aliases {
+            for alias in std::iter::once(canonical).chain(aliases) {
+                let key = alias.trim().to_lowercase();
+                if let Some(other) = seen_aliases.insert(key, canonical) {
+                    if other != canonical {
+                        return Err(invalid_config(format!(
+                            "invalid [authors.aliases]: {:?} is listed under both {:?} and {:?}",
+                            alias, other, canonical
+                        )));
+                    }
+                }
+            }
+        }
+        for limit in &config.check.file_limits {
+            globset::Glob::new(&limit.path).map_err(|e| {
+                Error::config(
+                    "invalid [[check.file_limits]] path",
+                    Error::Glob {
+                        pattern: limit.path.clone(),
+                        source: e,
+                    },
+                )
+            })?;
+        }
+        Ok(config)
+    }
+
+    /// Files larger than this many bytes are skipped while scanning.
+    pub fn max_file_size(&self) -> u64 {
+        // Validated by `Config::parse`
+        self.scan
+            .max_file_size
+            .as_deref()
+            .and_then(|s| parse_byte_size(s).ok())
+            .unwrap_or(MAX_FILE_SIZE)
+    }
+
+    /// Whether scanning skips files ignored by git (`--no-ignore` turns this off).
+    pub fn respect_gitignore(&self) -> bool {
+        self.scan.respect_gitignore.unwrap_or(true)
+    }
+
+    /// Whether `@todo`-style doc tags count as tags.
+    pub fn at_tags(&self) -> bool {
+        self.scan.at_tags.unwrap_or(false)
+    }
+
+    /// Whether `TODO->FIXME` escalation markers are parsed.
+    pub fn escalation(&self) -> bool {
+        self.scan.escalation.unwrap_or(false)
+    }
+
+    /// Whether `@handle` mentions are extracted from messages.
+    pub fn mentions(&self) -> bool {
+        self.scan.mentions.unwrap_or(false)
+    }
+
+    /// Whether item columns count UTF-16 code units rather than bytes.
+    pub fn utf16_columns(&self) -> bool {
+        self.scan.utf16_columns.unwrap_or(false)
+    }
+
+    /// Whether continuation comment lines are joined into the TODO message.
+    pub fn multiline(&self) -> bool {
+        self.scan.multiline.unwrap_or(false)
+    }
+
+    /// Walker thread count, where 0 means automatic.
+    pub fn threads(&self) -> usize {
+        self.scan.threads.unwrap_or(0)
+    }
+
+    /// Alias lookup for `[authors.aliases]`. Each canonical name also maps to
+    /// itself so differently-cased spellings of it collapse too.
+    pub fn author_aliases(&self) -> AuthorAliases {
+        let mut map = HashMap::new();
+        for (canonical, aliases) in &self.authors.aliases {
+            for alias in std::iter::once(canonical).chain(aliases) {
+                map.insert(alias.trim().to_lowercase(), canonical.clone());
+            }
+        }
+        AuthorAliases(map)
+    }
+
+    /// Scan cache directory from `[cache] path` (or `--cache-dir`), resolved
+    /// against `root`; `None` uses the OS cache directory.
+    pub fn cache_dir(&self, root: &Path) -> Option<PathBuf> {
+        self.cache.path.as_deref().map(|p| root.join(p))
+    }
+
+    /// Directory that emitted file paths are relative to (`--path-base` overrides).
+    pub fn path_base(&self) -> PathBase {
+        self.scan.path_base.unwrap_or_default()
     }
 
     /// Load config from .todo-scan.toml, searching up from the given directory
     pub fn load(start_dir: &Path) -> Result<Self> {
-        if let Some(path) = find_config_file(start_dir) {
-            let content = std::fs::read_to_string(&path)
-                .with_context(|| format!("Failed to read config: {}", path.display()))?;
-            let config: Config = toml::from_str(&content)
-                .with_context(|| format!("Failed to parse config: {}", path.display()))?;
-            Ok(config)
-        } else {
-            Ok(Config::default())
+        match find_config_file(start_dir) {
+            Some(path) => Config::load_file(&path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Load config from an explicit file (`--config`).
+    pub fn load_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::config(format!("Failed to read config: {}", path.display()), e))?;
+        Config::parse(&content)
+            .map_err(|e| Error::config(format!("Failed to parse config: {}", path.display()), e))
+    }
+
+    /// Load config TOML piped to standard input (`--config -`).
+    pub fn load_stdin() -> Result<Self> {
+        let content = std::io::read_to_string(std::io::stdin())
+            .map_err(|e| Error::config("Failed to read config from stdin", e))?;
+        Config::parse(&content).map_err(|e| Error::config("Failed to parse config from stdin", e))
+    }
+}
+
+/// A config value that is well-formed TOML but not acceptable.
+fn invalid_config(message: String) -> Error {
+    Error::Config {
+        message,
+        source: None,
+    }
+}
+
+/// A clearer error for a key no config section declares: where it is and,
+/// when one is close, the key that was probably meant. `None` for any other
+/// TOML error.
+fn unknown_key_error(content: &str, err: &toml::de::Error) -> Option<Error> {
+    // serde's wording: "unknown field `x`, expected one of `a`, `b`"
+    let rest = err.message().strip_prefix("unknown field `")?;
+    let (key, expected) = rest.split_once('`')?;
+    let candidates: Vec<&str> = expected.split('`').skip(1).step_by(2).collect();
+
+    let mut message = format!("unknown key `{}`", key);
+    if let Some(span) = err.span() {
+        let before = &content[..span.start.min(content.len())];
+        // Only headers on earlier lines; the key may itself be a table name
+        let earlier_lines = &before[..before.rfind('\n').map_or(0, |i| i + 1)];
+        let header = earlier_lines
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| line.starts_with('['));
+        if let Some(header) = header {
+            let header = header.split('#').next().unwrap_or(header).trim_end();
+            message.push_str(&format!(" in {}", header));
         }
+        message.push_str(&format!(" at line {}", before.matches('\n').count() + 1));
     }
+    match closest_name(key, &candidates) {
+        Some(name) => message.push_str(&format!(" (did you mean `{}`?)", name)),
+        None if !candidates.is_empty() => {
+            message.push_str(&format!(" (expected one of: {})", candidates.join(", ")))
+        }
+        None => {}
+    }
+    Some(invalid_config(message))
+}
+
+/// The candidate within a small edit distance of `name`, if any.
+fn closest_name<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let limit = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(name, c), *c))
+        .filter(|(d, _)| *d <= limit)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
 }
 
-/// Search for .todo-scan.toml from start_dir upward
-fn find_config_file(start_dir: &Path) -> Option<PathBuf> {
-    let mut dir = start_dir.to_path_buf();
+/// Levenshtein distance, counted in chars.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            current.push(substitute.min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+/// Parse a size like "10MiB", "512KiB", "1GiB", or a bare byte count.
+pub fn parse_byte_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let (numeric, multiplier) = [
+        ("GiB", 1 << 30),
+        ("MiB", 1 << 20),
+        ("KiB", 1 << 10),
+        ("B", 1),
+    ]
+    .iter()
+    .find_map(|(suffix, mult)| s.strip_suffix(suffix).map(|n| (n.trim_end(), *mult)))
+    .unwrap_or((s, 1u64));
+    numeric
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| Error::Invalid(format!("invalid size: {}", s)))
+}
+
+/// Search for .todo-scan.toml from start_dir upward, stopping at the git
+/// top-level (the first directory containing `.git`) or the filesystem root.
+pub fn find_config_file(start_dir: &Path) -> Option<PathBuf> {
+    // Canonicalize so relative roots like "." or "src" can walk past their
+    // first component
+    let mut dir = start_dir
+        .canonicalize()
+        .unwrap_or_else(|_| start_dir.to_path_buf());
     loop {
         let candidate = dir.join(".todo-scan.toml");
         if candidate.is_file() {
             return Some(candidate);
         }
-        if !dir.pop() {
+        // `.git` is a file in worktrees and submodules, so only check existence
+        if dir.join(".git").exists() || !dir.pop() {
             return None;
         }
     }
@@ -195,6 +657,39 @@ block_tags = ["BUG"]
         assert_eq!(config.check.block_tags, vec!["BUG"]);
     }
 
+    #[test]
+    fn test_author_aliases_lookup() {
+        let config = Config::parse(
+            r#"
+[authors.aliases]
+"Alice Smith" = ["alice", "alice@corp.com"]
+"#,
+        )
+        .unwrap();
+        let aliases = config.author_aliases();
+        assert_eq!(aliases.canonical("Alice"), "Alice Smith");
+        assert_eq!(aliases.canonical("alice smith"), "Alice Smith");
+        assert_eq!(aliases.canonical("bob"), "bob");
+        assert_eq!(
+            aliases.canonical_identity("A. Smith", "ALICE@corp.com"),
+            "Alice Smith"
+        );
+        assert_eq!(aliases.canonical_identity("Bob", "bob@corp.com"), "Bob");
+    }
+
+    #[test]
+    fn test_author_alias_under_two_names_is_rejected() {
+        let err = Config::parse(
+            r#"
+[authors.aliases]
+"Alice Smith" = ["al"]
+"Albert" = ["al"]
+"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("[authors.aliases]"));
+    }
+
     #[test]
     fn test_workspace_config_default() {
         let config = Config::default();
@@ -287,6 +782,41 @@ max = 10
         assert!(re.is_match("// HACK: test"));
     }
 
+    #[test]
+    fn test_custom_tags_from_toml() {
+        let toml_str = r#"
+[custom_tags.OPTIMIZE]
+severity = 2
+color = "cyan"
+
+[custom_tags.SECURITY]
+severity = 5
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.custom_tags.len(), 2);
+        assert_eq!(config.custom_tags["OPTIMIZE"].severity, Some(2));
+        assert_eq!(
+            config.custom_tags["OPTIMIZE"].color.as_deref(),
+            Some("cyan")
+        );
+        assert_eq!(config.custom_tags["SECURITY"].color, None);
+    }
+
+    #[test]
+    fn test_tags_pattern_includes_custom_tags() {
+        let config: Config = toml::from_str("[custom_tags.REVIEW]\nseverity = 2\n").unwrap();
+        let re = regex::Regex::new(&config.tags_pattern()).unwrap();
+        assert!(re.is_match("// REVIEW: check this"));
+        assert!(re.is_match("// TODO: still matched"));
+    }
+
+    #[test]
+    fn test_all_tags_skips_custom_tags_already_listed() {
+        let config: Config =
+            toml::from_str("tags = [\"TODO\"]\n[custom_tags.todo]\nseverity = 4\n").unwrap();
+        assert_eq!(config.all_tags(), vec!["TODO"]);
+    }
+
     // --- Config::load() tests ---
 
     #[test]
@@ -330,6 +860,240 @@ max = 10
         assert_eq!(config.tags, vec!["BUG"]);
     }
 
+    #[test]
+    fn test_load_stops_at_git_top_level() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".todo-scan.toml"), "tags = [\"BUG\"]\n").unwrap();
+        let repo = dir.path().join("repo");
+        let sub = repo.join("a").join("b");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::create_dir(repo.join(".git")).unwrap();
+
+        let config = Config::load(&sub).unwrap();
+        assert_eq!(config.tags, Config::default().tags);
+    }
+
+    #[test]
+    fn test_load_finds_config_at_git_top_level() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sub = dir.path().join("a").join("b");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(dir.path().join(".git"), "gitdir: ../.git/worktrees/x\n").unwrap();
+        std::fs::write(dir.path().join(".todo-scan.toml"), "tags = [\"HACK\"]\n").unwrap();
+
+        let config = Config::load(&sub).unwrap();
+        assert_eq!(config.tags, vec!["HACK"]);
+    }
+
+    #[test]
+    fn test_load_invalid_issue_pattern_returns_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join(".todo-scan.toml"),
+            "[issue]\npatterns = ['GH-(\\d+']\n",
+        )
+        .unwrap();
+        let err = Config::load(dir.path()).unwrap_err();
+        // The regex error is kept as the cause
+        let err = anyhow::Error::new(err);
+        assert!(format!("{:#}", err).contains(r"invalid [issue] pattern: invalid regex: GH-(\d+"));
+    }
+
+    #[test]
+    fn test_parse_unknown_key_suggests_nearest_field() {
+        let err = Config::parse("exclude_dir = [\"vendor\"]\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown key `exclude_dir` at line 1 (did you mean `exclude_dirs`?)"
+        );
+
+        let err = Config::parse("tags = [\"TODO\"]\n\n[scan] # walking\nthreds = 2\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown key `threds` in [scan] at line 4 (did you mean `threads`?)"
+        );
+    }
+
+    #[test]
+    fn test_parse_multiline_under_scan() {
+        let config = Config::parse("[scan]\nmultiline = true\n").unwrap();
+        assert!(config.multiline());
+        assert!(!Config::default().multiline());
+        assert!(Config::parse("multiline = true\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_key_without_close_match_lists_fields() {
+        let err = Config::parse("[blame]\nwhatever = 1\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown key `whatever` in [blame] at line 2 (expected one of: stale_threshold)"
+        );
+
+        let err = Config::parse("[scann]\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown key `scann` at line 1 (did you mean `scan`?)"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_exclude_pattern_returns_error() {
+        let err = Config::parse("exclude_patterns = [\"(\"]\n").unwrap_err();
+        let err = anyhow::Error::new(err);
+        assert!(
+            format!("{:#}", err).starts_with("invalid exclude_patterns entry: invalid regex: (")
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("threds", "threads"), 1);
+        assert_eq!(edit_distance("max", "mx"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(
+            closest_name("severty", &["severity", "color"]),
+            Some("severity")
+        );
+        assert_eq!(closest_name("foo", &["severity", "color"]), None);
+    }
+
+    #[test]
+    fn test_parse_check_file_limits() {
+        let config = Config::parse(
+            "[check]\nfile_max = 10\n\n[[check.file_limits]]\npath = \"src/legacy/**\"\nmax = 50\n",
+        )
+        .unwrap();
+        assert_eq!(config.check.file_max, Some(10));
+        assert_eq!(config.check.file_limits.len(), 1);
+        assert_eq!(config.check.file_limits[0].path, "src/legacy/**");
+        assert_eq!(config.check.file_limits[0].max, 50);
+    }
+
+    #[test]
+    fn test_parse_invalid_file_limit_glob_returns_error() {
+        let err = Config::parse("[[check.file_limits]]\npath = \"src/[\"\nmax = 1\n").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Config {
+                source: Some(ref source),
+                ..
+            } if matches!(source.downcast_ref::<Error>(), Some(Error::Glob { .. }))
+        ));
+        let err = anyhow::Error::new(err);
+        assert!(format!("{:#}", err)
+            .contains("invalid [[check.file_limits]] path: invalid glob pattern: src/["));
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("10MiB").unwrap(), 10 * 1024 * 1024);
+        assert_eq!(parse_byte_size("512KiB").unwrap(), 512 * 1024);
+        assert_eq!(parse_byte_size("2GiB").unwrap(), 2 << 30);
+        assert_eq!(parse_byte_size("100 KiB").unwrap(), 100 * 1024);
+        assert_eq!(parse_byte_size("300B").unwrap(), 300);
+        assert_eq!(parse_byte_size("4096").unwrap(), 4096);
+    }
+
+    #[test]
+    fn test_parse_byte_size_rejects_invalid() {
+        assert!(parse_byte_size("").is_err());
+        assert!(parse_byte_size("10MB").is_err());
+        assert!(parse_byte_size("-1KiB").is_err());
+        assert!(parse_byte_size("1.5MiB").is_err());
+        assert!(parse_byte_size("99999999999999GiB").is_err());
+    }
+
+    #[test]
+    fn test_max_file_size_default_and_override() {
+        assert_eq!(Config::default().max_file_size(), MAX_FILE_SIZE);
+        let config = Config::parse("[scan]\nmax_file_size = \"64KiB\"\n").unwrap();
+        assert_eq!(config.max_file_size(), 64 * 1024);
+    }
+
+    #[test]
+    fn test_path_base_default_and_override() {
+        assert_eq!(Config::default().path_base(), PathBase::Root);
+        let config = Config::parse("[scan]\npath_base = \"git-root\"\n").unwrap();
+        assert_eq!(config.path_base(), PathBase::GitRoot);
+    }
+
+    #[test]
+    fn test_cache_dir_resolves_against_root() {
+        let root = Path::new("/work/project");
+        assert_eq!(Config::default().cache_dir(root), None);
+        let config = Config::parse("[cache]\npath = \".cache\"\n").unwrap();
+        assert_eq!(config.cache_dir(root), Some(root.join(".cache")));
+        let config = Config::parse("[cache]\npath = \"/tmp/todo-scan\"\n").unwrap();
+        assert_eq!(
+            config.cache_dir(root),
+            Some(PathBuf::from("/tmp/todo-scan"))
+        );
+    }
+
+    #[test]
+    fn test_threads_default_and_override() {
+        assert_eq!(Config::default().threads(), 0);
+        let config = Config::parse("[scan]\nthreads = 2\n").unwrap();
+        assert_eq!(config.threads(), 2);
+    }
+
+    #[test]
+    fn test_parse_invalid_max_file_size_returns_error() {
+        let err = anyhow::Error::new(
+            Config::parse("[scan]\nmax_file_size = \"ten megs\"\n").unwrap_err(),
+        );
+        assert!(
+            format!("{:#}", err).contains("invalid [scan] max_file_size: invalid size: ten megs")
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_due_soon_returns_error() {
+        let err = anyhow::Error::new(Config::parse("[check]\ndue_soon = \"soon\"\n").unwrap_err());
+        assert!(format!("{:#}", err).contains("invalid [check] due_soon: invalid duration: soon"));
+    }
+
+    #[test]
+    fn test_parse_max_density_out_of_range_returns_error() {
+        let err = Config::parse("[lint]\nmax_density = 5.0\n").unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid [lint] max_density: 5"));
+        let config = Config::parse("[lint]\nmax_density = 0.05\n").unwrap();
+        assert_eq!(config.lint.max_density, Some(0.05));
+    }
+
+    #[test]
+    fn test_parse_comment_prefixes() {
+        let config = Config::parse(
+            "[scan]\ncomment_prefixes = [\"REM \", \"::\"]\nline_start_prefixes = []\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.scan.comment_prefixes,
+            Some(vec!["REM ".to_string(), "::".to_string()])
+        );
+        assert_eq!(config.scan.line_start_prefixes, Some(vec![]));
+        assert_eq!(Config::default().scan.comment_prefixes, None);
+    }
+
+    #[test]
+    fn test_parse_empty_comment_prefix_returns_error() {
+        let err = Config::parse("[scan]\ncomment_prefixes = [\"//\", \" \"]\n").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid [scan] comment_prefixes: prefixes must not be empty"));
+        let err = Config::parse("[scan]\nline_start_prefixes = [\"\"]\n").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid [scan] line_start_prefixes"));
+    }
+
+    #[test]
+    fn test_parse_issue_patterns() {
+        let config = Config::parse("[issue]\npatterns = ['(GH-\\d+)']\n").unwrap();
+        assert_eq!(config.issue.patterns, vec![r"(GH-\d+)"]);
+    }
+
     #[test]
     fn test_load_invalid_toml_returns_error() {
         let dir = tempfile::TempDir::new().unwrap();
@@ -550,6 +1314,19 @@ exclude_dirs = ["vendor"]
         assert!(config.clean.since.is_none());
     }
 
+    #[test]
+    fn test_priority_config_from_toml() {
+        let toml_str = r#"
+[priority]
+high = ["important"]
+urgent = ["URGENT", "asap"]
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.priority.high, vec!["important"]);
+        assert_eq!(config.priority.urgent, vec!["URGENT", "asap"]);
+        assert!(Config::default().priority.high.is_empty());
+    }
+
     #[test]
     fn test_full_config_from_toml() {
         let toml_str = r#"
@@ -1,20 +1,39 @@
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::path::Path;
 
 use anyhow::{Context, Result};
 
 use crate::model::TodoItem;
+use crate::scanner::read_text_file;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ContextLine {
     pub line_number: usize,
     pub content: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// How the lines around a TODO are chosen
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContextMode {
+    /// A fixed number of lines above and below
+    #[default]
+    Lines,
+    /// The enclosing (or immediately following) function or block
+    Function,
+}
+
+/// Upper bound on lines taken from each side of a TODO in function mode
+pub const MAX_FUNCTION_CONTEXT_LINES: usize = 200;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct ContextInfo {
     pub before: Vec<ContextLine>,
     pub after: Vec<ContextLine>,
+    /// Mode actually used; function mode falls back to lines for unknown
+    /// languages or when no enclosing block is found
+    pub mode: ContextMode,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -25,6 +44,7 @@ pub struct RichContext {
     pub todo_line: String,
     pub after: Vec<ContextLine>,
     pub related_todos: Vec<RelatedTodo>,
+    pub mode: ContextMode,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -41,6 +61,7 @@ pub fn extract_context(content: &str, target_line: usize, n: usize) -> ContextIn
         return ContextInfo {
             before: Vec::new(),
             after: Vec::new(),
+            mode: ContextMode::Lines,
         };
     }
 
@@ -52,6 +73,7 @@ pub fn extract_context(content: &str, target_line: usize, n: usize) -> ContextIn
         return ContextInfo {
             before: Vec::new(),
             after: Vec::new(),
+            mode: ContextMode::Lines,
         };
     }
 
@@ -71,7 +93,202 @@ pub fn extract_context(content: &str, target_line: usize, n: usize) -> ContextIn
         })
         .collect();
 
-    ContextInfo { before, after }
+    ContextInfo {
+        before,
+        after,
+        mode: ContextMode::Lines,
+    }
+}
+
+/// Languages whose blocks are delimited by braces or by indentation
+enum BlockSyntax {
+    Braces,
+    Indentation,
+}
+
+fn block_syntax(file: &str) -> Option<BlockSyntax> {
+    let ext = Path::new(file).extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "java" | "js" | "jsx" | "mjs" | "cjs"
+        | "ts" | "tsx" | "go" | "cs" | "swift" | "kt" | "kts" | "scala" | "php" | "dart" => {
+            Some(BlockSyntax::Braces)
+        }
+        "py" | "pyi" => Some(BlockSyntax::Indentation),
+        _ => None,
+    }
+}
+
+/// Extract context for a 1-based `target_line` using `mode`. Function mode
+/// falls back to `n` lines when the language is unknown or no block is found.
+pub fn extract_context_with_mode(
+    content: &str,
+    file: &str,
+    target_line: usize,
+    n: usize,
+    mode: ContextMode,
+) -> ContextInfo {
+    if mode == ContextMode::Function && target_line > 0 {
+        let lines: Vec<&str> = content.lines().collect();
+        let idx = target_line - 1;
+        let block = match (idx < lines.len(), block_syntax(file)) {
+            (true, Some(BlockSyntax::Braces)) => brace_block(&lines, idx),
+            (true, Some(BlockSyntax::Indentation)) => indent_block(&lines, idx),
+            _ => None,
+        };
+        if let Some((start, end)) = block {
+            let start = start.max(idx.saturating_sub(MAX_FUNCTION_CONTEXT_LINES));
+            let end = end.min(idx + MAX_FUNCTION_CONTEXT_LINES);
+            let to_line = |i: usize| ContextLine {
+                line_number: i + 1,
+                content: lines[i].to_string(),
+            };
+            return ContextInfo {
+                before: (start..idx).map(to_line).collect(),
+                after: (idx + 1..=end).map(to_line).collect(),
+                mode: ContextMode::Function,
+            };
+        }
+    }
+    extract_context(content, target_line, n)
+}
+
+/// Code part of a line, with any `//` comment removed.
+fn code_part(line: &str) -> &str {
+    line.find("//").map_or(line, |pos| &line[..pos])
+}
+
+fn is_comment_line(line: &str) -> bool {
+    let t = line.trim_start();
+    t.starts_with("//") || t.starts_with("/*") || t.starts_with('*') || t.starts_with('#')
+}
+
+/// Whether a block opener is a control-flow statement rather than a
+/// function, type, or module body.
+fn is_control_flow(line: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "if", "else", "for", "while", "loop", "match", "switch", "do", "try", "catch", "finally",
+    ];
+    let t = line.trim_start().trim_start_matches('}').trim_start();
+    let word = t
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("");
+    KEYWORDS.contains(&word)
+}
+
+/// 0-based index of the line closing the block opened on `open`.
+fn brace_block_end(lines: &[&str], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, line) in lines.iter().enumerate().skip(open) {
+        for c in code_part(line).chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.checked_sub(1)?;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Span `(start, end)` of the brace block documented by the TODO at `idx`
+/// (a definition right after the comment lines) or else the innermost
+/// non-control-flow block enclosing it.
+fn brace_block(lines: &[&str], idx: usize) -> Option<(usize, usize)> {
+    let next_code = (idx + 1..lines.len()).find(|&i| !is_comment_line(lines[i]));
+    if let Some(sig) = next_code.filter(|&i| !is_control_flow(lines[i])) {
+        // Signature lines up to the first `{`, stopping at a statement or block end
+        let open = (sig..lines.len().min(sig + 10))
+            .take_while(|&i| i == sig || !code_part(lines[i - 1]).trim_end().ends_with([';', '}']))
+            .find(|&i| code_part(lines[i]).contains('{'));
+        if let Some(open) = open {
+            if let Some(end) = brace_block_end(lines, open) {
+                return Some((idx, end));
+            }
+        }
+    }
+
+    let mut depth = 0usize;
+    for i in (0..idx).rev() {
+        for c in code_part(lines[i]).chars().rev() {
+            match c {
+                '}' => depth += 1,
+                '{' if depth == 0 => {
+                    // A lone `{` belongs to the signature on the line above
+                    let start = if lines[i].trim() == "{" && i > 0 {
+                        i - 1
+                    } else {
+                        i
+                    };
+                    if is_control_flow(lines[start]) {
+                        continue;
+                    }
+                    let end = brace_block_end(lines, i)?;
+                    return (end > idx).then_some((start, end));
+                }
+                '{' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_python_def(line: &str) -> bool {
+    let t = line.trim_start();
+    t.starts_with("def ") || t.starts_with("async def ") || t.starts_with("class ")
+}
+
+/// Span `(start, end)` of the Python `def`/`class` documented by the TODO at
+/// `idx` (the definition right after the comment lines) or else the one
+/// enclosing it.
+fn indent_block(lines: &[&str], idx: usize) -> Option<(usize, usize)> {
+    let todo_indent = indent_of(lines[idx]);
+    let next_code =
+        (idx + 1..lines.len()).find(|&i| !lines[i].trim().is_empty() && !is_comment_line(lines[i]));
+    let (start, header) = match next_code {
+        Some(i) if is_python_def(lines[i]) && indent_of(lines[i]) == todo_indent => (idx, i),
+        _ => {
+            // Walk out through each less-indented parent until one is a def
+            let mut limit = todo_indent;
+            let mut header = None;
+            for i in (0..idx).rev() {
+                let line = lines[i];
+                if line.trim().is_empty() || is_comment_line(line) || indent_of(line) >= limit {
+                    continue;
+                }
+                if is_python_def(line) {
+                    header = Some(i);
+                    break;
+                }
+                limit = indent_of(line);
+            }
+            let header = header?;
+            (header, header)
+        }
+    };
+
+    let header_indent = indent_of(lines[header]);
+    let mut end = header;
+    for (i, line) in lines.iter().enumerate().skip(header + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent_of(line) <= header_indent {
+            break;
+        }
+        end = i;
+    }
+    Some((start, end.max(idx)))
 }
 
 /// Read file and extract context around the given line.
@@ -81,10 +298,10 @@ pub fn read_file_context(
     file: &str,
     line: usize,
     n: usize,
+    mode: ContextMode,
 ) -> Result<(ContextInfo, String)> {
     let path = root.join(file);
-    let content =
-        std::fs::read_to_string(&path).with_context(|| format!("cannot read file: {}", file))?;
+    let content = read_text_file(&path).with_context(|| format!("cannot read file: {}", file))?;
 
     let lines: Vec<&str> = content.lines().collect();
     let todo_line = if line > 0 && line <= lines.len() {
@@ -93,7 +310,7 @@ pub fn read_file_context(
         String::new()
     };
 
-    let ctx = extract_context(&content, line, n);
+    let ctx = extract_context_with_mode(&content, file, line, n, mode);
     Ok((ctx, todo_line))
 }
 
@@ -103,12 +320,13 @@ pub fn build_rich_context(
     file: &str,
     line: usize,
     n: usize,
+    mode: ContextMode,
     todos_in_file: &[&TodoItem],
 ) -> Result<RichContext> {
-    let (ctx, todo_line) = read_file_context(root, file, line, n)?;
+    let (ctx, todo_line) = read_file_context(root, file, line, n, mode)?;
 
-    let window_start = line.saturating_sub(n);
-    let window_end = line + n;
+    let window_start = ctx.before.first().map_or(line, |l| l.line_number);
+    let window_end = ctx.after.last().map_or(line, |l| l.line_number);
 
     let related_todos: Vec<RelatedTodo> = todos_in_file
         .iter()
@@ -127,6 +345,7 @@ pub fn build_rich_context(
         todo_line,
         after: ctx.after,
         related_todos,
+        mode: ctx.mode,
     })
 }
 
@@ -135,6 +354,7 @@ pub fn collect_context_map(
     root: &Path,
     items: &[TodoItem],
     n: usize,
+    mode: ContextMode,
 ) -> std::collections::HashMap<String, ContextInfo> {
     use std::collections::HashMap;
 
@@ -144,10 +364,10 @@ pub fn collect_context_map(
     for item in items {
         let content = file_contents.entry(item.file.clone()).or_insert_with(|| {
             let path = root.join(&item.file);
-            std::fs::read_to_string(&path).unwrap_or_default()
+            read_text_file(&path).unwrap_or_default()
         });
 
-        let ctx = extract_context(content, item.line, n);
+        let ctx = extract_context_with_mode(content, &item.file, item.line, n, mode);
         let key = format!("{}:{}", item.file, item.line);
         context_map.insert(key, ctx);
     }
@@ -156,11 +376,12 @@ pub fn collect_context_map(
 }
 
 /// Resolve a location that may be a stable TODO ID or a `file:line` string.
-/// First tries to match against `item.id()` for all scanned items.
+/// First tries to match against `item.id()` or `item.stable_id()` for all
+/// scanned items.
 /// Falls back to `parse_location()` if no ID match is found.
 pub fn resolve_location(location: &str, items: &[TodoItem]) -> Result<(String, usize)> {
     for item in items {
-        if item.id() == location {
+        if item.id() == location || item.stable_id() == location {
             return Ok((item.file.clone(), item.line));
         }
     }
@@ -270,12 +491,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "src/main.rs".to_string(),
             line: 42,
+            column: 1,
             tag: crate::model::Tag::Todo,
             message: "fix this bug".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }];
         let (file, line) = resolve_location("src/main.rs:TODO:fix this bug", &items).unwrap();
         assert_eq!(file, "src/main.rs");
@@ -287,12 +513,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "src/main.rs".to_string(),
             line: 42,
+            column: 1,
             tag: crate::model::Tag::Todo,
             message: "fix this bug".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }];
         // No ID match, falls back to parse_location
         let (file, line) = resolve_location("src/lib.rs:10", &items).unwrap();
@@ -306,12 +537,17 @@ mod tests {
         let items = vec![TodoItem {
             file: "src/main.rs".to_string(),
             line: 99,
+            column: 1,
             tag: crate::model::Tag::Fixme,
             message: "urgent problem".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }];
         let (file, line) = resolve_location("src/main.rs:FIXME:urgent problem", &items).unwrap();
         assert_eq!(file, "src/main.rs");
@@ -353,7 +589,8 @@ mod tests {
         let file_path = dir.path().join("test.rs");
         std::fs::write(&file_path, "line1\nline2\nline3\nline4\nline5\n").unwrap();
 
-        let (ctx, todo_line) = read_file_context(dir.path(), "test.rs", 3, 1).unwrap();
+        let (ctx, todo_line) =
+            read_file_context(dir.path(), "test.rs", 3, 1, ContextMode::Lines).unwrap();
         assert_eq!(todo_line, "line3");
         assert_eq!(ctx.before.len(), 1);
         assert_eq!(ctx.before[0].content, "line2");
@@ -367,7 +604,8 @@ mod tests {
         let file_path = dir.path().join("test.rs");
         std::fs::write(&file_path, "only line\n").unwrap();
 
-        let (ctx, todo_line) = read_file_context(dir.path(), "test.rs", 100, 2).unwrap();
+        let (ctx, todo_line) =
+            read_file_context(dir.path(), "test.rs", 100, 2, ContextMode::Lines).unwrap();
         assert_eq!(todo_line, "");
         assert!(ctx.before.is_empty());
         assert!(ctx.after.is_empty());
@@ -379,7 +617,8 @@ mod tests {
         let file_path = dir.path().join("test.rs");
         std::fs::write(&file_path, "line1\n").unwrap();
 
-        let (ctx, todo_line) = read_file_context(dir.path(), "test.rs", 0, 2).unwrap();
+        let (ctx, todo_line) =
+            read_file_context(dir.path(), "test.rs", 0, 2, ContextMode::Lines).unwrap();
         assert_eq!(todo_line, "");
         assert!(ctx.before.is_empty());
         assert!(ctx.after.is_empty());
@@ -388,7 +627,7 @@ mod tests {
     #[test]
     fn test_read_file_context_file_not_found() {
         let dir = tempfile::tempdir().unwrap();
-        let result = read_file_context(dir.path(), "nonexistent.rs", 1, 2);
+        let result = read_file_context(dir.path(), "nonexistent.rs", 1, 2, ContextMode::Lines);
         assert!(result.is_err());
     }
 
@@ -405,16 +644,29 @@ mod tests {
         let item1 = TodoItem {
             file: "test.rs".to_string(),
             line: 2,
+            column: 1,
             tag: crate::model::Tag::Todo,
             message: "fix this".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
 
         let todos_in_file: Vec<&TodoItem> = vec![&item1];
-        let rich = build_rich_context(dir.path(), "test.rs", 2, 1, &todos_in_file).unwrap();
+        let rich = build_rich_context(
+            dir.path(),
+            "test.rs",
+            2,
+            1,
+            ContextMode::Lines,
+            &todos_in_file,
+        )
+        .unwrap();
         assert_eq!(rich.file, "test.rs");
         assert_eq!(rich.line, 2);
         assert!(rich.todo_line.contains("TODO"));
@@ -437,26 +689,44 @@ mod tests {
         let item1 = TodoItem {
             file: "test.rs".to_string(),
             line: 2,
+            column: 1,
             tag: crate::model::Tag::Todo,
             message: "first".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         let item2 = TodoItem {
             file: "test.rs".to_string(),
             line: 4,
+            column: 1,
             tag: crate::model::Tag::Fixme,
             message: "second".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
 
         let todos_in_file: Vec<&TodoItem> = vec![&item1, &item2];
-        let rich = build_rich_context(dir.path(), "test.rs", 2, 3, &todos_in_file).unwrap();
+        let rich = build_rich_context(
+            dir.path(),
+            "test.rs",
+            2,
+            3,
+            ContextMode::Lines,
+            &todos_in_file,
+        )
+        .unwrap();
 
         // item2 at line 4 is within window (2-3=0..2+3=5), and != target line 2
         assert_eq!(rich.related_todos.len(), 1);
@@ -474,15 +744,20 @@ mod tests {
         let items = vec![TodoItem {
             file: "test.rs".to_string(),
             line: 2,
+            column: 1,
             tag: crate::model::Tag::Todo,
             message: "do something".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }];
 
-        let map = collect_context_map(dir.path(), &items, 1);
+        let map = collect_context_map(dir.path(), &items, 1, ContextMode::Lines);
         assert_eq!(map.len(), 1);
         let ctx = map.get("test.rs:2").unwrap();
         assert_eq!(ctx.before.len(), 1);
@@ -501,26 +776,36 @@ mod tests {
             TodoItem {
                 file: "test.rs".to_string(),
                 line: 2,
+                column: 1,
                 tag: crate::model::Tag::Todo,
                 message: "first".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: crate::model::Priority::Normal,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             },
             TodoItem {
                 file: "test.rs".to_string(),
                 line: 4,
+                column: 1,
                 tag: crate::model::Tag::Fixme,
                 message: "second".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: crate::model::Priority::Normal,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             },
         ];
 
-        let map = collect_context_map(dir.path(), &items, 1);
+        let map = collect_context_map(dir.path(), &items, 1, ContextMode::Lines);
         assert_eq!(map.len(), 2);
         assert!(map.contains_key("test.rs:2"));
         assert!(map.contains_key("test.rs:4"));
@@ -532,15 +817,20 @@ mod tests {
         let items = vec![TodoItem {
             file: "nonexistent.rs".to_string(),
             line: 1,
+            column: 1,
             tag: crate::model::Tag::Todo,
             message: "missing".to_string(),
             author: None,
             issue_ref: None,
             priority: crate::model::Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }];
 
-        let map = collect_context_map(dir.path(), &items, 1);
+        let map = collect_context_map(dir.path(), &items, 1, ContextMode::Lines);
         // Should still have an entry but with empty context
         assert_eq!(map.len(), 1);
         let ctx = map.get("nonexistent.rs:1").unwrap();
@@ -562,4 +852,111 @@ mod tests {
         assert_eq!(ctx.before.len(), 4); // lines 1-4
         assert_eq!(ctx.after.len(), 5); // lines 6-10
     }
+
+    // ---- function mode ----
+
+    fn span(ctx: &ContextInfo) -> (usize, usize) {
+        (
+            ctx.before.first().map_or(0, |l| l.line_number),
+            ctx.after.last().map_or(0, |l| l.line_number),
+        )
+    }
+
+    const RUST_SRC: &str = "use std::io;\n\
+        \n\
+        fn outer() {\n\
+        \x20   let x = 1;\n\
+        \x20   // TODO: inside\n\
+        \x20   if x > 0 {\n\
+        \x20       println!(\"{}\", x);\n\
+        \x20   }\n\
+        }\n\
+        \n\
+        // FIXME: documents next\n\
+        #[inline]\n\
+        fn next(\n\
+        \x20   a: u32,\n\
+        ) -> u32 {\n\
+        \x20   a\n\
+        }\n\
+        // NOTE: trailing\n";
+
+    #[test]
+    fn test_function_mode_enclosing_brace_block() {
+        let ctx = extract_context_with_mode(RUST_SRC, "a.rs", 5, 2, ContextMode::Function);
+        assert_eq!(ctx.mode, ContextMode::Function);
+        assert_eq!(span(&ctx), (3, 9));
+    }
+
+    #[test]
+    fn test_function_mode_skips_control_flow_blocks() {
+        // Inside the `if` body, the context is still the whole function
+        let ctx = extract_context_with_mode(RUST_SRC, "a.rs", 7, 2, ContextMode::Function);
+        assert_eq!(span(&ctx), (3, 9));
+    }
+
+    #[test]
+    fn test_function_mode_following_brace_block() {
+        let ctx = extract_context_with_mode(RUST_SRC, "a.rs", 11, 2, ContextMode::Function);
+        assert_eq!(ctx.mode, ContextMode::Function);
+        assert!(ctx.before.is_empty());
+        assert_eq!(ctx.after.last().unwrap().line_number, 17);
+    }
+
+    #[test]
+    fn test_function_mode_top_level_falls_back_to_lines() {
+        let ctx = extract_context_with_mode(RUST_SRC, "a.rs", 18, 2, ContextMode::Function);
+        assert_eq!(ctx.mode, ContextMode::Lines);
+        assert_eq!(span(&ctx), (16, 0));
+    }
+
+    #[test]
+    fn test_function_mode_lone_open_brace_includes_signature() {
+        let src = "void f(int a)\n{\n    // TODO: c style\n    return;\n}\n";
+        let ctx = extract_context_with_mode(src, "f.c", 3, 1, ContextMode::Function);
+        assert_eq!(span(&ctx), (1, 5));
+    }
+
+    #[test]
+    fn test_function_mode_python_enclosing_def() {
+        let src = "import os\n\
+            \n\
+            class A:\n\
+            \x20   def f(self):\n\
+            \x20       if True:\n\
+            \x20           # TODO: nested\n\
+            \x20           pass\n\
+            \n\
+            \x20       return 1\n\
+            \n\
+            \x20   def g(self):\n\
+            \x20       pass\n";
+        let ctx = extract_context_with_mode(src, "a.py", 6, 1, ContextMode::Function);
+        assert_eq!(ctx.mode, ContextMode::Function);
+        assert_eq!(span(&ctx), (4, 9));
+    }
+
+    #[test]
+    fn test_function_mode_python_following_def() {
+        let src = "x = 1\n# TODO: document\ndef f():\n    return x\n\ny = 2\n";
+        let ctx = extract_context_with_mode(src, "a.py", 2, 1, ContextMode::Function);
+        assert_eq!(ctx.mode, ContextMode::Function);
+        assert!(ctx.before.is_empty());
+        assert_eq!(ctx.after.last().unwrap().line_number, 4);
+    }
+
+    #[test]
+    fn test_function_mode_python_outside_def_falls_back() {
+        let src = "def f():\n    pass\nif x:\n    # TODO: module level\n    y = 1\n";
+        let ctx = extract_context_with_mode(src, "a.py", 4, 1, ContextMode::Function);
+        assert_eq!(ctx.mode, ContextMode::Lines);
+    }
+
+    #[test]
+    fn test_function_mode_unknown_language_falls_back() {
+        let src = "a\nb\n# TODO: x\nd\ne\n";
+        let ctx = extract_context_with_mode(src, "notes.txt", 3, 1, ContextMode::Function);
+        assert_eq!(ctx.mode, ContextMode::Lines);
+        assert_eq!(span(&ctx), (2, 4));
+    }
 }
@@ -1,6 +1,10 @@
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 
+use crate::date_utils;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Deadline {
     pub year: u16,
@@ -12,6 +16,16 @@ impl Deadline {
     pub fn is_expired(&self, today: &Deadline) -> bool {
         (self.year, self.month, self.day) < (today.year, today.month, today.day)
     }
+
+    /// Whole days from `today` until this deadline; negative once expired.
+    pub fn days_until(&self, today: &Deadline) -> i64 {
+        epoch_days(self) - epoch_days(today)
+    }
+
+    /// The date `days` days earlier.
+    pub fn days_before(&self, days: u64) -> Deadline {
+        from_epoch_days(epoch_days(self) - days as i64)
+    }
 }
 
 impl fmt::Display for Deadline {
@@ -29,6 +43,19 @@ impl Serialize for Deadline {
     }
 }
 
+impl JsonSchema for Deadline {
+    fn schema_name() -> Cow<'static, str> {
+        "Deadline".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "format": "date"
+        })
+    }
+}
+
 impl<'de> Deserialize<'de> for Deadline {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -80,6 +107,143 @@ pub fn parse_deadline(s: &str) -> Option<Deadline> {
     Some(Deadline { year, month, day })
 }
 
+/// A deadline written relative to a reference date, e.g. `+14d` or
+/// `next-quarter`. Resolved against the line's git blame date when
+/// available, otherwise against today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelativeDeadline {
+    Days(u32),
+    Weeks(u32),
+    Months(u32),
+    /// End of the quarter after the reference date's quarter.
+    NextQuarter,
+}
+
+impl RelativeDeadline {
+    /// Compute the absolute deadline counted from `reference`.
+    /// Month offsets clamp to the last day of the target month.
+    pub fn resolve(&self, reference: &Deadline) -> Deadline {
+        match *self {
+            RelativeDeadline::Days(n) => add_days(reference, n as i64),
+            RelativeDeadline::Weeks(n) => add_days(reference, n as i64 * 7),
+            RelativeDeadline::Months(n) => {
+                let months = reference.year as i64 * 12 + (reference.month as i64 - 1) + n as i64;
+                let year = (months / 12) as u16;
+                let month = (months % 12 + 1) as u8;
+                let day = reference.day.min(days_in_month(year, month));
+                Deadline { year, month, day }
+            }
+            RelativeDeadline::NextQuarter => {
+                let quarter = (reference.month - 1) / 3 + 1;
+                let (year, quarter) = if quarter == 4 {
+                    (reference.year + 1, 1)
+                } else {
+                    (reference.year, quarter + 1)
+                };
+                let month = quarter * 3;
+                Deadline {
+                    year,
+                    month,
+                    day: days_in_month(year, month),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for RelativeDeadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RelativeDeadline::Days(n) => write!(f, "+{n}d"),
+            RelativeDeadline::Weeks(n) => write!(f, "+{n}w"),
+            RelativeDeadline::Months(n) => write!(f, "+{n}m"),
+            RelativeDeadline::NextQuarter => write!(f, "next-quarter"),
+        }
+    }
+}
+
+impl Serialize for RelativeDeadline {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for RelativeDeadline {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        parse_relative_deadline(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid relative deadline: {s}")))
+    }
+}
+
+impl JsonSchema for RelativeDeadline {
+    fn schema_name() -> Cow<'static, str> {
+        "RelativeDeadline".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": "^(\\+[0-9]+[dwm]|next-quarter)$"
+        })
+    }
+}
+
+/// Parse a relative deadline: `+Nd`, `+Nw`, `+Nm`, or `next-quarter`.
+pub fn parse_relative_deadline(s: &str) -> Option<RelativeDeadline> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("next-quarter") {
+        return Some(RelativeDeadline::NextQuarter);
+    }
+    let rest = s.strip_prefix('+')?;
+    let unit = rest.chars().last()?;
+    let amount: u32 = rest[..rest.len() - unit.len_utf8()].parse().ok()?;
+    match unit.to_ascii_lowercase() {
+        'd' => Some(RelativeDeadline::Days(amount)),
+        'w' => Some(RelativeDeadline::Weeks(amount)),
+        'm' => Some(RelativeDeadline::Months(amount)),
+        _ => None,
+    }
+}
+
+fn add_days(date: &Deadline, days: i64) -> Deadline {
+    from_epoch_days(epoch_days(date) + days)
+}
+
+fn epoch_days(date: &Deadline) -> i64 {
+    date_utils::ymd_to_days(date.year as i64, date.month as u32, date.day as u32)
+}
+
+fn from_epoch_days(days: i64) -> Deadline {
+    let (year, month, day) = date_utils::days_to_ymd(days);
+    Deadline {
+        year: year as u16,
+        month: month as u8,
+        day: day as u8,
+    }
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    let (next_year, next_month) = if month == 12 {
+        (year as i64 + 1, 1)
+    } else {
+        (year as i64, month as u32 + 1)
+    };
+    let first_of_next = date_utils::ymd_to_days(next_year, next_month, 1);
+    date_utils::days_to_ymd(first_of_next - 1).2 as u8
+}
+
+/// Convert a unix timestamp (e.g. a git blame author time) to its UTC date.
+pub fn date_from_timestamp(timestamp: i64) -> Deadline {
+    from_epoch_days(timestamp.div_euclid(86400))
+}
+
 /// Get today's date as a `Deadline`.
 pub fn today() -> Deadline {
     let now = time::OffsetDateTime::now_utc();
@@ -363,4 +527,111 @@ mod tests {
         };
         assert!(deadline.is_expired(&today));
     }
+
+    #[test]
+    fn test_days_until() {
+        let today = parse_deadline("2025-06-15").unwrap();
+        assert_eq!(parse_deadline("2025-06-15").unwrap().days_until(&today), 0);
+        assert_eq!(parse_deadline("2025-06-22").unwrap().days_until(&today), 7);
+        assert_eq!(parse_deadline("2025-06-10").unwrap().days_until(&today), -5);
+        assert_eq!(parse_deadline("2025-Q4").unwrap().days_until(&today), 199);
+    }
+
+    // --- relative deadlines ---
+
+    fn date(year: u16, month: u8, day: u8) -> Deadline {
+        Deadline { year, month, day }
+    }
+
+    #[test]
+    fn test_parse_relative_units() {
+        assert_eq!(
+            parse_relative_deadline("+14d"),
+            Some(RelativeDeadline::Days(14))
+        );
+        assert_eq!(
+            parse_relative_deadline(" +2w "),
+            Some(RelativeDeadline::Weeks(2))
+        );
+        assert_eq!(
+            parse_relative_deadline("+3M"),
+            Some(RelativeDeadline::Months(3))
+        );
+        assert_eq!(
+            parse_relative_deadline("next-quarter"),
+            Some(RelativeDeadline::NextQuarter)
+        );
+    }
+
+    #[test]
+    fn test_parse_relative_invalid() {
+        assert!(parse_relative_deadline("14d").is_none());
+        assert!(parse_relative_deadline("+d").is_none());
+        assert!(parse_relative_deadline("+14y").is_none());
+        assert!(parse_relative_deadline("+").is_none());
+        assert!(parse_relative_deadline("2025-06-01").is_none());
+    }
+
+    #[test]
+    fn test_absolute_parser_rejects_relative() {
+        assert!(parse_deadline("+14d").is_none());
+        assert!(parse_deadline("next-quarter").is_none());
+    }
+
+    #[test]
+    fn test_resolve_days_crosses_year() {
+        let d = RelativeDeadline::Days(14).resolve(&date(2024, 12, 25));
+        assert_eq!(d, date(2025, 1, 8));
+    }
+
+    #[test]
+    fn test_resolve_weeks() {
+        let d = RelativeDeadline::Weeks(2).resolve(&date(2025, 2, 20));
+        assert_eq!(d, date(2025, 3, 6));
+    }
+
+    #[test]
+    fn test_resolve_months_clamps_day() {
+        assert_eq!(
+            RelativeDeadline::Months(1).resolve(&date(2024, 1, 31)),
+            date(2024, 2, 29)
+        );
+        assert_eq!(
+            RelativeDeadline::Months(13).resolve(&date(2024, 11, 30)),
+            date(2025, 12, 30)
+        );
+    }
+
+    #[test]
+    fn test_resolve_next_quarter() {
+        assert_eq!(
+            RelativeDeadline::NextQuarter.resolve(&date(2025, 2, 10)),
+            date(2025, 6, 30)
+        );
+        assert_eq!(
+            RelativeDeadline::NextQuarter.resolve(&date(2025, 11, 1)),
+            date(2026, 3, 31)
+        );
+    }
+
+    #[test]
+    fn test_relative_display_and_serde_round_trip() {
+        for rel in [
+            RelativeDeadline::Days(14),
+            RelativeDeadline::Weeks(1),
+            RelativeDeadline::Months(6),
+            RelativeDeadline::NextQuarter,
+        ] {
+            let json = serde_json::to_string(&rel).unwrap();
+            assert_eq!(json, format!("\"{rel}\""));
+            let back: RelativeDeadline = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, rel);
+        }
+    }
+
+    #[test]
+    fn test_date_from_timestamp() {
+        assert_eq!(date_from_timestamp(1704067200), date(2024, 1, 1));
+        assert_eq!(date_from_timestamp(1704067199), date(2023, 12, 31));
+    }
 }
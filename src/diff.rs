@@ -1,12 +1,12 @@
-use anyhow::{Context, Result};
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use crate::config::Config;
-use crate::git::git_command;
+use crate::error::{Error, Result};
+use crate::git::{git_command, read_blobs, GitError};
 use crate::model::*;
-use crate::scanner::scan_content;
+use crate::scanner::{scan_content, ExcludeDirs, ScanOptions};
 
 /// Detect which files changed between `base_ref` and the current working tree.
 ///
@@ -18,7 +18,7 @@ fn detect_changed_files(
     base_files: &HashSet<String>,
     current: &ScanResult,
 ) -> HashSet<String> {
-    let diff_from_ref = git_command(&["diff", "--name-only", "--", base_ref], root);
+    let diff_from_ref = git_command(&["diff", "--name-only", base_ref, "--"], root);
     let diff_unstaged = git_command(&["diff", "--name-only"], root);
 
     // If either diff command failed, fall back to all files
@@ -57,27 +57,18 @@ pub fn compute_diff(
     root: &Path,
     config: &Config,
 ) -> Result<DiffResult> {
-    anyhow::ensure!(
-        !base_ref.starts_with('-'),
-        "invalid git ref '{}': must not start with '-'",
-        base_ref
-    );
+    check_ref(base_ref)?;
 
-    let file_list = git_command(&["ls-tree", "-r", "--name-only", "--", base_ref], root)
-        .with_context(|| format!("Failed to list files at ref {}", base_ref))?;
+    let base_files = scannable(list_files_at_ref(base_ref, root)?, config);
 
     let pattern = config.tags_pattern();
-    let re = Regex::new(&pattern).with_context(|| format!("Invalid tags pattern: {}", pattern))?;
-
-    let base_files: HashSet<String> = file_list
-        .lines()
-        .map(|l| l.trim().to_string())
-        .filter(|l| !l.is_empty())
-        .collect();
+    let re = Regex::new(&pattern).map_err(|e| Error::regex(&pattern, e))?;
+    let options = ScanOptions::from_config(config);
 
     let changed_files = detect_changed_files(base_ref, root, &base_files, current);
 
-    // Only scan changed files from base ref (instead of all files)
+    // Only scan changed files from base ref (instead of all files); files
+    // the working-tree scan excludes are not in `base_files`
     let mut base_items: Vec<TodoItem> = Vec::new();
     for path in &changed_files {
         if !base_files.contains(path) {
@@ -89,25 +80,291 @@ pub fn compute_diff(
             Err(_) => continue, // skip binary or inaccessible files
         };
 
-        let result = scan_content(&content, path, &re);
+        let result = scan_content(&content, path, &re, &options);
         base_items.extend(result.items);
     }
 
     // Only compare current items from changed files
-    let current_changed: Vec<&TodoItem> = current
+    let (current_changed, current_unchanged): (Vec<&TodoItem>, Vec<&TodoItem>) = current
         .items
         .iter()
-        .filter(|i| changed_files.contains(&i.file))
+        .partition(|i| changed_files.contains(&i.file));
+
+    // Unchanged files hold the same TODOs at the base ref
+    let base_tag_counts = count_tags(current_unchanged.into_iter().chain(&base_items));
+
+    Ok(build_diff(
+        &current_changed,
+        &base_items,
+        base_tag_counts,
+        base_ref.to_string(),
+        None,
+    ))
+}
+
+/// Compare TODOs between two git refs (`base_ref..head_ref`) without touching
+/// the working tree: both sides are read as blobs through one
+/// `git cat-file --batch` process each. Exclude directories and patterns
+/// apply to both sides, as in a working-tree diff.
+pub fn compute_ref_diff(
+    base_ref: &str,
+    head_ref: &str,
+    root: &Path,
+    config: &Config,
+) -> Result<DiffResult> {
+    check_ref(base_ref)?;
+    check_ref(head_ref)?;
+
+    let base_files = scannable(list_files_at_ref(base_ref, root)?, config);
+    let head_files = scannable(list_files_at_ref(head_ref, root)?, config);
+
+    let pattern = config.tags_pattern();
+    let re = Regex::new(&pattern).map_err(|e| Error::regex(&pattern, e))?;
+    let options = ScanOptions::from_config(config);
+
+    // Files changed between the two refs; fall back to every file on either side
+    let changed_files: HashSet<String> = match git_command(
+        &[
+            "diff",
+            "--name-only",
+            "--relative",
+            base_ref,
+            head_ref,
+            "--",
+        ],
+        root,
+    ) {
+        Ok(output) => output
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| base_files.contains(l) || head_files.contains(l))
+            .collect(),
+        Err(_) => base_files.union(&head_files).cloned().collect(),
+    };
+
+    let scan_at_ref = |git_ref: &str, paths: Vec<&String>| -> Result<Vec<TodoItem>> {
+        Ok(scan_blobs(git_ref, &paths, root, &re, &options)?.items)
+    };
+
+    // The whole (filtered) base tree is scanned so `base_total` covers
+    // unchanged files, matching what a working-tree diff counts
+    let (base_items, base_unchanged): (Vec<TodoItem>, Vec<TodoItem>) =
+        scan_at_ref(base_ref, base_files.iter().collect())?
+            .into_iter()
+            .partition(|i| changed_files.contains(&i.file));
+    let head_items = scan_at_ref(
+        head_ref,
+        changed_files
+            .iter()
+            .filter(|p| head_files.contains(*p))
+            .collect(),
+    )?;
+    let head_refs: Vec<&TodoItem> = head_items.iter().collect();
+    let base_tag_counts = count_tags(base_unchanged.iter().chain(&base_items));
+
+    Ok(build_diff(
+        &head_refs,
+        &base_items,
+        base_tag_counts,
+        base_ref.to_string(),
+        Some(head_ref.to_string()),
+    ))
+}
+
+/// Scan every file in the tree at `git_ref`, read as blobs without touching
+/// the working tree. Exclude directories and patterns apply as in a
+/// working-tree scan.
+pub fn scan_ref(git_ref: &str, root: &Path, config: &Config) -> Result<ScanResult> {
+    check_ref(git_ref)?;
+    let files = scannable(list_files_at_ref(git_ref, root)?, config);
+    let mut paths: Vec<&String> = files.iter().collect();
+    paths.sort();
+
+    let pattern = config.tags_pattern();
+    let re = Regex::new(&pattern).map_err(|e| Error::regex(&pattern, e))?;
+    let options = ScanOptions::from_config(config);
+    scan_blobs(git_ref, &paths, root, &re, &options)
+}
+
+/// Scan the staged content of every file in the index, leaving the working
+/// tree alone, so unstaged edits never count. Exclude directories and
+/// patterns apply as in a working-tree scan.
+pub fn scan_index(root: &Path, config: &Config) -> Result<ScanResult> {
+    let listing = git_command(&["ls-files", "-z"], root)?;
+    let files = scannable(
+        listing
+            .split('\0')
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect(),
+        config,
+    );
+    let mut paths: Vec<&String> = files.iter().collect();
+    paths.sort();
+
+    let pattern = config.tags_pattern();
+    let re = Regex::new(&pattern).map_err(|e| Error::regex(&pattern, e))?;
+    let options = ScanOptions::from_config(config);
+    // An empty rev names the index: `:<path>`
+    scan_blobs("", &paths, root, &re, &options)
+}
+
+/// Compare a `scan_index` result against `HEAD`: what the next commit would
+/// add or remove. Before the first commit there is no `HEAD`, so every
+/// staged TODO counts as added.
+pub fn compute_staged_diff(
+    staged: &ScanResult,
+    root: &Path,
+    config: &Config,
+) -> Result<DiffResult> {
+    let has_head = git_command(&["rev-parse", "--verify", "--quiet", "HEAD"], root).is_ok();
+    let (base_files, changed_files): (HashSet<String>, HashSet<String>) = if has_head {
+        let changed = git_command(
+            &[
+                "diff",
+                "--cached",
+                "--name-only",
+                "--relative",
+                "-z",
+                "HEAD",
+                "--",
+            ],
+            root,
+        )?
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect();
+        (scannable(list_files_at_ref("HEAD", root)?, config), changed)
+    } else {
+        let all = staged.items.iter().map(|i| i.file.clone()).collect();
+        (HashSet::new(), all)
+    };
+
+    let pattern = config.tags_pattern();
+    let re = Regex::new(&pattern).map_err(|e| Error::regex(&pattern, e))?;
+    let options = ScanOptions::from_config(config);
+    let base_paths: Vec<&String> = changed_files
+        .iter()
+        .filter(|p| base_files.contains(*p))
         .collect();
+    let base_items = scan_blobs("HEAD", &base_paths, root, &re, &options)?.items;
 
-    let current_keys: HashSet<String> = current_changed.iter().map(|i| i.match_key()).collect();
-    let base_keys: HashSet<String> = base_items.iter().map(|i| i.match_key()).collect();
+    let (staged_changed, staged_unchanged): (Vec<&TodoItem>, Vec<&TodoItem>) = staged
+        .items
+        .iter()
+        .partition(|i| changed_files.contains(&i.file));
+    let base_tag_counts = count_tags(staged_unchanged.into_iter().chain(&base_items));
+
+    Ok(build_diff(
+        &staged_changed,
+        &base_items,
+        base_tag_counts,
+        "HEAD".to_string(),
+        None,
+    ))
+}
+
+/// Drop the files a working-tree scan would skip: those under
+/// `exclude_dirs` or matching `exclude_patterns`.
+fn scannable(files: HashSet<String>, config: &Config) -> HashSet<String> {
+    let exclude_dirs = ExcludeDirs::new(&config.exclude_dirs);
+    let exclude_regexes: Vec<Regex> = config
+        .exclude_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+    files
+        .into_iter()
+        .filter(|p| !exclude_dirs.is_excluded(Path::new(p.as_str())))
+        .filter(|p| !exclude_regexes.iter().any(|re| re.is_match(p)))
+        .collect()
+}
+
+/// Scan `paths` as of `git_ref`. Binary or inaccessible files are skipped
+/// and not counted as scanned. `paths` are relative to `root`, which may be
+/// below the repository root, so specs use the `./` form git resolves
+/// against the working directory.
+fn scan_blobs(
+    git_ref: &str,
+    paths: &[&String],
+    root: &Path,
+    re: &Regex,
+    options: &ScanOptions,
+) -> Result<ScanResult> {
+    let specs: Vec<String> = paths.iter().map(|p| format!("{git_ref}:./{p}")).collect();
+    let mut result = ScanResult {
+        items: Vec::new(),
+        ignored_items: Vec::new(),
+        files_scanned: 0,
+        scanned_files: Vec::new(),
+    };
+    for (path, content) in paths.iter().zip(read_blobs(&specs, root)?) {
+        if let Some(content) = content {
+            let scanned = scan_content(&content, path, re, options);
+            result.items.extend(scanned.items);
+            result.ignored_items.extend(scanned.ignored_items);
+            result.scanned_files.push(path.to_string());
+        }
+    }
+    result.files_scanned = result.scanned_files.len();
+    Ok(result)
+}
+
+/// Refuse refs git would parse as options.
+fn check_ref(git_ref: &str) -> Result<()> {
+    if git_ref.starts_with('-') {
+        return Err(Error::Invalid(format!(
+            "invalid git ref '{}': must not start with '-'",
+            git_ref
+        )));
+    }
+    Ok(())
+}
+
+fn list_files_at_ref(git_ref: &str, root: &Path) -> Result<HashSet<String>> {
+    let file_list = git_command(&["ls-tree", "-r", "--name-only", "--", git_ref], root).map_err(
+        |e| match e {
+            Error::Git(GitError(message)) => Error::Git(GitError(format!(
+                "Failed to list files at ref {}: {}",
+                git_ref, message
+            ))),
+            other => other,
+        },
+    )?;
+    Ok(file_list
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect())
+}
+
+fn count_tags<'a>(items: impl IntoIterator<Item = &'a TodoItem>) -> HashMap<Tag, usize> {
+    let mut counts = HashMap::new();
+    for item in items {
+        *counts.entry(item.tag.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Match items by `stable_id`: added = only on the head side, removed = only
+/// on the base side. Rewording that only changes case, punctuation or
+/// whitespace is not reported.
+fn build_diff(
+    head_items: &[&TodoItem],
+    base_items: &[TodoItem],
+    base_tag_counts: HashMap<Tag, usize>,
+    base_ref: String,
+    head_ref: Option<String>,
+) -> DiffResult {
+    let head_keys: HashSet<String> = head_items.iter().map(|i| i.stable_id()).collect();
+    let base_keys: HashSet<String> = base_items.iter().map(|i| i.stable_id()).collect();
 
     let mut entries: Vec<DiffEntry> = Vec::new();
 
-    // Added = in current but not in base
-    for item in &current_changed {
-        if !base_keys.contains(&item.match_key()) {
+    // Added = in head but not in base
+    for item in head_items {
+        if !base_keys.contains(&item.stable_id()) {
             entries.push(DiffEntry {
                 status: DiffStatus::Added,
                 item: (*item).clone(),
@@ -115,9 +372,9 @@ pub fn compute_diff(
         }
     }
 
-    // Removed = in base but not in current
-    for item in &base_items {
-        if !current_keys.contains(&item.match_key()) {
+    // Removed = in base but not in head
+    for item in base_items {
+        if !head_keys.contains(&item.stable_id()) {
             entries.push(DiffEntry {
                 status: DiffStatus::Removed,
                 item: item.clone(),
@@ -125,26 +382,58 @@ pub fn compute_diff(
         }
     }
 
-    let added_count = entries
-        .iter()
-        .filter(|e| matches!(e.status, DiffStatus::Added))
-        .count();
-    let removed_count = entries
-        .iter()
-        .filter(|e| matches!(e.status, DiffStatus::Removed))
-        .count();
-
-    Ok(DiffResult {
+    let mut result = DiffResult {
         entries,
-        added_count,
-        removed_count,
-        base_ref: base_ref.to_string(),
-    })
+        added_count: 0,
+        removed_count: 0,
+        net_change: 0,
+        base_total: 0,
+        base_ref,
+        head_ref,
+        base_tag_counts,
+    };
+    result.recount(&[]);
+    result
+}
+
+/// Aggregate diff entries per file for `diff --stat`, most churn first and
+/// ties by path.
+pub fn diff_stat(result: &DiffResult) -> DiffStatResult {
+    let mut by_file: HashMap<&str, (usize, usize)> = HashMap::new();
+    for entry in &result.entries {
+        let counts = by_file.entry(entry.item.file.as_str()).or_default();
+        match entry.status {
+            DiffStatus::Added => counts.0 += 1,
+            DiffStatus::Removed => counts.1 += 1,
+        }
+    }
+    let mut files: Vec<DiffFileStat> = by_file
+        .into_iter()
+        .map(|(file, (added, removed))| DiffFileStat {
+            file: file.to_string(),
+            added,
+            removed,
+        })
+        .collect();
+    files.sort_by(|a, b| {
+        (b.added + b.removed)
+            .cmp(&(a.added + a.removed))
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    DiffStatResult {
+        files,
+        added_count: result.added_count,
+        removed_count: result.removed_count,
+        net_change: result.net_change,
+        base_ref: result.base_ref.clone(),
+        head_ref: result.head_ref.clone(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::helpers::git;
     use std::process::Command;
 
     // ---- Helper functions ----
@@ -155,29 +444,10 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let cwd = dir.path();
 
-        Command::new("git")
-            .args(["init"])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["config", "user.email", "test@test.com"])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["config", "user.name", "Test"])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["config", "commit.gpgsign", "false"])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
+        git(cwd, &["init"]);
+        git(cwd, &["config", "user.email", "test@test.com"]);
+        git(cwd, &["config", "user.name", "Test"]);
+        git(cwd, &["config", "commit.gpgsign", "false"]);
 
         for (path, content) in initial_files {
             let full_path = cwd.join(path);
@@ -187,17 +457,8 @@ mod tests {
             std::fs::write(full_path, content).unwrap();
         }
 
-        Command::new("git")
-            .args(["add", "."])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
-
-        Command::new("git")
-            .args(["commit", "-m", "initial"])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
+        git(cwd, &["add", "."]);
+        git(cwd, &["commit", "-m", "initial"]);
 
         dir
     }
@@ -207,12 +468,17 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            column: 1,
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
@@ -223,6 +489,7 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -332,6 +599,7 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -350,6 +618,7 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -487,6 +756,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compute_diff_net_change_and_base_total_cover_unchanged_files() {
+        let dir = setup_git_repo(&[
+            ("a.rs", "// TODO: one\n// TODO: two\n"),
+            ("b.rs", "// FIXME: untouched\n"),
+        ]);
+        let cwd = dir.path();
+        std::fs::write(cwd.join("a.rs"), "// TODO: one\n").unwrap();
+        std::fs::write(cwd.join("c.rs"), "// BUG: new\n// BUG: newer\n").unwrap();
+
+        let config = Config::default();
+        let current = crate::scanner::scan_directory(cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+
+        assert_eq!(result.added_count, 2);
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(result.net_change, 1);
+        assert_eq!(result.base_total, 3);
+    }
+
+    #[test]
+    fn test_build_diff_synthetic_net_change_and_tag_recount() {
+        let head = [
+            make_item("a.rs", 1, Tag::Todo, "kept"),
+            make_item("a.rs", 2, Tag::Bug, "added bug"),
+        ];
+        let head_refs: Vec<&TodoItem> = head.iter().collect();
+        let base = vec![
+            make_item("a.rs", 1, Tag::Todo, "kept"),
+            make_item("a.rs", 2, Tag::Todo, "removed one"),
+            make_item("a.rs", 3, Tag::Todo, "removed two"),
+        ];
+        let counts = HashMap::from([(Tag::Todo, 3), (Tag::Fixme, 4)]);
+
+        let mut result = build_diff(&head_refs, &base, counts, "main".to_string(), None);
+        assert_eq!((result.added_count, result.removed_count), (1, 2));
+        assert_eq!(result.net_change, -1);
+        assert_eq!(result.base_total, 7);
+
+        result.entries.retain(|e| e.item.tag == Tag::Todo);
+        result.recount(&[Tag::Todo]);
+        assert_eq!((result.added_count, result.removed_count), (0, 2));
+        assert_eq!(result.net_change, -2);
+        assert_eq!(result.base_total, 3);
+    }
+
     #[test]
     fn test_compute_diff_preserves_base_ref_in_result() {
         let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
@@ -506,11 +821,7 @@ mod tests {
         let cwd = dir.path();
 
         // Create a branch at current HEAD
-        Command::new("git")
-            .args(["branch", "baseline"])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
+        git(cwd, &["branch", "baseline"]);
 
         // Add a TODO in working tree
         std::fs::write(cwd.join("main.rs"), "// TODO: after branch\nfn main() {}\n").unwrap();
@@ -524,10 +835,29 @@ mod tests {
         assert_eq!(result.entries[0].item.message, "after branch");
     }
 
+    #[test]
+    fn test_compute_diff_ignores_case_and_punctuation_edits() {
+        let dir = setup_git_repo(&[("main.rs", "// FIXME: Fix this bug\nfn main() {}\n")]);
+        let cwd = dir.path();
+
+        std::fs::write(
+            cwd.join("main.rs"),
+            "// FIXME: fix this  bug!\nfn main() {}\n",
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let current = crate::scanner::scan_directory(cwd, &config).unwrap();
+        let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
+
+        assert_eq!(result.added_count, 0);
+        assert_eq!(result.removed_count, 0);
+    }
+
     #[test]
     fn test_compute_diff_todo_message_change_is_added_and_removed() {
         // Changing a TODO message means the old one is "removed" and new one is "added"
-        // because match_key includes the message
+        // because stable_id includes the message words
         let dir = setup_git_repo(&[("main.rs", "// TODO: original message\nfn main() {}\n")]);
         let cwd = dir.path();
 
@@ -562,7 +892,7 @@ mod tests {
     #[test]
     fn test_compute_diff_tag_change_is_added_and_removed() {
         // Changing a tag (e.g., TODO -> FIXME) with same message is add+remove
-        // because match_key includes the tag
+        // because stable_id includes the tag
         let dir = setup_git_repo(&[("main.rs", "// TODO: fix something\nfn main() {}\n")]);
         let cwd = dir.path();
 
@@ -597,7 +927,7 @@ mod tests {
     #[test]
     fn test_compute_diff_line_number_change_only_is_not_a_diff() {
         // Moving a TODO to a different line but keeping same content should NOT
-        // cause a diff, because match_key() excludes line numbers
+        // cause a diff, because stable_id() excludes line numbers
         let dir = setup_git_repo(&[("main.rs", "// TODO: stable task\nfn main() {}\n")]);
         let cwd = dir.path();
 
@@ -679,7 +1009,7 @@ mod tests {
         let result = compute_diff(&current, "HEAD", cwd, &config).unwrap();
 
         assert_eq!(result.added_count, 6);
-        let tags: Vec<Tag> = result.entries.iter().map(|e| e.item.tag).collect();
+        let tags: Vec<Tag> = result.entries.iter().map(|e| e.item.tag.clone()).collect();
         assert!(tags.contains(&Tag::Todo));
         assert!(tags.contains(&Tag::Fixme));
         assert!(tags.contains(&Tag::Hack));
@@ -705,6 +1035,7 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -736,6 +1067,7 @@ mod tests {
         let current = ScanResult {
             items: vec![make_item("newfile.rs", 1, Tag::Todo, "new")],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -760,6 +1092,7 @@ mod tests {
         let current = ScanResult {
             items: vec![make_item("current.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -793,6 +1126,7 @@ mod tests {
                 make_item("base1.rs", 3, Tag::Hack, "t3"), // overlaps with base
             ],
             files_scanned: 3,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -814,6 +1148,7 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -838,6 +1173,7 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -862,6 +1198,7 @@ mod tests {
         let current = ScanResult {
             items: vec![make_item("main.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -916,6 +1253,7 @@ mod tests {
         let current = ScanResult {
             items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -986,16 +1324,8 @@ mod tests {
         )
         .unwrap();
 
-        Command::new("git")
-            .args(["add", "."])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
-        Command::new("git")
-            .args(["commit", "-m", "second"])
-            .current_dir(cwd)
-            .output()
-            .unwrap();
+        git(cwd, &["add", "."]);
+        git(cwd, &["commit", "-m", "second"]);
 
         // Now add more in working tree
         std::fs::write(
@@ -1055,4 +1385,188 @@ mod tests {
         assert_eq!(result.removed_count, actual_removed);
         assert_eq!(result.entries.len(), actual_added + actual_removed);
     }
+
+    // ---- compute_ref_diff ----
+
+    /// Repo with tag `v1`, a second commit tagged `v2`, and an uncommitted edit.
+    fn setup_two_tagged_commits() -> tempfile::TempDir {
+        let dir = setup_git_repo(&[
+            ("main.rs", "// TODO: kept\n// FIXME: fixed in v2\n"),
+            ("old.rs", "// HACK: deleted file\n"),
+        ]);
+        let cwd = dir.path();
+        git(cwd, &["tag", "v1"]);
+
+        std::fs::write(cwd.join("main.rs"), "// TODO: kept\n// BUG: new in v2\n").unwrap();
+        std::fs::remove_file(cwd.join("old.rs")).unwrap();
+        std::fs::create_dir_all(cwd.join("src")).unwrap();
+        std::fs::write(cwd.join("src/new.rs"), "// NOTE: new file\n").unwrap();
+        git(cwd, &["add", "-A"]);
+        git(cwd, &["commit", "-m", "second"]);
+        git(cwd, &["tag", "v2"]);
+
+        // Working tree changes must not leak into a ref..ref diff
+        std::fs::write(cwd.join("main.rs"), "// XXX: uncommitted\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_compute_ref_diff_between_tags() {
+        let dir = setup_two_tagged_commits();
+        let result = compute_ref_diff("v1", "v2", dir.path(), &Config::default()).unwrap();
+
+        let mut added: Vec<&str> = result
+            .entries
+            .iter()
+            .filter(|e| matches!(e.status, DiffStatus::Added))
+            .map(|e| e.item.message.as_str())
+            .collect();
+        added.sort();
+        let mut removed: Vec<&str> = result
+            .entries
+            .iter()
+            .filter(|e| matches!(e.status, DiffStatus::Removed))
+            .map(|e| e.item.message.as_str())
+            .collect();
+        removed.sort();
+
+        assert_eq!(added, vec!["new file", "new in v2"]);
+        assert_eq!(removed, vec!["deleted file", "fixed in v2"]);
+        assert_eq!(result.added_count, 2);
+        assert_eq!(result.removed_count, 2);
+        assert_eq!(result.base_ref, "v1");
+        assert_eq!(result.head_ref.as_deref(), Some("v2"));
+    }
+
+    #[test]
+    fn test_compute_ref_diff_base_total_counts_whole_base_tree() {
+        let dir = setup_two_tagged_commits();
+        let cwd = dir.path();
+        // A file that never changes between the refs still counts at the base
+        std::fs::write(cwd.join("stable.rs"), "// NOTE: stable\n").unwrap();
+        git(cwd, &["add", "stable.rs"]);
+        git(cwd, &["commit", "-m", "stable"]);
+        git(cwd, &["tag", "v3"]);
+
+        let result = compute_ref_diff("v2", "v3", cwd, &Config::default()).unwrap();
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.net_change, 1);
+        assert_eq!(result.base_total, 3);
+
+        let result = compute_ref_diff("v1", "v2", cwd, &Config::default()).unwrap();
+        assert_eq!(result.net_change, 0);
+        assert_eq!(result.base_total, 3);
+    }
+
+    #[test]
+    fn test_compute_ref_diff_skips_excluded_dirs_and_patterns() {
+        let dir = setup_two_tagged_commits();
+        let cwd = dir.path();
+        std::fs::create_dir_all(cwd.join("vendor")).unwrap();
+        std::fs::write(cwd.join("vendor/x.rs"), "// TODO: vendored\n").unwrap();
+        std::fs::write(cwd.join("gen.pb.rs"), "// TODO: generated\n").unwrap();
+        git(cwd, &["add", "vendor/x.rs", "gen.pb.rs"]);
+        git(cwd, &["commit", "-m", "vendor"]);
+        git(cwd, &["tag", "v3"]);
+
+        let config = Config {
+            exclude_dirs: vec!["vendor".to_string()],
+            exclude_patterns: vec![r"\.pb\.rs$".to_string()],
+            ..Config::default()
+        };
+        let result = compute_ref_diff("v2", "v3", cwd, &config).unwrap();
+        assert!(result.entries.is_empty());
+
+        let result = compute_ref_diff("v3", "v2", cwd, &config).unwrap();
+        assert!(result.entries.is_empty());
+    }
+
+    #[test]
+    fn test_base_total_skips_excluded_dirs_for_ranges_and_working_tree() {
+        let dir = setup_git_repo(&[
+            ("main.rs", "// TODO: one\n"),
+            ("vendor/x.rs", "// TODO: vendored\n// FIXME: vendored too\n"),
+        ]);
+        let cwd = dir.path();
+        git(cwd, &["tag", "v1"]);
+        std::fs::write(cwd.join("main.rs"), "// TODO: one\n// TODO: two\n").unwrap();
+        git(cwd, &["commit", "-am", "second"]);
+        git(cwd, &["tag", "v2"]);
+
+        let config = Config {
+            exclude_dirs: vec!["vendor".to_string()],
+            ..Config::default()
+        };
+        let range = compute_ref_diff("v1", "v2", cwd, &config).unwrap();
+        let current = crate::scanner::scan_directory(cwd, &config).unwrap();
+        let working_tree = compute_diff(&current, "v1", cwd, &config).unwrap();
+
+        assert_eq!(range.base_total, 1);
+        assert_eq!(working_tree.base_total, 1);
+        assert_eq!(range.net_change, working_tree.net_change);
+    }
+
+    #[test]
+    fn test_compute_ref_diff_reversed_swaps_added_and_removed() {
+        let dir = setup_two_tagged_commits();
+        let result = compute_ref_diff("v2", "v1", dir.path(), &Config::default()).unwrap();
+        assert_eq!(result.added_count, 2);
+        assert_eq!(result.removed_count, 2);
+        assert!(result
+            .entries
+            .iter()
+            .any(|e| matches!(e.status, DiffStatus::Added) && e.item.message == "fixed in v2"));
+    }
+
+    #[test]
+    fn test_compute_ref_diff_same_ref_is_empty() {
+        let dir = setup_two_tagged_commits();
+        let result = compute_ref_diff("v2", "HEAD", dir.path(), &Config::default()).unwrap();
+        assert!(result.entries.is_empty());
+    }
+
+    #[test]
+    fn test_compute_ref_diff_rejects_invalid_refs() {
+        let dir = setup_two_tagged_commits();
+        let config = Config::default();
+        assert!(compute_ref_diff("v1", "--output=x", dir.path(), &config).is_err());
+        assert!(compute_ref_diff("v1", "no-such-ref", dir.path(), &config).is_err());
+    }
+
+    #[test]
+    fn test_diff_stat_groups_by_file_most_churn_first() {
+        let entry = |status, file: &str, message: &str| DiffEntry {
+            status,
+            item: make_item(file, 1, Tag::Todo, message),
+        };
+        let mut result = DiffResult {
+            entries: vec![
+                entry(DiffStatus::Added, "b.rs", "one"),
+                entry(DiffStatus::Added, "a.rs", "two"),
+                entry(DiffStatus::Added, "c.rs", "three"),
+                entry(DiffStatus::Removed, "c.rs", "four"),
+                entry(DiffStatus::Removed, "c.rs", "five"),
+            ],
+            added_count: 0,
+            removed_count: 0,
+            net_change: 0,
+            base_total: 0,
+            base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: HashMap::new(),
+        };
+        result.recount(&[]);
+
+        let stat = diff_stat(&result);
+        let files: Vec<(&str, usize, usize)> = stat
+            .files
+            .iter()
+            .map(|f| (f.file.as_str(), f.added, f.removed))
+            .collect();
+        assert_eq!(files, vec![("c.rs", 1, 2), ("a.rs", 1, 0), ("b.rs", 1, 0)]);
+        assert_eq!(stat.added_count, 3);
+        assert_eq!(stat.removed_count, 2);
+        assert_eq!(stat.net_change, 1);
+        assert_eq!(stat.base_ref, "main");
+    }
 }
@@ -0,0 +1,184 @@
+//! `list --open`: launch TODO locations in the user's editor.
+
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use dialoguer::MultiSelect;
+
+use todo_scan::model::TodoItem;
+
+/// How an editor expects to be told which line to open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineArg {
+    /// `+LINE FILE`, one file per launch (vim, emacs, nano, ...)
+    Plus,
+    /// `--line LINE FILE`, one file per launch (JetBrains IDEs)
+    LineFlag,
+    /// `--goto FILE:LINE ...`, every file in one launch (VS Code and forks)
+    Goto,
+    /// `FILE:LINE ...`, every file in one launch (Sublime Text, Zed, Helix)
+    Colon,
+}
+
+/// Pick the line convention from the editor command's program name.
+fn line_arg(editor: &str) -> LineArg {
+    let program = editor.split_whitespace().next().unwrap_or_default();
+    let name = Path::new(program)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    match name.as_str() {
+        "code" | "code-insiders" | "codium" | "cursor" | "windsurf" => LineArg::Goto,
+        "subl" | "sublime_text" | "zed" | "zeditor" | "hx" | "helix" => LineArg::Colon,
+        "idea" | "pycharm" | "webstorm" | "goland" | "clion" | "rustrover" | "phpstorm"
+        | "rubymine" => LineArg::LineFlag,
+        _ => LineArg::Plus,
+    }
+}
+
+/// Argument lists for each editor launch, in order.
+fn invocations(editor: &str, targets: &[(PathBuf, usize)]) -> Vec<Vec<String>> {
+    let location = |(path, line): &(PathBuf, usize)| format!("{}:{}", path.display(), line);
+    match line_arg(editor) {
+        LineArg::Plus => targets
+            .iter()
+            .map(|(path, line)| vec![format!("+{}", line), path.display().to_string()])
+            .collect(),
+        LineArg::LineFlag => targets
+            .iter()
+            .map(|(path, line)| {
+                vec![
+                    "--line".to_string(),
+                    line.to_string(),
+                    path.display().to_string(),
+                ]
+            })
+            .collect(),
+        LineArg::Goto => {
+            let mut args = vec!["--goto".to_string()];
+            args.extend(targets.iter().map(location));
+            vec![args]
+        }
+        LineArg::Colon => vec![targets.iter().map(location).collect()],
+    }
+}
+
+/// The editor to launch: `--editor`, else `$EDITOR`.
+pub fn resolve_editor(editor: Option<String>) -> Option<String> {
+    editor
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|e| !e.trim().is_empty())
+}
+
+/// Ask which of `items` to open. Returns the chosen subset, in order.
+pub fn pick_items(items: &[TodoItem]) -> Result<Vec<&TodoItem>> {
+    if !std::io::stdin().is_terminal() {
+        bail!("--pick requires an interactive terminal");
+    }
+    let labels: Vec<String> = items
+        .iter()
+        .map(|item| {
+            format!(
+                "{}:{} [{}] {}",
+                item.file, item.line, item.tag, item.message
+            )
+        })
+        .collect();
+    let chosen = MultiSelect::new()
+        .with_prompt("Select TODOs to open (space to toggle, enter to confirm)")
+        .items(&labels)
+        .interact()?;
+    Ok(chosen.into_iter().map(|i| &items[i]).collect())
+}
+
+/// Open each item's file at its line in `editor`, waiting for every launch
+/// to exit before the next. Item paths are resolved against `base_dir`.
+pub fn open_items(editor: &str, items: &[&TodoItem], base_dir: &Path) -> Result<()> {
+    let targets: Vec<(PathBuf, usize)> = items
+        .iter()
+        .map(|item| (base_dir.join(&item.file), item.line))
+        .collect();
+    if targets.is_empty() {
+        return Ok(());
+    }
+    for args in invocations(editor, &targets) {
+        let status = editor_command(editor, &args)
+            .status()
+            .with_context(|| format!("failed to launch editor '{}'", editor))?;
+        if !status.success() {
+            bail!("editor '{}' exited with {}", editor, status);
+        }
+    }
+    Ok(())
+}
+
+/// Run `editor` through the shell, like git does, so `$EDITOR` may carry
+/// its own arguments (e.g. `code --wait`).
+#[cfg(unix)]
+fn editor_command(editor: &str, args: &[String]) -> std::process::Command {
+    let mut cmd = std::process::Command::new("sh");
+    cmd.arg("-c")
+        .arg(format!("{} \"$@\"", editor))
+        .arg(editor)
+        .args(args);
+    cmd
+}
+
+#[cfg(windows)]
+fn editor_command(editor: &str, args: &[String]) -> std::process::Command {
+    let mut words = editor.split_whitespace();
+    let mut cmd = std::process::Command::new(words.next().unwrap_or(editor));
+    cmd.args(words).args(args);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets() -> Vec<(PathBuf, usize)> {
+        vec![
+            (PathBuf::from("/p/a.rs"), 3),
+            (PathBuf::from("/p/b.rs"), 10),
+        ]
+    }
+
+    #[test]
+    fn test_line_arg_by_program_name() {
+        assert_eq!(line_arg("vim"), LineArg::Plus);
+        assert_eq!(line_arg("/usr/bin/nvim -p"), LineArg::Plus);
+        assert_eq!(line_arg("code --wait"), LineArg::Goto);
+        assert_eq!(line_arg("/opt/zed/zed"), LineArg::Colon);
+        assert_eq!(line_arg("idea"), LineArg::LineFlag);
+        assert_eq!(line_arg("Code.exe"), LineArg::Goto);
+    }
+
+    #[test]
+    fn test_plus_editors_launch_once_per_item() {
+        assert_eq!(
+            invocations("vim", &targets()),
+            vec![vec!["+3", "/p/a.rs"], vec!["+10", "/p/b.rs"]]
+        );
+        assert_eq!(
+            invocations("idea", &targets()),
+            vec![
+                vec!["--line", "3", "/p/a.rs"],
+                vec!["--line", "10", "/p/b.rs"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_file_editors_launch_once() {
+        assert_eq!(
+            invocations("code --wait", &targets()),
+            vec![vec!["--goto", "/p/a.rs:3", "/p/b.rs:10"]]
+        );
+        assert_eq!(
+            invocations("subl", &targets()),
+            vec![vec!["/p/a.rs:3", "/p/b.rs:10"]]
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Error type returned by the scanner, config, git, diff and blame APIs, so
+//! embedding callers can match on the kind of failure instead of parsing
+//! messages.
+
+use std::fmt;
+
+use crate::git::GitError;
+
+/// Result alias for the library's fallible functions.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Why a library call failed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// `.todo-scan.toml` could not be read or holds an invalid value.
+    Config {
+        message: String,
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// A git command could not produce what was asked of it, e.g. an unknown
+    /// ref or a directory outside any repository.
+    Git(GitError),
+    /// Reading a file or talking to a subprocess failed.
+    Io {
+        context: String,
+        source: std::io::Error,
+    },
+    /// A glob pattern did not compile.
+    Glob {
+        pattern: String,
+        source: globset::Error,
+    },
+    /// A regular expression, such as the tags pattern, did not compile.
+    Regex {
+        pattern: String,
+        source: regex::Error,
+    },
+    /// An argument such as a duration, date or git ref is malformed.
+    Invalid(String),
+}
+
+impl Error {
+    pub(crate) fn config(
+        message: impl Into<String>,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Error::Config {
+            message: message.into(),
+            source: Some(Box::new(source)),
+        }
+    }
+
+    pub(crate) fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        Error::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    pub(crate) fn regex(pattern: &str, source: regex::Error) -> Self {
+        Error::Regex {
+            pattern: pattern.to_string(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Config { message, .. } => f.write_str(message),
+            Error::Git(err) => err.fmt(f),
+            Error::Io { context, .. } => f.write_str(context),
+            Error::Glob { pattern, .. } => write!(f, "invalid glob pattern: {}", pattern),
+            Error::Regex { pattern, .. } => write!(f, "invalid regex: {}", pattern),
+            Error::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Config { source, .. } => source.as_deref().map(|e| e as _),
+            Error::Io { source, .. } => Some(source),
+            Error::Glob { source, .. } => Some(source),
+            Error::Regex { source, .. } => Some(source),
+            Error::Git(_) | Error::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<GitError> for Error {
+    fn from(err: GitError) -> Self {
+        Error::Git(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Message and causes joined the way `anyhow` prints `{:#}`.
+    fn chain(err: &Error) -> String {
+        let mut out = err.to_string();
+        let mut source = std::error::Error::source(err);
+        while let Some(cause) = source {
+            out.push_str(&format!(": {}", cause));
+            source = cause.source();
+        }
+        out
+    }
+
+    #[test]
+    fn test_config_error_chains_its_cause() {
+        let inner = Error::Invalid("invalid size: ten megs".to_string());
+        let err = Error::config("invalid [scan] max_file_size", inner);
+        assert_eq!(
+            chain(&err),
+            "invalid [scan] max_file_size: invalid size: ten megs"
+        );
+    }
+
+    #[test]
+    fn test_regex_error_names_pattern() {
+        let pattern = "(".to_string();
+        let source = regex::Regex::new(&pattern).unwrap_err();
+        let err = Error::regex(&pattern, source);
+        assert!(err.to_string().starts_with("invalid regex: ("));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn test_git_error_is_not_repeated_as_its_own_cause() {
+        let err = Error::from(GitError("git show failed".to_string()));
+        assert_eq!(chain(&err), "git show failed");
+        assert!(matches!(err, Error::Git(_)));
+    }
+}
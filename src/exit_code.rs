@@ -0,0 +1,96 @@
+use todo_scan::Error;
+
+/// Command completed and, for check-family commands, found no violations.
+pub const SUCCESS: u8 = 0;
+/// A check-family command (`check`, `lint`, `clean --check`) found violations.
+pub const VIOLATION: u8 = 1;
+/// Invalid arguments or configuration. Also used for errors not otherwise
+/// classified. Matches clap's exit code for argument parsing errors.
+pub const USAGE: u8 = 2;
+/// Reading files or running git failed.
+pub const IO: u8 = 3;
+
+/// Exit code for a library error kind.
+fn for_kind(err: &Error) -> u8 {
+    match err {
+        Error::Git(_) | Error::Io { .. } => IO,
+        _ => USAGE,
+    }
+}
+
+/// Map an error returned from a command to its exit code. The outermost
+/// library error decides, so an unreadable config file counts as a config
+/// problem rather than an IO one.
+pub fn for_error(err: &anyhow::Error) -> u8 {
+    if let Some(kind) = err.chain().find_map(|cause| cause.downcast_ref::<Error>()) {
+        return for_kind(kind);
+    }
+    if err.chain().any(|cause| cause.is::<std::io::Error>()) {
+        IO
+    } else {
+        USAGE
+    }
+}
+
+/// Exit code for a check-family command. `exit_zero` reports violations
+/// without failing (`--exit-zero`).
+pub fn for_policy(passed: bool, exit_zero: bool) -> u8 {
+    if passed || exit_zero {
+        SUCCESS
+    } else {
+        VIOLATION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use todo_scan::git::GitError;
+
+    #[test]
+    fn test_for_error_defaults_to_usage() {
+        assert_eq!(for_error(&anyhow::anyhow!("invalid duration: x")), USAGE);
+    }
+
+    #[test]
+    fn test_for_error_io() {
+        let err = std::fs::read_to_string("/nonexistent/todo-scan/file")
+            .context("cannot read file")
+            .unwrap_err();
+        assert_eq!(for_error(&err), IO);
+    }
+
+    #[test]
+    fn test_for_error_git() {
+        let err = anyhow::Error::new(Error::Git(GitError("git show failed".to_string())))
+            .context("failed to compute diff");
+        assert_eq!(for_error(&err), IO);
+    }
+
+    #[test]
+    fn test_for_error_library_kinds() {
+        let err = anyhow::Error::new(Error::Invalid("invalid duration: x".to_string()));
+        assert_eq!(for_error(&err), USAGE);
+        let pattern = "(".to_string();
+        let source = regex::Regex::new(&pattern).unwrap_err();
+        let err = anyhow::Error::new(Error::Regex { pattern, source });
+        assert_eq!(for_error(&err), USAGE);
+    }
+
+    #[test]
+    fn test_for_error_config_wins_over_io() {
+        let err =
+            todo_scan::Config::load_file(std::path::Path::new("/nonexistent/.todo-scan.toml"))
+                .context("invalid configuration")
+                .unwrap_err();
+        assert_eq!(for_error(&err), USAGE);
+    }
+
+    #[test]
+    fn test_for_policy() {
+        assert_eq!(for_policy(true, false), SUCCESS);
+        assert_eq!(for_policy(false, false), VIOLATION);
+        assert_eq!(for_policy(false, true), SUCCESS);
+    }
+}
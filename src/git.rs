@@ -1,28 +1,163 @@
-use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+
+use crate::error::{Error, Result};
+
+/// A git invocation that ran but exited unsuccessfully.
+#[derive(Debug)]
+pub struct GitError(pub String);
+
+impl fmt::Display for GitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for GitError {}
 
 pub fn git_command(args: &[&str], cwd: &Path) -> Result<String> {
     let output = Command::new("git")
         .args(args)
         .current_dir(cwd)
         .output()
-        .with_context(|| format!("Failed to execute git {}", args.join(" ")))?;
+        .map_err(|e| Error::io(format!("Failed to execute git {}", args.join(" ")), e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim());
+        return Err(GitError(format!("git {} failed: {}", args.join(" "), stderr.trim())).into());
     }
 
-    let stdout =
-        String::from_utf8(output.stdout).with_context(|| "git output is not valid UTF-8")?;
+    String::from_utf8(output.stdout)
+        .map_err(|_| GitError(format!("git {} output is not valid UTF-8", args.join(" "))).into())
+}
+
+/// Read many `<rev>:<path>` blobs through one `git cat-file --batch` process
+/// instead of a `git show` per file. Entries that are missing, are not blobs,
+/// or are not valid UTF-8 come back as `None`, in the order of `specs`.
+pub fn read_blobs(specs: &[String], cwd: &Path) -> Result<Vec<Option<String>>> {
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch"])
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| Error::io("Failed to execute git cat-file --batch", e))?;
+
+    // Feed stdin from another thread so a full stdout pipe cannot deadlock us
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input: String = specs.iter().map(|s| format!("{s}\n")).collect();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
 
-    Ok(stdout)
+    let mut reader = BufReader::new(child.stdout.take().expect("stdout is piped"));
+    let mut blobs = Vec::with_capacity(specs.len());
+    for _ in specs {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .map_err(|e| Error::io("Failed to read from git cat-file", e))?;
+        // `<oid> <type> <size>`, or `<spec> missing` / `<spec> ambiguous`
+        let mut fields = header.trim_end().rsplitn(3, ' ');
+        let size = fields.next().and_then(|s| s.parse::<usize>().ok());
+        let kind = fields.next();
+        let Some(size) = size else {
+            blobs.push(None);
+            continue;
+        };
+        // The content is followed by a newline
+        let mut content = vec![0; size + 1];
+        reader
+            .read_exact(&mut content)
+            .map_err(|e| Error::io("Failed to read from git cat-file", e))?;
+        content.pop();
+        blobs.push(
+            (kind == Some("blob"))
+                .then(|| String::from_utf8(content).ok())
+                .flatten(),
+        );
+    }
+
+    writer
+        .join()
+        .expect("cat-file writer panicked")
+        .map_err(|e| Error::io("Failed to write to git cat-file", e))?;
+    let status = child
+        .wait()
+        .map_err(|e| Error::io("Failed to wait for git cat-file", e))?;
+    if !status.success() {
+        return Err(GitError("git cat-file --batch failed".to_string()).into());
+    }
+    Ok(blobs)
+}
+
+/// Tracked files with staged or unstaged changes, per `git status`, as
+/// paths relative to `cwd`. Untracked files and changes outside `cwd` are
+/// left out.
+pub fn modified_files(cwd: &Path) -> Result<HashSet<String>> {
+    let prefix = git_command(&["rev-parse", "--show-prefix"], cwd)?;
+    let prefix = prefix.trim_end_matches('\n');
+    let status = git_command(
+        &["status", "--porcelain=v1", "-z", "--untracked-files=no"],
+        cwd,
+    )?;
+
+    let mut files = HashSet::new();
+    let mut records = status.split('\0').filter(|r| !r.is_empty());
+    while let Some(record) = records.next() {
+        let (Some(code), Some(path)) = (record.get(..2), record.get(3..)) else {
+            continue;
+        };
+        // Renames and copies are followed by the original path
+        if code.contains(['R', 'C']) {
+            records.next();
+        }
+        if let Some(rel) = path.strip_prefix(prefix) {
+            files.insert(rel.to_string());
+        }
+    }
+    Ok(files)
+}
+
+/// The remote default branch as a ref such as `origin/main`.
+///
+/// Reads `origin/HEAD`, falling back to `origin/main` and then
+/// `origin/master` when the remote HEAD was never recorded locally.
+pub fn default_branch(cwd: &Path) -> Result<String> {
+    if let Ok(head) = git_command(
+        &[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ],
+        cwd,
+    ) {
+        let head = head.trim();
+        if !head.is_empty() {
+            return Ok(head.to_string());
+        }
+    }
+    for candidate in ["origin/main", "origin/master"] {
+        let refname = format!("refs/remotes/{candidate}");
+        if git_command(&["rev-parse", "--verify", "--quiet", &refname], cwd).is_ok() {
+            return Ok(candidate.to_string());
+        }
+    }
+    Err(GitError(
+        "could not determine the default branch: origin/HEAD, origin/main and origin/master \
+         are all missing (run `git remote set-head origin --auto` or pass --since)"
+            .to_string(),
+    )
+    .into())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::helpers::git;
     use tempfile::TempDir;
 
     #[test]
@@ -69,4 +204,101 @@ mod tests {
             stdout
         );
     }
+
+    #[test]
+    fn test_modified_files_staged_unstaged_and_renamed() {
+        let dir = TempDir::new().unwrap();
+        let cwd = dir.path();
+        git(cwd, &["init"]);
+        git(cwd, &["config", "user.email", "t@example.com"]);
+        git(cwd, &["config", "user.name", "T"]);
+        std::fs::create_dir(cwd.join("sub")).unwrap();
+        for f in ["a.rs", "b.rs", "c.rs", "old.rs", "sub/d.rs"] {
+            std::fs::write(cwd.join(f), "fn x() {}\n").unwrap();
+        }
+        git(cwd, &["add", "-A"]);
+        git(cwd, &["commit", "-m", "init"]);
+
+        std::fs::write(cwd.join("a.rs"), "// TODO: unstaged\n").unwrap();
+        std::fs::write(cwd.join("b.rs"), "// TODO: staged\n").unwrap();
+        git(cwd, &["add", "b.rs"]);
+        git(cwd, &["mv", "old.rs", "new.rs"]);
+        std::fs::write(cwd.join("sub/d.rs"), "// TODO: nested\n").unwrap();
+        std::fs::write(cwd.join("untracked.rs"), "// TODO: new\n").unwrap();
+
+        let mut files: Vec<String> = modified_files(cwd).unwrap().into_iter().collect();
+        files.sort();
+        assert_eq!(files, vec!["a.rs", "b.rs", "new.rs", "sub/d.rs"]);
+
+        // Paths are relative to a subdirectory cwd, limited to it
+        let sub: Vec<String> = modified_files(&cwd.join("sub"))
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(sub, vec!["d.rs"]);
+    }
+
+    fn init_repo(cwd: &Path) {
+        git(cwd, &["init", "-b", "trunk"]);
+        git(cwd, &["config", "user.email", "t@example.com"]);
+        git(cwd, &["config", "user.name", "T"]);
+        std::fs::write(cwd.join("a.rs"), "fn x() {}\n").unwrap();
+        git(cwd, &["add", "-A"]);
+        git(cwd, &["commit", "-m", "init"]);
+    }
+
+    #[test]
+    fn test_default_branch_from_origin_head() {
+        let dir = TempDir::new().unwrap();
+        let upstream = dir.path().join("upstream");
+        std::fs::create_dir(&upstream).unwrap();
+        init_repo(&upstream);
+        git(dir.path(), &["clone", "-q", "upstream", "clone"]);
+
+        let branch = default_branch(&dir.path().join("clone")).unwrap();
+        assert_eq!(branch, "origin/trunk");
+    }
+
+    #[test]
+    fn test_default_branch_falls_back_to_origin_main() {
+        let dir = TempDir::new().unwrap();
+        let cwd = dir.path();
+        init_repo(cwd);
+        git(cwd, &["update-ref", "refs/remotes/origin/main", "HEAD"]);
+
+        assert_eq!(default_branch(cwd).unwrap(), "origin/main");
+    }
+
+    #[test]
+    fn test_default_branch_without_remote_errors() {
+        let dir = TempDir::new().unwrap();
+        init_repo(dir.path());
+
+        let err = default_branch(dir.path()).unwrap_err().to_string();
+        assert!(
+            err.contains("could not determine the default branch"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_read_blobs_in_order_with_missing_entries() {
+        let dir = TempDir::new().unwrap();
+        init_repo(dir.path());
+        std::fs::write(dir.path().join("b.rs"), "// TODO: b\n").unwrap();
+        git(dir.path(), &["add", "-A"]);
+        git(dir.path(), &["commit", "-m", "b"]);
+
+        let specs = ["HEAD:b.rs", "HEAD:missing.rs", "HEAD:a.rs", "HEAD~1:b.rs"].map(String::from);
+        let blobs = read_blobs(&specs, dir.path()).unwrap();
+        assert_eq!(
+            blobs,
+            vec![
+                Some("// TODO: b\n".to_string()),
+                None,
+                Some("fn x() {}\n".to_string()),
+                None,
+            ]
+        );
+    }
 }
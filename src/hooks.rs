@@ -0,0 +1,204 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use todo_scan::git::git_command;
+
+const SHEBANG: &str = "#!/bin/sh\n";
+const BEGIN_SENTINEL: &str = "# >>> todo-scan >>>";
+const END_SENTINEL: &str = "# <<< todo-scan <<<";
+
+/// The block written into the hook, between the sentinel comments so it can
+/// be found again by a later install or by `--uninstall`.
+fn hook_section() -> String {
+    format!(
+        "{BEGIN_SENTINEL}\n\
+         # Added by `todo-scan hooks install`; remove with `todo-scan hooks install --uninstall`.\n\
+         todo-scan check --staged --max-new 0 || exit 1\n\
+         {END_SENTINEL}\n"
+    )
+}
+
+/// Byte range of the todo-scan section in `content`, including the end
+/// sentinel's line break.
+fn find_section(content: &str) -> Option<(usize, usize)> {
+    let start = content.find(BEGIN_SENTINEL)?;
+    let end_offset = content[start..].find(END_SENTINEL)?;
+    let mut end = start + end_offset + END_SENTINEL.len();
+    if content[end..].starts_with('\n') {
+        end += 1;
+    }
+    Some((start, end))
+}
+
+/// New hook contents with the todo-scan section installed. An existing
+/// section is refreshed in place; a hook todo-scan did not write is only
+/// appended to with `force`.
+fn install_into(existing: Option<&str>, force: bool) -> Result<String> {
+    let Some(content) = existing else {
+        return Ok(format!("{SHEBANG}{}", hook_section()));
+    };
+    if let Some((start, end)) = find_section(content) {
+        return Ok(format!(
+            "{}{}{}",
+            &content[..start],
+            hook_section(),
+            &content[end..]
+        ));
+    }
+    if !force {
+        bail!("a pre-commit hook already exists. Use --force to append the todo-scan check to it.");
+    }
+    let mut out = content.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push('\n');
+    out.push_str(&hook_section());
+    Ok(out)
+}
+
+/// Hook contents with the todo-scan section removed, or `None` if it has no
+/// such section. Blank lines left around the section are collapsed.
+fn remove_section(content: &str) -> Option<String> {
+    let (start, end) = find_section(content)?;
+    let before = content[..start].trim_end_matches('\n');
+    let after = content[end..].trim_start_matches('\n');
+    let mut out = before.to_string();
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    if !after.is_empty() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(after);
+    }
+    Some(out)
+}
+
+/// Whether a hook holds nothing but a shebang and blank lines.
+fn is_effectively_empty(content: &str) -> bool {
+    content
+        .lines()
+        .all(|line| line.trim().is_empty() || line.starts_with("#!"))
+}
+
+/// Path of the pre-commit hook, honoring `core.hooksPath` and worktrees.
+fn pre_commit_path(root: &Path) -> Result<PathBuf> {
+    let hooks_dir = git_command(&["rev-parse", "--git-path", "hooks"], root)
+        .context("hooks install requires a git repository")?;
+    Ok(root
+        .join(hooks_dir.trim_end_matches('\n'))
+        .join("pre-commit"))
+}
+
+pub fn cmd_hooks_install(root: &Path, force: bool, uninstall: bool) -> Result<()> {
+    let path = pre_commit_path(root)?;
+    let existing = if path.exists() {
+        Some(
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("cannot read {}", path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    if uninstall {
+        let Some(content) = existing.as_deref() else {
+            eprintln!(
+                "No pre-commit hook at {}; nothing to remove",
+                path.display()
+            );
+            return Ok(());
+        };
+        let Some(remaining) = remove_section(content) else {
+            eprintln!(
+                "{} has no todo-scan section; nothing to remove",
+                path.display()
+            );
+            return Ok(());
+        };
+        if is_effectively_empty(&remaining) {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("cannot remove {}", path.display()))?;
+            eprintln!("Removed {}", path.display());
+        } else {
+            std::fs::write(&path, remaining)
+                .with_context(|| format!("cannot write {}", path.display()))?;
+            eprintln!("Removed the todo-scan section from {}", path.display());
+        }
+        return Ok(());
+    }
+
+    let updated = install_into(existing.as_deref(), force)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+    std::fs::write(&path, updated).with_context(|| format!("cannot write {}", path.display()))?;
+    if existing.is_none() {
+        make_executable(&path)?;
+    }
+    eprintln!("Installed pre-commit hook at {}", path.display());
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("cannot make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_into_new_hook() {
+        let out = install_into(None, false).unwrap();
+        assert!(out.starts_with("#!/bin/sh\n# >>> todo-scan >>>\n"));
+        assert!(out.contains("todo-scan check --staged --max-new 0"));
+        assert!(out.ends_with("# <<< todo-scan <<<\n"));
+    }
+
+    #[test]
+    fn test_install_into_existing_hook_requires_force() {
+        let hook = "#!/bin/sh\ncargo fmt --check\n";
+        let err = install_into(Some(hook), false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+
+        let out = install_into(Some(hook), true).unwrap();
+        assert!(out.starts_with("#!/bin/sh\ncargo fmt --check\n\n# >>> todo-scan >>>"));
+    }
+
+    #[test]
+    fn test_install_into_refreshes_existing_section() {
+        let hook = "#!/bin/sh\n# >>> todo-scan >>>\nold\n# <<< todo-scan <<<\necho done\n";
+        let out = install_into(Some(hook), false).unwrap();
+        assert!(!out.contains("old"));
+        assert_eq!(out.matches(BEGIN_SENTINEL).count(), 1);
+        assert!(out.ends_with("# <<< todo-scan <<<\necho done\n"));
+    }
+
+    #[test]
+    fn test_remove_section_restores_original_hook() {
+        let hook = "#!/bin/sh\ncargo fmt --check\n";
+        let installed = install_into(Some(hook), true).unwrap();
+        assert_eq!(remove_section(&installed).unwrap(), hook);
+        assert_eq!(remove_section(hook), None);
+    }
+
+    #[test]
+    fn test_remove_section_leaves_empty_hook() {
+        let installed = install_into(None, false).unwrap();
+        let remaining = remove_section(&installed).unwrap();
+        assert!(is_effectively_empty(&remaining));
+        assert!(!is_effectively_empty("#!/bin/sh\nmake lint\n"));
+    }
+}
@@ -54,13 +54,25 @@ fn collect_suggested_dirs(hints: &[ProjectHint]) -> Vec<&'static str> {
     dirs
 }
 
-pub fn cmd_init(root: &Path, non_interactive: bool) -> Result<()> {
+/// Answers given on the command line. Each flag that is set skips its
+/// prompt; `yes` takes the detected defaults for the rest.
+#[derive(Debug, Default)]
+pub struct InitOptions {
+    pub yes: bool,
+    pub tags: Vec<String>,
+    pub exclude: Vec<String>,
+    pub max: Option<usize>,
+    pub force: bool,
+}
+
+pub fn cmd_init(root: &Path, opts: &InitOptions) -> Result<()> {
     let config_path = root.join(".todo-scan.toml");
+    let non_interactive = opts.yes;
 
     // Check for existing config
-    if config_path.exists() {
+    if config_path.exists() && !opts.force {
         if non_interactive {
-            bail!(".todo-scan.toml already exists. Use interactive mode to overwrite.");
+            bail!(".todo-scan.toml already exists. Use --force to overwrite.");
         }
         let overwrite = Confirm::new()
             .with_prompt(".todo-scan.toml already exists. Overwrite?")
@@ -80,7 +92,9 @@ pub fn cmd_init(root: &Path, non_interactive: bool) -> Result<()> {
     }
 
     // Select tags
-    let selected_tags: Vec<String> = if non_interactive {
+    let selected_tags: Vec<String> = if !opts.tags.is_empty() {
+        opts.tags.iter().map(|t| t.trim().to_string()).collect()
+    } else if non_interactive {
         ALL_TAGS.iter().map(|s| s.to_string()).collect()
     } else {
         let defaults: Vec<bool> = vec![true; ALL_TAGS.len()];
@@ -94,7 +108,9 @@ pub fn cmd_init(root: &Path, non_interactive: bool) -> Result<()> {
 
     // Select exclude dirs
     let suggested_dirs = collect_suggested_dirs(&hints);
-    let selected_dirs: Vec<String> = if non_interactive {
+    let selected_dirs: Vec<String> = if !opts.exclude.is_empty() {
+        opts.exclude.iter().map(|d| d.trim().to_string()).collect()
+    } else if non_interactive {
         suggested_dirs.iter().map(|s| s.to_string()).collect()
     } else if suggested_dirs.is_empty() {
         Vec::new()
@@ -112,8 +128,8 @@ pub fn cmd_init(root: &Path, non_interactive: bool) -> Result<()> {
     };
 
     // CI check max
-    let check_max: Option<usize> = if non_interactive {
-        None
+    let check_max: Option<usize> = if opts.max.is_some() || non_interactive {
+        opts.max
     } else {
         let want_max = Confirm::new()
             .with_prompt("Set a maximum TODO count for CI checks?")
@@ -170,6 +186,13 @@ fn build_config_toml(tags: &[String], exclude_dirs: &[String], check_max: Option
 mod tests {
     use super::*;
 
+    fn yes() -> InitOptions {
+        InitOptions {
+            yes: true,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_detect_rust_project() {
         let dir = tempfile::TempDir::new().unwrap();
@@ -265,7 +288,7 @@ mod tests {
     #[test]
     fn test_cmd_init_non_interactive_creates_config() {
         let dir = tempfile::TempDir::new().unwrap();
-        let result = cmd_init(dir.path(), true);
+        let result = cmd_init(dir.path(), &yes());
         assert!(result.is_ok(), "cmd_init should succeed: {:?}", result);
         let config_path = dir.path().join(".todo-scan.toml");
         assert!(config_path.exists(), ".todo-scan.toml should be created");
@@ -284,9 +307,10 @@ mod tests {
         let dir = tempfile::TempDir::new().unwrap();
         let config_path = dir.path().join(".todo-scan.toml");
         std::fs::write(&config_path, "tags = [\"TODO\"]").unwrap();
-        let result = cmd_init(dir.path(), true);
+        let result = cmd_init(dir.path(), &yes());
         assert!(result.is_err(), "cmd_init should fail when config exists");
         let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("--force"), "error should suggest --force");
         assert!(
             err_msg.contains("already exists"),
             "error should mention 'already exists', got: {}",
@@ -363,7 +387,7 @@ mod tests {
     fn test_cmd_init_non_interactive_rust_project_includes_target() {
         let dir = tempfile::TempDir::new().unwrap();
         std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
-        let result = cmd_init(dir.path(), true);
+        let result = cmd_init(dir.path(), &yes());
         assert!(result.is_ok(), "cmd_init should succeed: {:?}", result);
         let content = std::fs::read_to_string(dir.path().join(".todo-scan.toml")).unwrap();
         let parsed: crate::config::Config = toml::from_str(&content).unwrap();
@@ -378,7 +402,7 @@ mod tests {
     fn test_cmd_init_non_interactive_node_project_includes_node_modules() {
         let dir = tempfile::TempDir::new().unwrap();
         std::fs::write(dir.path().join("package.json"), "{}").unwrap();
-        let result = cmd_init(dir.path(), true);
+        let result = cmd_init(dir.path(), &yes());
         assert!(result.is_ok(), "cmd_init should succeed: {:?}", result);
         let content = std::fs::read_to_string(dir.path().join(".todo-scan.toml")).unwrap();
         let parsed: crate::config::Config = toml::from_str(&content).unwrap();
@@ -393,7 +417,7 @@ mod tests {
     fn test_cmd_init_non_interactive_go_project_includes_vendor() {
         let dir = tempfile::TempDir::new().unwrap();
         std::fs::write(dir.path().join("go.mod"), "module example").unwrap();
-        let result = cmd_init(dir.path(), true);
+        let result = cmd_init(dir.path(), &yes());
         assert!(result.is_ok(), "cmd_init should succeed: {:?}", result);
         let content = std::fs::read_to_string(dir.path().join(".todo-scan.toml")).unwrap();
         let parsed: crate::config::Config = toml::from_str(&content).unwrap();
@@ -404,6 +428,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cmd_init_flags_override_detected_defaults() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        let opts = InitOptions {
+            yes: true,
+            tags: vec!["TODO".to_string(), " FIXME".to_string()],
+            exclude: vec!["build".to_string()],
+            max: Some(100),
+            force: false,
+        };
+        cmd_init(dir.path(), &opts).unwrap();
+        let content = std::fs::read_to_string(dir.path().join(".todo-scan.toml")).unwrap();
+        let parsed: crate::config::Config = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.tags, vec!["TODO", "FIXME"]);
+        assert_eq!(parsed.exclude_dirs, vec!["build"]);
+        assert_eq!(parsed.check.max, Some(100));
+    }
+
+    #[test]
+    fn test_cmd_init_omitted_flags_use_detected_defaults() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("go.mod"), "module example").unwrap();
+        let opts = InitOptions {
+            yes: true,
+            tags: vec!["BUG".to_string()],
+            ..Default::default()
+        };
+        cmd_init(dir.path(), &opts).unwrap();
+        let content = std::fs::read_to_string(dir.path().join(".todo-scan.toml")).unwrap();
+        let parsed: crate::config::Config = toml::from_str(&content).unwrap();
+        assert_eq!(parsed.tags, vec!["BUG"]);
+        assert_eq!(parsed.exclude_dirs, vec!["vendor"]);
+        assert_eq!(parsed.check.max, None);
+    }
+
+    #[test]
+    fn test_cmd_init_force_overwrites_existing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = dir.path().join(".todo-scan.toml");
+        std::fs::write(&config_path, "tags = [\"NOTE\"]").unwrap();
+        let opts = InitOptions {
+            yes: true,
+            tags: vec!["TODO".to_string()],
+            force: true,
+            ..Default::default()
+        };
+        cmd_init(dir.path(), &opts).unwrap();
+        let parsed: crate::config::Config =
+            toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(parsed.tags, vec!["TODO"]);
+    }
+
     // --- collect_suggested_dirs edge cases ---
 
     #[test]
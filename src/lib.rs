@@ -0,0 +1,57 @@
+//! Library API for todo-scan.
+//!
+//! The `todo-scan` binary is a thin CLI over these modules. Everything here
+//! returns typed results and never prints or exits, so the scanner, diff,
+//! blame, stats, and check engines can be embedded in other tools:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! let root = Path::new(".");
+//! let config = todo_scan::Config::load(root)?;
+//! let scan = todo_scan::scan_directory(root, &config)?;
+//! let stats = todo_scan::compute_stats(&scan, None);
+//! println!("{} TODOs", stats.total_items);
+//! # Ok::<(), todo_scan::Error>(())
+//! ```
+//!
+//! The scan honors the whole config, `[custom_tags]` included. Helpers that
+//! rank tags, such as [`tasks::sort_by_priority`], take the configured
+//! severities from [`Config::tag_styles`].
+//!
+//! The scanner, config, git, diff, and blame functions fail with
+//! [`Error`], whose variants tell configuration problems, git failures and
+//! IO errors apart.
+
+pub mod blame;
+pub mod brief;
+pub mod cache;
+pub mod check;
+pub mod clean;
+pub mod config;
+pub mod context;
+pub mod date_utils;
+pub mod deadline;
+pub mod diff;
+pub mod error;
+pub mod git;
+pub mod lint;
+pub mod model;
+pub mod relate;
+pub mod report;
+pub mod scanner;
+pub mod search;
+pub mod stats;
+pub mod tasks;
+#[cfg(test)]
+mod test_helpers;
+pub mod workspace;
+
+pub use blame::compute_blame;
+pub use check::run_check;
+pub use config::Config;
+pub use diff::compute_diff;
+pub use error::Error;
+pub use model::{CheckResult, DiffResult, ScanResult, StatsResult, TodoItem};
+pub use scanner::scan_directory;
+pub use stats::compute_stats;
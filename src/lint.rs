@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 
 use regex::Regex;
 
+use crate::clean::normalize_message;
 use crate::config::Config;
+use crate::deadline::{self, Deadline};
 use crate::model::{LintResult, LintViolation, ScanResult, TodoItem};
 use crate::scanner;
 
@@ -14,6 +16,9 @@ pub struct LintOverrides {
     pub require_issue_ref: Vec<String>,
     pub uppercase_tag: bool,
     pub require_colon: bool,
+    pub max_density: Option<f64>,
+    pub escalate_overdue: bool,
+    pub no_local_duplicates: bool,
 }
 
 struct ResolvedLint {
@@ -23,6 +28,10 @@ struct ResolvedLint {
     require_issue_ref: Vec<String>,
     uppercase_tag: bool,
     require_colon: bool,
+    max_density: Option<f64>,
+    escalate_overdue: bool,
+    no_local_duplicates: bool,
+    today: Deadline,
 }
 
 fn resolve_config(config: &Config, overrides: &LintOverrides) -> ResolvedLint {
@@ -43,6 +52,12 @@ fn resolve_config(config: &Config, overrides: &LintOverrides) -> ResolvedLint {
         },
         uppercase_tag: overrides.uppercase_tag || config.lint.uppercase_tag.unwrap_or(true),
         require_colon: overrides.require_colon || config.lint.require_colon.unwrap_or(true),
+        max_density: overrides.max_density.or(config.lint.max_density),
+        escalate_overdue: overrides.escalate_overdue
+            || config.lint.escalate_overdue.unwrap_or(true),
+        no_local_duplicates: overrides.no_local_duplicates
+            || config.lint.no_local_duplicates.unwrap_or(false),
+        today: deadline::today(),
     }
 }
 
@@ -65,6 +80,14 @@ pub fn run_lint(
         check_raw_text_rules(scan, config, root, &resolved, &mut violations);
     }
 
+    // Phase 3: File-level rules (max_density, no_local_duplicates)
+    if let Some(max_density) = resolved.max_density {
+        check_density(scan, root, max_density, &mut violations);
+    }
+    if resolved.no_local_duplicates {
+        check_local_duplicates(scan, &mut violations);
+    }
+
     // Sort by file, then line
     violations.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
 
@@ -147,6 +170,24 @@ fn check_metadata_rules(
             });
         }
     }
+
+    // escalate_overdue
+    if resolved.escalate_overdue {
+        if let (Some(target), Some(deadline)) = (&item.escalate_to, &item.deadline) {
+            if *target != item.tag && deadline.is_expired(&resolved.today) {
+                violations.push(LintViolation {
+                    rule: "escalate_overdue".to_string(),
+                    message: format!(
+                        "{} passed its deadline {} without being escalated to {}",
+                        item.tag, deadline, target
+                    ),
+                    file: item.file.clone(),
+                    line: item.line,
+                    suggestion: Some(format!("{}: {}", target, item.message)),
+                });
+            }
+        }
+    }
 }
 
 fn check_raw_text_rules(
@@ -162,11 +203,15 @@ fn check_raw_text_rules(
         file_items.entry(item.file.as_str()).or_default().push(item);
     }
 
-    // Build regex for raw-text analysis
+    // Build regex for raw-text analysis; an escalation marker (`TODO->FIXME`)
+    // may sit between the tag and its parentheses
     let tags = config.tags.join("|");
-    let raw_re = Regex::new(&format!(r"(?i)\b({})(?:\([^)]*\))?(:)?", tags))
+    let raw_re = Regex::new(&format!(r"(?i)\b({})(?:->[\w-]+)?(?:\([^)]*\))?(:)?", tags))
         .expect("invalid raw lint regex");
 
+    let comment_prefixes = scanner::prefix_strs(config.scan.comment_prefixes.as_deref());
+    let line_start_prefixes = scanner::prefix_strs(config.scan.line_start_prefixes.as_deref());
+
     for (file_path, items) in &file_items {
         let full_path = root.join(file_path);
         let content = match std::fs::read_to_string(&full_path) {
@@ -174,6 +219,8 @@ fn check_raw_text_rules(
             Err(_) => continue,
         };
         let lines: Vec<&str> = content.lines().collect();
+        let syntax = scanner::CommentSyntax::for_path(file_path)
+            .with_overrides(comment_prefixes.as_deref(), line_start_prefixes.as_deref());
 
         for item in items {
             let line_idx = item.line.saturating_sub(1);
@@ -185,7 +232,7 @@ fn check_raw_text_rules(
             // Find the tag occurrence that is inside a comment
             for caps in raw_re.captures_iter(line) {
                 let tag_match = caps.get(1).unwrap();
-                if !scanner::is_in_comment(line, tag_match.start()) {
+                if !scanner::is_in_comment(line, tag_match.start(), &syntax) {
                     continue;
                 }
 
@@ -224,6 +271,91 @@ fn check_raw_text_rules(
     }
 }
 
+/// Flag files whose share of TODO lines exceeds `max_density`. The
+/// violation points at the file's first TODO.
+fn check_density(
+    scan: &ScanResult,
+    root: &Path,
+    max_density: f64,
+    violations: &mut Vec<LintViolation>,
+) {
+    // Count distinct lines, so a multi-tag line counts once
+    let mut file_lines: HashMap<&str, BTreeSet<usize>> = HashMap::new();
+    for item in &scan.items {
+        file_lines
+            .entry(item.file.as_str())
+            .or_default()
+            .insert(item.line);
+    }
+
+    for (file_path, todo_lines) in &file_lines {
+        let total_lines = match std::fs::read_to_string(root.join(file_path)) {
+            Ok(content) => content.lines().count(),
+            Err(_) => continue,
+        };
+        if total_lines == 0 {
+            continue;
+        }
+        let density = todo_lines.len() as f64 / total_lines as f64;
+        if density > max_density {
+            violations.push(LintViolation {
+                rule: "max_density".to_string(),
+                message: format!(
+                    "TODO density {:.1}% ({} of {} lines) exceeds maximum {:.1}%",
+                    density * 100.0,
+                    todo_lines.len(),
+                    total_lines,
+                    max_density * 100.0
+                ),
+                file: file_path.to_string(),
+                line: todo_lines.first().copied().unwrap_or(1),
+                suggestion: Some("Resolve or consolidate TODOs in this file".to_string()),
+            });
+        }
+    }
+}
+
+/// Flag a message repeated within one file, most likely a copy-paste
+/// leftover. Messages compare as `clean` duplicates do; every occurrence
+/// after the first is reported and points back at it.
+fn check_local_duplicates(scan: &ScanResult, violations: &mut Vec<LintViolation>) {
+    let mut by_file: HashMap<&str, Vec<&TodoItem>> = HashMap::new();
+    for item in &scan.items {
+        by_file.entry(item.file.as_str()).or_default().push(item);
+    }
+
+    for items in by_file.values_mut() {
+        items.sort_by_key(|item| item.line);
+        let mut first_lines: HashMap<String, usize> = HashMap::new();
+        for item in items.iter() {
+            let normalized = normalize_message(&item.message);
+            if normalized.is_empty() {
+                continue;
+            }
+            match first_lines.get(&normalized) {
+                Some(&first) => violations.push(LintViolation {
+                    rule: "no_local_duplicates".to_string(),
+                    message: format!(
+                        "Duplicate of the {} on line {}: \"{}\"",
+                        item.tag,
+                        first,
+                        item.message.trim()
+                    ),
+                    file: item.file.clone(),
+                    line: item.line,
+                    suggestion: Some(format!(
+                        "Remove this comment or merge it into line {}",
+                        first
+                    )),
+                }),
+                None => {
+                    first_lines.insert(normalized, item.line);
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +370,9 @@ mod tests {
             require_issue_ref: vec![],
             uppercase_tag: false,
             require_colon: false,
+            max_density: None,
+            escalate_overdue: false,
+            no_local_duplicates: false,
         }
     }
 
@@ -246,6 +381,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -261,6 +397,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "real message")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -276,6 +413,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "this is a long message")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -296,6 +434,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no author")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -318,6 +457,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -337,6 +477,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Bug, "no issue ref")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -359,6 +500,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![item],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -378,6 +520,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Note, "just a note")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -401,6 +544,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "lowercase tag")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -424,6 +568,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "uppercase tag")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -446,6 +591,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "fix without colon")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -469,6 +615,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("test.rs", 1, Tag::Todo, "fix with colon")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -487,6 +634,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "valid message")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -504,6 +652,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -527,6 +676,7 @@ mod tests {
                 make_item("a.rs", 2, Tag::Bug, "no issue ref"),
             ],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -546,6 +696,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Fixme, "missing author")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -564,6 +715,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no ref")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -587,6 +739,7 @@ mod tests {
                 "a fairly long message here",
             )],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -605,6 +758,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "12345")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -624,6 +778,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "no author")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -645,6 +800,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "   ")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -668,6 +824,7 @@ mod tests {
                 make_item("a.rs", 2, Tag::Fixme, ""),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -693,6 +850,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let config = Config::default();
@@ -714,6 +872,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("nonexistent.rs", 1, Tag::Todo, "msg")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -733,6 +892,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Fixme, "")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let mut config = Config::default();
@@ -747,4 +907,193 @@ mod tests {
         assert!(suggestion.contains("FIXME"));
         assert!(suggestion.contains("<description>"));
     }
+
+    // --- max_density ---
+
+    fn density_scan(file: &str, lines: &[usize]) -> ScanResult {
+        ScanResult {
+            items: lines
+                .iter()
+                .map(|&line| make_item(file, line, Tag::Todo, "msg"))
+                .collect(),
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        }
+    }
+
+    fn duplicates_scan() -> ScanResult {
+        ScanResult {
+            items: vec![
+                make_item("a.rs", 9, Tag::Todo, "Handle  errors"),
+                make_item("a.rs", 2, Tag::Todo, "handle errors"),
+                make_item("a.rs", 5, Tag::Fixme, "something else"),
+                make_item("a.rs", 14, Tag::Fixme, "handle errors"),
+                make_item("b.rs", 1, Tag::Todo, "handle errors"),
+            ],
+            files_scanned: 2,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        }
+    }
+
+    #[test]
+    fn test_no_local_duplicates_flags_later_occurrences_in_a_file() {
+        let overrides = LintOverrides {
+            no_local_duplicates: true,
+            ..default_overrides()
+        };
+        let result = run_lint(
+            &duplicates_scan(),
+            &Config::default(),
+            &overrides,
+            Path::new("/tmp"),
+        );
+        let dups: Vec<&LintViolation> = result
+            .violations
+            .iter()
+            .filter(|v| v.rule == "no_local_duplicates")
+            .collect();
+        // b.rs repeats the message too, but in another file
+        assert_eq!(dups.len(), 2);
+        assert_eq!((dups[0].file.as_str(), dups[0].line), ("a.rs", 9));
+        assert_eq!(
+            dups[0].message,
+            "Duplicate of the TODO on line 2: \"Handle  errors\""
+        );
+        assert_eq!((dups[1].file.as_str(), dups[1].line), ("a.rs", 14));
+        assert!(dups[1].message.contains("on line 2"));
+    }
+
+    #[test]
+    fn test_no_local_duplicates_off_by_default_and_enabled_by_config() {
+        let scan = duplicates_scan();
+        let result = run_lint(
+            &scan,
+            &Config::default(),
+            &default_overrides(),
+            Path::new("/tmp"),
+        );
+        assert!(!result
+            .violations
+            .iter()
+            .any(|v| v.rule == "no_local_duplicates"));
+
+        let mut config = Config::default();
+        config.lint.no_local_duplicates = Some(true);
+        let result = run_lint(&scan, &config, &default_overrides(), Path::new("/tmp"));
+        assert_eq!(
+            result
+                .violations
+                .iter()
+                .filter(|v| v.rule == "no_local_duplicates")
+                .count(),
+            2
+        );
+    }
+
+    fn density_config() -> Config {
+        let mut config = Config::default();
+        config.lint.no_bare_tags = Some(false);
+        config.lint.uppercase_tag = Some(false);
+        config.lint.require_colon = Some(false);
+        config.lint.max_density = Some(0.05);
+        config
+    }
+
+    #[test]
+    fn test_max_density_flags_dense_file() {
+        let dir = tempfile::tempdir().unwrap();
+        // 3 TODOs in 10 lines
+        std::fs::write(dir.path().join("dense.rs"), "x\n".repeat(10)).unwrap();
+        let scan = density_scan("dense.rs", &[2, 4, 6]);
+
+        let result = run_lint(&scan, &density_config(), &default_overrides(), dir.path());
+        assert!(!result.passed);
+        assert_eq!(result.violations.len(), 1);
+        let v = &result.violations[0];
+        assert_eq!(v.rule, "max_density");
+        assert_eq!(v.file, "dense.rs");
+        assert_eq!(v.line, 2);
+        assert_eq!(
+            v.message,
+            "TODO density 30.0% (3 of 10 lines) exceeds maximum 5.0%"
+        );
+    }
+
+    #[test]
+    fn test_max_density_passes_sparse_file() {
+        let dir = tempfile::tempdir().unwrap();
+        // 2 TODOs in 100 lines
+        std::fs::write(dir.path().join("sparse.rs"), "x\n".repeat(100)).unwrap();
+        let scan = density_scan("sparse.rs", &[10, 50]);
+
+        let result = run_lint(&scan, &density_config(), &default_overrides(), dir.path());
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_max_density_counts_shared_lines_once_and_override_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "x\n".repeat(20)).unwrap();
+        // Two items on one line make a density of 1/20
+        let scan = density_scan("a.rs", &[3, 3]);
+
+        let result = run_lint(&scan, &density_config(), &default_overrides(), dir.path());
+        assert!(result.passed);
+
+        let overrides = LintOverrides {
+            max_density: Some(0.01),
+            ..default_overrides()
+        };
+        let result = run_lint(&scan, &density_config(), &overrides, dir.path());
+        assert_eq!(result.violations[0].rule, "max_density");
+    }
+
+    #[test]
+    fn test_escalate_overdue_flags_unescalated_past_deadline() {
+        let mut overdue = make_item("a.rs", 1, Tag::Todo, "escalate me");
+        overdue.escalate_to = Some(Tag::Fixme);
+        overdue.deadline = Some(Deadline {
+            year: 2000,
+            month: 1,
+            day: 1,
+        });
+        // Not yet due
+        let mut pending = overdue.clone();
+        pending.line = 2;
+        pending.deadline = Some(Deadline {
+            year: 2999,
+            month: 1,
+            day: 1,
+        });
+        // Already escalated
+        let mut done = overdue.clone();
+        done.line = 3;
+        done.tag = Tag::Fixme;
+        let scan = ScanResult {
+            items: vec![overdue, pending, done],
+            ignored_items: vec![],
+            files_scanned: 1,
+            scanned_files: vec![],
+        };
+        let mut config = Config::default();
+        config.lint.uppercase_tag = Some(false);
+        config.lint.require_colon = Some(false);
+
+        let result = run_lint(&scan, &config, &default_overrides(), Path::new("/tmp"));
+        assert_eq!(result.violation_count, 1);
+        let v = &result.violations[0];
+        assert_eq!(v.rule, "escalate_overdue");
+        assert_eq!(v.line, 1);
+        assert_eq!(
+            v.message,
+            "TODO passed its deadline 2000-01-01 without being escalated to FIXME"
+        );
+        assert_eq!(v.suggestion.as_deref(), Some("FIXME: escalate me"));
+
+        config.lint.escalate_overdue = Some(false);
+        let result = run_lint(&scan, &config, &default_overrides(), Path::new("/tmp"));
+        assert!(result.passed);
+    }
 }
@@ -1,97 +1,225 @@
-mod blame;
-mod brief;
-mod cache;
-mod check;
-mod clean;
 mod cli;
 mod cmd;
 mod completions;
-mod config;
-mod context;
-mod date_utils;
-mod deadline;
-mod diff;
-mod git;
+mod editor;
+mod exit_code;
+mod hooks;
 mod init;
-mod lint;
-mod model;
 mod output;
-mod relate;
-mod report;
-mod scanner;
-mod search;
-mod stats;
-mod tasks;
+mod profile;
+mod progress;
+mod schema;
 #[cfg(test)]
 mod test_helpers;
+mod validate;
 mod watch;
-mod workspace;
 
-use std::process;
+use todo_scan::{
+    blame, brief, cache, check, clean, config, context, date_utils, deadline, diff, git, lint,
+    model, relate, report, scanner, search, stats, tasks, workspace,
+};
+
+use std::process::ExitCode;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 
 use check::CheckOverrides;
-use cli::{Cli, Command, WorkspaceAction};
+use cli::{
+    Cli, ColorChoice, Command, ConfigAction, Format, HooksAction, PathBase, WorkspaceAction,
+};
 use cmd::*;
 use config::Config;
 use lint::LintOverrides;
 
-fn main() {
-    if let Err(e) = run() {
-        eprintln!("error: {:#}", e);
-        process::exit(2);
+fn main() -> ExitCode {
+    let result = run();
+    profile::report();
+    match result {
+        Ok(code) => ExitCode::from(code),
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            ExitCode::from(exit_code::for_error(&e))
+        }
     }
 }
 
-fn run() -> Result<()> {
-    let cli = Cli::parse();
+/// Run the selected command and return its exit code. Errors are mapped to
+/// an exit code by `exit_code::for_error`.
+fn run() -> Result<u8> {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Some(commands) = cli.format.supported_commands() {
+        let command = matches.subcommand_name().unwrap_or_default();
+        anyhow::ensure!(
+            commands.contains(&command),
+            "--format {} is only supported by {}",
+            cli.format.name(),
+            commands.join(", ")
+        );
+    }
+    if cli.profile {
+        profile::enable(matches!(cli.format, Format::Json));
+    }
+
+    let color = if cli.no_color {
+        ColorChoice::Never
+    } else {
+        cli.color.clone()
+    };
+    output::configure_color(&color, std::env::var("NO_COLOR").ok().as_deref());
+    output::set_quiet(cli.quiet);
+    progress::configure(&cli.progress, &cli.format, cli.quiet);
+    output::configure_width(cli.width);
+    cmd::set_exclude_paths(&cli.exclude_path)?;
 
     let root = match cli.root {
         Some(p) => p,
         None => std::env::current_dir().context("cannot determine current directory")?,
     };
+    if cli.envelope {
+        output::set_envelope(matches.subcommand_name().unwrap_or_default(), &root);
+    }
 
     match cli.command {
         // Commands that don't need config
-        Command::Init { yes } => init::cmd_init(&root, yes),
-        Command::Completions { shell } => completions::cmd_completions(shell),
+        Command::Init {
+            yes,
+            tags,
+            exclude,
+            max,
+            force,
+        } => {
+            let opts = init::InitOptions {
+                yes,
+                tags,
+                exclude,
+                max,
+                force,
+            };
+            init::cmd_init(&root, &opts).map(|()| exit_code::SUCCESS)
+        }
+        Command::Hooks { action } => match action {
+            HooksAction::Install { force, uninstall } => {
+                hooks::cmd_hooks_install(&root, force, uninstall).map(|()| exit_code::SUCCESS)
+            }
+        },
+        Command::Config { action } => match action {
+            ConfigAction::Validate => {
+                validate::cmd_config_validate(&root, cli.config.as_deref(), &cli.format)
+                    .map(|()| exit_code::SUCCESS)
+            }
+        },
+        Command::Completions { shell } => {
+            completions::cmd_completions(shell).map(|()| exit_code::SUCCESS)
+        }
+        Command::Schema { command } => schema::cmd_schema(command).map(|()| exit_code::SUCCESS),
 
         // Commands that need config
         command => {
-            let config = if let Some(ref config_path) = cli.config {
-                let content = std::fs::read_to_string(config_path)?;
-                toml::from_str(&content)?
-            } else {
-                Config::load(&root)?
-            };
+            // Checked before loading, which would otherwise consume stdin first
+            if cli.config.as_deref().is_some_and(is_stdin) {
+                anyhow::ensure!(
+                    !matches!(command, Command::List { files: true, .. }),
+                    "--config - and --files cannot both read stdin"
+                );
+            }
+            let mut config = profile::time("config", || load_config(&root, cli.config.as_deref()))
+                .context("invalid configuration")?;
+            if cli.no_ignore {
+                config.scan.respect_gitignore = Some(false);
+            }
+            if cli.utf16_columns {
+                config.scan.utf16_columns = Some(true);
+            }
+            if let Some(ref dir) = cli.cache_dir {
+                // Relative to the working directory, unlike [cache] path
+                let dir = std::path::absolute(dir)
+                    .with_context(|| format!("invalid --cache-dir: {}", dir.display()))?;
+                config.cache.path = Some(dir.to_string_lossy().into_owned());
+            }
+            if let Some(threads) = cli.threads {
+                config.scan.threads = Some(threads);
+            }
+            if let Some(base) = cli.path_base {
+                config.scan.path_base = Some(match base {
+                    PathBase::Root => config::PathBase::Root,
+                    PathBase::GitRoot => config::PathBase::GitRoot,
+                });
+            }
+            output::set_tag_styles(config.tag_styles());
             let no_cache = cli.no_cache;
+            // `--author` filters accept any alias of a canonical author
+            let aliases = config.author_aliases();
 
-            match command {
-                Command::Init { .. } | Command::Completions { .. } => unreachable!(),
+            // Check-family commands return whether they passed; every other
+            // command exits with SUCCESS unless it errors
+            let result = match command {
+                Command::Init { .. }
+                | Command::Hooks { .. }
+                | Command::Config { .. }
+                | Command::Completions { .. }
+                | Command::Schema { .. } => {
+                    unreachable!()
+                }
                 Command::List {
+                    mut paths,
+                    files,
                     tag,
                     sort,
                     group_by,
                     priority,
+                    priority_min,
                     author,
+                    mention,
                     path,
                     limit,
                     context,
                     package,
+                    count_only,
+                    invert_match,
+                    dedupe,
+                    modified_only,
+                    since_date,
+                    fields,
+                    template,
+                    table,
+                    open,
+                    pick,
+                    editor,
                 } => {
+                    if files {
+                        paths.extend(read_stdin_paths()?);
+                    }
+                    if mention.is_some() {
+                        config.scan.mentions = Some(true);
+                    }
                     let opts = ListOptions {
+                        paths,
                         tag,
                         sort,
                         group_by,
                         priority,
-                        author,
+                        priority_min,
+                        author: author.map(|a| aliases.canonicalize(a)),
+                        mention,
                         path,
                         limit,
                         context,
                         show_ignored: cli.show_ignored,
                         detail: cli.detail.clone(),
+                        context_mode: cli.context_mode.to_context_mode(),
+                        count_only,
+                        invert_match,
+                        dedupe,
+                        modified_only,
+                        since_date,
+                        fields,
+                        template,
+                        table,
+                        open,
+                        pick,
+                        editor,
                     };
                     let scan_root = resolve_package_root(&root, &config, package.as_deref())?;
                     cmd_list(&scan_root, &config, &cli.format, opts, no_cache)
@@ -103,14 +231,20 @@ fn run() -> Result<()> {
                     stale_threshold,
                     tag,
                     path,
+                    group_by,
+                    oldest,
+                    limit,
                 } => {
                     let opts = BlameOptions {
                         sort,
-                        author,
+                        author: author.map(|a| aliases.canonicalize(a)),
                         min_age,
                         stale_threshold,
                         tag,
                         path,
+                        group_by,
+                        oldest,
+                        limit,
                     };
                     cmd_blame(&root, &config, &cli.format, opts, no_cache)
                 }
@@ -119,25 +253,59 @@ fn run() -> Result<()> {
                     exact,
                     context,
                     author,
+                    mention,
+                    priority_min,
                     tag,
                     path,
                     sort,
                     group_by,
+                    history,
+                    max_commits,
+                    template,
                 } => {
+                    if mention.is_some() {
+                        config.scan.mentions = Some(true);
+                    }
                     let opts = SearchOptions {
                         query,
                         exact,
                         context,
-                        author,
+                        author: author.map(|a| aliases.canonicalize(a)),
+                        mention,
+                        priority_min,
                         tag,
                         path,
                         sort,
                         group_by,
                         detail: cli.detail.clone(),
+                        context_mode: cli.context_mode.to_context_mode(),
+                        history: history.then_some(max_commits),
+                        template,
                     };
                     cmd_search(&root, &config, &cli.format, opts, no_cache)
                 }
-                Command::Stats { since } => cmd_stats(&root, &config, &cli.format, since, no_cache),
+                Command::Age { buckets } => {
+                    cmd_age(&root, &config, &cli.format, buckets.as_deref(), no_cache)
+                }
+                Command::Stats {
+                    range,
+                    since,
+                    by,
+                    top,
+                    history,
+                } => match range {
+                    Some(range) => cmd_stats_compare(&root, &config, &cli.format, &range, top),
+                    None => cmd_stats(
+                        &root,
+                        &config,
+                        &cli.format,
+                        since,
+                        by,
+                        top,
+                        history,
+                        no_cache,
+                    ),
+                },
                 Command::Brief { since, budget } => {
                     cmd_brief(&root, &config, &cli.format, since, budget, no_cache)
                 }
@@ -145,14 +313,21 @@ fn run() -> Result<()> {
                     git_ref,
                     tag,
                     context,
+                    only,
                     package,
+                    template,
+                    stat,
                 } => {
                     let scan_root = resolve_package_root(&root, &config, package.as_deref())?;
                     let opts = DiffOptions {
                         git_ref,
                         tag,
                         context,
+                        only,
                         detail: cli.detail.clone(),
+                        context_mode: cli.context_mode.to_context_mode(),
+                        template,
+                        stat,
                     };
                     cmd_diff(&scan_root, &config, &cli.format, opts, no_cache)
                 }
@@ -161,40 +336,96 @@ fn run() -> Result<()> {
                     block_tags,
                     max_new,
                     since,
+                    staged,
                     expired,
+                    max_age,
+                    due_soon,
+                    due_soon_fails,
+                    require_deadline,
+                    block_priority,
                     package,
                     workspace: ws_mode,
+                    exit_zero,
                 } => {
-                    if ws_mode {
-                        cmd_workspace_check(&root, &config, &cli.format, no_cache)
+                    let passed = if ws_mode {
+                        cmd_workspace_check(&root, &config, &cli.format, no_cache)?
                     } else {
                         let overrides = CheckOverrides {
                             max,
                             block_tags,
                             max_new,
                             expired,
+                            max_age: max_age
+                                .as_deref()
+                                .map(blame::parse_duration_days)
+                                .transpose()?,
+                            due_soon: due_soon
+                                .as_deref()
+                                .map(blame::parse_duration_days)
+                                .transpose()?,
+                            due_soon_fails,
+                            require_deadline,
+                            block_priority: block_priority.map(|p| p.to_priority()),
                         };
                         let scan_root = resolve_package_root(&root, &config, package.as_deref())?;
-                        cmd_check(&scan_root, &config, &cli.format, overrides, since, no_cache)
-                    }
-                }
-                Command::Context { location, context } => {
-                    cmd_context(&root, &config, &cli.format, &location, context, no_cache)
+                        cmd_check(
+                            &scan_root,
+                            &config,
+                            &cli.format,
+                            overrides,
+                            since,
+                            staged,
+                            no_cache,
+                        )?
+                    };
+                    return Ok(exit_code::for_policy(passed, exit_zero));
                 }
-                Command::Clean { check, since } => {
-                    cmd_clean(&root, &config, &cli.format, check, since, no_cache)
+                Command::Context {
+                    location,
+                    context,
+                    highlight,
+                } => cmd_context(
+                    &root,
+                    &config,
+                    &cli.format,
+                    &location,
+                    context,
+                    cli.context_mode.to_context_mode(),
+                    highlight,
+                    no_cache,
+                ),
+                Command::Clean {
+                    check,
+                    since,
+                    fix,
+                    dry_run,
+                    allow_dirty,
+                    exit_zero,
+                } => {
+                    let opts = CleanOptions {
+                        check,
+                        since,
+                        fix: fix.then_some(FixMode {
+                            dry_run,
+                            allow_dirty,
+                        }),
+                    };
+                    let passed = cmd_clean(&root, &config, &cli.format, opts, no_cache)?;
+                    return Ok(exit_code::for_policy(passed, exit_zero));
                 }
                 Command::Relate {
                     cluster,
                     r#for: for_item,
                     min_score,
                     proximity,
+                    min_cluster_size,
                 } => {
                     let opts = RelateOptions {
                         cluster,
                         for_item,
                         min_score,
                         proximity,
+                        min_cluster_size,
                     };
                     cmd_relate(&root, &config, &cli.format, opts, no_cache)
                 }
@@ -205,6 +436,10 @@ fn run() -> Result<()> {
                     require_issue_ref,
                     uppercase_tag,
                     require_colon,
+                    max_density,
+                    escalate_overdue,
+                    no_local_duplicates,
+                    exit_zero,
                 } => {
                     let overrides = LintOverrides {
                         no_bare_tags,
@@ -213,14 +448,29 @@ fn run() -> Result<()> {
                         require_issue_ref,
                         uppercase_tag,
                         require_colon,
+                        max_density,
+                        escalate_overdue,
+                        no_local_duplicates,
                     };
-                    cmd_lint(&root, &config, &cli.format, overrides, no_cache)
+                    let passed = cmd_lint(&root, &config, &cli.format, overrides, no_cache)?;
+                    return Ok(exit_code::for_policy(passed, exit_zero));
                 }
                 Command::Report {
                     output,
                     history,
                     stale_threshold,
-                } => cmd_report(&root, &config, &output, history, stale_threshold, no_cache),
+                    history_file,
+                    include_resolved,
+                } => cmd_report(
+                    &root,
+                    &config,
+                    &output,
+                    history,
+                    stale_threshold,
+                    history_file.as_deref(),
+                    include_resolved,
+                    no_cache,
+                ),
                 Command::Tasks {
                     tag,
                     context,
@@ -228,6 +478,7 @@ fn run() -> Result<()> {
                     dry_run,
                     since,
                     priority,
+                    priority_min,
                     author,
                     path,
                 } => {
@@ -238,20 +489,79 @@ fn run() -> Result<()> {
                         dry_run,
                         since,
                         priority,
-                        author,
+                        priority_min,
+                        author: author.map(|a| aliases.canonicalize(a)),
                         path,
+                        context_mode: cli.context_mode.to_context_mode(),
                     };
                     cmd_tasks(&root, &config, &cli.format, opts, no_cache)
                 }
-                Command::Watch { tag, max, debounce } => {
-                    watch::cmd_watch(&root, &config, &cli.format, &tag, max, debounce)
+                Command::Export {
+                    tag,
+                    author,
+                    path,
+                    output,
+                } => {
+                    let opts = ExportOptions {
+                        tag,
+                        author: author.map(|a| aliases.canonicalize(a)),
+                        path,
+                        output,
+                    };
+                    cmd_export(&root, &config, opts)
                 }
+                Command::Watch {
+                    tag,
+                    max,
+                    debounce,
+                    run,
+                    clear,
+                } => watch::cmd_watch(
+                    &root,
+                    &config,
+                    &cli.format,
+                    &tag,
+                    max,
+                    debounce,
+                    run.as_deref(),
+                    clear,
+                    no_cache,
+                ),
                 Command::Workspace { action } => match action {
                     WorkspaceAction::List => {
                         cmd_workspace_list(&root, &config, &cli.format, no_cache)
                     }
                 },
-            }
+            };
+            result.map(|()| exit_code::SUCCESS)
         }
     }
 }
+
+fn load_config(
+    root: &std::path::Path,
+    config_path: Option<&std::path::Path>,
+) -> Result<Config, todo_scan::Error> {
+    match config_path {
+        Some(path) if is_stdin(path) => Config::load_stdin(),
+        Some(path) => Config::load_file(path),
+        None => Config::load(root),
+    }
+}
+
+/// `--config -`: read the config from stdin instead of a file.
+fn is_stdin(config_path: &std::path::Path) -> bool {
+    config_path == std::path::Path::new("-")
+}
+
+/// Paths for `list --files`: one per line on stdin, blank lines skipped.
+fn read_stdin_paths() -> Result<Vec<std::path::PathBuf>> {
+    let input =
+        std::io::read_to_string(std::io::stdin()).context("cannot read paths from stdin")?;
+    Ok(input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(std::path::PathBuf::from)
+        .collect())
+}
@@ -1,11 +1,14 @@
-use serde::{Deserialize, Serialize};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::str::FromStr;
 
-use crate::deadline::Deadline;
+use crate::context::ContextInfo;
+use crate::deadline::{Deadline, RelativeDeadline};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "UPPERCASE")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Tag {
     Todo,
     Fixme,
@@ -13,10 +16,66 @@ pub enum Tag {
     Xxx,
     Bug,
     Note,
+    /// A project-specific tag declared under `[custom_tags]` (stored uppercase).
+    Custom(String),
+}
+
+/// Severity and color for a tag, as declared under `[custom_tags]` in config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagStyle {
+    pub severity: u8,
+    pub color: Option<String>,
+}
+
+/// Tag styles declared under `[custom_tags]`, keyed by uppercase tag name.
+///
+/// Built from config by `Config::tag_styles` and carried in `ScanOptions`.
+/// Declared names become valid tags, and their severity and color take
+/// precedence over the built-in defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagStyles {
+    styles: HashMap<String, TagStyle>,
+}
+
+impl TagStyles {
+    pub fn new(styles: HashMap<String, TagStyle>) -> Self {
+        Self {
+            styles: styles
+                .into_iter()
+                .map(|(name, style)| (name.to_uppercase(), style))
+                .collect(),
+        }
+    }
+
+    /// Look up the declared style for a tag name (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&TagStyle> {
+        self.styles.get(&name.to_uppercase())
+    }
+
+    /// Parse a built-in tag or a declared custom tag.
+    pub fn parse(&self, s: &str) -> Option<Tag> {
+        if let Some(tag) = Tag::builtin(s) {
+            return Some(tag);
+        }
+        self.get(s).map(|_| Tag::Custom(s.to_uppercase()))
+    }
+
+    /// Severity used for tag sorting and grouping. A declared severity
+    /// overrides the built-in one.
+    pub fn severity(&self, tag: &Tag) -> u8 {
+        self.get(tag.as_str())
+            .map_or_else(|| tag.severity(), |style| style.severity)
+    }
+
+    /// Color declared for a tag, if any.
+    pub fn color(&self, tag: &Tag) -> Option<&str> {
+        self.get(tag.as_str())
+            .and_then(|style| style.color.as_deref())
+    }
 }
 
 impl Tag {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             Tag::Todo => "TODO",
             Tag::Fixme => "FIXME",
@@ -24,34 +83,57 @@ impl Tag {
             Tag::Xxx => "XXX",
             Tag::Bug => "BUG",
             Tag::Note => "NOTE",
+            Tag::Custom(name) => name,
         }
     }
 
+    /// Built-in severity used for tag sorting and grouping; custom tags rank
+    /// like TODO. See `TagStyles::severity` for configured severities.
     pub fn severity(&self) -> u8 {
         match self {
             Tag::Note => 0,
-            Tag::Todo => 1,
+            Tag::Todo | Tag::Custom(_) => 1,
             Tag::Hack => 2,
             Tag::Xxx => 3,
             Tag::Fixme => 4,
             Tag::Bug => 5,
         }
     }
+
+    fn builtin(s: &str) -> Option<Self> {
+        match s.to_uppercase().as_str() {
+            "TODO" => Some(Tag::Todo),
+            "FIXME" => Some(Tag::Fixme),
+            "HACK" => Some(Tag::Hack),
+            "XXX" => Some(Tag::Xxx),
+            "BUG" => Some(Tag::Bug),
+            "NOTE" => Some(Tag::Note),
+            _ => None,
+        }
+    }
 }
 
 impl FromStr for Tag {
     type Err = ();
 
+    /// Parse a built-in tag. Custom tags parse through `TagStyles::parse`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_uppercase().as_str() {
-            "TODO" => Ok(Tag::Todo),
-            "FIXME" => Ok(Tag::Fixme),
-            "HACK" => Ok(Tag::Hack),
-            "XXX" => Ok(Tag::Xxx),
-            "BUG" => Ok(Tag::Bug),
-            "NOTE" => Ok(Tag::Note),
-            _ => Err(()),
-        }
+        Tag::builtin(s).ok_or(())
+    }
+}
+
+impl Serialize for Tag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Tag {
+    /// Unlike `FromStr`, any name is accepted so cached items keep their
+    /// custom tags.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(Tag::builtin(&s).unwrap_or_else(|| Tag::Custom(s.to_uppercase())))
     }
 }
 
@@ -61,7 +143,21 @@ impl fmt::Display for Tag {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+impl JsonSchema for Tag {
+    fn schema_name() -> Cow<'static, str> {
+        "Tag".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Tag keyword, uppercase (built-in or configured under [custom_tags])",
+            "examples": ["TODO", "FIXME", "HACK", "XXX", "BUG", "NOTE"]
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Priority {
     Normal,
@@ -91,16 +187,32 @@ impl Ord for Priority {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TodoItem {
     pub file: String,
     pub line: usize,
+    /// 1-based column of the tag within the line, in bytes, or UTF-16 code
+    /// units with `[scan] utf16_columns`
+    #[serde(default)]
+    pub column: usize,
     pub tag: Tag,
     pub message: String,
     pub author: Option<String>,
     pub issue_ref: Option<String>,
     pub priority: Priority,
     pub deadline: Option<Deadline>,
+    /// Relative form the deadline was written in (e.g. `+14d`); `deadline`
+    /// then holds its resolution against today.
+    pub relative_deadline: Option<RelativeDeadline>,
+    /// Checkbox state of a Markdown/text task list item (`- [ ]` / `- [x]`)
+    pub checked: Option<bool>,
+    /// Tag an escalation marker such as `TODO->FIXME` says this item should
+    /// become (`[scan] escalation`)
+    pub escalate_to: Option<Tag>,
+    /// `@handle` mentions in the message, in order and without duplicates
+    /// (`[scan] mentions`). Unlike `author`, these are not ownership.
+    #[serde(default)]
+    pub mentions: Vec<String>,
 }
 
 impl TodoItem {
@@ -110,56 +222,220 @@ impl TodoItem {
         format!("{}:{}:{}", self.file, self.tag, normalized)
     }
 
+    /// Re-resolve a relative deadline (e.g. `+14d`) against `reference`.
+    /// Absolute deadlines are left unchanged.
+    pub fn resolve_relative_deadline(&mut self, reference: &Deadline) {
+        if let Some(relative) = self.relative_deadline {
+            self.deadline = Some(relative.resolve(reference));
+        }
+    }
+
     /// Stable, content-based identifier for this TODO item.
     /// Unlike `file:line`, this ID survives line-number changes from edits.
     pub fn id(&self) -> String {
         self.match_key()
     }
+
+    /// Content-hash identifier that, unlike `id`, also survives changes to
+    /// case, punctuation and whitespace in the message.
+    pub fn stable_id(&self) -> String {
+        stable_id(&self.file, self.tag.as_str(), &self.message)
+    }
 }
 
-#[derive(Debug, Serialize)]
+/// Normalize a message for fingerprinting: lowercased, punctuation
+/// stripped, and whitespace runs collapsed to a single space.
+pub fn message_fingerprint(message: &str) -> String {
+    let stripped: String = message
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect();
+    stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Hash of file, tag and message fingerprint, as 16 hex characters.
+pub fn stable_id(file: &str, tag: &str, message: &str) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(file.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(tag.to_uppercase().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(message_fingerprint(message).as_bytes());
+    hasher.finalize().to_hex()[..16].to_string()
+}
+
+/// Schema-only shape of a `TodoItem` in JSON output: the serialized struct
+/// plus the fields the printers inject.
+#[derive(JsonSchema)]
+#[schemars(
+    title = "TodoItem",
+    description = "A TODO comment as it appears in JSON output",
+    transform = minimal_detail_fields_optional
+)]
+#[allow(dead_code)]
+pub struct JsonTodoItem {
+    #[serde(flatten)]
+    item: TodoItem,
+    /// Stable content-based identifier (`file:TAG:message`, message trimmed and lowercased)
+    id: String,
+    /// Hash of file, tag and normalized message; unaffected by case,
+    /// punctuation and whitespace edits
+    stable_id: String,
+    /// Surrounding source lines, present with `--context`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<ContextInfo>,
+    /// Same value as `id`, present with `--detail full`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    match_key: Option<String>,
+}
+
+/// `--detail minimal` strips these fields, so they cannot be required.
+fn minimal_detail_fields_optional(schema: &mut Schema) {
+    if let Some(required) = schema.get_mut("required").and_then(|r| r.as_array_mut()) {
+        required.retain(|name| {
+            !matches!(
+                name.as_str(),
+                Some(
+                    "author"
+                        | "issue_ref"
+                        | "priority"
+                        | "deadline"
+                        | "relative_deadline"
+                        | "checked"
+                        | "mentions"
+                )
+            )
+        });
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct ScanResult {
+    #[schemars(with = "Vec<JsonTodoItem>")]
     pub items: Vec<TodoItem>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub ignored_items: Vec<TodoItem>,
     pub files_scanned: usize,
+    /// Sorted relative paths of the files read by a directory walk; empty for
+    /// scans of other sources (e.g., a git ref).
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub scanned_files: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum DiffStatus {
     Added,
     Removed,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DiffEntry {
     pub status: DiffStatus,
+    #[schemars(with = "JsonTodoItem")]
     pub item: TodoItem,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct DiffResult {
     pub entries: Vec<DiffEntry>,
     pub added_count: usize,
     pub removed_count: usize,
+    /// `added_count - removed_count`; positive when TODO debt grew
+    pub net_change: i64,
+    /// TODOs at the base ref, counting only the tags the entries are filtered to
+    pub base_total: usize,
     pub base_ref: String,
+    /// Set when comparing two refs; absent when diffing against the working tree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_ref: Option<String>,
+    /// Per-tag TODO counts at the base ref, so `recount` can narrow
+    /// `base_total` to a tag filter.
+    #[serde(skip)]
+    #[schemars(skip)]
+    pub base_tag_counts: HashMap<Tag, usize>,
 }
 
-#[derive(Debug, Serialize)]
+impl DiffResult {
+    /// Recompute the summary counts after `entries` were filtered. `tags`
+    /// restricts `base_total` to those tags; empty means every tag.
+    pub fn recount(&mut self, tags: &[Tag]) {
+        self.added_count = self
+            .entries
+            .iter()
+            .filter(|e| e.status == DiffStatus::Added)
+            .count();
+        self.removed_count = self.entries.len() - self.added_count;
+        self.net_change = self.added_count as i64 - self.removed_count as i64;
+        self.base_total = self
+            .base_tag_counts
+            .iter()
+            .filter(|(tag, _)| tags.is_empty() || tags.contains(tag))
+            .map(|(_, count)| count)
+            .sum();
+    }
+
+    /// Remove the entries whose item matches `drop`, as if neither ref had
+    /// those TODOs: removed ones also leave `base_tag_counts`. Call
+    /// `recount` afterwards.
+    pub fn drop_entries(&mut self, drop: impl Fn(&TodoItem) -> bool) {
+        let base_tag_counts = &mut self.base_tag_counts;
+        self.entries.retain(|entry| {
+            if !drop(&entry.item) {
+                return true;
+            }
+            if entry.status == DiffStatus::Removed {
+                if let Some(count) = base_tag_counts.get_mut(&entry.item.tag) {
+                    *count = count.saturating_sub(1);
+                }
+            }
+            false
+        });
+    }
+}
+
+/// Output of `diff --stat`: added and removed TODOs per file.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DiffStatResult {
+    /// Most changed files first
+    pub files: Vec<DiffFileStat>,
+    pub added_count: usize,
+    pub removed_count: usize,
+    pub net_change: i64,
+    pub base_ref: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_ref: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, JsonSchema)]
+pub struct DiffFileStat {
+    pub file: String,
+    pub added: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CheckResult {
     pub passed: bool,
     pub total: usize,
     pub violations: Vec<CheckViolation>,
+    /// Reported without failing the check (e.g. `--due-soon`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<CheckViolation>,
+    /// Rules that were evaluated, so reporters can list the ones that passed
+    #[serde(skip)]
+    pub rules: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CheckViolation {
     pub rule: String,
     pub message: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct StatsResult {
     pub total_items: usize,
     pub total_files: usize,
@@ -167,24 +443,66 @@ pub struct StatsResult {
     pub priority_counts: PriorityCounts,
     pub author_counts: Vec<(String, usize)>,
     pub hotspot_files: Vec<(String, usize)>,
+    /// Number of distinct authors, including any cut by `stats --top`
+    pub total_authors: usize,
+    /// Whether `stats --top` cut the author or hotspot list
+    pub truncated: bool,
     pub trend: Option<TrendInfo>,
+    /// Per-author added/removed counts since the base ref (`stats --by author`)
+    pub author_churn: Option<Vec<AuthorChurn>>,
+    /// Total TODO count at sampled commits, oldest first (`stats --history N`)
+    pub history: Option<Vec<HistoryPoint>>,
 }
 
-#[derive(Debug, Serialize)]
+/// `stats BASE..HEAD`: the full breakdown at both refs and the change in
+/// each count.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct StatsComparison {
+    pub base_ref: String,
+    pub head_ref: String,
+    pub base: StatsResult,
+    pub head: StatsResult,
+    /// `items` and `files`
+    pub totals: Vec<CountDelta>,
+    pub tags: Vec<CountDelta>,
+    pub priorities: Vec<CountDelta>,
+    /// Largest change first
+    pub authors: Vec<CountDelta>,
+    /// Number of authors at either ref, including any cut by `stats --top`
+    pub total_authors: usize,
+}
+
+/// One count at two refs; `delta` is `head - base`.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct CountDelta {
+    pub name: String,
+    pub base: usize,
+    pub head: usize,
+    pub delta: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct PriorityCounts {
     pub normal: usize,
     pub high: usize,
     pub urgent: usize,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct AuthorChurn {
+    pub author: String,
+    pub added: usize,
+    pub removed: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct TrendInfo {
     pub added: usize,
     pub removed: usize,
     pub base_ref: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct BlameInfo {
     pub author: String,
     pub email: String,
@@ -193,33 +511,81 @@ pub struct BlameInfo {
     pub commit: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct BlameEntry {
     #[serde(flatten)]
+    #[schemars(with = "JsonTodoItem")]
     pub item: TodoItem,
     pub blame: BlameInfo,
     pub stale: bool,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct BlameResult {
     pub entries: Vec<BlameEntry>,
     pub total: usize,
     pub avg_age_days: u64,
     pub stale_count: usize,
     pub stale_threshold_days: u64,
+    /// Entry with the oldest known blame date, if any.
+    pub oldest: Option<BlameAgeSummary>,
+    /// Entry with the newest known blame date, if any.
+    pub newest: Option<BlameAgeSummary>,
+    /// Per-author rollup (`blame --group-by author`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authors: Option<Vec<BlameAuthorSummary>>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct BlameAuthorSummary {
+    pub author: String,
+    pub count: usize,
+    pub avg_age_days: u64,
+    pub stale_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BlameAgeSummary {
+    pub file: String,
+    pub line: usize,
+    pub date: String,
+    pub age_days: u64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct SearchResult {
     pub query: String,
     pub exact: bool,
+    #[schemars(with = "Vec<JsonTodoItem>")]
     pub items: Vec<TodoItem>,
     pub match_count: usize,
     pub file_count: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// A TODO found by `search --history`, as it read in the commit where it
+/// first appeared.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HistoryMatch {
+    #[serde(flatten)]
+    #[schemars(with = "JsonTodoItem")]
+    pub item: TodoItem,
+    pub first_seen_commit: String,
+    pub first_seen_date: String,
+    /// Whether the TODO is still in the working tree
+    pub present_now: bool,
+}
+
+/// Output of `search --history`, oldest first sighting first.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HistorySearchResult {
+    pub query: String,
+    pub exact: bool,
+    pub matches: Vec<HistoryMatch>,
+    pub match_count: usize,
+    pub commits_scanned: usize,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct LintViolation {
     pub rule: String,
     pub message: String,
@@ -228,7 +594,7 @@ pub struct LintViolation {
     pub suggestion: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct LintResult {
     pub passed: bool,
     pub total_items: usize,
@@ -253,7 +619,7 @@ pub struct WatchEvent {
     pub total_delta: i64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct CleanViolation {
     pub rule: String,
     pub message: String,
@@ -263,7 +629,7 @@ pub struct CleanViolation {
     pub duplicate_of: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct CleanResult {
     pub passed: bool,
     pub total_items: usize,
@@ -283,6 +649,27 @@ pub struct ReportResult {
     pub history: Vec<HistoryPoint>,
     pub age_histogram: Vec<AgeBucket>,
     pub items: Vec<TodoItem>,
+    /// Present with `report --include-resolved`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<ResolvedReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResolvedReport {
+    pub items: Vec<ResolvedItem>,
+    /// Resolved items as a share of every TODO seen over the history window,
+    /// the current ones included
+    pub resolution_rate: f64,
+}
+
+/// A TODO seen at a sampled commit that no longer exists.
+#[derive(Debug, Serialize)]
+pub struct ResolvedItem {
+    #[serde(flatten)]
+    pub item: TodoItem,
+    /// The latest sampled commit that still had it
+    pub last_seen_commit: String,
+    pub last_seen_date: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -296,9 +683,11 @@ pub struct ReportSummary {
     pub avg_age_days: u64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct HistoryPoint {
-    pub commit: String,
+    /// Sampled commit; absent for points read from a history file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
     pub date: String,
     pub count: usize,
 }
@@ -331,6 +720,14 @@ pub struct ClaudeTaskMetadata {
     pub todo_scan_match_key: String,
 }
 
+/// A task shaped for `gh issue create`: `--title`, `--body` and `--label`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GhIssue {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct TasksResult {
     pub tasks: Vec<ClaudeTask>,
@@ -355,6 +752,17 @@ impl Severity {
             Tag::Bug | Tag::Fixme => Severity::Error,
             Tag::Todo | Tag::Hack | Tag::Xxx => Severity::Warning,
             Tag::Note => Severity::Notice,
+            Tag::Custom(_) => Severity::Warning,
+        }
+    }
+
+    /// Severity used for SARIF results: BUG/XXX and urgent items are errors,
+    /// FIXME and high-priority items warnings, everything else notes.
+    pub fn sarif_from_item(item: &TodoItem) -> Self {
+        match (&item.tag, item.priority) {
+            (Tag::Bug | Tag::Xxx, _) | (_, Priority::Urgent) => Severity::Error,
+            (Tag::Fixme, _) | (_, Priority::High) => Severity::Warning,
+            _ => Severity::Notice,
         }
     }
 
@@ -366,6 +774,15 @@ impl Severity {
         }
     }
 
+    /// Azure Pipelines issues are either errors or warnings; notices are
+    /// reported as warnings.
+    pub fn as_azure_devops_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning | Severity::Notice => "warning",
+        }
+    }
+
     pub fn as_sarif_level(&self) -> &'static str {
         match self {
             Severity::Error => "error",
@@ -452,6 +869,8 @@ pub struct Cluster {
     pub items: Vec<String>,
     pub suggested_order: Vec<String>,
     pub relationships: Vec<Relationship>,
+    /// Full details of each member, in suggested order
+    pub members: Vec<TodoItem>,
 }
 
 #[derive(Debug, Serialize)]
@@ -459,6 +878,9 @@ pub struct RelateResult {
     pub relationships: Vec<Relationship>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub clusters: Option<Vec<Cluster>>,
+    /// Items left out of every cluster (only set with clusters)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ungrouped_items: Option<usize>,
     pub total_relationships: usize,
     pub total_items: usize,
     pub min_score: f64,
@@ -475,6 +897,45 @@ pub struct BriefResult {
     pub trend: Option<TrendInfo>,
 }
 
+/// Output of `list --invert-match`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FilesWithoutTodosResult {
+    pub files_without_todos: Vec<String>,
+}
+
+/// A `file:line` position of one occurrence of a deduplicated TODO.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct Location {
+    pub file: String,
+    pub line: usize,
+}
+
+/// TODOs sharing a tag and message, shown once by `list --dedupe`. `item` is
+/// the first occurrence in list order.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DedupedItem {
+    #[serde(flatten)]
+    #[schemars(with = "JsonTodoItem")]
+    pub item: TodoItem,
+    pub count: usize,
+    pub locations: Vec<Location>,
+}
+
+/// Output of `list --dedupe`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DedupeResult {
+    pub items: Vec<DedupedItem>,
+    /// Number of TODOs before collapsing
+    pub total_items: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CountResult {
+    pub total: usize,
+    pub by_tag: BTreeMap<String, usize>,
+    pub files: usize,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,16 +945,110 @@ mod tests {
         let item = TodoItem {
             file: "src/main.rs".to_string(),
             line: 42,
+            column: 1,
             tag: Tag::Todo,
             message: "fix this bug".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         assert_eq!(item.id(), item.match_key());
     }
 
+    #[test]
+    fn drop_entries_also_drops_removed_items_from_base_counts() {
+        let in_file = |file: &str| {
+            let mut item = item_with_message("fix");
+            item.file = file.to_string();
+            item
+        };
+        let entry = |status, file: &str| DiffEntry {
+            status,
+            item: in_file(file),
+        };
+        let mut diff = DiffResult {
+            entries: vec![
+                entry(DiffStatus::Added, "src/a.rs"),
+                entry(DiffStatus::Added, "tests/a.rs"),
+                entry(DiffStatus::Removed, "tests/b.rs"),
+            ],
+            added_count: 2,
+            removed_count: 1,
+            net_change: 1,
+            base_total: 3,
+            base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: HashMap::from([(Tag::Fixme, 3)]),
+        };
+        diff.drop_entries(|item| item.file.starts_with("tests/"));
+        diff.recount(&[]);
+        assert_eq!(diff.entries.len(), 1);
+        assert_eq!(diff.added_count, 1);
+        assert_eq!(diff.removed_count, 0);
+        assert_eq!(diff.base_total, 2);
+    }
+
+    fn item_with_message(message: &str) -> TodoItem {
+        TodoItem {
+            file: "src/main.rs".to_string(),
+            line: 1,
+            column: 1,
+            tag: Tag::Fixme,
+            message: message.to_string(),
+            author: None,
+            issue_ref: None,
+            priority: Priority::Normal,
+            deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
+        }
+    }
+
+    #[test]
+    fn message_fingerprint_normalizes_case_punctuation_and_whitespace() {
+        assert_eq!(message_fingerprint("  Fix this,  bug!\t"), "fix this bug");
+        assert_eq!(message_fingerprint("don't panic"), "dont panic");
+        assert_eq!(message_fingerprint("!!!"), "");
+    }
+
+    #[test]
+    fn stable_id_ignores_minor_message_edits() {
+        let a = item_with_message("Fix this bug");
+        let b = item_with_message("fix this  bug!");
+        assert_ne!(a.id(), b.id());
+        assert_eq!(a.stable_id(), b.stable_id());
+        assert_eq!(a.stable_id().len(), 16);
+    }
+
+    #[test]
+    fn stable_id_differs_by_file_tag_and_words() {
+        let base = item_with_message("fix this bug");
+        let mut other_file = base.clone();
+        other_file.file = "src/lib.rs".to_string();
+        let mut other_tag = base.clone();
+        other_tag.tag = Tag::Todo;
+        let reworded = item_with_message("fix that bug");
+
+        assert_ne!(base.stable_id(), other_file.stable_id());
+        assert_ne!(base.stable_id(), other_tag.stable_id());
+        assert_ne!(base.stable_id(), reworded.stable_id());
+    }
+
+    #[test]
+    fn stable_id_ignores_line_number() {
+        let a = item_with_message("fix this bug");
+        let mut b = a.clone();
+        b.line = 99;
+        assert_eq!(a.stable_id(), b.stable_id());
+    }
+
     #[test]
     fn priority_numeric_order_values() {
         assert_eq!(Priority::Normal.numeric_order(), 0);
@@ -580,6 +1135,7 @@ mod tests {
             total_items: 0,
             min_score: 0.3,
             target: None,
+            ungrouped_items: None,
         };
         let json = serde_json::to_string_pretty(&result).unwrap();
         assert!(json.contains("\"total_relationships\": 0"));
@@ -598,11 +1154,13 @@ mod tests {
                 items: vec!["src/auth.rs:10".to_string()],
                 suggested_order: vec!["src/auth.rs:10".to_string()],
                 relationships: vec![],
+                members: vec![],
             }]),
             total_relationships: 0,
             total_items: 1,
             min_score: 0.3,
             target: Some("src/auth.rs:10".to_string()),
+            ungrouped_items: None,
         };
         let json = serde_json::to_string_pretty(&result).unwrap();
         assert!(json.contains("\"clusters\""));
@@ -627,6 +1185,61 @@ mod tests {
         assert_eq!("TODOS".parse::<Tag>(), Err(()));
     }
 
+    #[test]
+    fn custom_tag_parses_from_declared_styles() {
+        assert_eq!("OPTIMIZE".parse::<Tag>(), Err(()));
+        let styles = TagStyles::new(HashMap::from([(
+            "optimize".to_string(),
+            TagStyle {
+                severity: 3,
+                color: Some("cyan".to_string()),
+            },
+        )]));
+        assert_eq!(
+            styles.parse("Optimize"),
+            Some(Tag::Custom("OPTIMIZE".to_string()))
+        );
+        assert_eq!(styles.parse("fixme"), Some(Tag::Fixme));
+        assert_eq!(styles.parse("UNDECLARED"), None);
+        let tag = Tag::Custom("OPTIMIZE".to_string());
+        assert_eq!(tag.as_str(), "OPTIMIZE");
+        assert_eq!(styles.severity(&tag), 3);
+        assert_eq!(styles.color(&tag), Some("cyan"));
+        // Parsing with one config does not leak into another
+        assert_eq!(TagStyles::default().parse("OPTIMIZE"), None);
+    }
+
+    #[test]
+    fn declared_style_overrides_builtin_severity() {
+        let styles = TagStyles::new(HashMap::from([(
+            "NOTE".to_string(),
+            TagStyle {
+                severity: 9,
+                color: None,
+            },
+        )]));
+        assert_eq!(styles.severity(&Tag::Note), 9);
+        assert_eq!(styles.severity(&Tag::Todo), Tag::Todo.severity());
+    }
+
+    #[test]
+    fn undeclared_custom_tag_ranks_like_todo() {
+        let tag = Tag::Custom("UNREGISTERED_TAG".to_string());
+        assert_eq!(tag.severity(), Tag::Todo.severity());
+        assert_eq!(TagStyles::default().severity(&tag), Tag::Todo.severity());
+    }
+
+    #[test]
+    fn tag_serializes_as_plain_string() {
+        assert_eq!(serde_json::to_string(&Tag::Fixme).unwrap(), "\"FIXME\"");
+        let custom = Tag::Custom("SECURITY".to_string());
+        assert_eq!(serde_json::to_string(&custom).unwrap(), "\"SECURITY\"");
+        let back: Tag = serde_json::from_str("\"security\"").unwrap();
+        assert_eq!(back, custom);
+        let builtin: Tag = serde_json::from_str("\"HACK\"").unwrap();
+        assert_eq!(builtin, Tag::Hack);
+    }
+
     #[test]
     fn tag_display() {
         assert_eq!(Tag::Todo.to_string(), "TODO");
@@ -639,12 +1252,17 @@ mod tests {
         let mut item = TodoItem {
             file: "a.rs".to_string(),
             line: 1,
+            column: 1,
             tag: Tag::Note, // Note normally => Notice
             message: "test".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Urgent,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         // Urgent overrides to Error regardless of tag
         assert_eq!(Severity::from_item(&item), Severity::Error);
@@ -658,12 +1276,17 @@ mod tests {
         let make = |tag: Tag| TodoItem {
             file: "a.rs".to_string(),
             line: 1,
+            column: 1,
             tag,
             message: "test".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         assert_eq!(Severity::from_item(&make(Tag::Bug)), Severity::Error);
         assert_eq!(Severity::from_item(&make(Tag::Fixme)), Severity::Error);
@@ -680,6 +1303,13 @@ mod tests {
         assert_eq!(Severity::Notice.as_github_actions_str(), "notice");
     }
 
+    #[test]
+    fn severity_azure_devops_str() {
+        assert_eq!(Severity::Error.as_azure_devops_str(), "error");
+        assert_eq!(Severity::Warning.as_azure_devops_str(), "warning");
+        assert_eq!(Severity::Notice.as_azure_devops_str(), "warning");
+    }
+
     #[test]
     fn severity_sarif_level() {
         assert_eq!(Severity::Error.as_sarif_level(), "error");
@@ -0,0 +1,374 @@
+use crate::model::*;
+
+/// Escape message data per the Azure Pipelines logging command spec.
+fn escape_message(s: &str) -> String {
+    s.replace('%', "%AZP25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape property values (additionally escape `;` and `]`).
+fn escape_property(s: &str) -> String {
+    escape_message(s).replace(';', "%3B").replace(']', "%5D")
+}
+
+fn log_issue(level: &str, location: Option<(&str, usize)>, code: &str, msg: &str) -> String {
+    let mut props = format!("type={level};");
+    if let Some((file, line)) = location {
+        props.push_str(&format!(
+            "sourcepath={};linenumber={line};",
+            escape_property(file)
+        ));
+    }
+    props.push_str(&format!("code={};", escape_property(code)));
+    format!("##vso[task.logissue {props}]{}", escape_message(msg))
+}
+
+fn format_item_issue(item: &TodoItem) -> String {
+    let severity = Severity::sarif_from_item(item);
+    let tag = item.tag.as_str();
+    let mut msg = format!("[{tag}] {}", item.message);
+    if let Some(ref deadline) = item.deadline {
+        msg.push_str(&format!(" (deadline: {})", deadline));
+    }
+    log_issue(
+        severity.as_azure_devops_str(),
+        Some((&item.file, item.line)),
+        tag,
+        &msg,
+    )
+}
+
+/// Plain log line, escaped so it cannot start a logging command of its own.
+fn log_line(msg: &str) -> String {
+    escape_message(msg).replace("##", "#%23")
+}
+
+pub fn format_list(result: &ScanResult) -> String {
+    let mut lines: Vec<String> = result.items.iter().map(format_item_issue).collect();
+    lines.push(format!("todo-scan: {} items found", result.items.len()));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_search(result: &SearchResult) -> String {
+    let mut lines: Vec<String> = result.items.iter().map(format_item_issue).collect();
+    lines.push(log_line(&format!(
+        "todo-scan search: {} matches (query: \"{}\")",
+        result.match_count, result.query
+    )));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_diff(result: &DiffResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for entry in &result.entries {
+        match entry.status {
+            DiffStatus::Added => {
+                lines.push(format_item_issue(&entry.item));
+            }
+            DiffStatus::Removed => {
+                lines.push(log_line(&format!(
+                    "Removed {}:{}: [{}] {}",
+                    entry.item.file,
+                    entry.item.line,
+                    entry.item.tag.as_str(),
+                    entry.item.message
+                )));
+            }
+        }
+    }
+    lines.push(format!(
+        "todo-scan diff: +{} -{} (net {:+}, base {})",
+        result.added_count, result.removed_count, result.net_change, result.base_total
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_blame(result: &BlameResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    for entry in &result.entries {
+        let tag = entry.item.tag.as_str();
+        let msg = format!(
+            "[{}] {} @{} {} ({} days ago)",
+            tag, entry.item.message, entry.blame.author, entry.blame.date, entry.blame.age_days,
+        );
+        if entry.stale {
+            lines.push(log_issue(
+                "warning",
+                Some((&entry.item.file, entry.item.line)),
+                &format!("Stale {}", tag),
+                &msg,
+            ));
+        } else {
+            lines.push(log_line(&format!(
+                "{}:{}: {}",
+                entry.item.file, entry.item.line, msg
+            )));
+        }
+    }
+
+    lines.push(format!(
+        "todo-scan blame: {} items, {} stale",
+        result.total, result.stale_count,
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_lint(result: &LintResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if result.passed {
+        lines.push("todo-scan lint: PASS".to_string());
+    } else {
+        for violation in &result.violations {
+            lines.push(log_issue(
+                "error",
+                Some((&violation.file, violation.line)),
+                &violation.rule,
+                &violation.message,
+            ));
+        }
+        lines.push(format!(
+            "##vso[task.logissue type=error;]todo-scan lint: FAIL ({} violations)",
+            result.violation_count
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_check(result: &CheckResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for warning in &result.warnings {
+        lines.push(log_issue("warning", None, &warning.rule, &warning.message));
+    }
+    if result.passed {
+        lines.push("todo-scan check: PASS".to_string());
+    } else {
+        for violation in &result.violations {
+            lines.push(log_issue(
+                "error",
+                None,
+                &violation.rule,
+                &violation.message,
+            ));
+        }
+        lines.push("##vso[task.logissue type=error;]todo-scan check: FAIL".to_string());
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_clean(result: &CleanResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if result.passed {
+        lines.push("todo-scan clean: PASS".to_string());
+    } else {
+        for violation in &result.violations {
+            lines.push(log_issue(
+                "error",
+                Some((&violation.file, violation.line)),
+                &violation.rule,
+                &violation.message,
+            ));
+        }
+        lines.push(format!(
+            "##vso[task.logissue type=error;]todo-scan clean: FAIL ({} stale, {} duplicates)",
+            result.stale_count, result.duplicate_count
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_item(tag: Tag, message: &str) -> TodoItem {
+        TodoItem {
+            file: "src/main.rs".to_string(),
+            line: 10,
+            column: 1,
+            tag,
+            message: message.to_string(),
+            author: None,
+            issue_ref: None,
+            priority: Priority::Normal,
+            deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
+        }
+    }
+
+    fn scan_result(items: Vec<TodoItem>) -> ScanResult {
+        ScanResult {
+            items,
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        }
+    }
+
+    #[test]
+    fn test_format_list_single_item() {
+        let output = format_list(&scan_result(vec![sample_item(
+            Tag::Todo,
+            "implement feature",
+        )]));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "##vso[task.logissue type=warning;sourcepath=src/main.rs;linenumber=10;code=TODO;][TODO] implement feature",
+                "todo-scan: 1 items found",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_list_severity_follows_sarif_mapping() {
+        let mut urgent = sample_item(Tag::Todo, "urgent task");
+        urgent.priority = Priority::Urgent;
+        let output = format_list(&scan_result(vec![
+            sample_item(Tag::Bug, "critical bug"),
+            sample_item(Tag::Xxx, "dangerous"),
+            sample_item(Tag::Fixme, "broken"),
+            sample_item(Tag::Note, "a note"),
+            urgent,
+        ]));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines[0],
+            "##vso[task.logissue type=error;sourcepath=src/main.rs;linenumber=10;code=BUG;][BUG] critical bug"
+        );
+        assert_eq!(
+            lines[1],
+            "##vso[task.logissue type=error;sourcepath=src/main.rs;linenumber=10;code=XXX;][XXX] dangerous"
+        );
+        assert_eq!(
+            lines[2],
+            "##vso[task.logissue type=warning;sourcepath=src/main.rs;linenumber=10;code=FIXME;][FIXME] broken"
+        );
+        assert_eq!(
+            lines[3],
+            "##vso[task.logissue type=warning;sourcepath=src/main.rs;linenumber=10;code=NOTE;][NOTE] a note"
+        );
+        assert_eq!(
+            lines[4],
+            "##vso[task.logissue type=error;sourcepath=src/main.rs;linenumber=10;code=TODO;][TODO] urgent task"
+        );
+    }
+
+    #[test]
+    fn test_escape_special_characters() {
+        let mut item = sample_item(Tag::Todo, "fix 100% of bugs\nline2");
+        item.file = "dir;x]/a.rs".to_string();
+        let output = format_list(&scan_result(vec![item]));
+        assert!(output.contains("sourcepath=dir%3Bx%5D/a.rs;"));
+        assert!(output.contains("][TODO] fix 100%AZP25 of bugs%0Aline2"));
+    }
+
+    #[test]
+    fn test_format_diff_added_and_removed() {
+        let result = DiffResult {
+            entries: vec![
+                DiffEntry {
+                    status: DiffStatus::Added,
+                    item: sample_item(Tag::Fixme, "new fix"),
+                },
+                DiffEntry {
+                    status: DiffStatus::Removed,
+                    item: sample_item(Tag::Todo, "old task"),
+                },
+            ],
+            added_count: 1,
+            removed_count: 1,
+            base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
+        };
+        let output = format_diff(&result);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "##vso[task.logissue type=warning;sourcepath=src/main.rs;linenumber=10;code=FIXME;][FIXME] new fix",
+                "Removed src/main.rs:10: [TODO] old task",
+                "todo-scan diff: +1 -1 (net +0, base 0)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_check_pass_with_warning() {
+        let result = CheckResult {
+            passed: true,
+            rules: vec![],
+            total: 1,
+            violations: vec![],
+            warnings: vec![CheckViolation {
+                rule: "due_soon".to_string(),
+                message: "Deadline 2025-06-20 in a.rs:1 is due in 5 days".to_string(),
+            }],
+        };
+        let output = format_check(&result);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "##vso[task.logissue type=warning;code=due_soon;]Deadline 2025-06-20 in a.rs:1 is due in 5 days",
+                "todo-scan check: PASS",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_check_fail() {
+        let result = CheckResult {
+            passed: false,
+            rules: vec![],
+            total: 10,
+            violations: vec![CheckViolation {
+                rule: "max".to_string(),
+                message: "10 exceeds max 5".to_string(),
+            }],
+            warnings: vec![],
+        };
+        let output = format_check(&result);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "##vso[task.logissue type=error;code=max;]10 exceeds max 5",
+                "##vso[task.logissue type=error;]todo-scan check: FAIL",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_search_escapes_query() {
+        let result = SearchResult {
+            items: vec![],
+            match_count: 0,
+            file_count: 0,
+            exact: false,
+            query: "evil\n##vso[task.complete result=Failed;]".to_string(),
+        };
+        let output = format_search(&result);
+        for line in output.lines() {
+            assert!(
+                !line.contains("##vso"),
+                "query must not be able to inject a logging command: {line}"
+            );
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use crate::model::*;
+
+use super::sanitize_for_terminal;
+
+const ITEM_HEADER: &str = "file,line,tag,priority,author,issue_ref,deadline,message";
+
+/// Quote a field per RFC 4180 when it contains a comma, quote, or line break.
+/// Control characters are stripped first, matching the text output.
+fn escape_field(s: &str) -> String {
+    let s = sanitize_for_terminal(s);
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s
+    }
+}
+
+fn priority_str(priority: &Priority) -> &'static str {
+    match priority {
+        Priority::Normal => "normal",
+        Priority::High => "high",
+        Priority::Urgent => "urgent",
+    }
+}
+
+fn item_row(item: &TodoItem) -> String {
+    let author = item.author.as_deref().map(escape_field).unwrap_or_default();
+    let issue = item
+        .issue_ref
+        .as_deref()
+        .map(escape_field)
+        .unwrap_or_default();
+    let deadline = item
+        .deadline
+        .as_ref()
+        .map(|d| d.to_string())
+        .unwrap_or_default();
+    format!(
+        "{},{},{},{},{},{},{},{}",
+        escape_field(&item.file),
+        item.line,
+        escape_field(item.tag.as_str()),
+        priority_str(&item.priority),
+        author,
+        issue,
+        deadline,
+        escape_field(&item.message)
+    )
+}
+
+fn format_items<'a>(items: impl Iterator<Item = &'a TodoItem>) -> String {
+    let mut lines = vec![ITEM_HEADER.to_string()];
+    lines.extend(items.map(item_row));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_list(result: &ScanResult) -> String {
+    format_items(result.items.iter())
+}
+
+pub fn format_search(result: &SearchResult) -> String {
+    format_items(result.items.iter())
+}
+
+pub fn format_diff(result: &DiffResult) -> String {
+    let mut lines = vec![format!("status,{ITEM_HEADER}")];
+    for entry in &result.entries {
+        let status = match entry.status {
+            DiffStatus::Added => "added",
+            DiffStatus::Removed => "removed",
+        };
+        lines.push(format!("{status},{}", item_row(&entry.item)));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_diff_stat(result: &DiffStatResult) -> String {
+    let mut lines = vec!["file,added,removed".to_string()];
+    for file in &result.files {
+        lines.push(format!(
+            "{},{},{}",
+            escape_field(&file.file),
+            file.added,
+            file.removed
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_blame(result: &BlameResult) -> String {
+    if let Some(ref authors) = result.authors {
+        let mut lines = vec!["author,count,avg_age_days,stale_count".to_string()];
+        for a in authors {
+            lines.push(format!(
+                "{},{},{},{}",
+                escape_field(&a.author),
+                a.count,
+                a.avg_age_days,
+                a.stale_count
+            ));
+        }
+        lines.push(String::new());
+        return lines.join("\n");
+    }
+
+    let mut lines = vec![format!(
+        "{ITEM_HEADER},blame_author,blame_email,blame_date,age_days,commit,stale"
+    )];
+    for entry in &result.entries {
+        lines.push(format!(
+            "{},{},{},{},{},{},{}",
+            item_row(&entry.item),
+            escape_field(&entry.blame.author),
+            escape_field(&entry.blame.email),
+            escape_field(&entry.blame.date),
+            entry.blame.age_days,
+            escape_field(&entry.blame.commit),
+            entry.stale
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Stats as a long-format table: one `section,name,count` row per figure.
+pub fn format_stats(result: &StatsResult) -> String {
+    let mut lines = vec!["section,name,count".to_string()];
+    lines.push(format!("summary,total_items,{}", result.total_items));
+    lines.push(format!("summary,total_files,{}", result.total_files));
+    for (tag, count) in &result.tag_counts {
+        lines.push(format!("tag,{},{}", escape_field(tag.as_str()), count));
+    }
+    let pc = &result.priority_counts;
+    lines.push(format!("priority,normal,{}", pc.normal));
+    lines.push(format!("priority,high,{}", pc.high));
+    lines.push(format!("priority,urgent,{}", pc.urgent));
+    for (author, count) in &result.author_counts {
+        lines.push(format!("author,{},{}", escape_field(author), count));
+    }
+    for (file, count) in &result.hotspot_files {
+        lines.push(format!("hotspot,{},{}", escape_field(file), count));
+    }
+    if let Some(ref trend) = result.trend {
+        lines.push(format!("trend,added,{}", trend.added));
+        lines.push(format!("trend,removed,{}", trend.removed));
+    }
+    for churn in result.author_churn.iter().flatten() {
+        let author = escape_field(&churn.author);
+        lines.push(format!("churn_added,{},{}", author, churn.added));
+        lines.push(format!("churn_removed,{},{}", author, churn.removed));
+    }
+    for point in result.history.iter().flatten() {
+        let name = point.commit.as_deref().unwrap_or(&point.date);
+        lines.push(format!("history,{},{}", escape_field(name), point.count));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Counts as `name,count` rows: `total`, `files`, then one row per tag.
+pub fn format_count(result: &CountResult) -> String {
+    let mut lines = vec!["name,count".to_string()];
+    lines.push(format!("total,{}", result.total));
+    lines.push(format!("files,{}", result.files));
+    for (tag, count) in &result.by_tag {
+        lines.push(format!("{},{}", escape_field(tag), count));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_lint(result: &LintResult) -> String {
+    let mut lines = vec!["file,line,rule,message,suggestion".to_string()];
+    for v in &result.violations {
+        lines.push(format!(
+            "{},{},{},{},{}",
+            escape_field(&v.file),
+            v.line,
+            escape_field(&v.rule),
+            escape_field(&v.message),
+            v.suggestion
+                .as_deref()
+                .map(escape_field)
+                .unwrap_or_default()
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_check(result: &CheckResult) -> String {
+    let mut lines = vec!["rule,message".to_string()];
+    for v in result.violations.iter().chain(&result.warnings) {
+        lines.push(format!(
+            "{},{}",
+            escape_field(&v.rule),
+            escape_field(&v.message)
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_clean(result: &CleanResult) -> String {
+    let mut lines = vec!["file,line,rule,message,issue_ref,duplicate_of".to_string()];
+    for v in &result.violations {
+        lines.push(format!(
+            "{},{},{},{},{},{}",
+            escape_field(&v.file),
+            v.line,
+            escape_field(&v.rule),
+            escape_field(&v.message),
+            v.issue_ref.as_deref().map(escape_field).unwrap_or_default(),
+            v.duplicate_of
+                .as_deref()
+                .map(escape_field)
+                .unwrap_or_default()
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::helpers::make_item;
+
+    fn read_rows(output: &str) -> Vec<Vec<String>> {
+        ::csv::Reader::from_reader(output.as_bytes())
+            .records()
+            .map(|r| r.unwrap().iter().map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_escape_field_plain_is_unquoted() {
+        assert_eq!(escape_field("fix this"), "fix this");
+    }
+
+    #[test]
+    fn test_escape_field_quotes_comma_and_doubles_quotes() {
+        assert_eq!(escape_field("a, b"), "\"a, b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_escape_field_strips_control_characters() {
+        assert_eq!(escape_field("a\x1b[31mb\nc"), "a[31mbc");
+    }
+
+    #[test]
+    fn test_format_list_round_trips_through_csv_reader() {
+        let mut item = make_item("src/a,b.rs", 7, Tag::Fixme, "handle \"quoted\", commas");
+        item.author = Some("alice".to_string());
+        item.issue_ref = Some("#12".to_string());
+        item.priority = Priority::Urgent;
+        let result = ScanResult {
+            items: vec![item, make_item("lib.rs", 1, Tag::Todo, "plain")],
+            ignored_items: vec![],
+            files_scanned: 2,
+            scanned_files: vec![],
+        };
+        let output = format_list(&result);
+        assert!(output.starts_with(ITEM_HEADER));
+
+        let rows = read_rows(&output);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            vec![
+                "src/a,b.rs",
+                "7",
+                "FIXME",
+                "urgent",
+                "alice",
+                "#12",
+                "",
+                "handle \"quoted\", commas"
+            ]
+        );
+        assert_eq!(rows[1][0], "lib.rs");
+        assert_eq!(rows[1][7], "plain");
+    }
+
+    #[test]
+    fn test_format_diff_prefixes_status_column() {
+        let result = DiffResult {
+            entries: vec![DiffEntry {
+                status: DiffStatus::Removed,
+                item: make_item("a.rs", 3, Tag::Hack, "gone"),
+            }],
+            added_count: 0,
+            removed_count: 1,
+            base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
+        };
+        let rows = read_rows(&format_diff(&result));
+        assert_eq!(rows[0][0], "removed");
+        assert_eq!(rows[0][1], "a.rs");
+        assert_eq!(rows[0][8], "gone");
+    }
+
+    #[test]
+    fn test_format_stats_long_format() {
+        let result = StatsResult {
+            total_items: 3,
+            total_files: 2,
+            tag_counts: vec![(Tag::Todo, 2), (Tag::Bug, 1)],
+            priority_counts: PriorityCounts {
+                normal: 2,
+                high: 0,
+                urgent: 1,
+            },
+            author_counts: vec![("bob, jr".to_string(), 1)],
+            hotspot_files: vec![("src/main.rs".to_string(), 2)],
+            trend: None,
+            author_churn: None,
+            history: None,
+            total_authors: 1,
+            truncated: false,
+        };
+        let rows = read_rows(&format_stats(&result));
+        assert!(rows.contains(&vec!["summary".into(), "total_items".into(), "3".into()]));
+        assert!(rows.contains(&vec!["tag".into(), "TODO".into(), "2".into()]));
+        assert!(rows.contains(&vec!["priority".into(), "urgent".into(), "1".into()]));
+        assert!(rows.contains(&vec!["author".into(), "bob, jr".into(), "1".into()]));
+        assert!(rows.contains(&vec!["hotspot".into(), "src/main.rs".into(), "2".into()]));
+        assert!(!rows.iter().any(|r| r[0] == "trend"));
+        assert!(!rows.iter().any(|r| r[0].starts_with("churn")));
+    }
+
+    #[test]
+    fn test_format_stats_author_churn_rows() {
+        let result = StatsResult {
+            total_items: 0,
+            total_files: 0,
+            tag_counts: vec![],
+            priority_counts: PriorityCounts {
+                normal: 0,
+                high: 0,
+                urgent: 0,
+            },
+            author_counts: vec![],
+            hotspot_files: vec![],
+            trend: None,
+            author_churn: Some(vec![AuthorChurn {
+                author: "alice".to_string(),
+                added: 3,
+                removed: 1,
+            }]),
+            history: Some(vec![HistoryPoint {
+                commit: Some("abc12345".to_string()),
+                date: "2025-01-02".to_string(),
+                count: 7,
+            }]),
+            total_authors: 0,
+            truncated: false,
+        };
+        let rows = read_rows(&format_stats(&result));
+        assert!(rows.contains(&vec!["churn_added".into(), "alice".into(), "3".into()]));
+        assert!(rows.contains(&vec!["churn_removed".into(), "alice".into(), "1".into()]));
+        assert!(rows.contains(&vec!["history".into(), "abc12345".into(), "7".into()]));
+    }
+
+    #[test]
+    fn test_format_blame_authors_rows() {
+        let result = BlameResult {
+            entries: vec![],
+            total: 3,
+            avg_age_days: 40,
+            stale_count: 1,
+            stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: Some(vec![BlameAuthorSummary {
+                author: "Doe, Jane".to_string(),
+                count: 3,
+                avg_age_days: 40,
+                stale_count: 1,
+            }]),
+        };
+        let output = format_blame(&result);
+        assert!(output.starts_with("author,count,avg_age_days,stale_count\n"));
+        assert_eq!(
+            read_rows(&output),
+            vec![vec![
+                "Doe, Jane".to_string(),
+                "3".into(),
+                "40".into(),
+                "1".into()
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_format_check_lists_violations() {
+        let result = CheckResult {
+            passed: false,
+            rules: vec![],
+            total: 5,
+            violations: vec![CheckViolation {
+                rule: "max".to_string(),
+                message: "5 TODOs found, max allowed is 3".to_string(),
+            }],
+            warnings: vec![],
+        };
+        let rows = read_rows(&format_check(&result));
+        assert_eq!(rows, vec![vec!["max", "5 TODOs found, max allowed is 3"]]);
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use crate::model::*;
+
+use super::sanitize_for_terminal;
+
+/// Quote a string as a DOT double-quoted ID, escaping backslashes and quotes.
+/// Control characters are stripped first, matching the text output.
+fn quote(s: &str) -> String {
+    let s = sanitize_for_terminal(s);
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Assigns each `file:line` location a node ID that is a plain DOT identifier
+/// (`n0`, `n1`, ...), so paths never need escaping in edge statements. The
+/// location itself is kept as the node label.
+#[derive(Default)]
+struct NodeIds<'a> {
+    ids: HashMap<&'a str, usize>,
+}
+
+impl<'a> NodeIds<'a> {
+    fn id(&mut self, location: &'a str) -> String {
+        let next = self.ids.len();
+        let idx = *self.ids.entry(location).or_insert(next);
+        format!("n{}", idx)
+    }
+}
+
+fn node_stmt(id: &str, location: &str, indent: &str) -> String {
+    format!("{}{} [label={}];\n", indent, id, quote(location))
+}
+
+/// Render relationships as an undirected Graphviz graph: one node per
+/// location, one edge per relationship labeled with its score and reason.
+/// Clusters become `subgraph cluster_N` blocks titled by their theme.
+pub fn format_relate(result: &RelateResult) -> String {
+    let mut ids = NodeIds::default();
+    let mut out = String::from("graph todo_scan {\n");
+    out.push_str("  node [shape=box];\n");
+
+    if let Some(ref clusters) = result.clusters {
+        for cluster in clusters {
+            out.push_str(&format!("  subgraph cluster_{} {{\n", cluster.id));
+            out.push_str(&format!("    label={};\n", quote(&cluster.theme)));
+            for loc in &cluster.suggested_order {
+                let id = ids.id(loc);
+                out.push_str(&node_stmt(&id, loc, "    "));
+            }
+            out.push_str("  }\n");
+        }
+    }
+
+    for rel in &result.relationships {
+        for loc in [&rel.from, &rel.to] {
+            // Clustered locations were already declared inside their subgraph
+            if !ids.ids.contains_key(loc.as_str()) {
+                let id = ids.id(loc);
+                out.push_str(&node_stmt(&id, loc, "  "));
+            }
+        }
+    }
+
+    for rel in &result.relationships {
+        let from = ids.id(&rel.from);
+        let to = ids.id(&rel.to);
+        let label = format!("{:.2} {}", rel.score, rel.reason);
+        out.push_str(&format!(
+            "  {} -- {} [label={}, weight={}, penwidth={:.1}];\n",
+            from,
+            to,
+            quote(&label),
+            (rel.score * 100.0).round() as u32,
+            1.0 + rel.score * 3.0
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel(from: &str, to: &str, score: f64, reason: &str) -> Relationship {
+        Relationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            score,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn result(relationships: Vec<Relationship>, clusters: Option<Vec<Cluster>>) -> RelateResult {
+        RelateResult {
+            total_relationships: relationships.len(),
+            relationships,
+            clusters,
+            total_items: 3,
+            min_score: 0.3,
+            target: None,
+            ungrouped_items: None,
+        }
+    }
+
+    #[test]
+    fn test_quote_escapes_quotes_and_backslashes() {
+        assert_eq!(quote(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(quote("x\ny"), "\"xy\"");
+    }
+
+    #[test]
+    fn test_format_relate_nodes_and_edges() {
+        let out = format_relate(&result(
+            vec![
+                rel("src/a.rs:1", "src/a.rs:3", 0.75, "same_file"),
+                rel("src/a.rs:3", "lib/b.rs:10", 0.4, "shared_keyword"),
+            ],
+            None,
+        ));
+
+        assert!(out.starts_with("graph todo_scan {\n"));
+        assert!(out.ends_with("}\n"));
+        assert!(out.contains("  n0 [label=\"src/a.rs:1\"];\n"));
+        assert!(out.contains("  n1 [label=\"src/a.rs:3\"];\n"));
+        assert!(out.contains("  n2 [label=\"lib/b.rs:10\"];\n"));
+        assert!(out.contains("  n0 -- n1 [label=\"0.75 same_file\", weight=75, penwidth=3.2];\n"));
+        assert!(out.contains("  n1 -- n2 [label=\"0.40 shared_keyword\", weight=40"));
+        assert!(!out.contains("subgraph"));
+    }
+
+    #[test]
+    fn test_format_relate_clusters_become_subgraphs() {
+        let rels = vec![rel("a.rs:1", "a.rs:2", 0.5, "same_file")];
+        let clusters = vec![Cluster {
+            id: 1,
+            theme: "auth \"flow\"".to_string(),
+            items: vec!["a.rs:1".to_string(), "a.rs:2".to_string()],
+            suggested_order: vec!["a.rs:2".to_string(), "a.rs:1".to_string()],
+            relationships: rels.clone(),
+            members: vec![],
+        }];
+        let out = format_relate(&result(rels, Some(clusters)));
+
+        assert!(out.contains("  subgraph cluster_1 {\n    label=\"auth \\\"flow\\\"\";\n"));
+        assert!(out.contains("    n0 [label=\"a.rs:2\"];\n"));
+        assert!(out.contains("    n1 [label=\"a.rs:1\"];\n"));
+        // Clustered nodes are not redeclared at the top level
+        assert_eq!(out.matches("[label=\"a.rs:1\"]").count(), 1);
+        assert!(out.contains("  n1 -- n0 [label=\"0.50 same_file\""));
+    }
+
+    #[test]
+    fn test_format_relate_empty() {
+        let out = format_relate(&result(vec![], None));
+        assert_eq!(out, "graph todo_scan {\n  node [shape=box];\n}\n");
+    }
+}
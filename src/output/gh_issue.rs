@@ -0,0 +1,105 @@
+use crate::model::*;
+
+/// Convert a task into an issue: the subject becomes the title, the task
+/// description (location, priority, issue ref and code context) the body,
+/// and the tag and priority become labels.
+pub fn issue_from_task(task: &ClaudeTask) -> GhIssue {
+    let meta = &task.metadata;
+    GhIssue {
+        title: task.subject.clone(),
+        body: task.description.clone(),
+        labels: vec![
+            meta.todo_scan_tag.to_lowercase(),
+            format!("priority:{}", meta.todo_scan_priority),
+        ],
+    }
+}
+
+/// Render all tasks as a JSON array of `{title, body, labels}` objects.
+pub fn format_tasks(result: &TasksResult) -> String {
+    let issues: Vec<GhIssue> = result.tasks.iter().map(issue_from_task).collect();
+    let mut out = serde_json::to_string_pretty(&issues).expect("failed to serialize");
+    out.push('\n');
+    out
+}
+
+/// Quote a value for YAML front matter. JSON strings are valid YAML
+/// double-quoted scalars, so serde_json handles all escaping.
+fn yaml_quote(s: &str) -> String {
+    serde_json::to_string(s).expect("failed to serialize")
+}
+
+/// Render an issue as a Markdown file with the same front matter GitHub uses
+/// for issue templates (`title`, `labels`), followed by the body.
+pub fn format_issue_file(issue: &GhIssue) -> String {
+    let labels: Vec<String> = issue.labels.iter().map(|l| yaml_quote(l)).collect();
+    format!(
+        "---\ntitle: {}\nlabels: [{}]\n---\n\n{}\n",
+        yaml_quote(&issue.title),
+        labels.join(", "),
+        issue.body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_task() -> ClaudeTask {
+        ClaudeTask {
+            subject: "Fix \"login\" crash".to_string(),
+            description: "**[FIXME]** `src/auth.rs:12`\n\nPriority: High (!)".to_string(),
+            active_form: "Fixing \"login\" crash".to_string(),
+            metadata: ClaudeTaskMetadata {
+                todo_scan_file: "src/auth.rs".to_string(),
+                todo_scan_line: 12,
+                todo_scan_tag: "FIXME".to_string(),
+                todo_scan_priority: "high".to_string(),
+                todo_scan_author: None,
+                todo_scan_issue_ref: Some("#42".to_string()),
+                todo_scan_match_key: "src/auth.rs:fixme:fix login crash".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_issue_from_task() {
+        let issue = issue_from_task(&sample_task());
+        assert_eq!(issue.title, "Fix \"login\" crash");
+        assert!(issue.body.starts_with("**[FIXME]** `src/auth.rs:12`"));
+        assert_eq!(issue.labels, vec!["fixme", "priority:high"]);
+    }
+
+    #[test]
+    fn test_format_tasks_json_array() {
+        let result = TasksResult {
+            tasks: vec![sample_task()],
+            total: 1,
+            output_dir: None,
+        };
+        let json: serde_json::Value = serde_json::from_str(&format_tasks(&result)).unwrap();
+        let issues = json.as_array().unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0]["title"], "Fix \"login\" crash");
+        assert_eq!(issues[0]["labels"][1], "priority:high");
+    }
+
+    #[test]
+    fn test_format_tasks_empty() {
+        let result = TasksResult {
+            tasks: vec![],
+            total: 0,
+            output_dir: None,
+        };
+        assert_eq!(format_tasks(&result), "[]\n");
+    }
+
+    #[test]
+    fn test_format_issue_file_front_matter() {
+        let out = format_issue_file(&issue_from_task(&sample_task()));
+        assert!(out.starts_with(
+            "---\ntitle: \"Fix \\\"login\\\" crash\"\nlabels: [\"fixme\", \"priority:high\"]\n---\n\n"
+        ));
+        assert!(out.ends_with("Priority: High (!)\n"));
+    }
+}
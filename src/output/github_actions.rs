@@ -67,8 +67,8 @@ pub fn format_diff(result: &DiffResult) -> String {
         }
     }
     lines.push(format!(
-        "::notice::todo-scan diff: +{} -{}",
-        result.added_count, result.removed_count
+        "::notice::todo-scan diff: +{} -{} (net {:+}, base {})",
+        result.added_count, result.removed_count, result.net_change, result.base_total
     ));
     lines.push(String::new());
     lines.join("\n")
@@ -129,6 +129,11 @@ pub fn format_lint(result: &LintResult) -> String {
 
 pub fn format_check(result: &CheckResult) -> String {
     let mut lines: Vec<String> = Vec::new();
+    for warning in &result.warnings {
+        let msg = escape_message(&warning.message);
+        let rule = escape_property(&warning.rule);
+        lines.push(format!("::warning title={rule}::{msg}"));
+    }
     if result.passed {
         lines.push("::notice::todo-scan check: PASS".to_string());
     } else {
@@ -174,12 +179,17 @@ mod tests {
         TodoItem {
             file: "src/main.rs".to_string(),
             line: 10,
+            column: 1,
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
@@ -188,6 +198,7 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "implement feature")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -204,6 +215,7 @@ mod tests {
                 sample_item(Tag::Note, "a note"),
             ],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -217,14 +229,20 @@ mod tests {
             items: vec![TodoItem {
                 file: "lib.rs".to_string(),
                 line: 5,
+                column: 1,
                 tag: Tag::Todo,
                 message: "urgent task".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Urgent,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             }],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -236,6 +254,7 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "fix 100% of bugs\nline2")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -258,6 +277,10 @@ mod tests {
             added_count: 1,
             removed_count: 1,
             base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
         let output = format_diff(&result);
         assert!(output.contains("::error file=src/main.rs,line=10,title=FIXME::[FIXME] new fix"));
@@ -270,8 +293,10 @@ mod tests {
     fn test_format_check_pass() {
         let result = CheckResult {
             passed: true,
+            rules: vec![],
             total: 5,
             violations: vec![],
+            warnings: vec![],
         };
         let output = format_check(&result);
         assert!(output.contains("::notice::todo-scan check: PASS"));
@@ -282,17 +307,37 @@ mod tests {
     fn test_format_check_fail() {
         let result = CheckResult {
             passed: false,
+            rules: vec![],
             total: 10,
             violations: vec![CheckViolation {
                 rule: "max".to_string(),
                 message: "10 exceeds max 5".to_string(),
             }],
+            warnings: vec![],
         };
         let output = format_check(&result);
         assert!(output.contains("::error title=max::10 exceeds max 5"));
         assert!(output.contains("::error::todo-scan check: FAIL"));
     }
 
+    #[test]
+    fn test_format_check_warnings() {
+        let result = CheckResult {
+            passed: true,
+            rules: vec![],
+            total: 1,
+            violations: vec![],
+            warnings: vec![CheckViolation {
+                rule: "due_soon".to_string(),
+                message: "Deadline 2025-06-20 in a.rs:1 is due in 5 days".to_string(),
+            }],
+        };
+        let output = format_check(&result);
+        assert!(output
+            .contains("::warning title=due_soon::Deadline 2025-06-20 in a.rs:1 is due in 5 days"));
+        assert!(output.contains("::notice::todo-scan check: PASS"));
+    }
+
     #[test]
     fn test_format_search_escapes_query_with_newline() {
         let result = SearchResult {
@@ -369,6 +414,9 @@ mod tests {
             avg_age_days: 400,
             stale_count: 1,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let output = format_blame(&result);
         assert!(output.contains("::warning file=src/main.rs,line=10,title=Stale TODO::"));
@@ -395,6 +443,9 @@ mod tests {
             avg_age_days: 10,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let output = format_blame(&result);
         assert!(output.contains("::notice file=src/main.rs,line=10,title=FIXME::"));
@@ -440,6 +491,7 @@ mod tests {
         let item = TodoItem {
             file: "test.rs".to_string(),
             line: 5,
+            column: 1,
             tag: Tag::Todo,
             message: "task".to_string(),
             author: None,
@@ -450,6 +502,10 @@ mod tests {
                 month: 6,
                 day: 15,
             }),
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         let output = format_item_annotation(&item);
         assert!(output.contains("(deadline: 2025-06-15)"));
@@ -0,0 +1,88 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::as_24_bit_terminal_escaped;
+
+const THEME_NAME: &str = "base16-ocean.dark";
+
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+static THEME: LazyLock<Theme> = LazyLock::new(|| {
+    ThemeSet::load_defaults()
+        .themes
+        .remove(THEME_NAME)
+        .expect("bundled theme should exist")
+});
+
+/// Syntax-highlight consecutive lines of `file` for a 24-bit color terminal,
+/// choosing the syntax by file extension.
+///
+/// Lines must already be passed through `sanitize_for_terminal`; the only
+/// escapes in the output are the ones added here. Returns `None` for unknown
+/// extensions or when highlighting fails, so callers can print plain lines.
+pub fn highlight_lines(file: &str, lines: &[String]) -> Option<Vec<String>> {
+    let ext = Path::new(file).extension()?.to_str()?;
+    let syntax = SYNTAXES.find_syntax_by_extension(ext)?;
+    let mut highlighter = HighlightLines::new(syntax, &THEME);
+
+    lines
+        .iter()
+        .map(|line| {
+            // The newline-aware syntaxes expect each line to end with '\n'
+            let line = format!("{}\n", line);
+            let ranges = highlighter.highlight_line(&line, &SYNTAXES).ok()?;
+            let escaped = as_24_bit_terminal_escaped(&ranges, false);
+            Some(format!("{}\x1b[0m", escaped.trim_end_matches('\n')))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip_escapes(s: &str) -> String {
+        let mut out = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                for c in chars.by_ref() {
+                    if c == 'm' {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_highlight_known_extension() {
+        let lines = vec!["fn main() {".to_string(), "}".to_string()];
+        let highlighted = highlight_lines("src/main.rs", &lines).unwrap();
+
+        assert_eq!(highlighted.len(), 2);
+        assert!(highlighted[0].contains("\x1b[38;2;"));
+        assert!(highlighted[0].ends_with("\x1b[0m"));
+        assert_eq!(strip_escapes(&highlighted[0]), "fn main() {");
+        assert_eq!(strip_escapes(&highlighted[1]), "}");
+    }
+
+    #[test]
+    fn test_highlight_unknown_extension_returns_none() {
+        let lines = vec!["whatever".to_string()];
+        assert!(highlight_lines("data.unknownext", &lines).is_none());
+        assert!(highlight_lines("Makefile-no-ext", &lines).is_none());
+    }
+
+    #[test]
+    fn test_highlight_empty_lines() {
+        let highlighted = highlight_lines("a.py", &[]).unwrap();
+        assert!(highlighted.is_empty());
+    }
+}
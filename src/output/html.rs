@@ -1,4 +1,6 @@
-use crate::model::ReportResult;
+use std::fmt::Write;
+
+use crate::model::{ReportResult, StatsResult};
 
 /// Render a self-contained HTML dashboard report.
 pub fn render_html(report: &ReportResult) -> String {
@@ -189,6 +191,23 @@ footer {{
   </div>
 </div>
 
+<div class="section" id="resolved-section" style="display:none">
+  <h2>Resolved Recently (<span id="resolved-count"></span>)</h2>
+  <table id="resolved-table">
+    <thead>
+      <tr>
+        <th>File</th>
+        <th>Line</th>
+        <th>Tag</th>
+        <th>Message</th>
+        <th>Last Seen</th>
+      </tr>
+    </thead>
+    <tbody></tbody>
+  </table>
+  <p id="resolved-empty" style="display:none;color:var(--text-muted);text-align:center;padding:1rem;">No TODOs resolved in the sampled history</p>
+</div>
+
 <div class="section">
   <h2>All Items (<span id="item-count"></span>)</h2>
   <table id="items-table">
@@ -227,6 +246,10 @@ const REPORT_DATA = {safe_json};
     {{ value: D.summary.stale_count, label: 'Stale', cls: D.summary.stale_count > 0 ? 'warning' : 'success' }},
     {{ value: D.summary.avg_age_days + 'd', label: 'Avg Age', cls: '' }},
   ];
+  if (D.resolved) {{
+    cards.push({{ value: D.resolved.items.length, label: 'Resolved', cls: 'success' }});
+    cards.push({{ value: Math.round(D.resolved.resolution_rate * 100) + '%', label: 'Resolution Rate', cls: '' }});
+  }}
   const cardsEl = document.getElementById('summary-cards');
   cards.forEach(c => {{
     const div = document.createElement('div');
@@ -423,6 +446,27 @@ const REPORT_DATA = {safe_json};
     tbody.appendChild(tr);
   }});
 
+  // Resolved recently
+  if (D.resolved) {{
+    document.getElementById('resolved-section').style.display = 'block';
+    document.getElementById('resolved-count').textContent = D.resolved.items.length;
+    const resolvedBody = document.querySelector('#resolved-table tbody');
+    D.resolved.items.forEach(item => {{
+      const tr = document.createElement('tr');
+      tr.innerHTML =
+        '<td>' + escapeHtml(item.file) + '</td>' +
+        '<td>' + item.line + '</td>' +
+        '<td><span class="tag tag-' + escapeHtml(item.tag) + '">' + escapeHtml(item.tag) + '</span></td>' +
+        '<td>' + escapeHtml(item.message) + '</td>' +
+        '<td>' + escapeHtml(item.last_seen_date + ' (' + item.last_seen_commit + ')') + '</td>';
+      resolvedBody.appendChild(tr);
+    }});
+    if (D.resolved.items.length === 0) {{
+      document.getElementById('resolved-table').style.display = 'none';
+      document.getElementById('resolved-empty').style.display = 'block';
+    }}
+  }}
+
   // Sortable table
   let sortCol = 'file', sortAsc = true;
   document.querySelectorAll('#items-table th').forEach(th => {{
@@ -453,6 +497,120 @@ const REPORT_DATA = {safe_json};
     )
 }
 
+/// Escape text for HTML element content and quoted attribute values.
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const BAR_ROW_HEIGHT: usize = 22;
+const BAR_LABEL_WIDTH: usize = 160;
+const BAR_MAX_WIDTH: usize = 240;
+
+/// Horizontal bar chart as inline SVG, one row per `(label, count)`, with
+/// bars scaled to the largest count. Long labels are cut; the full text is
+/// kept in a `<title>` tooltip.
+fn svg_bar_chart(rows: &[(String, usize)], color: &str) -> String {
+    let max = rows.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1);
+    let width = BAR_LABEL_WIDTH + BAR_MAX_WIDTH + 60;
+    let height = rows.len() * BAR_ROW_HEIGHT;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" role=\"img\" font-size=\"12\">\n"
+    );
+    for (i, (label, count)) in rows.iter().enumerate() {
+        let y = i * BAR_ROW_HEIGHT;
+        let text_y = y + BAR_ROW_HEIGHT / 2 + 4;
+        let bar_width = (count * BAR_MAX_WIDTH).div_ceil(max);
+        let shown: String = if label.chars().count() > 24 {
+            label.chars().take(23).chain(['…']).collect()
+        } else {
+            label.clone()
+        };
+        let _ = writeln!(
+            svg,
+            "<g><title>{title}: {count}</title>\
+             <text x=\"0\" y=\"{text_y}\" fill=\"currentColor\">{label}</text>\
+             <rect x=\"{BAR_LABEL_WIDTH}\" y=\"{bar_y}\" width=\"{bar_width}\" height=\"14\" rx=\"2\" fill=\"{color}\"/>\
+             <text x=\"{count_x}\" y=\"{text_y}\" fill=\"currentColor\">{count}</text></g>",
+            title = escape_html(label),
+            label = escape_html(&shown),
+            bar_y = y + 4,
+            count_x = BAR_LABEL_WIDTH + bar_width + 6,
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render stats as an HTML fragment for embedding in an existing page: a
+/// single `<div>` with inline SVG bar charts for tags and authors. Unlike
+/// `render_html` there is no document wrapper, stylesheet or script.
+pub fn render_stats_fragment(result: &StatsResult) -> String {
+    let mut out = String::from(
+        "<div class=\"todo-scan-stats\" style=\"font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif\">\n",
+    );
+    let _ = writeln!(
+        out,
+        "<p><strong>{}</strong> items across <strong>{}</strong> files</p>",
+        result.total_items, result.total_files
+    );
+
+    let tags: Vec<(String, usize)> = result
+        .tag_counts
+        .iter()
+        .map(|(tag, count)| (tag.as_str().to_string(), *count))
+        .collect();
+    if !tags.is_empty() {
+        out.push_str("<h3>Tags</h3>\n");
+        out.push_str(&svg_bar_chart(&tags, "#0d6efd"));
+    }
+
+    let pc = &result.priority_counts;
+    let _ = writeln!(
+        out,
+        "<p>Priority: normal {} | high {} | urgent {}</p>",
+        pc.normal, pc.high, pc.urgent
+    );
+
+    if !result.author_counts.is_empty() {
+        out.push_str("<h3>Authors</h3>\n");
+        out.push_str(&svg_bar_chart(&result.author_counts, "#198754"));
+    }
+
+    if !result.hotspot_files.is_empty() {
+        out.push_str("<h3>Hotspots</h3>\n<ul>\n");
+        for (file, count) in &result.hotspot_files {
+            let _ = writeln!(out, "<li><code>{}</code> ({count})</li>", escape_html(file));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if let Some(ref trend) = result.trend {
+        let net = trend.added as i64 - trend.removed as i64;
+        let _ = writeln!(
+            out,
+            "<p>Trend since <code>{}</code>: {} added, {} removed ({net:+})</p>",
+            escape_html(&trend.base_ref),
+            trend.added,
+            trend.removed
+        );
+    }
+
+    out.push_str("</div>\n");
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -481,6 +639,7 @@ mod tests {
             history: vec![],
             age_histogram: vec![],
             items: vec![],
+            resolved: None,
         }
     }
 
@@ -496,12 +655,17 @@ mod tests {
         report.items.push(TodoItem {
             file: "test.rs".to_string(),
             line: 1,
+            column: 1,
             tag: Tag::Todo,
             message: "hello world".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         });
         let html = render_html(&report);
         // Extract JSON from REPORT_DATA
@@ -512,18 +676,69 @@ mod tests {
         assert_eq!(parsed["items"][0]["message"], "hello world");
     }
 
+    #[test]
+    fn test_render_html_embeds_resolved_items() {
+        let mut report = minimal_report();
+        report.resolved = Some(ResolvedReport {
+            items: vec![ResolvedItem {
+                item: TodoItem {
+                    file: "old.rs".to_string(),
+                    line: 3,
+                    column: 1,
+                    tag: Tag::Fixme,
+                    message: "gone now".to_string(),
+                    author: None,
+                    issue_ref: None,
+                    priority: Priority::Normal,
+                    deadline: None,
+                    relative_deadline: None,
+                    checked: None,
+                    escalate_to: None,
+                    mentions: vec![],
+                },
+                last_seen_commit: "abc12345".to_string(),
+                last_seen_date: "2025-01-10".to_string(),
+            }],
+            resolution_rate: 0.5,
+        });
+        let html = render_html(&report);
+        assert!(html.contains("Resolved Recently"));
+        let start = html.find("const REPORT_DATA = ").unwrap() + "const REPORT_DATA = ".len();
+        let end = html[start..].find(";\n").unwrap() + start;
+        let parsed: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+        assert_eq!(parsed["resolved"]["resolution_rate"], 0.5);
+        assert_eq!(parsed["resolved"]["items"][0]["message"], "gone now");
+        assert_eq!(
+            parsed["resolved"]["items"][0]["last_seen_commit"],
+            "abc12345"
+        );
+
+        let parsed_without: serde_json::Value = {
+            let html = render_html(&minimal_report());
+            let start = html.find("const REPORT_DATA = ").unwrap() + "const REPORT_DATA = ".len();
+            let end = html[start..].find(";\n").unwrap() + start;
+            serde_json::from_str(&html[start..end]).unwrap()
+        };
+        assert!(parsed_without.get("resolved").is_none());
+    }
+
     #[test]
     fn test_render_html_escapes_script_tags() {
         let mut report = minimal_report();
         report.items.push(TodoItem {
             file: "test.rs".to_string(),
             line: 1,
+            column: 1,
             tag: Tag::Todo,
             message: "has </script> in it".to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         });
         let html = render_html(&report);
         // The raw </script> should not appear inside our <script> block
@@ -559,12 +774,17 @@ mod tests {
             report.items.push(TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                column: 1,
                 tag: Tag::Todo,
                 message: format!("xss attempt {variant}"),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             });
             let html = render_html(&report);
             let script_start = html.find("const REPORT_DATA = ").unwrap();
@@ -577,4 +797,52 @@ mod tests {
             );
         }
     }
+
+    fn stats_with_author(author: &str) -> StatsResult {
+        StatsResult {
+            total_items: 3,
+            total_files: 2,
+            tag_counts: vec![(Tag::Todo, 2), (Tag::Fixme, 1)],
+            priority_counts: PriorityCounts {
+                normal: 3,
+                high: 0,
+                urgent: 0,
+            },
+            author_counts: vec![(author.to_string(), 2)],
+            hotspot_files: vec![("src/<main>.rs".to_string(), 2)],
+            total_authors: 1,
+            truncated: false,
+            trend: None,
+            author_churn: None,
+            history: None,
+        }
+    }
+
+    #[test]
+    fn test_render_stats_fragment_is_a_single_div() {
+        let html = render_stats_fragment(&stats_with_author("alice"));
+        assert!(html.starts_with("<div class=\"todo-scan-stats\""));
+        assert!(html.trim_end().ends_with("</div>"));
+        assert!(!html.contains("<html"));
+        assert!(!html.contains("<head"));
+        assert!(!html.contains("<script"));
+        assert_eq!(html.matches("<svg").count(), 2);
+        assert!(html.contains(">TODO</text>"));
+        assert!(html.contains("<strong>3</strong> items across <strong>2</strong> files"));
+    }
+
+    #[test]
+    fn test_render_stats_fragment_escapes_user_content() {
+        let html = render_stats_fragment(&stats_with_author("<img src=x onerror=alert(1)>"));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("&lt;img src=x onerror=alert(1)&gt;"));
+        assert!(html.contains("src/&lt;main&gt;.rs"));
+    }
+
+    #[test]
+    fn test_svg_bar_chart_scales_to_largest_count() {
+        let svg = svg_bar_chart(&[("a".to_string(), 4), ("b".to_string(), 2)], "red");
+        assert!(svg.contains(&format!("width=\"{BAR_MAX_WIDTH}\" height=\"14\"")));
+        assert!(svg.contains(&format!("width=\"{}\" height=\"14\"", BAR_MAX_WIDTH / 2)));
+    }
 }
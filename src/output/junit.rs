@@ -0,0 +1,265 @@
+//! JUnit XML for `check` and `lint`, so CI test reporters (Jenkins, GitLab)
+//! can show results alongside unit tests.
+
+use crate::model::*;
+
+/// Escape text for XML attributes and content. Characters XML 1.0 cannot
+/// represent at all are dropped.
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() && (c as u32) < 0x20 => {}
+            '\u{FFFE}' | '\u{FFFF}' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+struct Failure<'a> {
+    kind: &'a str,
+    message: &'a str,
+    body: String,
+}
+
+struct TestCase<'a> {
+    classname: String,
+    name: String,
+    failure: Option<Failure<'a>>,
+    output: Option<&'a str>,
+}
+
+fn render_suite(suite: &str, cases: &[TestCase]) -> String {
+    let failures = cases.iter().filter(|c| c.failure.is_some()).count();
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"todo-scan\" tests=\"{}\" failures=\"{}\">\n",
+        cases.len(),
+        failures
+    ));
+    out.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"0\">\n",
+        escape_xml(suite),
+        cases.len(),
+        failures
+    ));
+    for case in cases {
+        let open = format!(
+            "    <testcase classname=\"{}\" name=\"{}\"",
+            escape_xml(&case.classname),
+            escape_xml(&case.name)
+        );
+        if case.failure.is_none() && case.output.is_none() {
+            out.push_str(&open);
+            out.push_str("/>\n");
+            continue;
+        }
+        out.push_str(&open);
+        out.push_str(">\n");
+        if let Some(ref failure) = case.failure {
+            out.push_str(&format!(
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>\n",
+                escape_xml(failure.kind),
+                escape_xml(failure.message),
+                escape_xml(&failure.body)
+            ));
+        }
+        if let Some(output) = case.output {
+            out.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(output)
+            ));
+        }
+        out.push_str("    </testcase>\n");
+    }
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    out
+}
+
+/// One failing case per violation and one passing case per evaluated rule
+/// that found nothing. `--due-soon` warnings are passing cases carrying the
+/// warning as output. A run with nothing else to report gets a single
+/// passing summary case.
+pub fn format_check(result: &CheckResult) -> String {
+    let mut cases: Vec<TestCase> = result
+        .violations
+        .iter()
+        .map(|v| TestCase {
+            classname: format!("todo-scan.check.{}", v.rule),
+            name: v.message.clone(),
+            failure: Some(Failure {
+                kind: &v.rule,
+                message: &v.message,
+                body: v.message.clone(),
+            }),
+            output: None,
+        })
+        .collect();
+    let reported = |rule: &str| {
+        result
+            .violations
+            .iter()
+            .chain(&result.warnings)
+            .any(|v| v.rule == rule)
+    };
+    cases.extend(
+        result
+            .rules
+            .iter()
+            .filter(|rule| !reported(rule))
+            .map(|rule| TestCase {
+                classname: format!("todo-scan.check.{}", rule),
+                name: format!("{}: {} TODOs pass", rule, result.total),
+                failure: None,
+                output: None,
+            }),
+    );
+    if cases.is_empty() {
+        cases.push(TestCase {
+            classname: "todo-scan.check".to_string(),
+            name: format!("{} TODOs pass all checks", result.total),
+            failure: None,
+            output: None,
+        });
+    }
+    cases.extend(result.warnings.iter().map(|w| TestCase {
+        classname: format!("todo-scan.check.{}", w.rule),
+        name: w.message.clone(),
+        failure: None,
+        output: Some(&w.message),
+    }));
+    render_suite("todo-scan check", &cases)
+}
+
+/// One failing case per violation, named by its location and rule. A run
+/// without violations is a single passing case.
+pub fn format_lint(result: &LintResult) -> String {
+    let mut cases: Vec<TestCase> = result
+        .violations
+        .iter()
+        .map(|v| {
+            let mut body = v.message.clone();
+            if let Some(ref suggestion) = v.suggestion {
+                body.push_str(&format!("\nsuggestion: {}", suggestion));
+            }
+            TestCase {
+                classname: format!("todo-scan.lint.{}", v.rule),
+                name: format!("{}:{} {}", v.file, v.line, v.rule),
+                failure: Some(Failure {
+                    kind: &v.rule,
+                    message: &v.message,
+                    body,
+                }),
+                output: None,
+            }
+        })
+        .collect();
+    if cases.is_empty() {
+        cases.push(TestCase {
+            classname: "todo-scan.lint".to_string(),
+            name: format!("{} items pass all lint rules", result.total_items),
+            failure: None,
+            output: None,
+        });
+    }
+    render_suite("todo-scan lint", &cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("a<b> & \"c\" 'd'\x1b"),
+            "a&lt;b&gt; &amp; &quot;c&quot; &apos;d&apos;"
+        );
+    }
+
+    #[test]
+    fn test_format_check_failures() {
+        let result = CheckResult {
+            passed: false,
+            rules: vec![],
+            total: 3,
+            violations: vec![CheckViolation {
+                rule: "max".to_string(),
+                message: "Total TODOs (3) exceeds max (1)".to_string(),
+            }],
+            warnings: vec![],
+        };
+        let xml = format_check(&result);
+        assert!(xml.contains("<testsuites name=\"todo-scan\" tests=\"1\" failures=\"1\">"));
+        assert!(xml.contains("classname=\"todo-scan.check.max\""));
+        assert!(xml.contains("<failure type=\"max\" message=\"Total TODOs (3) exceeds max (1)\">"));
+    }
+
+    #[test]
+    fn test_format_check_lists_passing_rules() {
+        let result = CheckResult {
+            passed: false,
+            total: 3,
+            violations: vec![CheckViolation {
+                rule: "max".to_string(),
+                message: "Total TODOs (3) exceeds max (1)".to_string(),
+            }],
+            warnings: vec![],
+            rules: vec!["max".to_string(), "expired".to_string()],
+        };
+        let xml = format_check(&result);
+        assert!(xml.contains("<testsuites name=\"todo-scan\" tests=\"2\" failures=\"1\">"));
+        assert!(xml.contains(
+            "<testcase classname=\"todo-scan.check.expired\" name=\"expired: 3 TODOs pass\"/>"
+        ));
+        assert!(!xml.contains("pass all checks"));
+    }
+
+    #[test]
+    fn test_format_check_pass_and_warnings() {
+        let result = CheckResult {
+            passed: true,
+            rules: vec![],
+            total: 2,
+            violations: vec![],
+            warnings: vec![CheckViolation {
+                rule: "due_soon".to_string(),
+                message: "Deadline 2025-01-02 in a.rs:1 is due in 1 day".to_string(),
+            }],
+        };
+        let xml = format_check(&result);
+        assert!(xml.contains("tests=\"2\" failures=\"0\""));
+        assert!(xml.contains("name=\"2 TODOs pass all checks\"/>"));
+        assert!(
+            xml.contains("<system-out>Deadline 2025-01-02 in a.rs:1 is due in 1 day</system-out>")
+        );
+        assert!(!xml.contains("<failure"));
+    }
+
+    #[test]
+    fn test_format_lint_names_location_and_escapes() {
+        let result = LintResult {
+            passed: false,
+            total_items: 1,
+            violation_count: 1,
+            violations: vec![LintViolation {
+                rule: "require_colon".to_string(),
+                message: "Missing colon after <TODO>".to_string(),
+                file: "src/a&b.rs".to_string(),
+                line: 7,
+                suggestion: Some("TODO: fix".to_string()),
+            }],
+        };
+        let xml = format_lint(&result);
+        assert!(xml.contains("name=\"src/a&amp;b.rs:7 require_colon\""));
+        assert!(xml.contains("message=\"Missing colon after &lt;TODO&gt;\""));
+        assert!(xml.contains("suggestion: TODO: fix</failure>"));
+    }
+}
@@ -116,17 +116,54 @@ pub fn format_diff(result: &DiffResult) -> String {
     }
 
     lines.push(String::new());
+    let head = result
+        .head_ref
+        .as_deref()
+        .map(|h| format!(", head: `{}`", escape_cell(h)))
+        .unwrap_or_default();
     lines.push(format!(
-        "**+{} -{}** (base: `{}`)",
+        "**+{} -{}** (base: `{}`{head}) (net {:+}, base {})",
         result.added_count,
         result.removed_count,
-        escape_cell(&result.base_ref)
+        escape_cell(&result.base_ref),
+        result.net_change,
+        result.base_total
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_diff_stat(result: &DiffStatResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    lines.push("| File | Added | Removed |".to_string());
+    lines.push("|------|-------|---------|".to_string());
+    for file in &result.files {
+        lines.push(format!(
+            "| {} | +{} | -{} |",
+            escape_cell(&file.file),
+            file.added,
+            file.removed
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**{} files changed, +{} -{}** (net {:+})",
+        result.files.len(),
+        result.added_count,
+        result.removed_count,
+        result.net_change
     ));
     lines.push(String::new());
     lines.join("\n")
 }
 
 pub fn format_blame(result: &BlameResult) -> String {
+    if let Some(ref authors) = result.authors {
+        return format_blame_authors(result, authors);
+    }
+
     let mut lines: Vec<String> = Vec::new();
 
     lines.push("| File | Line | Tag | Message | Author | Date | Age (days) | Stale |".to_string());
@@ -154,6 +191,34 @@ pub fn format_blame(result: &BlameResult) -> String {
     lines.join("\n")
 }
 
+fn format_blame_authors(result: &BlameResult, authors: &[BlameAuthorSummary]) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    lines.push("| Author | Count | Avg age (days) | Stale |".to_string());
+    lines.push("|--------|------:|---------------:|------:|".to_string());
+    for a in authors {
+        lines.push(format!(
+            "| {} | {} | {} | {} |",
+            escape_cell(&a.author),
+            a.count,
+            a.avg_age_days,
+            a.stale_count
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**{} items by {} authors, avg age {} days, {} stale** (threshold: {} days)",
+        result.total,
+        authors.len(),
+        result.avg_age_days,
+        result.stale_count,
+        result.stale_threshold_days,
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
 pub fn format_lint(result: &LintResult) -> String {
     let mut lines: Vec<String> = Vec::new();
 
@@ -210,6 +275,18 @@ pub fn format_check(result: &CheckResult) -> String {
             ));
         }
     }
+    if !result.warnings.is_empty() {
+        lines.push(String::new());
+        lines.push("### Warnings".to_string());
+        lines.push(String::new());
+        for warning in &result.warnings {
+            lines.push(format!(
+                "- **{}**: {}",
+                escape_cell(&warning.rule),
+                escape_cell(&warning.message)
+            ));
+        }
+    }
     lines.push(String::new());
     lines.join("\n")
 }
@@ -261,6 +338,86 @@ pub fn format_clean(result: &CleanResult) -> String {
     lines.join("\n")
 }
 
+/// Append a two-column name/count table; counts are right-aligned.
+fn push_count_table(lines: &mut Vec<String>, title: &str, header: &str, rows: &[(String, usize)]) {
+    lines.push(format!("## {title}"));
+    lines.push(String::new());
+    lines.push(format!("| {header} | Count |"));
+    lines.push(format!("|{}|------:|", "-".repeat(header.len() + 2)));
+    for (name, count) in rows {
+        lines.push(format!("| {} | {count} |", escape_cell(name)));
+    }
+    lines.push(String::new());
+}
+
+pub fn format_stats(result: &StatsResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    let tags: Vec<(String, usize)> = result
+        .tag_counts
+        .iter()
+        .map(|(tag, count)| (tag.as_str().to_string(), *count))
+        .collect();
+    push_count_table(&mut lines, "Tags", "Tag", &tags);
+
+    let pc = &result.priority_counts;
+    let priorities = [
+        ("normal".to_string(), pc.normal),
+        ("high".to_string(), pc.high),
+        ("urgent".to_string(), pc.urgent),
+    ];
+    push_count_table(&mut lines, "Priority", "Priority", &priorities);
+
+    if !result.author_counts.is_empty() {
+        push_count_table(&mut lines, "Authors", "Author", &result.author_counts);
+    }
+    if !result.hotspot_files.is_empty() {
+        push_count_table(&mut lines, "Hotspots", "File", &result.hotspot_files);
+    }
+
+    if let Some(churn) = result.author_churn.as_ref().filter(|c| !c.is_empty()) {
+        lines.push("## Author churn".to_string());
+        lines.push(String::new());
+        lines.push("| Author | Added | Removed | Net |".to_string());
+        lines.push("|--------|------:|--------:|----:|".to_string());
+        for c in churn {
+            let net = c.added as i64 - c.removed as i64;
+            let sign = if net > 0 { "+" } else { "" };
+            lines.push(format!(
+                "| {} | {} | {} | {sign}{net} |",
+                escape_cell(&c.author),
+                c.added,
+                c.removed
+            ));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push(format!(
+        "**{} items across {} files**",
+        result.total_items, result.total_files
+    ));
+    if let Some(ref trend) = result.trend {
+        let net = trend.added as i64 - trend.removed as i64;
+        let sign = if net > 0 { "+" } else { "" };
+        lines.push(String::new());
+        lines.push(format!(
+            "Trend since `{}`: {} added, {} removed ({sign}{net})",
+            escape_cell(&trend.base_ref),
+            trend.added,
+            trend.removed
+        ));
+    }
+    if let Some(ref history) = result.history {
+        if let Some(summary) = super::history_summary(history) {
+            lines.push(String::new());
+            lines.push(format!("History ({} commits): {}", history.len(), summary));
+        }
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,12 +426,17 @@ mod tests {
         TodoItem {
             file: "src/main.rs".to_string(),
             line: 10,
+            column: 1,
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
@@ -283,6 +445,7 @@ mod tests {
         let result = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -297,14 +460,20 @@ mod tests {
             items: vec![TodoItem {
                 file: "lib.rs".to_string(),
                 line: 42,
+                column: 1,
                 tag: Tag::Todo,
                 message: "add tests".to_string(),
                 author: Some("alice".to_string()),
                 issue_ref: Some("#123".to_string()),
                 priority: Priority::High,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             }],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -343,14 +512,20 @@ mod tests {
             items: vec![TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                column: 1,
                 tag: Tag::Todo,
                 message: "task".to_string(),
                 author: Some("user\ninjected".to_string()),
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             }],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -364,14 +539,20 @@ mod tests {
             items: vec![TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                column: 1,
                 tag: Tag::Todo,
                 message: "task".to_string(),
                 author: None,
                 issue_ref: Some("[link](evil)".to_string()),
                 priority: Priority::Normal,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             }],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -397,6 +578,9 @@ mod tests {
             avg_age_days: 10,
             stale_count: 0,
             stale_threshold_days: 180,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let output = format_blame(&result);
         assert!(output.contains("user\\|inject"));
@@ -426,12 +610,42 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "a | b")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
         assert!(output.contains("a \\| b"));
     }
 
+    #[test]
+    fn test_format_diff_stat_table() {
+        let result = DiffStatResult {
+            files: vec![
+                DiffFileStat {
+                    file: "src/a|b.rs".to_string(),
+                    added: 2,
+                    removed: 1,
+                },
+                DiffFileStat {
+                    file: "lib.rs".to_string(),
+                    added: 0,
+                    removed: 1,
+                },
+            ],
+            added_count: 2,
+            removed_count: 2,
+            net_change: 0,
+            base_ref: "main".to_string(),
+            head_ref: None,
+        };
+        let output = format_diff_stat(&result);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "| File | Added | Removed |");
+        assert_eq!(lines[2], "| src/a\\|b.rs | +2 | -1 |");
+        assert_eq!(lines[3], "| lib.rs | +0 | -1 |");
+        assert_eq!(lines[5], "**2 files changed, +2 -2** (net +0)");
+    }
+
     #[test]
     fn test_format_diff_table() {
         let result = DiffResult {
@@ -448,6 +662,10 @@ mod tests {
             added_count: 1,
             removed_count: 1,
             base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
         let output = format_diff(&result);
         assert!(output.contains("| + | src/main.rs | 10 | FIXME | new fix |"));
@@ -459,8 +677,10 @@ mod tests {
     fn test_format_check_pass() {
         let result = CheckResult {
             passed: true,
+            rules: vec![],
             total: 3,
             violations: vec![],
+            warnings: vec![],
         };
         let output = format_check(&result);
         assert!(output.contains("## PASS"));
@@ -471,11 +691,13 @@ mod tests {
     fn test_format_check_fail() {
         let result = CheckResult {
             passed: false,
+            rules: vec![],
             total: 10,
             violations: vec![CheckViolation {
                 rule: "max".to_string(),
                 message: "10 exceeds max 5".to_string(),
             }],
+            warnings: vec![],
         };
         let output = format_check(&result);
         assert!(output.contains("## FAIL"));
@@ -511,12 +733,17 @@ mod tests {
             items: vec![TodoItem {
                 file: "lib.rs".to_string(),
                 line: 5,
+                column: 1,
                 tag: Tag::Fixme,
                 message: "fix this".to_string(),
                 author: Some("bob".to_string()),
                 issue_ref: Some("#42".to_string()),
                 priority: Priority::Urgent,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             }],
             match_count: 1,
             file_count: 1,
@@ -544,6 +771,9 @@ mod tests {
             avg_age_days: 700,
             stale_count: 1,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let output = format_blame(&result);
         assert!(output
@@ -569,6 +799,9 @@ mod tests {
             avg_age_days: 10,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let output = format_blame(&result);
         assert!(
@@ -577,6 +810,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_blame_group_by_author() {
+        let result = BlameResult {
+            entries: vec![],
+            total: 3,
+            avg_age_days: 40,
+            stale_count: 1,
+            stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: Some(vec![
+                BlameAuthorSummary {
+                    author: "al|ice".to_string(),
+                    count: 2,
+                    avg_age_days: 55,
+                    stale_count: 1,
+                },
+                BlameAuthorSummary {
+                    author: "bob".to_string(),
+                    count: 1,
+                    avg_age_days: 10,
+                    stale_count: 0,
+                },
+            ]),
+        };
+        let output = format_blame(&result);
+        assert!(output.contains(
+            "| Author | Count | Avg age (days) | Stale |\n|--------|------:|---------------:|------:|\n| al\\|ice | 2 | 55 | 1 |\n| bob | 1 | 10 | 0 |\n"
+        ));
+        assert!(output.contains("**3 items by 2 authors, avg age 40 days, 1 stale**"));
+        assert!(!output.contains("| File |"));
+    }
+
     #[test]
     fn test_format_lint_pass() {
         let result = LintResult {
@@ -719,6 +985,7 @@ mod tests {
             items: vec![TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                column: 1,
                 tag: Tag::Todo,
                 message: "task".to_string(),
                 author: None,
@@ -729,11 +996,92 @@ mod tests {
                     month: 6,
                     day: 15,
                 }),
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             }],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
         assert!(output.contains("2025-06-15"));
     }
+
+    fn sample_stats() -> StatsResult {
+        StatsResult {
+            total_items: 5,
+            total_files: 2,
+            tag_counts: vec![(Tag::Todo, 3), (Tag::Fixme, 2)],
+            priority_counts: PriorityCounts {
+                normal: 3,
+                high: 1,
+                urgent: 1,
+            },
+            author_counts: vec![("al|ice".to_string(), 2)],
+            hotspot_files: vec![("src/main.rs".to_string(), 4)],
+            trend: Some(TrendInfo {
+                added: 3,
+                removed: 1,
+                base_ref: "main".to_string(),
+            }),
+            author_churn: None,
+            history: None,
+            total_authors: 1,
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_format_stats_tables() {
+        let output = format_stats(&sample_stats());
+
+        assert!(output.contains(
+            "## Tags\n\n| Tag | Count |\n|-----|------:|\n| TODO | 3 |\n| FIXME | 2 |\n"
+        ));
+        assert!(output.contains("| Priority | Count |\n|----------|------:|\n| normal | 3 |\n| high | 1 |\n| urgent | 1 |\n"));
+        assert!(output
+            .contains("## Authors\n\n| Author | Count |\n|--------|------:|\n| al\\|ice | 2 |\n"));
+        assert!(output.contains("| File | Count |\n|------|------:|\n| src/main.rs | 4 |\n"));
+        assert!(output.contains("**5 items across 2 files**"));
+        assert!(output.contains("Trend since `main`: 3 added, 1 removed (+2)"));
+        assert!(!output.contains("Author churn"));
+    }
+
+    #[test]
+    fn test_format_stats_omits_empty_sections() {
+        let mut stats = sample_stats();
+        stats.author_counts.clear();
+        stats.hotspot_files.clear();
+        stats.trend = None;
+        let output = format_stats(&stats);
+
+        assert!(!output.contains("## Authors"));
+        assert!(!output.contains("## Hotspots"));
+        assert!(!output.contains("Trend since"));
+    }
+
+    #[test]
+    fn test_format_stats_author_churn() {
+        let mut stats = sample_stats();
+        stats.author_churn = Some(vec![
+            AuthorChurn {
+                author: "bob".to_string(),
+                added: 2,
+                removed: 0,
+            },
+            AuthorChurn {
+                author: "carol".to_string(),
+                added: 0,
+                removed: 3,
+            },
+        ]);
+        let output = format_stats(&stats);
+
+        assert!(output
+            .contains("| Author | Added | Removed | Net |\n|--------|------:|--------:|----:|\n"));
+        assert!(output.contains("| bob | 2 | 0 | +2 |"));
+        assert!(output.contains("| carol | 0 | 3 | -3 |"));
+    }
 }
@@ -1,20 +1,31 @@
+mod azure_devops;
+mod csv;
+mod dot;
+pub mod gh_issue;
 mod github_actions;
+mod highlight;
 pub mod html;
+mod junit;
 mod markdown;
 mod sarif;
+pub mod template;
+mod wrap;
+mod xcode;
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
 use colored::*;
 
-use crate::cli::{DetailLevel, Format, GroupBy};
+use crate::cli::{ColorChoice, DetailLevel, Format, GroupBy, ListField};
 use crate::context::{ContextInfo, RichContext};
 use crate::model::*;
 use std::path::Path;
 
 /// Apply detail-level transformations to a flat JSON item (TodoItem-shaped object).
 /// - Always: inject stable `id` field
-/// - Minimal: remove author, issue_ref, priority, deadline
+/// - Minimal: remove author, issue_ref, priority, deadline, relative_deadline
 /// - Full: inject match_key (backward compatibility)
 fn apply_detail_to_json_item(item_val: &mut serde_json::Value, detail: &DetailLevel) {
     inject_id_field(item_val);
@@ -25,6 +36,9 @@ fn apply_detail_to_json_item(item_val: &mut serde_json::Value, detail: &DetailLe
         obj.remove("issue_ref");
         obj.remove("priority");
         obj.remove("deadline");
+        obj.remove("relative_deadline");
+        obj.remove("checked");
+        obj.remove("mentions");
     }
     if *detail == DetailLevel::Full {
         let id = item_val["id"].as_str().unwrap_or("").to_string();
@@ -35,7 +49,127 @@ fn apply_detail_to_json_item(item_val: &mut serde_json::Value, detail: &DetailLe
     }
 }
 
+/// Decide whether to force colors on or off. `None` leaves the choice to
+/// `colored`, which colors only when stdout is a terminal.
+fn color_override(choice: &ColorChoice, no_color_env: Option<&str>) -> Option<bool> {
+    match choice {
+        ColorChoice::Always => Some(true),
+        ColorChoice::Never => Some(false),
+        // https://no-color.org: any non-empty NO_COLOR disables color
+        ColorChoice::Auto => no_color_env.filter(|v| !v.is_empty()).map(|_| false),
+    }
+}
+
+/// Apply `--color` / `--no-color` / `NO_COLOR` to every later `print_*` call.
+/// Must run before any output is written.
+pub fn configure_color(choice: &ColorChoice, no_color_env: Option<&str>) {
+    if let Some(enabled) = color_override(choice, no_color_env) {
+        colored::control::set_override(enabled);
+    }
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Apply `--quiet`: later `print_*` calls omit text-mode summary lines, and
+/// callers check `is_quiet` before printing non-error warnings.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+static WIDTH: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Apply `--width`: text-mode item lines are fitted to this many columns.
+/// Without it, the terminal width is used when stdout is a terminal; `0`
+/// or piped output leaves lines untouched.
+pub fn configure_width(width: Option<usize>) {
+    let width = match width {
+        Some(0) => None,
+        Some(n) => Some(n),
+        None => terminal_size::terminal_size().map(|(w, _)| w.0 as usize),
+    };
+    let _ = WIDTH.set(width);
+}
+
+/// Fit an item line to the output width: `--detail full` wraps the whole
+/// line, other levels truncate it with an ellipsis.
+fn fit_to_width(line: &str, detail: &DetailLevel) -> String {
+    match WIDTH.get().copied().flatten() {
+        None => line.to_string(),
+        Some(width) if *detail == DetailLevel::Full => wrap::wrap_line(line, width),
+        Some(width) => wrap::truncate_line(line, width),
+    }
+}
+
+static TAG_STYLES: OnceLock<TagStyles> = OnceLock::new();
+
+/// Apply `[custom_tags]` colors and severities to later text output.
+pub fn set_tag_styles(styles: TagStyles) {
+    let _ = TAG_STYLES.set(styles);
+}
+
+fn tag_styles() -> &'static TagStyles {
+    TAG_STYLES.get_or_init(TagStyles::default)
+}
+
+/// Provenance wrapped around JSON output by `--envelope`.
+struct Envelope {
+    command: String,
+    root: String,
+}
+
+static ENVELOPE: OnceLock<Envelope> = OnceLock::new();
+
+/// Apply `--envelope`: later JSON output is wrapped in an object carrying the
+/// todo-scan version, generation time, scan root, and subcommand, with the
+/// usual payload under `data`.
+pub fn set_envelope(command: &str, root: &Path) {
+    let _ = ENVELOPE.set(Envelope {
+        command: command.to_string(),
+        root: root.display().to_string(),
+    });
+}
+
+#[derive(serde::Serialize)]
+struct Enveloped<'a, T: ?Sized> {
+    todo_scan_version: &'static str,
+    generated_at: String,
+    root: &'a str,
+    command: &'a str,
+    data: &'a T,
+}
+
+fn wrap_envelope<'a, T: serde::Serialize + ?Sized>(
+    envelope: &'a Envelope,
+    payload: &'a T,
+) -> Enveloped<'a, T> {
+    Enveloped {
+        todo_scan_version: env!("CARGO_PKG_VERSION"),
+        generated_at: crate::date_utils::now_iso8601(),
+        root: &envelope.root,
+        command: &envelope.command,
+        data: payload,
+    }
+}
+
+/// Pretty-print a JSON payload, inside the `--envelope` wrapper when enabled.
+fn to_json<T: serde::Serialize + ?Sized>(payload: &T) -> String {
+    match ENVELOPE.get() {
+        Some(envelope) => serde_json::to_string_pretty(&wrap_envelope(envelope, payload)),
+        None => serde_json::to_string_pretty(payload),
+    }
+    .expect("failed to serialize")
+}
+
+/// Color a tag for terminal output. A color configured under `[custom_tags]`
+/// wins over the built-in palette.
 fn colorize_tag(tag: &Tag) -> ColoredString {
+    if let Some(color) = tag_styles().color(tag) {
+        return tag.as_str().color(color);
+    }
     match tag {
         Tag::Todo => tag.as_str().yellow(),
         Tag::Fixme => tag.as_str().red(),
@@ -43,9 +177,13 @@ fn colorize_tag(tag: &Tag) -> ColoredString {
         Tag::Bug => tag.as_str().red().bold(),
         Tag::Note => tag.as_str().blue(),
         Tag::Xxx => tag.as_str().red(),
+        Tag::Custom(_) => tag.as_str().normal(),
     }
 }
 
+/// Group key for items without an issue reference under `--group-by issue`.
+const NO_ISSUE_GROUP: &str = "no-issue";
+
 fn group_key(item: &TodoItem, group_by: &GroupBy) -> String {
     match group_by {
         GroupBy::File => item.file.clone(),
@@ -70,6 +208,10 @@ fn group_key(item: &TodoItem, group_by: &GroupBy) -> String {
                 }
             })
             .unwrap_or_else(|| ".".to_string()),
+        GroupBy::Issue => item
+            .issue_ref
+            .clone()
+            .unwrap_or_else(|| NO_ISSUE_GROUP.to_string()),
     }
 }
 
@@ -98,15 +240,24 @@ fn group_items<'a>(items: &'a [TodoItem], group_by: &GroupBy) -> Vec<(String, Ve
                     _ => 3,
                 }
             };
-            groups.sort_by(|a, b| priority_order(&a.0).cmp(&priority_order(&b.0)));
+            groups.sort_by_key(|a| priority_order(&a.0));
         }
         GroupBy::Tag => {
             groups.sort_by(|a, b| {
-                let sa = a.1.first().map(|i| i.tag.severity()).unwrap_or(0);
-                let sb = b.1.first().map(|i| i.tag.severity()).unwrap_or(0);
+                let severity = |group: &[&TodoItem]| {
+                    group.first().map_or(0, |i| tag_styles().severity(&i.tag))
+                };
+                let (sa, sb) = (severity(&a.1), severity(&b.1));
                 sb.cmp(&sa)
             });
         }
+        GroupBy::Issue => {
+            groups.sort_by(|a, b| {
+                (a.0 == NO_ISSUE_GROUP)
+                    .cmp(&(b.0 == NO_ISSUE_GROUP))
+                    .then_with(|| a.0.cmp(&b.0))
+            });
+        }
         _ => {
             groups.sort_by(|a, b| a.0.cmp(&b.0));
         }
@@ -115,6 +266,137 @@ fn group_items<'a>(items: &'a [TodoItem], group_by: &GroupBy) -> Vec<(String, Ve
     groups
 }
 
+/// Render an item as the `--fields` columns in the requested order, separated
+/// by spaces. Missing optional values print as `-` so columns stay positional.
+fn format_item_fields(item: &TodoItem, fields: &[ListField]) -> String {
+    let or_dash = |value: Option<&str>| value.map(sanitize_for_terminal).unwrap_or("-".into());
+    fields
+        .iter()
+        .map(|field| match field {
+            ListField::File => sanitize_for_terminal(&item.file),
+            ListField::Line => item.line.to_string(),
+            ListField::Tag => format!("[{}]", colorize_tag(&item.tag)),
+            ListField::Priority => match item.priority {
+                Priority::Normal => "normal".to_string(),
+                Priority::High => "high".to_string(),
+                Priority::Urgent => "urgent".to_string(),
+            },
+            ListField::Author => item
+                .author
+                .as_deref()
+                .map(|a| format!("@{}", sanitize_for_terminal(a)))
+                .unwrap_or("-".into()),
+            ListField::IssueRef => or_dash(item.issue_ref.as_deref()),
+            ListField::Deadline => match item.deadline {
+                Some(ref deadline) if deadline.is_expired(&crate::deadline::today()) => {
+                    deadline.to_string().red().to_string()
+                }
+                Some(ref deadline) => deadline.to_string(),
+                None => "-".to_string(),
+            },
+            ListField::Message => sanitize_for_terminal(&item.message),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Append an item's author, issue reference and deadline to its text line.
+fn push_item_details(line: &mut String, item: &TodoItem) {
+    if let Some(ref author) = item.author {
+        line.push_str(&format!(" (@{})", sanitize_for_terminal(author)));
+    }
+    if let Some(ref issue) = item.issue_ref {
+        line.push_str(&format!(" ({})", sanitize_for_terminal(issue)));
+    }
+    if let Some(ref deadline) = item.deadline {
+        let today = crate::deadline::today();
+        if deadline.is_expired(&today) {
+            line.push_str(&format!(" {}", format!("[expired: {}]", deadline).red()));
+        } else {
+            line.push_str(&format!(" [deadline: {}]", deadline));
+        }
+    }
+}
+
+/// Pad `cell` with spaces to `width` display columns; escape sequences and
+/// wide characters are measured as they appear on screen.
+fn pad_cell(cell: &str, width: usize) -> String {
+    let padding = width.saturating_sub(wrap::visible_width(cell));
+    format!("{}{}", cell, " ".repeat(padding))
+}
+
+/// Rows of `list --table`: a header, then one row per item with tag,
+/// priority marker, location and message columns, each padded to its widest
+/// cell. The message column is last and left unpadded.
+fn format_list_table(items: &[TodoItem], detail: &DetailLevel) -> Vec<String> {
+    let rows: Vec<[String; 4]> = items
+        .iter()
+        .map(|item| {
+            let marker = match item.priority {
+                Priority::Urgent => "!!".red().bold().to_string(),
+                Priority::High => "!".yellow().to_string(),
+                Priority::Normal => String::new(),
+            };
+            let mut message = sanitize_for_terminal(&item.message);
+            if *detail != DetailLevel::Minimal {
+                push_item_details(&mut message, item);
+            }
+            [
+                colorize_tag(&item.tag).to_string(),
+                marker,
+                format!("{}:{}", sanitize_for_terminal(&item.file), item.line),
+                message,
+            ]
+        })
+        .collect();
+
+    let header = ["TAG", "PRI", "LOCATION", "MESSAGE"].map(String::from);
+    let mut widths = [0; 3];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(wrap::visible_width(cell));
+        }
+    }
+    let render = |row: &[String; 4]| {
+        let mut line: Vec<String> = widths
+            .iter()
+            .zip(row)
+            .map(|(width, cell)| pad_cell(cell, *width))
+            .collect();
+        line.push(row[3].clone());
+        line.join("  ")
+    };
+
+    std::iter::once(render(&header).bold().to_string())
+        .chain(rows.iter().map(render))
+        .collect()
+}
+
+/// `list --table`: items as one flat, aligned table fitted to the terminal
+/// width, followed by the usual summary line.
+pub fn print_list_table(result: &ScanResult, ignored_count: usize, detail: &DetailLevel) {
+    for line in format_list_table(&result.items, detail) {
+        println!("{}", fit_to_width(&line, detail));
+    }
+    if is_quiet() {
+        return;
+    }
+    let files: std::collections::HashSet<&str> =
+        result.items.iter().map(|i| i.file.as_str()).collect();
+    let ignored_suffix = if ignored_count > 0 {
+        format!(" ({} ignored)", ignored_count)
+    } else {
+        String::new()
+    };
+    println!(
+        "{} items in {} files{}",
+        result.items.len(),
+        files.len(),
+        ignored_suffix
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn print_list(
     result: &ScanResult,
     format: &Format,
@@ -123,6 +405,7 @@ pub fn print_list(
     ignored_count: usize,
     show_ignored: bool,
     detail: &DetailLevel,
+    fields: &[ListField],
 ) {
     let has_context = !context_map.is_empty();
 
@@ -160,37 +443,26 @@ pub fn print_list(
 
                     let msg = sanitize_for_terminal(&item.message);
                     let file = sanitize_for_terminal(&item.file);
-                    let mut line = if is_file_group {
+                    let mut line = if !fields.is_empty() {
+                        // Group headers still name the file or group, so the
+                        // item line shows exactly the requested columns
+                        format!("  {}", format_item_fields(item, fields))
+                    } else if is_file_group {
                         format!("  L{}: [{}] {}", item.line, tag_str, msg)
                     } else {
                         format!("  {}:{}: [{}] {}", file, item.line, tag_str, msg)
                     };
 
-                    if *detail != DetailLevel::Minimal {
-                        if let Some(ref author) = item.author {
-                            line.push_str(&format!(" (@{})", sanitize_for_terminal(author)));
-                        }
-                        if let Some(ref issue) = item.issue_ref {
-                            line.push_str(&format!(" ({})", sanitize_for_terminal(issue)));
-                        }
-                        if let Some(ref deadline) = item.deadline {
-                            let today = crate::deadline::today();
-                            if deadline.is_expired(&today) {
-                                line.push_str(&format!(
-                                    " {}",
-                                    format!("[expired: {}]", deadline).red()
-                                ));
-                            } else {
-                                line.push_str(&format!(" [deadline: {}]", deadline));
-                            }
-                        }
+                    if fields.is_empty() && *detail != DetailLevel::Minimal {
+                        push_item_details(&mut line, item);
                     }
 
-                    if has_context {
-                        println!("{} {}", "  →".cyan(), line.trim_start());
+                    let line = if has_context {
+                        format!("{} {}", "  →".cyan(), line.trim_start())
                     } else {
-                        println!("{}", line);
-                    }
+                        line
+                    };
+                    println!("{}", fit_to_width(&line, detail));
 
                     // Print after-context lines
                     if let Some(ctx) = context_map.get(&ctx_key) {
@@ -221,16 +493,22 @@ pub fn print_list(
                         let tag_str = colorize_tag(&item.tag);
                         let msg = sanitize_for_terminal(&item.message);
                         let file = sanitize_for_terminal(&item.file);
-                        let line = if is_file_group {
+                        let line = if !fields.is_empty() {
+                            format!("  {}", format_item_fields(item, fields))
+                        } else if is_file_group {
                             format!("  L{}: [{}] {}", item.line, tag_str, msg)
                         } else {
                             format!("  {}:{}: [{}] {}", file, item.line, tag_str, msg)
                         };
-                        println!("{}", line.dimmed());
+                        println!("{}", fit_to_width(&line.dimmed().to_string(), detail));
                     }
                 }
             }
 
+            if is_quiet() {
+                return;
+            }
+
             // Summary line
             let ignored_suffix = if ignored_count > 0 {
                 format!(" ({} ignored)", ignored_count)
@@ -254,6 +532,9 @@ pub fn print_list(
                 );
             }
         }
+        Format::Dot | Format::GhIssue | Format::Html | Format::Junit => {
+            unreachable!("--format {} is rejected for this command", format.name())
+        }
         Format::Json => {
             let mut value: serde_json::Value =
                 serde_json::to_value(result).expect("failed to serialize");
@@ -279,12 +560,15 @@ pub fn print_list(
                     apply_detail_to_json_item(item_val, detail);
                 }
             }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
+            let json = to_json(&value);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_list(result)),
+        Format::AzureDevops => print!("{}", azure_devops::format_list(result)),
+        Format::Xcode => print!("{}", xcode::format_list(result)),
         Format::Sarif => print!("{}", sarif::format_list(result)),
         Format::Markdown => print!("{}", markdown::format_list(result)),
+        Format::Csv => print!("{}", csv::format_list(result)),
     }
 }
 
@@ -357,11 +641,12 @@ pub fn print_search(
                         }
                     }
 
-                    if has_context {
-                        println!("{} {}", "  →".cyan(), line.trim_start());
+                    let line = if has_context {
+                        format!("{} {}", "  →".cyan(), line.trim_start())
                     } else {
-                        println!("{}", line);
-                    }
+                        line
+                    };
+                    println!("{}", fit_to_width(&line, detail));
 
                     // Print after-context lines
                     if let Some(ctx) = context_map.get(&ctx_key) {
@@ -377,6 +662,10 @@ pub fn print_search(
                 }
             }
 
+            if is_quiet() {
+                return;
+            }
+
             if is_file_group {
                 println!(
                     "{} matches across {} files (query: \"{}\")",
@@ -393,6 +682,9 @@ pub fn print_search(
                 );
             }
         }
+        Format::Dot | Format::GhIssue | Format::Html | Format::Junit => {
+            unreachable!("--format {} is rejected for this command", format.name())
+        }
         Format::Json => {
             let mut value: serde_json::Value =
                 serde_json::to_value(result).expect("failed to serialize");
@@ -418,12 +710,15 @@ pub fn print_search(
                     apply_detail_to_json_item(item_val, detail);
                 }
             }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
+            let json = to_json(&value);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_search(result)),
+        Format::AzureDevops => print!("{}", azure_devops::format_search(result)),
+        Format::Xcode => print!("{}", xcode::format_search(result)),
         Format::Sarif => print!("{}", sarif::format_search(result)),
         Format::Markdown => print!("{}", markdown::format_search(result)),
+        Format::Csv => print!("{}", csv::format_search(result)),
     }
 }
 
@@ -479,10 +774,32 @@ pub fn print_diff(
                 }
             }
 
-            println!(
-                "\n+{} -{} (base: {})",
-                result.added_count, result.removed_count, result.base_ref
-            );
+            if is_quiet() {
+                return;
+            }
+
+            match result.head_ref {
+                Some(ref head_ref) => println!(
+                    "\n+{} -{} (base: {}, head: {}) (net {:+}, base {})",
+                    result.added_count,
+                    result.removed_count,
+                    result.base_ref,
+                    head_ref,
+                    result.net_change,
+                    result.base_total
+                ),
+                None => println!(
+                    "\n+{} -{} (base: {}) (net {:+}, base {})",
+                    result.added_count,
+                    result.removed_count,
+                    result.base_ref,
+                    result.net_change,
+                    result.base_total
+                ),
+            }
+        }
+        Format::Dot | Format::GhIssue | Format::Html | Format::Junit => {
+            unreachable!("--format {} is rejected for this command", format.name())
         }
         Format::Json => {
             let mut value: serde_json::Value =
@@ -514,12 +831,15 @@ pub fn print_diff(
                     }
                 }
             }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
+            let json = to_json(&value);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_diff(result)),
+        Format::AzureDevops => print!("{}", azure_devops::format_diff(result)),
+        Format::Xcode => print!("{}", xcode::format_diff(result)),
         Format::Sarif => print!("{}", sarif::format_diff(result)),
         Format::Markdown => print!("{}", markdown::format_diff(result)),
+        Format::Csv => print!("{}", csv::format_diff(result)),
     }
 }
 
@@ -531,6 +851,40 @@ fn bar(count: usize, max: usize, width: usize) -> String {
     "\u{2588}".repeat(filled)
 }
 
+/// One block character per count, scaled between the series' minimum and
+/// maximum; a flat series is drawn at the lowest level.
+fn sparkline(counts: &[usize]) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let (Some(&min), Some(&max)) = (counts.iter().min(), counts.iter().max()) else {
+        return String::new();
+    };
+    let span = max - min;
+    counts
+        .iter()
+        .map(|&count| {
+            let level = ((count - min) * (LEVELS.len() - 1))
+                .checked_div(span)
+                .unwrap_or(0);
+            LEVELS[level]
+        })
+        .collect()
+}
+
+/// `stats --history`: sparkline of the sampled counts with the first and
+/// last values and dates, e.g. `▁▃▅█  12 → 20 (2025-01-02 .. 2025-03-04)`.
+pub(crate) fn history_summary(history: &[HistoryPoint]) -> Option<String> {
+    let (first, last) = (history.first()?, history.last()?);
+    let counts: Vec<usize> = history.iter().map(|p| p.count).collect();
+    Some(format!(
+        "{}  {} → {} ({} .. {})",
+        sparkline(&counts),
+        first.count,
+        last.count,
+        first.date,
+        last.date
+    ))
+}
+
 pub fn print_brief(result: &BriefResult, format: &Format, budget: Option<usize>) {
     match format {
         Format::Text => {
@@ -598,12 +952,200 @@ pub fn print_brief(result: &BriefResult, format: &Format, budget: Option<usize>)
             }
         }
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
+            println!("{}", json);
+        }
+    }
+}
+
+/// Print `diff --stat`: one `+added -removed` row per file and a total.
+pub fn print_diff_stat(result: &DiffStatResult, format: &Format) {
+    match format {
+        Format::Text => {
+            let files: Vec<String> = result
+                .files
+                .iter()
+                .map(|f| sanitize_for_terminal(&f.file))
+                .collect();
+            let width = files.iter().map(|f| f.chars().count()).max().unwrap_or(0);
+            for (file, stat) in files.iter().zip(&result.files) {
+                let pad = width - file.chars().count();
+                println!(
+                    " {}{} | {} {}",
+                    file,
+                    " ".repeat(pad),
+                    format!("+{}", stat.added).green(),
+                    format!("-{}", stat.removed).red()
+                );
+            }
+            if is_quiet() {
+                return;
+            }
+            println!(
+                "{} files changed, +{} -{} (net {:+})",
+                result.files.len(),
+                result.added_count,
+                result.removed_count,
+                result.net_change
+            );
+        }
+        Format::Markdown => print!("{}", markdown::format_diff_stat(result)),
+        Format::Csv => print!("{}", csv::format_diff_stat(result)),
+        _ => {
+            let json = to_json(result);
+            println!("{}", json);
+        }
+    }
+}
+
+pub fn print_count(result: &CountResult, format: &Format) {
+    match format {
+        Format::Text => {
+            println!("{} items in {} files", result.total, result.files);
+            let mut by_tag: Vec<(&String, &usize)> = result.by_tag.iter().collect();
+            by_tag.sort_by_key(|e| std::cmp::Reverse(*e.1));
+            for (tag, count) in by_tag {
+                println!("  {}: {}", sanitize_for_terminal(tag), count);
+            }
+        }
+        Format::Csv => print!("{}", csv::format_count(result)),
+        _ => {
+            let json = to_json(result);
+            println!("{}", json);
+        }
+    }
+}
+
+pub fn print_files_without_todos(result: &FilesWithoutTodosResult, format: &Format) {
+    match format {
+        Format::Text => {
+            for file in &result.files_without_todos {
+                println!("{}", sanitize_for_terminal(file));
+            }
+        }
+        _ => {
+            let json = to_json(result);
+            println!("{}", json);
+        }
+    }
+}
+
+pub fn print_dedupe(result: &DedupeResult, format: &Format, detail: &DetailLevel) {
+    match format {
+        Format::Text => {
+            for deduped in &result.items {
+                let item = &deduped.item;
+                let occurrences = if deduped.count > 1 {
+                    format!(" ({} occurrences)", deduped.count)
+                        .dimmed()
+                        .to_string()
+                } else {
+                    String::new()
+                };
+                println!(
+                    "[{}] {}{}",
+                    colorize_tag(&item.tag),
+                    sanitize_for_terminal(&item.message),
+                    occurrences
+                );
+                for loc in &deduped.locations {
+                    println!("  {}:{}", sanitize_for_terminal(&loc.file), loc.line);
+                }
+            }
+            if !is_quiet() {
+                println!(
+                    "{} unique items ({} total)",
+                    result.items.len(),
+                    result.total_items
+                );
+            }
+        }
+        _ => {
+            let mut value: serde_json::Value =
+                serde_json::to_value(result).expect("failed to serialize");
+            if let Some(items) = value.get_mut("items").and_then(|v| v.as_array_mut()) {
+                for item_val in items.iter_mut() {
+                    apply_detail_to_json_item(item_val, detail);
+                }
+            }
+            let json = to_json(&value);
             println!("{}", json);
         }
     }
 }
 
+pub fn print_search_history(result: &HistorySearchResult, format: &Format, detail: &DetailLevel) {
+    match format {
+        Format::Text => {
+            for m in &result.matches {
+                let item = &m.item;
+                let status = if m.present_now { "present" } else { "removed" };
+                println!(
+                    "{}:{}: [{}] {} {}",
+                    sanitize_for_terminal(&item.file),
+                    item.line,
+                    colorize_tag(&item.tag),
+                    sanitize_for_terminal(&item.message),
+                    format!(
+                        "(first seen {} on {}, {})",
+                        m.first_seen_commit, m.first_seen_date, status
+                    )
+                    .dimmed()
+                );
+            }
+            if !is_quiet() {
+                println!(
+                    "{} matches in {} commits",
+                    result.match_count, result.commits_scanned
+                );
+            }
+        }
+        _ => {
+            let mut value: serde_json::Value =
+                serde_json::to_value(result).expect("failed to serialize");
+            if let Some(items) = value.get_mut("matches").and_then(|v| v.as_array_mut()) {
+                for item_val in items.iter_mut() {
+                    apply_detail_to_json_item(item_val, detail);
+                }
+            }
+            let json = to_json(&value);
+            println!("{}", json);
+        }
+    }
+}
+
+pub fn print_age(buckets: &[AgeBucket], format: &Format) {
+    match format {
+        Format::Text => {
+            let max = buckets.iter().map(|b| b.count).max().unwrap_or(0);
+            let width = buckets.iter().map(|b| b.label.len()).max().unwrap_or(0);
+            for bucket in buckets {
+                println!(
+                    "  {:<width$} {:>4}  {}",
+                    bucket.label,
+                    bucket.count,
+                    bar(bucket.count, max, 20).dimmed()
+                );
+            }
+            if !is_quiet() {
+                let total: usize = buckets.iter().map(|b| b.count).sum();
+                println!("\n{} items", total);
+            }
+        }
+        _ => {
+            let json = to_json(buckets);
+            println!("{}", json);
+        }
+    }
+}
+
+/// "… and K more" under a list cut short by `stats --top`.
+fn print_more(total: usize, shown: usize) {
+    if total > shown {
+        println!("  {}", format!("… and {} more", total - shown).dimmed());
+    }
+}
+
 pub fn print_stats(result: &StatsResult, format: &Format) {
     match format {
         Format::Text => {
@@ -641,6 +1183,7 @@ pub fn print_stats(result: &StatsResult, format: &Format) {
                         bar(*count, author_max, 20).dimmed()
                     );
                 }
+                print_more(result.total_authors, result.author_counts.len());
             }
 
             // Hotspot files
@@ -649,6 +1192,7 @@ pub fn print_stats(result: &StatsResult, format: &Format) {
                 for (file, count) in &result.hotspot_files {
                     println!("  {} ({})", sanitize_for_terminal(file), count);
                 }
+                print_more(result.total_files, result.hotspot_files.len());
             }
 
             // Total summary
@@ -666,20 +1210,119 @@ pub fn print_stats(result: &StatsResult, format: &Format) {
                     trend.base_ref, trend.added, trend.removed, sign, net
                 );
             }
+
+            // History sparkline
+            if let Some(ref history) = result.history {
+                match history_summary(history) {
+                    Some(summary) => {
+                        println!("History ({} commits): {}", history.len(), summary)
+                    }
+                    None => println!("History: no commits to sample"),
+                }
+            }
+
+            // Per-author churn
+            if let Some(ref churn) = result.author_churn {
+                if !churn.is_empty() {
+                    println!("\n{}", "Author churn".bold().underline());
+                    for c in churn {
+                        let net = c.added as i64 - c.removed as i64;
+                        let sign = if net > 0 { "+" } else { "" };
+                        println!(
+                            "  {:20} {:>4} added {:>4} removed  ({}{})",
+                            sanitize_for_terminal(&c.author),
+                            c.added,
+                            c.removed,
+                            sign,
+                            net
+                        );
+                    }
+                }
+            }
         }
+        Format::Markdown => print!("{}", markdown::format_stats(result)),
+        Format::Csv => print!("{}", csv::format_stats(result)),
+        Format::Html => print!("{}", html::render_stats_fragment(result)),
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
             println!("{}", json);
         }
     }
 }
 
+/// `stats BASE..HEAD`: one row per count with both refs and the change.
+/// Growth is red and shrinkage green, as for TODO debt.
+pub fn print_stats_comparison(result: &StatsComparison, format: &Format) {
+    if !matches!(format, Format::Text) {
+        println!("{}", to_json(result));
+        return;
+    }
+    let sections: [(&str, &[CountDelta]); 3] = [
+        ("Tags", &result.tags),
+        ("Priority", &result.priorities),
+        ("Authors", &result.authors),
+    ];
+    let name_width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter())
+        .chain(&result.totals)
+        .map(|row| sanitize_for_terminal(&row.name).chars().count() + 2)
+        .chain(sections.iter().map(|(title, _)| title.len()))
+        .max()
+        .unwrap_or(0);
+    let base_ref = sanitize_for_terminal(&result.base_ref);
+    let head_ref = sanitize_for_terminal(&result.head_ref);
+    let base_width = base_ref.chars().count().max(5);
+    let head_width = head_ref.chars().count().max(5);
+
+    let row = |name: &str, r: &CountDelta| {
+        let delta = format!("{:>+6}", r.delta);
+        let delta = match r.delta.signum() {
+            1 => delta.red().to_string(),
+            -1 => delta.green().to_string(),
+            _ => format!("{:>6}", 0).dimmed().to_string(),
+        };
+        println!(
+            "{:<name_width$}  {:>base_width$}  {:>head_width$}  {}",
+            name, r.base, r.head, delta
+        );
+    };
+
+    for (i, (title, rows)) in sections.iter().enumerate() {
+        if rows.is_empty() {
+            continue;
+        }
+        if i > 0 {
+            println!();
+        }
+        let header = format!(
+            "{:<name_width$}  {:>base_width$}  {:>head_width$}  {:>6}",
+            title, base_ref, head_ref, "change"
+        );
+        println!("{}", header.bold());
+        for r in rows.iter() {
+            row(&format!("  {}", sanitize_for_terminal(&r.name)), r);
+        }
+        if *title == "Authors" {
+            print_more(result.total_authors, rows.len());
+        }
+    }
+    println!();
+    for r in &result.totals {
+        let mut name = sanitize_for_terminal(&r.name);
+        name[..1].make_ascii_uppercase();
+        row(&name, r);
+    }
+}
+
 pub fn print_lint(result: &LintResult, format: &Format) {
     match format {
         Format::Text => {
             if result.passed {
                 println!("{}", "PASS".green().bold());
-                println!("{} items checked, no violations", result.total_items);
+                if !is_quiet() {
+                    println!("{} items checked, no violations", result.total_items);
+                }
             } else {
                 println!("{}", "FAIL".red().bold());
 
@@ -717,19 +1360,28 @@ pub fn print_lint(result: &LintResult, format: &Format) {
                     }
                 }
 
-                println!(
-                    "\n{} violations in {} items",
-                    result.violation_count, result.total_items
-                );
+                if !is_quiet() {
+                    println!(
+                        "\n{} violations in {} items",
+                        result.violation_count, result.total_items
+                    );
+                }
             }
         }
+        Format::Dot | Format::GhIssue | Format::Html => {
+            unreachable!("--format {} is rejected for this command", format.name())
+        }
         Format::Json => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_lint(result)),
+        Format::AzureDevops => print!("{}", azure_devops::format_lint(result)),
+        Format::Xcode => print!("{}", xcode::format_lint(result)),
         Format::Sarif => print!("{}", sarif::format_lint(result)),
         Format::Markdown => print!("{}", markdown::format_lint(result)),
+        Format::Csv => print!("{}", csv::format_lint(result)),
+        Format::Junit => print!("{}", junit::format_lint(result)),
     }
 }
 
@@ -738,7 +1390,9 @@ pub fn print_clean(result: &CleanResult, format: &Format) {
         Format::Text => {
             if result.passed {
                 println!("{}", "PASS".green().bold());
-                println!("{} items checked, no violations", result.total_items);
+                if !is_quiet() {
+                    println!("{} items checked, no violations", result.total_items);
+                }
             } else {
                 println!("{}", "FAIL".red().bold());
 
@@ -776,20 +1430,30 @@ pub fn print_clean(result: &CleanResult, format: &Format) {
                     }
                 }
 
-                let violation_count = result.violations.len();
-                println!(
-                    "\n{} violations ({} stale, {} duplicates) in {} items",
-                    violation_count, result.stale_count, result.duplicate_count, result.total_items
-                );
+                if !is_quiet() {
+                    println!(
+                        "\n{} violations ({} stale, {} duplicates) in {} items",
+                        result.violations.len(),
+                        result.stale_count,
+                        result.duplicate_count,
+                        result.total_items
+                    );
+                }
             }
         }
+        Format::Dot | Format::GhIssue | Format::Html | Format::Junit => {
+            unreachable!("--format {} is rejected for this command", format.name())
+        }
         Format::Json => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_clean(result)),
+        Format::AzureDevops => print!("{}", azure_devops::format_clean(result)),
+        Format::Xcode => print!("{}", xcode::format_clean(result)),
         Format::Sarif => print!("{}", sarif::format_clean(result)),
         Format::Markdown => print!("{}", markdown::format_clean(result)),
+        Format::Csv => print!("{}", csv::format_clean(result)),
     }
 }
 
@@ -808,19 +1472,37 @@ pub fn print_check(result: &CheckResult, format: &Format) {
                     );
                 }
             }
+            for warning in &result.warnings {
+                println!(
+                    "  {} {}: {}",
+                    "warning".yellow().bold(),
+                    sanitize_for_terminal(&warning.rule).yellow(),
+                    sanitize_for_terminal(&warning.message)
+                );
+            }
+        }
+        Format::Dot | Format::GhIssue | Format::Html => {
+            unreachable!("--format {} is rejected for this command", format.name())
         }
         Format::Json => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_check(result)),
+        Format::AzureDevops => print!("{}", azure_devops::format_check(result)),
+        Format::Xcode => print!("{}", xcode::format_check(result)),
         Format::Sarif => print!("{}", sarif::format_check(result)),
         Format::Markdown => print!("{}", markdown::format_check(result)),
+        Format::Csv => print!("{}", csv::format_check(result)),
+        Format::Junit => print!("{}", junit::format_check(result)),
     }
 }
 
-pub fn print_blame(result: &BlameResult, format: &Format) {
+/// Print blame results. When `entries` was cut short of `total`, the text
+/// summary says how many are shown; `oldest` words it for `--oldest`.
+pub fn print_blame(result: &BlameResult, format: &Format, oldest: bool) {
     match format {
+        Format::Text if result.authors.is_some() => print_blame_authors(result),
         Format::Text => {
             // Group by file
             let mut groups: Vec<(String, Vec<&BlameEntry>)> = Vec::new();
@@ -859,10 +1541,23 @@ pub fn print_blame(result: &BlameResult, format: &Format) {
                 }
             }
 
-            println!(
-                "\n{} items, avg age {} days, {} stale (threshold: {} days)",
-                result.total, result.avg_age_days, result.stale_count, result.stale_threshold_days,
-            );
+            if !is_quiet() {
+                let shown = result.entries.len();
+                let count = if oldest {
+                    format!("Showing the oldest {} of {} items", shown, result.total)
+                } else if shown < result.total {
+                    format!("Showing {} of {} items", shown, result.total)
+                } else {
+                    format!("{} items", result.total)
+                };
+                println!(
+                    "\n{}, avg age {} days, {} stale (threshold: {} days)",
+                    count, result.avg_age_days, result.stale_count, result.stale_threshold_days,
+                );
+            }
+        }
+        Format::Dot | Format::GhIssue | Format::Html | Format::Junit => {
+            unreachable!("--format {} is rejected for this command", format.name())
         }
         Format::Json => {
             let mut value: serde_json::Value =
@@ -872,16 +1567,80 @@ pub fn print_blame(result: &BlameResult, format: &Format) {
                     inject_id_field(entry_val);
                 }
             }
-            let json = serde_json::to_string_pretty(&value).expect("failed to serialize");
+            let json = to_json(&value);
             println!("{}", json);
         }
         Format::GithubActions => print!("{}", github_actions::format_blame(result)),
+        Format::AzureDevops => print!("{}", azure_devops::format_blame(result)),
+        Format::Xcode => print!("{}", xcode::format_blame(result)),
         Format::Sarif => print!("{}", sarif::format_blame(result)),
         Format::Markdown => print!("{}", markdown::format_blame(result)),
+        Format::Csv => print!("{}", csv::format_blame(result)),
+    }
+}
+
+/// Text rendering of `blame --group-by author`: one row per author.
+fn print_blame_authors(result: &BlameResult) {
+    let authors = result.authors.as_deref().unwrap_or_default();
+    let width = authors
+        .iter()
+        .map(|a| sanitize_for_terminal(&a.author).chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("AUTHOR".len());
+
+    println!(
+        "{}",
+        format!(
+            "{:<width$}  {:>5}  {:>12}  {:>5}",
+            "AUTHOR", "COUNT", "AVG AGE", "STALE"
+        )
+        .bold()
+    );
+    for a in authors {
+        let stale = format!("{:>5}", a.stale_count);
+        let stale = if a.stale_count > 0 {
+            stale.red().to_string()
+        } else {
+            stale
+        };
+        println!(
+            "{:<width$}  {:>5}  {:>12}  {}",
+            sanitize_for_terminal(&a.author),
+            a.count,
+            format!("{} days", a.avg_age_days),
+            stale,
+        );
+    }
+
+    if is_quiet() {
+        return;
+    }
+    println!(
+        "\n{} items by {} authors, avg age {} days, {} stale (threshold: {} days)",
+        result.total,
+        authors.len(),
+        result.avg_age_days,
+        result.stale_count,
+        result.stale_threshold_days,
+    );
+}
+
+/// Write items as JSON Lines, one object per line with the stable `id` injected.
+/// Each line is serialized and written on its own, so `export` can call this
+/// once per scanned file.
+pub fn write_jsonl<W: std::io::Write>(items: &[TodoItem], out: &mut W) -> std::io::Result<()> {
+    for item in items {
+        let mut value = serde_json::to_value(item).expect("failed to serialize");
+        inject_id_field(&mut value);
+        serde_json::to_writer(&mut *out, &value)?;
+        out.write_all(b"\n")?;
     }
+    Ok(())
 }
 
-/// Inject a stable `id` field into a JSON object that has flattened TodoItem fields.
+/// Inject the stable `id` and `stable_id` fields into a JSON object that has
+/// flattened TodoItem fields.
 fn inject_id_field(val: &mut serde_json::Value) {
     let file = val
         .get("file")
@@ -899,12 +1658,19 @@ fn inject_id_field(val: &mut serde_json::Value) {
         .unwrap_or("")
         .to_string();
     let id = format!("{}:{}:{}", file, tag, message.trim().to_lowercase());
-    val.as_object_mut()
-        .unwrap()
-        .insert("id".to_string(), serde_json::Value::String(id));
+    let stable_id = stable_id(&file, &tag, &message);
+    let obj = val.as_object_mut().unwrap();
+    obj.insert("id".to_string(), serde_json::Value::String(id));
+    obj.insert(
+        "stable_id".to_string(),
+        serde_json::Value::String(stable_id),
+    );
 }
 
-pub fn print_context(rich: &RichContext, format: &Format) {
+/// Print a rich context view. With `highlight`, surrounding lines are
+/// syntax-highlighted by file extension (falling back to dimmed plain text)
+/// and the TODO line is shown in cyan.
+pub fn print_context(rich: &RichContext, format: &Format, highlight: bool) {
     match format {
         Format::Text => {
             println!(
@@ -913,25 +1679,47 @@ pub fn print_context(rich: &RichContext, format: &Format) {
             );
             println!();
 
-            for cl in &rich.before {
+            // Highlight the whole window at once so multi-line constructs
+            // carry over between lines
+            let lines: Vec<String> = rich
+                .before
+                .iter()
+                .map(|cl| cl.content.as_str())
+                .chain(std::iter::once(rich.todo_line.as_str()))
+                .chain(rich.after.iter().map(|cl| cl.content.as_str()))
+                .map(sanitize_for_terminal)
+                .collect();
+            let highlighted = if highlight {
+                highlight::highlight_lines(&rich.file, &lines)
+            } else {
+                None
+            };
+            let render = |idx: usize| match highlighted {
+                Some(ref h) => h[idx].clone(),
+                None => lines[idx].dimmed().to_string(),
+            };
+
+            for (idx, cl) in rich.before.iter().enumerate() {
                 println!(
                     "  {} {}",
                     format!("{:>4}", cl.line_number).dimmed(),
-                    sanitize_for_terminal(&cl.content).dimmed()
+                    render(idx)
                 );
             }
 
-            println!(
-                "  {} {}",
-                format!("{:>4}", rich.line).cyan(),
-                sanitize_for_terminal(&rich.todo_line)
-            );
+            let todo_idx = rich.before.len();
+            let todo_line = if highlight {
+                lines[todo_idx].cyan().to_string()
+            } else {
+                lines[todo_idx].clone()
+            };
+            println!("  {} {}", format!("{:>4}", rich.line).cyan(), todo_line);
 
-            for cl in &rich.after {
+            for (offset, cl) in rich.after.iter().enumerate() {
                 println!(
                     "  {} {}",
                     format!("{:>4}", cl.line_number).dimmed(),
-                    sanitize_for_terminal(&cl.content).dimmed()
+                    render(todo_idx + 1 + offset)
                 );
             }
 
@@ -949,7 +1737,7 @@ pub fn print_context(rich: &RichContext, format: &Format) {
             }
         }
         _ => {
-            let json = serde_json::to_string_pretty(rich).expect("failed to serialize");
+            let json = to_json(rich);
             println!("{}", json);
         }
     }
@@ -1043,6 +1831,98 @@ pub fn print_watch_event(event: &WatchEvent, format: &Format, max: Option<usize>
     }
 }
 
+/// Screen contents for `watch --clear`: totals by tag, the most urgent open
+/// items, and the last change. Item lines are truncated to the output width.
+pub fn format_watch_dashboard(
+    tag_counts: &[(Tag, usize)],
+    total: usize,
+    urgent: &[TodoItem],
+    last: Option<&WatchEvent>,
+    max: Option<usize>,
+) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let fit = |line: String| fit_to_width(&line, &DetailLevel::Normal);
+
+    lines.push(format!("{} {} items", "todo-scan watch".bold(), total));
+    let tags: Vec<String> = tag_counts
+        .iter()
+        .map(|(tag, count)| format!("{} {}", colorize_tag(tag), count))
+        .collect();
+    if !tags.is_empty() {
+        lines.push(fit(format!("  {}", tags.join("  "))));
+    }
+    if let Some(threshold) = max {
+        if total >= threshold {
+            lines.push(
+                format!(
+                    "  Warning: total {} reached --max threshold {}",
+                    total, threshold
+                )
+                .yellow()
+                .to_string(),
+            );
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("Top urgent".bold().underline().to_string());
+    if urgent.is_empty() {
+        lines.push(format!("  {}", "none".dimmed()));
+    }
+    for item in urgent {
+        let marker = match item.priority {
+            Priority::Urgent => "!!".red().bold().to_string(),
+            _ => "! ".yellow().to_string(),
+        };
+        lines.push(fit(format!(
+            "  {} {}:{} [{}] {}",
+            marker,
+            sanitize_for_terminal(&item.file),
+            item.line,
+            colorize_tag(&item.tag),
+            sanitize_for_terminal(&item.message)
+        )));
+    }
+
+    lines.push(String::new());
+    lines.push("Last change".bold().underline().to_string());
+    match last {
+        None => lines.push(format!("  {}", "none yet".dimmed())),
+        Some(event) => {
+            let delta = match event.total_delta {
+                d if d > 0 => format!("+{}", d).green().to_string(),
+                d if d < 0 => d.to_string().red().to_string(),
+                _ => "±0".to_string(),
+            };
+            lines.push(fit(format!(
+                "  {} {} ({})",
+                event.timestamp.dimmed(),
+                sanitize_for_terminal(&event.file).bold(),
+                delta
+            )));
+            for (sign, items) in [("+".green(), &event.added), ("-".red(), &event.removed)] {
+                for item in items {
+                    lines.push(fit(format!(
+                        "    {} L{}: [{}] {}",
+                        sign,
+                        item.line,
+                        colorize_tag(&item.tag),
+                        sanitize_for_terminal(&item.message)
+                    )));
+                }
+            }
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(
+        "Watching for changes... (Ctrl+C to stop)"
+            .dimmed()
+            .to_string(),
+    );
+    lines.join("\n") + "\n"
+}
+
 pub fn print_tasks(result: &TasksResult, format: &Format) {
     match format {
         Format::Text => {
@@ -1068,13 +1948,16 @@ pub fn print_tasks(result: &TasksResult, format: &Format) {
                 );
             }
 
-            println!("\n{} tasks exported", result.total);
-            if let Some(ref dir) = result.output_dir {
-                println!("Output: {}", sanitize_for_terminal(dir));
+            if !is_quiet() {
+                println!("\n{} tasks exported", result.total);
+                if let Some(ref dir) = result.output_dir {
+                    println!("Output: {}", sanitize_for_terminal(dir));
+                }
             }
         }
+        Format::GhIssue => print!("{}", gh_issue::format_tasks(result)),
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
             println!("{}", json);
         }
     }
@@ -1126,6 +2009,9 @@ pub fn print_relate(result: &RelateResult, format: &Format) {
                         }
                     }
                 }
+                if let Some(ungrouped) = result.ungrouped_items.filter(|&n| n > 0) {
+                    println!("\n{} ungrouped items", ungrouped);
+                }
             } else {
                 for rel in &result.relationships {
                     println!(
@@ -1138,13 +2024,16 @@ pub fn print_relate(result: &RelateResult, format: &Format) {
                 }
             }
 
-            println!(
-                "\n{} relationships across {} items",
-                result.total_relationships, result.total_items
-            );
+            if !is_quiet() {
+                println!(
+                    "\n{} relationships across {} items",
+                    result.total_relationships, result.total_items
+                );
+            }
         }
+        Format::Dot => print!("{}", dot::format_relate(result)),
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
             println!("{}", json);
         }
     }
@@ -1164,7 +2053,9 @@ fn sanitize_for_terminal(s: &str) -> String {
 pub fn print_report(report: &ReportResult, output_path: &str) -> std::io::Result<()> {
     let content = html::render_html(report);
     std::fs::write(output_path, content)?;
-    println!("Report written to {}", sanitize_for_terminal(output_path));
+    if !is_quiet() {
+        println!("Report written to {}", sanitize_for_terminal(output_path));
+    }
     Ok(())
 }
 
@@ -1208,7 +2099,7 @@ pub fn print_workspace_list(
             );
         }
         _ => {
-            let json = serde_json::to_string_pretty(result).expect("failed to serialize");
+            let json = to_json(result);
             println!("{}", json);
         }
     }
@@ -1218,6 +2109,70 @@ pub fn print_workspace_list(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_wrap_envelope_fields_and_data() {
+        let envelope = Envelope {
+            command: "stats".to_string(),
+            root: "/repo".to_string(),
+        };
+        let payload = serde_json::json!({"total_items": 3, "tags": ["TODO"]});
+        let value = serde_json::to_value(wrap_envelope(&envelope, &payload)).unwrap();
+
+        assert_eq!(value["todo_scan_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(value["root"], "/repo");
+        assert_eq!(value["command"], "stats");
+        assert!(value["generated_at"].as_str().unwrap().ends_with('Z'));
+        assert_eq!(value["data"], payload);
+    }
+
+    fn strip_ansi(line: &str) -> String {
+        regex::Regex::new("\x1b\\[[0-9;]*m")
+            .unwrap()
+            .replace_all(line, "")
+            .into_owned()
+    }
+
+    #[test]
+    fn test_format_list_table_aligns_columns() {
+        let mut author = make_item("src/a.rs", 7, Tag::Fixme, "broken", Priority::High);
+        author.author = Some("alice".to_string());
+        let items = vec![
+            make_item("src/main.rs", 120, Tag::Todo, "first", Priority::Normal),
+            author,
+            make_item("b.rs", 3, Tag::Bug, "crash", Priority::Urgent),
+        ];
+        let lines: Vec<String> = format_list_table(&items, &DetailLevel::Normal)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "TAG    PRI  LOCATION         MESSAGE",
+                "TODO        src/main.rs:120  first",
+                "FIXME  !    src/a.rs:7       broken (@alice)",
+                "BUG    !!   b.rs:3           crash",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_list_table_pads_by_display_width() {
+        let items = vec![
+            make_item("日本.rs", 1, Tag::Todo, "wide", Priority::Normal),
+            make_item("ab.rs", 2, Tag::Todo, "narrow", Priority::Normal),
+        ];
+        let lines: Vec<String> = format_list_table(&items, &DetailLevel::Minimal)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+        // "日本" takes four columns, so both messages start in the same column
+        let column =
+            |line: &str, text: &str| wrap::visible_width(&line[..line.find(text).unwrap()]);
+        assert_eq!(column(&lines[1], "wide"), column(&lines[2], "narrow"));
+        assert_eq!(lines[2], "TODO       ab.rs:2    narrow");
+    }
+
     #[test]
     fn test_sanitize_strips_ansi_escape() {
         assert_eq!(
@@ -1293,12 +2248,17 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            column: 1,
             tag,
             message: msg.to_string(),
             author: None,
             issue_ref: None,
             priority,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
@@ -1313,15 +2273,57 @@ mod tests {
         TodoItem {
             file: file.to_string(),
             line,
+            column: 1,
             tag,
             message: msg.to_string(),
             author: author.map(|a| a.to_string()),
             issue_ref: None,
             priority,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
+    // --- format_item_fields tests ---
+
+    #[test]
+    fn test_format_item_fields_respects_order() {
+        let mut item = make_item("src/a.rs", 7, Tag::Todo, "fix me", Priority::High);
+        item.issue_ref = Some("#12".to_string());
+        let out = format_item_fields(
+            &item,
+            &[
+                ListField::Message,
+                ListField::Line,
+                ListField::Priority,
+                ListField::IssueRef,
+                ListField::File,
+            ],
+        );
+        assert_eq!(out, "fix me 7 high #12 src/a.rs");
+    }
+
+    #[test]
+    fn test_format_item_fields_missing_values_are_dashes() {
+        let item = make_item("a.rs", 1, Tag::Todo, "task", Priority::Normal);
+        let out = format_item_fields(
+            &item,
+            &[ListField::Author, ListField::IssueRef, ListField::Deadline],
+        );
+        assert_eq!(out, "- - -");
+    }
+
+    #[test]
+    fn test_format_item_fields_author_and_tag() {
+        let item = make_item_with_author("a.rs", 1, Tag::Fixme, "x", Priority::Normal, Some("ana"));
+        let out = format_item_fields(&item, &[ListField::Author, ListField::Tag]);
+        assert!(out.starts_with("@ana ["));
+        assert!(out.contains("FIXME"));
+    }
+
     // --- inject_id_field tests ---
 
     #[test]
@@ -1333,6 +2335,10 @@ mod tests {
         });
         inject_id_field(&mut val);
         assert_eq!(val["id"].as_str().unwrap(), "src/main.rs:TODO:fix this bug");
+        assert_eq!(
+            val["stable_id"].as_str().unwrap(),
+            stable_id("src/main.rs", "TODO", "fix this  bug!")
+        );
     }
 
     #[test]
@@ -1360,6 +2366,20 @@ mod tests {
         assert_eq!(val["id"].as_str().unwrap(), "lib.rs:TODO:spaces around");
     }
 
+    #[test]
+    fn test_inject_id_field_ignores_line_and_column() {
+        let mut a = serde_json::json!({
+            "file": "a.rs", "line": 1, "column": 4, "tag": "TODO", "message": "x"
+        });
+        let mut b = serde_json::json!({
+            "file": "a.rs", "line": 9, "column": 30, "tag": "TODO", "message": "x"
+        });
+        inject_id_field(&mut a);
+        inject_id_field(&mut b);
+        assert_eq!(a["id"], b["id"]);
+        assert_eq!(a["stable_id"], b["stable_id"]);
+    }
+
     #[test]
     fn test_inject_id_field_missing_fields_uses_defaults() {
         let mut val = serde_json::json!({});
@@ -1379,6 +2399,33 @@ mod tests {
         assert_eq!(val["id"].as_str().unwrap(), "a.rs:BUG:crash");
     }
 
+    // --- write_jsonl tests ---
+
+    #[test]
+    fn test_write_jsonl_one_object_per_line_with_id() {
+        let items = vec![
+            make_item("a.rs", 1, Tag::Todo, "First", Priority::Normal),
+            make_item("b.rs", 2, Tag::Bug, "second", Priority::Urgent),
+        ];
+        let mut buf = Vec::new();
+        write_jsonl(&items, &mut buf).unwrap();
+        let output = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["id"], "a.rs:TODO:first");
+        assert_eq!(first["line"], 1);
+        let second: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second["priority"], "urgent");
+    }
+
+    #[test]
+    fn test_write_jsonl_empty_writes_nothing() {
+        let mut buf = Vec::new();
+        write_jsonl(&[], &mut buf).unwrap();
+        assert!(buf.is_empty());
+    }
+
     // --- apply_detail_to_json_item tests ---
 
     #[test]
@@ -1473,15 +2520,30 @@ mod tests {
     fn test_colorize_tag_returns_correct_text_for_all_tags() {
         // We verify the underlying text is correct for each tag variant.
         // Colored strings deref to the original text.
-        assert_eq!(colorize_tag(&Tag::Todo).to_string().contains("TODO"), true);
-        assert_eq!(
-            colorize_tag(&Tag::Fixme).to_string().contains("FIXME"),
-            true
-        );
-        assert_eq!(colorize_tag(&Tag::Hack).to_string().contains("HACK"), true);
-        assert_eq!(colorize_tag(&Tag::Bug).to_string().contains("BUG"), true);
-        assert_eq!(colorize_tag(&Tag::Note).to_string().contains("NOTE"), true);
-        assert_eq!(colorize_tag(&Tag::Xxx).to_string().contains("XXX"), true);
+        assert!(colorize_tag(&Tag::Todo).to_string().contains("TODO"));
+        assert!(colorize_tag(&Tag::Fixme).to_string().contains("FIXME"));
+        assert!(colorize_tag(&Tag::Hack).to_string().contains("HACK"));
+        assert!(colorize_tag(&Tag::Bug).to_string().contains("BUG"));
+        assert!(colorize_tag(&Tag::Note).to_string().contains("NOTE"));
+        assert!(colorize_tag(&Tag::Xxx).to_string().contains("XXX"));
+    }
+
+    #[test]
+    fn test_color_override() {
+        assert_eq!(color_override(&ColorChoice::Auto, None), None);
+        assert_eq!(color_override(&ColorChoice::Auto, Some("")), None);
+        assert_eq!(color_override(&ColorChoice::Auto, Some("1")), Some(false));
+        assert_eq!(color_override(&ColorChoice::Never, None), Some(false));
+        // An explicit --color always beats the environment
+        assert_eq!(color_override(&ColorChoice::Always, Some("1")), Some(true));
+    }
+
+    #[test]
+    fn test_configure_color_never_strips_tag_colors() {
+        configure_color(&ColorChoice::Never, None);
+        let result = colorize_tag(&Tag::Fixme);
+        colored::control::unset_override();
+        assert_eq!(result.to_string(), "FIXME");
     }
 
     #[test]
@@ -1725,6 +2787,41 @@ mod tests {
         assert_eq!(groups[2].0, "src/utils");
     }
 
+    fn make_item_with_issue(file: &str, line: usize, issue: Option<&str>) -> TodoItem {
+        let mut item = make_item(file, line, Tag::Todo, "msg", Priority::Normal);
+        item.issue_ref = issue.map(|i| i.to_string());
+        item
+    }
+
+    #[test]
+    fn test_group_key_issue() {
+        let item = make_item_with_issue("f.rs", 1, Some("JIRA-7"));
+        assert_eq!(group_key(&item, &GroupBy::Issue), "JIRA-7");
+        let item = make_item_with_issue("f.rs", 1, None);
+        assert_eq!(group_key(&item, &GroupBy::Issue), "no-issue");
+    }
+
+    #[test]
+    fn test_group_items_by_issue_no_issue_last() {
+        let items = vec![
+            make_item_with_issue("a.rs", 1, None),
+            make_item_with_issue("b.rs", 2, Some("#42")),
+            make_item_with_issue("c.rs", 3, Some("ABC-1")),
+            make_item_with_issue("d.rs", 4, Some("#42")),
+        ];
+
+        let groups = group_items(&items, &GroupBy::Issue);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].0, "#42");
+        assert_eq!(groups[0].1.len(), 2);
+        assert_eq!(groups[0].1[0].file, "b.rs");
+        assert_eq!(groups[0].1[1].file, "d.rs");
+        assert_eq!(groups[1].0, "ABC-1");
+        assert_eq!(groups[2].0, "no-issue");
+        assert_eq!(groups[2].1[0].file, "a.rs");
+    }
+
     #[test]
     fn test_group_items_empty_input() {
         let items: Vec<TodoItem> = vec![];
@@ -1884,12 +2981,17 @@ mod tests {
         let item = TodoItem {
             file: "src/scanner.rs".to_string(),
             line: 42,
+            column: 1,
             tag: Tag::Hack,
             message: "Workaround for bug #123".to_string(),
             author: Some("dev".to_string()),
             issue_ref: Some("#123".to_string()),
             priority: Priority::High,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         let mut val = serde_json::to_value(&item).unwrap();
         apply_detail_to_json_item(&mut val, &DetailLevel::Full);
@@ -1908,12 +3010,17 @@ mod tests {
         let item = TodoItem {
             file: "src/lib.rs".to_string(),
             line: 1,
+            column: 1,
             tag: Tag::Todo,
             message: "clean up".to_string(),
             author: Some("bob".to_string()),
             issue_ref: Some("JIRA-456".to_string()),
             priority: Priority::Urgent,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         let mut val = serde_json::to_value(&item).unwrap();
         apply_detail_to_json_item(&mut val, &DetailLevel::Minimal);
@@ -2025,6 +3132,33 @@ mod tests {
         assert_eq!(result.chars().count(), 1);
     }
 
+    #[test]
+    fn test_sparkline_scales_between_min_and_max() {
+        assert_eq!(sparkline(&[10, 12, 14, 17]), "▁▃▅█");
+        assert_eq!(sparkline(&[5, 1]), "█▁");
+    }
+
+    #[test]
+    fn test_sparkline_flat_and_empty() {
+        assert_eq!(sparkline(&[3, 3, 3]), "▁▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_history_summary_shows_endpoints() {
+        let point = |date: &str, count| HistoryPoint {
+            commit: None,
+            date: date.to_string(),
+            count,
+        };
+        let history = vec![point("2025-01-02", 12), point("2025-03-04", 20)];
+        assert_eq!(
+            history_summary(&history).unwrap(),
+            "▁█  12 → 20 (2025-01-02 .. 2025-03-04)"
+        );
+        assert!(history_summary(&[]).is_none());
+    }
+
     #[test]
     fn test_bar_exact_division() {
         let result = bar(4, 8, 16);
@@ -2302,6 +3436,7 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 2,
+            scanned_files: vec![],
         };
 
         // Replicate the JSON branch of print_list
@@ -2348,15 +3483,21 @@ mod tests {
             items: vec![TodoItem {
                 file: "src/main.rs".to_string(),
                 line: 10,
+                column: 1,
                 tag: Tag::Todo,
                 message: "do something".to_string(),
                 author: Some("bob".to_string()),
                 issue_ref: Some("#42".to_string()),
                 priority: Priority::Urgent,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             }],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
 
         let mut value: serde_json::Value =
@@ -2395,6 +3536,7 @@ mod tests {
             )],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
 
         let mut value: serde_json::Value =
@@ -2434,6 +3576,10 @@ mod tests {
             added_count: 1,
             removed_count: 1,
             base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
 
         // Replicate the JSON branch of print_diff
@@ -2485,6 +3631,10 @@ mod tests {
             added_count: 1,
             removed_count: 0,
             base_ref: "develop".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
 
         let mut value: serde_json::Value =
@@ -2515,6 +3665,7 @@ mod tests {
     fn test_print_check_json_serialization_path() {
         let check_result = CheckResult {
             passed: false,
+            rules: vec![],
             total: 15,
             violations: vec![
                 CheckViolation {
@@ -2526,13 +3677,14 @@ mod tests {
                     message: "Tag HACK is blocked".to_string(),
                 },
             ],
+            warnings: vec![],
         };
 
         // Replicate the JSON branch of print_check
         let json = serde_json::to_string_pretty(&check_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), false);
+        assert!(!parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total"].as_u64().unwrap(), 15);
 
         let violations = parsed["violations"].as_array().unwrap();
@@ -2549,14 +3701,16 @@ mod tests {
     fn test_print_check_json_serialization_path_passed() {
         let check_result = CheckResult {
             passed: true,
+            rules: vec![],
             total: 5,
             violations: vec![],
+            warnings: vec![],
         };
 
         let json = serde_json::to_string_pretty(&check_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), true);
+        assert!(parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total"].as_u64().unwrap(), 5);
         assert_eq!(parsed["violations"].as_array().unwrap().len(), 0);
     }
@@ -2592,6 +3746,9 @@ mod tests {
             avg_age_days: 227,
             stale_count: 1,
             stale_threshold_days: 180,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
 
         // Replicate the JSON branch of print_blame
@@ -2616,13 +3773,13 @@ mod tests {
         );
         assert_eq!(entries[0]["file"].as_str().unwrap(), "src/main.rs");
         assert_eq!(entries[0]["blame"]["author"].as_str().unwrap(), "alice");
-        assert_eq!(entries[0]["stale"].as_bool().unwrap(), false);
+        assert!(!entries[0]["stale"].as_bool().unwrap());
 
         assert_eq!(
             entries[1]["id"].as_str().unwrap(),
             "src/lib.rs:FIXME:urgent fix"
         );
-        assert_eq!(entries[1]["stale"].as_bool().unwrap(), true);
+        assert!(entries[1]["stale"].as_bool().unwrap());
         assert_eq!(entries[1]["blame"]["age_days"].as_u64().unwrap(), 365);
 
         // Top-level fields
@@ -2659,7 +3816,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&lint_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), false);
+        assert!(!parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total_items"].as_u64().unwrap(), 10);
         assert_eq!(parsed["violation_count"].as_u64().unwrap(), 2);
 
@@ -2690,7 +3847,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&lint_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), true);
+        assert!(parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["violation_count"].as_u64().unwrap(), 0);
         assert_eq!(parsed["violations"].as_array().unwrap().len(), 0);
     }
@@ -2726,7 +3883,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&clean_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), false);
+        assert!(!parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["total_items"].as_u64().unwrap(), 8);
         assert_eq!(parsed["stale_count"].as_u64().unwrap(), 1);
         assert_eq!(parsed["duplicate_count"].as_u64().unwrap(), 1);
@@ -2760,7 +3917,7 @@ mod tests {
         let json = serde_json::to_string_pretty(&clean_result).expect("failed to serialize");
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(parsed["passed"].as_bool().unwrap(), true);
+        assert!(parsed["passed"].as_bool().unwrap());
         assert_eq!(parsed["stale_count"].as_u64().unwrap(), 0);
         assert_eq!(parsed["duplicate_count"].as_u64().unwrap(), 0);
         assert_eq!(parsed["violations"].as_array().unwrap().len(), 0);
@@ -2772,6 +3929,7 @@ mod tests {
             items: vec![],
             ignored_items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
         };
 
         let mut value: serde_json::Value =
@@ -2798,6 +3956,10 @@ mod tests {
             added_count: 0,
             removed_count: 0,
             base_ref: "HEAD~1".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
 
         let mut value: serde_json::Value =
@@ -2877,7 +4039,7 @@ mod tests {
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         assert_eq!(parsed["query"].as_str().unwrap(), "memory");
-        assert_eq!(parsed["exact"].as_bool().unwrap(), false);
+        assert!(!parsed["exact"].as_bool().unwrap());
         assert_eq!(parsed["match_count"].as_u64().unwrap(), 2);
         assert_eq!(parsed["file_count"].as_u64().unwrap(), 2);
 
@@ -2909,6 +4071,7 @@ mod tests {
             )],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
 
         let mut context_map: HashMap<String, ContextInfo> = HashMap::new();
@@ -2923,6 +4086,7 @@ mod tests {
                     line_number: 11,
                     content: "}".to_string(),
                 }],
+                mode: ContextMode::Lines,
             },
         );
 
@@ -2976,7 +4140,7 @@ mod tests {
     // Text-format coverage tests
     // ========================================================================
 
-    use crate::context::{ContextLine as CL, RelatedTodo};
+    use crate::context::{ContextLine as CL, ContextMode, RelatedTodo};
     use crate::deadline::Deadline;
 
     fn ctx_line(n: usize, content: &str) -> CL {
@@ -2998,6 +4162,7 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 2,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         print_list(
@@ -3008,6 +4173,7 @@ mod tests {
             0,
             false,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3021,6 +4187,7 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 2,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         print_list(
@@ -3031,6 +4198,7 @@ mod tests {
             0,
             false,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3044,6 +4212,7 @@ mod tests {
             ],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         print_list(
@@ -3054,6 +4223,7 @@ mod tests {
             0,
             false,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3069,6 +4239,7 @@ mod tests {
             )],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
         let mut ctx = HashMap::new();
         ctx.insert(
@@ -3076,6 +4247,7 @@ mod tests {
             ContextInfo {
                 before: vec![ctx_line(9, "fn main() {")],
                 after: vec![ctx_line(11, "}")],
+                mode: ContextMode::Lines,
             },
         );
         print_list(
@@ -3086,6 +4258,7 @@ mod tests {
             0,
             false,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3110,6 +4283,7 @@ mod tests {
                 make_item("src/lib.rs", 5, Tag::Hack, "ignored hack", Priority::Normal),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         // show_ignored=true, ignored_count=2
@@ -3121,6 +4295,7 @@ mod tests {
             2,
             true,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3130,6 +4305,7 @@ mod tests {
             items: vec![make_item("a.rs", 1, Tag::Todo, "active", Priority::Normal)],
             ignored_items: vec![make_item("b.rs", 2, Tag::Note, "ignored", Priority::Normal)],
             files_scanned: 2,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         print_list(
@@ -3140,6 +4316,7 @@ mod tests {
             1,
             true,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3163,6 +4340,7 @@ mod tests {
             items: vec![item],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         // With Minimal, author/issue/deadline should not appear
@@ -3174,6 +4352,7 @@ mod tests {
             0,
             false,
             &DetailLevel::Minimal,
+            &[],
         );
     }
 
@@ -3198,6 +4377,7 @@ mod tests {
             items: vec![item],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         print_list(
@@ -3208,6 +4388,7 @@ mod tests {
             0,
             false,
             &DetailLevel::Full,
+            &[],
         );
     }
 
@@ -3223,6 +4404,7 @@ mod tests {
             items: vec![item],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         print_list(
@@ -3233,6 +4415,7 @@ mod tests {
             0,
             false,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3242,6 +4425,7 @@ mod tests {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task", Priority::Normal)],
             ignored_items: vec![],
             files_scanned: 1,
+            scanned_files: vec![],
         };
         let ctx = HashMap::new();
         // ignored_count > 0 but show_ignored=false => just summary suffix
@@ -3253,6 +4437,7 @@ mod tests {
             3,
             false,
             &DetailLevel::Normal,
+            &[],
         );
     }
 
@@ -3313,6 +4498,7 @@ mod tests {
             ContextInfo {
                 before: vec![ctx_line(8, "line 8"), ctx_line(9, "line 9")],
                 after: vec![ctx_line(11, "line 11")],
+                mode: ContextMode::Lines,
             },
         );
         print_search(
@@ -3430,6 +4616,10 @@ mod tests {
             added_count: 1,
             removed_count: 1,
             base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
         let ctx = HashMap::new();
         print_diff(&result, &Format::Text, &ctx, &DetailLevel::Normal);
@@ -3445,6 +4635,10 @@ mod tests {
             added_count: 1,
             removed_count: 0,
             base_ref: "HEAD~1".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
         let mut ctx = HashMap::new();
         ctx.insert(
@@ -3452,6 +4646,7 @@ mod tests {
             ContextInfo {
                 before: vec![ctx_line(9, "fn main() {")],
                 after: vec![ctx_line(11, "}")],
+                mode: ContextMode::Lines,
             },
         );
         print_diff(&result, &Format::Text, &ctx, &DetailLevel::Normal);
@@ -3574,6 +4769,10 @@ mod tests {
                 removed: 2,
                 base_ref: "main".to_string(),
             }),
+            author_churn: None,
+            history: None,
+            total_authors: 3,
+            truncated: false,
         };
         print_stats(&result, &Format::Text);
     }
@@ -3592,6 +4791,10 @@ mod tests {
             author_counts: vec![],
             hotspot_files: vec![],
             trend: None,
+            author_churn: None,
+            history: None,
+            total_authors: 0,
+            truncated: false,
         };
         print_stats(&result, &Format::Text);
     }
@@ -3614,6 +4817,10 @@ mod tests {
                 removed: 3,
                 base_ref: "develop".to_string(),
             }),
+            author_churn: None,
+            history: None,
+            total_authors: 0,
+            truncated: false,
         };
         print_stats(&result, &Format::Text);
     }
@@ -3721,8 +4928,10 @@ mod tests {
     fn text_print_check_passed() {
         let result = CheckResult {
             passed: true,
+            rules: vec![],
             total: 10,
             violations: vec![],
+            warnings: vec![],
         };
         print_check(&result, &Format::Text);
     }
@@ -3731,6 +4940,7 @@ mod tests {
     fn text_print_check_failed() {
         let result = CheckResult {
             passed: false,
+            rules: vec![],
             total: 150,
             violations: vec![
                 CheckViolation {
@@ -3742,6 +4952,7 @@ mod tests {
                     message: "Blocked tag BUG found".to_string(),
                 },
             ],
+            warnings: vec![],
         };
         print_check(&result, &Format::Text);
     }
@@ -3790,8 +5001,11 @@ mod tests {
             avg_age_days: 203,
             stale_count: 2,
             stale_threshold_days: 90,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
-        print_blame(&result, &Format::Text);
+        print_blame(&result, &Format::Text, false);
     }
 
     // --- print_context: Text format ---
@@ -3816,8 +5030,9 @@ mod tests {
                     message: "another related task".to_string(),
                 },
             ],
+            mode: ContextMode::Lines,
         };
-        print_context(&rich, &Format::Text);
+        print_context(&rich, &Format::Text, false);
     }
 
     #[test]
@@ -3829,8 +5044,37 @@ mod tests {
             todo_line: "// NOTE: important".to_string(),
             after: vec![ctx_line(6, "fn foo() {}")],
             related_todos: vec![],
+            mode: ContextMode::Lines,
+        };
+        print_context(&rich, &Format::Text, false);
+    }
+
+    #[test]
+    fn text_print_context_highlighted() {
+        let rich = RichContext {
+            file: "src/main.rs".to_string(),
+            line: 2,
+            before: vec![ctx_line(1, "fn main() {\x1b[31m")],
+            todo_line: "    // TODO: fix this".to_string(),
+            after: vec![ctx_line(3, "}")],
+            related_todos: vec![],
+            mode: ContextMode::Lines,
+        };
+        print_context(&rich, &Format::Text, true);
+    }
+
+    #[test]
+    fn text_print_context_highlight_unknown_extension() {
+        let rich = RichContext {
+            file: "notes.unknownext".to_string(),
+            line: 1,
+            before: vec![],
+            todo_line: "TODO: plain".to_string(),
+            after: vec![ctx_line(2, "more")],
+            related_todos: vec![],
+            mode: ContextMode::Lines,
         };
-        print_context(&rich, &Format::Text);
+        print_context(&rich, &Format::Text, true);
     }
 
     // --- print_initial_summary ---
@@ -3847,6 +5091,56 @@ mod tests {
         print_initial_summary(&tag_counts, 3, &Format::Json);
     }
 
+    // --- format_watch_dashboard ---
+
+    #[test]
+    fn test_watch_dashboard_sections() {
+        let urgent = vec![make_item(
+            "src/a.rs",
+            3,
+            Tag::Bug,
+            "crash on start",
+            Priority::Urgent,
+        )];
+        let event = WatchEvent {
+            timestamp: "2025-01-15T10:30:00Z".to_string(),
+            file: "src/b.rs".to_string(),
+            added: vec![make_item(
+                "src/b.rs",
+                7,
+                Tag::Todo,
+                "new task",
+                Priority::Normal,
+            )],
+            removed: vec![],
+            tag_summary: vec![],
+            total: 5,
+            total_delta: 1,
+        };
+        let screen = format_watch_dashboard(
+            &[(Tag::Todo, 4), (Tag::Bug, 1)],
+            5,
+            &urgent,
+            Some(&event),
+            Some(5),
+        );
+        assert!(screen.contains(" 5 items"));
+        assert!(screen.contains("Top urgent"));
+        assert!(screen.contains("src/a.rs:3"));
+        assert!(screen.contains("crash on start"));
+        assert!(screen.contains("src/b.rs"));
+        assert!(screen.contains("L7:"));
+        assert!(screen.contains("new task"));
+        assert!(screen.contains("reached --max threshold 5"));
+    }
+
+    #[test]
+    fn test_watch_dashboard_before_any_change() {
+        let screen = format_watch_dashboard(&[], 0, &[], None, None);
+        assert!(screen.contains("none yet"));
+        assert!(!screen.contains("threshold"));
+    }
+
     // --- print_watch_event ---
 
     #[test]
@@ -4060,6 +5354,7 @@ mod tests {
             total_items: 4,
             min_score: 0.5,
             target: None,
+            ungrouped_items: None,
         };
         print_relate(&result, &Format::Text);
     }
@@ -4078,6 +5373,7 @@ mod tests {
             total_items: 2,
             min_score: 0.3,
             target: Some("src/main.rs:10".to_string()),
+            ungrouped_items: None,
         };
         print_relate(&result, &Format::Text);
     }
@@ -4106,6 +5402,7 @@ mod tests {
                         score: 0.8,
                         reason: "same module".to_string(),
                     }],
+                    members: vec![],
                 },
                 Cluster {
                     id: 2,
@@ -4113,12 +5410,14 @@ mod tests {
                     items: vec!["src/log.rs:5".to_string()],
                     suggested_order: vec!["src/log.rs:5".to_string()],
                     relationships: vec![],
+                    members: vec![],
                 },
             ]),
             total_relationships: 1,
             total_items: 3,
             min_score: 0.5,
             target: None,
+            ungrouped_items: None,
         };
         print_relate(&result, &Format::Text);
     }
@@ -4132,6 +5431,7 @@ mod tests {
             total_items: 0,
             min_score: 0.5,
             target: None,
+            ungrouped_items: None,
         };
         print_relate(&result, &Format::Text);
     }
@@ -4211,12 +5511,12 @@ mod tests {
             hotspot_files: vec![("src/main.rs".to_string(), 5)],
             history: vec![
                 HistoryPoint {
-                    commit: "abc123".to_string(),
+                    commit: Some("abc123".to_string()),
                     date: "2025-01-10".to_string(),
                     count: 8,
                 },
                 HistoryPoint {
-                    commit: "def456".to_string(),
+                    commit: Some("def456".to_string()),
                     date: "2025-01-15".to_string(),
                     count: 10,
                 },
@@ -4239,6 +5539,7 @@ mod tests {
                 make_item("src/main.rs", 10, Tag::Todo, "fix this", Priority::Normal),
                 make_item("src/main.rs", 20, Tag::Bug, "crash", Priority::Urgent),
             ],
+            resolved: None,
         };
         let tmp = tempfile::TempDir::new().unwrap();
         let path = tmp.path().join("report.html");
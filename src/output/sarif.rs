@@ -1,7 +1,15 @@
 use crate::model::*;
 
 fn build_sarif_envelope(results: Vec<serde_json::Value>, rules: Vec<serde_json::Value>) -> String {
-    let sarif = serde_json::json!({
+    let sarif = sarif_document(results, rules);
+    serde_json::to_string_pretty(&sarif).expect("failed to serialize SARIF")
+}
+
+fn sarif_document(
+    results: Vec<serde_json::Value>,
+    rules: Vec<serde_json::Value>,
+) -> serde_json::Value {
+    serde_json::json!({
         "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json",
         "version": "2.1.0",
         "runs": [{
@@ -14,8 +22,7 @@ fn build_sarif_envelope(results: Vec<serde_json::Value>, rules: Vec<serde_json::
             },
             "results": results
         }]
-    });
-    serde_json::to_string_pretty(&sarif).expect("failed to serialize SARIF")
+    })
 }
 
 fn rule_id(tag: &Tag) -> String {
@@ -40,7 +47,7 @@ fn collect_rules(items: &[&TodoItem]) -> Vec<serde_json::Value> {
 }
 
 fn item_to_result(item: &TodoItem) -> serde_json::Value {
-    let severity = Severity::from_item(item);
+    let severity = Severity::sarif_from_item(item);
     let mut result = serde_json::json!({
         "ruleId": rule_id(&item.tag),
         "level": severity.as_sarif_level(),
@@ -58,18 +65,41 @@ fn item_to_result(item: &TodoItem) -> serde_json::Value {
             }
         }]
     });
+    let mut props = serde_json::Map::new();
     if let Some(ref deadline) = item.deadline {
+        props.insert(
+            "deadline".to_string(),
+            serde_json::Value::String(deadline.to_string()),
+        );
+    }
+    if let Some(ref issue_ref) = item.issue_ref {
+        props.insert(
+            "issueRef".to_string(),
+            serde_json::Value::String(issue_ref.clone()),
+        );
+    }
+    if !props.is_empty() {
         result
             .as_object_mut()
             .expect("SARIF result should be a JSON object")
-            .insert(
-                "properties".to_string(),
-                serde_json::json!({ "deadline": deadline.to_string() }),
-            );
+            .insert("properties".to_string(), serde_json::Value::Object(props));
     }
     result
 }
 
+/// Merge extra entries into a result's `properties` bag, keeping the
+/// item-level ones (deadline, issue ref) already set by `item_to_result`.
+fn extend_properties(result: &mut serde_json::Value, extra: serde_json::Value) {
+    let props = result
+        .as_object_mut()
+        .expect("SARIF result should be a JSON object")
+        .entry("properties")
+        .or_insert_with(|| serde_json::json!({}));
+    if let (Some(props), serde_json::Value::Object(extra)) = (props.as_object_mut(), extra) {
+        props.extend(extra);
+    }
+}
+
 pub fn format_list(result: &ScanResult) -> String {
     let results: Vec<serde_json::Value> = result.items.iter().map(item_to_result).collect();
     let all_items: Vec<&TodoItem> = result.items.iter().collect();
@@ -98,19 +128,21 @@ pub fn format_diff(result: &DiffResult) -> String {
                 DiffStatus::Added => "added",
                 DiffStatus::Removed => "removed",
             };
-            r.as_object_mut()
-                .expect("SARIF result should be a JSON object")
-                .insert(
-                    "properties".to_string(),
-                    serde_json::json!({ "diffStatus": status }),
-                );
+            extend_properties(&mut r, serde_json::json!({ "diffStatus": status }));
             r
         })
         .collect();
 
     let all_items: Vec<&TodoItem> = result.entries.iter().map(|e| &e.item).collect();
     let rules = collect_rules(&all_items);
-    let mut output = build_sarif_envelope(results, rules);
+    let mut sarif = sarif_document(results, rules);
+    sarif["runs"][0]["properties"] = serde_json::json!({
+        "addedCount": result.added_count,
+        "removedCount": result.removed_count,
+        "netChange": result.net_change,
+        "baseTotal": result.base_total,
+    });
+    let mut output = serde_json::to_string_pretty(&sarif).expect("failed to serialize SARIF");
     output.push('\n');
     output
 }
@@ -121,21 +153,19 @@ pub fn format_blame(result: &BlameResult) -> String {
         .iter()
         .map(|entry| {
             let mut r = item_to_result(&entry.item);
-            r.as_object_mut()
-                .expect("SARIF result should be a JSON object")
-                .insert(
-                    "properties".to_string(),
-                    serde_json::json!({
-                        "blame": {
-                            "author": entry.blame.author,
-                            "email": entry.blame.email,
-                            "date": entry.blame.date,
-                            "ageDays": entry.blame.age_days,
-                            "commit": entry.blame.commit,
-                            "stale": entry.stale,
-                        }
-                    }),
-                );
+            extend_properties(
+                &mut r,
+                serde_json::json!({
+                    "blame": {
+                        "author": entry.blame.author,
+                        "email": entry.blame.email,
+                        "date": entry.blame.date,
+                        "ageDays": entry.blame.age_days,
+                        "commit": entry.blame.commit,
+                        "stale": entry.stale,
+                    }
+                }),
+            );
             r
         })
         .collect();
@@ -233,13 +263,16 @@ pub fn format_lint(result: &LintResult) -> String {
 }
 
 pub fn format_check(result: &CheckResult) -> String {
+    let violation_level = if result.passed { "note" } else { "error" };
     let results: Vec<serde_json::Value> = result
         .violations
         .iter()
-        .map(|v| {
+        .map(|v| (v, violation_level))
+        .chain(result.warnings.iter().map(|w| (w, "warning")))
+        .map(|(v, level)| {
             serde_json::json!({
                 "ruleId": format!("todo-scan/check/{}", v.rule),
-                "level": if result.passed { "note" } else { "error" },
+                "level": level,
                 "message": {
                     "text": v.message
                 }
@@ -250,6 +283,7 @@ pub fn format_check(result: &CheckResult) -> String {
     let rules: Vec<serde_json::Value> = result
         .violations
         .iter()
+        .chain(&result.warnings)
         .map(|v| {
             serde_json::json!({
                 "id": format!("todo-scan/check/{}", v.rule),
@@ -388,12 +422,17 @@ mod tests {
         TodoItem {
             file: "src/main.rs".to_string(),
             line: 10,
+            column: 1,
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 
@@ -402,6 +441,7 @@ mod tests {
         let result = ScanResult {
             items: vec![sample_item(Tag::Todo, "implement feature")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -413,7 +453,7 @@ mod tests {
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0]["ruleId"], "todo-scan/TODO");
-        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[0]["level"], "note");
         assert_eq!(results[0]["message"]["text"], "implement feature");
         assert_eq!(
             results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
@@ -433,6 +473,7 @@ mod tests {
                 sample_item(Tag::Note, "info"),
             ],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -442,6 +483,88 @@ mod tests {
         assert_eq!(results[1]["level"], "note");
     }
 
+    #[test]
+    fn test_format_list_sarif_level_mapping() {
+        let with_priority = |tag, priority| TodoItem {
+            priority,
+            ..sample_item(tag, "task")
+        };
+        let result = ScanResult {
+            items: vec![
+                sample_item(Tag::Bug, "bug"),
+                sample_item(Tag::Xxx, "xxx"),
+                with_priority(Tag::Todo, Priority::Urgent),
+                sample_item(Tag::Fixme, "fixme"),
+                with_priority(Tag::Note, Priority::High),
+                sample_item(Tag::Todo, "todo"),
+                sample_item(Tag::Hack, "hack"),
+                sample_item(Tag::Note, "note"),
+                sample_item(Tag::Custom("SECURITY".to_string()), "custom"),
+            ],
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let output = format_list(&result);
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let levels: Vec<&str> = sarif["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["level"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            levels,
+            vec!["error", "error", "error", "warning", "warning", "note", "note", "note", "note"]
+        );
+    }
+
+    #[test]
+    fn test_format_list_sarif_issue_ref_property() {
+        let result = ScanResult {
+            items: vec![
+                TodoItem {
+                    issue_ref: Some("#42".to_string()),
+                    ..sample_item(Tag::Todo, "fix #42")
+                },
+                sample_item(Tag::Todo, "no ref"),
+            ],
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let output = format_list(&result);
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results[0]["properties"]["issueRef"], "#42");
+        assert!(results[1].get("properties").is_none());
+    }
+
+    #[test]
+    fn test_format_diff_sarif_keeps_item_properties() {
+        let result = DiffResult {
+            entries: vec![DiffEntry {
+                status: DiffStatus::Added,
+                item: TodoItem {
+                    issue_ref: Some("JIRA-7".to_string()),
+                    ..sample_item(Tag::Todo, "tracked")
+                },
+            }],
+            added_count: 1,
+            removed_count: 0,
+            base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
+        };
+        let output = format_diff(&result);
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let props = &sarif["runs"][0]["results"][0]["properties"];
+        assert_eq!(props["diffStatus"], "added");
+        assert_eq!(props["issueRef"], "JIRA-7");
+    }
+
     #[test]
     fn test_format_list_sarif_rules_deduplication() {
         let result = ScanResult {
@@ -451,6 +574,7 @@ mod tests {
                 sample_item(Tag::Bug, "a bug"),
             ],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
@@ -471,6 +595,10 @@ mod tests {
             added_count: 1,
             removed_count: 0,
             base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
         let output = format_diff(&result);
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -482,8 +610,10 @@ mod tests {
     fn test_format_check_sarif_pass() {
         let result = CheckResult {
             passed: true,
+            rules: vec![],
             total: 5,
             violations: vec![],
+            warnings: vec![],
         };
         let output = format_check(&result);
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -500,11 +630,13 @@ mod tests {
     fn test_format_check_sarif_fail() {
         let result = CheckResult {
             passed: false,
+            rules: vec![],
             total: 10,
             violations: vec![CheckViolation {
                 rule: "max".to_string(),
                 message: "10 exceeds max 5".to_string(),
             }],
+            warnings: vec![],
         };
         let output = format_check(&result);
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -513,6 +645,26 @@ mod tests {
         assert_eq!(results[0]["level"], "error");
     }
 
+    #[test]
+    fn test_format_check_sarif_warnings() {
+        let result = CheckResult {
+            passed: true,
+            rules: vec![],
+            total: 1,
+            violations: vec![],
+            warnings: vec![CheckViolation {
+                rule: "due_soon".to_string(),
+                message: "due in 5 days".to_string(),
+            }],
+        };
+        let output = format_check(&result);
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "todo-scan/check/due_soon");
+        assert_eq!(results[0]["level"], "warning");
+    }
+
     #[test]
     fn test_format_search_sarif() {
         let result = SearchResult {
@@ -527,7 +679,7 @@ mod tests {
         let results = sarif["runs"][0]["results"].as_array().unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0]["ruleId"], "todo-scan/FIXME");
-        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["level"], "warning");
     }
 
     #[test]
@@ -548,6 +700,9 @@ mod tests {
             avg_age_days: 400,
             stale_count: 1,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let output = format_blame(&result);
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -714,6 +869,10 @@ mod tests {
             added_count: 0,
             removed_count: 1,
             base_ref: "main".to_string(),
+            head_ref: None,
+            net_change: 0,
+            base_total: 0,
+            base_tag_counts: Default::default(),
         };
         let output = format_diff(&result);
         let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -727,6 +886,7 @@ mod tests {
         let item = TodoItem {
             file: "test.rs".to_string(),
             line: 1,
+            column: 1,
             tag: Tag::Todo,
             message: "task".to_string(),
             author: None,
@@ -737,6 +897,10 @@ mod tests {
                 month: 6,
                 day: 1,
             }),
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         };
         let result = item_to_result(&item);
         assert!(result["properties"]["deadline"].as_str().is_some());
@@ -747,6 +911,7 @@ mod tests {
         let result = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let output = format_list(&result);
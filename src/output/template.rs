@@ -0,0 +1,232 @@
+//! `--template`: bespoke text output rendered per item with minijinja.
+
+use anyhow::{bail, Context, Result};
+use minijinja::{Environment, UndefinedBehavior, Value};
+
+use crate::model::TodoItem;
+
+use super::sanitize_for_terminal;
+
+const TEMPLATE_NAME: &str = "item";
+
+/// Variables a template may use. `age_days` is only set where blame runs
+/// (`list`, `search`) and `status` only in `diff`; elsewhere they are empty.
+const VARIABLES: &[&str] = &[
+    "file",
+    "line",
+    "column",
+    "tag",
+    "message",
+    "author",
+    "issue_ref",
+    "priority",
+    "deadline",
+    "relative_deadline",
+    "checked",
+    "escalate_to",
+    "mentions",
+    "id",
+    "stable_id",
+    "age_days",
+    "status",
+];
+
+/// Per-item values computed outside the scan.
+#[derive(Default)]
+pub struct ItemExtras {
+    pub age_days: Option<u64>,
+    pub status: Option<&'static str>,
+}
+
+/// A compiled `--template`, checked for syntax errors and unknown variables
+/// when it is parsed so mistakes surface before any scanning.
+#[derive(Debug)]
+pub struct ItemTemplate {
+    env: Environment<'static>,
+    uses_age_days: bool,
+}
+
+impl ItemTemplate {
+    /// Compile `source`. The shell escapes `\n` and `\t` are turned into a
+    /// newline and a tab so templates can be written on one command line.
+    pub fn parse(source: &str) -> Result<Self> {
+        let source = unescape(source);
+        let mut env = Environment::new();
+        env.set_keep_trailing_newline(true);
+        env.set_undefined_behavior(UndefinedBehavior::Strict);
+        // Missing optional fields print as nothing rather than "none", and
+        // item text cannot smuggle terminal escape sequences
+        env.set_formatter(|out, state, value| {
+            if value.is_none() || value.is_undefined() {
+                return Ok(());
+            }
+            match value.as_str() {
+                Some(s) => Ok(out.write_str(&sanitize_for_terminal(s))?),
+                None => minijinja::escape_formatter(out, state, value),
+            }
+        });
+        env.add_template_owned(TEMPLATE_NAME, source)
+            .context("invalid --template")?;
+
+        let template = env.get_template(TEMPLATE_NAME)?;
+        let used = template.undeclared_variables(false);
+        let mut unknown: Vec<&String> = used
+            .iter()
+            .filter(|v| !VARIABLES.contains(&v.as_str()))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            bail!(
+                "invalid --template: unknown variable {} (available: {})",
+                unknown
+                    .iter()
+                    .map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                VARIABLES.join(", ")
+            );
+        }
+        let uses_age_days = used.contains("age_days");
+        Ok(Self { env, uses_age_days })
+    }
+
+    /// Whether the template reads `age_days`, which needs git blame.
+    pub fn uses_age_days(&self) -> bool {
+        self.uses_age_days
+    }
+
+    /// Render one item. The result always ends with a newline.
+    pub fn render(&self, item: &TodoItem, extras: &ItemExtras) -> Result<String> {
+        let mut context = serde_json::to_value(item).expect("failed to serialize item");
+        let fields = context.as_object_mut().expect("items serialize as objects");
+        fields.insert("id".to_string(), item.id().into());
+        fields.insert("stable_id".to_string(), item.stable_id().into());
+        fields.insert("age_days".to_string(), extras.age_days.into());
+        fields.insert("status".to_string(), extras.status.into());
+
+        let template = self.env.get_template(TEMPLATE_NAME)?;
+        let mut out = template
+            .render(Value::from_serialize(&context))
+            .with_context(|| {
+                format!(
+                    "failed to render --template for {}:{}",
+                    item.file, item.line
+                )
+            })?;
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+fn unescape(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Priority, Tag};
+
+    fn item() -> TodoItem {
+        TodoItem {
+            file: "src/main.rs".to_string(),
+            line: 12,
+            column: 1,
+            tag: Tag::Fixme,
+            message: "handle errors".to_string(),
+            author: None,
+            issue_ref: Some("#42".to_string()),
+            priority: Priority::High,
+            deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_render_item_fields() {
+        let template = ItemTemplate::parse(
+            "{{file}}:{{line}} {{tag}} {{message}} ({{priority}}, {{issue_ref}})",
+        )
+        .unwrap();
+        let out = template.render(&item(), &ItemExtras::default()).unwrap();
+        assert_eq!(out, "src/main.rs:12 FIXME handle errors (high, #42)\n");
+    }
+
+    #[test]
+    fn test_missing_values_render_empty() {
+        let template = ItemTemplate::parse("[{{author}}][{{age_days}}][{{status}}]").unwrap();
+        let out = template.render(&item(), &ItemExtras::default()).unwrap();
+        assert_eq!(out, "[][][]\n");
+    }
+
+    #[test]
+    fn test_extras_and_ids() {
+        let template = ItemTemplate::parse("{{status}} {{age_days}}d {{id}}").unwrap();
+        assert!(template.uses_age_days());
+        let extras = ItemExtras {
+            age_days: Some(30),
+            status: Some("added"),
+        };
+        let out = template.render(&item(), &extras).unwrap();
+        assert_eq!(out, format!("added 30d {}\n", item().id()));
+    }
+
+    #[test]
+    fn test_escapes_and_trailing_newline_not_doubled() {
+        let template = ItemTemplate::parse(r"{{tag}}\t{{line}}\n").unwrap();
+        assert!(!template.uses_age_days());
+        let out = template.render(&item(), &ItemExtras::default()).unwrap();
+        assert_eq!(out, "FIXME\t12\n");
+    }
+
+    #[test]
+    fn test_strips_control_characters_from_values() {
+        let mut item = item();
+        item.message = "evil \x1b[31mred".to_string();
+        let template = ItemTemplate::parse("{{message}}").unwrap();
+        let out = template.render(&item, &ItemExtras::default()).unwrap();
+        assert_eq!(out, "evil [31mred\n");
+    }
+
+    #[test]
+    fn test_invalid_templates_are_rejected() {
+        let err = ItemTemplate::parse("{{file").unwrap_err();
+        assert!(err.to_string().contains("invalid --template"));
+
+        let err = ItemTemplate::parse("{{file}} {{nope}}").unwrap_err();
+        assert!(err.to_string().contains("unknown variable nope"));
+    }
+
+    #[test]
+    fn test_conditionals_and_filters() {
+        let template = ItemTemplate::parse(
+            "{% if author %}@{{author}}{% else %}nobody{% endif %} {{tag|lower}}",
+        )
+        .unwrap();
+        let out = template.render(&item(), &ItemExtras::default()).unwrap();
+        assert_eq!(out, "nobody fixme\n");
+    }
+}
@@ -0,0 +1,270 @@
+//! Fitting text-mode lines to the terminal width.
+//!
+//! Lines may carry ANSI color codes from `colored`. Escape sequences take no
+//! columns and are never split; a style that is cut off or carried across a
+//! line break is reset and, when wrapping, reapplied on the next line.
+
+use unicode_width::UnicodeWidthChar;
+
+const RESET: &str = "\x1b[0m";
+
+/// Marker appended to a truncated line.
+const ELLIPSIS: char = '…';
+
+/// Extra indentation of wrapped continuation lines past the original indent.
+const CONTINUATION_INDENT: usize = 4;
+
+enum Piece<'a> {
+    Ansi(&'a str),
+    Char(char),
+}
+
+/// Byte length of the escape sequence at the start of `s`, which begins
+/// with ESC. Only CSI sequences (`ESC [ ... final`) are recognized.
+fn ansi_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    if bytes.get(1) != Some(&b'[') {
+        return 1;
+    }
+    bytes[2..]
+        .iter()
+        .position(|b| (0x40..=0x7e).contains(b))
+        .map_or(s.len(), |pos| pos + 3)
+}
+
+fn pieces(line: &str) -> Vec<Piece<'_>> {
+    let mut out = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '\x1b' {
+            let len = ansi_len(rest);
+            out.push(Piece::Ansi(&rest[..len]));
+            len
+        } else {
+            out.push(Piece::Char(c));
+            c.len_utf8()
+        };
+        rest = &rest[len..];
+    }
+    out
+}
+
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Columns `line` occupies on screen, ignoring escape sequences.
+pub fn visible_width(line: &str) -> usize {
+    pieces(line)
+        .iter()
+        .map(|p| match p {
+            Piece::Char(c) => char_width(*c),
+            Piece::Ansi(_) => 0,
+        })
+        .sum()
+}
+
+/// Track the style in effect after `seq`; a reset clears it.
+fn update_style<'a>(active: &mut Option<&'a str>, seq: &'a str) {
+    *active = if seq == RESET { None } else { Some(seq) };
+}
+
+/// Cut `line` to at most `width` columns, ending it with an ellipsis.
+pub fn truncate_line(line: &str, width: usize) -> String {
+    if visible_width(line) <= width {
+        return line.to_string();
+    }
+    let budget = width.saturating_sub(char_width(ELLIPSIS));
+    let mut out = String::new();
+    let mut col = 0;
+    let mut active = None;
+    for piece in pieces(line) {
+        match piece {
+            Piece::Ansi(seq) => {
+                out.push_str(seq);
+                update_style(&mut active, seq);
+            }
+            Piece::Char(c) => {
+                let w = char_width(c);
+                if col + w > budget {
+                    break;
+                }
+                out.push(c);
+                col += w;
+            }
+        }
+    }
+    // Keep the ellipsis against the last word
+    let kept = out.trim_end_matches(' ').len();
+    out.truncate(kept);
+    out.push(ELLIPSIS);
+    if active.is_some() {
+        out.push_str(RESET);
+    }
+    out
+}
+
+/// Word-wrap `line` to `width` columns. Continuation lines are indented past
+/// the line's own leading spaces; words longer than a line are split.
+pub fn wrap_line(line: &str, width: usize) -> String {
+    if visible_width(line) <= width {
+        return line.to_string();
+    }
+    let pieces = pieces(line);
+    let indent = pieces
+        .iter()
+        .filter_map(|p| match p {
+            Piece::Char(c) => Some(*c),
+            Piece::Ansi(_) => None,
+        })
+        .take_while(|&c| c == ' ')
+        .count();
+    let cont_indent = (indent + CONTINUATION_INDENT).min(width / 2);
+
+    let mut wrapper = Wrapper {
+        out: String::new(),
+        width,
+        cont_indent,
+        col: 0,
+        has_text: false,
+        active: None,
+    };
+    let mut word: Vec<&Piece> = Vec::new();
+    let mut pending_spaces = 0;
+    for piece in &pieces {
+        if matches!(piece, Piece::Char(' ')) {
+            if !word.is_empty() {
+                wrapper.push_word(&word, pending_spaces);
+                word.clear();
+                pending_spaces = 0;
+            }
+            pending_spaces += 1;
+        } else {
+            word.push(piece);
+        }
+    }
+    if !word.is_empty() {
+        wrapper.push_word(&word, pending_spaces);
+    }
+    wrapper.out
+}
+
+struct Wrapper<'a> {
+    out: String,
+    width: usize,
+    cont_indent: usize,
+    col: usize,
+    /// Whether the current output line holds any visible text yet.
+    has_text: bool,
+    active: Option<&'a str>,
+}
+
+impl<'a> Wrapper<'a> {
+    fn break_line(&mut self) {
+        if self.active.is_some() {
+            self.out.push_str(RESET);
+        }
+        self.out.push('\n');
+        self.out.push_str(&" ".repeat(self.cont_indent));
+        if let Some(seq) = self.active {
+            self.out.push_str(seq);
+        }
+        self.col = self.cont_indent;
+        self.has_text = false;
+    }
+
+    fn push_word(&mut self, word: &[&Piece<'a>], spaces: usize) {
+        let word_width: usize = word
+            .iter()
+            .map(|p| match p {
+                Piece::Char(c) => char_width(*c),
+                Piece::Ansi(_) => 0,
+            })
+            .sum();
+        if self.has_text && self.col + spaces + word_width > self.width {
+            self.break_line();
+        } else {
+            self.out.push_str(&" ".repeat(spaces));
+            self.col += spaces;
+        }
+        for piece in word {
+            match piece {
+                Piece::Ansi(seq) => {
+                    self.out.push_str(seq);
+                    update_style(&mut self.active, seq);
+                }
+                Piece::Char(c) => {
+                    let w = char_width(*c);
+                    if self.has_text && self.col + w > self.width {
+                        self.break_line();
+                    }
+                    self.out.push(*c);
+                    self.col += w;
+                    self.has_text = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_width_skips_ansi() {
+        assert_eq!(visible_width("\x1b[33mTODO\x1b[0m: x"), 7);
+        assert_eq!(visible_width("日本"), 4);
+    }
+
+    #[test]
+    fn test_truncate_short_line_unchanged() {
+        assert_eq!(truncate_line("  a.rs:1: hi", 40), "  a.rs:1: hi");
+    }
+
+    #[test]
+    fn test_truncate_adds_ellipsis_within_width() {
+        let out = truncate_line("  a.rs:1: [TODO] a very long message", 20);
+        assert_eq!(out, "  a.rs:1: [TODO] a…");
+        assert!(visible_width(&out) <= 20);
+    }
+
+    #[test]
+    fn test_truncate_keeps_escape_sequences_whole_and_resets() {
+        let line = "\x1b[2m  a.rs:1: dimmed message here\x1b[0m";
+        let out = truncate_line(line, 12);
+        assert!(out.starts_with("\x1b[2m  a.rs:1: d"));
+        assert!(out.ends_with("…\x1b[0m"));
+        assert_eq!(visible_width(&out), 12);
+    }
+
+    #[test]
+    fn test_truncate_does_not_split_wide_char() {
+        let out = truncate_line("日本語テキスト", 6);
+        assert_eq!(out, "日本…");
+        assert!(visible_width(&out) <= 6);
+    }
+
+    #[test]
+    fn test_wrap_indents_continuation_lines() {
+        let out = wrap_line("  L1: [TODO] alpha beta gamma delta", 20);
+        assert_eq!(out, "  L1: [TODO] alpha\n      beta gamma\n      delta");
+        assert!(out.lines().all(|l| visible_width(l) <= 20));
+    }
+
+    #[test]
+    fn test_wrap_reapplies_style_across_breaks() {
+        let line = "  x \x1b[31m[expired: 2020-01-01]\x1b[0m";
+        let out = wrap_line(line, 17);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("[expired:\x1b[0m"));
+        assert!(lines[1].starts_with("      \x1b[31m2020-01-01]"));
+    }
+
+    #[test]
+    fn test_wrap_splits_overlong_word() {
+        let out = wrap_line("abcdefghij", 4);
+        assert_eq!(out, "abcd\n  ef\n  gh\n  ij");
+    }
+}
@@ -0,0 +1,293 @@
+use crate::model::*;
+
+/// Xcode reads `file:line: warning: message` lines from run-script build
+/// phases into the issue navigator. Only BUG and urgent items are errors,
+/// since an error fails the build.
+fn item_level(item: &TodoItem) -> &'static str {
+    if item.tag == Tag::Bug || item.priority == Priority::Urgent {
+        "error"
+    } else {
+        "warning"
+    }
+}
+
+/// Each diagnostic must fit on one line, or Xcode drops the rest.
+fn one_line(s: &str) -> String {
+    s.replace(['\r', '\n'], " ")
+}
+
+fn diagnostic(location: Option<(&str, usize)>, level: &str, msg: &str) -> String {
+    match location {
+        Some((file, line)) => format!("{file}:{line}: {level}: {}", one_line(msg)),
+        None => format!("{level}: {}", one_line(msg)),
+    }
+}
+
+fn format_item_diagnostic(item: &TodoItem) -> String {
+    let mut msg = format!("[{}] {}", item.tag.as_str(), item.message);
+    if let Some(ref deadline) = item.deadline {
+        msg.push_str(&format!(" (deadline: {})", deadline));
+    }
+    diagnostic(Some((&item.file, item.line)), item_level(item), &msg)
+}
+
+pub fn format_list(result: &ScanResult) -> String {
+    let mut lines: Vec<String> = result.items.iter().map(format_item_diagnostic).collect();
+    lines.push(format!("todo-scan: {} items found", result.items.len()));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_search(result: &SearchResult) -> String {
+    let mut lines: Vec<String> = result.items.iter().map(format_item_diagnostic).collect();
+    lines.push(one_line(&format!(
+        "todo-scan search: {} matches (query: \"{}\")",
+        result.match_count, result.query
+    )));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_diff(result: &DiffResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for entry in &result.entries {
+        match entry.status {
+            DiffStatus::Added => {
+                lines.push(format_item_diagnostic(&entry.item));
+            }
+            DiffStatus::Removed => {
+                lines.push(one_line(&format!(
+                    "Removed {}:{}: [{}] {}",
+                    entry.item.file,
+                    entry.item.line,
+                    entry.item.tag.as_str(),
+                    entry.item.message
+                )));
+            }
+        }
+    }
+    lines.push(format!(
+        "todo-scan diff: +{} -{} (net {:+}, base {})",
+        result.added_count, result.removed_count, result.net_change, result.base_total
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_blame(result: &BlameResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    for entry in &result.entries {
+        let msg = format!(
+            "[{}] {} @{} {} ({} days ago)",
+            entry.item.tag.as_str(),
+            entry.item.message,
+            entry.blame.author,
+            entry.blame.date,
+            entry.blame.age_days,
+        );
+        let location = (entry.item.file.as_str(), entry.item.line);
+        if entry.stale {
+            lines.push(diagnostic(
+                Some(location),
+                "warning",
+                &format!("Stale {msg}"),
+            ));
+        } else {
+            lines.push(one_line(&format!("{}:{}: {}", location.0, location.1, msg)));
+        }
+    }
+
+    lines.push(format!(
+        "todo-scan blame: {} items, {} stale",
+        result.total, result.stale_count,
+    ));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_lint(result: &LintResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if result.passed {
+        lines.push("todo-scan lint: PASS".to_string());
+    } else {
+        for violation in &result.violations {
+            lines.push(diagnostic(
+                Some((&violation.file, violation.line)),
+                "error",
+                &format!("{}: {}", violation.rule, violation.message),
+            ));
+        }
+        lines.push(format!(
+            "error: todo-scan lint: FAIL ({} violations)",
+            result.violation_count
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_check(result: &CheckResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for warning in &result.warnings {
+        lines.push(diagnostic(
+            None,
+            "warning",
+            &format!("{}: {}", warning.rule, warning.message),
+        ));
+    }
+    if result.passed {
+        lines.push("todo-scan check: PASS".to_string());
+    } else {
+        for violation in &result.violations {
+            lines.push(diagnostic(
+                None,
+                "error",
+                &format!("{}: {}", violation.rule, violation.message),
+            ));
+        }
+        lines.push("error: todo-scan check: FAIL".to_string());
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+pub fn format_clean(result: &CleanResult) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if result.passed {
+        lines.push("todo-scan clean: PASS".to_string());
+    } else {
+        for violation in &result.violations {
+            lines.push(diagnostic(
+                Some((&violation.file, violation.line)),
+                "error",
+                &format!("{}: {}", violation.rule, violation.message),
+            ));
+        }
+        lines.push(format!(
+            "error: todo-scan clean: FAIL ({} stale, {} duplicates)",
+            result.stale_count, result.duplicate_count
+        ));
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_item(tag: Tag, message: &str) -> TodoItem {
+        TodoItem {
+            file: "src/main.rs".to_string(),
+            line: 10,
+            column: 1,
+            tag,
+            message: message.to_string(),
+            author: None,
+            issue_ref: None,
+            priority: Priority::Normal,
+            deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
+        }
+    }
+
+    fn scan_result(items: Vec<TodoItem>) -> ScanResult {
+        ScanResult {
+            items,
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        }
+    }
+
+    #[test]
+    fn test_format_list_single_item() {
+        let output = format_list(&scan_result(vec![sample_item(
+            Tag::Todo,
+            "implement feature",
+        )]));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "src/main.rs:10: warning: [TODO] implement feature",
+                "todo-scan: 1 items found",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_list_errors_only_for_bug_and_urgent() {
+        let mut urgent = sample_item(Tag::Todo, "urgent task");
+        urgent.priority = Priority::Urgent;
+        let output = format_list(&scan_result(vec![
+            sample_item(Tag::Bug, "critical bug"),
+            sample_item(Tag::Fixme, "broken"),
+            sample_item(Tag::Note, "a note"),
+            urgent,
+        ]));
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "src/main.rs:10: error: [BUG] critical bug");
+        assert_eq!(lines[1], "src/main.rs:10: warning: [FIXME] broken");
+        assert_eq!(lines[2], "src/main.rs:10: warning: [NOTE] a note");
+        assert_eq!(lines[3], "src/main.rs:10: error: [TODO] urgent task");
+    }
+
+    #[test]
+    fn test_format_list_includes_deadline() {
+        let mut item = sample_item(Tag::Todo, "ship it");
+        item.deadline = Some(crate::deadline::Deadline {
+            year: 2026,
+            month: 1,
+            day: 31,
+        });
+        let output = format_list(&scan_result(vec![item]));
+        assert!(
+            output.starts_with("src/main.rs:10: warning: [TODO] ship it (deadline: 2026-01-31)\n")
+        );
+    }
+
+    #[test]
+    fn test_search_query_cannot_add_diagnostics() {
+        let result = SearchResult {
+            items: vec![],
+            match_count: 0,
+            file_count: 0,
+            exact: false,
+            query: "evil\nsrc/a.rs:1: error: injected".to_string(),
+        };
+        let output = format_search(&result);
+        assert_eq!(output.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_format_check_fail() {
+        let result = CheckResult {
+            passed: false,
+            rules: vec![],
+            total: 10,
+            violations: vec![CheckViolation {
+                rule: "max".to_string(),
+                message: "10 exceeds max 5".to_string(),
+            }],
+            warnings: vec![CheckViolation {
+                rule: "due_soon".to_string(),
+                message: "Deadline 2025-06-20 in a.rs:1 is due in 5 days".to_string(),
+            }],
+        };
+        let output = format_check(&result);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "warning: due_soon: Deadline 2025-06-20 in a.rs:1 is due in 5 days",
+                "error: max: 10 exceeds max 5",
+                "error: todo-scan check: FAIL",
+            ]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! `--profile`: wall-clock time spent in each phase of a run (scanning, the
+//! cache, git blame and diff, context collection, output), printed to
+//! stderr when the run ends.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STARTED: OnceLock<Instant> = OnceLock::new();
+static JSON: AtomicBool = AtomicBool::new(false);
+/// Time per phase, in the order phases first ran, with how often each ran.
+static PHASES: Mutex<Vec<PhaseTime>> = Mutex::new(Vec::new());
+
+struct PhaseTime {
+    name: &'static str,
+    elapsed: Duration,
+    calls: usize,
+}
+
+/// Start recording. `json` prints the report as a JSON object instead of a
+/// table.
+pub fn enable(json: bool) {
+    STARTED.get_or_init(Instant::now);
+    JSON.store(json, Ordering::Relaxed);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn record(name: &'static str, elapsed: Duration) {
+    let mut phases = PHASES.lock().unwrap_or_else(|e| e.into_inner());
+    match phases.iter_mut().find(|p| p.name == name) {
+        Some(phase) => {
+            phase.elapsed += elapsed;
+            phase.calls += 1;
+        }
+        None => phases.push(PhaseTime {
+            name,
+            elapsed,
+            calls: 1,
+        }),
+    }
+}
+
+/// Run `f`, charging its time to `name`.
+pub fn time<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    if !is_enabled() {
+        return f();
+    }
+    let start = Instant::now();
+    let out = f();
+    record(name, start.elapsed());
+    out
+}
+
+/// Charges the time until it is dropped to a phase; for the tail of a
+/// function, such as printing its result.
+pub struct Phase {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+/// Time the rest of the enclosing scope as `name`.
+pub fn phase(name: &'static str) -> Phase {
+    Phase {
+        name,
+        start: is_enabled().then(Instant::now),
+    }
+}
+
+impl Drop for Phase {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(self.name, start.elapsed());
+        }
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+fn format_table(phases: &[PhaseTime], total: Duration) -> String {
+    let measured: Duration = phases.iter().map(|p| p.elapsed).sum();
+    let other = total.saturating_sub(measured);
+    let mut rows: Vec<(&str, Duration, String)> = phases
+        .iter()
+        .map(|p| (p.name, p.elapsed, p.calls.to_string()))
+        .collect();
+    rows.push(("other", other, String::new()));
+    rows.push(("total", total, String::new()));
+
+    let width = rows.iter().map(|(name, ..)| name.len()).max().unwrap_or(0);
+    let mut out = format!("{:<width$}  {:>10}  {:>5}\n", "phase", "time", "calls");
+    for (name, elapsed, calls) in rows {
+        out.push_str(&format!(
+            "{:<width$}  {:>7.1} ms  {:>5}\n",
+            name,
+            millis(elapsed),
+            calls
+        ));
+    }
+    out
+}
+
+fn format_json(phases: &[PhaseTime], total: Duration) -> String {
+    let phases: Vec<serde_json::Value> = phases
+        .iter()
+        .map(|p| {
+            serde_json::json!({
+                "phase": p.name,
+                "ms": millis(p.elapsed),
+                "calls": p.calls,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "timings": {
+            "phases": phases,
+            "total_ms": millis(total),
+        }
+    })
+    .to_string()
+}
+
+/// Print the recorded timings to stderr, if `--profile` is on.
+pub fn report() {
+    let Some(started) = STARTED.get().filter(|_| is_enabled()) else {
+        return;
+    };
+    let total = started.elapsed();
+    let phases = PHASES.lock().unwrap_or_else(|e| e.into_inner());
+    if JSON.load(Ordering::Relaxed) {
+        eprintln!("{}", format_json(&phases, total));
+    } else {
+        eprint!("{}", format_table(&phases, total));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phases() -> Vec<PhaseTime> {
+        vec![
+            PhaseTime {
+                name: "walk+scan",
+                elapsed: Duration::from_millis(12),
+                calls: 1,
+            },
+            PhaseTime {
+                name: "blame",
+                elapsed: Duration::from_millis(30),
+                calls: 2,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_table_adds_other_and_total() {
+        let table = format_table(&phases(), Duration::from_millis(50));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "phase            time  calls");
+        assert_eq!(lines[1], "walk+scan     12.0 ms      1");
+        assert_eq!(lines[2], "blame         30.0 ms      2");
+        assert_eq!(lines[3], "other          8.0 ms       ");
+        assert_eq!(lines[4], "total         50.0 ms       ");
+    }
+
+    #[test]
+    fn test_format_json_lists_phases_in_order() {
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json(&phases(), Duration::from_millis(50))).unwrap();
+        let timings = &json["timings"];
+        assert_eq!(timings["phases"][0]["phase"], "walk+scan");
+        assert_eq!(timings["phases"][1]["calls"], 2);
+        assert_eq!(timings["total_ms"], 50.0);
+    }
+}
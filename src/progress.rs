@@ -0,0 +1,98 @@
+//! `--progress`: a spinner with a running count of scanned files, drawn on
+//! stderr while the walker runs so scans of large repositories don't look
+//! frozen. The line is erased before any results are printed.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::cli::{Format, ProgressChoice};
+use crate::scanner;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Scans that finish sooner never draw, so quick runs don't flicker.
+const DELAY: Duration = Duration::from_millis(250);
+const TICK: Duration = Duration::from_millis(100);
+const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Apply `--progress`. Must run before the first scan.
+pub fn configure(choice: &ProgressChoice, format: &Format, quiet: bool) {
+    let terminal = std::io::stdout().is_terminal() && std::io::stderr().is_terminal();
+    let show = should_show(choice, matches!(format, Format::Text), quiet, terminal);
+    ENABLED.store(show, Ordering::Relaxed);
+}
+
+/// `auto` shows progress only for text output on a terminal; `always` also
+/// when piped or with a machine format, since it only writes to stderr.
+/// `--quiet` wins over both.
+fn should_show(choice: &ProgressChoice, text_format: bool, quiet: bool, terminal: bool) -> bool {
+    match choice {
+        ProgressChoice::Never => false,
+        ProgressChoice::Always => !quiet,
+        ProgressChoice::Auto => !quiet && text_format && terminal,
+    }
+}
+
+fn status_line(frame: char, files: usize) -> String {
+    let noun = if files == 1 { "file" } else { "files" };
+    format!("{frame} Scanning... {files} {noun}")
+}
+
+/// Run `scan`, showing progress on stderr until it returns.
+pub fn track<T>(scan: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return scan();
+    }
+    let start = scanner::files_scanned_so_far();
+    // Dropping the sender tells the ticker the scan is over
+    let (done, wait) = mpsc::channel::<()>();
+    let ticker = thread::spawn(move || {
+        if wait.recv_timeout(DELAY) != Err(RecvTimeoutError::Timeout) {
+            return;
+        }
+        for frame in FRAMES.iter().cycle() {
+            let files = scanner::files_scanned_so_far().saturating_sub(start);
+            eprint!("\r{}", status_line(*frame, files));
+            if wait.recv_timeout(TICK) != Err(RecvTimeoutError::Timeout) {
+                break;
+            }
+        }
+        // Carriage return, then erase the whole line
+        eprint!("\r\x1b[2K");
+    });
+
+    let out = scan();
+    drop(done);
+    let _ = ticker.join();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_show_auto_needs_text_terminal_and_no_quiet() {
+        let auto = ProgressChoice::Auto;
+        assert!(should_show(&auto, true, false, true));
+        assert!(!should_show(&auto, true, false, false));
+        assert!(!should_show(&auto, false, false, true));
+        assert!(!should_show(&auto, true, true, true));
+    }
+
+    #[test]
+    fn test_should_show_always_and_never() {
+        assert!(should_show(&ProgressChoice::Always, false, false, false));
+        assert!(!should_show(&ProgressChoice::Always, true, true, true));
+        assert!(!should_show(&ProgressChoice::Never, true, false, true));
+    }
+
+    #[test]
+    fn test_status_line_pluralizes() {
+        assert_eq!(status_line('⠋', 1), "⠋ Scanning... 1 file");
+        assert_eq!(status_line('⠙', 42), "⠙ Scanning... 42 files");
+    }
+}
@@ -1,6 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::model::{Cluster, RelateResult, Relationship, ScanResult, TodoItem};
+use anyhow::{bail, Result};
+
+use crate::config::edit_distance;
+use crate::context::parse_location;
+use crate::model::{Cluster, RelateResult, Relationship, ScanResult, TagStyles, TodoItem};
 
 const STOPWORDS: &[&str] = &[
     "a", "an", "the", "is", "it", "in", "to", "of", "for", "on", "and", "or", "but", "not", "with",
@@ -9,6 +13,11 @@ const STOPWORDS: &[&str] = &[
     "bug", "note",
 ];
 
+/// Shortest `stable_id` prefix `--for` accepts, like an abbreviated git hash.
+const MIN_ID_PREFIX: usize = 4;
+/// Near matches listed when a `--for` id matches nothing.
+const MAX_NEAR_MATCHES: usize = 3;
+
 const PROXIMITY_WEIGHT: f64 = 0.30;
 const KEYWORD_WEIGHT: f64 = 0.35;
 const CROSSREF_WEIGHT: f64 = 0.25;
@@ -153,6 +162,7 @@ pub fn compute_relations(
             total_items: items.len(),
             min_score,
             target: None,
+            ungrouped_items: None,
         };
     }
 
@@ -189,7 +199,89 @@ pub fn compute_relations(
         total_items: items.len(),
         min_score,
         target: None,
+        ungrouped_items: None,
+    }
+}
+
+/// Resolve a `relate --for` reference to the current `file:line` of a TODO.
+/// The reference is an item's `stable_id` (or a unique prefix of it), its
+/// `id`, or a literal FILE:LINE. Ids keep working after the TODO moves.
+pub fn resolve_target(items: &[TodoItem], reference: &str) -> Result<(String, usize)> {
+    if let Some(item) = items
+        .iter()
+        .find(|i| i.stable_id() == reference || i.id() == reference)
+    {
+        return Ok((item.file.clone(), item.line));
+    }
+
+    let is_hex = reference.chars().all(|c| c.is_ascii_hexdigit());
+    if is_hex && reference.len() >= MIN_ID_PREFIX {
+        let matches: Vec<&TodoItem> = items
+            .iter()
+            .filter(|i| i.stable_id().starts_with(reference))
+            .collect();
+        match matches.as_slice() {
+            [item] => return Ok((item.file.clone(), item.line)),
+            [] => {}
+            _ => bail!(
+                "id prefix '{}' is ambiguous; it matches:\n{}",
+                reference,
+                describe(&matches)
+            ),
+        }
+    }
+
+    if let Ok(location) = parse_location(reference) {
+        return Ok(location);
+    }
+
+    // Neither a known id nor a location: suggest ids a typo away
+    let limit = (reference.chars().count() / 3).max(1);
+    let mut near: Vec<(usize, &TodoItem)> = items
+        .iter()
+        .map(|i| {
+            let distance =
+                edit_distance(reference, &i.stable_id()).min(edit_distance(reference, &i.id()));
+            (distance, i)
+        })
+        .filter(|(d, _)| *d <= limit)
+        .collect();
+    near.sort_by_key(|(d, _)| *d);
+    let near: Vec<&TodoItem> = near
+        .into_iter()
+        .take(MAX_NEAR_MATCHES)
+        .map(|(_, i)| i)
+        .collect();
+    if near.is_empty() {
+        bail!(
+            "no TODO with id '{}', and it is not a FILE:LINE location (ids are the \
+             `stable_id` and `id` fields of `list --format json`)",
+            reference
+        );
     }
+    bail!(
+        "no TODO with id '{}'; did you mean:\n{}",
+        reference,
+        describe(&near)
+    )
+}
+
+/// One `stable_id  file:line [TAG] message` line per item, for error messages.
+fn describe(items: &[&TodoItem]) -> String {
+    items
+        .iter()
+        .map(|i| {
+            format!(
+                "  {}  {}:{} [{}] {}",
+                i.stable_id(),
+                i.file,
+                i.line,
+                i.tag,
+                i.message
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 pub fn filter_for_item(result: RelateResult, file: &str, line: usize) -> RelateResult {
@@ -208,6 +300,7 @@ pub fn filter_for_item(result: RelateResult, file: &str, line: usize) -> RelateR
         total_items: result.total_items,
         min_score: result.min_score,
         target: Some(target),
+        ungrouped_items: None,
     }
 }
 
@@ -278,17 +371,29 @@ pub fn generate_theme(items: &[&TodoItem]) -> String {
         .join(", ")
 }
 
-pub fn compute_suggested_order(items: &mut [&TodoItem]) {
+pub fn compute_suggested_order(items: &mut [&TodoItem], tag_styles: &TagStyles) {
     items.sort_by(|a, b| {
         b.priority
             .cmp(&a.priority)
-            .then(b.tag.severity().cmp(&a.tag.severity()))
+            .then(
+                tag_styles
+                    .severity(&b.tag)
+                    .cmp(&tag_styles.severity(&a.tag)),
+            )
             .then(a.file.cmp(&b.file))
             .then(a.line.cmp(&b.line))
     });
 }
 
-pub fn build_clusters(relationships: &[Relationship], items: &[TodoItem]) -> Vec<Cluster> {
+/// Group items connected by `relationships` into clusters of at least
+/// `min_size` members, largest first. Members are ordered by
+/// `compute_suggested_order`.
+pub fn build_clusters(
+    relationships: &[Relationship],
+    items: &[TodoItem],
+    min_size: usize,
+    tag_styles: &TagStyles,
+) -> Vec<Cluster> {
     if items.is_empty() {
         return Vec::new();
     }
@@ -315,18 +420,20 @@ pub fn build_clusters(relationships: &[Relationship], items: &[TodoItem]) -> Vec
         groups.entry(root).or_default().push(i);
     }
 
-    // Filter to clusters with 2+ members and build
+    // Filter to clusters with at least min_size members and build
     let mut clusters: Vec<Cluster> = Vec::new();
-    let mut cluster_id = 1;
 
-    let mut sorted_groups: Vec<_> = groups.into_iter().filter(|(_, v)| v.len() >= 2).collect();
+    let mut sorted_groups: Vec<_> = groups
+        .into_iter()
+        .filter(|(_, v)| v.len() >= min_size)
+        .collect();
     sorted_groups.sort_by_key(|(_, v)| std::cmp::Reverse(v.len()));
 
-    for (_, member_indices) in sorted_groups {
+    for (cluster_id, (_, member_indices)) in (1..).zip(sorted_groups) {
         let mut member_items: Vec<&TodoItem> = member_indices.iter().map(|&i| &items[i]).collect();
         let theme = generate_theme(&member_items);
 
-        compute_suggested_order(&mut member_items);
+        compute_suggested_order(&mut member_items, tag_styles);
 
         let item_locs: Vec<String> = member_items
             .iter()
@@ -347,8 +454,8 @@ pub fn build_clusters(relationships: &[Relationship], items: &[TodoItem]) -> Vec
             items: item_locs.clone(),
             suggested_order: item_locs,
             relationships: cluster_rels,
+            members: member_items.into_iter().cloned().collect(),
         });
-        cluster_id += 1;
     }
 
     clusters
@@ -362,6 +469,58 @@ mod tests {
 
     // --- extract_keywords ---
 
+    #[test]
+    fn test_resolve_target_by_stable_id_and_prefix() {
+        let a = make_item("src/auth.rs", 42, Tag::Todo, "fix auth");
+        let b = make_item("src/db.rs", 7, Tag::Fixme, "pool leak");
+        let items = vec![a.clone(), b];
+        let sid = a.stable_id();
+        assert_eq!(
+            resolve_target(&items, &sid).unwrap(),
+            ("src/auth.rs".to_string(), 42)
+        );
+        assert_eq!(
+            resolve_target(&items, &sid[..8]).unwrap(),
+            ("src/auth.rs".to_string(), 42)
+        );
+        assert_eq!(
+            resolve_target(&items, &a.id()).unwrap(),
+            ("src/auth.rs".to_string(), 42)
+        );
+    }
+
+    #[test]
+    fn test_resolve_target_falls_back_to_location() {
+        let items = vec![make_item("src/auth.rs", 42, Tag::Todo, "fix auth")];
+        assert_eq!(
+            resolve_target(&items, "src/other.rs:3").unwrap(),
+            ("src/other.rs".to_string(), 3)
+        );
+    }
+
+    #[test]
+    fn test_resolve_target_unknown_id_lists_near_matches() {
+        let a = make_item("src/auth.rs", 42, Tag::Todo, "fix auth");
+        let items = vec![a.clone()];
+        let mut typo = a.stable_id();
+        typo.replace_range(0..1, if typo.starts_with('z') { "y" } else { "z" });
+        let err = resolve_target(&items, &typo).unwrap_err().to_string();
+        assert!(err.contains("no TODO with id"), "{err}");
+        assert!(err.contains("did you mean"), "{err}");
+        assert!(err.contains(&a.stable_id()), "{err}");
+        assert!(err.contains("src/auth.rs:42 [TODO] fix auth"), "{err}");
+    }
+
+    #[test]
+    fn test_resolve_target_unknown_id_without_near_matches() {
+        let items = vec![make_item("src/auth.rs", 42, Tag::Todo, "fix auth")];
+        let err = resolve_target(&items, "nothing-like-it")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no TODO with id 'nothing-like-it'"), "{err}");
+        assert!(!err.contains("did you mean"), "{err}");
+    }
+
     #[test]
     fn extract_keywords_removes_stopwords_and_lowercases() {
         let kw = extract_keywords("Implement the input validation for users");
@@ -514,6 +673,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = compute_relations(&scan, 0.3, 10);
@@ -526,6 +686,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("src/main.rs", 10, Tag::Todo, "fix something")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let result = compute_relations(&scan, 0.3, 10);
@@ -541,6 +702,7 @@ mod tests {
                 make_item("src/main.rs", 12, Tag::Fixme, "broken authentication"),
             ],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         // With min_score=0.0, should find relationship
@@ -582,6 +744,7 @@ mod tests {
             total_items: 5,
             min_score: 0.3,
             target: None,
+            ungrouped_items: None,
         };
 
         let filtered = filter_for_item(result, "src/b.rs", 20);
@@ -605,7 +768,7 @@ mod tests {
             reason: "proximity".to_string(),
         }];
 
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, 2, &TagStyles::default());
         assert_eq!(clusters.len(), 1);
         assert_eq!(clusters[0].items.len(), 2);
         assert!(clusters[0].items.contains(&"src/a.rs:10".to_string()));
@@ -635,7 +798,7 @@ mod tests {
             },
         ];
 
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, 2, &TagStyles::default());
         assert_eq!(clusters.len(), 2);
     }
 
@@ -663,7 +826,7 @@ mod tests {
 
     #[test]
     fn build_clusters_empty_items() {
-        let clusters = build_clusters(&[], &[]);
+        let clusters = build_clusters(&[], &[], 2, &TagStyles::default());
         assert!(clusters.is_empty());
     }
 
@@ -674,7 +837,7 @@ mod tests {
             make_item("src/b.rs", 20, Tag::Bug, "crash"),
         ];
         // No relationships means no clusters with 2+ members
-        let clusters = build_clusters(&[], &items);
+        let clusters = build_clusters(&[], &items, 2, &TagStyles::default());
         assert!(clusters.is_empty());
     }
 
@@ -700,7 +863,7 @@ mod tests {
                 reason: "proximity".to_string(),
             },
         ];
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, 2, &TagStyles::default());
         assert_eq!(clusters.len(), 1);
         assert_eq!(clusters[0].items.len(), 3);
         assert_eq!(clusters[0].id, 1);
@@ -738,13 +901,68 @@ mod tests {
                 reason: "proximity".to_string(),
             },
         ];
-        let clusters = build_clusters(&relationships, &items);
+        let clusters = build_clusters(&relationships, &items, 2, &TagStyles::default());
         assert_eq!(clusters.len(), 2);
         // Larger cluster (3 items) should come first
         assert_eq!(clusters[0].items.len(), 3);
         assert_eq!(clusters[1].items.len(), 2);
     }
 
+    #[test]
+    fn build_clusters_min_size_drops_small_clusters() {
+        let items = vec![
+            make_item("src/a.rs", 10, Tag::Todo, "fix auth"),
+            make_item("src/a.rs", 12, Tag::Fixme, "broken auth"),
+            make_item("src/a.rs", 14, Tag::Bug, "auth crash"),
+            make_item("src/b.rs", 10, Tag::Todo, "cache"),
+            make_item("src/b.rs", 12, Tag::Todo, "cache miss"),
+            make_item("src/c.rs", 1, Tag::Note, "alone"),
+        ];
+        let rel = |from: &str, to: &str| Relationship {
+            from: from.to_string(),
+            to: to.to_string(),
+            score: 0.5,
+            reason: "proximity".to_string(),
+        };
+        let relationships = vec![
+            rel("src/a.rs:10", "src/a.rs:12"),
+            rel("src/a.rs:12", "src/a.rs:14"),
+            rel("src/b.rs:10", "src/b.rs:12"),
+        ];
+
+        let clusters = build_clusters(&relationships, &items, 3, &TagStyles::default());
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].items.len(), 3);
+
+        // Size 1 keeps the unrelated item as its own cluster
+        let clusters = build_clusters(&relationships, &items, 1, &TagStyles::default());
+        assert_eq!(clusters.len(), 3);
+        assert_eq!(clusters[2].items, vec!["src/c.rs:1"]);
+    }
+
+    #[test]
+    fn build_clusters_members_follow_suggested_order() {
+        let items = vec![
+            make_item("src/a.rs", 10, Tag::Todo, "fix auth"),
+            make_item("src/a.rs", 12, Tag::Bug, "broken auth"),
+        ];
+        let relationships = vec![Relationship {
+            from: "src/a.rs:10".to_string(),
+            to: "src/a.rs:12".to_string(),
+            score: 0.5,
+            reason: "proximity".to_string(),
+        }];
+
+        let clusters = build_clusters(&relationships, &items, 2, &TagStyles::default());
+        let member_locs: Vec<String> = clusters[0]
+            .members
+            .iter()
+            .map(|i| format!("{}:{}", i.file, i.line))
+            .collect();
+        assert_eq!(member_locs, clusters[0].suggested_order);
+        assert_eq!(clusters[0].members[0].message, "broken auth");
+    }
+
     // --- generate_theme edge cases ---
 
     #[test]
@@ -793,6 +1011,7 @@ mod tests {
             total_items: 3,
             min_score: 0.3,
             target: None,
+            ungrouped_items: None,
         };
         let filtered = filter_for_item(result, "src/c.rs", 30);
         assert!(filtered.relationships.is_empty());
@@ -840,7 +1059,7 @@ mod tests {
             },
         ];
         let mut refs: Vec<&TodoItem> = items.iter().collect();
-        compute_suggested_order(&mut refs);
+        compute_suggested_order(&mut refs, &TagStyles::default());
 
         assert_eq!(refs[0].priority, Priority::Urgent);
         assert_eq!(refs[1].priority, Priority::High);
@@ -1,30 +1,36 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 use crate::blame::compute_blame;
 use crate::config::Config;
 use crate::date_utils;
 use crate::git::git_command;
 use crate::model::*;
-use crate::scanner::scan_content;
+use crate::scanner::{scan_content, ScanOptions};
 use crate::stats::compute_stats;
 
-/// Compute the full report data from a scan result.
+/// Compute the full report data from a scan result. `include_resolved`
+/// also lists the TODOs present at a sampled commit that are gone now.
 pub fn compute_report(
     scan: &ScanResult,
     root: &Path,
     config: &Config,
     history_count: usize,
     stale_threshold_days: u64,
+    include_resolved: bool,
 ) -> Result<ReportResult> {
-    // Reuse stats computation
-    let stats = compute_stats(scan, None);
+    // Reuse stats computation; the report shows the top 5 hotspots
+    let mut stats = compute_stats(scan, None);
+    stats.hotspot_files.truncate(5);
 
     // Compute blame for age data
     let (age_histogram, stale_count, avg_age_days) =
-        match compute_blame(scan, root, stale_threshold_days) {
+        match compute_blame(scan, root, stale_threshold_days, &config.author_aliases()) {
             Ok(blame_result) => {
                 let histogram = build_age_histogram(&blame_result);
                 (
@@ -36,12 +42,15 @@ pub fn compute_report(
             Err(_) => (default_age_histogram(), 0, 0),
         };
 
-    // Compute history trend
-    let history = if history_count > 0 {
-        compute_history(root, config, history_count).unwrap_or_default()
+    // Compute history trend, keeping the sampled items when resolved TODOs
+    // are wanted
+    let samples = if history_count > 0 {
+        sample_history(root, config, history_count).unwrap_or_default()
     } else {
         Vec::new()
     };
+    let resolved = include_resolved.then(|| compute_resolved(scan, root, &samples));
+    let history = samples.into_iter().map(|(point, _)| point).collect();
 
     let generated_at = date_utils::now_iso8601();
 
@@ -65,6 +74,7 @@ pub fn compute_report(
         history,
         age_histogram,
         items: scan.items.clone(),
+        resolved,
     })
 }
 
@@ -74,6 +84,18 @@ pub fn compute_history(
     config: &Config,
     sample_count: usize,
 ) -> Result<Vec<HistoryPoint>> {
+    Ok(sample_history(root, config, sample_count)?
+        .into_iter()
+        .map(|(point, _)| point)
+        .collect())
+}
+
+/// Sample N commits from git history and scan each, oldest first.
+fn sample_history(
+    root: &Path,
+    config: &Config,
+    sample_count: usize,
+) -> Result<Vec<(HistoryPoint, Vec<TodoItem>)>> {
     // Get commit list (hash + date)
     let log_output = git_command(
         &[
@@ -102,6 +124,7 @@ pub fn compute_history(
     let indices = select_sample_indices(commits.len(), sample_count);
     let pattern_str = config.tags_pattern();
     let pattern = Regex::new(&pattern_str)?;
+    let options = ScanOptions::from_config(config);
 
     let mut history = Vec::new();
 
@@ -116,7 +139,7 @@ pub fn compute_history(
             Err(_) => continue,
         };
 
-        let mut count = 0;
+        let mut items = Vec::new();
         for file_path in file_list.lines() {
             let file_path = file_path.trim();
             if file_path.is_empty() {
@@ -128,14 +151,17 @@ pub fn compute_history(
                 Err(_) => continue,
             };
 
-            count += scan_content(&content, file_path, &pattern).items.len();
+            items.extend(scan_content(&content, file_path, &pattern, &options).items);
         }
 
-        history.push(HistoryPoint {
-            commit: short_hash.to_string(),
-            date: date_str.to_string(),
-            count,
-        });
+        history.push((
+            HistoryPoint {
+                commit: Some(short_hash.to_string()),
+                date: date_str.to_string(),
+                count: items.len(),
+            },
+            items,
+        ));
     }
 
     // Chronological order (oldest first)
@@ -144,6 +170,58 @@ pub fn compute_history(
     Ok(history)
 }
 
+/// TODOs found at a sampled commit but absent from the current scan, most
+/// recently seen first. Items whose file is still on disk but was not
+/// scanned (excluded, or outside the scan root) are left out, as their
+/// absence says nothing about whether they were resolved.
+pub fn compute_resolved(
+    scan: &ScanResult,
+    root: &Path,
+    samples: &[(HistoryPoint, Vec<TodoItem>)],
+) -> ResolvedReport {
+    let current: HashSet<String> = scan.items.iter().map(|i| i.stable_id()).collect();
+    let scanned: HashSet<&str> = scan.scanned_files.iter().map(String::as_str).collect();
+
+    // Later samples overwrite earlier ones, leaving the last sighting
+    let mut resolved: HashMap<String, ResolvedItem> = HashMap::new();
+    for (point, items) in samples {
+        for item in items {
+            let id = item.stable_id();
+            if current.contains(&id)
+                || (!scanned.contains(item.file.as_str()) && root.join(&item.file).exists())
+            {
+                continue;
+            }
+            resolved.insert(
+                id,
+                ResolvedItem {
+                    item: item.clone(),
+                    last_seen_commit: point.commit.clone().unwrap_or_default(),
+                    last_seen_date: point.date.clone(),
+                },
+            );
+        }
+    }
+
+    let mut items: Vec<ResolvedItem> = resolved.into_values().collect();
+    items.sort_by(|a, b| {
+        b.last_seen_date
+            .cmp(&a.last_seen_date)
+            .then_with(|| a.item.file.cmp(&b.item.file))
+            .then_with(|| a.item.line.cmp(&b.item.line))
+    });
+    let seen = items.len() + scan.items.len();
+    let resolution_rate = if seen == 0 {
+        0.0
+    } else {
+        items.len() as f64 / seen as f64
+    };
+    ResolvedReport {
+        items,
+        resolution_rate,
+    }
+}
+
 /// Build age histogram from blame result.
 pub fn build_age_histogram(blame_result: &BlameResult) -> Vec<AgeBucket> {
     let mut buckets = [0usize; 6];
@@ -221,9 +299,107 @@ pub fn select_sample_indices(total: usize, sample_count: usize) -> Vec<usize> {
         .collect()
 }
 
+/// One line of a `--history-file`: the report totals at a point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistorySnapshot {
+    /// ISO-8601 UTC timestamp of the report run
+    pub date: String,
+    pub total: usize,
+    pub by_tag: BTreeMap<String, usize>,
+    pub by_priority: BTreeMap<String, usize>,
+}
+
+impl HistorySnapshot {
+    pub fn from_report(report: &ReportResult) -> Self {
+        let by_tag = report
+            .tag_counts
+            .iter()
+            .map(|(tag, count)| (tag.as_str().to_string(), *count))
+            .collect();
+        let p = &report.priority_counts;
+        let by_priority = [("urgent", p.urgent), ("high", p.high), ("normal", p.normal)]
+            .into_iter()
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+        HistorySnapshot {
+            date: report.generated_at.clone(),
+            total: report.summary.total_items,
+            by_tag,
+            by_priority,
+        }
+    }
+
+    fn day(&self) -> &str {
+        self.date.split('T').next().unwrap_or(&self.date)
+    }
+}
+
+/// Append a snapshot as one JSON line. The line goes out in a single write
+/// to a file opened in append mode, so concurrent runs never interleave
+/// within a line.
+pub fn append_history(path: &Path, snapshot: &HistorySnapshot) -> Result<()> {
+    let mut line = serde_json::to_string(snapshot).context("failed to serialize snapshot")?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("cannot open history file: {}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .with_context(|| format!("cannot write history file: {}", path.display()))
+}
+
+/// Read a history file, oldest first, keeping only the latest snapshot of
+/// each day. A missing file is empty history; unparsable lines (e.g. one
+/// cut short by a crash) are skipped.
+pub fn load_history(path: &Path) -> Result<Vec<HistorySnapshot>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("cannot read history file: {}", path.display()))
+        }
+    };
+
+    let mut by_day: BTreeMap<String, HistorySnapshot> = BTreeMap::new();
+    for line in content.lines() {
+        let Ok(snapshot) = serde_json::from_str::<HistorySnapshot>(line) else {
+            continue;
+        };
+        // Later lines win ties, so a rerun within the same second still counts
+        match by_day.get(snapshot.day()) {
+            Some(existing) if existing.date > snapshot.date => {}
+            _ => {
+                by_day.insert(snapshot.day().to_string(), snapshot);
+            }
+        }
+    }
+    Ok(by_day.into_values().collect())
+}
+
+/// Combine git-sampled points with history file snapshots, ordered by date.
+/// On days covered by a snapshot, the snapshot replaces the git samples.
+pub fn merge_history(git: Vec<HistoryPoint>, snapshots: &[HistorySnapshot]) -> Vec<HistoryPoint> {
+    let snapshot_days: HashMap<&str, usize> =
+        snapshots.iter().map(|s| (s.day(), s.total)).collect();
+    let mut points: Vec<HistoryPoint> = git
+        .into_iter()
+        .filter(|p| !snapshot_days.contains_key(p.date.as_str()))
+        .collect();
+    points.extend(snapshots.iter().map(|s| HistoryPoint {
+        commit: None,
+        date: s.day().to_string(),
+        count: s.total,
+    }));
+    // Stable sort keeps git samples from the same day in commit order
+    points.sort_by(|a, b| a.date.cmp(&b.date));
+    points
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_helpers::helpers::git;
 
     #[test]
     fn test_select_sample_indices_basic() {
@@ -263,6 +439,9 @@ mod tests {
             avg_age_days: 0,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram.len(), 6);
@@ -277,12 +456,17 @@ mod tests {
             item: TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                column: 1,
                 tag: Tag::Todo,
                 message: "test".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             },
             blame: BlameInfo {
                 author: "test".to_string(),
@@ -299,6 +483,9 @@ mod tests {
             avg_age_days: 3,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[0].count, 1); // <1 week
@@ -316,12 +503,17 @@ mod tests {
                 item: TodoItem {
                     file: "test.rs".to_string(),
                     line: 1,
+                    column: 1,
                     tag: Tag::Todo,
                     message: "test".to_string(),
                     author: None,
                     issue_ref: None,
                     priority: Priority::Normal,
                     deadline: None,
+                    relative_deadline: None,
+                    checked: None,
+                    escalate_to: None,
+                    mentions: vec![],
                 },
                 blame: BlameInfo {
                     author: "test".to_string(),
@@ -340,6 +532,9 @@ mod tests {
             avg_age_days: 141,
             stale_count: 1,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         for bucket in &histogram {
@@ -353,12 +548,17 @@ mod tests {
             item: TodoItem {
                 file: "test.rs".to_string(),
                 line: 1,
+                column: 1,
                 tag: Tag::Todo,
                 message: "test".to_string(),
                 author: None,
                 issue_ref: None,
                 priority: Priority::Normal,
                 deadline: None,
+                relative_deadline: None,
+                checked: None,
+                escalate_to: None,
+                mentions: vec![],
             },
             blame: BlameInfo {
                 author: "tester".to_string(),
@@ -412,6 +612,9 @@ mod tests {
             avg_age_days: 6,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[0].count, 1, "6 days should be in <1 week bucket");
@@ -427,6 +630,9 @@ mod tests {
             avg_age_days: 7,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[0].count, 0, "7 days should NOT be in <1 week");
@@ -442,6 +648,9 @@ mod tests {
             avg_age_days: 28,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[1].count, 0, "28 days should NOT be in 1-4 weeks");
@@ -456,6 +665,9 @@ mod tests {
             avg_age_days: 27,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[1].count, 1, "27 days should be in 1-4 weeks");
@@ -470,6 +682,9 @@ mod tests {
             avg_age_days: 90,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[2].count, 0, "90 days should NOT be in 1-3 months");
@@ -484,6 +699,9 @@ mod tests {
             avg_age_days: 89,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[2].count, 1, "89 days should be in 1-3 months");
@@ -498,6 +716,9 @@ mod tests {
             avg_age_days: 180,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(
@@ -515,6 +736,9 @@ mod tests {
             avg_age_days: 179,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[3].count, 1, "179 days should be in 3-6 months");
@@ -529,6 +753,9 @@ mod tests {
             avg_age_days: 365,
             stale_count: 1,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(
@@ -546,6 +773,9 @@ mod tests {
             avg_age_days: 364,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[4].count, 1, "364 days should be in 6-12 months");
@@ -559,6 +789,9 @@ mod tests {
             avg_age_days: 0,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[0].count, 1, "0 days should be in <1 week");
@@ -572,6 +805,9 @@ mod tests {
             avg_age_days: 3650,
             stale_count: 1,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[5].count, 1, "3650 days should be in >1 year");
@@ -593,6 +829,9 @@ mod tests {
             avg_age_days: 2,
             stale_count: 0,
             stale_threshold_days: 365,
+            oldest: None,
+            newest: None,
+            authors: None,
         };
         let histogram = build_age_histogram(&blame);
         assert_eq!(histogram[0].count, 3, "all 3 should be in <1 week");
@@ -687,10 +926,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_report(&scan, tmp.path(), &config, 0, 365).unwrap();
+        let result = compute_report(&scan, tmp.path(), &config, 0, 365, false).unwrap();
 
         // Summary should be all zeros
         assert_eq!(result.summary.total_items, 0);
@@ -727,39 +967,55 @@ mod tests {
                 TodoItem {
                     file: "foo.rs".to_string(),
                     line: 10,
+                    column: 1,
                     tag: Tag::Todo,
                     message: "implement this".to_string(),
                     author: Some("alice".to_string()),
                     issue_ref: None,
                     priority: Priority::Normal,
                     deadline: None,
+                    relative_deadline: None,
+                    checked: None,
+                    escalate_to: None,
+                    mentions: vec![],
                 },
                 TodoItem {
                     file: "bar.rs".to_string(),
                     line: 20,
+                    column: 1,
                     tag: Tag::Fixme,
                     message: "urgent fix".to_string(),
                     author: None,
                     issue_ref: Some("#123".to_string()),
                     priority: Priority::Urgent,
                     deadline: None,
+                    relative_deadline: None,
+                    checked: None,
+                    escalate_to: None,
+                    mentions: vec![],
                 },
                 TodoItem {
                     file: "foo.rs".to_string(),
                     line: 30,
+                    column: 1,
                     tag: Tag::Hack,
                     message: "workaround".to_string(),
                     author: None,
                     issue_ref: None,
                     priority: Priority::High,
                     deadline: None,
+                    relative_deadline: None,
+                    checked: None,
+                    escalate_to: None,
+                    mentions: vec![],
                 },
             ],
             files_scanned: 5,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_report(&scan, tmp.path(), &config, 0, 365).unwrap();
+        let result = compute_report(&scan, tmp.path(), &config, 0, 365, false).unwrap();
 
         // Stats should reflect the items
         assert_eq!(result.summary.total_items, 3);
@@ -797,10 +1053,11 @@ mod tests {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_report(&scan, tmp.path(), &config, 5, 365).unwrap();
+        let result = compute_report(&scan, tmp.path(), &config, 5, 365, false).unwrap();
 
         // History should be empty because git commands fail in non-git dir
         assert!(result.history.is_empty());
@@ -817,15 +1074,218 @@ mod tests {
     #[test]
     fn test_compute_history_empty_repo_returns_error() {
         let dir = tempfile::tempdir().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(dir.path())
-            .output()
-            .unwrap();
+        git(dir.path(), &["init"]);
 
         let config = Config::default();
         let result = compute_history(dir.path(), &config, 5);
         // Either an error or empty vec (no commits)
         assert!(result.is_err() || result.unwrap().is_empty());
     }
+
+    fn sample(commit: &str, date: &str, items: Vec<TodoItem>) -> (HistoryPoint, Vec<TodoItem>) {
+        (
+            HistoryPoint {
+                commit: Some(commit.to_string()),
+                date: date.to_string(),
+                count: items.len(),
+            },
+            items,
+        )
+    }
+
+    #[test]
+    fn test_compute_resolved_keeps_last_sighting_of_removed_items() {
+        use crate::test_helpers::helpers::make_item;
+        let tmp = tempfile::tempdir().unwrap();
+        let scan = ScanResult {
+            items: vec![make_item("a.rs", 5, Tag::Todo, "still here")],
+            files_scanned: 1,
+            scanned_files: vec!["a.rs".to_string()],
+            ignored_items: vec![],
+        };
+        let samples = vec![
+            sample(
+                "aaa",
+                "2025-01-01",
+                vec![
+                    make_item("a.rs", 1, Tag::Todo, "still here"),
+                    make_item("a.rs", 2, Tag::Fixme, "fixed early"),
+                    make_item("gone.rs", 1, Tag::Bug, "deleted file"),
+                ],
+            ),
+            sample(
+                "bbb",
+                "2025-02-01",
+                vec![
+                    make_item("a.rs", 4, Tag::Todo, "still here"),
+                    make_item("gone.rs", 7, Tag::Bug, "deleted file"),
+                ],
+            ),
+        ];
+
+        let resolved = compute_resolved(&scan, tmp.path(), &samples);
+        let summary: Vec<(&str, usize, &str)> = resolved
+            .items
+            .iter()
+            .map(|r| {
+                (
+                    r.item.message.as_str(),
+                    r.item.line,
+                    r.last_seen_commit.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("deleted file", 7, "bbb"), ("fixed early", 2, "aaa")]
+        );
+        // Two resolved out of three seen (two gone plus one still open)
+        assert!((resolved.resolution_rate - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_compute_resolved_skips_files_present_but_not_scanned() {
+        use crate::test_helpers::helpers::make_item;
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("vendor")).unwrap();
+        std::fs::write(tmp.path().join("vendor/lib.rs"), "// TODO: vendored\n").unwrap();
+        let scan = ScanResult {
+            items: vec![],
+            files_scanned: 0,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let samples = vec![sample(
+            "aaa",
+            "2025-01-01",
+            vec![make_item("vendor/lib.rs", 1, Tag::Todo, "vendored")],
+        )];
+
+        let resolved = compute_resolved(&scan, tmp.path(), &samples);
+        assert!(resolved.items.is_empty());
+        assert_eq!(resolved.resolution_rate, 0.0);
+    }
+
+    fn snapshot(date: &str, total: usize) -> HistorySnapshot {
+        HistorySnapshot {
+            date: date.to_string(),
+            total,
+            by_tag: BTreeMap::from([("TODO".to_string(), total)]),
+            by_priority: BTreeMap::from([("normal".to_string(), total)]),
+        }
+    }
+
+    #[test]
+    fn test_history_snapshot_from_report() {
+        let stats_scan = ScanResult {
+            items: vec![
+                crate::test_helpers::helpers::make_item("a.rs", 1, Tag::Todo, "x"),
+                crate::test_helpers::helpers::make_item("a.rs", 2, Tag::Fixme, "y"),
+            ],
+            ignored_items: vec![],
+            files_scanned: 1,
+            scanned_files: vec![],
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let report =
+            compute_report(&stats_scan, tmp.path(), &Config::default(), 0, 365, false).unwrap();
+        let snap = HistorySnapshot::from_report(&report);
+
+        assert_eq!(snap.date, report.generated_at);
+        assert_eq!(snap.total, 2);
+        assert_eq!(snap.by_tag["TODO"], 1);
+        assert_eq!(snap.by_tag["FIXME"], 1);
+        assert_eq!(snap.by_priority["normal"], 2);
+        assert_eq!(snap.by_priority["urgent"], 0);
+    }
+
+    #[test]
+    fn test_append_and_load_history_dedupes_same_day() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("history.jsonl");
+
+        append_history(&path, &snapshot("2025-01-01T10:00:00Z", 5)).unwrap();
+        append_history(&path, &snapshot("2025-01-02T09:00:00Z", 7)).unwrap();
+        append_history(&path, &snapshot("2025-01-02T18:00:00Z", 4)).unwrap();
+        // Written out of order by a concurrent run: the later timestamp still wins
+        append_history(&path, &snapshot("2025-01-02T12:00:00Z", 9)).unwrap();
+
+        let history = load_history(&path).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].total, 5);
+        assert_eq!(history[1].date, "2025-01-02T18:00:00Z");
+        assert_eq!(history[1].total, 4);
+    }
+
+    #[test]
+    fn test_load_history_missing_file_is_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let history = load_history(&tmp.path().join("nope.jsonl")).unwrap();
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_load_history_skips_malformed_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("history.jsonl");
+        let good = serde_json::to_string(&snapshot("2025-01-01T00:00:00Z", 3)).unwrap();
+        std::fs::write(&path, format!("{good}\nnot json\n{{\"date\": \"2025-01")).unwrap();
+
+        let history = load_history(&path).unwrap();
+        assert_eq!(history, vec![snapshot("2025-01-01T00:00:00Z", 3)]);
+    }
+
+    #[test]
+    fn test_append_history_concurrent_writers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("history.jsonl");
+
+        std::thread::scope(|scope| {
+            for i in 0..8 {
+                let path = &path;
+                scope.spawn(move || {
+                    let date = format!("2025-02-{:02}T00:00:00Z", i + 1);
+                    append_history(path, &snapshot(&date, i)).unwrap();
+                });
+            }
+        });
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 8);
+        assert_eq!(load_history(&path).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn test_merge_history_snapshots_replace_git_points_on_same_day() {
+        let git = vec![
+            HistoryPoint {
+                commit: Some("aaa".to_string()),
+                date: "2025-01-01".to_string(),
+                count: 10,
+            },
+            HistoryPoint {
+                commit: Some("bbb".to_string()),
+                date: "2025-01-03".to_string(),
+                count: 12,
+            },
+        ];
+        let snapshots = vec![
+            snapshot("2025-01-02T08:00:00Z", 11),
+            snapshot("2025-01-03T08:00:00Z", 6),
+        ];
+
+        let merged = merge_history(git, &snapshots);
+        let summary: Vec<(&str, usize, bool)> = merged
+            .iter()
+            .map(|p| (p.date.as_str(), p.count, p.commit.is_some()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2025-01-01", 10, true),
+                ("2025-01-02", 11, false),
+                ("2025-01-03", 6, false),
+            ]
+        );
+    }
 }
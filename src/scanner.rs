@@ -1,30 +1,162 @@
-use anyhow::Result;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, WINDOWS_1252};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
 use ignore::{WalkBuilder, WalkState};
 use regex::Regex;
 use std::collections::HashSet;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
 
 use crate::cache::ScanCache;
-use crate::config::Config;
-use crate::deadline::{parse_deadline, Deadline};
-use crate::model::{Priority, ScanResult, Tag, TodoItem};
-
-/// Maximum file size (10 MiB) to prevent OOM when scanning very large files.
-pub(crate) const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+use crate::config::{AuthorAliases, Config};
+use crate::deadline::{parse_deadline, parse_relative_deadline, today, Deadline, RelativeDeadline};
+use crate::error::{Error, Result};
+use crate::model::{Priority, ScanResult, TagStyles, TodoItem};
+
+/// Default maximum file size (10 MiB) to prevent OOM when scanning very large
+/// files. Overridden by `[scan] max_file_size`.
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Files scanned by every scan in this process so far, bumped by the walker
+/// threads as they go. Only ever grows.
+static FILES_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+
+/// Running count of files scanned in this process, for progress displays:
+/// poll it from another thread and subtract the value read before the scan.
+pub fn files_scanned_so_far() -> usize {
+    FILES_PROGRESS.load(Ordering::Relaxed)
+}
 
 /// Check if a file should be skipped based on its metadata size.
 fn should_skip_file(metadata: &std::fs::Metadata, max_size: u64) -> bool {
     metadata.len() > max_size
 }
 
+/// Bytes at the start of a file inspected to tell text from binary.
+const SNIFF_LEN: usize = 4096;
+
+/// Read a source file as text.
+///
+/// Valid UTF-8 is used as is. Otherwise the encoding comes from a byte-order
+/// mark, is sniffed as BOM-less UTF-16, or falls back to Latin-1
+/// (windows-1252). Returns `None` if the file cannot be read or looks binary:
+/// any NUL in the first `SNIFF_LEN` bytes marks a file as binary unless it is
+/// UTF-16.
+pub fn read_text_file(path: &Path) -> Option<String> {
+    decode_text(std::fs::read(path).ok()?)
+}
+
+fn decode_text(bytes: Vec<u8>) -> Option<String> {
+    let has_nul = bytes[..bytes.len().min(SNIFF_LEN)].contains(&0);
+    let bytes = match String::from_utf8(bytes) {
+        // BOM-less UTF-16 of ASCII text is valid UTF-8 too, so UTF-8 with
+        // NULs goes through the UTF-16 checks below
+        Ok(text) if !has_nul => return Some(text),
+        Ok(text) => text.into_bytes(),
+        Err(e) => e.into_bytes(),
+    };
+    let encoding = match Encoding::for_bom(&bytes)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| sniff_utf16(&bytes))
+    {
+        Some(encoding) => encoding,
+        None if has_nul => return None,
+        None => WINDOWS_1252,
+    };
+    let (text, _, _) = encoding.decode(&bytes);
+    if looks_binary(&text) {
+        None
+    } else {
+        Some(text.into_owned())
+    }
+}
+
+/// Detect BOM-less UTF-16 from the NUL byte that every ASCII character leaves
+/// in the high half of its code unit.
+fn sniff_utf16(bytes: &[u8]) -> Option<&'static Encoding> {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN) & !1];
+    let units = sample.len() / 2;
+    if units == 0 {
+        return None;
+    }
+    let even_nuls = sample.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_nuls = sample
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .filter(|&&b| b == 0)
+        .count();
+    let mostly = |n: usize| n * 10 >= units * 9;
+    let rarely = |n: usize| n * 10 <= units;
+    if mostly(odd_nuls) && rarely(even_nuls) {
+        Some(UTF_16LE)
+    } else if mostly(even_nuls) && rarely(odd_nuls) {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// Text decoded from UTF-16 or Latin-1 counts as binary when more than 1% of
+/// its characters are NUL.
+fn looks_binary(text: &str) -> bool {
+    let total = text.chars().count();
+    let nuls = text.chars().filter(|&c| c == '\0').count();
+    nuls * 100 > total
+}
+
 static ISSUE_REF_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?:([A-Z]+-\d+)|#(\d+))").unwrap());
 
+static MENTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@([A-Za-z0-9](?:[A-Za-z0-9_-]*[A-Za-z0-9_])?)").unwrap());
+
+/// Extract `@handle` mentions from the message text, in order and without
+/// duplicates. The `@` must start a word, so `bob@example.com` is not a
+/// mention, and text inside backticks is skipped. A handle followed by
+/// `(`, `::` or `.name` reads as code (`@Override(...)`, `@obj.field`).
+fn extract_mentions(message: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    // Odd segments are inside a `code span`
+    for segment in message.split('`').step_by(2) {
+        for caps in MENTION_RE.captures_iter(segment) {
+            let whole = caps.get(0).unwrap();
+            let before = segment[..whole.start()].chars().next_back();
+            if before.is_some_and(|c| c.is_alphanumeric() || "_.@/\\".contains(c)) {
+                continue;
+            }
+            let after = &segment[whole.end()..];
+            let code_like = after.starts_with(['(', '@'])
+                || after.starts_with("::")
+                || after
+                    .strip_prefix('.')
+                    .is_some_and(|rest| rest.starts_with(|c: char| c.is_alphanumeric()));
+            if code_like {
+                continue;
+            }
+            let handle = &caps[1];
+            if !mentions.iter().any(|m| m == handle) {
+                mentions.push(handle.to_string());
+            }
+        }
+    }
+    mentions
+}
+
 /// Extract an issue reference from the message text.
-/// Matches patterns like #123 or JIRA-456.
-fn extract_issue_ref(message: &str) -> Option<String> {
+///
+/// Configured `patterns` are tried in order, taking the first capture group
+/// (or the whole match). Without any, matches the built-in #123 and JIRA-456
+/// styles.
+fn extract_issue_ref(message: &str, patterns: &[Regex]) -> Option<String> {
+    if !patterns.is_empty() {
+        return patterns.iter().find_map(|re| {
+            let caps = re.captures(message)?;
+            let m = caps.get(1).or_else(|| caps.get(0))?;
+            Some(m.as_str().to_string())
+        });
+    }
     ISSUE_REF_RE.captures(message).map(|caps| {
         caps.get(1)
             .or_else(|| caps.get(2))
@@ -39,23 +171,223 @@ fn extract_issue_ref(message: &str) -> Option<String> {
     })
 }
 
-/// Comment prefixes that can appear anywhere before the tag on the line.
+/// Comment prefixes that can appear anywhere before the tag on the line, in
+/// files of unrecognized type. `[scan] comment_prefixes` replaces these and
+/// every per-language list.
 const COMMENT_PREFIXES: &[&str] = &["//", "#", "/*", "--", "<!--", ";", "(*", "{-", "%"];
 
-/// Prefixes that only match at line start (after trimming whitespace).
+/// Prefixes that only match at line start (after trimming whitespace), in
+/// files of unrecognized type. `[scan] line_start_prefixes` replaces these
+/// and every per-language list.
 const LINE_START_PREFIXES: &[&str] = &["*"];
 
+/// Bumped whenever the scanning rules change what a file yields, so cached
+/// results from older rules are rescanned.
+pub const SCAN_RULES_VERSION: u32 = 8;
+
+/// List item and blockquote markers that open a TODO in prose files.
+const TASK_LIST_PREFIXES: &[&str] = &["-", "*", "+", ">"];
+
+/// Comment markers recognized for one language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CommentSyntax<'a> {
+    /// Prefixes that can appear anywhere before the tag on the line.
+    prefixes: &'a [&'a str],
+    /// Prefixes that only match at line start (after trimming whitespace).
+    line_start: &'a [&'a str],
+    /// Prose file: read `- [ ]` / `- [x]` checkboxes before the tag.
+    task_lists: bool,
+}
+
+const C_STYLE: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["//", "/*"],
+    line_start: &["*"],
+    task_lists: false,
+};
+const HASH: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["#"],
+    line_start: &[],
+    task_lists: false,
+};
+const HASH_AND_C_STYLE: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["#", "//", "/*"],
+    line_start: &["*"],
+    task_lists: false,
+};
+const DOUBLE_DASH: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["--", "/*"],
+    line_start: &["*"],
+    task_lists: false,
+};
+const HASKELL: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["--", "{-"],
+    line_start: &[],
+    task_lists: false,
+};
+const MARKUP: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["<!--"],
+    line_start: &[],
+    task_lists: false,
+};
+const MARKUP_AND_C_STYLE: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["<!--", "//", "/*"],
+    line_start: &["*"],
+    task_lists: false,
+};
+const SEMICOLON: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &[";"],
+    line_start: &[],
+    task_lists: false,
+};
+const INI: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &[";", "#"],
+    line_start: &[],
+    task_lists: false,
+};
+const ML: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["(*"],
+    line_start: &["*"],
+    task_lists: false,
+};
+const PERCENT: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["%"],
+    line_start: &[],
+    task_lists: false,
+};
+const MARKDOWN: CommentSyntax<'static> = CommentSyntax {
+    prefixes: &["<!--"],
+    line_start: TASK_LIST_PREFIXES,
+    task_lists: true,
+};
+
+/// Comment syntax by lowercase file extension. Extensions not listed fall
+/// back to the fallback prefixes; `.txt` uses them too, plus task lists.
+const LANGUAGE_COMMENTS: &[(&[&str], CommentSyntax<'static>)] = &[
+    (
+        &[
+            "rs", "c", "h", "cc", "cpp", "cxx", "hpp", "hh", "java", "kt", "kts", "go", "js",
+            "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts", "swift", "scala", "cs", "dart", "zig",
+            "proto", "css", "scss", "less", "groovy", "gradle", "v", "sol",
+        ],
+        C_STYLE,
+    ),
+    (
+        &[
+            "py", "pyi", "rb", "sh", "bash", "zsh", "fish", "pl", "pm", "r", "yaml", "yml", "toml",
+            "cmake", "mk", "ps1", "ex", "exs", "cr", "jl", "tf", "nim", "coffee",
+        ],
+        HASH,
+    ),
+    (&["php"], HASH_AND_C_STYLE),
+    (&["sql", "lua", "ada", "elm"], DOUBLE_DASH),
+    (&["hs", "lhs"], HASKELL),
+    (&["html", "htm", "xml", "xhtml", "svg"], MARKUP),
+    (&["md", "markdown"], MARKDOWN),
+    (&["vue", "svelte", "astro"], MARKUP_AND_C_STYLE),
+    (
+        &[
+            "lisp", "lsp", "el", "clj", "cljs", "cljc", "edn", "scm", "rkt", "asm", "s",
+        ],
+        SEMICOLON,
+    ),
+    (&["ini"], INI),
+    (&["ml", "mli", "fs", "fsi", "pas"], ML),
+    (&["tex", "sty", "erl", "hrl"], PERCENT),
+];
+
+/// Extensionless file names with a known comment syntax.
+const FILE_NAME_COMMENTS: &[(&str, CommentSyntax<'static>)] = &[
+    ("makefile", HASH),
+    ("dockerfile", HASH),
+    ("gemfile", HASH),
+    ("rakefile", HASH),
+];
+
+impl CommentSyntax<'static> {
+    /// Every built-in fallback prefix; used for unrecognized file types.
+    pub(crate) const ANY: CommentSyntax<'static> = CommentSyntax {
+        prefixes: COMMENT_PREFIXES,
+        line_start: LINE_START_PREFIXES,
+        task_lists: false,
+    };
+
+    /// Select the built-in comment syntax for a file from its name or
+    /// extension, using `ANY` for unrecognized ones.
+    pub(crate) fn for_path(path: &str) -> CommentSyntax<'static> {
+        let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+        let lower = name.to_ascii_lowercase();
+        if let Some((_, syntax)) = FILE_NAME_COMMENTS.iter().find(|(n, _)| *n == lower) {
+            return *syntax;
+        }
+        let Some((_, ext)) = lower.rsplit_once('.') else {
+            return CommentSyntax::ANY;
+        };
+        if ext == "txt" {
+            return CommentSyntax {
+                line_start: TASK_LIST_PREFIXES,
+                task_lists: true,
+                ..CommentSyntax::ANY
+            };
+        }
+        LANGUAGE_COMMENTS
+            .iter()
+            .find(|(exts, _)| exts.contains(&ext))
+            .map(|(_, syntax)| *syntax)
+            .unwrap_or(CommentSyntax::ANY)
+    }
+}
+
+impl<'a> CommentSyntax<'a> {
+    /// Apply `[scan] comment_prefixes` and `line_start_prefixes`: where set,
+    /// they replace the built-in prefixes of every file type. Task-list
+    /// markers in prose files are not comment prefixes and are kept.
+    pub(crate) fn with_overrides(
+        self,
+        prefixes: Option<&'a [&'a str]>,
+        line_start: Option<&'a [&'a str]>,
+    ) -> Self {
+        CommentSyntax {
+            prefixes: prefixes.unwrap_or(self.prefixes),
+            line_start: match line_start {
+                Some(line_start) if !self.task_lists => line_start,
+                _ => self.line_start,
+            },
+            ..self
+        }
+    }
+}
+
+/// Borrow `[scan] comment_prefixes` or `line_start_prefixes` for
+/// `CommentSyntax::with_overrides`.
+pub(crate) fn prefix_strs(prefixes: Option<&[String]>) -> Option<Vec<&str>> {
+    prefixes.map(|p| p.iter().map(String::as_str).collect())
+}
+
+/// Parsed deadline: the absolute date plus the relative form it came from.
+type ParsedDeadline = (Deadline, Option<RelativeDeadline>);
+
+/// Parse an absolute (`2025-06-01`, `2025-Q2`) or relative (`+14d`,
+/// `next-quarter`) deadline. Relative deadlines resolve against today here;
+/// `blame` and `check --expired` re-resolve them against the blame date.
+fn parse_deadline_expr(s: &str) -> Option<ParsedDeadline> {
+    if let Some(deadline) = parse_deadline(s) {
+        return Some((deadline, None));
+    }
+    let relative = parse_relative_deadline(s)?;
+    Some((relative.resolve(&today()), Some(relative)))
+}
+
 /// Parse the parenthesized content after a tag.
-/// Returns `(author, deadline)` extracted from the content.
+/// Returns `(author, deadline, relative_deadline)` extracted from the content.
 ///
 /// Supported formats:
 /// - `"alice"` → author only
-/// - `"2025-06-01"` → deadline only
+/// - `"2025-06-01"` or `"+14d"` → deadline only
 /// - `"alice, 2025-06-01"` → both author and deadline
-fn parse_paren_content(s: &str) -> (Option<String>, Option<Deadline>) {
+fn parse_paren_content(s: &str) -> (Option<String>, Option<Deadline>, Option<RelativeDeadline>) {
     let s = s.trim();
     if s.is_empty() {
-        return (None, None);
+        return (None, None, None);
     }
 
     // Check if there's a comma separating author and date
@@ -64,35 +396,35 @@ fn parse_paren_content(s: &str) -> (Option<String>, Option<Deadline>) {
         let right = s[idx + 1..].trim();
 
         // Try date on the right side
-        if let Some(deadline) = parse_deadline(right) {
+        if let Some((deadline, relative)) = parse_deadline_expr(right) {
             let author = if left.is_empty() {
                 None
             } else {
                 Some(left.to_string())
             };
-            return (author, Some(deadline));
+            return (author, Some(deadline), relative);
         }
 
         // Try date on the left side
-        if let Some(deadline) = parse_deadline(left) {
+        if let Some((deadline, relative)) = parse_deadline_expr(left) {
             let author = if right.is_empty() {
                 None
             } else {
                 Some(right.to_string())
             };
-            return (author, Some(deadline));
+            return (author, Some(deadline), relative);
         }
 
         // Neither side is a date; treat the whole thing as the author
-        return (Some(s.to_string()), None);
+        return (Some(s.to_string()), None, None);
     }
 
     // No comma: try as a date first, otherwise treat as author
-    if let Some(deadline) = parse_deadline(s) {
-        return (None, Some(deadline));
+    if let Some((deadline, relative)) = parse_deadline_expr(s) {
+        return (None, Some(deadline), relative);
     }
 
-    (Some(s.to_string()), None)
+    (Some(s.to_string()), None, None)
 }
 
 /// Returns true if the prefix at `pos` in `text` is outside any string literal,
@@ -101,61 +433,381 @@ fn prefix_outside_quotes(text: &str, pos: usize) -> bool {
     text[..pos].chars().filter(|&c| c == '"').count() % 2 == 0
 }
 
+/// Comment prefixes in `text` with their byte offsets, left to right.
+///
+/// At each position the longest matching prefix wins and is consumed whole,
+/// so `<!--` is one prefix rather than also yielding the `--` inside it.
+fn comment_prefixes_in<'a>(text: &str, prefixes: &[&'a str]) -> Vec<(&'a str, usize)> {
+    let mut found = Vec::new();
+    let mut next = 0;
+    for (pos, _) in text.char_indices() {
+        if pos < next {
+            continue;
+        }
+        let longest = prefixes
+            .iter()
+            .filter(|p| !p.is_empty() && text[pos..].starts_with(**p))
+            .max_by_key(|p| p.len());
+        if let Some(prefix) = longest {
+            found.push((*prefix, pos));
+            next = pos + prefix.len();
+        }
+    }
+    found
+}
+
 /// Heuristic: does the tag at `tag_start` appear to be inside a comment?
-pub(crate) fn is_in_comment(line: &str, tag_start: usize) -> bool {
+pub(crate) fn is_in_comment(line: &str, tag_start: usize, syntax: &CommentSyntax) -> bool {
     let before_tag = &line[..tag_start];
-    for prefix in COMMENT_PREFIXES {
-        let mut start = 0;
-        while let Some(pos) = before_tag[start..].find(prefix) {
-            let abs_pos = start + pos;
-            if prefix_outside_quotes(before_tag, abs_pos) {
-                return true;
-            }
-            start = abs_pos + prefix.len();
-        }
+    if comment_prefixes_in(before_tag, syntax.prefixes)
+        .into_iter()
+        .any(|(_, pos)| prefix_outside_quotes(before_tag, pos))
+    {
+        return true;
     }
     let trimmed = before_tag.trim_start();
-    if LINE_START_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
+    if syntax.line_start.iter().any(|p| trimmed.starts_with(p)) {
         let leading_ws = before_tag.len() - trimmed.len();
         return prefix_outside_quotes(before_tag, leading_ws);
     }
     false
 }
 
+/// Check that a tag match at `tag_start` is a real tag in a comment.
+/// An `@`-prefixed tag (`@todo`) counts only with `at_tags` enabled, and only
+/// when the `@` follows whitespace or a comment prefix, so `email@todo.com`
+/// never matches.
+fn is_tag_at(line: &str, tag_start: usize, syntax: &CommentSyntax, at_tags: bool) -> bool {
+    if !is_in_comment(line, tag_start, syntax) {
+        return false;
+    }
+    let Some(before_at) = line[..tag_start].strip_suffix('@') else {
+        return true;
+    };
+    at_tags
+        && (before_at.is_empty()
+            || before_at.ends_with(char::is_whitespace)
+            || syntax
+                .prefixes
+                .iter()
+                .chain(syntax.line_start)
+                .any(|p| before_at.ends_with(p)))
+}
+
+/// Locate the comment prefix that opens the comment containing `tag_start`.
+/// Returns the prefix and its byte offset, preferring the one closest to the tag.
+fn comment_prefix_before<'a>(
+    line: &str,
+    tag_start: usize,
+    syntax: &CommentSyntax<'a>,
+) -> Option<(&'a str, usize)> {
+    let before_tag = &line[..tag_start];
+    let found = comment_prefixes_in(before_tag, syntax.prefixes)
+        .into_iter()
+        .rfind(|&(_, pos)| prefix_outside_quotes(before_tag, pos));
+    if found.is_none() {
+        let trimmed = before_tag.trim_start();
+        let leading_ws = before_tag.len() - trimmed.len();
+        for prefix in syntax.line_start {
+            if trimmed.starts_with(prefix) {
+                return Some((prefix, leading_ws));
+            }
+        }
+    }
+    found
+}
+
+/// Checkbox state of a task list item such as `- [ ] TODO: ...`, read from
+/// the text before the tag: `Some(false)` for `[ ]`, `Some(true)` for `[x]`.
+fn checkbox_state(before_tag: &str) -> Option<bool> {
+    let trimmed = before_tag.trim_start();
+    let rest = ["-", "*", "+"]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    match rest.trim() {
+        "[ ]" => Some(false),
+        "[x]" | "[X]" => Some(true),
+        _ => None,
+    }
+}
+
+/// If `line` continues a TODO comment, return its text.
+///
+/// A continuation line starts with the same comment prefix as the tag line and
+/// its text is indented further than the tag was after that prefix.
+pub(crate) fn continuation_text<'a>(
+    line: &'a str,
+    prefix: &str,
+    tag_indent: usize,
+) -> Option<&'a str> {
+    let trimmed = line.trim_start();
+    let leading_ws = line.len() - trimmed.len();
+    if !prefix_outside_quotes(line, leading_ws) {
+        return None;
+    }
+    let rest = trimmed.strip_prefix(prefix)?;
+    let text = rest.trim_start();
+    let indent = rest.len() - text.len();
+    if text.is_empty() || indent <= tag_indent {
+        return None;
+    }
+    Some(text.trim_end())
+}
+
+/// Matcher for `exclude_dirs` entries.
+///
+/// Entries without a `/` (plain names like `vendor` or globs like `target-*`)
+/// match any single directory name. Entries with a `/` (like `**/generated`
+/// or `docs/api`) match a directory's path relative to the scan root. `*`
+/// never crosses a `/`; use `**` for that. An entry that is not a valid glob
+/// falls back to exact directory-name comparison.
+#[derive(Debug, Clone)]
+pub struct ExcludeDirs {
+    name_globs: GlobSet,
+    path_globs: GlobSet,
+    literal_names: Vec<String>,
+}
+
+impl ExcludeDirs {
+    pub fn new(entries: &[String]) -> Self {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        let mut literal_names = Vec::new();
+        for entry in entries {
+            let entry = entry.trim_end_matches('/');
+            match GlobBuilder::new(entry).literal_separator(true).build() {
+                Ok(glob) if entry.contains('/') => {
+                    paths.add(glob);
+                }
+                Ok(glob) => {
+                    names.add(glob);
+                }
+                Err(_) => literal_names.push(entry.to_string()),
+            }
+        }
+        Self {
+            name_globs: names.build().unwrap_or_else(|_| GlobSet::empty()),
+            path_globs: paths.build().unwrap_or_else(|_| GlobSet::empty()),
+            literal_names,
+        }
+    }
+
+    /// Whether any directory containing `relative_path` (a file path
+    /// relative to the scan root) is excluded.
+    pub fn is_excluded(&self, relative_path: &Path) -> bool {
+        let Some(parent) = relative_path.parent() else {
+            return false;
+        };
+        parent.ancestors().any(|dir| {
+            let Some(name) = dir.file_name() else {
+                return false;
+            };
+            self.name_globs.is_match(name)
+                || self.path_globs.is_match(dir)
+                || self
+                    .literal_names
+                    .iter()
+                    .any(|n| name.to_str() == Some(n.as_str()))
+        })
+    }
+}
+
+/// Options that change how `scan_content` interprets lines.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Append indented comment continuation lines to the preceding TODO message.
+    pub multiline: bool,
+    /// Lowercased keywords that set the priority of unmarked TODOs,
+    /// urgent ones first so they win when both kinds appear.
+    pub priority_keywords: Vec<(String, Priority)>,
+    /// Compiled `[issue] patterns`; empty means the built-in patterns.
+    pub issue_patterns: Vec<Regex>,
+    /// Accept `@todo`-style doc tags (`[scan] at_tags`).
+    pub at_tags: bool,
+    /// Parse `TODO->FIXME` escalation markers (`[scan] escalation`).
+    pub escalation: bool,
+    /// Record `@handle` mentions (`[scan] mentions`).
+    pub mentions: bool,
+    /// Count columns in UTF-16 code units (`[scan] utf16_columns`).
+    pub utf16_columns: bool,
+    /// `[authors.aliases]`, applied to `TODO(author)` names.
+    pub author_aliases: AuthorAliases,
+    /// `[scan] comment_prefixes`, replacing the built-in prefixes for
+    /// unrecognized file types.
+    pub comment_prefixes: Option<Vec<String>>,
+    /// `[scan] line_start_prefixes`, likewise.
+    pub line_start_prefixes: Option<Vec<String>>,
+    /// `[custom_tags]`; declared names are accepted as tags.
+    pub tag_styles: TagStyles,
+}
+
+impl ScanOptions {
+    pub fn from_config(config: &Config) -> Self {
+        let urgent = config
+            .priority
+            .urgent
+            .iter()
+            .map(|k| (k.to_lowercase(), Priority::Urgent));
+        let high = config
+            .priority
+            .high
+            .iter()
+            .map(|k| (k.to_lowercase(), Priority::High));
+        Self {
+            multiline: config.multiline(),
+            priority_keywords: urgent
+                .chain(high)
+                .filter(|(k, _)| !k.trim().is_empty())
+                .collect(),
+            // Patterns are validated by `Config::parse`
+            issue_patterns: config
+                .issue
+                .patterns
+                .iter()
+                .filter_map(|p| Regex::new(p).ok())
+                .collect(),
+            at_tags: config.at_tags(),
+            escalation: config.escalation(),
+            mentions: config.mentions(),
+            utf16_columns: config.utf16_columns(),
+            author_aliases: config.author_aliases(),
+            comment_prefixes: config.scan.comment_prefixes.clone(),
+            line_start_prefixes: config.scan.line_start_prefixes.clone(),
+            tag_styles: config.tag_styles(),
+        }
+    }
+}
+
+/// Check whether `keyword` occurs in `text` as a whole word.
+/// Both arguments must already be lowercased.
+fn contains_word(text: &str, keyword: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(keyword).any(|(pos, _)| {
+        let before = text[..pos].chars().next_back();
+        let after = text[pos + keyword.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+/// Infer a priority from configured keywords in a TODO message.
+fn infer_priority(message: &str, keywords: &[(String, Priority)]) -> Option<Priority> {
+    if keywords.is_empty() {
+        return None;
+    }
+    let lower = message.to_lowercase();
+    keywords
+        .iter()
+        .find(|(keyword, _)| contains_word(&lower, keyword))
+        .map(|(_, priority)| *priority)
+}
+
 /// Result of scanning content, separating normal items from suppressed ones.
 pub struct ScanContentResult {
     pub items: Vec<TodoItem>,
     pub ignored_items: Vec<TodoItem>,
 }
 
+/// Per-directory ignore file, using gitignore syntax, for paths that git
+/// should track but the scanner should skip.
+pub const IGNORE_FILENAME: &str = ".todo-scan-ignore";
+
 /// The inline suppression marker for the current line.
 const IGNORE_MARKER: &str = "todo-scan:ignore";
 
 /// The inline suppression marker for the next line.
 const IGNORE_NEXT_LINE_MARKER: &str = "todo-scan:ignore-next-line";
 
-/// Scan text content line by line for TODO-style comments.
-///
-/// Pure function: takes content, a file path label, and a compiled regex.
-/// Returns a `ScanContentResult` with matched items and suppressed items separated.
+/// Suppresses every TODO in the file.
+const IGNORE_FILE_MARKER: &str = "todo-scan:ignore-file";
+
+/// Opens a suppressed range, closed by `IGNORE_END_MARKER`.
+const IGNORE_BEGIN_MARKER: &str = "todo-scan:ignore-begin";
+
+/// Closes a range opened by `IGNORE_BEGIN_MARKER`.
+const IGNORE_END_MARKER: &str = "todo-scan:ignore-end";
+
+/// Whether `line` carries the bare same-line marker, as opposed to one of
+/// the longer `todo-scan:ignore-*` markers.
+fn has_inline_ignore(line: &str) -> bool {
+    line.match_indices(IGNORE_MARKER)
+        .any(|(pos, m)| line.as_bytes().get(pos + m.len()) != Some(&b'-'))
+}
+
+/// Line indices suppressed by `ignore-next-line`, `ignore-begin`/`ignore-end`,
+/// and `ignore-file` markers. `ignore-file` must sit inside a comment.
 ///
-/// Suppression markers:
-/// - `todo-scan:ignore` on the same line as a TODO suppresses that item
-/// - `todo-scan:ignore-next-line` on any line suppresses the immediately following line
-pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanContentResult {
-    let lines: Vec<&str> = content.lines().collect();
+/// A `ignore-begin` without a matching `ignore-end` runs to the end of the
+/// file, and nested ranges merge into the outermost one. The marker lines
+/// themselves fall inside the range.
+fn suppressed_line_set(lines: &[&str], syntax: &CommentSyntax) -> HashSet<usize> {
+    // Only a marker inside a comment counts, so prose or string literals that
+    // merely mention it (like this crate's README) suppress nothing
+    let has_marker = |line: &str, marker: &str| {
+        line.match_indices(marker)
+            .any(|(pos, _)| is_in_comment(line, pos, syntax))
+    };
+    if lines
+        .iter()
+        .any(|line| has_marker(line, IGNORE_FILE_MARKER))
+    {
+        return (0..lines.len()).collect();
+    }
 
-    // Pre-scan for todo-scan:ignore-next-line markers
-    let mut suppressed_lines: HashSet<usize> = HashSet::new();
+    let mut suppressed = HashSet::new();
+    let mut depth = 0usize;
     for (idx, line) in lines.iter().enumerate() {
-        if line.contains(IGNORE_NEXT_LINE_MARKER) {
+        if has_marker(line, IGNORE_BEGIN_MARKER) {
+            depth += 1;
+        }
+        if depth > 0 {
+            suppressed.insert(idx);
+        }
+        if has_marker(line, IGNORE_END_MARKER) {
+            depth = depth.saturating_sub(1);
+        }
+        if has_marker(line, IGNORE_NEXT_LINE_MARKER) {
             // Only suppress the immediately next line (no blank lines between)
             let next_idx = idx + 1;
             if next_idx < lines.len() && !lines[next_idx].trim().is_empty() {
-                suppressed_lines.insert(next_idx);
+                suppressed.insert(next_idx);
             }
         }
     }
+    suppressed
+}
+
+/// Scan text content line by line for TODO-style comments.
+///
+/// Pure function: takes content, a file path label, a compiled regex, and scan options.
+/// Returns a `ScanContentResult` with matched items and suppressed items separated.
+///
+/// Suppression markers:
+/// - `todo-scan:ignore` on the same line as a TODO suppresses that item
+/// - `todo-scan:ignore-next-line` on any line suppresses the immediately following line
+/// - `todo-scan:ignore-begin` ... `todo-scan:ignore-end` suppresses the lines in between
+/// - `todo-scan:ignore-file` anywhere suppresses the whole file
+///
+/// With `options.multiline` enabled, comment lines directly below a TODO that share its
+/// comment prefix and are indented past the tag are appended to the message.
+/// A blank line, a different prefix, or a new tag ends the block; `line` keeps
+/// pointing at the tag line.
+pub fn scan_content(
+    content: &str,
+    file_path: &str,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> ScanContentResult {
+    let lines: Vec<&str> = content.lines().collect();
+    let comment_prefixes = prefix_strs(options.comment_prefixes.as_deref());
+    let line_start_prefixes = prefix_strs(options.line_start_prefixes.as_deref());
+    let syntax = CommentSyntax::for_path(file_path)
+        .with_overrides(comment_prefixes.as_deref(), line_start_prefixes.as_deref());
+
+    // Pre-scan for line, range, and file suppression markers
+    let suppressed_lines = suppressed_line_set(&lines, &syntax);
 
     let mut items = Vec::new();
     let mut ignored_items = Vec::new();
@@ -163,42 +815,55 @@ pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanCont
     for (line_idx, line) in lines.iter().enumerate() {
         if let Some(caps) = pattern.captures(line) {
             let tag_match = caps.get(1).unwrap();
-            if !is_in_comment(line, tag_match.start()) {
+            if !is_tag_at(line, tag_match.start(), &syntax, options.at_tags) {
                 continue;
             }
 
+            // An escalation marker (`TODO->FIXME`) names a known tag after the arrow
+            let escalate_to = if options.escalation {
+                caps.get(2)
+                    .and_then(|m| options.tag_styles.parse(m.as_str()))
+            } else {
+                None
+            };
+
             // Skip if the tag is immediately followed by a hyphen (e.g., "todo-scan:ignore")
-            if line.as_bytes().get(tag_match.end()) == Some(&b'-') {
+            if escalate_to.is_none() && line.as_bytes().get(tag_match.end()) == Some(&b'-') {
                 continue;
             }
 
             let tag_str = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-            let tag = match tag_str.parse::<Tag>() {
-                Ok(t) => t,
-                Err(_) => continue,
+            let tag = match options.tag_styles.parse(tag_str) {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let checked = if syntax.task_lists {
+                checkbox_state(&line[..tag_match.start()])
+            } else {
+                None
             };
 
-            let (author, deadline) = match caps.get(2) {
+            let (author, deadline, relative_deadline) = match caps.get(3) {
                 Some(m) => parse_paren_content(m.as_str()),
-                None => (None, None),
+                None => (None, None, None),
             };
+            let author = author.map(|a| options.author_aliases.canonicalize(a));
 
-            let priority = match caps.get(3).map(|m| m.as_str()) {
-                Some("!!") => Priority::Urgent,
-                Some("!") => Priority::High,
-                _ => Priority::Normal,
+            let marker_priority = match caps.get(4).map(|m| m.as_str()) {
+                Some("!!") => Some(Priority::Urgent),
+                Some("!") => Some(Priority::High),
+                _ => None,
             };
 
             let mut message = caps
-                .get(4)
+                .get(5)
                 .map(|m| m.as_str().trim().to_string())
                 .unwrap_or_default();
 
             // Check if this line is suppressed
-            let has_inline_ignore =
-                line.contains(IGNORE_MARKER) && !line.contains(IGNORE_NEXT_LINE_MARKER);
-            let is_next_line_suppressed = suppressed_lines.contains(&line_idx);
-            let is_suppressed = has_inline_ignore || is_next_line_suppressed;
+            let has_inline_ignore = has_inline_ignore(line);
+            let is_suppressed = has_inline_ignore || suppressed_lines.contains(&line_idx);
 
             // Strip trailing todo-scan:ignore from message text
             if has_inline_ignore {
@@ -207,17 +872,63 @@ pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanCont
                 }
             }
 
-            let issue_ref = extract_issue_ref(&message);
+            if options.multiline {
+                if let Some((prefix, pos)) = comment_prefix_before(line, tag_match.start(), &syntax)
+                {
+                    let tag_indent = tag_match.start() - (pos + prefix.len());
+                    for next in &lines[line_idx + 1..] {
+                        let starts_new_tag = pattern
+                            .captures(next)
+                            .and_then(|c| c.get(1))
+                            .is_some_and(|m| is_tag_at(next, m.start(), &syntax, options.at_tags));
+                        if starts_new_tag {
+                            break;
+                        }
+                        match continuation_text(next, prefix, tag_indent) {
+                            Some(text) => {
+                                if !message.is_empty() {
+                                    message.push(' ');
+                                }
+                                message.push_str(text);
+                            }
+                            None => break,
+                        }
+                    }
+                }
+            }
+
+            // Explicit markers win over keyword inference
+            let priority = marker_priority
+                .or_else(|| infer_priority(&message, &options.priority_keywords))
+                .unwrap_or(Priority::Normal);
+
+            let issue_ref = extract_issue_ref(&message, &options.issue_patterns);
+            let mentions = if options.mentions {
+                extract_mentions(&message)
+            } else {
+                Vec::new()
+            };
+            let before_tag = &line[..tag_match.start()];
+            let column = if options.utf16_columns {
+                before_tag.encode_utf16().count()
+            } else {
+                before_tag.len()
+            } + 1;
 
             let item = TodoItem {
                 file: file_path.to_string(),
                 line: line_idx + 1,
+                column,
                 tag,
                 message,
                 author,
                 issue_ref,
                 priority,
                 deadline,
+                relative_deadline,
+                checked,
+                escalate_to,
+                mentions,
             };
 
             if is_suppressed {
@@ -234,14 +945,32 @@ pub fn scan_content(content: &str, file_path: &str, pattern: &Regex) -> ScanCont
     }
 }
 
+/// Directory walker shared by both scan paths. `.todo-scan-ignore` files are
+/// always honored; git ignore rules only while `[scan] respect_gitignore` is on.
+fn walk_builder(root: &Path, config: &Config) -> WalkBuilder {
+    let respect = config.respect_gitignore();
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .add_custom_ignore_filename(IGNORE_FILENAME)
+        .git_ignore(respect)
+        .git_global(respect)
+        .git_exclude(respect)
+        .ignore(respect)
+        .threads(config.threads());
+    builder
+}
+
 /// Walk a directory tree and scan all files for TODO-style comments.
 ///
-/// Respects `.gitignore` via `ignore::WalkBuilder`. Applies the exclude
+/// Respects `.gitignore` (unless `[scan] respect_gitignore = false`) and
+/// `.todo-scan-ignore` via `ignore::WalkBuilder`. Applies the exclude
 /// directories and exclude patterns from `Config`. Returns a `ScanResult`
 /// with every matched item and the total number of files scanned.
 pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
     let pattern_str = config.tags_pattern();
-    let pattern = Regex::new(&pattern_str)?;
+    let pattern = Regex::new(&pattern_str).map_err(|e| Error::regex(&pattern_str, e))?;
+    let options = ScanOptions::from_config(config);
+    let max_file_size = config.max_file_size();
 
     let exclude_regexes: Vec<Regex> = config
         .exclude_patterns
@@ -251,20 +980,21 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
 
     let items = Arc::new(Mutex::new(Vec::new()));
     let ignored_items = Arc::new(Mutex::new(Vec::new()));
-    let files_scanned = Arc::new(AtomicUsize::new(0));
-    let exclude_dirs = Arc::new(config.exclude_dirs.clone());
+    let scanned_files = Arc::new(Mutex::new(Vec::new()));
+    let exclude_dirs = Arc::new(ExcludeDirs::new(&config.exclude_dirs));
     let exclude_regexes = Arc::new(exclude_regexes);
     let root = root.to_path_buf();
 
-    let walker = WalkBuilder::new(&root).build_parallel();
+    let walker = walk_builder(&root, config).build_parallel();
 
     walker.run(|| {
         let items = Arc::clone(&items);
         let ignored_items = Arc::clone(&ignored_items);
-        let files_scanned = Arc::clone(&files_scanned);
+        let scanned_files = Arc::clone(&scanned_files);
         let exclude_dirs = Arc::clone(&exclude_dirs);
         let exclude_regexes = Arc::clone(&exclude_regexes);
         let pattern = pattern.clone();
+        let options = options.clone();
         let root = root.clone();
 
         Box::new(move |entry| {
@@ -280,11 +1010,7 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
             }
 
             // Check exclude_dirs
-            let should_exclude_dir = exclude_dirs.iter().any(|dir| {
-                path.components()
-                    .any(|c| c.as_os_str().to_str().map(|s| s == dir).unwrap_or(false))
-            });
-            if should_exclude_dir {
+            if exclude_dirs.is_excluded(path.strip_prefix(&root).unwrap_or(path)) {
                 return WalkState::Continue;
             }
 
@@ -297,15 +1023,15 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
 
             // Skip oversized files to prevent OOM
             if let Ok(meta) = path.metadata() {
-                if should_skip_file(&meta, MAX_FILE_SIZE) {
+                if should_skip_file(&meta, max_file_size) {
                     return WalkState::Continue;
                 }
             }
 
             // Read the file; skip binary or unreadable files
-            let content = match std::fs::read_to_string(path) {
-                Ok(c) => c,
-                Err(_) => return WalkState::Continue,
+            let content = match read_text_file(path) {
+                Some(c) => c,
+                None => return WalkState::Continue,
             };
 
             let relative_path = path
@@ -314,7 +1040,7 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
                 .to_string_lossy()
                 .to_string();
 
-            let result = scan_content(&content, &relative_path, &pattern);
+            let result = scan_content(&content, &relative_path, &pattern, &options);
             if !result.items.is_empty() {
                 items
                     .lock()
@@ -327,7 +1053,11 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
                     .expect("scan thread panicked")
                     .extend(result.ignored_items);
             }
-            files_scanned.fetch_add(1, Ordering::Relaxed);
+            scanned_files
+                .lock()
+                .expect("scan thread panicked")
+                .push(relative_path);
+            FILES_PROGRESS.fetch_add(1, Ordering::Relaxed);
 
             WalkState::Continue
         })
@@ -341,187 +1071,490 @@ pub fn scan_directory(root: &Path, config: &Config) -> Result<ScanResult> {
         .expect("all walker threads should have finished")
         .into_inner()
         .unwrap();
-    let files_scanned = files_scanned.load(Ordering::Relaxed);
+    let mut scanned_files = Arc::try_unwrap(scanned_files)
+        .expect("all walker threads should have finished")
+        .into_inner()
+        .unwrap();
+    scanned_files.sort();
 
     Ok(ScanResult {
         items,
         ignored_items,
-        files_scanned,
+        files_scanned: scanned_files.len(),
+        scanned_files,
     })
 }
 
-/// Result of a cached scan, wrapping ScanResult with cache statistics.
-pub struct CachedScanResult {
-    pub result: ScanResult,
-    #[allow(dead_code)]
-    pub cache_hits: usize,
-    #[allow(dead_code)]
-    pub cache_misses: usize,
-}
-
-/// Scan a directory using a two-layer cache (mtime + content hash).
+/// Walk `root` one file at a time and hand each file's items to `emit`
+/// before reading the next, so nothing accumulates however large the tree.
 ///
-/// Uses sequential walk because the cache requires `&mut ScanCache`.
-/// When cache is warm, most files are skipped via mtime check (no file I/O),
-/// so parallelism provides diminishing returns.
-pub fn scan_directory_cached(
+/// Applies the same ignore rules, excludes, size limit and binary detection
+/// as `scan_directory`, but walks on one thread, in file-name order within
+/// each directory, so the order of `emit` calls is stable. Files without
+/// TODOs are not passed to `emit`; ignored items are dropped.
+pub fn scan_directory_each<E: From<Error>>(
     root: &Path,
     config: &Config,
-    cache: &mut ScanCache,
-) -> Result<CachedScanResult> {
+    mut emit: impl FnMut(Vec<TodoItem>) -> std::result::Result<(), E>,
+) -> std::result::Result<(), E> {
     let pattern_str = config.tags_pattern();
-    let pattern = Regex::new(&pattern_str)?;
-
+    let pattern = Regex::new(&pattern_str).map_err(|e| Error::regex(&pattern_str, e))?;
+    let options = ScanOptions::from_config(config);
+    let max_file_size = config.max_file_size();
+    let exclude_dirs = ExcludeDirs::new(&config.exclude_dirs);
     let exclude_regexes: Vec<Regex> = config
         .exclude_patterns
         .iter()
         .filter_map(|p| Regex::new(p).ok())
         .collect();
 
-    let mut items = Vec::new();
-    let mut ignored_items = Vec::new();
-    let mut files_scanned: usize = 0;
-    let mut cache_hits: usize = 0;
-    let mut cache_misses: usize = 0;
-    let mut seen_paths = HashSet::new();
-
-    let walker = WalkBuilder::new(root).build();
-
-    for entry in walker {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
+    let mut builder = walk_builder(root, config);
+    builder.sort_by_file_name(|a, b| a.cmp(b));
+    for entry in builder.build().flatten() {
         let path = entry.path();
-
         if !path.is_file() {
             continue;
         }
-
-        // Check exclude_dirs
-        let should_exclude_dir = config.exclude_dirs.iter().any(|dir| {
-            path.components()
-                .any(|c| c.as_os_str().to_str().map(|s| s == dir).unwrap_or(false))
-        });
-        if should_exclude_dir {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        if exclude_dirs.is_excluded(relative) {
             continue;
         }
-
-        // Check exclude_patterns
         let path_str = path.to_string_lossy();
-        let should_exclude_pattern = exclude_regexes.iter().any(|re| re.is_match(&path_str));
-        if should_exclude_pattern {
+        if exclude_regexes.iter().any(|re| re.is_match(&path_str)) {
             continue;
         }
-
-        let relative_path = path.strip_prefix(root).unwrap_or(path).to_path_buf();
-
-        seen_paths.insert(relative_path.clone());
-
-        // Check file metadata; skip oversized files
-        let metadata = match path.metadata() {
-            Ok(m) => m,
-            Err(_) => continue,
-        };
-        if should_skip_file(&metadata, MAX_FILE_SIZE) {
+        if let Ok(meta) = path.metadata() {
+            if should_skip_file(&meta, max_file_size) {
+                continue;
+            }
+        }
+        let Some(content) = read_text_file(path) else {
             continue;
+        };
+        let relative_path = relative.to_string_lossy().to_string();
+        let result = scan_content(&content, &relative_path, &pattern, &options);
+        FILES_PROGRESS.fetch_add(1, Ordering::Relaxed);
+        if !result.items.is_empty() {
+            emit(result.items)?;
         }
+    }
+    Ok(())
+}
 
-        // Layer 1: mtime check
-        let mtime = metadata
-            .modified()
-            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+/// Scan only the given files and directories instead of walking all of `root`.
+///
+/// Paths are opened as given (relative ones resolve against the current
+/// directory) and reported relative to `root` when they lie inside it.
+/// Directories are walked like `scan_directory`; files named directly are
+/// scanned even if git would ignore them. Exclude directories, exclude
+/// patterns, the size limit and binary detection still apply. Paths that do
+/// not exist are skipped.
+pub fn scan_paths(root: &Path, paths: &[PathBuf], config: &Config) -> Result<ScanResult> {
+    let pattern_str = config.tags_pattern();
+    let pattern = Regex::new(&pattern_str).map_err(|e| Error::regex(&pattern_str, e))?;
+    let options = ScanOptions::from_config(config);
+    let max_file_size = config.max_file_size();
+    let exclude_dirs = ExcludeDirs::new(&config.exclude_dirs);
+    let exclude_regexes: Vec<Regex> = config
+        .exclude_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
 
-        if let Some(cached) = cache.check(&relative_path, mtime) {
-            items.extend(cached.items.iter().cloned());
-            ignored_items.extend(cached.ignored_items.iter().cloned());
-            files_scanned += 1;
-            cache_hits += 1;
+    // Expand directories into their files, keeping each file's display path
+    let mut files: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for path in paths {
+        let Ok(canonical) = path.canonicalize() else {
             continue;
+        };
+        let relative = |file: &Path, fallback: &Path| -> PathBuf {
+            file.strip_prefix(&canonical_root)
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|_| fallback.to_path_buf())
+        };
+        if canonical.is_dir() {
+            for entry in walk_builder(&canonical, config).build().flatten() {
+                let file = entry.path();
+                if file.is_file() {
+                    let fallback = path.join(file.strip_prefix(&canonical).unwrap_or(file));
+                    files.push((file.to_path_buf(), relative(file, &fallback)));
+                }
+            }
+        } else if canonical.is_file() {
+            files.push((canonical.clone(), relative(&canonical, path)));
         }
+    }
 
-        // Read file content
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => {
-                files_scanned += 1;
+    let mut seen = HashSet::new();
+    let mut result = ScanResult {
+        items: Vec::new(),
+        ignored_items: Vec::new(),
+        files_scanned: 0,
+        scanned_files: Vec::new(),
+    };
+    for (path, relative) in files {
+        if !seen.insert(relative.clone()) || exclude_dirs.is_excluded(&relative) {
+            continue;
+        }
+        let path_str = path.to_string_lossy();
+        if exclude_regexes.iter().any(|re| re.is_match(&path_str)) {
+            continue;
+        }
+        if let Ok(meta) = path.metadata() {
+            if should_skip_file(&meta, max_file_size) {
                 continue;
             }
-        };
-
-        // Layer 2: content hash check
-        let content_bytes = content.as_bytes();
-        if let Some(cached) = cache.check_with_content(&relative_path, content_bytes) {
-            // Content unchanged (mtime was different, e.g. touched file)
-            // Clone first to release the immutable borrow on cache
-            let cloned_items: Vec<TodoItem> = cached.items.to_vec();
-            let cloned_ignored: Vec<TodoItem> = cached.ignored_items.to_vec();
-            // Update mtime in cache so next time layer 1 hits
-            let content_hash = *blake3::hash(content_bytes).as_bytes();
-            cache.insert(
-                relative_path.clone(),
-                content_hash,
-                cloned_items.clone(),
-                cloned_ignored.clone(),
-                mtime,
-            );
-            items.extend(cloned_items);
-            ignored_items.extend(cloned_ignored);
-            files_scanned += 1;
-            cache_hits += 1;
-            continue;
         }
+        let Some(content) = read_text_file(&path) else {
+            continue;
+        };
+        let relative_path = relative.to_string_lossy().to_string();
+        let scanned = scan_content(&content, &relative_path, &pattern, &options);
+        result.items.extend(scanned.items);
+        result.ignored_items.extend(scanned.ignored_items);
+        result.scanned_files.push(relative_path);
+        FILES_PROGRESS.fetch_add(1, Ordering::Relaxed);
+    }
+    result.scanned_files.sort();
+    result.files_scanned = result.scanned_files.len();
+    Ok(result)
+}
 
-        // Cache miss: full scan
-        let relative_str = relative_path.to_string_lossy().to_string();
-        let result = scan_content(&content, &relative_str, &pattern);
-        let content_hash = *blake3::hash(content_bytes).as_bytes();
-        cache.insert(
-            relative_path,
-            content_hash,
-            result.items.clone(),
-            result.ignored_items.clone(),
-            mtime,
-        );
-        items.extend(result.items);
-        ignored_items.extend(result.ignored_items);
-        files_scanned += 1;
-        cache_misses += 1;
-    }
-
-    // Prune deleted files
-    cache.prune(&seen_paths);
+/// Result of a cached scan, wrapping ScanResult with cache statistics.
+pub struct CachedScanResult {
+    pub result: ScanResult,
+    #[allow(dead_code)]
+    pub cache_hits: usize,
+    #[allow(dead_code)]
+    pub cache_misses: usize,
+}
 
-    Ok(CachedScanResult {
-        result: ScanResult {
-            items,
-            ignored_items,
-            files_scanned,
-        },
-        cache_hits,
-        cache_misses,
-    })
+/// Per-file outcome of the parallel cached walk, merged into the cache
+/// after the walk so workers only need shared read access.
+enum CachedFileScan {
+    /// Layer 1 or layer 2 hit; `refresh` carries the entry to re-insert
+    /// with the new mtime when only the content hash matched.
+    Hit {
+        items: Vec<TodoItem>,
+        ignored_items: Vec<TodoItem>,
+        refresh: Option<CacheUpdate>,
+    },
+    /// Cache miss: freshly scanned, to be inserted.
+    Miss(CacheUpdate),
+    /// Unreadable (binary or no usable encoding); not counted as scanned.
+    Unreadable,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+struct CacheUpdate {
+    path: PathBuf,
+    content_hash: [u8; 32],
+    items: Vec<TodoItem>,
+    ignored_items: Vec<TodoItem>,
+    mtime: SystemTime,
+}
 
-    fn default_pattern() -> Regex {
-        let config = Config::default();
-        Regex::new(&config.tags_pattern()).unwrap()
+/// Prepend `prefix` (e.g. `"crates/core/"`) to every file path in a scan
+/// result, re-basing paths from the scan root onto an enclosing directory.
+pub fn rebase_paths(result: &mut ScanResult, prefix: &str) {
+    if prefix.is_empty() {
+        return;
     }
+    for item in result
+        .items
+        .iter_mut()
+        .chain(result.ignored_items.iter_mut())
+    {
+        item.file = format!("{}{}", prefix, item.file);
+    }
+    for file in &mut result.scanned_files {
+        *file = format!("{}{}", prefix, file);
+    }
+}
 
-    #[test]
-    fn test_basic_todo_detection() {
-        let pattern = default_pattern();
-        let content = "// TODO: implement this feature\n";
-        let result = scan_content(content, "test.rs", &pattern);
-
-        assert_eq!(result.items.len(), 1);
-        assert_eq!(result.items[0].tag, Tag::Todo);
-        assert_eq!(result.items[0].message, "implement this feature");
+/// Scan a directory using a two-layer cache (mtime + content hash).
+///
+/// Walks in parallel like `scan_directory`. Workers only read the cache;
+/// per-file outcomes are collected and merged into the cache afterwards,
+/// followed by pruning entries for files no longer present.
+pub fn scan_directory_cached(
+    root: &Path,
+    config: &Config,
+    cache: &mut ScanCache,
+) -> Result<CachedScanResult> {
+    let pattern_str = config.tags_pattern();
+    let pattern = Regex::new(&pattern_str).map_err(|e| Error::regex(&pattern_str, e))?;
+    let options = ScanOptions::from_config(config);
+    let max_file_size = config.max_file_size();
+
+    let exclude_regexes: Vec<Regex> = config
+        .exclude_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+
+    let exclude_dirs = ExcludeDirs::new(&config.exclude_dirs);
+    let outcomes = Mutex::new(Vec::new());
+    let seen_paths = Mutex::new(HashSet::new());
+
+    let walker = walk_builder(root, config).build_parallel();
+
+    {
+        let cache: &ScanCache = cache;
+        walker.run(|| {
+            let outcomes = &outcomes;
+            let seen_paths = &seen_paths;
+            let exclude_dirs = &exclude_dirs;
+            let exclude_regexes = &exclude_regexes;
+            let pattern = &pattern;
+            let options = &options;
+
+            Box::new(move |entry| {
+                let entry = match entry {
+                    Ok(e) => e,
+                    Err(_) => return WalkState::Continue,
+                };
+
+                let path = entry.path();
+
+                if !path.is_file() {
+                    return WalkState::Continue;
+                }
+
+                // Check exclude_dirs
+                if exclude_dirs.is_excluded(path.strip_prefix(root).unwrap_or(path)) {
+                    return WalkState::Continue;
+                }
+
+                // Check exclude_patterns
+                let path_str = path.to_string_lossy();
+                let should_exclude_pattern =
+                    exclude_regexes.iter().any(|re| re.is_match(&path_str));
+                if should_exclude_pattern {
+                    return WalkState::Continue;
+                }
+
+                let relative_path = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+
+                seen_paths
+                    .lock()
+                    .expect("scan thread panicked")
+                    .insert(relative_path.clone());
+
+                // Check file metadata; skip oversized files
+                let metadata = match path.metadata() {
+                    Ok(m) => m,
+                    Err(_) => return WalkState::Continue,
+                };
+                if should_skip_file(&metadata, max_file_size) {
+                    return WalkState::Continue;
+                }
+
+                let file = relative_path.to_string_lossy().to_string();
+                let outcome =
+                    scan_file_cached(path, relative_path, &metadata, cache, pattern, options);
+                outcomes
+                    .lock()
+                    .expect("scan thread panicked")
+                    .push((file, outcome));
+                FILES_PROGRESS.fetch_add(1, Ordering::Relaxed);
+
+                WalkState::Continue
+            })
+        });
+    }
+
+    let mut items = Vec::new();
+    let mut ignored_items = Vec::new();
+    let mut scanned_files = Vec::new();
+    let mut cache_hits: usize = 0;
+    let mut cache_misses: usize = 0;
+
+    let insert = |cache: &mut ScanCache, update: CacheUpdate| {
+        cache.insert(
+            update.path,
+            update.content_hash,
+            update.items,
+            update.ignored_items,
+            update.mtime,
+        );
+    };
+
+    for (file, outcome) in outcomes.into_inner().expect("scan thread panicked") {
+        // Unreadable files are skipped, as `scan_directory` does
+        if !matches!(outcome, CachedFileScan::Unreadable) {
+            scanned_files.push(file);
+        }
+        match outcome {
+            CachedFileScan::Hit {
+                items: hit_items,
+                ignored_items: hit_ignored,
+                refresh,
+            } => {
+                if let Some(update) = refresh {
+                    insert(cache, update);
+                }
+                items.extend(hit_items);
+                ignored_items.extend(hit_ignored);
+                cache_hits += 1;
+            }
+            CachedFileScan::Miss(update) => {
+                items.extend(update.items.iter().cloned());
+                ignored_items.extend(update.ignored_items.iter().cloned());
+                insert(cache, update);
+                cache_misses += 1;
+            }
+            CachedFileScan::Unreadable => {}
+        }
+    }
+
+    // Prune deleted files
+    cache.prune(&seen_paths.into_inner().expect("scan thread panicked"));
+    scanned_files.sort();
+
+    Ok(CachedScanResult {
+        result: ScanResult {
+            items,
+            ignored_items,
+            files_scanned: scanned_files.len(),
+            scanned_files,
+        },
+        cache_hits,
+        cache_misses,
+    })
+}
+
+/// Scan a single file through the cache and record the outcome in it.
+///
+/// Used by `watch` to rescan only the files reported by filesystem events.
+/// Returns `None` if the file cannot be read as text.
+pub fn scan_file_with_cache(
+    path: &Path,
+    relative_path: &Path,
+    metadata: &std::fs::Metadata,
+    cache: &mut ScanCache,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> Option<Vec<TodoItem>> {
+    let outcome = scan_file_cached(
+        path,
+        relative_path.to_path_buf(),
+        metadata,
+        cache,
+        pattern,
+        options,
+    );
+    let (items, update) = match outcome {
+        CachedFileScan::Hit { items, refresh, .. } => (items, refresh),
+        CachedFileScan::Miss(update) => (update.items.clone(), Some(update)),
+        CachedFileScan::Unreadable => return None,
+    };
+    if let Some(update) = update {
+        cache.insert(
+            update.path,
+            update.content_hash,
+            update.items,
+            update.ignored_items,
+            update.mtime,
+        );
+    }
+    Some(items)
+}
+
+/// Resolve one file against the cache without mutating it.
+fn scan_file_cached(
+    path: &Path,
+    relative_path: PathBuf,
+    metadata: &std::fs::Metadata,
+    cache: &ScanCache,
+    pattern: &Regex,
+    options: &ScanOptions,
+) -> CachedFileScan {
+    // Layer 1: mtime check
+    let mtime = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+    if let Some(cached) = cache.check(&relative_path, mtime) {
+        return CachedFileScan::Hit {
+            items: cached.items.to_vec(),
+            ignored_items: cached.ignored_items.to_vec(),
+            refresh: None,
+        };
+    }
+
+    // Read file content
+    let content = match read_text_file(path) {
+        Some(c) => c,
+        None => return CachedFileScan::Unreadable,
+    };
+
+    // Layer 2: content hash check
+    let content_bytes = content.as_bytes();
+    let content_hash = *blake3::hash(content_bytes).as_bytes();
+    if let Some(cached) = cache.check_with_content(&relative_path, content_bytes) {
+        // Content unchanged (mtime was different, e.g. touched file);
+        // refresh the mtime so next time layer 1 hits
+        let items = cached.items.to_vec();
+        let ignored_items = cached.ignored_items.to_vec();
+        return CachedFileScan::Hit {
+            refresh: Some(CacheUpdate {
+                path: relative_path,
+                content_hash,
+                items: items.clone(),
+                ignored_items: ignored_items.clone(),
+                mtime,
+            }),
+            items,
+            ignored_items,
+        };
+    }
+
+    // Cache miss: full scan
+    let relative_str = relative_path.to_string_lossy().to_string();
+    let result = scan_content(&content, &relative_str, pattern, options);
+    CachedFileScan::Miss(CacheUpdate {
+        path: relative_path,
+        content_hash,
+        items: result.items,
+        ignored_items: result.ignored_items,
+        mtime,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Tag;
+
+    fn default_pattern() -> Regex {
+        let config = Config::default();
+        Regex::new(&config.tags_pattern()).unwrap()
+    }
+
+    #[test]
+    fn test_relative_deadline_resolves_against_today() {
+        let pattern = default_pattern();
+        let content = "// TODO(alice, +14d): revisit\n// TODO(2025-06-01): fixed date\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        let relative = &result.items[0];
+        assert_eq!(relative.author.as_deref(), Some("alice"));
+        assert_eq!(relative.relative_deadline, Some(RelativeDeadline::Days(14)));
+        assert_eq!(
+            relative.deadline,
+            Some(RelativeDeadline::Days(14).resolve(&today()))
+        );
+        assert!(result.items[1].relative_deadline.is_none());
+    }
+
+    #[test]
+    fn test_basic_todo_detection() {
+        let pattern = default_pattern();
+        let content = "// TODO: implement this feature\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].tag, Tag::Todo);
+        assert_eq!(result.items[0].message, "implement this feature");
         assert_eq!(result.items[0].file, "test.rs");
         assert_eq!(result.items[0].line, 1);
         assert_eq!(result.items[0].priority, Priority::Normal);
@@ -532,7 +1565,7 @@ mod tests {
     fn test_fixme_with_author() {
         let pattern = default_pattern();
         let content = "// FIXME(alice): broken parsing logic\n";
-        let result = scan_content(content, "lib.rs", &pattern);
+        let result = scan_content(content, "lib.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Fixme);
@@ -544,7 +1577,7 @@ mod tests {
     fn test_priority_high() {
         let pattern = default_pattern();
         let content = "# TODO: ! fix memory leak\n";
-        let result = scan_content(content, "main.py", &pattern);
+        let result = scan_content(content, "main.py", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].priority, Priority::High);
@@ -554,18 +1587,87 @@ mod tests {
     fn test_priority_urgent() {
         let pattern = default_pattern();
         let content = "// BUG: !! crashes on empty input\n";
-        let result = scan_content(content, "app.rs", &pattern);
+        let result = scan_content(content, "app.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Bug);
         assert_eq!(result.items[0].priority, Priority::Urgent);
     }
 
+    fn keyword_options() -> ScanOptions {
+        let config: Config = toml::from_str(
+            r#"
+            [priority]
+            high = ["important"]
+            urgent = ["URGENT", "asap"]
+            "#,
+        )
+        .unwrap();
+        ScanOptions::from_config(&config)
+    }
+
+    #[test]
+    fn test_priority_inferred_from_keyword() {
+        let pattern = default_pattern();
+        let content = "// TODO: URGENT fix the leak\n// TODO: this is Important\n// TODO: plain\n";
+        let result = scan_content(content, "app.rs", &pattern, &keyword_options());
+
+        assert_eq!(result.items[0].priority, Priority::Urgent);
+        assert_eq!(result.items[1].priority, Priority::High);
+        assert_eq!(result.items[2].priority, Priority::Normal);
+    }
+
+    #[test]
+    fn test_priority_marker_without_keyword() {
+        let pattern = default_pattern();
+        let content = "// TODO: ! tidy up\n";
+        let result = scan_content(content, "app.rs", &pattern, &keyword_options());
+
+        assert_eq!(result.items[0].priority, Priority::High);
+    }
+
+    #[test]
+    fn test_priority_marker_wins_over_keyword() {
+        let pattern = default_pattern();
+        let content = "// TODO: ! urgent per the docs\n";
+        let result = scan_content(content, "app.rs", &pattern, &keyword_options());
+
+        assert_eq!(result.items[0].priority, Priority::High);
+    }
+
+    #[test]
+    fn test_priority_urgent_keyword_wins_over_high_keyword() {
+        let pattern = default_pattern();
+        let content = "// TODO: important and asap\n";
+        let result = scan_content(content, "app.rs", &pattern, &keyword_options());
+
+        assert_eq!(result.items[0].priority, Priority::Urgent);
+    }
+
+    #[test]
+    fn test_priority_keyword_ignores_substrings() {
+        let pattern = default_pattern();
+        let content =
+            "// TODO: nonurgent cleanup\n// TODO: urgently_needed flag\n// TODO: unimportant\n";
+        let result = scan_content(content, "app.rs", &pattern, &keyword_options());
+
+        assert!(result.items.iter().all(|i| i.priority == Priority::Normal));
+    }
+
+    #[test]
+    fn test_priority_keyword_at_punctuation_boundary() {
+        let pattern = default_pattern();
+        let content = "// TODO: (urgent) fix\n// TODO: asap.\n";
+        let result = scan_content(content, "app.rs", &pattern, &keyword_options());
+
+        assert!(result.items.iter().all(|i| i.priority == Priority::Urgent));
+    }
+
     #[test]
     fn test_issue_ref_hash() {
         let pattern = default_pattern();
         let content = "// TODO: fix layout issue #123\n";
-        let result = scan_content(content, "ui.rs", &pattern);
+        let result = scan_content(content, "ui.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].issue_ref.as_deref(), Some("#123"));
@@ -575,7 +1677,7 @@ mod tests {
     fn test_issue_ref_jira() {
         let pattern = default_pattern();
         let content = "// FIXME: address JIRA-456 regression\n";
-        let result = scan_content(content, "api.rs", &pattern);
+        let result = scan_content(content, "api.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].issue_ref.as_deref(), Some("JIRA-456"));
@@ -585,7 +1687,7 @@ mod tests {
     fn test_case_insensitivity() {
         let pattern = default_pattern();
         let content = "// todo: lowercase tag\n// Todo: mixed case\n// TODO: uppercase\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 3);
         for item in &result.items {
@@ -603,7 +1705,7 @@ fn foo() {}
 // HACK: workaround for upstream bug
 // NOTE: remember to update docs
 ";
-        let result = scan_content(content, "multi.rs", &pattern);
+        let result = scan_content(content, "multi.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 4);
         assert_eq!(result.items[0].tag, Tag::Todo);
@@ -623,7 +1725,7 @@ line three
 line four
 // FIXME: on line five
 ";
-        let result = scan_content(content, "lines.rs", &pattern);
+        let result = scan_content(content, "lines.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 2);
         assert_eq!(result.items[0].line, 2);
@@ -634,7 +1736,7 @@ line four
     fn test_xxx_tag() {
         let pattern = default_pattern();
         let content = "// XXX: dangerous code path\n";
-        let result = scan_content(content, "danger.rs", &pattern);
+        let result = scan_content(content, "danger.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Xxx);
@@ -644,7 +1746,7 @@ line four
     fn test_no_match_on_plain_text() {
         let pattern = default_pattern();
         let content = "This is just a regular comment with no tags.\n";
-        let result = scan_content(content, "plain.rs", &pattern);
+        let result = scan_content(content, "plain.rs", &pattern, &ScanOptions::default());
 
         assert!(result.items.is_empty());
     }
@@ -653,7 +1755,7 @@ line four
     fn test_author_with_special_chars() {
         let pattern = default_pattern();
         let content = "// TODO(user@domain.com): email-style author\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].author.as_deref(), Some("user@domain.com"));
@@ -661,12 +1763,12 @@ line four
 
     #[test]
     fn test_extract_issue_ref_function() {
-        assert_eq!(extract_issue_ref("fix #42"), Some("#42".to_string()));
+        assert_eq!(extract_issue_ref("fix #42", &[]), Some("#42".to_string()));
         assert_eq!(
-            extract_issue_ref("see PROJ-100"),
+            extract_issue_ref("see PROJ-100", &[]),
             Some("PROJ-100".to_string())
         );
-        assert_eq!(extract_issue_ref("no reference here"), None);
+        assert_eq!(extract_issue_ref("no reference here", &[]), None);
     }
 
     // --- False-positive rejection tests ---
@@ -675,7 +1777,7 @@ line four
     fn test_no_match_in_identifier() {
         let pattern = default_pattern();
         let content = "let service = TodoService::new();\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO inside identifier"
@@ -686,7 +1788,7 @@ line four
     fn test_no_match_in_camel_case() {
         let pattern = default_pattern();
         let content = "if isTodoCompleted() { return; }\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match Todo in camelCase"
@@ -697,7 +1799,7 @@ line four
     fn test_no_match_in_string_literal() {
         let pattern = default_pattern();
         let content = "let msg = \"TODO: not a real comment\";\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO inside string literal"
@@ -708,7 +1810,7 @@ line four
     fn test_no_match_in_plain_code() {
         let pattern = default_pattern();
         let content = "let todo_count = get_todos().len();\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match todo in variable name"
@@ -719,7 +1821,7 @@ line four
     fn test_no_match_enum_variant() {
         let pattern = default_pattern();
         let content = "enum State { Todo, InProgress, Done }\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match Todo enum variant"
@@ -730,7 +1832,7 @@ line four
     fn test_no_match_struct_name() {
         let pattern = default_pattern();
         let content = "struct TodoItem { title: String }\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match Todo in struct name"
@@ -741,7 +1843,7 @@ line four
     fn test_no_match_comment_prefix_in_string_literal() {
         let pattern = default_pattern();
         let content = r#"let s = "// TODO: not real";"#;
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO when // is inside a string literal"
@@ -752,7 +1854,7 @@ line four
     fn test_no_match_hash_prefix_in_string_literal() {
         let pattern = default_pattern();
         let content = r##"let s = "# TODO: not real";"##;
-        let result = scan_content(content, "test.py", &pattern);
+        let result = scan_content(content, "test.py", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO when # is inside a string literal"
@@ -763,7 +1865,7 @@ line four
     fn test_match_real_comment_after_quoted_prefix() {
         let pattern = default_pattern();
         let content = r#""//"; // TODO: fix this"#;
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(
             result.items.len(),
             1,
@@ -778,7 +1880,7 @@ line four
     fn test_comment_double_slash() {
         let pattern = default_pattern();
         let content = "// TODO: rust/js/c++ style comment\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -786,7 +1888,7 @@ line four
     fn test_comment_hash() {
         let pattern = default_pattern();
         let content = "# TODO: python/ruby/shell style comment\n";
-        let result = scan_content(content, "test.py", &pattern);
+        let result = scan_content(content, "test.py", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -794,7 +1896,7 @@ line four
     fn test_comment_block_start() {
         let pattern = default_pattern();
         let content = "/* TODO: c-style block comment */\n";
-        let result = scan_content(content, "test.c", &pattern);
+        let result = scan_content(content, "test.c", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -802,7 +1904,7 @@ line four
     fn test_comment_block_middle_star() {
         let pattern = default_pattern();
         let content = " * TODO: middle of block comment\n";
-        let result = scan_content(content, "test.java", &pattern);
+        let result = scan_content(content, "test.java", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -810,7 +1912,7 @@ line four
     fn test_comment_double_dash() {
         let pattern = default_pattern();
         let content = "-- TODO: sql/haskell style comment\n";
-        let result = scan_content(content, "test.sql", &pattern);
+        let result = scan_content(content, "test.sql", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -818,7 +1920,7 @@ line four
     fn test_comment_percent() {
         let pattern = default_pattern();
         let content = "% TODO: latex/erlang style comment\n";
-        let result = scan_content(content, "test.erl", &pattern);
+        let result = scan_content(content, "test.erl", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -826,7 +1928,7 @@ line four
     fn test_comment_html() {
         let pattern = default_pattern();
         let content = "<!-- TODO: html comment -->\n";
-        let result = scan_content(content, "test.html", &pattern);
+        let result = scan_content(content, "test.html", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -834,7 +1936,7 @@ line four
     fn test_comment_semicolon() {
         let pattern = default_pattern();
         let content = "; TODO: lisp/asm style comment\n";
-        let result = scan_content(content, "test.lisp", &pattern);
+        let result = scan_content(content, "test.lisp", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -842,7 +1944,7 @@ line four
     fn test_comment_ocaml_paren_star() {
         let pattern = default_pattern();
         let content = "(* TODO: ocaml/pascal style comment *)\n";
-        let result = scan_content(content, "test.ml", &pattern);
+        let result = scan_content(content, "test.ml", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
     }
 
@@ -850,15 +1952,166 @@ line four
     fn test_comment_haskell_brace_dash() {
         let pattern = default_pattern();
         let content = "{- TODO: haskell block comment -}\n";
-        let result = scan_content(content, "test.hs", &pattern);
+        let result = scan_content(content, "test.hs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items.len(), 1);
+    }
+
+    // --- per-language comment syntax ---
+
+    #[test]
+    fn test_comment_syntax_for_path() {
+        let for_path = CommentSyntax::for_path;
+        assert_eq!(for_path("src/main.rs"), C_STYLE);
+        assert_eq!(for_path("scripts/build.PY"), HASH);
+        assert_eq!(for_path("docker/Dockerfile"), HASH);
+        assert_eq!(for_path("web\\index.html"), MARKUP);
+        assert_eq!(for_path("docs/guide.md"), MARKDOWN);
+        assert_eq!(
+            for_path("notes.txt"),
+            CommentSyntax {
+                prefixes: COMMENT_PREFIXES,
+                line_start: TASK_LIST_PREFIXES,
+                task_lists: true,
+            }
+        );
+        assert_eq!(for_path("LICENSE"), CommentSyntax::ANY);
+    }
+
+    #[test]
+    fn test_comment_syntax_overrides_replace_every_type() {
+        let with_overrides =
+            |path| CommentSyntax::for_path(path).with_overrides(Some(&["REM "]), Some(&[]));
+        let bat = with_overrides("build.bat");
+        assert_eq!(bat.prefixes, &["REM "]);
+        assert!(bat.line_start.is_empty());
+        assert_eq!(with_overrides("main.py").prefixes, &["REM "]);
+        assert_eq!(with_overrides("main.rs").prefixes, &["REM "]);
+        // Task-list markers are kept
+        assert_eq!(with_overrides("notes.txt").line_start, TASK_LIST_PREFIXES);
+        assert!(with_overrides("notes.txt").task_lists);
+        assert!(is_in_comment("REM TODO: x", 4, &bat));
+        assert!(!is_in_comment("# TODO: x", 2, &bat));
+        assert!(!is_in_comment(" * TODO: x", 3, &bat));
+    }
+
+    #[test]
+    fn test_comment_syntax_without_overrides_is_built_in() {
+        let syntax = CommentSyntax::for_path("main.py").with_overrides(None, None);
+        assert_eq!(syntax, HASH);
+    }
+
+    // --- Markdown and plain-text task lists ---
+
+    #[test]
+    fn test_markdown_list_items_and_blockquotes() {
+        let pattern = default_pattern();
+        let content = "\
+# Design
+- [ ] TODO: wire up metrics
+- [x] TODO: add config
+* FIXME: bullet with star
+> FIXME: this section is wrong
+Plain TODO: prose is not a comment
+<!-- NOTE: html comment -->
+";
+        let result = scan_content(content, "docs/design.md", &pattern, &ScanOptions::default());
+        let found: Vec<(&str, Option<bool>)> = result
+            .items
+            .iter()
+            .map(|i| (i.message.as_str(), i.checked))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("wire up metrics", Some(false)),
+                ("add config", Some(true)),
+                ("bullet with star", None),
+                ("this section is wrong", None),
+                ("html comment -->", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_text_task_list() {
+        let pattern = default_pattern();
+        let content = "+ [X] TODO: done item\n- TODO: open item\n# TODO: hash still works\n";
+        let result = scan_content(content, "notes.txt", &pattern, &ScanOptions::default());
+        assert_eq!(result.items.len(), 3);
+        assert_eq!(result.items[0].checked, Some(true));
+        assert_eq!(result.items[1].checked, None);
+        assert_eq!(result.items[2].message, "hash still works");
+    }
+
+    #[test]
+    fn test_task_list_markers_do_not_apply_to_code_files() {
+        let pattern = default_pattern();
+        let content = "- [ ] TODO: not a comment\nx > TODO: not a comment\n";
+        let result = scan_content(content, "src/lib.rs", &pattern, &ScanOptions::default());
+        assert!(result.items.is_empty());
+
+        let result = scan_content(
+            "// - [ ] TODO: in comment\n",
+            "a.rs",
+            &pattern,
+            &ScanOptions::default(),
+        );
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].checked, None);
+    }
+
+    #[test]
+    fn test_checkbox_state() {
+        assert_eq!(checkbox_state("- [ ] "), Some(false));
+        assert_eq!(checkbox_state("  * [x] "), Some(true));
+        assert_eq!(checkbox_state("+ [X]"), Some(true));
+        assert_eq!(checkbox_state("- "), None);
+        assert_eq!(checkbox_state("-[ ] "), None);
+        assert_eq!(checkbox_state("> [ ] "), None);
+        assert_eq!(checkbox_state("- [ ] see "), None);
+    }
+
+    #[test]
+    fn test_hash_is_not_a_comment_in_rust() {
+        let pattern = default_pattern();
+        let content = "let s = r#\"raw\"#; TODO: not a comment\n// TODO: real\n";
+        let result = scan_content(content, "lib.rs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "real");
+    }
+
+    #[test]
+    fn test_double_slash_is_not_a_comment_in_python() {
+        let pattern = default_pattern();
+        let content = "half = n // 2  or TODO: not a comment\nx = n // 2  # TODO: tune\n";
+        let result = scan_content(content, "calc.py", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "tune");
+    }
+
+    #[test]
+    fn test_unknown_extension_uses_all_prefixes() {
+        let pattern = default_pattern();
+        let content = "# TODO: hash\n// TODO: slash\n-- TODO: dash\n";
+        let result = scan_content(content, "notes.unknown", &pattern, &ScanOptions::default());
+        assert_eq!(result.items.len(), 3);
+    }
+
+    #[test]
+    fn test_is_in_comment_respects_syntax() {
+        let line = "value # TODO: x";
+        assert!(is_in_comment(line, 8, &HASH));
+        assert!(!is_in_comment(line, 8, &C_STYLE));
+        assert!(!is_in_comment("  * TODO: x", 4, &HASH));
+        assert!(is_in_comment("  * TODO: x", 4, &C_STYLE));
     }
 
     #[test]
     fn test_indented_comment() {
         let pattern = default_pattern();
         let content = "    // TODO: indented with spaces\n\t# FIXME: indented with tab\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        // Mixed `//` and `#` prefixes: use a file type with the full prefix set
+        let result = scan_content(content, "test.txt", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 2);
     }
 
@@ -866,7 +2119,7 @@ line four
     fn test_inline_comment() {
         let pattern = default_pattern();
         let content = "let x = 42; // TODO: fix this value\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "fix this value");
     }
@@ -875,77 +2128,109 @@ line four
 
     #[test]
     fn test_is_in_comment_double_slash() {
-        assert!(is_in_comment("// TODO: test", 3));
+        assert!(is_in_comment("// TODO: test", 3, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_hash() {
-        assert!(is_in_comment("# TODO: test", 2));
+        assert!(is_in_comment("# TODO: test", 2, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_block_start() {
-        assert!(is_in_comment("/* TODO: test */", 3));
+        assert!(is_in_comment("/* TODO: test */", 3, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_star_line_start() {
-        assert!(is_in_comment(" * TODO: test", 3));
+        assert!(is_in_comment(" * TODO: test", 3, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_html() {
-        assert!(is_in_comment("<!-- TODO: test -->", 5));
+        assert!(is_in_comment("<!-- TODO: test -->", 5, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_inline() {
-        assert!(is_in_comment("let x = 1; // TODO: fix", 15));
+        assert!(is_in_comment(
+            "let x = 1; // TODO: fix",
+            15,
+            &CommentSyntax::ANY
+        ));
     }
 
     #[test]
     fn test_is_in_comment_false_for_code() {
-        assert!(!is_in_comment("let todo_count = 0;", 4));
+        assert!(!is_in_comment(
+            "let todo_count = 0;",
+            4,
+            &CommentSyntax::ANY
+        ));
     }
 
     #[test]
     fn test_is_in_comment_false_for_string() {
-        assert!(!is_in_comment("let s = \"TODO: test\";", 9));
+        assert!(!is_in_comment(
+            "let s = \"TODO: test\";",
+            9,
+            &CommentSyntax::ANY
+        ));
     }
 
     #[test]
     fn test_is_in_comment_false_for_identifier() {
-        assert!(!is_in_comment("TodoService::new()", 0));
+        assert!(!is_in_comment("TodoService::new()", 0, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_false_for_string_with_comment_prefix() {
         // "// TODO" inside a string literal should not be detected as a comment
-        assert!(!is_in_comment(r#"let s = "// TODO: test";"#, 12));
+        assert!(!is_in_comment(
+            r#"let s = "// TODO: test";"#,
+            12,
+            &CommentSyntax::ANY
+        ));
     }
 
     #[test]
     fn test_is_in_comment_inline_after_code() {
         // Real inline comment after code → should match
-        assert!(is_in_comment("let x = 1; // TODO: fix this", 15));
+        assert!(is_in_comment(
+            "let x = 1; // TODO: fix this",
+            15,
+            &CommentSyntax::ANY
+        ));
     }
 
     #[test]
     fn test_is_in_comment_false_for_string_with_hash_prefix() {
         // "# TODO" inside a string literal should not be detected as a comment
-        assert!(!is_in_comment(r##"let s = "# TODO: test";"##, 11));
+        assert!(!is_in_comment(
+            r##"let s = "# TODO: test";"##,
+            11,
+            &CommentSyntax::ANY
+        ));
     }
 
     #[test]
     fn test_is_in_comment_quoted_prefix_then_real_comment() {
         // "//"; // TODO — quoted prefix then real comment → should match
-        assert!(is_in_comment(r#""//"; // TODO: fix"#, 10));
+        assert!(is_in_comment(
+            r#""//"; // TODO: fix"#,
+            10,
+            &CommentSyntax::ANY
+        ));
     }
 
     #[test]
     fn test_is_in_comment_false_for_string_with_block_comment_prefix() {
         // "/* TODO" inside a string literal should not be detected as a comment
-        assert!(!is_in_comment(r#"let s = "/* TODO: test";"#, 12));
+        assert!(!is_in_comment(
+            r#"let s = "/* TODO: test";"#,
+            12,
+            &CommentSyntax::ANY
+        ));
     }
 
     // --- scan_directory() tests ---
@@ -1000,6 +2285,106 @@ line four
         assert_eq!(result.items[0].message, "keep this");
     }
 
+    #[test]
+    fn test_scan_directory_each_emits_one_file_at_a_time_in_name_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.rs"), "// TODO: b1\n// FIXME: b2\n").unwrap();
+        std::fs::write(dir.path().join("a.rs"), "// TODO: a1\n").unwrap();
+        std::fs::write(dir.path().join("empty.rs"), "fn main() {}\n").unwrap();
+        let vendor = dir.path().join("vendor");
+        std::fs::create_dir(&vendor).unwrap();
+        std::fs::write(vendor.join("c.rs"), "// TODO: skipped\n").unwrap();
+
+        let config = Config {
+            exclude_dirs: vec!["vendor".to_string()],
+            ..Config::default()
+        };
+        let mut batches: Vec<Vec<String>> = Vec::new();
+        scan_directory_each(dir.path(), &config, |items| -> Result<()> {
+            batches.push(items.into_iter().map(|i| i.message).collect());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(batches, vec![vec!["a1"], vec!["b1", "b2"]]);
+    }
+
+    #[test]
+    fn test_scan_directory_each_stops_at_emit_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "// TODO: a\n").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "// TODO: b\n").unwrap();
+
+        let mut calls = 0;
+        let result = scan_directory_each(dir.path(), &Config::default(), |_| {
+            calls += 1;
+            Err(Error::Invalid("stop".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_scan_paths_scans_only_given_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "// TODO: in a\n").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "// TODO: in b\n").unwrap();
+        let sub = dir.path().join("sub");
+        std::fs::create_dir(&sub).unwrap();
+        std::fs::write(sub.join("c.rs"), "// FIXME: in c\n").unwrap();
+        let vendor = dir.path().join("vendor");
+        std::fs::create_dir(&vendor).unwrap();
+        std::fs::write(vendor.join("d.rs"), "// TODO: in d\n").unwrap();
+
+        let config = Config {
+            exclude_dirs: vec!["vendor".to_string()],
+            ..Config::default()
+        };
+        let paths = vec![
+            dir.path().join("a.rs"),
+            sub.clone(),
+            vendor.join("d.rs"),
+            dir.path().join("missing.rs"),
+            dir.path().join("a.rs"),
+        ];
+        let result = scan_paths(dir.path(), &paths, &config).unwrap();
+
+        assert_eq!(result.scanned_files, vec!["a.rs", "sub/c.rs"]);
+        let mut messages: Vec<&str> = result.items.iter().map(|i| i.message.as_str()).collect();
+        messages.sort();
+        assert_eq!(messages, vec!["in a", "in c"]);
+    }
+
+    #[test]
+    fn test_scan_directory_respects_ignore_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("keep.rs"), "// TODO: keep this\n").unwrap();
+        std::fs::write(dir.path().join("gen.rs"), "// TODO: generated\n").unwrap();
+        std::fs::write(dir.path().join(IGNORE_FILENAME), "gen.rs\n").unwrap();
+        let fixtures = dir.path().join("fixtures");
+        std::fs::create_dir(&fixtures).unwrap();
+        std::fs::write(fixtures.join("snap.rs"), "// TODO: snapshot\n").unwrap();
+        std::fs::write(fixtures.join("real.rs"), "// TODO: real fixture\n").unwrap();
+        std::fs::write(fixtures.join(IGNORE_FILENAME), "snap.rs\n").unwrap();
+
+        let config = Config::default();
+        let mut messages: Vec<String> = scan_directory(dir.path(), &config)
+            .unwrap()
+            .items
+            .into_iter()
+            .map(|i| i.message)
+            .collect();
+        messages.sort();
+        assert_eq!(messages, vec!["keep this", "real fixture"]);
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cached = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        let mut messages: Vec<String> =
+            cached.result.items.into_iter().map(|i| i.message).collect();
+        messages.sort();
+        assert_eq!(messages, vec!["keep this", "real fixture"]);
+    }
+
     #[test]
     fn test_scan_directory_files_scanned_count() {
         let dir = tempfile::tempdir().unwrap();
@@ -1013,18 +2398,30 @@ line four
         assert_eq!(result.files_scanned, 2);
     }
 
+    #[test]
+    fn test_files_scanned_so_far_counts_scanned_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "// TODO: a\n").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "// TODO: b\n").unwrap();
+
+        let before = files_scanned_so_far();
+        scan_directory(dir.path(), &Config::default()).unwrap();
+        // Other tests scan concurrently, so only a lower bound holds
+        assert!(files_scanned_so_far() - before >= 2);
+    }
+
     // --- parse_paren_content tests ---
 
     #[test]
     fn test_parse_paren_author_only() {
-        let (author, deadline) = parse_paren_content("alice");
+        let (author, deadline, _) = parse_paren_content("alice");
         assert_eq!(author.as_deref(), Some("alice"));
         assert!(deadline.is_none());
     }
 
     #[test]
     fn test_parse_paren_date_only() {
-        let (author, deadline) = parse_paren_content("2025-06-01");
+        let (author, deadline, _) = parse_paren_content("2025-06-01");
         assert!(author.is_none());
         let d = deadline.unwrap();
         assert_eq!(d.year, 2025);
@@ -1034,7 +2431,7 @@ line four
 
     #[test]
     fn test_parse_paren_author_and_date() {
-        let (author, deadline) = parse_paren_content("alice, 2025-06-01");
+        let (author, deadline, _) = parse_paren_content("alice, 2025-06-01");
         assert_eq!(author.as_deref(), Some("alice"));
         let d = deadline.unwrap();
         assert_eq!(d.year, 2025);
@@ -1044,7 +2441,7 @@ line four
 
     #[test]
     fn test_parse_paren_quarter_format() {
-        let (author, deadline) = parse_paren_content("2025-Q2");
+        let (author, deadline, _) = parse_paren_content("2025-Q2");
         assert!(author.is_none());
         let d = deadline.unwrap();
         assert_eq!(d.year, 2025);
@@ -1054,7 +2451,7 @@ line four
 
     #[test]
     fn test_parse_paren_author_and_quarter() {
-        let (author, deadline) = parse_paren_content("bob, 2025-Q3");
+        let (author, deadline, _) = parse_paren_content("bob, 2025-Q3");
         assert_eq!(author.as_deref(), Some("bob"));
         let d = deadline.unwrap();
         assert_eq!(d.year, 2025);
@@ -1064,7 +2461,7 @@ line four
 
     #[test]
     fn test_parse_paren_empty() {
-        let (author, deadline) = parse_paren_content("");
+        let (author, deadline, _) = parse_paren_content("");
         assert!(author.is_none());
         assert!(deadline.is_none());
     }
@@ -1075,7 +2472,7 @@ line four
     fn test_scan_todo_with_date() {
         let pattern = default_pattern();
         let content = "// TODO(2025-06-01): finish this by June\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].tag, Tag::Todo);
@@ -1091,7 +2488,7 @@ line four
     fn test_scan_todo_with_author_and_date() {
         let pattern = default_pattern();
         let content = "// TODO(alice, 2025-06-01): finish this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].author.as_deref(), Some("alice"));
@@ -1105,7 +2502,7 @@ line four
     fn test_scan_todo_with_quarter() {
         let pattern = default_pattern();
         let content = "// TODO(2025-Q4): year-end cleanup\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert!(result.items[0].author.is_none());
@@ -1119,7 +2516,7 @@ line four
     fn test_scan_todo_author_only_still_works() {
         let pattern = default_pattern();
         let content = "// TODO(bob): no date here\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].author.as_deref(), Some("bob"));
@@ -1130,7 +2527,7 @@ line four
     fn test_scan_todo_no_parens_no_deadline() {
         let pattern = default_pattern();
         let content = "// TODO: plain task\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert!(result.items[0].author.is_none());
@@ -1143,7 +2540,7 @@ line four
     fn test_no_match_todox_in_comment() {
         let pattern = default_pattern();
         let content = "// todox report generates HTML\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO as prefix of 'todox'"
@@ -1154,7 +2551,7 @@ line four
     fn test_no_match_todo_scan_in_comment() {
         let pattern = default_pattern();
         let content = "// todo-scan report generates HTML\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO as prefix of 'todo-scan'"
@@ -1165,7 +2562,7 @@ line four
     fn test_no_match_todos_in_comment() {
         let pattern = default_pattern();
         let content = "// TODOS remaining in the backlog\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match TODO as prefix of 'TODOS'"
@@ -1176,7 +2573,7 @@ line four
     fn test_no_match_noted_in_comment() {
         let pattern = default_pattern();
         let content = "# NOTEd this for future reference\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match NOTE as prefix of 'NOTEd'"
@@ -1187,7 +2584,7 @@ line four
     fn test_no_match_fixme_suffix_in_comment() {
         let pattern = default_pattern();
         let content = "// FIXMEd the issue yesterday\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "should not match FIXME as prefix of 'FIXMEd'"
@@ -1200,7 +2597,7 @@ line four
     fn test_still_matches_todo_colon() {
         let pattern = default_pattern();
         let content = "// TODO: fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1, "TODO: should still match");
     }
 
@@ -1208,7 +2605,7 @@ line four
     fn test_still_matches_todo_paren() {
         let pattern = default_pattern();
         let content = "// TODO(alice): fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1, "TODO(author) should still match");
     }
 
@@ -1216,7 +2613,7 @@ line four
     fn test_still_matches_todo_space() {
         let pattern = default_pattern();
         let content = "// TODO fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(
             result.items.len(),
             1,
@@ -1228,7 +2625,7 @@ line four
     fn test_still_matches_todo_bang() {
         let pattern = default_pattern();
         let content = "// TODO! fix this\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1, "TODO! should still match");
     }
 
@@ -1273,6 +2670,29 @@ line four
         assert_eq!(result.cache_misses, 0);
     }
 
+    #[test]
+    fn test_cached_scan_many_files_across_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..200 {
+            let sub = dir.path().join(format!("mod_{}", i % 10));
+            std::fs::create_dir_all(&sub).unwrap();
+            std::fs::write(sub.join(format!("f{i}.rs")), format!("// TODO: task {i}\n")).unwrap();
+        }
+
+        let config = Config::default();
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+
+        let first = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(first.cache_misses, 200);
+        assert_eq!(first.result.files_scanned, 200);
+        assert_eq!(cache.entries.len(), 200);
+
+        let second = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(second.cache_hits, 200);
+        assert_eq!(second.cache_misses, 0);
+        assert_eq!(second.result.items.len(), 200);
+    }
+
     #[test]
     fn test_cached_scan_modified_file_detected() {
         let dir = tempfile::tempdir().unwrap();
@@ -1371,7 +2791,7 @@ line four
     fn test_ignore_inline_suppresses_item() {
         let pattern = default_pattern();
         let content = "// TODO: keep this\n// TODO: suppress this todo-scan:ignore\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "keep this");
@@ -1383,7 +2803,7 @@ line four
     fn test_ignore_next_line_suppresses_following_item() {
         let pattern = default_pattern();
         let content = "// todo-scan:ignore-next-line\n// TODO: suppressed by next-line\n// TODO: not suppressed\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "not suppressed");
@@ -1396,7 +2816,7 @@ line four
         let pattern = default_pattern();
         let content =
             "// todo-scan:ignore-next-line\n// TODO: suppressed\n// TODO: not suppressed\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "not suppressed");
@@ -1407,7 +2827,7 @@ line four
     fn test_ignore_next_line_blank_line_between_does_not_suppress() {
         let pattern = default_pattern();
         let content = "// todo-scan:ignore-next-line\n\n// TODO: should not be suppressed\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "should not be suppressed");
@@ -1424,7 +2844,7 @@ line four
 // HACK: normal hack
 // BUG: suppressed bug todo-scan:ignore
 ";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 2);
         assert_eq!(result.items[0].message, "normal item");
@@ -1435,6 +2855,134 @@ line four
         assert_eq!(result.ignored_items[1].message, "suppressed bug");
     }
 
+    #[test]
+    fn test_ignore_file_suppresses_every_item() {
+        let pattern = default_pattern();
+        let content = "// todo-scan:ignore-file
+// TODO: one
+fn x() {}
+// FIXME: two
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert!(result.items.is_empty());
+        assert_eq!(result.ignored_items.len(), 2);
+        assert_eq!(result.ignored_items[0].message, "one");
+    }
+
+    #[test]
+    fn test_ignore_file_marker_outside_comment_is_ignored() {
+        let pattern = default_pattern();
+        let content = "let s = \"todo-scan:ignore-file\";\n// TODO: still tracked\n";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert!(result.ignored_items.is_empty());
+    }
+
+    #[test]
+    fn test_ignore_begin_end_suppresses_range() {
+        let pattern = default_pattern();
+        let content = "\
+// TODO: before
+// todo-scan:ignore-begin
+// TODO: inside one
+// FIXME: inside two
+// todo-scan:ignore-end
+// TODO: after
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        let kept: Vec<&str> = result.items.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(kept, vec!["before", "after"]);
+        let ignored: Vec<&str> = result
+            .ignored_items
+            .iter()
+            .map(|i| i.message.as_str())
+            .collect();
+        assert_eq!(ignored, vec!["inside one", "inside two"]);
+    }
+
+    #[test]
+    fn test_ignore_markers_in_string_literals_are_ignored() {
+        let pattern = default_pattern();
+        let content = "\
+let begin = \"todo-scan:ignore-begin\";
+// TODO: still tracked
+let next = \"todo-scan:ignore-next-line\";
+// FIXME: also tracked
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        let kept: Vec<&str> = result.items.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(kept, vec!["still tracked", "also tracked"]);
+        assert!(result.ignored_items.is_empty());
+    }
+
+    #[test]
+    fn test_ignore_end_in_string_literal_does_not_close_range() {
+        let pattern = default_pattern();
+        let content = "\
+// todo-scan:ignore-begin
+let end = \"todo-scan:ignore-end\";
+// TODO: still ignored
+// todo-scan:ignore-end
+// TODO: after
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "after");
+        assert_eq!(result.ignored_items.len(), 1);
+    }
+
+    #[test]
+    fn test_ignore_begin_without_end_runs_to_eof() {
+        let pattern = default_pattern();
+        let content = "// TODO: kept
+// todo-scan:ignore-begin
+// TODO: a
+
+// TODO: b
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "kept");
+        assert_eq!(result.ignored_items.len(), 2);
+    }
+
+    #[test]
+    fn test_ignore_nested_ranges_merge() {
+        let pattern = default_pattern();
+        let content = "\
+// todo-scan:ignore-begin
+// todo-scan:ignore-begin
+// TODO: inner
+// todo-scan:ignore-end
+// TODO: still outer
+// todo-scan:ignore-end
+// todo-scan:ignore-end
+// TODO: visible
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "visible");
+        assert_eq!(result.ignored_items.len(), 2);
+    }
+
+    #[test]
+    fn test_ignore_range_markers_are_not_inline_ignores() {
+        let pattern = default_pattern();
+        let content = "// TODO: closes todo-scan:ignore-end
+";
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+
+        assert_eq!(result.items.len(), 1);
+        assert!(result.ignored_items.is_empty());
+    }
+
     // --- File size limit tests ---
 
     #[test]
@@ -1486,7 +3034,7 @@ line four
     fn test_ignore_no_items_affected_when_no_markers() {
         let pattern = default_pattern();
         let content = "// TODO: first\n// FIXME: second\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.items.len(), 2);
         assert!(result.ignored_items.is_empty());
@@ -1496,7 +3044,7 @@ line four
     fn test_ignore_strips_marker_from_message() {
         let pattern = default_pattern();
         let content = "// TODO: fix this todo-scan:ignore\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
 
         assert_eq!(result.ignored_items.len(), 1);
         assert_eq!(result.ignored_items[0].message, "fix this");
@@ -1525,7 +3073,7 @@ line four
     #[test]
     fn test_parse_paren_comma_empty_left_with_date_right() {
         // ", 2025-06-01" → (None, Some(deadline))
-        let (author, deadline) = parse_paren_content(", 2025-06-01");
+        let (author, deadline, _) = parse_paren_content(", 2025-06-01");
         assert!(author.is_none(), "empty left side should yield no author");
         let d = deadline.unwrap();
         assert_eq!(d.year, 2025);
@@ -1536,7 +3084,7 @@ line four
     #[test]
     fn test_parse_paren_comma_empty_right() {
         // "alice, " → author only, no deadline (right side is empty, not a date)
-        let (author, deadline) = parse_paren_content("alice, ");
+        let (author, deadline, _) = parse_paren_content("alice, ");
         // The right side is empty, so neither side is a date.
         // Since left is not a date and right is not a date, whole string is treated as author.
         // Actually let's trace the code: left="alice", right="" (trimmed).
@@ -1559,7 +3107,7 @@ line four
     #[test]
     fn test_parse_paren_comma_neither_side_is_date() {
         // "alice, bob" → (Some("alice, bob"), None)
-        let (author, deadline) = parse_paren_content("alice, bob");
+        let (author, deadline, _) = parse_paren_content("alice, bob");
         assert!(deadline.is_none());
         assert_eq!(
             author.as_deref(),
@@ -1571,7 +3119,7 @@ line four
     #[test]
     fn test_parse_paren_date_on_left_side() {
         // "2025-06-01, alice" → (Some("alice"), Some(deadline))
-        let (author, deadline) = parse_paren_content("2025-06-01, alice");
+        let (author, deadline, _) = parse_paren_content("2025-06-01, alice");
         assert_eq!(
             author.as_deref(),
             Some("alice"),
@@ -1586,7 +3134,7 @@ line four
     #[test]
     fn test_parse_paren_whitespace_only() {
         // "   " → (None, None) because trimmed is empty
-        let (author, deadline) = parse_paren_content("   ");
+        let (author, deadline, _) = parse_paren_content("   ");
         assert!(author.is_none());
         assert!(deadline.is_none());
     }
@@ -1594,7 +3142,7 @@ line four
     #[test]
     fn test_parse_paren_date_on_left_with_empty_right() {
         // "2025-06-01," → (None, Some(deadline)) because right side is empty after trim
-        let (author, deadline) = parse_paren_content("2025-06-01,");
+        let (author, deadline, _) = parse_paren_content("2025-06-01,");
         assert!(
             author.is_none(),
             "author should be None when right side is empty"
@@ -1643,38 +3191,83 @@ line four
         );
     }
 
+    // --- extract_issue_ref with configured patterns ---
+
+    #[test]
+    fn test_extract_issue_ref_configured_patterns_in_order() {
+        let patterns = vec![
+            Regex::new(r"https://linear\.app/\S+/issue/([A-Z]+-\d+)").unwrap(),
+            Regex::new(r"\bGH-\d+").unwrap(),
+        ];
+        assert_eq!(
+            extract_issue_ref(
+                "see https://linear.app/acme/issue/ENG-1234/fix-it and GH-5",
+                &patterns
+            ),
+            Some("ENG-1234".to_string())
+        );
+        // No capture group: the whole match is the reference
+        assert_eq!(
+            extract_issue_ref("blocked on GH-77", &patterns),
+            Some("GH-77".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_issue_ref_configured_patterns_replace_builtin() {
+        let patterns = vec![Regex::new(r"GH-(\d+)").unwrap()];
+        assert_eq!(extract_issue_ref("fix #42", &patterns), None);
+        assert_eq!(
+            extract_issue_ref("fix GH-42", &patterns),
+            Some("42".to_string())
+        );
+    }
+
+    #[test]
+    fn test_scan_content_uses_configured_issue_patterns() {
+        let config: Config = toml::from_str("[issue]\npatterns = ['\\b(GH-\\d+)']\n").unwrap();
+        let options = ScanOptions::from_config(&config);
+        let result = scan_content(
+            "// TODO: retry GH-9 and #3\n",
+            "a.rs",
+            &default_pattern(),
+            &options,
+        );
+        assert_eq!(result.items[0].issue_ref.as_deref(), Some("GH-9"));
+    }
+
     // --- extract_issue_ref edge cases ---
 
     #[test]
     fn test_extract_issue_ref_no_reference() {
-        assert_eq!(extract_issue_ref("just a plain message"), None);
+        assert_eq!(extract_issue_ref("just a plain message", &[]), None);
     }
 
     #[test]
     fn test_extract_issue_ref_both_jira_and_hash() {
         // When both JIRA-style and hash-style refs are present,
         // the regex should return the first match.
-        let result = extract_issue_ref("fix PROJ-42 and also #99");
+        let result = extract_issue_ref("fix PROJ-42 and also #99", &[]);
         // JIRA pattern matches first because the regex alternation tries JIRA first
         assert_eq!(result, Some("PROJ-42".to_string()));
     }
 
     #[test]
     fn test_extract_issue_ref_hash_only() {
-        assert_eq!(extract_issue_ref("see #7"), Some("#7".to_string()));
+        assert_eq!(extract_issue_ref("see #7", &[]), Some("#7".to_string()));
     }
 
     #[test]
     fn test_extract_issue_ref_jira_only() {
         assert_eq!(
-            extract_issue_ref("relates to ABC-1234"),
+            extract_issue_ref("relates to ABC-1234", &[]),
             Some("ABC-1234".to_string())
         );
     }
 
     #[test]
     fn test_extract_issue_ref_empty_string() {
-        assert_eq!(extract_issue_ref(""), None);
+        assert_eq!(extract_issue_ref("", &[]), None);
     }
 
     // --- prefix_outside_quotes edge cases ---
@@ -1714,19 +3307,19 @@ line four
     #[test]
     fn test_is_in_comment_star_at_line_start_with_whitespace() {
         // "   * TODO: test" — star at start after whitespace (Javadoc-style)
-        assert!(is_in_comment("   * TODO: test", 5));
+        assert!(is_in_comment("   * TODO: test", 5, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_star_not_at_line_start() {
         // "x * TODO: test" — star NOT at start of line (after non-whitespace)
-        assert!(!is_in_comment("x * TODO: test", 4));
+        assert!(!is_in_comment("x * TODO: test", 4, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_star_at_line_start_no_whitespace() {
         // "* TODO: test" — star right at position 0
-        assert!(is_in_comment("* TODO: test", 2));
+        assert!(is_in_comment("* TODO: test", 2, &CommentSyntax::ANY));
     }
 
     #[test]
@@ -1734,28 +3327,28 @@ line four
         // \" * TODO: test\" — star preceded by quote (inside string)
         // The star is at the line start after trim, but the leading_ws prefix
         // is inside quotes, so prefix_outside_quotes returns false
-        assert!(!is_in_comment("\" * TODO: test\"", 5));
+        assert!(!is_in_comment("\" * TODO: test\"", 5, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_tab_then_star() {
         // Tab + star is a line-start prefix pattern
-        assert!(is_in_comment("\t* TODO: test", 3));
+        assert!(is_in_comment("\t* TODO: test", 3, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_semicolon_prefix() {
-        assert!(is_in_comment("; TODO: test", 2));
+        assert!(is_in_comment("; TODO: test", 2, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_double_dash() {
-        assert!(is_in_comment("-- TODO: test", 3));
+        assert!(is_in_comment("-- TODO: test", 3, &CommentSyntax::ANY));
     }
 
     #[test]
     fn test_is_in_comment_no_comment_prefix_at_all() {
-        assert!(!is_in_comment("TODO: test", 0));
+        assert!(!is_in_comment("TODO: test", 0, &CommentSyntax::ANY));
     }
     // === Additional coverage tests ===
 
@@ -1764,7 +3357,7 @@ line four
     #[test]
     fn test_parse_paren_date_left_empty_right() {
         // "2025-06-01, " → date on left side, right trimmed to empty → (None, Some(deadline))
-        let (author, deadline) = parse_paren_content("2025-06-01, ");
+        let (author, deadline, _) = parse_paren_content("2025-06-01, ");
         assert!(author.is_none(), "empty right side should yield no author");
         let d = deadline.unwrap();
         assert_eq!(d.year, 2025);
@@ -1772,7 +3365,7 @@ line four
         assert_eq!(d.day, 1);
     }
 
-    // --- scan_content: unknown tag that regex matches but Tag::from_str rejects ---
+    // --- scan_content: unknown tag that regex matches but no tag style declares ---
 
     #[test]
     fn test_scan_content_unknown_tag_skipped() {
@@ -1783,8 +3376,8 @@ line four
         };
         let pattern = Regex::new(&config.tags_pattern()).unwrap();
         let content = "// WARN: this is a warning\n";
-        let result = scan_content(content, "test.rs", &pattern);
-        // WARN matches the regex but Tag::from_str("WARN") returns Err,
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
+        // WARN matches the regex but is neither built in nor under [custom_tags],
         // so the item should be skipped (not included in results)
         assert!(
             result.items.is_empty(),
@@ -1792,6 +3385,182 @@ line four
         );
     }
 
+    #[test]
+    fn test_scan_content_declared_custom_tag() {
+        let config: Config =
+            toml::from_str("[custom_tags.DEBT]\nseverity = 3\ncolor = \"magenta\"\n").unwrap();
+        let pattern = Regex::new(&config.tags_pattern()).unwrap();
+        let content = "// DEBT(alice): pay this down\n// debt: lowercase too\n";
+        let result = scan_content(
+            content,
+            "test.rs",
+            &pattern,
+            &ScanOptions::from_config(&config),
+        );
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].tag, Tag::Custom("DEBT".to_string()));
+        assert_eq!(result.items[0].author.as_deref(), Some("alice"));
+        assert_eq!(result.items[0].message, "pay this down");
+        assert_eq!(result.items[1].tag, Tag::Custom("DEBT".to_string()));
+    }
+
+    // --- multiline continuation ---
+
+    fn multiline_options() -> ScanOptions {
+        ScanOptions {
+            multiline: true,
+            ..ScanOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_multiline_appends_indented_continuation() {
+        let pattern = default_pattern();
+        let content = "\
+// TODO: refactor the auth flow
+//   because the session cache is wrong
+//   and tokens leak
+fn main() {}
+";
+        let result = scan_content(content, "auth.rs", &pattern, &multiline_options());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(
+            result.items[0].message,
+            "refactor the auth flow because the session cache is wrong and tokens leak"
+        );
+        assert_eq!(result.items[0].line, 1);
+    }
+
+    #[test]
+    fn test_multiline_disabled_by_default() {
+        let pattern = default_pattern();
+        let content = "// TODO: refactor\n//   because reasons\n";
+        let result = scan_content(content, "a.rs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items[0].message, "refactor");
+    }
+
+    #[test]
+    fn test_multiline_blank_line_ends_block() {
+        let pattern = default_pattern();
+        let content = "// TODO: first part\n\n//   unrelated comment\n";
+        let result = scan_content(content, "a.rs", &pattern, &multiline_options());
+        assert_eq!(result.items[0].message, "first part");
+    }
+
+    #[test]
+    fn test_multiline_new_tag_ends_block() {
+        let pattern = default_pattern();
+        let content = "// TODO: first\n//   FIXME: second\n//     more for second\n";
+        let result = scan_content(content, "a.rs", &pattern, &multiline_options());
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].message, "first");
+        assert_eq!(result.items[1].message, "second more for second");
+        assert_eq!(result.items[1].line, 2);
+    }
+
+    #[test]
+    fn test_multiline_requires_extra_indent() {
+        let pattern = default_pattern();
+        let content = "// TODO: first\n// a separate comment\n";
+        let result = scan_content(content, "a.rs", &pattern, &multiline_options());
+        assert_eq!(result.items[0].message, "first");
+    }
+
+    #[test]
+    fn test_multiline_requires_same_prefix() {
+        let pattern = default_pattern();
+        let content = "// TODO: first\n#   hash comment\n";
+        let result = scan_content(content, "a.rs", &pattern, &multiline_options());
+        assert_eq!(result.items[0].message, "first");
+    }
+
+    #[test]
+    fn test_multiline_block_comment_star_prefix() {
+        let pattern = default_pattern();
+        let content = "/**\n * TODO: handle errors\n *   from the network layer\n */\n";
+        let result = scan_content(content, "a.java", &pattern, &multiline_options());
+        assert_eq!(
+            result.items[0].message,
+            "handle errors from the network layer"
+        );
+    }
+
+    #[test]
+    fn test_multiline_ignores_string_literal_lines() {
+        let pattern = default_pattern();
+        let content = "// TODO: first\n\"//   not a comment\";\n";
+        let result = scan_content(content, "a.rs", &pattern, &multiline_options());
+        assert_eq!(result.items[0].message, "first");
+    }
+
+    #[test]
+    fn test_comment_prefix_before_picks_closest_prefix() {
+        assert_eq!(
+            comment_prefix_before("// TODO: x", 3, &CommentSyntax::ANY),
+            Some(("//", 0))
+        );
+        assert_eq!(
+            comment_prefix_before("let x = 1; # TODO: x", 13, &CommentSyntax::ANY),
+            Some(("#", 11))
+        );
+        assert_eq!(
+            comment_prefix_before(" * TODO: x", 3, &CommentSyntax::ANY),
+            Some(("*", 1))
+        );
+        assert_eq!(
+            comment_prefix_before("TODO: x", 0, &CommentSyntax::ANY),
+            None
+        );
+    }
+
+    #[test]
+    fn test_comment_prefixes_in_prefers_longest_prefix() {
+        let prefixes = CommentSyntax::ANY.prefixes;
+        assert_eq!(comment_prefixes_in("<!-- x", prefixes), vec![("<!--", 0)]);
+        assert_eq!(
+            comment_prefixes_in("<!-- a -- b", prefixes),
+            vec![("<!--", 0), ("--", 7)]
+        );
+        assert_eq!(
+            comment_prefixes_in("--<!-- x", prefixes),
+            vec![("--", 0), ("<!--", 2)]
+        );
+    }
+
+    #[test]
+    fn test_comment_prefix_before_treats_html_comment_as_unit() {
+        assert_eq!(
+            comment_prefix_before("<!-- TODO: x -->", 5, &CommentSyntax::ANY),
+            Some(("<!--", 0))
+        );
+        assert_eq!(
+            comment_prefix_before("<!-- note -- TODO: x", 13, &CommentSyntax::ANY),
+            Some(("--", 10))
+        );
+    }
+
+    #[test]
+    fn test_html_and_sql_comments_on_adjacent_lines() {
+        let pattern = default_pattern();
+        let content = "<!-- TODO: html -->\n-- TODO: sql\n<!-- x --> -- FIXME: mixed\n";
+        let result = scan_content(content, "notes.txt", &pattern, &ScanOptions::default());
+        let found: Vec<(usize, &str)> = result
+            .items
+            .iter()
+            .map(|i| (i.line, i.message.as_str()))
+            .collect();
+        assert_eq!(found, vec![(1, "html -->"), (2, "sql"), (3, "mixed")]);
+    }
+
+    #[test]
+    fn test_multiline_html_comment_continues_with_full_prefix() {
+        let pattern = default_pattern();
+        let content = "<!-- TODO: first\n<!--   second -->\n-- unrelated\n";
+        let result = scan_content(content, "notes.txt", &pattern, &multiline_options());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "first second -->");
+    }
+
     // --- scan_directory: exclude_patterns ---
 
     #[test]
@@ -1816,10 +3585,10 @@ line four
     fn test_scan_directory_skips_binary_files() {
         let dir = tempfile::tempdir().unwrap();
         std::fs::write(dir.path().join("good.rs"), "// TODO: keep\n").unwrap();
-        // Write binary content (invalid UTF-8)
+        // Write binary content (invalid UTF-8, mostly NUL bytes)
         std::fs::write(
             dir.path().join("binary.dat"),
-            &[0xFF, 0xFE, 0x00, 0x01, 0x80, 0x81],
+            [0x00, 0x01, 0x00, 0x00, 0xFF, 0x80, 0x00, 0x00],
         )
         .unwrap();
 
@@ -1829,10 +3598,119 @@ line four
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "keep");
         // files_scanned should only count the readable file
-        // (binary file fails read_to_string and is skipped)
+        // (binary file fails read_text_file and is skipped)
         assert_eq!(result.files_scanned, 1);
     }
 
+    // --- Encoding fallback ---
+
+    fn utf16le(text: &str, bom: bool) -> Vec<u8> {
+        let mut bytes = if bom { vec![0xFF, 0xFE] } else { Vec::new() };
+        bytes.extend(text.encode_utf16().flat_map(|u| u.to_le_bytes()));
+        bytes
+    }
+
+    #[test]
+    fn test_decode_text_utf8_passthrough() {
+        assert_eq!(
+            decode_text(b"// TODO: a\n".to_vec()).unwrap(),
+            "// TODO: a\n"
+        );
+    }
+
+    #[test]
+    fn test_decode_text_utf16le_with_bom() {
+        let text = decode_text(utf16le("// TODO: é\r\n", true)).unwrap();
+        assert_eq!(text, "// TODO: é\r\n");
+    }
+
+    #[test]
+    fn test_decode_text_utf16_without_bom() {
+        let le = decode_text(utf16le("x = 1 // TODO: sniffed\n", false)).unwrap();
+        assert_eq!(le, "x = 1 // TODO: sniffed\n");
+
+        let be: Vec<u8> = "// FIXME: big\n"
+            .encode_utf16()
+            .flat_map(|u| u.to_be_bytes())
+            .collect();
+        assert_eq!(decode_text(be).unwrap(), "// FIXME: big\n");
+    }
+
+    #[test]
+    fn test_decode_text_latin1_fallback() {
+        let text = decode_text(b"# TODO: caf\xe9 men\xfc\n".to_vec()).unwrap();
+        assert_eq!(text, "# TODO: café menü\n");
+    }
+
+    #[test]
+    fn test_decode_text_rejects_nul_heavy_binary() {
+        assert!(decode_text(vec![0x00, 0x01, 0x00, 0x00, 0xFF, 0x80, 0x00, 0x00]).is_none());
+        // A UTF-16 BOM does not make NUL-filled data text
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend([0u8; 32]);
+        assert!(decode_text(bytes).is_none());
+    }
+
+    #[test]
+    fn test_decode_text_rejects_random_bytes() {
+        // Deterministic xorshift noise: a handful of NULs, well under 1%
+        let mut state: u32 = 0x9E37_79B9;
+        let bytes: Vec<u8> = (0..SNIFF_LEN)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                (state >> 24) as u8
+            })
+            .collect();
+        let nuls = bytes.iter().filter(|&&b| b == 0).count();
+        assert!(nuls > 0 && nuls * 100 < bytes.len());
+        assert!(decode_text(bytes).is_none());
+    }
+
+    #[test]
+    fn test_decode_text_rejects_utf8_with_nul() {
+        assert!(decode_text(b"// TODO: a\n\0\x01\x02 more text here\n".to_vec()).is_none());
+    }
+
+    #[test]
+    fn test_scan_directory_utf16le_file_keeps_line_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("win.cs"),
+            utf16le(
+                "using System;\r\n// TODO: port to net8\r\nclass A {}\r\n",
+                true,
+            ),
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let result = scan_directory(dir.path(), &config).unwrap();
+
+        assert_eq!(result.files_scanned, 1);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].line, 2);
+        assert_eq!(result.items[0].message, "port to net8");
+    }
+
+    #[test]
+    fn test_cached_scan_utf16le_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("win.cs"),
+            utf16le("// FIXME: encoded\r\n", true),
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let result = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+
+        assert_eq!(result.result.items.len(), 1);
+        assert_eq!(result.result.items[0].message, "encoded");
+    }
+
     // --- scan_directory_cached: exclude_dirs ---
 
     #[test]
@@ -1856,6 +3734,105 @@ line four
         assert_eq!(result.result.items[0].message, "keep");
     }
 
+    fn exclude_dirs_fixture() -> (tempfile::TempDir, Config) {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, msg) in [
+            ("src/keep.rs", "keep"),
+            ("target-debug/out.rs", "skip target"),
+            ("target/out.rs", "keep target"),
+            ("web/app/node_modules/pkg/index.js", "skip nested"),
+            ("node_modules/pkg/index.js", "skip top"),
+            ("vendor/lib.rs", "skip vendor"),
+            ("src/vendor/lib.rs", "skip nested vendor"),
+            ("src/vendored.rs", "keep vendored"),
+        ] {
+            let full = dir.path().join(path);
+            std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+            std::fs::write(full, format!("// TODO: {}\n", msg)).unwrap();
+        }
+        let config = Config {
+            exclude_dirs: vec![
+                "target-*".to_string(),
+                "**/node_modules".to_string(),
+                "vendor".to_string(),
+            ],
+            ..Config::default()
+        };
+        (dir, config)
+    }
+
+    fn sorted_messages(items: &[TodoItem]) -> Vec<String> {
+        let mut messages: Vec<String> = items.iter().map(|i| i.message.clone()).collect();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    fn test_scan_directory_exclude_dirs_globs() {
+        let (dir, config) = exclude_dirs_fixture();
+        let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(
+            sorted_messages(&result.items),
+            vec!["keep", "keep target", "keep vendored"]
+        );
+    }
+
+    #[test]
+    fn test_cached_scan_exclude_dirs_globs() {
+        let (dir, config) = exclude_dirs_fixture();
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let result = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(
+            sorted_messages(&result.result.items),
+            vec!["keep", "keep target", "keep vendored"]
+        );
+    }
+
+    #[test]
+    fn test_exclude_dirs_matching() {
+        let excludes = ExcludeDirs::new(&[
+            "target-*".to_string(),
+            "**/node_modules".to_string(),
+            "vendor".to_string(),
+            "docs/api/".to_string(),
+        ]);
+        let excluded = |p: &str| excludes.is_excluded(Path::new(p));
+
+        assert!(excluded("target-release/main.rs"));
+        assert!(excluded("crates/a/target-x/main.rs"));
+        assert!(!excluded("target/main.rs"));
+        assert!(excluded("node_modules/a.js"));
+        assert!(excluded("a/b/node_modules/c/d.js"));
+        assert!(excluded("vendor/lib.rs"));
+        assert!(excluded("src/vendor/lib.rs"));
+        assert!(!excluded("src/vendored.rs"));
+        assert!(!excluded("vendor"));
+        assert!(excluded("docs/api/index.md"));
+        assert!(!excluded("src/docs/api/index.md"));
+    }
+
+    #[test]
+    fn test_exclude_dirs_invalid_glob_falls_back_to_exact_name() {
+        let excludes = ExcludeDirs::new(&["build[".to_string()]);
+        assert!(excludes.is_excluded(Path::new("build[/out.rs")));
+        assert!(!excludes.is_excluded(Path::new("build/out.rs")));
+    }
+
+    #[test]
+    fn test_exclude_dirs_ignores_root_ancestors() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("vendor").join("project");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("main.rs"), "// TODO: inside root\n").unwrap();
+
+        let config = Config {
+            exclude_dirs: vec!["vendor".to_string()],
+            ..Config::default()
+        };
+        let result = scan_directory(&root, &config).unwrap();
+        assert_eq!(result.items.len(), 1);
+    }
+
     // --- scan_directory_cached: exclude_patterns ---
 
     #[test]
@@ -1898,14 +3875,96 @@ line four
         assert_eq!(result.result.items[0].message, "keep");
     }
 
+    #[test]
+    fn test_scan_respects_configured_max_file_size() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("small.rs"), "// TODO: keep\n").unwrap();
+        // One byte over a 1 KiB limit
+        let mut big_content = "// TODO: should be skipped\n".to_string();
+        big_content.push_str(&"x".repeat(1024 + 1 - big_content.len()));
+        std::fs::write(dir.path().join("big.rs"), &big_content).unwrap();
+
+        let config = Config::parse("[scan]\nmax_file_size = \"1KiB\"\n").unwrap();
+        let result = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "keep");
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let result = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(result.result.items.len(), 1);
+
+        // The default limit still picks it up
+        let result = scan_directory(dir.path(), &Config::default()).unwrap();
+        assert_eq!(result.items.len(), 2);
+    }
+
+    fn setup_gitignored_vendor() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "vendor/\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("vendor/sdk")).unwrap();
+        std::fs::create_dir_all(dir.path().join("vendor/docs")).unwrap();
+        std::fs::write(dir.path().join("main.rs"), "// TODO: ours\n").unwrap();
+        std::fs::write(dir.path().join("vendor/sdk/lib.rs"), "// FIXME: patched\n").unwrap();
+        std::fs::write(dir.path().join("vendor/docs/a.rs"), "// TODO: docs\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_scan_skips_gitignored_files_by_default() {
+        let dir = setup_gitignored_vendor();
+        let result = scan_directory(dir.path(), &Config::default()).unwrap();
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "ours");
+    }
+
+    #[test]
+    fn test_scan_respect_gitignore_false_includes_ignored_files() {
+        let dir = setup_gitignored_vendor();
+        let mut config = Config::parse("[scan]\nrespect_gitignore = false\n").unwrap();
+        config.exclude_dirs = vec!["docs".to_string()];
+
+        let result = scan_directory(dir.path(), &config).unwrap();
+        let mut files: Vec<&str> = result.items.iter().map(|i| i.file.as_str()).collect();
+        files.sort();
+        // exclude_dirs still applies
+        assert_eq!(files, vec!["main.rs", "vendor/sdk/lib.rs"]);
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        let cached = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+        assert_eq!(cached.result.items.len(), 2);
+    }
+
+    #[test]
+    fn test_rebase_paths_prefixes_items_and_scanned_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/a.rs"), "// TODO: a\n").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "fn b() {}\n").unwrap();
+        let mut result = scan_directory(dir.path(), &Config::default()).unwrap();
+
+        rebase_paths(&mut result, "crates/core/");
+        assert_eq!(result.items[0].file, "crates/core/src/a.rs");
+        let mut files = result.scanned_files.clone();
+        files.sort();
+        assert_eq!(files, vec!["crates/core/b.rs", "crates/core/src/a.rs"]);
+
+        rebase_paths(&mut result, "");
+        assert_eq!(result.items[0].file, "crates/core/src/a.rs");
+    }
+
     // --- scan_directory_cached: binary file read error ---
 
     #[test]
     fn test_cached_scan_skips_binary_files() {
         let dir = tempfile::tempdir().unwrap();
         std::fs::write(dir.path().join("good.rs"), "// TODO: keep\n").unwrap();
-        // Write binary content (invalid UTF-8)
-        std::fs::write(dir.path().join("binary.dat"), &[0xFF, 0xFE, 0x00, 0x01]).unwrap();
+        // Write binary content (invalid UTF-8, mostly NUL bytes)
+        std::fs::write(
+            dir.path().join("binary.dat"),
+            [0x00, 0x01, 0x00, 0x00, 0xFF, 0x80, 0x00, 0x00],
+        )
+        .unwrap();
 
         let config = Config::default();
         let config_hash = ScanCache::config_hash(&config);
@@ -1917,6 +3976,30 @@ line four
         assert_eq!(result.result.items[0].message, "keep");
     }
 
+    #[test]
+    fn test_cached_and_uncached_scans_count_the_same_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("good.rs"), "// TODO: keep\n").unwrap();
+        std::fs::write(dir.path().join("plain.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(
+            dir.path().join("binary.dat"),
+            [0x00, 0x01, 0x00, 0x00, 0xFF, 0x80, 0x00, 0x00],
+        )
+        .unwrap();
+
+        let config = Config::default();
+        let uncached = scan_directory(dir.path(), &config).unwrap();
+        assert_eq!(uncached.files_scanned, 2);
+
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        for _ in 0..2 {
+            // Cold, then warm
+            let cached = scan_directory_cached(dir.path(), &config, &mut cache).unwrap();
+            assert_eq!(cached.result.files_scanned, uncached.files_scanned);
+            assert_eq!(cached.result.scanned_files, uncached.scanned_files);
+        }
+    }
+
     // --- scan_directory_cached: Layer 2 content hash hit (touched file) ---
 
     #[test]
@@ -1956,7 +4039,7 @@ line four
     #[test]
     fn test_scan_content_empty() {
         let pattern = default_pattern();
-        let result = scan_content("", "empty.rs", &pattern);
+        let result = scan_content("", "empty.rs", &pattern, &ScanOptions::default());
         assert!(result.items.is_empty());
         assert!(result.ignored_items.is_empty());
     }
@@ -1967,7 +4050,7 @@ line four
     fn test_scan_content_no_matches() {
         let pattern = default_pattern();
         let content = "fn main() {\n    println!(\"hello\");\n}\n";
-        let result = scan_content(content, "main.rs", &pattern);
+        let result = scan_content(content, "main.rs", &pattern, &ScanOptions::default());
         assert!(result.items.is_empty());
     }
 
@@ -2004,7 +4087,7 @@ line four
         let pattern = default_pattern();
         // "todo-" should be skipped (e.g., "todo-scan" tool name)
         let content = "// todo-scan:ignore is a suppression marker\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert!(
             result.items.is_empty(),
             "tag followed by hyphen should be skipped"
@@ -2017,7 +4100,7 @@ line four
     fn test_scan_content_no_colon() {
         let pattern = default_pattern();
         let content = "// TODO fix this now\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "fix this now");
     }
@@ -2028,7 +4111,7 @@ line four
     fn test_scan_content_empty_message() {
         let pattern = default_pattern();
         let content = "// TODO:\n";
-        let result = scan_content(content, "test.rs", &pattern);
+        let result = scan_content(content, "test.rs", &pattern, &ScanOptions::default());
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.items[0].message, "");
     }
@@ -2048,4 +4131,222 @@ line four
         assert_eq!(result.items.len(), 1);
         assert_eq!(result.files_scanned, 3);
     }
+
+    // --- @-prefixed doc tags ---
+
+    fn at_tag_options() -> ScanOptions {
+        ScanOptions {
+            at_tags: true,
+            ..ScanOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_at_tag_in_jsdoc_block() {
+        let pattern = default_pattern();
+        let content = "/**\n * @todo implement caching\n * @FIXME(alice) broken\n */\n";
+        let result = scan_content(content, "cache.js", &pattern, &at_tag_options());
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].tag, Tag::Todo);
+        assert_eq!(result.items[0].message, "implement caching");
+        assert_eq!(result.items[1].tag, Tag::Fixme);
+        assert_eq!(result.items[1].author.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn test_at_tag_directly_after_comment_prefix() {
+        let pattern = default_pattern();
+        let result = scan_content("//@todo tighten\n", "a.js", &pattern, &at_tag_options());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "tighten");
+    }
+
+    #[test]
+    fn test_at_tag_ignored_by_default() {
+        let pattern = default_pattern();
+        let content = " * @todo implement caching\n";
+        let result = scan_content(content, "Cache.java", &pattern, &ScanOptions::default());
+        assert!(result.items.is_empty());
+    }
+
+    #[test]
+    fn test_at_tag_rejects_email_address() {
+        let pattern = default_pattern();
+        let content = "// contact email@todo.com for access\n";
+        for options in [ScanOptions::default(), at_tag_options()] {
+            let result = scan_content(content, "a.js", &pattern, &options);
+            assert!(result.items.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_at_tag_ends_multiline_block() {
+        let pattern = default_pattern();
+        let content = " * @todo first\n *   more\n * @fixme second\n";
+        let options = ScanOptions {
+            multiline: true,
+            at_tags: true,
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "a.js", &pattern, &options);
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.items[0].message, "first more");
+        assert_eq!(result.items[1].message, "second");
+    }
+
+    // --- escalation markers ---
+
+    fn escalation_options() -> ScanOptions {
+        ScanOptions {
+            escalation: true,
+            ..ScanOptions::default()
+        }
+    }
+
+    // --- configured comment prefixes ---
+
+    #[test]
+    fn test_configured_prefixes_match_batch_file_todo() {
+        let pattern = default_pattern();
+        let content = "@echo off\nREM TODO: port to PowerShell\n# TODO: not a comment here\n";
+        let default = scan_content(content, "build.bat", &pattern, &ScanOptions::default());
+        assert_eq!(default.items.len(), 1);
+        assert_eq!(default.items[0].line, 3);
+
+        let options = ScanOptions {
+            comment_prefixes: Some(vec!["REM ".to_string(), "::".to_string()]),
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "build.bat", &pattern, &options);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].line, 2);
+        assert_eq!(result.items[0].message, "port to PowerShell");
+    }
+
+    #[test]
+    fn test_configured_prefixes_replace_known_languages() {
+        let pattern = default_pattern();
+        let options = ScanOptions {
+            comment_prefixes: Some(vec!["//".to_string()]),
+            ..ScanOptions::default()
+        };
+        let content = "# TODO: not a comment now\nx = 1  // TODO: py\n";
+        let default = scan_content(content, "a.py", &pattern, &ScanOptions::default());
+        assert_eq!(default.items.len(), 1);
+        assert_eq!(default.items[0].line, 1);
+
+        let result = scan_content(content, "a.py", &pattern, &options);
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "py");
+
+        let options = ScanOptions {
+            line_start_prefixes: Some(vec![]),
+            ..ScanOptions::default()
+        };
+        let result = scan_content(" * TODO: block\n", "a.rs", &pattern, &options);
+        assert!(result.items.is_empty());
+    }
+
+    #[test]
+    fn test_escalation_marker_parsed_when_enabled() {
+        let pattern = default_pattern();
+        let content = "// TODO->FIXME(alice, 2025-01-01): !! escalated #12
+";
+        let result = scan_content(content, "a.rs", &pattern, &escalation_options());
+        assert_eq!(result.items.len(), 1);
+        let item = &result.items[0];
+        assert_eq!(item.tag, Tag::Todo);
+        assert_eq!(item.escalate_to, Some(Tag::Fixme));
+        assert_eq!(item.author.as_deref(), Some("alice"));
+        assert_eq!(item.deadline.as_ref().unwrap().to_string(), "2025-01-01");
+        assert_eq!(item.priority, Priority::Urgent);
+        assert_eq!(item.message, "escalated #12");
+        assert_eq!(item.issue_ref.as_deref(), Some("#12"));
+    }
+
+    #[test]
+    fn test_escalation_marker_ignored_by_default() {
+        let pattern = default_pattern();
+        let content = "// TODO->FIXME: escalated
+// TODO: plain
+";
+        let result = scan_content(content, "a.rs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "plain");
+        assert_eq!(result.items[0].escalate_to, None);
+    }
+
+    #[test]
+    fn test_escalation_requires_known_target_tag() {
+        let pattern = default_pattern();
+        let content = "// TODO->later: not a tag
+// todo-scan:ignore
+// TODO: ! plain
+";
+        let result = scan_content(content, "a.rs", &pattern, &escalation_options());
+        assert_eq!(result.items.len(), 1);
+        assert_eq!(result.items[0].message, "plain");
+        assert_eq!(result.items[0].priority, Priority::High);
+        assert_eq!(result.items[0].escalate_to, None);
+    }
+
+    // --- @handle mentions ---
+
+    #[test]
+    fn test_extract_mentions() {
+        assert_eq!(
+            extract_mentions("@bob please review with @carol-x, then @bob again"),
+            vec!["bob", "carol-x"]
+        );
+        assert_eq!(extract_mentions("ask (@dave_1): done?"), vec!["dave_1"]);
+    }
+
+    #[test]
+    fn test_extract_mentions_ignores_emails_and_code() {
+        assert!(extract_mentions("mail bob@example.com").is_empty());
+        assert!(extract_mentions("drop `@deprecated` and @Override(x)").is_empty());
+        assert!(extract_mentions("see @self.field and @Foo::bar").is_empty());
+        assert!(extract_mentions("path a/@scope/pkg and @@twice").is_empty());
+        assert_eq!(extract_mentions("thanks @erin."), vec!["erin"]);
+    }
+
+    #[test]
+    fn test_column_points_at_tag() {
+        let pattern = default_pattern();
+        let content = "// TODO: first\nlet s = \"é\"; // FIXME: inline\n";
+        let result = scan_content(content, "a.rs", &pattern, &ScanOptions::default());
+        assert_eq!(result.items[0].column, 4);
+        // `é` is two bytes but one UTF-16 code unit
+        assert_eq!(result.items[1].column, 18);
+
+        let options = ScanOptions {
+            utf16_columns: true,
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "a.rs", &pattern, &options);
+        assert_eq!(result.items[0].column, 4);
+        assert_eq!(result.items[1].column, 17);
+
+        let astral = "/* 🦀 */ // TODO: crab\n";
+        let result = scan_content(astral, "a.rs", &pattern, &options);
+        assert_eq!(result.items[0].column, 13);
+    }
+
+    #[test]
+    fn test_mentions_recorded_only_when_enabled() {
+        let pattern = default_pattern();
+        let content = "// TODO(alice): @bob please review\n";
+        let result = scan_content(content, "a.rs", &pattern, &ScanOptions::default());
+        assert!(result.items[0].mentions.is_empty());
+
+        let options = ScanOptions {
+            mentions: true,
+            ..ScanOptions::default()
+        };
+        let result = scan_content(content, "a.rs", &pattern, &options);
+        let item = &result.items[0];
+        assert_eq!(item.author.as_deref(), Some("alice"));
+        assert_eq!(item.mentions, vec!["bob"]);
+        assert_eq!(item.message, "@bob please review");
+    }
 }
@@ -0,0 +1,259 @@
+use anyhow::Result;
+use schemars::{generate::SchemaSettings, JsonSchema, Schema};
+
+use crate::cli::SchemaTarget;
+use crate::model::*;
+
+/// Schema of `T` as serialized (not deserialized), so fields skipped when
+/// empty are not marked required.
+fn serialized_schema<T: JsonSchema>() -> Schema {
+    SchemaSettings::default()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<T>()
+}
+
+/// Output modes share no set of required fields, so the `anyOf` schemars
+/// derives for an untagged enum can be the stricter `oneOf`.
+fn any_of_to_one_of(schema: &mut Schema) {
+    if let Some(variants) = schema.remove("anyOf") {
+        schema.insert("oneOf".to_string(), variants);
+    }
+}
+
+/// Schema-only union of what `list --format json` prints.
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[schemars(
+    title = "ListOutput",
+    description = "Output of `list --format json`",
+    transform = any_of_to_one_of
+)]
+#[allow(dead_code)]
+enum ListOutput {
+    Items(ScanResult),
+    /// `list --count-only`
+    Count(CountResult),
+    /// `list --dedupe`
+    Dedupe(DedupeResult),
+    /// `list --invert-match`
+    FilesWithoutTodos(FilesWithoutTodosResult),
+}
+
+/// Schema-only union of what `search --format json` prints.
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[schemars(
+    title = "SearchOutput",
+    description = "Output of `search --format json`",
+    transform = any_of_to_one_of
+)]
+#[allow(dead_code)]
+enum SearchOutput {
+    Items(SearchResult),
+    /// `search --history`
+    History(HistorySearchResult),
+}
+
+/// Schema-only union of what `diff --format json` prints.
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[schemars(
+    title = "DiffOutput",
+    description = "Output of `diff --format json`",
+    transform = any_of_to_one_of
+)]
+#[allow(dead_code)]
+enum DiffOutput {
+    Entries(DiffResult),
+    /// `diff --stat`
+    Stat(DiffStatResult),
+}
+
+/// Schema-only union of what `stats --format json` prints.
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[schemars(
+    title = "StatsOutput",
+    description = "Output of `stats --format json`",
+    transform = any_of_to_one_of
+)]
+#[allow(dead_code, clippy::large_enum_variant)]
+enum StatsOutput {
+    Summary(StatsResult),
+    /// `stats BASE..HEAD`
+    Comparison(StatsComparison),
+}
+
+/// JSON Schema for the `--format json` output of a command, derived from the
+/// serde models. A command with several output modes gets one `oneOf`
+/// branch per mode. For `export`, the schema describes a single line.
+pub fn output_schema(target: &SchemaTarget) -> Schema {
+    match target {
+        SchemaTarget::List => serialized_schema::<ListOutput>(),
+        SchemaTarget::Search => serialized_schema::<SearchOutput>(),
+        SchemaTarget::Diff => serialized_schema::<DiffOutput>(),
+        SchemaTarget::Blame => serialized_schema::<BlameResult>(),
+        SchemaTarget::Stats => serialized_schema::<StatsOutput>(),
+        SchemaTarget::Check => serialized_schema::<CheckResult>(),
+        SchemaTarget::Lint => serialized_schema::<LintResult>(),
+        SchemaTarget::Clean => serialized_schema::<CleanResult>(),
+        SchemaTarget::Export => serialized_schema::<JsonTodoItem>(),
+    }
+}
+
+pub fn cmd_schema(target: SchemaTarget) -> Result<()> {
+    let schema = output_schema(&target);
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn item_def(schema: &Schema) -> Value {
+        schema.as_value()["$defs"]["JsonTodoItem"].clone()
+    }
+
+    fn required(schema: &Value) -> Vec<&str> {
+        schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_list_items_include_injected_fields() {
+        let schema = output_schema(&SchemaTarget::List);
+        let value = schema.as_value();
+        let scan = &value["$defs"]["ScanResult"];
+        assert_eq!(
+            scan["properties"]["items"]["items"]["$ref"],
+            "#/$defs/JsonTodoItem"
+        );
+        // ignored_items are serialized as-is, without an id
+        assert_eq!(
+            scan["properties"]["ignored_items"]["items"]["$ref"],
+            "#/$defs/TodoItem"
+        );
+
+        let item = item_def(&schema);
+        let props = item["properties"].as_object().unwrap();
+        for field in [
+            "file",
+            "line",
+            "tag",
+            "message",
+            "id",
+            "context",
+            "match_key",
+        ] {
+            assert!(props.contains_key(field), "missing property {field}");
+        }
+        let req = required(&item);
+        assert!(req.contains(&"id"));
+        assert!(!req.contains(&"context"));
+        assert!(!req.contains(&"priority"));
+        assert!(value["$defs"]["ContextInfo"].is_object());
+    }
+
+    fn one_of_refs(target: &SchemaTarget) -> Vec<String> {
+        output_schema(target).as_value()["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["$ref"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_list_has_a_branch_per_output_mode() {
+        assert_eq!(
+            one_of_refs(&SchemaTarget::List),
+            [
+                "#/$defs/ScanResult",
+                "#/$defs/CountResult",
+                "#/$defs/DedupeResult",
+                "#/$defs/FilesWithoutTodosResult"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_has_a_branch_per_output_mode() {
+        assert_eq!(
+            one_of_refs(&SchemaTarget::Search),
+            ["#/$defs/SearchResult", "#/$defs/HistorySearchResult"]
+        );
+    }
+
+    #[test]
+    fn test_diff_has_a_branch_per_output_mode() {
+        assert_eq!(
+            one_of_refs(&SchemaTarget::Diff),
+            ["#/$defs/DiffResult", "#/$defs/DiffStatResult"]
+        );
+    }
+
+    #[test]
+    fn test_skipped_when_empty_fields_are_optional() {
+        let schema = output_schema(&SchemaTarget::List);
+        let req = required(&schema.as_value()["$defs"]["ScanResult"]);
+        assert!(req.contains(&"items"));
+        assert!(!req.contains(&"ignored_items"));
+    }
+
+    #[test]
+    fn test_blame_entry_flattens_item_with_id() {
+        let schema = output_schema(&SchemaTarget::Blame);
+        let entry = &schema.as_value()["$defs"]["BlameEntry"];
+        let text = entry.to_string();
+        assert!(text.contains("\"blame\""));
+        assert!(text.contains("\"id\""));
+    }
+
+    #[test]
+    fn test_export_schema_is_single_item() {
+        let schema = output_schema(&SchemaTarget::Export);
+        let value = schema.as_value();
+        assert_eq!(value["title"], "TodoItem");
+        assert!(required(value).contains(&"id"));
+        assert_eq!(
+            value["properties"]["deadline"]["anyOf"][0]["$ref"],
+            "#/$defs/Deadline"
+        );
+    }
+
+    #[test]
+    fn test_schema_covers_serialized_result_fields() {
+        let result = StatsResult {
+            total_items: 0,
+            total_files: 0,
+            tag_counts: vec![],
+            priority_counts: PriorityCounts {
+                normal: 0,
+                high: 0,
+                urgent: 0,
+            },
+            author_counts: vec![],
+            hotspot_files: vec![],
+            trend: None,
+            author_churn: None,
+            history: None,
+            total_authors: 0,
+            truncated: false,
+        };
+        let serialized = serde_json::to_value(&result).unwrap();
+        let schema = output_schema(&SchemaTarget::Stats);
+        let props = schema.as_value()["$defs"]["StatsResult"]["properties"]
+            .as_object()
+            .unwrap();
+        for key in serialized.as_object().unwrap().keys() {
+            assert!(props.contains_key(key), "schema missing {key}");
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use std::collections::HashSet;
+use std::path::Path;
 
-use crate::model::{ScanResult, SearchResult, TodoItem};
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::config::Config;
+use crate::git::git_command;
+use crate::model::{HistoryMatch, HistorySearchResult, ScanResult, SearchResult, TodoItem};
+use crate::scanner::{scan_content, ScanOptions};
 
 fn matches_query(item: &TodoItem, query: &str, exact: bool) -> bool {
     if exact {
@@ -35,15 +42,101 @@ pub fn search_items(scan: &ScanResult, query: &str, exact: bool) -> SearchResult
     }
 }
 
+/// Search the last `max_commits` commits for matching TODOs under `root`.
+///
+/// The oldest commit in the window is read in full; each later commit only
+/// re-reads the files it changed. Every match is reported once, as of the
+/// commit where it first appeared, and marked `present_now` when `current`
+/// (a scan of the working tree, with paths relative to `root`) still has it.
+pub fn search_history(
+    root: &Path,
+    config: &Config,
+    current: &ScanResult,
+    query: &str,
+    exact: bool,
+    max_commits: usize,
+) -> Result<HistorySearchResult> {
+    let log_output = git_command(
+        &["log", "--format=%H %aI", "-n", &max_commits.to_string()],
+        root,
+    )
+    .context("search --history requires a git repository")?;
+
+    let mut commits: Vec<(&str, &str)> = log_output
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .collect();
+    commits.reverse();
+
+    let pattern = config.tags_pattern();
+    let re = Regex::new(&pattern).with_context(|| format!("Invalid tags pattern: {}", pattern))?;
+    let options = ScanOptions::from_config(config);
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut matches: Vec<HistoryMatch> = Vec::new();
+
+    for (i, (hash, date)) in commits.iter().enumerate() {
+        // Paths are relative to `root` and limited to its subtree
+        let files = if i == 0 {
+            git_command(&["ls-tree", "-r", "--name-only", hash], root)
+        } else {
+            git_command(
+                &[
+                    "diff-tree",
+                    "-r",
+                    "--name-only",
+                    "--no-commit-id",
+                    "--relative",
+                    hash,
+                ],
+                root,
+            )
+        };
+        let Ok(files) = files else { continue };
+
+        for path in files.lines().map(str::trim).filter(|p| !p.is_empty()) {
+            let content = match git_command(&["show", &format!("{}:./{}", hash, path)], root) {
+                Ok(c) => c,
+                Err(_) => continue, // deleted in this commit, binary, or inaccessible
+            };
+            for item in scan_content(&content, path, &re, &options).items {
+                if matches_query(&item, query, exact) && seen.insert(item.stable_id()) {
+                    matches.push(HistoryMatch {
+                        item,
+                        first_seen_commit: hash[..hash.len().min(8)].to_string(),
+                        first_seen_date: date.split('T').next().unwrap_or(date).to_string(),
+                        present_now: false,
+                    });
+                }
+            }
+        }
+    }
+
+    let present: HashSet<String> = current.items.iter().map(TodoItem::stable_id).collect();
+    for m in &mut matches {
+        m.present_now = present.contains(&m.item.stable_id());
+    }
+
+    let match_count = matches.len();
+    Ok(HistorySearchResult {
+        query: query.to_string(),
+        exact,
+        matches,
+        match_count,
+        commits_scanned: commits.len(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::model::{ScanResult, Tag};
-    use crate::test_helpers::helpers::make_item;
+    use crate::test_helpers::helpers::{git, make_item};
 
     fn make_scan(items: Vec<TodoItem>) -> ScanResult {
         ScanResult {
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
             items,
         }
@@ -176,4 +269,76 @@ mod tests {
         let result = search_items(&scan, "#999", false);
         assert_eq!(result.match_count, 0);
     }
+
+    // ---- search_history ----
+
+    fn commit_file(cwd: &Path, path: &str, content: &str, message: &str) {
+        let full = cwd.join(path);
+        std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+        std::fs::write(full, content).unwrap();
+        git(cwd, &["add", "-A"]);
+        git(cwd, &["commit", "-m", message]);
+    }
+
+    fn setup_history() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path();
+        git(cwd, &["init"]);
+        git(cwd, &["config", "user.email", "test@test.com"]);
+        git(cwd, &["config", "user.name", "Test"]);
+        commit_file(cwd, "a.rs", "// TODO: rotate password\n", "one");
+        commit_file(
+            cwd,
+            "b.rs",
+            "// FIXME: password in env\n// TODO: other\n",
+            "two",
+        );
+        commit_file(cwd, "a.rs", "fn a() {}\n", "three");
+        dir
+    }
+
+    #[test]
+    fn test_search_history_reports_first_sighting_and_presence() {
+        let dir = setup_history();
+        let config = Config::default();
+        let current = crate::scanner::scan_directory(dir.path(), &config).unwrap();
+
+        let result = search_history(dir.path(), &config, &current, "password", false, 100).unwrap();
+        assert_eq!(result.commits_scanned, 3);
+        assert_eq!(result.match_count, 2);
+
+        let first = &result.matches[0];
+        assert_eq!(first.item.file, "a.rs");
+        assert_eq!(first.item.message, "rotate password");
+        assert!(!first.present_now);
+        assert_eq!(first.first_seen_commit.len(), 8);
+        assert_eq!(first.first_seen_date.len(), 10);
+
+        let second = &result.matches[1];
+        assert_eq!(second.item.file, "b.rs");
+        assert!(second.present_now);
+    }
+
+    #[test]
+    fn test_search_history_max_commits_limits_window() {
+        let dir = setup_history();
+        let config = Config::default();
+        let current = crate::scanner::scan_directory(dir.path(), &config).unwrap();
+
+        // Only the last commit: the full tree at that point, so "rotate
+        // password" (already removed) is never seen
+        let result = search_history(dir.path(), &config, &current, "password", false, 1).unwrap();
+        assert_eq!(result.commits_scanned, 1);
+        assert_eq!(result.match_count, 1);
+        assert_eq!(result.matches[0].item.file, "b.rs");
+    }
+
+    #[test]
+    fn test_search_history_outside_git_repo_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let current = make_scan(vec![]);
+        let err =
+            search_history(dir.path(), &Config::default(), &current, "x", false, 10).unwrap_err();
+        assert!(err.to_string().contains("requires a git repository"));
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::model::*;
 
@@ -15,10 +15,10 @@ pub fn compute_stats(scan: &ScanResult, diff: Option<&DiffResult>) -> StatsResul
     // Tag counts
     let mut tag_map: HashMap<Tag, usize> = HashMap::new();
     for item in &scan.items {
-        *tag_map.entry(item.tag).or_insert(0) += 1;
+        *tag_map.entry(item.tag.clone()).or_insert(0) += 1;
     }
     let mut tag_counts: Vec<(Tag, usize)> = tag_map.into_iter().collect();
-    tag_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    tag_counts.sort_by_key(|e| std::cmp::Reverse(e.1));
 
     // Priority counts
     let mut normal = 0;
@@ -47,15 +47,15 @@ pub fn compute_stats(scan: &ScanResult, diff: Option<&DiffResult>) -> StatsResul
         *author_map.entry(key).or_insert(0) += 1;
     }
     let mut author_counts: Vec<(String, usize)> = author_map.into_iter().collect();
-    author_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    sort_counts(&mut author_counts);
+    let total_authors = author_counts.len();
 
-    // Hotspot files (top 5 by count)
+    // Hotspot files, most TODOs first
     let mut hotspot_files: Vec<(String, usize)> = file_set
         .into_iter()
         .map(|(k, v)| (k.to_string(), v))
         .collect();
-    hotspot_files.sort_by(|a, b| b.1.cmp(&a.1));
-    hotspot_files.truncate(5);
+    sort_counts(&mut hotspot_files);
 
     // Trend info from diff
     let trend = diff.map(|d| TrendInfo {
@@ -71,16 +71,228 @@ pub fn compute_stats(scan: &ScanResult, diff: Option<&DiffResult>) -> StatsResul
         priority_counts,
         author_counts,
         hotspot_files,
+        total_authors,
+        truncated: false,
         trend,
+        author_churn: None,
+        history: None,
     }
 }
 
+/// Largest count first; ties by name so truncation is deterministic.
+fn sort_counts(counts: &mut [(String, usize)]) {
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+}
+
+/// Keep only the `top` largest author and hotspot entries (`stats --top`).
+/// Totals are left unchanged; `truncated` records whether anything was cut.
+pub fn truncate_stats(result: &mut StatsResult, top: usize) {
+    if result.author_counts.len() > top || result.hotspot_files.len() > top {
+        result.truncated = true;
+    }
+    result.author_counts.truncate(top);
+    result.hotspot_files.truncate(top);
+}
+
+/// Compare two full snapshots (`stats BASE..HEAD`). Call before
+/// `truncate_stats` so every author is compared. Tags are ordered by their
+/// larger count, authors by the size of their change.
+pub fn compare_stats(
+    base: StatsResult,
+    head: StatsResult,
+    base_ref: &str,
+    head_ref: &str,
+) -> StatsComparison {
+    let totals = vec![
+        count_delta("items", base.total_items, head.total_items),
+        count_delta("files", base.total_files, head.total_files),
+    ];
+
+    let mut tags = merge_counts(
+        base.tag_counts.iter().map(|(t, c)| (t.to_string(), *c)),
+        head.tag_counts.iter().map(|(t, c)| (t.to_string(), *c)),
+    );
+    tags.sort_by(|a, b| {
+        b.base
+            .max(b.head)
+            .cmp(&a.base.max(a.head))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    let (bp, hp) = (&base.priority_counts, &head.priority_counts);
+    let priorities = vec![
+        count_delta("urgent", bp.urgent, hp.urgent),
+        count_delta("high", bp.high, hp.high),
+        count_delta("normal", bp.normal, hp.normal),
+    ];
+
+    let mut authors = merge_counts(
+        base.author_counts.iter().cloned(),
+        head.author_counts.iter().cloned(),
+    );
+    authors.sort_by(|a, b| {
+        b.delta
+            .abs()
+            .cmp(&a.delta.abs())
+            .then_with(|| b.head.cmp(&a.head))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let total_authors = authors.len();
+
+    StatsComparison {
+        base_ref: base_ref.to_string(),
+        head_ref: head_ref.to_string(),
+        base,
+        head,
+        totals,
+        tags,
+        priorities,
+        authors,
+        total_authors,
+    }
+}
+
+fn count_delta(name: &str, base: usize, head: usize) -> CountDelta {
+    CountDelta {
+        name: name.to_string(),
+        base,
+        head,
+        delta: head as i64 - base as i64,
+    }
+}
+
+/// Join two name/count lists; a name missing on one side counts 0 there.
+fn merge_counts(
+    base: impl Iterator<Item = (String, usize)>,
+    head: impl Iterator<Item = (String, usize)>,
+) -> Vec<CountDelta> {
+    let mut counts: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for (name, count) in base {
+        counts.entry(name).or_default().0 = count;
+    }
+    for (name, count) in head {
+        counts.entry(name).or_default().1 = count;
+    }
+    counts
+        .into_iter()
+        .map(|(name, (b, h))| count_delta(&name, b, h))
+        .collect()
+}
+
+/// Fallback bucket for diff entries whose blame author is unavailable.
+pub const UNKNOWN_AUTHOR: &str = "unknown";
+
+/// Count added and removed TODOs per blame author.
+///
+/// `authors` runs parallel to `diff.entries`; entries whose blame is
+/// unavailable (`None`) are counted under `unknown`. Sorted by net growth
+/// (added minus removed), largest first, then by author name.
+pub fn compute_author_churn(diff: &DiffResult, authors: &[Option<String>]) -> Vec<AuthorChurn> {
+    let mut by_author: BTreeMap<&str, AuthorChurn> = BTreeMap::new();
+    for (entry, author) in diff.entries.iter().zip(authors) {
+        let author = author.as_deref().unwrap_or(UNKNOWN_AUTHOR);
+        let churn = by_author.entry(author).or_insert_with(|| AuthorChurn {
+            author: author.to_string(),
+            added: 0,
+            removed: 0,
+        });
+        match entry.status {
+            DiffStatus::Added => churn.added += 1,
+            DiffStatus::Removed => churn.removed += 1,
+        }
+    }
+    let mut churn: Vec<AuthorChurn> = by_author.into_values().collect();
+    churn.sort_by_key(|c| std::cmp::Reverse(c.added as i64 - c.removed as i64));
+    churn
+}
+
+/// Totals for `list --count-only`: item count, per-tag counts, and the
+/// number of distinct files containing an item.
+pub fn compute_counts(items: &[TodoItem]) -> CountResult {
+    let mut by_tag: BTreeMap<String, usize> = BTreeMap::new();
+    let mut files: HashSet<&str> = HashSet::new();
+    for item in items {
+        *by_tag.entry(item.tag.as_str().to_string()).or_insert(0) += 1;
+        files.insert(item.file.as_str());
+    }
+    CountResult {
+        total: items.len(),
+        by_tag,
+        files: files.len(),
+    }
+}
+
+/// Files from `scanned_files` with no entry in `items`, in the input order,
+/// for `list --invert-match`.
+pub fn files_without_todos(scanned_files: &[String], items: &[TodoItem]) -> Vec<String> {
+    let with_todos: HashSet<&str> = items.iter().map(|i| i.file.as_str()).collect();
+    scanned_files
+        .iter()
+        .filter(|f| !with_todos.contains(f.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Collapse items with the same tag and message fingerprint for
+/// `list --dedupe`, keeping the order of first occurrence.
+pub fn dedupe_items(items: &[TodoItem]) -> Vec<DedupedItem> {
+    let mut index: HashMap<(&str, String), usize> = HashMap::new();
+    let mut deduped: Vec<DedupedItem> = Vec::new();
+    for item in items {
+        let location = Location {
+            file: item.file.clone(),
+            line: item.line,
+        };
+        let key = (item.tag.as_str(), message_fingerprint(&item.message));
+        match index.get(&key) {
+            Some(&idx) => {
+                deduped[idx].count += 1;
+                deduped[idx].locations.push(location);
+            }
+            None => {
+                index.insert(key, deduped.len());
+                deduped.push(DedupedItem {
+                    item: item.clone(),
+                    count: 1,
+                    locations: vec![location],
+                });
+            }
+        }
+    }
+    deduped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::model::{Priority, Tag};
     use crate::test_helpers::helpers::make_item;
 
+    #[test]
+    fn test_author_aliases_collapse_into_one_bucket() {
+        let config = crate::config::Config::parse(
+            "[authors.aliases]\n\"Alice Smith\" = [\"alice\", \"alice@corp.com\"]\n",
+        )
+        .unwrap();
+        let pattern = regex::Regex::new(&config.tags_pattern()).unwrap();
+        let options = crate::scanner::ScanOptions::from_config(&config);
+        let content = "// TODO(alice): one\n// TODO(Alice@corp.com): two\n// TODO(bob): three\n";
+        let scanned = crate::scanner::scan_content(content, "a.rs", &pattern, &options);
+        let scan = ScanResult {
+            items: scanned.items,
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+
+        let result = compute_stats(&scan, None);
+        assert_eq!(
+            result.author_counts,
+            vec![("Alice Smith".to_string(), 2), ("bob".to_string(), 1)]
+        );
+        assert_eq!(result.total_authors, 2);
+    }
+
     #[test]
     fn test_basic_counts() {
         let scan = ScanResult {
@@ -90,6 +302,7 @@ mod tests {
                 make_item("b.rs", 1, Tag::Fixme, "fix this"),
             ],
             files_scanned: 2,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -113,6 +326,7 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -135,6 +349,7 @@ mod tests {
         let scan = ScanResult {
             items,
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -143,19 +358,50 @@ mod tests {
     }
 
     #[test]
-    fn test_hotspot_files_limited_to_5() {
-        let items: Vec<TodoItem> = (0..10)
-            .map(|i| make_item(&format!("file{}.rs", i), 1, Tag::Todo, "task"))
+    fn test_truncate_stats_keeps_top_n_and_totals() {
+        let mut items: Vec<TodoItem> = (0..10)
+            .map(|i| {
+                let mut item = make_item(&format!("file{}.rs", i), 1, Tag::Todo, "task");
+                item.author = Some(format!("dev{}", i % 4));
+                item
+            })
             .collect();
+        items.push(make_item("file3.rs", 2, Tag::Todo, "second"));
 
         let scan = ScanResult {
             items,
             files_scanned: 10,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
-        let result = compute_stats(&scan, None);
-        assert_eq!(result.hotspot_files.len(), 5);
+        let mut result = compute_stats(&scan, None);
+        assert_eq!(result.hotspot_files.len(), 10);
+        assert_eq!(result.total_authors, 5);
+        assert!(!result.truncated);
+
+        truncate_stats(&mut result, 3);
+        assert!(result.truncated);
+        assert_eq!(result.hotspot_files.len(), 3);
+        assert_eq!(result.hotspot_files[0], ("file3.rs".to_string(), 2));
+        assert_eq!(result.hotspot_files[1].0, "file0.rs");
+        assert_eq!(result.author_counts.len(), 3);
+        assert_eq!(result.total_authors, 5);
+        assert_eq!(result.total_items, 11);
+        assert_eq!(result.total_files, 10);
+    }
+
+    #[test]
+    fn test_truncate_stats_not_flagged_when_within_limit() {
+        let scan = ScanResult {
+            items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
+            files_scanned: 1,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let mut result = compute_stats(&scan, None);
+        truncate_stats(&mut result, 1);
+        assert!(!result.truncated);
     }
 
     #[test]
@@ -163,6 +409,7 @@ mod tests {
         let scan = ScanResult {
             items: vec![make_item("a.rs", 1, Tag::Todo, "task")],
             files_scanned: 1,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
         let diff = DiffResult {
@@ -170,6 +417,10 @@ mod tests {
             added_count: 3,
             removed_count: 1,
             base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
         };
 
         let result = compute_stats(&scan, Some(&diff));
@@ -180,11 +431,68 @@ mod tests {
         assert_eq!(trend.base_ref, "main");
     }
 
+    #[test]
+    fn test_author_churn_counts_per_author() {
+        let entry = |status, file: &str| DiffEntry {
+            status,
+            item: make_item(file, 1, Tag::Todo, "task"),
+        };
+        let diff = DiffResult {
+            entries: vec![
+                entry(DiffStatus::Added, "a.rs"),
+                entry(DiffStatus::Added, "b.rs"),
+                entry(DiffStatus::Removed, "c.rs"),
+                entry(DiffStatus::Removed, "d.rs"),
+                entry(DiffStatus::Added, "e.rs"),
+            ],
+            added_count: 3,
+            removed_count: 2,
+            base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
+        };
+        let authors = vec![
+            Some("alice".to_string()),
+            Some("alice".to_string()),
+            Some("bob".to_string()),
+            None,
+            None,
+        ];
+
+        let churn = compute_author_churn(&diff, &authors);
+        let summary: Vec<(&str, usize, usize)> = churn
+            .iter()
+            .map(|c| (c.author.as_str(), c.added, c.removed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("alice", 2, 0), ("unknown", 1, 1), ("bob", 0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_author_churn_empty_diff() {
+        let diff = DiffResult {
+            entries: vec![],
+            added_count: 0,
+            removed_count: 0,
+            base_ref: "main".to_string(),
+            head_ref: None,
+            base_tag_counts: Default::default(),
+            net_change: 0,
+            base_total: 0,
+        };
+        assert!(compute_author_churn(&diff, &[]).is_empty());
+    }
+
     #[test]
     fn test_empty_scan() {
         let scan = ScanResult {
             items: vec![],
             files_scanned: 0,
+            scanned_files: vec![],
             ignored_items: vec![],
         };
 
@@ -195,4 +503,111 @@ mod tests {
         assert!(result.author_counts.is_empty());
         assert!(result.hotspot_files.is_empty());
     }
+
+    #[test]
+    fn test_compute_counts() {
+        let items = vec![
+            make_item("a.rs", 1, Tag::Todo, "one"),
+            make_item("a.rs", 2, Tag::Todo, "two"),
+            make_item("b.rs", 1, Tag::Fixme, "three"),
+        ];
+        let counts = compute_counts(&items);
+        assert_eq!(counts.total, 3);
+        assert_eq!(counts.files, 2);
+        assert_eq!(counts.by_tag.get("TODO"), Some(&2));
+        assert_eq!(counts.by_tag.get("FIXME"), Some(&1));
+        assert_eq!(counts.by_tag.len(), 2);
+    }
+
+    #[test]
+    fn test_files_without_todos() {
+        let scanned = vec!["a.rs".to_string(), "b.rs".to_string(), "c.rs".to_string()];
+        let items = vec![
+            make_item("b.rs", 1, Tag::Todo, "one"),
+            make_item("b.rs", 2, Tag::Fixme, "two"),
+        ];
+        assert_eq!(files_without_todos(&scanned, &items), vec!["a.rs", "c.rs"]);
+        assert!(files_without_todos(&[], &items).is_empty());
+    }
+
+    #[test]
+    fn test_dedupe_items_collapses_same_tag_and_message() {
+        let items = vec![
+            make_item("a.rs", 1, Tag::Todo, "Handle errors"),
+            make_item("b.rs", 5, Tag::Fixme, "handle errors"),
+            make_item("c.rs", 9, Tag::Todo, "handle  errors!"),
+            make_item("d.rs", 2, Tag::Todo, "something else"),
+        ];
+        let deduped = dedupe_items(&items);
+
+        assert_eq!(deduped.len(), 3);
+        assert_eq!(deduped[0].item.file, "a.rs");
+        assert_eq!(deduped[0].count, 2);
+        assert_eq!(
+            deduped[0].locations,
+            vec![
+                Location {
+                    file: "a.rs".to_string(),
+                    line: 1
+                },
+                Location {
+                    file: "c.rs".to_string(),
+                    line: 9
+                },
+            ]
+        );
+        assert_eq!(deduped[1].item.tag, Tag::Fixme);
+        assert_eq!(deduped[1].count, 1);
+        assert_eq!(deduped[2].item.message, "something else");
+        assert!(dedupe_items(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_compare_stats_merges_both_sides() {
+        let scan = |items: Vec<TodoItem>| ScanResult {
+            items,
+            files_scanned: 0,
+            scanned_files: vec![],
+            ignored_items: vec![],
+        };
+        let mut owned = make_item("a.rs", 1, Tag::Todo, "one");
+        owned.author = Some("alice".to_string());
+        let base = compute_stats(
+            &scan(vec![
+                owned.clone(),
+                make_item("a.rs", 2, Tag::Fixme, "two"),
+                make_item("a.rs", 3, Tag::Fixme, "three"),
+            ]),
+            None,
+        );
+        let mut urgent = make_item("b.rs", 1, Tag::Hack, "new");
+        urgent.priority = Priority::Urgent;
+        let head = compute_stats(&scan(vec![urgent]), None);
+
+        let result = compare_stats(base, head, "v1", "v2");
+        assert_eq!(result.totals[0], count_delta("items", 3, 1));
+        assert_eq!(result.totals[1], count_delta("files", 1, 1));
+        assert_eq!(
+            result.tags,
+            vec![
+                count_delta("FIXME", 2, 0),
+                count_delta("HACK", 0, 1),
+                count_delta("TODO", 1, 0),
+            ]
+        );
+        assert_eq!(result.priorities[0], count_delta("urgent", 0, 1));
+        assert_eq!(result.priorities[2], count_delta("normal", 3, 0));
+        // Largest change first: unassigned 2 -> 1 ties alice 1 -> 0 on size
+        assert_eq!(result.authors[0].name, "unassigned");
+        assert_eq!(result.authors[1], count_delta("alice", 1, 0));
+        assert_eq!(result.total_authors, 2);
+    }
+
+    #[test]
+    fn test_compute_counts_empty() {
+        let counts = compute_counts(&[]);
+        assert_eq!(counts.total, 0);
+        assert_eq!(counts.files, 0);
+        assert!(counts.by_tag.is_empty());
+    }
 }
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::context::ContextInfo;
-use crate::model::{ClaudeTask, ClaudeTaskMetadata, Priority, Tag, TodoItem};
+use crate::model::{ClaudeTask, ClaudeTaskMetadata, Priority, Tag, TagStyles, TodoItem};
 
 /// Map a tag to an imperative action verb for task subjects.
 pub fn action_verb(tag: &Tag) -> &'static str {
@@ -9,7 +9,7 @@ pub fn action_verb(tag: &Tag) -> &'static str {
         Tag::Bug | Tag::Fixme => "Fix",
         Tag::Todo => "Implement",
         Tag::Hack => "Refactor",
-        Tag::Xxx => "Address",
+        Tag::Xxx | Tag::Custom(_) => "Address",
         Tag::Note => "Review",
     }
 }
@@ -20,7 +20,7 @@ pub fn active_verb(tag: &Tag) -> &'static str {
         Tag::Bug | Tag::Fixme => "Fixing",
         Tag::Todo => "Implementing",
         Tag::Hack => "Refactoring",
-        Tag::Xxx => "Addressing",
+        Tag::Xxx | Tag::Custom(_) => "Addressing",
         Tag::Note => "Reviewing",
     }
 }
@@ -129,11 +129,15 @@ pub fn build_tasks(
 }
 
 /// Sort items by priority (Urgent > High > Normal), then tag severity, then file/line.
-pub fn sort_by_priority(items: &mut [TodoItem]) {
+pub fn sort_by_priority(items: &mut [TodoItem], tag_styles: &TagStyles) {
     items.sort_by(|a, b| {
         b.priority
             .cmp(&a.priority)
-            .then(b.tag.severity().cmp(&a.tag.severity()))
+            .then(
+                tag_styles
+                    .severity(&b.tag)
+                    .cmp(&tag_styles.severity(&a.tag)),
+            )
             .then(a.file.cmp(&b.file))
             .then(a.line.cmp(&b.line))
     });
@@ -142,7 +146,7 @@ pub fn sort_by_priority(items: &mut [TodoItem]) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::context::ContextLine;
+    use crate::context::{ContextLine, ContextMode};
     use crate::model::Priority;
 
     use crate::test_helpers::helpers::make_item;
@@ -225,6 +229,7 @@ mod tests {
                 line_number: 11,
                 content: "let y = 2;".to_string(),
             }],
+            mode: ContextMode::Lines,
         };
 
         let desc = build_description(&item, Some(&ctx));
@@ -272,7 +277,7 @@ mod tests {
             },
         ];
 
-        sort_by_priority(&mut items);
+        sort_by_priority(&mut items, &TagStyles::default());
 
         assert_eq!(items[0].priority, Priority::Urgent);
         assert_eq!(items[1].priority, Priority::High);
@@ -287,7 +292,7 @@ mod tests {
             make_item("src/main.rs", 10, Tag::Todo, "todo item"),
         ];
 
-        sort_by_priority(&mut items);
+        sort_by_priority(&mut items, &TagStyles::default());
 
         assert_eq!(items[0].tag, Tag::Bug);
         assert_eq!(items[1].tag, Tag::Todo);
@@ -1,17 +1,43 @@
 #[cfg(test)]
 pub mod helpers {
+    use std::path::Path;
+    use std::process::Command;
+
     use crate::model::{Priority, Tag, TodoItem};
 
+    /// Run `git` in `cwd` and fail the test if it exits unsuccessfully.
+    // Only the library's tests build git repositories
+    #[allow(dead_code)]
+    pub fn git(cwd: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}{}",
+            args,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
     pub fn make_item(file: &str, line: usize, tag: Tag, message: &str) -> TodoItem {
         TodoItem {
             file: file.to_string(),
             line,
+            column: 1,
             tag,
             message: message.to_string(),
             author: None,
             issue_ref: None,
             priority: Priority::Normal,
             deadline: None,
+            relative_deadline: None,
+            checked: None,
+            escalate_to: None,
+            mentions: vec![],
         }
     }
 }
@@ -0,0 +1,44 @@
+//! `config validate`: load the config file the way every command does, but
+//! stop before scanning.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use todo_scan::config::{find_config_file, Config};
+
+use crate::cli::Format;
+
+/// Check `config_path` (`--config`, where `-` reads stdin), or the
+/// `.todo-scan.toml` found from `root`. Problems are returned as configuration errors (exit code 2).
+pub fn cmd_config_validate(root: &Path, config_path: Option<&Path>, format: &Format) -> Result<()> {
+    let path = match config_path {
+        Some(path) => Some(path.to_path_buf()),
+        None => find_config_file(root),
+    };
+    let from_stdin = path.as_deref() == Some(Path::new("-"));
+    if let Some(ref path) = path {
+        let loaded = if from_stdin {
+            Config::load_stdin()
+        } else {
+            Config::load_file(path)
+        };
+        loaded.context("invalid configuration")?;
+    }
+
+    match format {
+        Format::Text => match path {
+            Some(_) if from_stdin => println!("stdin: valid"),
+            Some(path) => println!("{}: valid", path.display()),
+            None => println!("No .todo-scan.toml found; the defaults apply"),
+        },
+        _ => {
+            let json = serde_json::json!({
+                "path": path.map(|p| p.display().to_string()),
+                "valid": true,
+            });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        }
+    }
+    Ok(())
+}
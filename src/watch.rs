@@ -1,37 +1,68 @@
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
+use crossterm::{cursor, execute, queue, terminal};
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
 use regex::Regex;
 
+use crate::cache::ScanCache;
 use crate::cli::Format;
 use crate::config::Config;
 use crate::date_utils;
-use crate::model::{FileUpdate, Tag, TodoItem, WatchEvent};
-use crate::output::{print_initial_summary, print_watch_event};
-use crate::scanner::{scan_content, scan_directory, MAX_FILE_SIZE};
+use crate::deadline::today;
+use crate::model::{FileUpdate, Priority, Tag, TodoItem, WatchEvent};
+use crate::output::{format_watch_dashboard, is_quiet, print_initial_summary, print_watch_event};
+use crate::scanner::{
+    read_text_file, scan_content, scan_directory, scan_directory_cached, scan_file_with_cache,
+    ExcludeDirs, ScanOptions,
+};
 
 /// In-memory index of TODO items grouped by file path.
 pub struct TodoIndex {
     items: HashMap<String, Vec<TodoItem>>,
     pattern: Regex,
+    options: ScanOptions,
     root: PathBuf,
-    exclude_dirs: Vec<String>,
+    exclude_dirs: ExcludeDirs,
     exclude_regexes: Vec<Regex>,
+    max_file_size: u64,
+    /// Scan cache kept in memory for the whole session; `None` with `--no-cache`.
+    cache: Option<ScanCache>,
 }
 
 impl TodoIndex {
     /// Build a new index by performing a full directory scan.
     pub fn new(root: &Path, config: &Config) -> Result<Self> {
-        let pattern = Regex::new(&config.tags_pattern())?;
         let scan = scan_directory(root, config)?;
+        Self::build(root, config, scan.items, None)
+    }
+
+    /// Build a new index from the scan cache, rescanning only files whose
+    /// mtime and content changed. Later updates go through the same cache.
+    pub fn with_cache(root: &Path, config: &Config, mut cache: ScanCache) -> Result<Self> {
+        let scan = scan_directory_cached(root, config, &mut cache)?;
+        Self::build(root, config, scan.result.items, Some(cache))
+    }
 
+    fn build(
+        root: &Path,
+        config: &Config,
+        scanned: Vec<TodoItem>,
+        cache: Option<ScanCache>,
+    ) -> Result<Self> {
+        let pattern = Regex::new(&config.tags_pattern())?;
+
+        // Cached relative deadlines were resolved on the day they were scanned
+        let today = today();
         let mut items: HashMap<String, Vec<TodoItem>> = HashMap::new();
-        for item in scan.items {
+        for mut item in scanned {
+            item.resolve_relative_deadline(&today);
             items.entry(item.file.clone()).or_default().push(item);
         }
 
@@ -44,12 +75,20 @@ impl TodoIndex {
         Ok(Self {
             items,
             pattern,
+            options: ScanOptions::from_config(config),
             root: root.to_path_buf(),
-            exclude_dirs: config.exclude_dirs.clone(),
+            exclude_dirs: ExcludeDirs::new(&config.exclude_dirs),
             exclude_regexes,
+            max_file_size: config.max_file_size(),
+            cache,
         })
     }
 
+    /// The in-memory scan cache, if the index was built with one.
+    pub fn cache(&self) -> Option<&ScanCache> {
+        self.cache.as_ref()
+    }
+
     /// Re-scan a single file and return added/removed items.
     pub fn update_file(&mut self, relative_path: &str) -> Result<FileUpdate> {
         let abs_path = self.root.join(relative_path);
@@ -57,7 +96,10 @@ impl TodoIndex {
         // Check file size before reading to prevent OOM on large files
         let metadata = std::fs::metadata(&abs_path)
             .with_context(|| format!("failed to stat {}", abs_path.display()))?;
-        if metadata.len() > MAX_FILE_SIZE {
+        if metadata.len() > self.max_file_size {
+            if let Some(cache) = self.cache.as_mut() {
+                cache.remove(Path::new(relative_path));
+            }
             let removed = self.items.remove(relative_path).unwrap_or_default();
             return Ok(FileUpdate {
                 added: vec![],
@@ -65,11 +107,26 @@ impl TodoIndex {
             });
         }
 
-        let content = std::fs::read_to_string(&abs_path)
-            .with_context(|| format!("failed to read {}", abs_path.display()))?;
-
-        let scan_result = scan_content(&content, relative_path, &self.pattern);
-        let new_items = scan_result.items;
+        let mut new_items = match self.cache.as_mut() {
+            Some(cache) => scan_file_with_cache(
+                &abs_path,
+                Path::new(relative_path),
+                &metadata,
+                cache,
+                &self.pattern,
+                &self.options,
+            )
+            .with_context(|| format!("failed to read {}", abs_path.display()))?,
+            None => {
+                let content = read_text_file(&abs_path)
+                    .with_context(|| format!("failed to read {}", abs_path.display()))?;
+                scan_content(&content, relative_path, &self.pattern, &self.options).items
+            }
+        };
+        let today = today();
+        for item in &mut new_items {
+            item.resolve_relative_deadline(&today);
+        }
         let old_items = self.items.remove(relative_path).unwrap_or_default();
 
         let old_keys: HashMap<String, &TodoItem> =
@@ -95,8 +152,11 @@ impl TodoIndex {
         Ok(FileUpdate { added, removed })
     }
 
-    /// Remove a file from the index, returning its former items.
+    /// Remove a file from the index and cache, returning its former items.
     pub fn remove_file(&mut self, relative_path: &str) -> Vec<TodoItem> {
+        if let Some(cache) = self.cache.as_mut() {
+            cache.remove(Path::new(relative_path));
+        }
         self.items.remove(relative_path).unwrap_or_default()
     }
 
@@ -110,23 +170,35 @@ impl TodoIndex {
         let mut counts: HashMap<Tag, usize> = HashMap::new();
         for items in self.items.values() {
             for item in items {
-                *counts.entry(item.tag).or_insert(0) += 1;
+                *counts.entry(item.tag.clone()).or_insert(0) += 1;
             }
         }
         let mut result: Vec<(Tag, usize)> = counts.into_iter().collect();
-        result.sort_by(|a, b| b.1.cmp(&a.1));
+        result.sort_by_key(|e| std::cmp::Reverse(e.1));
         result
     }
 
+    /// Urgent then high-priority items, each group in file and line order,
+    /// at most `limit` of them.
+    pub fn top_priority_items(&self, limit: usize) -> Vec<TodoItem> {
+        let mut items: Vec<&TodoItem> = self
+            .items
+            .values()
+            .flatten()
+            .filter(|i| i.priority != Priority::Normal)
+            .collect();
+        items.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.file.cmp(&b.file))
+                .then(a.line.cmp(&b.line))
+        });
+        items.into_iter().take(limit).cloned().collect()
+    }
+
     /// Check if a path should be excluded based on config.
     pub fn should_exclude(&self, relative_path: &str) -> bool {
-        let path = Path::new(relative_path);
-
-        let excluded_by_dir = self.exclude_dirs.iter().any(|dir| {
-            path.components()
-                .any(|c| c.as_os_str().to_str().is_some_and(|s| s == dir))
-        });
-        if excluded_by_dir {
+        if self.exclude_dirs.is_excluded(Path::new(relative_path)) {
             return true;
         }
 
@@ -186,7 +258,103 @@ fn build_watch_event(
     }
 }
 
+/// Run a `--run` hook through the shell with the event JSON on stdin.
+///
+/// Hook failures are reported as warnings and never stop the watch loop.
+/// Returns whether the hook ran and exited successfully.
+pub fn run_hook(command: &str, event: &WatchEvent, cwd: &Path) -> bool {
+    let mut child = match shell_command(command)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("warning: failed to run hook: {}", e);
+            return false;
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let json = serde_json::to_string(event).expect("failed to serialize");
+        // The hook may exit without reading stdin; a broken pipe is not an error
+        let _ = stdin.write_all(json.as_bytes());
+        let _ = stdin.write_all(b"\n");
+    }
+
+    match child.wait() {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            eprintln!("warning: hook exited with {}", status);
+            false
+        }
+        Err(e) => {
+            eprintln!("warning: failed to wait for hook: {}", e);
+            false
+        }
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// Number of urgent and high-priority items shown by `watch --clear`.
+const DASHBOARD_URGENT_ITEMS: usize = 10;
+
+/// Alternate screen used by `watch --clear`. Dropping it shows the cursor
+/// and returns to the normal screen, so the terminal is restored however
+/// the watch loop ends (Ctrl+C or an error).
+struct Dashboard {
+    out: std::io::Stdout,
+}
+
+impl Dashboard {
+    fn enter() -> Result<Self> {
+        let mut out = std::io::stdout();
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)
+            .context("failed to set up the terminal")?;
+        Ok(Self { out })
+    }
+
+    /// Clear the screen and draw the current state.
+    fn draw(&mut self, index: &TodoIndex, last: Option<&WatchEvent>, max: Option<usize>) {
+        let screen = format_watch_dashboard(
+            &index.tag_counts(),
+            index.total_count(),
+            &index.top_priority_items(DASHBOARD_URGENT_ITEMS),
+            last,
+            max,
+        );
+        // Drawing is best effort; a failed frame is replaced by the next one
+        let _ = queue!(
+            self.out,
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        );
+        let _ = self.out.write_all(screen.as_bytes());
+        let _ = self.out.flush();
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        let _ = execute!(self.out, cursor::Show, terminal::LeaveAlternateScreen);
+    }
+}
+
 /// Main watch command entry point.
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_watch(
     root: &Path,
     config: &Config,
@@ -194,18 +362,41 @@ pub fn cmd_watch(
     tag_filter: &[String],
     max: Option<usize>,
     debounce_ms: u64,
+    run: Option<&str>,
+    clear: bool,
+    no_cache: bool,
 ) -> Result<()> {
+    ensure!(
+        !clear || matches!(format, Format::Text),
+        "--clear renders a text dashboard and cannot be combined with --format"
+    );
+
+    // The cache is keyed by the root as given, like the other commands
+    let cache_root = root.to_path_buf();
+
     // Canonicalize root to match paths reported by the OS watcher
     // (e.g., macOS resolves /tmp → /private/tmp)
     let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
 
-    let mut index = TodoIndex::new(&root, config)?;
+    let mut index = if no_cache {
+        TodoIndex::new(&root, config)?
+    } else {
+        TodoIndex::with_cache(&root, config, ScanCache::load_for(&cache_root, config))?
+    };
+    let tag_styles = config.tag_styles();
     let filter_tags: Vec<Tag> = tag_filter
         .iter()
-        .filter_map(|s| s.parse::<Tag>().ok())
+        .filter_map(|s| tag_styles.parse(s))
         .collect();
 
-    print_initial_summary(&index.tag_counts(), index.total_count(), format);
+    let mut dashboard = if clear {
+        let mut dashboard = Dashboard::enter()?;
+        dashboard.draw(&index, None, max);
+        Some(dashboard)
+    } else {
+        print_initial_summary(&index.tag_counts(), index.total_count(), format);
+        None
+    };
 
     let running = Arc::new(AtomicBool::new(true));
     let r = running.clone();
@@ -223,7 +414,9 @@ pub fn cmd_watch(
         .watch(&root, notify::RecursiveMode::Recursive)
         .context("failed to watch directory")?;
 
-    eprintln!("Watching for changes... (Ctrl+C to stop)");
+    if !is_quiet() && dashboard.is_none() {
+        eprintln!("Watching for changes... (Ctrl+C to stop)");
+    }
 
     while running.load(Ordering::SeqCst) {
         match rx.recv_timeout(Duration::from_millis(200)) {
@@ -265,7 +458,14 @@ pub fn cmd_watch(
                         }
                     }
 
-                    print_watch_event(&event, format, max);
+                    match dashboard.as_mut() {
+                        Some(dashboard) => dashboard.draw(&index, Some(&event), max),
+                        None => print_watch_event(&event, format, max),
+                    }
+
+                    if let Some(command) = run {
+                        run_hook(command, &event, &root);
+                    }
                 }
             }
             Ok(Err(_)) => continue,
@@ -274,7 +474,17 @@ pub fn cmd_watch(
         }
     }
 
-    eprintln!("Watching stopped.");
+    // Leave the dashboard before printing anything else
+    drop(dashboard);
+
+    // Best-effort save so the next run starts from the updated cache
+    if let Some(cache) = index.cache() {
+        let _ = cache.save(&cache_root, config.cache_dir(&cache_root).as_deref());
+    }
+
+    if !is_quiet() {
+        eprintln!("Watching stopped.");
+    }
     Ok(())
 }
 
@@ -395,6 +605,76 @@ mod tests {
         assert_eq!(index.total_count(), 1);
     }
 
+    fn setup_cached_index(files: &[(&str, &str)]) -> (TempDir, TodoIndex) {
+        let dir = TempDir::new().unwrap();
+        for (path, content) in files {
+            fs::write(dir.path().join(path), content).unwrap();
+        }
+        let config = Config::default();
+        let cache = ScanCache::new(ScanCache::config_hash(&config));
+        let index = TodoIndex::with_cache(dir.path(), &config, cache).unwrap();
+        (dir, index)
+    }
+
+    #[test]
+    fn test_cached_index_reuses_cache_entries() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("a.rs"), "// TODO: on disk\n").unwrap();
+        let mtime = fs::metadata(dir.path().join("a.rs"))
+            .unwrap()
+            .modified()
+            .unwrap();
+
+        // A matching mtime means the cached items are used without reading
+        let config = Config::default();
+        let mut cache = ScanCache::new(ScanCache::config_hash(&config));
+        cache.insert(
+            "a.rs".into(),
+            [0; 32],
+            vec![crate::test_helpers::helpers::make_item(
+                "a.rs",
+                1,
+                Tag::Fixme,
+                "cached",
+            )],
+            vec![],
+            mtime,
+        );
+        let index = TodoIndex::with_cache(dir.path(), &config, cache).unwrap();
+
+        assert_eq!(index.total_count(), 1);
+        assert_eq!(index.tag_counts(), vec![(Tag::Fixme, 1)]);
+    }
+
+    #[test]
+    fn test_cached_index_update_file_refreshes_cache() {
+        let (dir, mut index) = setup_cached_index(&[("a.rs", "// TODO: one\n")]);
+
+        fs::write(dir.path().join("a.rs"), "// TODO: one\n// FIXME: two\n").unwrap();
+
+        let update = index.update_file("a.rs").unwrap();
+        assert_eq!(update.added.len(), 1);
+        assert_eq!(update.added[0].tag, Tag::Fixme);
+        assert!(update.removed.is_empty());
+
+        let entry = &index.cache().unwrap().entries[Path::new("a.rs")];
+        assert_eq!(entry.items.len(), 2);
+    }
+
+    #[test]
+    fn test_cached_index_remove_file_prunes_cache() {
+        let (dir, mut index) =
+            setup_cached_index(&[("a.rs", "// TODO: gone\n"), ("b.rs", "// TODO: stays\n")]);
+
+        fs::remove_file(dir.path().join("a.rs")).unwrap();
+        let removed = index.remove_file("a.rs");
+
+        assert_eq!(removed.len(), 1);
+        let cache = index.cache().unwrap();
+        assert!(!cache.entries.contains_key(Path::new("a.rs")));
+        assert!(cache.entries.contains_key(Path::new("b.rs")));
+    }
+
     #[test]
     fn test_should_exclude_dirs() {
         let (_dir, _index) = setup_index(&[]);
@@ -639,6 +919,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_top_priority_items_urgent_first() {
+        let (_dir, index) = setup_index(&[
+            ("a.rs", "// TODO: ! high one\n// TODO: normal\n"),
+            ("b.rs", "// BUG: !! urgent one\n// FIXME: ! high two\n"),
+        ]);
+
+        let top = index.top_priority_items(10);
+        let messages: Vec<&str> = top.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, vec!["urgent one", "high one", "high two"]);
+        assert_eq!(index.top_priority_items(1).len(), 1);
+    }
+
     #[test]
     fn test_update_file_error_nonexistent() {
         let (_dir, mut index) = setup_index(&[("a.rs", "// TODO: exists\n")]);
@@ -771,4 +1064,50 @@ mod tests {
         let files = collect_changed_files(&events, dir.path());
         assert!(files.is_empty());
     }
+
+    fn sample_event() -> WatchEvent {
+        WatchEvent {
+            timestamp: "2025-01-01T00:00:00Z".to_string(),
+            file: "main.rs".to_string(),
+            added: vec![crate::test_helpers::helpers::make_item(
+                "main.rs",
+                1,
+                Tag::Todo,
+                "new task",
+            )],
+            removed: vec![],
+            tag_summary: vec![("TODO".to_string(), 1)],
+            total: 1,
+            total_delta: 1,
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_hook_receives_event_on_stdin() {
+        let dir = TempDir::new().unwrap();
+
+        assert!(run_hook("cat > event.json", &sample_event(), dir.path()));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("event.json")).unwrap())
+                .unwrap();
+        assert_eq!(json["file"], "main.rs");
+        assert_eq!(json["added"][0]["message"], "new task");
+        assert_eq!(json["total_delta"], 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_hook_failure_returns_false() {
+        let dir = TempDir::new().unwrap();
+        assert!(!run_hook("exit 3", &sample_event(), dir.path()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_hook_ignoring_stdin_succeeds() {
+        let dir = TempDir::new().unwrap();
+        assert!(run_hook("true", &sample_event(), dir.path()));
+    }
 }
@@ -0,0 +1,27 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::path::Path;
+use std::process::Command;
+
+/// Run `git` in `cwd` and fail the test if it exits unsuccessfully.
+pub fn git(cwd: &Path, args: &[&str]) {
+    git_with_env(cwd, args, &[]);
+}
+
+/// Like `git`, with extra environment variables such as `GIT_AUTHOR_DATE`.
+pub fn git_with_env(cwd: &Path, args: &[&str], envs: &[(&str, &str)]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .envs(envs.iter().copied())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}{}",
+        args,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
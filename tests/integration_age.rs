@@ -0,0 +1,92 @@
+mod common;
+
+use assert_cmd::Command;
+use common::git;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+/// A repo with two committed TODOs plus one in an untracked file.
+fn setup_git_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    git(dir.path(), &["init"]);
+    git(dir.path(), &["config", "user.email", "test@test.com"]);
+    git(dir.path(), &["config", "user.name", "Test"]);
+    fs::write(dir.path().join("main.rs"), "// TODO: one\n// FIXME: two\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "initial"]);
+    fs::write(dir.path().join("new.rs"), "// TODO: untracked\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_age_json_default_buckets() {
+    let dir = setup_git_repo();
+
+    let output = todo_scan()
+        .args([
+            "age",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {"label": "0-7d", "count": 2},
+            {"label": "8-30d", "count": 0},
+            {"label": "31-90d", "count": 0},
+            {"label": "91-365d", "count": 0},
+            {"label": "366d+", "count": 0},
+            {"label": "unknown", "count": 1},
+        ])
+    );
+}
+
+#[test]
+fn test_age_text_custom_buckets() {
+    let dir = setup_git_repo();
+
+    todo_scan()
+        .args([
+            "age",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--buckets",
+            "1d,14d",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"0-1d\s+2  █+").unwrap())
+        .stdout(predicate::str::contains("2-14d"))
+        .stdout(predicate::str::contains("15d+"))
+        .stdout(predicate::str::is_match(r"unknown\s+1").unwrap())
+        .stdout(predicate::str::contains("3 items"));
+}
+
+#[test]
+fn test_age_rejects_unordered_buckets() {
+    let dir = setup_git_repo();
+
+    todo_scan()
+        .args([
+            "age",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--buckets",
+            "30d,7d",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("strictly increasing"));
+}
@@ -1,7 +1,9 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
-use std::process;
 use tempfile::TempDir;
 
 fn todo_scan() -> Command {
@@ -12,23 +14,9 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
     let dir = TempDir::new().unwrap();
     let cwd = dir.path();
 
-    process::Command::new("git")
-        .args(["init"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-
-    process::Command::new("git")
-        .args(["config", "user.email", "test@test.com"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-
-    process::Command::new("git")
-        .args(["config", "user.name", "Test Author"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["config", "user.name", "Test Author"]);
 
     for (path, content) in files {
         let full_path = cwd.join(path);
@@ -38,17 +26,8 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
         fs::write(full_path, content).unwrap();
     }
 
-    process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-
-    process::Command::new("git")
-        .args(["commit", "-m", "initial"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "initial"]);
 
     dir
 }
@@ -377,3 +356,349 @@ fn test_blame_config_stale_threshold() {
         .success()
         .stdout(predicate::str::contains("threshold: 1 days"));
 }
+
+#[test]
+fn test_blame_resolves_relative_deadline_against_commit_date() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO(+1m): revisit\n")]);
+    let cwd = dir.path();
+    git(
+        cwd,
+        &[
+            "commit",
+            "--amend",
+            "--no-edit",
+            "--date",
+            "2020-01-31T12:00:00",
+        ],
+    );
+
+    let output = todo_scan()
+        .args(["blame", "--root", cwd.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entry = &json["entries"][0];
+    assert_eq!(entry["relative_deadline"], "+1m");
+    assert_eq!(entry["deadline"], "2020-02-29");
+}
+
+#[test]
+fn test_blame_json_field_shape() {
+    let dir = setup_git_repo(&[("old.rs", "// TODO: old task\n")]);
+    let cwd = dir.path();
+    git(
+        cwd,
+        &[
+            "commit",
+            "--amend",
+            "--no-edit",
+            "--date",
+            "2020-01-15T12:00:00",
+        ],
+    );
+    fs::write(cwd.join("new.rs"), "// FIXME: new task\n").unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "second"]);
+
+    let output = todo_scan()
+        .args(["blame", "--root", cwd.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    for entry in entries {
+        let blame = &entry["blame"];
+        assert!(blame["author"].is_string());
+        assert!(blame["email"].is_string());
+        assert!(blame["date"].is_string());
+        assert!(blame["age_days"].is_u64());
+        assert!(blame["commit"].is_string());
+        assert!(entry["stale"].is_boolean());
+    }
+
+    assert!(json["total"].is_u64());
+    assert!(json["avg_age_days"].is_u64());
+    assert!(json["stale_count"].is_u64());
+    assert!(json["stale_threshold_days"].is_u64());
+
+    let oldest = &json["oldest"];
+    assert_eq!(oldest["file"], "old.rs");
+    assert_eq!(oldest["line"], 1);
+    assert_eq!(oldest["date"], "2020-01-15");
+    assert!(oldest["age_days"].is_u64());
+
+    let newest = &json["newest"];
+    assert_eq!(newest["file"], "new.rs");
+    assert!(newest["date"].is_string());
+    assert!(newest["age_days"].as_u64().unwrap() < oldest["age_days"].as_u64().unwrap());
+}
+
+#[test]
+fn test_blame_json_oldest_newest_null_when_empty() {
+    let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
+    let cwd = dir.path();
+
+    let output = todo_scan()
+        .args(["blame", "--root", cwd.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["oldest"].is_null());
+    assert!(json["newest"].is_null());
+}
+
+#[test]
+fn test_blame_group_by_author_text() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: first\n// FIXME: second\n")]);
+    let cwd = dir.path();
+
+    todo_scan()
+        .args([
+            "blame",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--group-by",
+            "author",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("AUTHOR"))
+        .stdout(predicate::str::is_match(r"Test Author\s+2\s+0 days\s+0").unwrap())
+        .stdout(predicate::str::contains("2 items by 1 authors"))
+        .stdout(predicate::str::contains("first").not());
+}
+
+#[test]
+fn test_blame_group_by_author_json() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: first\n// FIXME: second\n")]);
+    let cwd = dir.path();
+
+    let output = todo_scan()
+        .args([
+            "blame",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--group-by",
+            "author",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let authors = json["authors"].as_array().unwrap();
+    assert_eq!(authors.len(), 1);
+    assert_eq!(authors[0]["author"], "Test Author");
+    assert_eq!(authors[0]["count"], 2);
+    assert_eq!(authors[0]["avg_age_days"], 0);
+    assert_eq!(authors[0]["stale_count"], 0);
+}
+
+#[test]
+fn test_blame_json_omits_authors_without_group_by() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: first\n")]);
+    let cwd = dir.path();
+
+    let output = todo_scan()
+        .args(["blame", "--root", cwd.to_str().unwrap(), "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("authors").is_none());
+}
+
+/// Repo with one TODO per file, each committed on its own date.
+fn setup_aged_repo() -> TempDir {
+    let dir = setup_git_repo(&[("README.md", "# aged\n")]);
+    let cwd = dir.path();
+    for (file, date) in [
+        ("mid.rs", "2021-06-01T12:00:00"),
+        ("old.rs", "2019-03-01T12:00:00"),
+        ("new.rs", "2023-09-01T12:00:00"),
+    ] {
+        fs::write(cwd.join(file), format!("// TODO: {}\n", file)).unwrap();
+        git(cwd, &["add", "."]);
+        git(cwd, &["commit", "-m", file, "--date", date]);
+    }
+    dir
+}
+
+#[test]
+fn test_blame_oldest_text() {
+    let dir = setup_aged_repo();
+    todo_scan()
+        .args([
+            "blame",
+            "--oldest",
+            "2",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("old.rs"))
+        .stdout(predicate::str::contains("mid.rs"))
+        .stdout(predicate::str::contains("new.rs").not())
+        .stdout(predicate::str::contains("Showing the oldest 2 of 3 items"));
+}
+
+#[test]
+fn test_blame_oldest_json_keeps_full_total() {
+    let dir = setup_aged_repo();
+    let output = todo_scan()
+        .args([
+            "blame",
+            "--oldest",
+            "2",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files: Vec<&str> = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["file"].as_str().unwrap())
+        .collect();
+    assert_eq!(files, vec!["old.rs", "mid.rs"]);
+    assert_eq!(json["total"], 3);
+    assert_eq!(json["newest"]["file"], "new.rs");
+}
+
+#[test]
+fn test_blame_limit_keeps_sort_order() {
+    let dir = setup_aged_repo();
+    todo_scan()
+        .args([
+            "blame",
+            "--limit",
+            "1",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("mid.rs"))
+        .stdout(predicate::str::contains("old.rs").not())
+        .stdout(predicate::str::contains("Showing 1 of 3 items"));
+}
+
+#[test]
+fn test_blame_limit_above_total_reports_plain_count() {
+    let dir = setup_aged_repo();
+    todo_scan()
+        .args([
+            "blame",
+            "--limit",
+            "10",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\n3 items, avg age"));
+}
+
+#[test]
+fn test_blame_oldest_conflicts_with_sort() {
+    let dir = setup_aged_repo();
+    todo_scan()
+        .args([
+            "blame",
+            "--oldest",
+            "1",
+            "--sort",
+            "file",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_path_base_git_root_from_subdirectory() {
+    let dir = setup_git_repo(&[
+        ("crates/core/src/lib.rs", "// TODO: core work\n"),
+        ("README.md", "# readme\n"),
+    ]);
+    let sub = dir.path().join("crates/core");
+
+    todo_scan()
+        .args(["list", "--root", sub.to_str().unwrap(), "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"file\": \"src/lib.rs\""));
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            sub.to_str().unwrap(),
+            "--path-base",
+            "git-root",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "\"file\": \"crates/core/src/lib.rs\"",
+        ));
+
+    // Blame must resolve the re-based path against the git top-level
+    todo_scan()
+        .args([
+            "blame",
+            "--root",
+            sub.to_str().unwrap(),
+            "--path-base",
+            "git-root",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("crates/core/src/lib.rs"))
+        .stdout(predicate::str::contains("@Test Author"));
+}
+
+#[test]
+fn test_path_base_git_root_from_config() {
+    let dir = setup_git_repo(&[
+        ("app/.todo-scan.toml", "[scan]\npath_base = \"git-root\"\n"),
+        ("app/main.rs", "// FIXME: from config\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().join("app").to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("app/main.rs"));
+}
+
+#[test]
+fn test_path_base_git_root_outside_repo_fails() {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("main.rs"), "// TODO: no repo\n").unwrap();
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--path-base",
+            "git-root",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires a git repository"));
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -147,21 +150,9 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
     let dir = TempDir::new().unwrap();
     let cwd = dir.path();
 
-    std::process::Command::new("git")
-        .args(["init"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.email", "test@test.com"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.name", "Test"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["config", "user.name", "Test"]);
 
     for (path, content) in files {
         let full_path = cwd.join(path);
@@ -171,16 +162,8 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
         fs::write(full_path, content).unwrap();
     }
 
-    std::process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["commit", "-m", "initial"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "initial"]);
 
     dir
 }
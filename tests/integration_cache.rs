@@ -194,3 +194,121 @@ fn test_check_command_works_with_cache() {
         .assert()
         .success();
 }
+
+/// Every cache file under `dir`, at `<dir>/<repo-hash>/scan-cache.bin`.
+fn cache_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .filter_map(|e| Some(e.ok()?.path().join("scan-cache.bin")))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+#[test]
+fn test_cache_dir_flag_stores_cache_there() {
+    let dir = setup_project(&[("main.rs", "// TODO: first task\n")]);
+    let other = setup_project(&[("lib.rs", "// FIXME: other task\n")]);
+    let cache = TempDir::new().unwrap();
+    let cache_dir = cache.path().to_str().unwrap();
+
+    for project in [&dir, &other] {
+        todo_scan()
+            .args([
+                "list",
+                "--cache-dir",
+                cache_dir,
+                "--root",
+                project.path().to_str().unwrap(),
+            ])
+            .assert()
+            .success();
+    }
+
+    // One cache per project, and none written into the project trees
+    assert_eq!(cache_files(cache.path()).len(), 2);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    // Warm runs read the same results back
+    todo_scan()
+        .args([
+            "list",
+            "--cache-dir",
+            cache_dir,
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("first task"))
+        .stdout(predicate::str::contains("other task").not());
+}
+
+#[test]
+fn test_cache_path_config_is_relative_to_root() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: first task\n"),
+        (".todo-scan.toml", "[cache]\npath = \".cache/todo-scan\"\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("first task"));
+
+    assert_eq!(cache_files(&dir.path().join(".cache/todo-scan")).len(), 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_read_only_root_scans_with_cache() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = setup_project(&[("main.rs", "// TODO: first task\n")]);
+    let cache = TempDir::new().unwrap();
+    fs::set_permissions(dir.path(), fs::Permissions::from_mode(0o555)).unwrap();
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--cache-dir",
+            cache.path().to_str().unwrap(),
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    fs::set_permissions(dir.path(), fs::Permissions::from_mode(0o755)).unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("first task"));
+    assert_eq!(cache_files(cache.path()).len(), 1);
+}
+
+#[test]
+fn test_comment_prefix_change_invalidates_cache() {
+    let dir = setup_project(&[("build.bat", "REM TODO: port to PowerShell\n")]);
+    let cache_dir = TempDir::new().unwrap();
+    let list = || {
+        todo_scan()
+            .args([
+                "list",
+                "--root",
+                dir.path().to_str().unwrap(),
+                "--cache-dir",
+                cache_dir.path().to_str().unwrap(),
+            ])
+            .assert()
+            .success()
+    };
+
+    list().stdout(predicate::str::contains("port to PowerShell").not());
+    fs::write(
+        dir.path().join(".todo-scan.toml"),
+        "[scan]\ncomment_prefixes = [\"REM \"]\n",
+    )
+    .unwrap();
+    list().stdout(predicate::str::contains("port to PowerShell"));
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -164,6 +167,30 @@ fn test_check_github_actions_format_fail() {
         .stdout(predicate::str::contains("::error::todo-scan check: FAIL"));
 }
 
+#[test]
+fn test_check_azure_devops_format_fail() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n// TODO: two\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "azure-devops",
+            "--max",
+            "1",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "##vso[task.logissue type=error;code=max;]",
+        ))
+        .stdout(predicate::str::contains(
+            "##vso[task.logissue type=error;]todo-scan check: FAIL",
+        ));
+}
+
 #[test]
 fn test_check_sarif_format() {
     let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
@@ -230,21 +257,9 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
     let dir = TempDir::new().unwrap();
     let cwd = dir.path();
 
-    std::process::Command::new("git")
-        .args(["init"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.email", "test@test.com"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.name", "Test"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["config", "user.name", "Test"]);
 
     for (path, content) in files {
         let full_path = cwd.join(path);
@@ -254,16 +269,8 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
         fs::write(full_path, content).unwrap();
     }
 
-    std::process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["commit", "-m", "initial"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "initial"]);
 
     dir
 }
@@ -323,6 +330,83 @@ fn test_check_since_max_new_fails() {
         .stdout(predicate::str::contains("exceeds max_new"));
 }
 
+#[test]
+fn test_check_staged_from_subdirectory() {
+    let dir = setup_git_repo(&[
+        ("main.rs", "fn main() {}\n"),
+        ("pkg/lib.rs", "fn lib() {}\n"),
+    ]);
+    let cwd = dir.path();
+    let pkg = cwd.join("pkg");
+
+    // Staged in the package, then dropped again from the working tree
+    fs::write(pkg.join("lib.rs"), "// TODO: staged\nfn lib() {}\n").unwrap();
+    git(cwd, &["add", "pkg/lib.rs"]);
+    fs::write(pkg.join("lib.rs"), "fn lib() {}\n").unwrap();
+    // Staged outside the package, so never counted
+    fs::write(cwd.join("main.rs"), "// FIXME: outside\nfn main() {}\n").unwrap();
+    git(cwd, &["add", "main.rs"]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--staged",
+            "--max-new",
+            "0",
+            "--root",
+            pkg.to_str().unwrap(),
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "New TODOs (1) exceeds max_new (0)",
+        ));
+}
+
+#[test]
+fn test_check_max_new_defaults_to_remote_default_branch() {
+    let upstream = setup_git_repo(&[("main.rs", "// TODO: existing\nfn main() {}\n")]);
+    let dir = TempDir::new().unwrap();
+    git(
+        dir.path(),
+        &["clone", "-q", upstream.path().to_str().unwrap(), "clone"],
+    );
+    let cwd = dir.path().join("clone");
+
+    fs::write(
+        cwd.join("main.rs"),
+        "// TODO: existing\n// TODO: new one\n// TODO: new two\nfn main() {}\n",
+    )
+    .unwrap();
+
+    todo_scan()
+        .args(["check", "--root", cwd.to_str().unwrap(), "--max-new", "1"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "New TODOs (2) exceeds max_new (1)",
+        ));
+}
+
+#[test]
+fn test_check_max_new_without_remote_errors() {
+    let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-new",
+            "1",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "could not determine the default branch",
+        ));
+}
+
 // --- Expired deadline tests ---
 
 #[test]
@@ -361,6 +445,107 @@ fn test_check_no_expired_flag_ignores_deadline() {
         .stdout(predicate::str::contains("PASS"));
 }
 
+#[test]
+fn test_check_require_deadline() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// FIXME(alice, 2099-12-31): dated\n// FIXME: undated\n// TODO: not required\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--require-deadline",
+            "FIXME,BUG",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("require_deadline"))
+        .stdout(predicate::str::contains("main.rs:2"))
+        .stdout(predicate::str::contains("FIXME(author, YYYY-MM-DD)"))
+        .stdout(predicate::str::contains("main.rs:1").not());
+}
+
+#[test]
+fn test_check_require_deadline_from_config_passes() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[check]\nrequire_deadline = [\"BUG\"]\n"),
+        ("main.rs", "// BUG(2099-01-01): dated\n// TODO: free\n"),
+    ]);
+
+    todo_scan()
+        .args(["check", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"));
+}
+
+#[test]
+fn test_check_block_priority_urgent_present() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO!!: ship the fix
+// TODO!: soon
+// FIXME!!: also urgent
+",
+    )]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--block-priority",
+            "urgent",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("block_priority"))
+        .stdout(predicate::str::contains("main.rs:1"))
+        .stdout(predicate::str::contains("main.rs:3"))
+        .stdout(predicate::str::contains("main.rs:2").not());
+}
+
+#[test]
+fn test_check_block_priority_urgent_absent() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO!: soon
+// TODO: later
+",
+    )]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--block-priority",
+            "urgent",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"));
+}
+
+#[test]
+fn test_check_block_priority_from_config() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[check]\nblock_priority = \"high\"\n"),
+        ("main.rs", "// TODO!: soon\n// TODO: later\n"),
+    ]);
+
+    todo_scan()
+        .args(["check", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "Blocked high priority TODO found in main.rs:1",
+        ));
+}
+
 #[test]
 fn test_check_expired_author_and_date() {
     let dir = setup_project(&[("main.rs", "// TODO(alice, 2020-06-01): overdue task\n")]);
@@ -385,6 +570,35 @@ fn test_check_expired_quarter_format() {
         .stdout(predicate::str::contains("expired"));
 }
 
+/// Rewrite HEAD's author date so blame reports an old commit.
+fn backdate_head(cwd: &std::path::Path, date: &str) {
+    git(cwd, &["commit", "--amend", "--no-edit", "--date", date]);
+}
+
+#[test]
+fn test_check_expired_relative_deadline_uses_blame_date() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO(+14d): revisit\n")]);
+    backdate_head(dir.path(), "2020-01-01T12:00:00");
+
+    todo_scan()
+        .args(["check", "--root", dir.path().to_str().unwrap(), "--expired"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("expired"))
+        .stdout(predicate::str::contains("2020-01-15"));
+}
+
+#[test]
+fn test_check_expired_relative_deadline_without_git_uses_today() {
+    let dir = setup_project(&[("main.rs", "// TODO(alice, +2w): revisit\n")]);
+
+    todo_scan()
+        .args(["check", "--root", dir.path().to_str().unwrap(), "--expired"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"));
+}
+
 #[test]
 fn test_check_max_does_not_count_ignored() {
     // 3 TODOs total, but 1 is ignored via todo-scan:ignore
@@ -424,3 +638,371 @@ fn test_check_max_fails_without_ignore() {
         .code(1)
         .stdout(predicate::str::contains("FAIL"));
 }
+
+#[test]
+fn test_check_max_age_fails_on_old_todo() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: ancient\n")]);
+    backdate_head(dir.path(), "2020-01-01T12:00:00");
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-age",
+            "180d",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("max_age"))
+        .stdout(predicate::str::contains("main.rs:1"))
+        .stdout(predicate::str::contains("Test"));
+}
+
+#[test]
+fn test_check_max_age_passes_on_recent_todo() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: fresh\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-age",
+            "180d",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_check_max_age_ignores_untracked_files() {
+    let dir = setup_project(&[("main.rs", "// TODO: not in git\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-age",
+            "0d",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_check_max_age_invalid_duration() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-age",
+            "six months",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid duration"));
+}
+
+// --- Exit codes ---
+
+#[test]
+fn test_check_exit_zero_reports_but_passes() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n// TODO: two\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max",
+            "1",
+            "--exit-zero",
+        ])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("FAIL"))
+        .stdout(predicate::str::contains("exceeds max"));
+}
+
+#[test]
+fn test_check_invalid_config_exits_2() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: task\n"),
+        (".todo-scan.toml", "tags = [\n"),
+    ]);
+
+    todo_scan()
+        .args(["check", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid configuration"));
+}
+
+#[test]
+fn test_check_missing_config_file_exits_2() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--config",
+            dir.path().join("missing.toml").to_str().unwrap(),
+        ])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_check_unknown_since_ref_exits_3() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--since",
+            "no-such-ref",
+        ])
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_check_due_soon_warns_without_failing() {
+    // Untracked files resolve relative deadlines against today
+    let dir = setup_project(&[("main.rs", "// TODO(+3d): ship the fix\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--due-soon",
+            "7d",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"))
+        .stdout(predicate::str::contains("due_soon"))
+        .stdout(predicate::str::contains("is due in 3 days"));
+}
+
+#[test]
+fn test_check_due_soon_fails_flag() {
+    let dir = setup_project(&[("main.rs", "// TODO(+3d): ship the fix\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--due-soon",
+            "7d",
+            "--due-soon-fails",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("FAIL"))
+        .stdout(predicate::str::contains("is due in 3 days"));
+}
+
+#[test]
+fn test_check_due_soon_ignores_far_future() {
+    let dir = setup_project(&[("main.rs", "// TODO(2099-12-31): far future task\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--due-soon",
+            "7d",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"passed\": true"))
+        .stdout(predicate::str::contains("warnings").not());
+}
+
+#[test]
+fn test_check_due_soon_from_config() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO(+1d): ship the fix\n"),
+        (
+            ".todo-scan.toml",
+            "[check]\ndue_soon = \"2d\"\ndue_soon_fails = true\n",
+        ),
+    ]);
+
+    todo_scan()
+        .args(["check", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("is due in 1 day"));
+}
+
+#[test]
+fn test_check_due_soon_invalid_duration() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--due-soon",
+            "soon",
+        ])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_check_file_limits_from_config() {
+    let dir = setup_project(&[
+        ("src/legacy/old.rs", "// TODO: a\n// TODO: b\n// TODO: c\n"),
+        ("src/new.rs", "// TODO: a\n// TODO: b\n"),
+        (
+            ".todo-scan.toml",
+            "[check]\nfile_max = 1\n\n[[check.file_limits]]\npath = \"src/legacy/**\"\nmax = 3\n",
+        ),
+    ]);
+
+    todo_scan()
+        .args(["check", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("file_max"))
+        .stdout(predicate::str::contains("src/new.rs has 2 TODOs (max: 1)"))
+        .stdout(predicate::str::contains("old.rs").not());
+}
+
+#[test]
+fn test_check_junit_failures_match_violations() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: one\n// BUG: two\n// FIXME: <three> & \"four\"\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+    let args = ["check", "--max", "1", "--block-tags", "BUG", "--root", root];
+
+    let output = todo_scan()
+        .args(args)
+        .args(["--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let violations = json["violations"].as_array().unwrap().len();
+    assert_eq!(violations, 2);
+
+    let output = todo_scan()
+        .args(args)
+        .args(["--format", "junit"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let xml = String::from_utf8(output.stdout).unwrap();
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    assert_eq!(
+        doc.root_element().attribute("failures"),
+        Some(violations.to_string().as_str())
+    );
+    let rules: Vec<&str> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("failure"))
+        .map(|n| n.attribute("type").unwrap())
+        .collect();
+    assert_eq!(rules, vec!["block_tags", "max"]);
+}
+
+#[test]
+fn test_check_junit_pass() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n")]);
+    let output = todo_scan()
+        .args([
+            "check",
+            "--max",
+            "5",
+            "--expired",
+            "--format",
+            "junit",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let xml = String::from_utf8(output.stdout).unwrap();
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    assert_eq!(doc.root_element().attribute("failures"), Some("0"));
+    let passed: Vec<&str> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("testcase"))
+        .map(|n| n.attribute("classname").unwrap())
+        .collect();
+    assert_eq!(
+        passed,
+        vec!["todo-scan.check.max", "todo-scan.check.expired"]
+    );
+}
+
+#[test]
+fn test_check_config_from_stdin() {
+    let dir = setup_project(&[("main.rs", "// TODO: one task\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--config",
+            "-",
+        ])
+        .write_stdin("[check]\nmax = 0\n")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("FAIL"));
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--config",
+            "-",
+        ])
+        .write_stdin("[check]\nmax = 5\n")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_check_config_from_stdin_reports_parse_errors() {
+    let dir = setup_project(&[("main.rs", "// TODO: one task\n")]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--config",
+            "-",
+        ])
+        .write_stdin("[check]\nmaxx = 0\n")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "Failed to parse config from stdin",
+        ))
+        .stderr(predicate::str::contains("unknown key `maxx`"));
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -116,6 +119,26 @@ fn test_clean_no_check_exit_0_even_with_violations() {
         .code(0);
 }
 
+#[test]
+fn test_clean_check_exit_zero_with_violations() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: same message\n"),
+        ("b.rs", "// TODO: same message\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "clean",
+            "--check",
+            "--exit-zero",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("FAIL"));
+}
+
 // --- Output formats ---
 
 #[test]
@@ -342,3 +365,192 @@ duplicates = false
         .success()
         .stdout(predicate::str::contains("PASS"));
 }
+
+// --- Issue provider ---
+
+#[test]
+fn test_clean_gitlab_provider_warns_without_glab() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "[clean]\nprovider = \"gitlab\"\nproject = \"group/repo\"\n",
+        ),
+        ("main.rs", "// TODO: fix login #12\n"),
+    ]);
+    let empty_path = TempDir::new().unwrap();
+
+    todo_scan()
+        .env("PATH", empty_path.path())
+        .args(["clean", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "glab CLI not found, skipping stale issue detection",
+        ));
+}
+
+#[test]
+fn test_clean_quiet_suppresses_missing_cli_warning() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[clean]\nprovider = \"gitlab\"\n"),
+        ("main.rs", "// TODO: fix login #12\n"),
+    ]);
+    let empty_path = TempDir::new().unwrap();
+
+    todo_scan()
+        .env("PATH", empty_path.path())
+        .args(["clean", "--quiet", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PASS"))
+        .stdout(predicate::str::contains("items checked").not())
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_clean_unknown_provider_is_config_error() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[clean]\nprovider = \"jira\"\n"),
+        ("main.rs", "// TODO: task\n"),
+    ]);
+
+    todo_scan()
+        .args(["clean", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unknown variant `jira`"));
+}
+
+// --- Fixes ---
+
+/// A committed project whose `#42` is closed according to a fake `gh` on
+/// PATH. Returns the project and the directory holding `gh`.
+#[cfg(unix)]
+fn setup_stale_repo(main_rs: &str) -> (TempDir, TempDir) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = setup_project(&[("main.rs", main_rs)]);
+    git(dir.path(), &["init", "-q"]);
+    git(dir.path(), &["config", "user.name", "test"]);
+    git(dir.path(), &["config", "user.email", "test@example.com"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "init"]);
+
+    let bin = TempDir::new().unwrap();
+    let gh = bin.path().join("gh");
+    fs::write(
+        &gh,
+        "#!/bin/sh\ncase \"$1\" in\n  issue) echo '{\"state\":\"CLOSED\",\"closedAt\":null}' ;;\n  *) echo 'gh version 2.0.0' ;;\nesac\n",
+    )
+    .unwrap();
+    fs::set_permissions(&gh, fs::Permissions::from_mode(0o755)).unwrap();
+    (dir, bin)
+}
+
+#[cfg(unix)]
+fn path_with(bin: &TempDir) -> String {
+    format!(
+        "{}:{}",
+        bin.path().display(),
+        std::env::var("PATH").unwrap_or_default()
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn test_clean_fix_removes_stale_lines() {
+    let content =
+        "fn main() {\r\n    // TODO: retry login #42\r\n    run(); // TODO: inline #42\r\n}\r\n";
+    let (dir, bin) = setup_stale_repo(content);
+
+    todo_scan()
+        .env("PATH", path_with(&bin))
+        .args(["clean", "--fix", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("stale_issue"))
+        .stderr(predicate::str::contains(
+            "main.rs:3: left in place: code shares the line",
+        ))
+        .stderr(predicate::str::contains(
+            "Removed 1 stale TODO line(s) from 1 file(s)",
+        ));
+
+    let fixed = fs::read_to_string(dir.path().join("main.rs")).unwrap();
+    assert_eq!(
+        fixed,
+        "fn main() {\r\n    run(); // TODO: inline #42\r\n}\r\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_clean_fix_dry_run_prints_diff() {
+    let content = "fn main() {\n    // TODO: retry login #42\n}\n";
+    let (dir, bin) = setup_stale_repo(content);
+
+    todo_scan()
+        .env("PATH", path_with(&bin))
+        .args([
+            "clean",
+            "--fix",
+            "--dry-run",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "--- a/main.rs\n+++ b/main.rs\n@@ -1,3 +1,2 @@\n fn main() {\n-    // TODO: retry login #42\n }\n",
+        ));
+
+    assert_eq!(
+        fs::read_to_string(dir.path().join("main.rs")).unwrap(),
+        content
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_clean_fix_refuses_dirty_tree() {
+    let (dir, bin) = setup_stale_repo("// TODO: retry login #42\n");
+    fs::write(
+        dir.path().join("main.rs"),
+        "// TODO: retry login #42\n// edited\n",
+    )
+    .unwrap();
+
+    todo_scan()
+        .env("PATH", path_with(&bin))
+        .args(["clean", "--fix", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("uncommitted changes"));
+
+    todo_scan()
+        .env("PATH", path_with(&bin))
+        .args([
+            "clean",
+            "--fix",
+            "--allow-dirty",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("main.rs")).unwrap(),
+        "// edited\n"
+    );
+}
+
+#[test]
+fn test_clean_dry_run_requires_fix() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args(["clean", "--dry-run", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--fix"));
+}
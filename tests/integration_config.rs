@@ -0,0 +1,113 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+fn setup_project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(full_path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_config_validate_accepts_valid_file() {
+    let dir = setup_project(&[(".todo-scan.toml", "[scan]\nthreads = 2\n")]);
+
+    todo_scan()
+        .args(["config", "validate", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".todo-scan.toml: valid"));
+}
+
+#[test]
+fn test_config_validate_without_config_file() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args(["config", "validate", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No .todo-scan.toml found"));
+}
+
+#[test]
+fn test_config_validate_reports_unknown_key_with_suggestion() {
+    let dir = setup_project(&[(
+        ".todo-scan.toml",
+        "[check]\nmax = 10\nblock_tag = [\"BUG\"]\n",
+    )]);
+
+    todo_scan()
+        .args(["config", "validate", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "unknown key `block_tag` in [check] at line 3 (did you mean `block_tags`?)",
+        ));
+}
+
+#[test]
+fn test_config_validate_explicit_path_json() {
+    let dir = setup_project(&[("custom.toml", "tags = [\"TODO\"]\n")]);
+    let path = dir.path().join("custom.toml");
+
+    let output = todo_scan()
+        .args([
+            "config",
+            "validate",
+            "--format",
+            "json",
+            "--config",
+            path.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["valid"], true);
+    assert_eq!(json["path"], path.to_str().unwrap());
+}
+
+#[test]
+fn test_unknown_key_fails_other_commands_too() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "exclude_dir = [\"vendor\"]\n"),
+        ("main.rs", "// TODO: task\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("did you mean `exclude_dirs`?"));
+}
+
+#[test]
+fn test_config_validate_reads_stdin() {
+    let dir = TempDir::new().unwrap();
+
+    todo_scan()
+        .args([
+            "config",
+            "validate",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--config",
+            "-",
+        ])
+        .write_stdin("[check]\nmax = 3\n")
+        .assert()
+        .success()
+        .stdout("stdin: valid\n");
+}
@@ -186,3 +186,85 @@ fn test_context_resolves_stable_id() {
         .stdout(predicate::str::contains("main.rs:3"))
         .stdout(predicate::str::contains("TODO: fix this"));
 }
+
+#[test]
+fn test_context_highlight_disabled_when_not_a_tty() {
+    let dir = setup_project(&[("main.rs", "fn main() {\n    // TODO: fix this\n}\n")]);
+
+    todo_scan()
+        .args([
+            "context",
+            "main.rs:2",
+            "--highlight",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO: fix this"))
+        .stdout(predicate::str::contains("fn main() {"))
+        .stdout(predicate::str::contains("\x1b[38;2;").not());
+}
+
+const FUNCTION_SRC: &str = "use std::io;\n\nfn long() {\n    let a = 1;\n    let b = 2;\n    let c = 3;\n    // TODO: deep inside\n    let d = 4;\n    let e = 5;\n    let f = 6;\n}\n";
+
+#[test]
+fn test_context_function_mode_json() {
+    let dir = setup_project(&[("main.rs", FUNCTION_SRC)]);
+
+    let output = todo_scan()
+        .args([
+            "context",
+            "main.rs:7",
+            "-C",
+            "1",
+            "--context-mode",
+            "function",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["mode"], "function");
+    assert_eq!(json["before"][0]["line_number"], 3);
+    assert_eq!(
+        json["after"].as_array().unwrap().last().unwrap()["line_number"],
+        11
+    );
+}
+
+#[test]
+fn test_list_function_mode_enables_context() {
+    let dir = setup_project(&[
+        ("main.rs", FUNCTION_SRC),
+        ("notes.txt", "a\n// TODO: plain\nb\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--context-mode",
+            "function",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    let rust = items.iter().find(|i| i["file"] == "main.rs").unwrap();
+    assert_eq!(rust["context"]["mode"], "function");
+    assert_eq!(rust["context"]["before"].as_array().unwrap().len(), 4);
+    // Unknown languages fall back to line context
+    let txt = items.iter().find(|i| i["file"] == "notes.txt").unwrap();
+    assert_eq!(txt["context"]["mode"], "lines");
+}
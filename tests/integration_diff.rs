@@ -1,7 +1,9 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
-use std::process;
 use tempfile::TempDir;
 
 fn todo_scan() -> Command {
@@ -13,23 +15,9 @@ fn setup_git_repo(initial_files: &[(&str, &str)]) -> TempDir {
     let cwd = dir.path();
 
     // Initialize git repo
-    process::Command::new("git")
-        .args(["init"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-
-    process::Command::new("git")
-        .args(["config", "user.email", "test@test.com"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-
-    process::Command::new("git")
-        .args(["config", "user.name", "Test"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["config", "user.name", "Test"]);
 
     // Create initial files and commit
     for (path, content) in initial_files {
@@ -40,17 +28,8 @@ fn setup_git_repo(initial_files: &[(&str, &str)]) -> TempDir {
         fs::write(full_path, content).unwrap();
     }
 
-    process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-
-    process::Command::new("git")
-        .args(["commit", "-m", "initial"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "initial"]);
 
     dir
 }
@@ -110,6 +89,56 @@ fn test_diff_json_format() {
         .stdout(predicate::str::contains("\"tag\": \"FIXME\""));
 }
 
+#[test]
+fn test_diff_net_change_and_base_total() {
+    let dir = setup_git_repo(&[
+        (
+            "main.rs",
+            "// TODO: old one
+// TODO: old two
+fn main() {}
+",
+        ),
+        (
+            "lib.rs",
+            "// FIXME: untouched
+",
+        ),
+    ]);
+    let cwd = dir.path();
+
+    fs::write(
+        cwd.join("main.rs"),
+        "fn main() {}
+",
+    )
+    .unwrap();
+
+    todo_scan()
+        .args(["diff", "HEAD", "--root", cwd.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "+0 -2 (base: HEAD) (net -2, base 3)",
+        ));
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["net_change"], -2);
+    assert_eq!(json["base_total"], 3);
+    assert!(json.get("base_tag_counts").is_none());
+}
+
 #[test]
 fn test_diff_no_changes() {
     let dir = setup_git_repo(&[("main.rs", "// TODO: existing\nfn main() {}\n")]);
@@ -177,6 +206,27 @@ fn test_diff_deleted_file() {
         .stdout(predicate::str::contains("this will be removed"));
 }
 
+#[test]
+fn test_diff_detects_changes_committed_since_base() {
+    let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
+    let cwd = dir.path();
+
+    git(cwd, &["tag", "base"]);
+    // Committed after the base, so the working tree itself is clean
+    fs::write(
+        cwd.join("main.rs"),
+        "// TODO: committed later\nfn main() {}\n",
+    )
+    .unwrap();
+    git(cwd, &["commit", "-am", "add todo"]);
+
+    todo_scan()
+        .args(["diff", "base", "--root", cwd.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("committed later"));
+}
+
 #[test]
 fn test_diff_new_untracked_file() {
     let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
@@ -308,6 +358,105 @@ fn test_diff_github_actions_format() {
         .stdout(predicate::str::contains("::notice::todo-scan diff: +1 -0"));
 }
 
+#[test]
+fn test_diff_azure_devops_format() {
+    let dir = setup_git_repo(&[("main.rs", "// HACK: old workaround\nfn main() {}\n")]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("main.rs"), "// TODO: new feature\nfn main() {}\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--format",
+            "azure-devops",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "##vso[task.logissue type=warning;sourcepath=main.rs;linenumber=1;code=TODO;][TODO] new feature",
+        ))
+        .stdout(predicate::str::contains(
+            "Removed main.rs:1: [HACK] old workaround",
+        ))
+        .stdout(predicate::str::contains("todo-scan diff: +1 -1"));
+}
+
+#[test]
+fn test_diff_stat_text_sorted_by_churn() {
+    let dir = setup_git_repo(&[
+        ("a.rs", "// TODO: old a\n"),
+        ("src/busy.rs", "// TODO: old one\n// FIXME: old two\n"),
+    ]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("a.rs"), "// TODO: old a\n// TODO: new a\n").unwrap();
+    fs::write(cwd.join("src/busy.rs"), "// HACK: new busy\n").unwrap();
+
+    todo_scan()
+        .args(["diff", "HEAD", "--stat", "--root", cwd.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            " src/busy.rs | +1 -2\n a.rs        | +1 -0\n2 files changed, +2 -2 (net +0)\n",
+        ));
+}
+
+#[test]
+fn test_diff_stat_json() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: old task\nfn main() {}\n")]);
+    let cwd = dir.path();
+
+    fs::write(
+        cwd.join("main.rs"),
+        "// TODO: new task\n// BUG: new bug\nfn main() {}\n",
+    )
+    .unwrap();
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--stat",
+            "--format",
+            "json",
+            "--root",
+            cwd.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json["files"],
+        serde_json::json!([{"file": "main.rs", "added": 2, "removed": 1}])
+    );
+    assert_eq!(json["added_count"], 2);
+    assert_eq!(json["removed_count"], 1);
+    assert!(json.get("entries").is_none());
+}
+
+#[test]
+fn test_diff_stat_conflicts_with_template() {
+    let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--stat",
+            "--template",
+            "{{file}}",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .code(2);
+}
+
 #[test]
 fn test_diff_sarif_format() {
     let dir = setup_git_repo(&[("main.rs", "fn main() {}\n")]);
@@ -482,6 +631,105 @@ fn test_diff_tag_filter() {
     assert_eq!(entries[0]["item"]["tag"].as_str().unwrap(), "FIXME");
     assert_eq!(json["added_count"].as_u64().unwrap(), 1);
     assert_eq!(json["removed_count"].as_u64().unwrap(), 0);
+    assert_eq!(json["net_change"].as_i64().unwrap(), 1);
+    // The base commit had no FIXMEs
+    assert_eq!(json["base_total"].as_u64().unwrap(), 0);
+}
+
+#[test]
+fn test_diff_only_added() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: old task\nfn main() {}\n")]);
+    let cwd = dir.path();
+
+    fs::write(
+        cwd.join("main.rs"),
+        "// TODO: new task\n// FIXME: new fix\nfn main() {}\n",
+    )
+    .unwrap();
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--only",
+            "added",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries
+        .iter()
+        .all(|e| e["status"].as_str().unwrap() == "added"));
+    assert_eq!(json["added_count"].as_u64().unwrap(), 2);
+    assert_eq!(json["removed_count"].as_u64().unwrap(), 0);
+}
+
+#[test]
+fn test_diff_only_removed_with_tag() {
+    let dir = setup_git_repo(&[(
+        "main.rs",
+        "// TODO: old task\n// FIXME: old fix\nfn main() {}\n",
+    )]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("main.rs"), "// HACK: new hack\nfn main() {}\n").unwrap();
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--only",
+            "removed",
+            "--tag",
+            "FIXME",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let entries = json["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["status"].as_str().unwrap(), "removed");
+    assert_eq!(entries[0]["item"]["tag"].as_str().unwrap(), "FIXME");
+    assert_eq!(json["added_count"].as_u64().unwrap(), 0);
+    assert_eq!(json["removed_count"].as_u64().unwrap(), 1);
+}
+
+#[test]
+fn test_diff_only_added_github_actions() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: old task\nfn main() {}\n")]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("main.rs"), "// FIXME: new fix\nfn main() {}\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--only",
+            "added",
+            "--format",
+            "github-actions",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("new fix"))
+        .stdout(predicate::str::contains("old task").not());
 }
 
 #[test]
@@ -560,3 +808,185 @@ fn test_diff_full_detail_auto_context_json() {
         "full detail should include match_key"
     );
 }
+
+// --- Ref range (BASE..HEAD) ---
+
+#[test]
+fn test_diff_detects_removal_committed_after_base() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: keep\n// FIXME: drop me\n")]);
+    let cwd = dir.path();
+    fs::write(cwd.join("main.rs"), "// TODO: keep\n").unwrap();
+    git(cwd, &["commit", "-am", "drop fixme"]);
+
+    todo_scan()
+        .args(["diff", "HEAD~1", "--root", cwd.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("drop me"))
+        .stdout(predicate::str::contains("+0 -1"));
+}
+
+fn setup_two_releases() -> TempDir {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: shipped in v1\nfn main() {}\n")]);
+    let cwd = dir.path();
+    git(cwd, &["tag", "v1.0"]);
+    fs::write(cwd.join("main.rs"), "// FIXME: added in v2\nfn main() {}\n").unwrap();
+    git(cwd, &["commit", "-am", "v2"]);
+    git(cwd, &["tag", "v2.0"]);
+    // Uncommitted change that a ref range must ignore
+    fs::write(cwd.join("main.rs"), "// HACK: local only\nfn main() {}\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_diff_ref_range_text() {
+    let dir = setup_two_releases();
+
+    todo_scan()
+        .args(["diff", "v1.0..v2.0", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("added in v2"))
+        .stdout(predicate::str::contains("shipped in v1"))
+        .stdout(predicate::str::contains("local only").not())
+        .stdout(predicate::str::contains("+1 -1 (base: v1.0, head: v2.0)"));
+}
+
+#[test]
+fn test_diff_ref_range_json_includes_head_ref() {
+    let dir = setup_two_releases();
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "v1.0..v2.0",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["base_ref"], "v1.0");
+    assert_eq!(json["head_ref"], "v2.0");
+    assert_eq!(json["added_count"], 1);
+    assert_eq!(json["removed_count"], 1);
+}
+
+#[test]
+fn test_diff_single_ref_omits_head_ref() {
+    let dir = setup_two_releases();
+
+    let output = todo_scan()
+        .args([
+            "diff",
+            "v2.0",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("head_ref").is_none());
+    // Working tree is compared: the local edit shows up
+    assert!(String::from_utf8_lossy(&output.stdout).contains("local only"));
+}
+
+#[test]
+fn test_diff_ref_range_rejects_three_dots_and_context() {
+    let dir = setup_two_releases();
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["diff", "v1.0...v2.0", "--root", root])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("three-dot"));
+
+    todo_scan()
+        .args(["diff", "v1.0..v2.0", "-C", "2", "--root", root])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--context"));
+}
+
+#[test]
+fn test_diff_ref_range_from_subdirectory() {
+    let dir = setup_git_repo(&[
+        ("main.rs", "// TODO: at the top\n"),
+        ("sub/lib.rs", "fn lib() {}\n"),
+    ]);
+    let cwd = dir.path();
+    fs::write(
+        cwd.join("sub/lib.rs"),
+        "// FIXME: added below\nfn lib() {}\n",
+    )
+    .unwrap();
+    git(cwd, &["commit", "-am", "add fixme"]);
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD~1..HEAD",
+            "--root",
+            cwd.join("sub").to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("added below"))
+        .stdout(predicate::str::contains("at the top").not())
+        .stdout(predicate::str::contains("+1 -0"));
+}
+
+#[test]
+fn test_diff_template_exposes_status() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: old task\nfn main() {}\n")]);
+    let cwd = dir.path();
+    fs::write(cwd.join("main.rs"), "// FIXME: new task\nfn main() {}\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--template",
+            "{{status}} {{tag}} {{message}}",
+            "--root",
+            cwd.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("added FIXME new task\n"))
+        .stdout(predicate::str::contains("removed TODO old task\n"));
+}
+
+#[test]
+fn test_diff_exclude_path_drops_both_sides() {
+    let dir = setup_git_repo(&[
+        ("main.rs", "// TODO: old\n"),
+        ("tests/a.rs", "// TODO: test old\n"),
+    ]);
+    let cwd = dir.path();
+    fs::write(cwd.join("main.rs"), "// TODO: old\n// TODO: new\n").unwrap();
+    fs::write(cwd.join("tests/a.rs"), "// FIXME: test new\n").unwrap();
+
+    todo_scan()
+        .args([
+            "diff",
+            "HEAD",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--exclude-path",
+            "**/tests/**",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("test old").not())
+        .stdout(predicate::str::contains("test new").not())
+        .stdout(predicate::str::contains(
+            "+1 -0 (base: HEAD) (net +1, base 1)",
+        ));
+}
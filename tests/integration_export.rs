@@ -0,0 +1,144 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+fn setup_project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(full_path, content).unwrap();
+    }
+    dir
+}
+
+fn parse_lines(stdout: &[u8]) -> Vec<serde_json::Value> {
+    String::from_utf8(stdout.to_vec())
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_export_one_json_object_per_item() {
+    let dir = setup_project(&[
+        ("src/main.rs", "// TODO: first\n// FIXME(alice): second\n"),
+        ("lib.rs", "// HACK: third\n"),
+    ]);
+
+    let output = todo_scan()
+        .args(["export", "--root", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let items = parse_lines(&output.stdout);
+    assert_eq!(items.len(), 3);
+    // Files in name order, then lines in file order
+    assert_eq!(items[0]["file"], "lib.rs");
+    assert_eq!(items[1]["file"], "src/main.rs");
+    assert_eq!(items[1]["id"], "src/main.rs:TODO:first");
+    assert_eq!(items[2]["author"], "alice");
+}
+
+#[test]
+fn test_export_filters_like_list() {
+    let dir = setup_project(&[
+        (
+            "src/a.rs",
+            "// TODO(alice): keep\n// FIXME(alice): drop by tag\n",
+        ),
+        ("tests/b.rs", "// TODO(alice): drop by path\n"),
+        ("src/c.rs", "// TODO(bob): drop by author\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "export",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--tag",
+            "TODO",
+            "--author",
+            "alice",
+            "--path",
+            "src/**",
+        ])
+        .output()
+        .unwrap();
+
+    let items = parse_lines(&output.stdout);
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["message"], "keep");
+}
+
+#[test]
+fn test_export_to_output_file() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n// NOTE: two\n")]);
+    let out_path = dir.path().join("todos.jsonl");
+
+    todo_scan()
+        .args([
+            "export",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--output",
+            out_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let content = fs::read(&out_path).unwrap();
+    let items = parse_lines(&content);
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1]["tag"], "NOTE");
+}
+
+#[test]
+fn test_export_empty_project_prints_nothing() {
+    let dir = setup_project(&[("main.rs", "fn main() {}\n")]);
+
+    todo_scan()
+        .args(["export", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_export_streams_files_in_walk_order_with_excludes() {
+    let dir = setup_project(&[
+        ("b.rs", "// TODO: b1\n// TODO: b2\n"),
+        ("a/z.rs", "// TODO: a/z\n"),
+        ("gen/out.rs", "// TODO: generated\n"),
+        ("c.rs", "// TODO: c\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "export",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--exclude-path",
+            "gen/**",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let messages: Vec<String> = parse_lines(&output.stdout)
+        .iter()
+        .map(|item| item["message"].as_str().unwrap().to_string())
+        .collect();
+    // Entries sorted by name within each directory, lines in file order
+    assert_eq!(messages, vec!["a/z", "b1", "b2", "c"]);
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use assert_cmd::Command;
+use common::git;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+fn setup_git_repo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    git(dir.path(), &["init"]);
+    dir
+}
+
+#[test]
+fn test_hooks_install_and_uninstall() {
+    let dir = setup_git_repo();
+    let root = dir.path().to_str().unwrap();
+    let hook = dir.path().join(".git/hooks/pre-commit");
+
+    todo_scan()
+        .args(["hooks", "install", "--root", root])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Installed pre-commit hook"));
+    let content = fs::read_to_string(&hook).unwrap();
+    assert!(content.starts_with("#!/bin/sh\n"));
+    assert!(content.contains("todo-scan check --staged --max-new 0"));
+
+    // Installing again refreshes the section instead of duplicating it
+    todo_scan()
+        .args(["hooks", "install", "--root", root])
+        .assert()
+        .success();
+    let content = fs::read_to_string(&hook).unwrap();
+    assert_eq!(content.matches("# >>> todo-scan >>>").count(), 1);
+
+    todo_scan()
+        .args(["hooks", "install", "--uninstall", "--root", root])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Removed"));
+    assert!(!hook.exists());
+}
+
+#[test]
+fn test_hooks_install_refuses_existing_hook_without_force() {
+    let dir = setup_git_repo();
+    let root = dir.path().to_str().unwrap();
+    let hook = dir.path().join(".git/hooks/pre-commit");
+    let original = "#!/bin/sh\ncargo fmt --check\n";
+    fs::write(&hook, original).unwrap();
+
+    todo_scan()
+        .args(["hooks", "install", "--root", root])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+    assert_eq!(fs::read_to_string(&hook).unwrap(), original);
+
+    todo_scan()
+        .args(["hooks", "install", "--force", "--root", root])
+        .assert()
+        .success();
+    let content = fs::read_to_string(&hook).unwrap();
+    assert!(content.starts_with(original));
+    assert!(content.contains("--max-new 0"));
+
+    todo_scan()
+        .args(["hooks", "install", "--uninstall", "--root", root])
+        .assert()
+        .success();
+    assert_eq!(fs::read_to_string(&hook).unwrap(), original);
+}
+
+#[test]
+fn test_hooks_install_outside_git_repo_fails() {
+    let dir = TempDir::new().unwrap();
+    todo_scan()
+        .args(["hooks", "install", "--root", dir.path().to_str().unwrap()])
+        .env("GIT_CEILING_DIRECTORIES", dir.path().parent().unwrap())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires a git repository"));
+}
+
+/// Run git in `cwd` with the freshly built todo-scan first on `PATH`, so an
+/// installed hook calls it.
+fn git_with_hook(cwd: &std::path::Path, args: &[&str]) -> std::process::Output {
+    let bin_dir = std::path::Path::new(env!("CARGO_BIN_EXE_todo-scan"))
+        .parent()
+        .unwrap();
+    let path = std::env::join_paths(std::iter::once(bin_dir.to_path_buf()).chain(
+        std::env::split_paths(&std::env::var_os("PATH").unwrap_or_default()),
+    ))
+    .unwrap();
+    std::process::Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@test.com"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .current_dir(cwd)
+        .env("PATH", path)
+        .output()
+        .unwrap()
+}
+
+fn install_hook(dir: &TempDir) {
+    todo_scan()
+        .args(["hooks", "install", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_hook_checks_staged_content_only() {
+    let dir = setup_git_repo();
+    let cwd = dir.path();
+    fs::write(cwd.join("a.rs"), "fn a() {}\n").unwrap();
+    fs::write(cwd.join("b.rs"), "fn b() {}\n").unwrap();
+    assert!(git_with_hook(cwd, &["add", "."]).status.success());
+    assert!(git_with_hook(cwd, &["commit", "-m", "initial"])
+        .status
+        .success());
+    install_hook(&dir);
+
+    // An unstaged TODO elsewhere must not block a clean staged change
+    fs::write(cwd.join("b.rs"), "// TODO: not staged\nfn b() {}\n").unwrap();
+    fs::write(cwd.join("a.rs"), "fn a() { 1; }\n").unwrap();
+    assert!(git_with_hook(cwd, &["add", "a.rs"]).status.success());
+    let commit = git_with_hook(cwd, &["commit", "-m", "clean"]);
+    assert!(
+        commit.status.success(),
+        "{}",
+        String::from_utf8_lossy(&commit.stderr)
+    );
+
+    // A staged TODO is rejected even once the working tree drops it again
+    fs::write(cwd.join("a.rs"), "// TODO: staged\nfn a() { 1; }\n").unwrap();
+    assert!(git_with_hook(cwd, &["add", "a.rs"]).status.success());
+    fs::write(cwd.join("a.rs"), "fn a() { 1; }\n").unwrap();
+    let commit = git_with_hook(cwd, &["commit", "-m", "todo"]);
+    assert!(!commit.status.success());
+    // git passes the hook's output through on stderr
+    assert!(String::from_utf8_lossy(&commit.stderr).contains("max_new"));
+}
+
+#[test]
+fn test_hook_allows_first_commit_without_new_todos() {
+    let dir = setup_git_repo();
+    let cwd = dir.path();
+    install_hook(&dir);
+
+    fs::write(cwd.join("a.rs"), "// TODO: staged\n").unwrap();
+    assert!(git_with_hook(cwd, &["add", "a.rs"]).status.success());
+    assert!(!git_with_hook(cwd, &["commit", "-m", "first"])
+        .status
+        .success());
+
+    fs::write(cwd.join("a.rs"), "fn a() {}\n").unwrap();
+    assert!(git_with_hook(cwd, &["add", "a.rs"]).status.success());
+    let commit = git_with_hook(cwd, &["commit", "-m", "first"]);
+    assert!(
+        commit.status.success(),
+        "{}",
+        String::from_utf8_lossy(&commit.stderr)
+    );
+}
@@ -103,3 +103,57 @@ fn test_init_config_has_all_default_tags() {
         assert!(content.contains(tag), "missing tag: {}", tag);
     }
 }
+
+#[test]
+fn test_init_scripted_flags() {
+    let dir = TempDir::new().unwrap();
+
+    todo_scan()
+        .args([
+            "init",
+            "--yes",
+            "--tags",
+            "TODO,FIXME",
+            "--exclude",
+            "target,node_modules",
+            "--max",
+            "100",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(dir.path().join(".todo-scan.toml")).unwrap();
+    let value: toml::Value = toml::from_str(&content).unwrap();
+    assert_eq!(value["tags"], toml::Value::from(vec!["TODO", "FIXME"]));
+    assert_eq!(
+        value["exclude_dirs"],
+        toml::Value::from(vec!["target", "node_modules"])
+    );
+    assert_eq!(value["check"]["max"].as_integer(), Some(100));
+}
+
+#[test]
+fn test_init_force_overwrites_in_non_interactive() {
+    let dir = TempDir::new().unwrap();
+    let config_path = dir.path().join(".todo-scan.toml");
+    fs::write(&config_path, "tags = [\"NOTE\"]").unwrap();
+
+    todo_scan()
+        .args([
+            "init",
+            "--yes",
+            "--force",
+            "--tags",
+            "BUG",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(content.contains("BUG"));
+    assert!(!content.contains("NOTE"));
+}
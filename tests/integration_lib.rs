@@ -0,0 +1,105 @@
+mod common;
+
+use std::fs;
+
+use common::git;
+use tempfile::TempDir;
+use todo_scan::check::CheckOverrides;
+use todo_scan::deadline::today;
+use todo_scan::model::DiffStatus;
+use todo_scan::Config;
+
+fn setup_project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(full_path, content).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_lib_scan_and_stats() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: first\n// FIXME: second\n"),
+        ("lib.rs", "// TODO: third\n"),
+    ]);
+    let config = Config::default();
+
+    let scan = todo_scan::scan_directory(dir.path(), &config).unwrap();
+    assert_eq!(scan.items.len(), 3);
+
+    let stats = todo_scan::compute_stats(&scan, None);
+    assert_eq!(stats.total_items, 3);
+    assert_eq!(stats.total_files, 2);
+}
+
+#[test]
+fn test_lib_scan_keeps_custom_tags() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "[custom_tags.DEBT]\nseverity = 9\n\n[custom_tags.NOTE]\nseverity = 8\n",
+        ),
+        (
+            "main.rs",
+            "// TODO: plain\n// DEBT: pay down\n// NOTE: remember\n",
+        ),
+    ]);
+    let config = Config::load(dir.path()).unwrap();
+
+    let scan = todo_scan::scan_directory(dir.path(), &config).unwrap();
+    let tags: Vec<&str> = scan.items.iter().map(|i| i.tag.as_str()).collect();
+    assert_eq!(tags, vec!["TODO", "DEBT", "NOTE"]);
+
+    let mut items = scan.items;
+    todo_scan::tasks::sort_by_priority(&mut items, &config.tag_styles());
+    let tags: Vec<&str> = items.iter().map(|i| i.tag.as_str()).collect();
+    assert_eq!(tags, vec!["DEBT", "NOTE", "TODO"]);
+}
+
+#[test]
+fn test_lib_run_check() {
+    let dir = setup_project(&[("main.rs", "// TODO: a\n// TODO: b\n")]);
+    let config = Config::default();
+    let scan = todo_scan::scan_directory(dir.path(), &config).unwrap();
+
+    let overrides = CheckOverrides {
+        max: Some(1),
+        ..Default::default()
+    };
+    let result = todo_scan::run_check(&scan, None, None, &config, &overrides, &today());
+    assert!(!result.passed);
+    assert_eq!(result.total, 2);
+    assert_eq!(result.violations.len(), 1);
+}
+
+#[test]
+fn test_lib_compute_diff_and_blame() {
+    let dir = setup_project(&[("main.rs", "// TODO: old\nfn main() {}\n")]);
+    git(dir.path(), &["init"]);
+    git(dir.path(), &["config", "user.email", "test@test.com"]);
+    git(dir.path(), &["config", "user.name", "Test"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "initial"]);
+
+    let config = Config::default();
+    let committed = todo_scan::scan_directory(dir.path(), &config).unwrap();
+    let blame =
+        todo_scan::compute_blame(&committed, dir.path(), 365, &config.author_aliases()).unwrap();
+    assert_eq!(blame.entries.len(), 1);
+    assert_eq!(blame.entries[0].blame.author, "Test");
+
+    fs::write(dir.path().join("main.rs"), "// TODO: new\nfn main() {}\n").unwrap();
+    let current = todo_scan::scan_directory(dir.path(), &config).unwrap();
+    let diff = todo_scan::compute_diff(&current, "HEAD", dir.path(), &config).unwrap();
+    assert_eq!(diff.added_count, 1);
+    assert_eq!(diff.removed_count, 1);
+    assert!(diff
+        .entries
+        .iter()
+        .any(|e| matches!(e.status, DiffStatus::Added) && e.item.message == "new"));
+}
@@ -478,6 +478,22 @@ fn test_lint_exit_code_1_on_fail() {
         .code(1);
 }
 
+#[test]
+fn test_lint_exit_zero_on_fail() {
+    let dir = setup_project(&[("main.rs", "// todo: lowercase\n")]);
+
+    todo_scan()
+        .args([
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--exit-zero",
+        ])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::contains("FAIL"));
+}
+
 // --- Empty project ---
 
 #[test]
@@ -506,3 +522,178 @@ fn test_lint_multiple_files() {
         .success()
         .stdout(predicate::str::contains("PASS"));
 }
+
+// --- Density ---
+
+#[test]
+fn test_lint_max_density_dense_and_sparse_files() {
+    let sparse = format!("// TODO: one thing\n{}", "fn f() {}\n".repeat(49));
+    let dir = setup_project(&[
+        (
+            "dense.rs",
+            "// TODO: a\n// TODO: b\nfn main() {}\n// FIXME: c\n",
+        ),
+        ("sparse.rs", &sparse),
+    ]);
+
+    todo_scan()
+        .args([
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-density",
+            "0.05",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("max_density"))
+        .stdout(predicate::str::contains("dense.rs"))
+        .stdout(predicate::str::contains(
+            "TODO density 75.0% (3 of 4 lines) exceeds maximum 5.0%",
+        ))
+        .stdout(predicate::str::contains("sparse.rs").not());
+}
+
+#[test]
+fn test_lint_max_density_from_config() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: a\nfn main() {}\n"),
+        (".todo-scan.toml", "[lint]\nmax_density = 0.6\n"),
+    ]);
+
+    todo_scan()
+        .args(["lint", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_lint_max_density_rejects_out_of_range() {
+    let dir = setup_project(&[("main.rs", "// TODO: a\n")]);
+
+    todo_scan()
+        .args([
+            "lint",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--max-density",
+            "5",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("between 0 and 1"));
+}
+
+#[test]
+fn test_lint_junit_failures_match_violation_count() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO:\n// todo: lowercase\n// FIXME missing colon\n// TODO: fine\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    let output = todo_scan()
+        .args(["lint", "--format", "json", "--root", root])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let violation_count = json["violation_count"].as_u64().unwrap() as usize;
+    assert!(violation_count > 0);
+
+    let output = todo_scan()
+        .args(["lint", "--format", "junit", "--root", root])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let xml = String::from_utf8(output.stdout).unwrap();
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let suite = doc
+        .descendants()
+        .find(|n| n.has_tag_name("testsuite"))
+        .unwrap();
+    assert_eq!(
+        suite.attribute("failures"),
+        Some(violation_count.to_string().as_str())
+    );
+    let failures = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("failure"))
+        .count();
+    assert_eq!(failures, violation_count);
+    let testcase = doc
+        .descendants()
+        .find(|n| n.has_tag_name("testcase"))
+        .unwrap();
+    assert!(testcase.attribute("name").unwrap().starts_with("main.rs:"));
+}
+
+#[test]
+fn test_lint_junit_pass_has_passing_case() {
+    let dir = setup_project(&[("main.rs", "// TODO: fine\n")]);
+    let output = todo_scan()
+        .args([
+            "lint",
+            "--format",
+            "junit",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let xml = String::from_utf8(output.stdout).unwrap();
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let cases: Vec<_> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("testcase"))
+        .collect();
+    assert_eq!(cases.len(), 1);
+    assert!(!cases[0].children().any(|c| c.has_tag_name("failure")));
+}
+
+#[test]
+fn test_lint_escalate_overdue() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[scan]\nescalation = true\n"),
+        (
+            "main.rs",
+            "// TODO->BUG(alice, 2001-02-03): overdue\n// BUG->BUG(bob, 2001-02-03): already escalated\n",
+        ),
+    ]);
+    todo_scan()
+        .args(["lint", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("escalate_overdue"))
+        .stdout(predicate::str::contains(
+            "TODO passed its deadline 2001-02-03 without being escalated to BUG",
+        ))
+        .stdout(predicate::str::contains("1 violations"));
+}
+
+#[test]
+fn test_lint_no_local_duplicates() {
+    let dir = setup_project(&[
+        (
+            "main.rs",
+            "// TODO: handle errors\nfn a() {}\n// TODO: handle errors\n",
+        ),
+        ("lib.rs", "// TODO: handle errors\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["lint", "--root", root])
+        .assert()
+        .success();
+
+    todo_scan()
+        .args(["lint", "--root", root, "--no-local-duplicates"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("no_local_duplicates"))
+        .stdout(predicate::str::contains(
+            "L3: no_local_duplicates - Duplicate of the TODO on line 1: \"handle errors\"",
+        ))
+        .stdout(predicate::str::contains("1 violations"));
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::{git, git_with_env};
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -212,6 +215,79 @@ fn test_list_github_actions_format() {
         ));
 }
 
+#[test]
+fn test_list_azure_devops_format() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: implement feature\n// BUG: critical issue\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "azure-devops",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "##vso[task.logissue type=warning;sourcepath=main.rs;linenumber=1;code=TODO;][TODO] implement feature\n",
+        ))
+        .stdout(predicate::str::contains(
+            "##vso[task.logissue type=error;sourcepath=main.rs;linenumber=2;code=BUG;][BUG] critical issue\n",
+        ))
+        .stdout(predicate::str::contains("todo-scan: 2 items found"));
+}
+
+#[test]
+fn test_list_xcode_format() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: implement feature\n// BUG: critical issue\n// FIXME: !! ship blocker\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "xcode",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            "main.rs:1: warning: [TODO] implement feature\n\
+             main.rs:2: error: [BUG] critical issue\n\
+             main.rs:3: error: [FIXME] ship blocker\n\
+             todo-scan: 3 items found\n",
+        );
+}
+
+#[test]
+fn test_list_rejects_command_specific_formats() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    for format in ["dot", "gh-issue", "html", "junit"] {
+        todo_scan()
+            .args([
+                "list",
+                "--root",
+                dir.path().to_str().unwrap(),
+                "--format",
+                format,
+            ])
+            .assert()
+            .code(2)
+            .stdout(predicate::str::is_empty())
+            .stderr(predicate::str::contains(format!(
+                "--format {format} is only supported by"
+            )));
+    }
+}
+
 #[test]
 fn test_list_sarif_format() {
     let dir = setup_project(&[("main.rs", "// TODO: sarif test\n")]);
@@ -231,6 +307,35 @@ fn test_list_sarif_format() {
         .stdout(predicate::str::contains("\"text\": \"sarif test\""));
 }
 
+#[test]
+fn test_list_sarif_levels_and_issue_ref() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// BUG: crashes\n// FIXME: flaky #12\n// TODO: !! now\n// NOTE: context\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "sarif",
+            "--sort",
+            "file",
+        ])
+        .output()
+        .unwrap();
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    let levels: Vec<&str> = results
+        .iter()
+        .map(|r| r["level"].as_str().unwrap())
+        .collect();
+    assert_eq!(levels, vec!["error", "warning", "error", "note"]);
+    assert_eq!(results[1]["properties"]["issueRef"], "#12");
+}
+
 #[test]
 fn test_list_markdown_format() {
     let dir = setup_project(&[("main.rs", "// TODO(alice): implement feature #42\n")]);
@@ -349,6 +454,48 @@ fn test_list_filter_priority() {
         .stdout(predicate::str::contains("1 items"));
 }
 
+#[test]
+fn test_list_filter_priority_min() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO!!: urgent task\n// TODO!: high task\n// TODO: normal task\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--priority-min",
+            "high",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("urgent task"))
+        .stdout(predicate::str::contains("high task"))
+        .stdout(predicate::str::contains("normal task").not())
+        .stdout(predicate::str::contains("2 items"));
+}
+
+#[test]
+fn test_list_priority_and_priority_min_conflict() {
+    let dir = setup_project(&[("main.rs", "// TODO!: high task\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--priority",
+            "high",
+            "--priority-min",
+            "high",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
 #[test]
 fn test_list_filter_author() {
     let dir = setup_project(&[(
@@ -394,6 +541,74 @@ fn test_list_filter_path() {
         .stdout(predicate::str::contains("1 items"));
 }
 
+#[test]
+fn test_list_filter_several_paths() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO: in src\n"),
+        ("lib/util.rs", "// TODO: in lib\n"),
+        ("tests/test.rs", "// TODO: in tests\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--path",
+            "src/**",
+            "--path",
+            "lib/**",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("in src"))
+        .stdout(predicate::str::contains("in lib"))
+        .stdout(predicate::str::contains("in tests").not())
+        .stdout(predicate::str::contains("2 items"));
+}
+
+#[test]
+fn test_list_exclude_path_at_runtime() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO: in src\n"),
+        ("src/tests/unit.rs", "// TODO: in unit tests\n"),
+        ("tests/it.rs", "// TODO: in tests\n"),
+    ]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["list", "--root", root, "--exclude-path", "**/tests/**"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("in src"))
+        .stdout(predicate::str::contains("tests").not())
+        .stdout(predicate::str::contains("1 items"));
+
+    // Only results are filtered: the cached scan still holds every file
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 items"));
+}
+
+#[test]
+fn test_list_exclude_path_rejects_invalid_glob() {
+    let dir = setup_project(&[("main.rs", "// TODO: a\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--exclude-path",
+            "[oops",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid --exclude-path"));
+}
+
 #[test]
 fn test_list_limit() {
     let dir = setup_project(&[(
@@ -507,6 +722,39 @@ fn test_list_group_by_dir() {
         .stdout(predicate::str::contains("3 items in 2 groups"));
 }
 
+#[test]
+fn test_list_group_by_issue() {
+    let dir = setup_project(&[
+        (
+            "a.rs",
+            "// TODO: no ticket
+// FIXME: parser crash #42
+",
+        ),
+        (
+            "b.rs",
+            "// TODO: parser cleanup #42
+",
+        ),
+    ]);
+
+    todo_scan()
+        .env("NO_COLOR", "1")
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "issue",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("#42 (2 items)"))
+        .stdout(predicate::str::contains("no-issue (1 items)"))
+        .stdout(predicate::str::is_match(r"(?s)#42 \(2 items\).*no-issue \(1 items\)").unwrap())
+        .stdout(predicate::str::contains("3 items in 2 groups"));
+}
+
 #[test]
 fn test_list_group_by_with_json() {
     let dir = setup_project(&[("main.rs", "// TODO!!: urgent task\n// TODO: normal task\n")]);
@@ -680,6 +928,37 @@ fn test_list_ignore_next_line_works_e2e() {
         .stdout(predicate::str::contains("(1 ignored)"));
 }
 
+#[test]
+fn test_list_ignore_file_and_range_markers_e2e() {
+    let dir = setup_project(&[
+        (
+            "generated.rs",
+            "// todo-scan:ignore-file\n// TODO: generated one\n// TODO: generated two\n",
+        ),
+        (
+            "main.rs",
+            "// todo-scan:ignore-begin\n// TODO: vendored\n// todo-scan:ignore-end\n// TODO: visible\n",
+        ),
+    ]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("visible"))
+        .stdout(predicate::str::contains("generated").not())
+        .stdout(predicate::str::contains("vendored").not())
+        .stdout(predicate::str::contains("(3 ignored)"));
+
+    todo_scan()
+        .args(["list", "--root", root, "--show-ignored"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("generated two"))
+        .stdout(predicate::str::contains("vendored"));
+}
+
 #[test]
 fn test_list_no_ignored_shows_no_suffix() {
     let dir = setup_project(&[("main.rs", "// TODO: just a normal todo\n")]);
@@ -891,6 +1170,123 @@ fn test_list_json_contains_id_field_at_normal_detail() {
     assert_eq!(items.len(), 1);
     let item = &items[0];
     assert_eq!(item["id"].as_str().unwrap(), "main.rs:TODO:stable id test");
+    assert_eq!(item["stable_id"].as_str().unwrap().len(), 16);
+}
+
+#[test]
+fn test_list_format_from_env() {
+    let dir = setup_project(&[("main.rs", "// TODO: env task\n")]);
+
+    let output = todo_scan()
+        .env("TODO_SCAN_FORMAT", "json")
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["items"][0]["message"], "env task");
+}
+
+#[test]
+fn test_list_format_flag_wins_over_env() {
+    let dir = setup_project(&[("main.rs", "// TODO: env task\n")]);
+
+    todo_scan()
+        .env("TODO_SCAN_FORMAT", "json")
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "text",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[TODO] env task"));
+}
+
+#[test]
+fn test_list_root_and_config_from_env() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: todo task\n// FIXME: fixme task\n"),
+        ("ci.toml", "tags = [\"FIXME\"]\n"),
+    ]);
+
+    todo_scan()
+        .env("TODO_SCAN_ROOT", dir.path())
+        .env("TODO_SCAN_CONFIG", dir.path().join("ci.toml"))
+        .env("TODO_SCAN_NO_CACHE", "1")
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fixme task"))
+        .stdout(predicate::str::contains("todo task").not());
+}
+
+#[test]
+fn test_list_invalid_env_value_is_a_usage_error() {
+    let dir = setup_project(&[("main.rs", "// TODO: env task\n")]);
+
+    todo_scan()
+        .env("TODO_SCAN_FORMAT", "yaml")
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'yaml'"));
+}
+
+#[test]
+fn test_list_json_column_in_bytes_or_utf16() {
+    let dir = setup_project(&[("main.rs", "let s = \"日本\"; // TODO: inline\n")]);
+
+    let column_of = |extra: &[&str]| {
+        let output = todo_scan()
+            .args([
+                "list",
+                "--root",
+                dir.path().to_str().unwrap(),
+                "--format",
+                "json",
+                "--no-cache",
+            ])
+            .args(extra)
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["items"][0]["column"].as_u64().unwrap()
+    };
+
+    // Each of the two characters is three bytes but one UTF-16 code unit
+    assert_eq!(column_of(&[]), 22);
+    assert_eq!(column_of(&["--utf16-columns"]), 18);
+}
+
+#[test]
+fn test_list_json_stable_id_survives_minor_edits() {
+    let stable_id_of = |content: &str| {
+        let dir = setup_project(&[("main.rs", content)]);
+        let output = todo_scan()
+            .args([
+                "list",
+                "--root",
+                dir.path().to_str().unwrap(),
+                "--format",
+                "json",
+            ])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["items"][0]["stable_id"].as_str().unwrap().to_string()
+    };
+
+    assert_eq!(
+        stable_id_of("// FIXME: Fix this bug\n"),
+        stable_id_of("\n// FIXME: fix this  bug!\n")
+    );
+    assert_ne!(
+        stable_id_of("// FIXME: Fix this bug\n"),
+        stable_id_of("// FIXME: fix that bug\n")
+    );
 }
 
 #[test]
@@ -1241,3 +1637,1415 @@ fn test_list_text_search_expired_deadline_in_group_by_tag() {
         .success()
         .stdout(predicate::str::contains("[expired: 2020-01-01]"));
 }
+
+// --- Custom tags ---
+
+#[test]
+fn test_list_custom_tags_sorted_by_configured_severity() {
+    let dir = setup_project(&[
+        (
+            "main.rs",
+            "// TODO: medium severity\n// SECURITY: sanitize input\n// OPTIMIZE: cache lookups\n",
+        ),
+        (
+            ".todo-scan.toml",
+            r#"
+[custom_tags.SECURITY]
+severity = 9
+color = "red"
+
+[custom_tags.OPTIMIZE]
+severity = 0
+"#,
+        ),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--sort",
+            "tag",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["tag"].as_str().unwrap(), "SECURITY");
+    assert_eq!(items[1]["tag"].as_str().unwrap(), "TODO");
+    assert_eq!(items[2]["tag"].as_str().unwrap(), "OPTIMIZE");
+}
+
+#[test]
+fn test_list_custom_tag_filter_and_group() {
+    let dir = setup_project(&[
+        ("main.rs", "// TODO: builtin\n// DEBT: pay down later\n"),
+        (".todo-scan.toml", "[custom_tags.DEBT]\nseverity = 2\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--tag",
+            "debt",
+            "--group-by",
+            "tag",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("DEBT (1 items)"))
+        .stdout(predicate::str::contains("pay down later"))
+        .stdout(predicate::str::contains("builtin").not());
+}
+
+// --- CSV output ---
+
+#[test]
+fn test_list_csv_round_trip() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): ! handle \"a, b\" case #7\n// FIXME: second\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "csv",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut reader = csv::Reader::from_reader(output.stdout.as_slice());
+    let headers = reader.headers().unwrap().clone();
+    assert_eq!(
+        headers.iter().collect::<Vec<_>>(),
+        vec![
+            "file",
+            "line",
+            "tag",
+            "priority",
+            "author",
+            "issue_ref",
+            "deadline",
+            "message"
+        ]
+    );
+    let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(&rows[0][0], "main.rs");
+    assert_eq!(&rows[0][2], "TODO");
+    assert_eq!(&rows[0][3], "high");
+    assert_eq!(&rows[0][4], "alice");
+    assert_eq!(&rows[0][5], "#7");
+    assert_eq!(&rows[0][7], "handle \"a, b\" case #7");
+    assert_eq!(&rows[1][2], "FIXME");
+}
+
+// --- Multiline continuation ---
+
+#[test]
+fn test_list_multiline_config_joins_continuation_lines() {
+    let dir = setup_project(&[
+        (
+            "main.rs",
+            "// TODO: refactor the auth flow\n//   because the session cache is wrong\nfn main() {}\n",
+        ),
+        (".todo-scan.toml", "[scan]\nmultiline = true\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "L1: [TODO] refactor the auth flow because the session cache is wrong",
+        ))
+        .stdout(predicate::str::contains("1 items"));
+}
+
+// --- Count only ---
+
+#[test]
+fn test_list_count_only_json() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: one\n// TODO: two\n// FIXME: three\n"),
+        ("b.rs", "// TODO: four\n"),
+        ("c.rs", "fn main() {}\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--count-only",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"total": 4, "by_tag": {"FIXME": 1, "TODO": 3}, "files": 2})
+    );
+}
+
+#[test]
+fn test_list_count_only_respects_filters() {
+    let dir = setup_project(&[("a.rs", "// TODO: one\n// FIXME: two\n// FIXME: three\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--count-only",
+            "--tag",
+            "FIXME",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 items in 1 files"))
+        .stdout(predicate::str::contains("FIXME: 2"))
+        .stdout(predicate::str::contains("TODO").not())
+        .stdout(predicate::str::contains("L1:").not());
+}
+
+// --- Config discovery ---
+
+#[test]
+fn test_list_relative_root_finds_config_two_levels_up() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "tags = [\"HACK\"]\n"),
+        (
+            "a/b/main.rs",
+            "// TODO: not configured\n// HACK: configured\n",
+        ),
+    ]);
+
+    todo_scan()
+        .current_dir(dir.path().join("a").join("b"))
+        .args(["list", "--root", "."])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("configured"))
+        .stdout(predicate::str::contains("not configured").not());
+}
+
+// --- Field selection ---
+
+#[test]
+fn test_list_fields_selects_and_orders_columns() {
+    let dir = setup_project(&[("src/main.rs", "// TODO(alice): fix parser #42\n")]);
+
+    todo_scan()
+        .env("NO_COLOR", "1")
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--fields",
+            "line,message,author",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/main.rs\n"))
+        .stdout(predicate::str::contains("  1 fix parser #42 @alice\n"))
+        .stdout(predicate::str::contains("[TODO]").not());
+}
+
+#[test]
+fn test_list_fields_without_file_keeps_group_headers() {
+    let dir = setup_project(&[("a.rs", "// TODO: first\n"), ("b.rs", "// FIXME: second\n")]);
+
+    todo_scan()
+        .env("NO_COLOR", "1")
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--group-by",
+            "tag",
+            "--fields",
+            "message",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TODO (1 items)\n  first\n"))
+        .stdout(predicate::str::contains("FIXME (1 items)\n  second\n"));
+}
+
+#[test]
+fn test_list_fields_unknown_name_errors() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--fields",
+            "file,colour",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'colour'"));
+}
+
+// --- Issue reference patterns ---
+
+#[test]
+fn test_list_custom_issue_patterns() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "[issue]\npatterns = ['linear\\.app/\\S+/issue/([A-Z]+-\\d+)', '\\bGH-\\d+']\n",
+        ),
+        (
+            "main.rs",
+            "// TODO: see https://linear.app/acme/issue/ENG-1234/x\n// FIXME: tracked in GH-12\n",
+        ),
+    ]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"issue_ref\": \"ENG-1234\""))
+        .stdout(predicate::str::contains("\"issue_ref\": \"GH-12\""));
+}
+
+#[test]
+fn test_list_invalid_issue_pattern_is_config_error() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[issue]\npatterns = ['GH-(\\d+']\n"),
+        ("main.rs", "// TODO: task\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid [issue] pattern"));
+}
+
+#[test]
+fn test_list_invert_match_prints_files_without_todos() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: something\n"),
+        ("b.rs", "fn clean() {}\n"),
+        ("src/c.rs", "fn also_clean() {}\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--invert-match",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout("b.rs\nsrc/c.rs\n");
+}
+
+#[test]
+fn test_list_no_todos_alias_json() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: something\n"),
+        ("b.rs", "fn clean() {}\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--no-todos",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["files_without_todos"], serde_json::json!(["b.rs"]));
+}
+
+#[test]
+fn test_list_dedupe_collapses_identical_todos() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: validate input\n"),
+        ("b.rs", "fn f() {}\n// TODO: validate input\n"),
+        ("src/c.rs", "// TODO: validate input\n// FIXME: unrelated\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--dedupe",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total_items"], 4);
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+
+    let deduped = items
+        .iter()
+        .find(|i| i["message"] == "validate input")
+        .unwrap();
+    assert_eq!(deduped["count"], 3);
+    assert_eq!(
+        deduped["locations"],
+        serde_json::json!([
+            {"file": "a.rs", "line": 1},
+            {"file": "b.rs", "line": 2},
+            {"file": "src/c.rs", "line": 1},
+        ])
+    );
+    assert!(deduped["stable_id"].is_string());
+}
+
+#[test]
+fn test_list_dedupe_text() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: validate input\n"),
+        ("b.rs", "// TODO: validate input\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--dedupe", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("validate input (2 occurrences)"))
+        .stdout(predicate::str::contains("  a.rs:1\n  b.rs:1\n"))
+        .stdout(predicate::str::contains("1 unique items (2 total)"));
+}
+
+#[test]
+fn test_list_invert_match_applies_filters() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: something\n"),
+        ("b.rs", "// FIXME: broken\n"),
+        ("c.rs", "fn clean() {}\n"),
+    ]);
+
+    // With --tag FIXME, a.rs has no matching items and counts as "without"
+    todo_scan()
+        .args([
+            "list",
+            "--invert-match",
+            "--tag",
+            "FIXME",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout("a.rs\nc.rs\n");
+}
+
+#[test]
+fn test_list_invert_match_conflicts_with_count_only() {
+    let dir = setup_project(&[("a.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--invert-match",
+            "--count-only",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_list_color_always_forces_color_when_piped() {
+    let dir = setup_project(&[("main.rs", "// TODO: colored\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--color",
+            "always",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .env_remove("NO_COLOR")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[test]
+fn test_list_no_color_flag_wins_over_clicolor_force() {
+    let dir = setup_project(&[("main.rs", "// TODO: plain\n")]);
+
+    todo_scan()
+        .args(["list", "--no-color", "--root", dir.path().to_str().unwrap()])
+        .env("CLICOLOR_FORCE", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("plain"))
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_list_no_ignore_includes_gitignored_files() {
+    let dir = setup_project(&[
+        (".gitignore", "vendor/\n"),
+        ("main.rs", "// TODO: ours\n"),
+        ("vendor/sdk.rs", "// FIXME: patched upstream\n"),
+    ]);
+    fs::create_dir(dir.path().join(".git")).unwrap();
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("patched upstream").not());
+
+    // The cache from the first run must not hide the newly visible file
+    todo_scan()
+        .args(["list", "--no-ignore", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("patched upstream"))
+        .stdout(predicate::str::contains("ours"));
+}
+
+#[test]
+fn test_list_quiet_omits_summary_line() {
+    let dir = setup_project(&[("main.rs", "// TODO: first\n// FIXME: second\n")]);
+
+    todo_scan()
+        .args(["list", "--quiet", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("first"))
+        .stdout(predicate::str::contains("second"))
+        .stdout(predicate::str::contains("items in").not());
+}
+
+#[test]
+fn test_list_quiet_json_is_unchanged() {
+    let dir = setup_project(&[("main.rs", "// TODO: first\n")]);
+    let root = dir.path().to_str().unwrap();
+
+    let plain = todo_scan()
+        .args(["list", "--format", "json", "--root", root])
+        .output()
+        .unwrap();
+    let quiet = todo_scan()
+        .args(["-q", "list", "--format", "json", "--root", root])
+        .output()
+        .unwrap();
+    assert!(quiet.status.success());
+    assert_eq!(plain.stdout, quiet.stdout);
+}
+
+#[test]
+fn test_list_no_color_env_disables_color() {
+    let dir = setup_project(&[("main.rs", "// TODO: plain\n")]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .env("CLICOLOR_FORCE", "1")
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+}
+
+#[test]
+fn test_list_progress_always_keeps_stdout_clean() {
+    let dir = setup_project(&[("main.rs", "// TODO: a\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--progress",
+            "always",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["items"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_list_progress_rejects_unknown_value() {
+    todo_scan()
+        .args(["list", "--progress", "sometimes"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_list_markdown_task_lists() {
+    let dir = setup_project(&[(
+        "docs/plan.md",
+        "# Plan\n\n- [ ] TODO: wire up metrics\n- [x] TODO: add config\n> FIXME: this section is wrong\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0]["message"], "wire up metrics");
+    assert_eq!(items[0]["checked"], false);
+    assert_eq!(items[1]["checked"], true);
+    assert_eq!(items[2]["tag"], "FIXME");
+    assert!(items[2]["checked"].is_null());
+}
+
+#[test]
+fn test_list_sort_age_oldest_first_untracked_last() {
+    let dir = setup_project(&[("new.rs", "// TODO: recent\n")]);
+    let root = dir.path();
+    git(root, &["init"]);
+    git(root, &["config", "user.email", "test@test.com"]);
+    git(root, &["config", "user.name", "Test"]);
+
+    fs::write(root.join("old.rs"), "// TODO: ancient\n").unwrap();
+    git(root, &["add", "old.rs"]);
+    git_with_env(
+        root,
+        &["commit", "-m", "old"],
+        &[
+            ("GIT_AUTHOR_DATE", "2020-01-01T00:00:00Z"),
+            ("GIT_COMMITTER_DATE", "2020-01-01T00:00:00Z"),
+        ],
+    );
+    git(root, &["add", "new.rs"]);
+    git(root, &["commit", "-m", "new"]);
+    fs::write(root.join("a_untracked.rs"), "// TODO: not in git\n").unwrap();
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--sort",
+            "age",
+            "--format",
+            "json",
+            "--root",
+            root.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("git blame"));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let messages: Vec<&str> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["message"].as_str().unwrap())
+        .collect();
+    assert_eq!(messages, vec!["ancient", "recent", "not in git"]);
+}
+
+#[test]
+fn test_list_modified_only() {
+    let dir = setup_project(&[
+        ("clean.rs", "// TODO: committed and untouched\n"),
+        ("edited.rs", "fn edited() {}\n"),
+        ("staged.rs", "fn staged() {}\n"),
+    ]);
+    let cwd = dir.path();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "t@example.com"]);
+    git(cwd, &["config", "user.name", "T"]);
+    git(cwd, &["add", "-A"]);
+    git(cwd, &["commit", "-m", "init"]);
+
+    fs::write(
+        cwd.join("edited.rs"),
+        "// TODO: left behind\n// FIXME: also\n",
+    )
+    .unwrap();
+    fs::write(cwd.join("staged.rs"), "// FIXME: staged fix\n").unwrap();
+    git(cwd, &["add", "staged.rs"]);
+    fs::write(cwd.join("untracked.rs"), "// TODO: not in git\n").unwrap();
+
+    todo_scan()
+        .args(["list", "--modified-only", "--root", cwd.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("left behind"))
+        .stdout(predicate::str::contains("staged fix"))
+        .stdout(predicate::str::contains("committed and untouched").not())
+        .stdout(predicate::str::contains("not in git").not());
+
+    todo_scan()
+        .args([
+            "list",
+            "--modified-only",
+            "--tag",
+            "FIXME",
+            "--count-only",
+            "--format",
+            "json",
+            "--root",
+            cwd.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"total\": 2"));
+}
+
+#[test]
+fn test_list_modified_only_outside_git_fails() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--modified-only",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires a git repository"));
+}
+
+#[test]
+fn test_list_envelope_wraps_json_output() {
+    let dir = setup_project(&[("main.rs", "// TODO: wrapped\n// FIXME: too\n")]);
+    let root = dir.path().to_str().unwrap();
+
+    let bare = todo_scan()
+        .args(["list", "--format", "json", "--root", root])
+        .output()
+        .unwrap();
+    let wrapped = todo_scan()
+        .args(["ls", "--format", "json", "--envelope", "--root", root])
+        .output()
+        .unwrap();
+    assert!(bare.status.success() && wrapped.status.success());
+
+    let bare: serde_json::Value = serde_json::from_slice(&bare.stdout).unwrap();
+    let wrapped: serde_json::Value = serde_json::from_slice(&wrapped.stdout).unwrap();
+    assert_eq!(wrapped["command"], "list");
+    assert_eq!(wrapped["root"], root);
+    assert_eq!(wrapped["todo_scan_version"], env!("CARGO_PKG_VERSION"));
+    assert!(wrapped["generated_at"].is_string());
+    assert_eq!(wrapped["data"], bare);
+}
+
+#[test]
+fn test_list_envelope_leaves_text_output_alone() {
+    let dir = setup_project(&[("main.rs", "// TODO: plain\n")]);
+
+    todo_scan()
+        .args(["list", "--envelope", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("todo_scan_version").not())
+        .stdout(predicate::str::contains("plain"));
+}
+
+#[test]
+fn test_list_at_tags_from_config() {
+    let dir = setup_project(&[(
+        "cache.js",
+        "/**\n * @todo implement caching\n */\n// contact email@todo.com\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("implement caching").not());
+
+    fs::write(
+        dir.path().join(".todo-scan.toml"),
+        "[scan]\nat_tags = true\n",
+    )
+    .unwrap();
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("implement caching"))
+        .stdout(predicate::str::contains(".com").not());
+}
+
+#[test]
+fn test_list_width_truncates_long_messages() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: this message is far too long to fit on a narrow terminal line\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    todo_scan()
+        .args(["list", "--root", root, "--width", "30"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  L1: [TODO] this message is…\n"))
+        .stdout(predicate::str::contains("terminal line").not());
+
+    // Piped output is left alone without --width
+    todo_scan()
+        .args(["list", "--root", root])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("narrow terminal line"));
+}
+
+#[test]
+fn test_list_width_wraps_with_detail_full() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: this message is far too long to fit on a narrow terminal line\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--width",
+            "30",
+            "--detail",
+            "full",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "L1: [TODO] this message is\n      far too long to fit on a\n      narrow terminal line\n",
+        ));
+}
+
+#[test]
+fn test_list_scans_only_given_paths() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: in a\n"),
+        ("b.rs", "// TODO: in b\n"),
+        ("sub/c.rs", "// FIXME: in c\n"),
+    ]);
+
+    todo_scan()
+        .current_dir(dir.path())
+        .args(["list", "a.rs", "sub", "gone.rs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("in a"))
+        .stdout(predicate::str::contains("sub/c.rs"))
+        .stdout(predicate::str::contains("in b").not())
+        .stderr(predicate::str::contains("warning: gone.rs: no such file"));
+}
+
+#[test]
+fn test_list_files_reads_paths_from_stdin() {
+    let dir = setup_project(&[
+        ("a.rs", "// TODO: in a\n"),
+        ("b.rs", "// TODO: in b\n"),
+        ("c.rs", "// TODO: in c\n"),
+    ]);
+
+    let output = todo_scan()
+        .current_dir(dir.path())
+        .args(["list", "--files", "--format", "json"])
+        .write_stdin("b.rs\n\nc.rs\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let files: Vec<&str> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["file"].as_str().unwrap())
+        .collect();
+    assert_eq!(files, vec!["b.rs", "c.rs"]);
+}
+
+#[test]
+fn test_list_files_conflicts_with_config_from_stdin() {
+    let dir = setup_project(&[("a.rs", "// TODO: in a\n")]);
+
+    todo_scan()
+        .current_dir(dir.path())
+        .args(["list", "--files", "--config", "-"])
+        .write_stdin("a.rs\n")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot both read stdin"));
+}
+
+#[test]
+fn test_list_author_aliases_canonicalize_and_filter() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "[authors.aliases]\n\"Alice Smith\" = [\"alice\", \"alice@corp.com\"]\n",
+        ),
+        (
+            "main.rs",
+            "// TODO(alice): one\n// TODO(alice@corp.com): two\n// TODO(bob): three\n",
+        ),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--author",
+            "ALICE",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let authors: Vec<&str> = json["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["author"].as_str().unwrap())
+        .collect();
+    assert_eq!(authors, vec!["Alice Smith", "Alice Smith"]);
+}
+
+#[test]
+fn test_list_since_date_keeps_recently_blamed_items() {
+    let dir = setup_project(&[("new.rs", "// TODO: recent\n")]);
+    let root = dir.path();
+    git(root, &["init"]);
+    git(root, &["config", "user.email", "test@test.com"]);
+    git(root, &["config", "user.name", "Test"]);
+
+    fs::write(root.join("old.rs"), "// TODO: ancient\n").unwrap();
+    git(root, &["add", "old.rs"]);
+    git_with_env(
+        root,
+        &["commit", "-m", "old"],
+        &[
+            ("GIT_AUTHOR_DATE", "2020-01-01T00:00:00Z"),
+            ("GIT_COMMITTER_DATE", "2020-01-01T00:00:00Z"),
+        ],
+    );
+    git(root, &["add", "new.rs"]);
+    git(root, &["commit", "-m", "new"]);
+    fs::write(root.join("untracked.rs"), "// TODO: not in git\n").unwrap();
+
+    let messages = |window: &str| -> Vec<String> {
+        let output = todo_scan()
+            .args([
+                "list",
+                "--since-date",
+                window,
+                "--format",
+                "json",
+                "--root",
+                root.to_str().unwrap(),
+            ])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["message"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    assert_eq!(messages("30d"), vec!["recent"]);
+    assert_eq!(messages("2019-12-31"), vec!["recent", "ancient"]);
+}
+
+#[test]
+fn test_list_since_date_rejects_invalid_window() {
+    let dir = setup_project(&[("a.rs", "// TODO: x\n")]);
+    todo_scan()
+        .args([
+            "list",
+            "--since-date",
+            "last week",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid --since-date"));
+}
+
+#[test]
+fn test_list_template_renders_each_item() {
+    let dir = setup_project(&[("a.rs", "// TODO: first\n// FIXME(alice): second #7\n")]);
+    todo_scan()
+        .args([
+            "list",
+            "--template",
+            r"{{file}}:{{line}} {{tag}} {{message}} [{{author}}]\n",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout("a.rs:1 TODO first []\na.rs:2 FIXME second #7 [alice]\n");
+}
+
+#[test]
+fn test_list_invalid_template_fails_before_scanning() {
+    let dir = setup_project(&[("a.rs", "// TODO: x\n")]);
+    todo_scan()
+        .args([
+            "list",
+            "--template",
+            "{{file}} {{colour}}",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown variable colour"));
+
+    todo_scan()
+        .args([
+            "list",
+            "--template",
+            "{% if %}",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid --template"));
+}
+
+#[test]
+fn test_list_template_rejects_non_text_format() {
+    let dir = setup_project(&[("a.rs", "// TODO: x\n")]);
+    todo_scan()
+        .args([
+            "list",
+            "--template",
+            "{{file}}",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--template replaces text output"));
+}
+
+#[test]
+fn test_list_threads_one_matches_default() {
+    let files: Vec<(String, String)> = (0..40)
+        .map(|i| {
+            (
+                format!("src/mod{}/file{}.rs", i % 5, i),
+                format!("// TODO: task {i}\nfn f() {{}}\n// FIXME: fix {i}\n"),
+            )
+        })
+        .collect();
+    let refs: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(p, c)| (p.as_str(), c.as_str()))
+        .collect();
+    let dir = setup_project(&refs);
+    let root = dir.path().to_str().unwrap();
+
+    let run = |extra: &[&str]| {
+        let output = todo_scan()
+            .args(["list", "--no-cache", "--format", "json", "--root", root])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        json["items"].clone()
+    };
+
+    let default = run(&[]);
+    assert_eq!(default.as_array().unwrap().len(), 80);
+    assert_eq!(run(&["--threads", "1"]), default);
+    assert_eq!(run(&["--threads", "0"]), default);
+}
+
+/// An executable editor stand-in that appends its arguments to `log`.
+#[cfg(unix)]
+fn recording_editor(dir: &std::path::Path, log: &std::path::Path) -> String {
+    use std::os::unix::fs::PermissionsExt;
+    let script = dir.join("record-editor");
+    fs::write(
+        &script,
+        format!("#!/bin/sh\necho \"$@\" >> '{}'\n", log.display()),
+    )
+    .unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    script.to_str().unwrap().to_string()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_list_open_launches_editor_per_item() {
+    let dir = setup_project(&[(
+        "src/a.rs",
+        "fn a() {}\n// FIXME: first\n// TODO: skip\n// FIXME: second\n",
+    )]);
+    let tools = TempDir::new().unwrap();
+    let log = tools.path().join("log");
+    let editor = recording_editor(tools.path(), &log);
+
+    todo_scan()
+        .args([
+            "list",
+            "--tag",
+            "FIXME",
+            "--open",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .env("EDITOR", &editor)
+        .assert()
+        .success()
+        .stdout("");
+
+    let file = dir.path().join("src/a.rs");
+    assert_eq!(
+        fs::read_to_string(&log).unwrap(),
+        format!("+2 {}\n+4 {}\n", file.display(), file.display())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_list_open_editor_flag_overrides_env() {
+    let dir = setup_project(&[("a.rs", "// TODO: only\n")]);
+    let tools = TempDir::new().unwrap();
+    let log = tools.path().join("log");
+    let editor = recording_editor(tools.path(), &log);
+
+    todo_scan()
+        .args([
+            "list",
+            "--open",
+            "--editor",
+            &editor,
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .env("EDITOR", "false")
+        .assert()
+        .success();
+    assert!(fs::read_to_string(&log).unwrap().starts_with("+1 "));
+}
+
+#[test]
+fn test_list_open_without_editor_lists_instead() {
+    let dir = setup_project(&[("a.rs", "// TODO: only\n")]);
+    todo_scan()
+        .args(["list", "--open", "--root", dir.path().to_str().unwrap()])
+        .env_remove("EDITOR")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("only"))
+        .stderr(predicate::str::contains("$EDITOR is not set"));
+}
+
+#[test]
+fn test_list_pick_requires_terminal() {
+    let dir = setup_project(&[("a.rs", "// TODO: only\n")]);
+    todo_scan()
+        .args([
+            "list",
+            "--pick",
+            "--editor",
+            "vim",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--pick requires an interactive terminal",
+        ));
+}
+
+#[test]
+fn test_list_editor_requires_open() {
+    let dir = setup_project(&[("a.rs", "// TODO: only\n")]);
+    todo_scan()
+        .args([
+            "list",
+            "--editor",
+            "vim",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_list_json_includes_escalation_target() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[scan]\nescalation = true\n"),
+        ("a.rs", "// TODO->FIXME: escalate soon\n// TODO: plain\n"),
+    ]);
+    let output = todo_scan()
+        .args([
+            "list",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items[0]["tag"], "TODO");
+    assert_eq!(items[0]["escalate_to"], "FIXME");
+    assert_eq!(items[0]["message"], "escalate soon");
+    assert!(items[1]["escalate_to"].is_null());
+}
+
+#[test]
+fn test_list_json_includes_mentions() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[scan]\nmentions = true\n"),
+        (
+            "a.rs",
+            "// TODO(alice): @bob please review, cc @carol\n// TODO: mail bob@example.com\n",
+        ),
+    ]);
+    let output = todo_scan()
+        .args([
+            "list",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let items = json["items"].as_array().unwrap();
+    assert_eq!(items[0]["author"], "alice");
+    assert_eq!(items[0]["mentions"], serde_json::json!(["bob", "carol"]));
+    assert_eq!(items[1]["mentions"], serde_json::json!([]));
+}
+
+#[test]
+fn test_list_filter_by_mention() {
+    let dir = setup_project(&[(
+        "a.rs",
+        "// TODO: @bob please review\n// TODO(bob): own work\n// FIXME: ask @carol\n",
+    )]);
+    todo_scan()
+        .args([
+            "list",
+            "--mention",
+            "@bob",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("please review"))
+        .stdout(predicate::str::contains("own work").not())
+        .stdout(predicate::str::contains("ask @carol").not());
+}
+
+#[test]
+fn test_list_configured_comment_prefixes() {
+    let dir = setup_project(&[
+        (
+            ".todo-scan.toml",
+            "[scan]\ncomment_prefixes = [\"REM \", \"::\"]\n",
+        ),
+        (
+            "build.bat",
+            "@echo off\nREM TODO: port to PowerShell\n:: FIXME: quote paths\n# TODO: not a comment\n",
+        ),
+        ("main.rs", "// TODO: replaced too\n"),
+    ]);
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("port to PowerShell"))
+        .stdout(predicate::str::contains("quote paths"))
+        .stdout(predicate::str::contains("replaced too").not())
+        .stdout(predicate::str::contains("not a comment").not());
+}
+
+#[test]
+fn test_list_configured_comment_prefixes_disable_hash_in_python() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[scan]\ncomment_prefixes = [\"//\"]\n"),
+        ("main.py", "# TODO: hash comment\nprint(1)\n"),
+        ("main.rs", "// TODO: slash comment\n"),
+    ]);
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("slash comment"))
+        .stdout(predicate::str::contains("hash comment").not());
+}
+
+#[test]
+fn test_list_rejects_empty_comment_prefix() {
+    let dir = setup_project(&[
+        (".todo-scan.toml", "[scan]\ncomment_prefixes = [\"\"]\n"),
+        ("a.rs", "// TODO: x\n"),
+    ]);
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "invalid [scan] comment_prefixes: prefixes must not be empty",
+        ));
+}
+
+#[test]
+fn test_list_profile_prints_phase_timings_to_stderr() {
+    let dir = setup_project(&[("main.rs", "// TODO: implement feature\n")]);
+
+    todo_scan()
+        .args(["list", "--profile", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("implement feature"))
+        .stdout(predicate::str::contains("walk+scan").not())
+        .stderr(predicate::str::contains("phase"))
+        .stderr(predicate::str::contains("cache load"))
+        .stderr(predicate::str::contains("walk+scan"))
+        .stderr(predicate::str::contains("output"))
+        .stderr(predicate::str::contains("total"));
+}
+
+#[test]
+fn test_list_profile_json_keeps_stdout_parseable() {
+    let dir = setup_project(&[("main.rs", "// TODO: implement feature\n")]);
+
+    let output = todo_scan()
+        .args([
+            "list",
+            "--profile",
+            "--no-cache",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stdout["items"].as_array().unwrap().len(), 1);
+
+    let stderr: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    let phases: Vec<&str> = stderr["timings"]["phases"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["phase"].as_str().unwrap())
+        .collect();
+    assert!(phases.contains(&"walk+scan"));
+    assert!(!phases.contains(&"cache load"));
+    assert!(stderr["timings"]["total_ms"].as_f64().is_some());
+}
+
+#[test]
+fn test_list_table_aligns_columns() {
+    let dir = setup_project(&[
+        ("src/main.rs", "// TODO: first\n// BUG: crash\n"),
+        ("a.rs", "// FIXME: broken\n"),
+    ]);
+
+    todo_scan()
+        .args(["list", "--root", dir.path().to_str().unwrap(), "--table"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "TAG    PRI  LOCATION       MESSAGE\n\
+             FIXME       a.rs:1         broken\n\
+             TODO        src/main.rs:1  first\n\
+             BUG         src/main.rs:2  crash\n\
+             3 items in 2 files\n",
+        ));
+}
+
+#[test]
+fn test_list_table_requires_text_format() {
+    let dir = setup_project(&[("main.rs", "// TODO: a\n")]);
+
+    todo_scan()
+        .args([
+            "list",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--table",
+            "--format",
+            "json",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--table is a text layout"));
+}
@@ -225,3 +225,155 @@ fn test_relate_proximity_threshold_flag() {
         .success()
         .stdout(predicate::str::contains("proximity"));
 }
+
+#[test]
+fn test_relate_dot_format_with_clusters() {
+    let dir = setup_project(&[(
+        "src/main.rs",
+        "// TODO: fix input validation\n// FIXME: broken input handling\nfn main() {}\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "relate",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--cluster",
+            "--format",
+            "dot",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("graph todo_scan {"))
+        .stdout(predicate::str::contains("subgraph cluster_1 {"))
+        .stdout(predicate::str::contains("[label=\"src/main.rs:1\"]"))
+        .stdout(predicate::str::contains("n1 -- n0 [label="))
+        // Locations are only ever labels, never bare node IDs
+        .stdout(predicate::str::contains("src/main.rs:1 --").not());
+}
+
+#[test]
+fn test_relate_min_cluster_size_summarizes_ungrouped() {
+    let dir = setup_project(&[
+        (
+            "src/auth.rs",
+            "// TODO: fix login\n// FIXME: broken login\n// BUG: login crash\n",
+        ),
+        ("src/cache.rs", "// TODO: evict\n// TODO: evict faster\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "relate",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--cluster",
+            "--min-cluster-size",
+            "3",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cluster 1"))
+        .stdout(predicate::str::contains("Cluster 2").not())
+        .stdout(predicate::str::contains("2 ungrouped items"));
+}
+
+#[test]
+fn test_relate_cluster_json_includes_member_details() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): fix input validation\n// FIXME: broken input handling\n",
+    )]);
+
+    let output = todo_scan()
+        .args([
+            "relate",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--cluster",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let members = json["clusters"][0]["members"].as_array().unwrap();
+    assert_eq!(members.len(), 2);
+    assert!(members.iter().any(|m| m["author"] == "alice"));
+    assert!(members.iter().all(|m| m["message"].is_string()));
+    assert_eq!(json["ungrouped_items"], 0);
+}
+
+#[test]
+fn test_relate_min_cluster_size_requires_cluster() {
+    let dir = setup_project(&[("main.rs", "// TODO: a\n")]);
+
+    todo_scan()
+        .args([
+            "relate",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--min-cluster-size",
+            "3",
+        ])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_relate_for_accepts_stable_id() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): fix input validation\n// FIXME: broken input handling\n",
+    )]);
+    let root = dir.path().to_str().unwrap();
+
+    let list = todo_scan()
+        .args(["list", "--root", root, "--format", "json"])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&list.stdout).unwrap();
+    let stable_id = json["items"][0]["stable_id"].as_str().unwrap().to_string();
+
+    let by_id = todo_scan()
+        .args([
+            "relate", "--root", root, "--for", &stable_id, "--format", "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(by_id.status.success());
+    let by_location = todo_scan()
+        .args([
+            "relate",
+            "--root",
+            root,
+            "--for",
+            "main.rs:1",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(by_id.stdout, by_location.stdout);
+}
+
+#[test]
+fn test_relate_for_unknown_id_fails_with_message() {
+    let dir = setup_project(&[("main.rs", "// TODO: a\n")]);
+
+    todo_scan()
+        .args([
+            "relate",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--for",
+            "deadbeefdeadbeef",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "no TODO with id 'deadbeefdeadbeef'",
+        ));
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -21,31 +24,11 @@ fn setup_project(files: &[(&str, &str)]) -> TempDir {
 
 fn setup_git_project(files: &[(&str, &str)]) -> TempDir {
     let dir = setup_project(files);
-    std::process::Command::new("git")
-        .args(["init"])
-        .current_dir(dir.path())
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.email", "test@test.com"])
-        .current_dir(dir.path())
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.name", "Test"])
-        .current_dir(dir.path())
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(dir.path())
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["commit", "-m", "initial"])
-        .current_dir(dir.path())
-        .output()
-        .unwrap();
+    git(dir.path(), &["init"]);
+    git(dir.path(), &["config", "user.email", "test@test.com"]);
+    git(dir.path(), &["config", "user.name", "Test"]);
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "initial"]);
     dir
 }
 
@@ -154,16 +137,8 @@ fn test_report_with_history() {
         "// TODO: first task\n// TODO: second task\n",
     )
     .unwrap();
-    std::process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(dir.path())
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["commit", "-m", "add second todo"])
-        .current_dir(dir.path())
-        .output()
-        .unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "add second todo"]);
 
     let output_path = dir.path().join("report.html");
 
@@ -185,6 +160,60 @@ fn test_report_with_history() {
     assert!(content.contains("\"history\""));
 }
 
+#[test]
+fn test_report_include_resolved_lists_removed_todos() {
+    let dir = setup_git_project(&[("main.rs", "// TODO: first task\n// FIXME: flaky test\n")]);
+
+    fs::write(dir.path().join("main.rs"), "// TODO: first task\n").unwrap();
+    git(dir.path(), &["commit", "-am", "fix flaky test"]);
+
+    let output_path = dir.path().join("report.html");
+    todo_scan()
+        .args([
+            "report",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--output",
+            output_path.to_str().unwrap(),
+            "--include-resolved",
+        ])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&output_path).unwrap();
+    assert!(content.contains("Resolved Recently"));
+    let start = content.find("const REPORT_DATA = ").unwrap() + "const REPORT_DATA = ".len();
+    let end = content[start..].find(";\n").unwrap() + start;
+    let parsed: serde_json::Value = serde_json::from_str(&content[start..end]).unwrap();
+
+    let resolved = &parsed["resolved"];
+    let items = resolved["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["tag"], "FIXME");
+    assert_eq!(items[0]["message"], "flaky test");
+    assert_eq!(resolved["resolution_rate"], 0.5);
+}
+
+#[test]
+fn test_report_without_include_resolved_omits_section_data() {
+    let dir = setup_git_project(&[("main.rs", "// TODO: first task\n")]);
+    let output_path = dir.path().join("report.html");
+
+    todo_scan()
+        .args([
+            "report",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--output",
+            output_path.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&output_path).unwrap();
+    assert!(!content.contains("\"resolved\""));
+}
+
 #[test]
 fn test_report_empty_project() {
     let dir = setup_project(&[("main.rs", "fn main() {}\n")]);
@@ -245,3 +274,45 @@ fn test_report_embedded_json_valid() {
     assert!(parsed["summary"]["total_items"].as_u64().unwrap() >= 3);
     assert!(parsed["items"].as_array().unwrap().len() >= 3);
 }
+
+#[test]
+fn test_report_history_file_accumulates_snapshots() {
+    let dir = setup_project(&[("main.rs", "// TODO: first\n// FIXME: second\n")]);
+    let output_path = dir.path().join("report.html");
+    let history_path = dir.path().join(".todo-scan-history.jsonl");
+
+    for _ in 0..2 {
+        todo_scan()
+            .args([
+                "report",
+                "--root",
+                dir.path().to_str().unwrap(),
+                "--output",
+                output_path.to_str().unwrap(),
+                "--history-file",
+                history_path.to_str().unwrap(),
+            ])
+            .assert()
+            .success();
+    }
+
+    // Both runs append; same-day entries collapse when read back
+    let content = fs::read_to_string(&history_path).unwrap();
+    let lines: Vec<serde_json::Value> = content
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["total"], 2);
+    assert_eq!(lines[0]["by_tag"]["TODO"], 1);
+    assert_eq!(lines[0]["by_tag"]["FIXME"], 1);
+    assert_eq!(lines[0]["by_priority"]["normal"], 2);
+
+    let html = fs::read_to_string(&output_path).unwrap();
+    let day = &lines[1]["date"].as_str().unwrap()[..10];
+    assert_eq!(
+        html.matches(&format!("\"date\":\"{}\"", day)).count(),
+        1,
+        "trend should hold one point for today"
+    );
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use assert_cmd::Command;
+use common::git;
+use serde_json::Value;
+use std::fs;
+use tempfile::TempDir;
+
+fn todo_scan() -> Command {
+    assert_cmd::cargo_bin_cmd!("todo-scan")
+}
+
+fn setup_project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(full_path, content).unwrap();
+    }
+    dir
+}
+
+fn schema_for(command: &str) -> Value {
+    let output = todo_scan().args(["schema", command]).output().unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn run_json(dir: &TempDir, args: &[&str]) -> Value {
+    let output = todo_scan()
+        .args(args)
+        .args(["--format", "json", "--root", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// Validate `instance` with a full JSON Schema validator; `label` names the
+/// run in the failure message.
+fn assert_valid(schema: &Value, instance: &Value, label: &str) {
+    let validator = jsonschema::validator_for(schema).unwrap();
+    let errors: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|e| format!("{} at {}", e, e.instance_path()))
+        .collect();
+    assert!(
+        errors.is_empty(),
+        "{label} does not match its schema: {errors:#?}\n{instance:#}"
+    );
+}
+
+/// Repo tagged `v1`, one commit later at `HEAD`, with an uncommitted edit.
+fn setup_history() -> TempDir {
+    let dir = setup_project(&[
+        ("plain.rs", "fn plain() {}\n"),
+        (
+            "main.rs",
+            "fn main() {\n    // TODO(alice): wire up #12 by:2030-01-01\n}\n",
+        ),
+        (
+            "lib.rs",
+            "// FIXME: fix the parser\n// todo-scan:ignore\n// HACK: hidden\n",
+        ),
+    ]);
+    let cwd = dir.path();
+    git(cwd, &["init", "-q"]);
+    git(cwd, &["config", "user.name", "Test"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-q", "-m", "initial"]);
+    git(cwd, &["tag", "v1"]);
+    fs::write(
+        cwd.join("lib.rs"),
+        "// FIXME: fix the parser\n// BUG(bob)!!: crashes on empty input\n",
+    )
+    .unwrap();
+    git(cwd, &["commit", "-q", "-am", "second"]);
+    fs::write(
+        cwd.join("util.rs"),
+        "// TODO: fix later\n// TODO: fix later\n",
+    )
+    .unwrap();
+    dir
+}
+
+/// Every `--format json` output mode, as `(schema command, arguments)`.
+const OUTPUT_MODES: &[(&str, &[&str])] = &[
+    ("list", &["list"]),
+    ("list", &["list", "--context", "1"]),
+    ("list", &["list", "--detail", "minimal"]),
+    ("list", &["list", "--detail", "full"]),
+    ("list", &["list", "--show-ignored"]),
+    ("list", &["list", "--count-only"]),
+    ("list", &["list", "--dedupe"]),
+    ("list", &["list", "--dedupe", "--detail", "minimal"]),
+    ("list", &["list", "--invert-match"]),
+    ("search", &["search", "fix"]),
+    ("search", &["search", "fix", "--history"]),
+    (
+        "search",
+        &["search", "fix", "--history", "--detail", "minimal"],
+    ),
+    ("diff", &["diff", "v1"]),
+    ("diff", &["diff", "v1..HEAD"]),
+    ("diff", &["diff", "v1", "--stat"]),
+    ("diff", &["diff", "v1..HEAD", "--stat"]),
+    ("blame", &["blame"]),
+    ("blame", &["blame", "--group-by", "author"]),
+    ("stats", &["stats"]),
+    ("stats", &["stats", "--since", "v1", "--by", "author"]),
+    ("stats", &["stats", "--history", "2"]),
+    ("stats", &["stats", "v1..HEAD"]),
+    ("check", &["check", "--max", "0"]),
+    ("lint", &["lint"]),
+    ("clean", &["clean"]),
+];
+
+#[test]
+fn test_schema_validates_every_output_mode() {
+    let dir = setup_history();
+    for (command, args) in OUTPUT_MODES {
+        let output = run_json(&dir, args);
+        assert_valid(&schema_for(command), &output, &args.join(" "));
+    }
+}
+
+/// Every key in `object` is declared and every required key is present.
+fn assert_matches_properties(object: &Value, schema: &Value) {
+    let props = schema["properties"].as_object().unwrap();
+    for key in object.as_object().unwrap().keys() {
+        assert!(props.contains_key(key), "schema missing property {key}");
+    }
+    for key in schema["required"].as_array().unwrap() {
+        let key = key.as_str().unwrap();
+        assert!(object.get(key).is_some(), "output missing required {key}");
+    }
+}
+
+#[test]
+fn test_schema_list_matches_output_with_context() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "fn main() {\n    // TODO(alice): wire up #12 by:2030-01-01\n}\n",
+    )]);
+    let schema = schema_for("list");
+    let output = run_json(&dir, &["list", "--context", "1"]);
+
+    assert_matches_properties(&output, &schema["$defs"]["ScanResult"]);
+    let item = &output["items"][0];
+    assert!(item.get("context").is_some());
+    assert_matches_properties(item, &schema["$defs"]["JsonTodoItem"]);
+}
+
+#[test]
+fn test_schema_list_accepts_minimal_detail() {
+    let dir = setup_project(&[("main.rs", "// FIXME: fix\n")]);
+    let schema = schema_for("list");
+    let output = run_json(&dir, &["list", "--detail", "minimal"]);
+
+    assert!(output["items"][0].get("priority").is_none());
+    assert_matches_properties(&output["items"][0], &schema["$defs"]["JsonTodoItem"]);
+}
+
+#[test]
+fn test_schema_export_matches_each_line() {
+    let dir = setup_project(&[("main.rs", "// TODO: one\n// BUG(bob): two\n")]);
+    let schema = schema_for("export");
+    let output = todo_scan()
+        .args(["export", "--root", dir.path().to_str().unwrap()])
+        .output()
+        .unwrap();
+
+    for line in String::from_utf8(output.stdout).unwrap().lines() {
+        let item: Value = serde_json::from_str(line).unwrap();
+        assert_matches_properties(&item, &schema);
+        assert_valid(&schema, &item, "export");
+    }
+}
+
+#[test]
+fn test_schema_rejects_unknown_command() {
+    todo_scan().args(["schema", "watch"]).assert().failure();
+}
+
+#[test]
+fn test_schema_is_hidden_from_help() {
+    let output = todo_scan().arg("--help").output().unwrap();
+    let help = String::from_utf8(output.stdout).unwrap();
+    assert!(!help.contains("schema"));
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -100,6 +103,52 @@ fn test_search_author_filter() {
         .stdout(predicate::str::contains("1 matches"));
 }
 
+#[test]
+fn test_search_mention_filter() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: fix this, @alice\n// TODO: fix that, @bob\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "search",
+            "fix",
+            "--mention",
+            "alice",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fix this"))
+        .stdout(predicate::str::contains("fix that").not())
+        .stdout(predicate::str::contains("1 matches"));
+}
+
+#[test]
+fn test_search_priority_min_filter() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO!!: fix urgent\n// TODO!: fix high\n// TODO: fix normal\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "search",
+            "fix",
+            "--priority-min",
+            "urgent",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fix urgent"))
+        .stdout(predicate::str::contains("fix high").not())
+        .stdout(predicate::str::contains("1 matches"));
+}
+
 #[test]
 fn test_search_tag_filter() {
     let dir = setup_project(&[(
@@ -570,3 +619,126 @@ fn test_search_text_shows_expired_deadline() {
         .success()
         .stdout(predicate::str::contains("[expired: 2020-01-01]"));
 }
+
+fn setup_history_repo() -> TempDir {
+    let dir = setup_project(&[("auth.rs", "// TODO: hardcoded password\n")]);
+    let cwd = dir.path();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["config", "user.name", "Test"]);
+    git(cwd, &["add", "-A"]);
+    git(cwd, &["commit", "-m", "one"]);
+    fs::write(cwd.join("auth.rs"), "fn auth() {}\n").unwrap();
+    fs::write(cwd.join("env.rs"), "// FIXME: read password from env\n").unwrap();
+    git(cwd, &["add", "-A"]);
+    git(cwd, &["commit", "-m", "two"]);
+    dir
+}
+
+#[test]
+fn test_search_history_text_output() {
+    let dir = setup_history_repo();
+
+    todo_scan()
+        .args([
+            "search",
+            "password",
+            "--history",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("auth.rs:1:"))
+        .stdout(predicate::str::contains("removed)"))
+        .stdout(predicate::str::contains("env.rs:1:"))
+        .stdout(predicate::str::contains("present)"))
+        .stdout(predicate::str::contains("2 matches in 2 commits"));
+}
+
+#[test]
+fn test_search_history_json_fields_and_filters() {
+    let dir = setup_history_repo();
+
+    let output = todo_scan()
+        .args([
+            "search",
+            "password",
+            "--history",
+            "--tag",
+            "TODO",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["match_count"], 1);
+    assert_eq!(json["commits_scanned"], 2);
+    let m = &json["matches"][0];
+    assert_eq!(m["file"], "auth.rs");
+    assert_eq!(m["present_now"], false);
+    assert_eq!(m["first_seen_commit"].as_str().unwrap().len(), 8);
+    assert!(m["first_seen_date"].is_string());
+}
+
+#[test]
+fn test_search_max_commits_requires_history() {
+    let dir = setup_project(&[("main.rs", "// TODO: x\n")]);
+
+    todo_scan()
+        .args([
+            "search",
+            "x",
+            "--max-commits",
+            "5",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--history"));
+}
+
+#[test]
+fn test_search_width_truncates_long_messages() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO: cache the response so repeated lookups stay cheap\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "search",
+            "cache",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--width",
+            "24",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  L1: [TODO] cache the…\n"))
+        .stdout(predicate::str::contains("1 matches across 1 files"));
+}
+
+#[test]
+fn test_search_template() {
+    let dir = setup_project(&[("a.rs", "// TODO: fix parser\n// TODO: other\n")]);
+    todo_scan()
+        .args([
+            "search",
+            "parser",
+            "--template",
+            r"{{line}}\t{{message|upper}}",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout("1\tFIX PARSER\n");
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -89,6 +92,81 @@ fn test_stats_hotspot_files() {
         .stdout(predicate::str::contains("lib.rs (1)"));
 }
 
+fn setup_many_authors() -> TempDir {
+    let content: String = (0..4)
+        .map(|i| format!("// TODO(dev{}): task\n", i))
+        .collect();
+    setup_project(&[
+        ("a.rs", &content),
+        ("b.rs", "// TODO: b\n// TODO: b2\n"),
+        ("c.rs", "// TODO: c\n"),
+    ])
+}
+
+#[test]
+fn test_stats_top_truncates_lists() {
+    let dir = setup_many_authors();
+
+    todo_scan()
+        .args([
+            "stats",
+            "--top",
+            "2",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("… and 3 more"))
+        .stdout(predicate::str::contains("… and 1 more"))
+        .stdout(predicate::str::contains("c.rs (1)").not())
+        .stdout(predicate::str::contains("7 items across 3 files"));
+}
+
+#[test]
+fn test_stats_top_json_flags_truncation() {
+    let dir = setup_many_authors();
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--top",
+            "1",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["truncated"], true);
+    assert_eq!(json["author_counts"].as_array().unwrap().len(), 1);
+    assert_eq!(json["hotspot_files"][0][0], "a.rs");
+    assert_eq!(json["total_authors"], 5);
+    assert_eq!(json["total_items"], 7);
+}
+
+#[test]
+fn test_stats_top_zero_shows_all() {
+    let dir = setup_many_authors();
+
+    todo_scan()
+        .args([
+            "stats",
+            "--top",
+            "0",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("more").not())
+        .stdout(predicate::str::contains("c.rs (1)"));
+}
+
 #[test]
 fn test_stats_json_format() {
     let dir = setup_project(&[("main.rs", "// TODO: json test\n// FIXME: another\n")]);
@@ -137,21 +215,9 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
     let dir = TempDir::new().unwrap();
     let cwd = dir.path();
 
-    std::process::Command::new("git")
-        .args(["init"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.email", "test@test.com"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.name", "Test"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["config", "user.name", "Test"]);
 
     for (path, content) in files {
         let full_path = cwd.join(path);
@@ -161,16 +227,8 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
         fs::write(full_path, content).unwrap();
     }
 
-    std::process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["commit", "-m", "initial"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "initial"]);
 
     dir
 }
@@ -224,3 +282,454 @@ fn test_stats_with_trend_json() {
     assert!(json.get("trend").is_some());
     assert_eq!(json["trend"]["base_ref"].as_str().unwrap(), "HEAD");
 }
+
+#[test]
+fn test_stats_csv_format() {
+    let dir = setup_project(&[
+        (
+            "main.rs",
+            "// TODO(alice): one\n// TODO: two\n// BUG!!: three\n",
+        ),
+        ("lib.rs", "// HACK: four\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "csv",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let mut reader = csv::Reader::from_reader(output.stdout.as_slice());
+    assert_eq!(
+        reader.headers().unwrap().iter().collect::<Vec<_>>(),
+        vec!["section", "name", "count"]
+    );
+    let rows: Vec<Vec<String>> = reader
+        .records()
+        .map(|r| r.unwrap().iter().map(String::from).collect())
+        .collect();
+    let has = |section: &str, name: &str, count: &str| {
+        rows.iter()
+            .any(|r| r[0] == section && r[1] == name && r[2] == count)
+    };
+    assert!(has("summary", "total_items", "4"));
+    assert!(has("summary", "total_files", "2"));
+    assert!(has("tag", "TODO", "2"));
+    assert!(has("priority", "urgent", "1"));
+    assert!(has("author", "alice", "1"));
+    assert!(has("hotspot", "main.rs", "3"));
+}
+
+#[test]
+fn test_stats_author_churn_json() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: keep\n// FIXME: drop me\n")]);
+    let cwd = dir.path();
+
+    fs::write(cwd.join("main.rs"), "// TODO: keep\n").unwrap();
+    fs::write(cwd.join("lib.rs"), "// HACK: new debt\n").unwrap();
+    git(cwd, &["add", "."]);
+    git(
+        cwd,
+        &[
+            "-c",
+            "user.name=Alice",
+            "-c",
+            "user.email=alice@test.com",
+            "commit",
+            "-m",
+            "churn",
+        ],
+    );
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--since",
+            "HEAD~1",
+            "--by",
+            "author",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let churn = json["author_churn"].as_array().unwrap();
+    assert_eq!(churn.len(), 2);
+    assert_eq!(churn[0]["author"], "Alice");
+    assert_eq!(churn[0]["added"], 1);
+    assert_eq!(churn[0]["removed"], 0);
+    assert_eq!(churn[1]["author"], "Test");
+    assert_eq!(churn[1]["added"], 0);
+    assert_eq!(churn[1]["removed"], 1);
+}
+
+#[test]
+fn test_stats_author_churn_text() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: existing task\n")]);
+    let cwd = dir.path();
+
+    fs::write(
+        cwd.join("main.rs"),
+        "// TODO: existing task\n// FIXME: new\n",
+    )
+    .unwrap();
+
+    todo_scan()
+        .args([
+            "stats",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--since",
+            "HEAD",
+            "--by",
+            "author",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Author churn"));
+}
+
+#[test]
+fn test_stats_without_by_has_null_author_churn() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: task\n")]);
+    let cwd = dir.path();
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--since",
+            "HEAD",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["author_churn"].is_null());
+}
+
+#[test]
+fn test_stats_by_author_requires_since() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--by",
+            "author",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--since"));
+}
+
+#[test]
+fn test_stats_markdown_format() {
+    let dir = setup_project(&[(
+        "main.rs",
+        "// TODO(alice): one\n// TODO: two\n// FIXME: !! three\n",
+    )]);
+
+    todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "markdown",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## Tags"))
+        .stdout(predicate::str::contains("| TODO | 2 |"))
+        .stdout(predicate::str::contains("| urgent | 1 |"))
+        .stdout(predicate::str::contains("| alice | 1 |"))
+        .stdout(predicate::str::contains("| main.rs | 3 |"))
+        .stdout(predicate::str::contains("**3 items across 1 files**"));
+}
+
+#[test]
+fn test_stats_html_fragment() {
+    let dir = setup_project(&[("main.rs", "// TODO(<b>mallory</b>): one\n// FIXME: two\n")]);
+
+    todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "html",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "<div class=\"todo-scan-stats\"",
+        ))
+        .stdout(predicate::str::contains("<svg"))
+        .stdout(predicate::str::contains("&lt;b&gt;mallory&lt;/b&gt;"))
+        .stdout(predicate::str::contains("<b>").not())
+        .stdout(predicate::str::contains("<html").not());
+}
+
+// --- Comparing two refs ---
+
+/// Repo tagged `v1` and `v2`, with the working tree changed after `v2`.
+fn setup_two_releases() -> TempDir {
+    let dir = setup_git_repo(&[(
+        "a.rs",
+        "// TODO(alice): one\n// FIXME: two\n// FIXME: three\n",
+    )]);
+    let cwd = dir.path();
+    git(cwd, &["tag", "v1"]);
+    fs::write(
+        cwd.join("a.rs"),
+        "// TODO(alice): one\n// TODO(bob)!!: urgent\n// FIXME: three\n",
+    )
+    .unwrap();
+    fs::write(cwd.join("b.rs"), "// HACK: new file\n").unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "second"]);
+    git(cwd, &["tag", "v2"]);
+    // Uncommitted work must not leak into either snapshot
+    fs::write(cwd.join("c.rs"), "// BUG: uncommitted\n").unwrap();
+    dir
+}
+
+#[test]
+fn test_stats_compare_refs_text() {
+    let dir = setup_two_releases();
+    todo_scan()
+        .args(["stats", "v1..v2", "--root", dir.path().to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"Tags\s+v1\s+v2\s+change").unwrap())
+        .stdout(predicate::str::is_match(r"TODO\s+1\s+2\s+\+1").unwrap())
+        .stdout(predicate::str::is_match(r"FIXME\s+2\s+1\s+-1").unwrap())
+        .stdout(predicate::str::is_match(r"Items\s+3\s+4\s+\+1").unwrap())
+        .stdout(predicate::str::contains("BUG").not());
+}
+
+#[test]
+fn test_stats_compare_refs_json() {
+    let dir = setup_two_releases();
+    let output = todo_scan()
+        .args([
+            "stats",
+            "v1..v2",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["base_ref"], "v1");
+    assert_eq!(json["head_ref"], "v2");
+    assert_eq!(json["base"]["total_items"], 3);
+    assert_eq!(json["head"]["total_items"], 4);
+    assert_eq!(json["head"]["priority_counts"]["urgent"], 1);
+
+    let row = |section: &str, name: &str| {
+        json[section]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["name"] == name)
+            .cloned()
+            .unwrap()
+    };
+    assert_eq!(row("tags", "HACK")["base"], 0);
+    assert_eq!(row("tags", "HACK")["delta"], 1);
+    assert_eq!(row("priorities", "urgent")["delta"], 1);
+    assert_eq!(row("authors", "bob")["head"], 1);
+    assert_eq!(row("totals", "files")["delta"], 1);
+}
+
+#[test]
+fn test_stats_compare_refs_from_subdirectory() {
+    let dir = setup_git_repo(&[
+        ("a.rs", "// TODO: at the top\n"),
+        ("sub/b.rs", "// FIXME: below\n"),
+    ]);
+    let cwd = dir.path();
+    git(cwd, &["tag", "v1"]);
+    fs::write(cwd.join("sub/b.rs"), "// FIXME: below\n// HACK: added\n").unwrap();
+    git(cwd, &["commit", "-am", "second"]);
+    git(cwd, &["tag", "v2"]);
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "v1..v2",
+            "--format",
+            "json",
+            "--root",
+            cwd.join("sub").to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["base"]["total_items"], 1);
+    assert_eq!(json["head"]["total_items"], 2);
+    let tags: Vec<&str> = json["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(tags, vec!["FIXME", "HACK"]);
+}
+
+#[test]
+fn test_stats_compare_rejects_html() {
+    let dir = setup_two_releases();
+    todo_scan()
+        .args([
+            "stats",
+            "v1..v2",
+            "--format",
+            "html",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("--format html is not supported"));
+}
+
+#[test]
+fn test_stats_compare_rejects_bad_ranges() {
+    let dir = setup_two_releases();
+    let root = dir.path().to_str().unwrap();
+    todo_scan()
+        .args(["stats", "v1", "--root", root])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("expected BASE..HEAD"));
+    todo_scan()
+        .args(["stats", "v1...v2", "--root", root])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("three-dot ranges"));
+    todo_scan()
+        .args(["stats", "v1..nope", "--root", root])
+        .assert()
+        .code(3);
+    todo_scan()
+        .args(["stats", "v1..v2", "--since", "v1", "--root", root])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_stats_history_sparkline() {
+    let dir = setup_git_repo(&[("main.rs", "// TODO: first\n")]);
+    let cwd = dir.path();
+    fs::write(cwd.join("main.rs"), "// TODO: first\n// TODO: second\n").unwrap();
+    git(cwd, &["commit", "-am", "second"]);
+
+    todo_scan()
+        .args(["stats", "--root", cwd.to_str().unwrap(), "--history", "5"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("History (2 commits): ▁█  1 → 2"));
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            cwd.to_str().unwrap(),
+            "--history",
+            "5",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let counts: Vec<u64> = json["history"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["count"].as_u64().unwrap())
+        .collect();
+    assert_eq!(counts, vec![1, 2]);
+}
+
+#[test]
+fn test_stats_exclude_path() {
+    let dir = setup_project(&[
+        ("src/lib.rs", "// TODO: a\n// TODO: b\n"),
+        ("tests/it.rs", "// TODO: c\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--exclude-path",
+            "tests/**",
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["total_items"], 2);
+    assert_eq!(json["total_files"], 1);
+}
+
+#[test]
+fn test_stats_without_history_has_null_history() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    let output = todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+        ])
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json["history"].is_null());
+}
+
+#[test]
+fn test_stats_history_outside_git_fails() {
+    let dir = setup_project(&[("main.rs", "// TODO: task\n")]);
+
+    todo_scan()
+        .args([
+            "stats",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--history",
+            "3",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot read git history"));
+}
@@ -1,4 +1,7 @@
+mod common;
+
 use assert_cmd::Command;
+use common::git;
 use predicates::prelude::*;
 use std::fs;
 use tempfile::TempDir;
@@ -338,21 +341,9 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
     let dir = TempDir::new().unwrap();
     let cwd = dir.path();
 
-    std::process::Command::new("git")
-        .args(["init"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.email", "test@test.com"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["config", "user.name", "Test"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["init"]);
+    git(cwd, &["config", "user.email", "test@test.com"]);
+    git(cwd, &["config", "user.name", "Test"]);
 
     for (path, content) in files {
         let full_path = cwd.join(path);
@@ -362,16 +353,8 @@ fn setup_git_repo(files: &[(&str, &str)]) -> TempDir {
         fs::write(full_path, content).unwrap();
     }
 
-    std::process::Command::new("git")
-        .args(["add", "."])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
-    std::process::Command::new("git")
-        .args(["commit", "-m", "initial"])
-        .current_dir(cwd)
-        .output()
-        .unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "initial"]);
 
     dir
 }
@@ -472,3 +455,59 @@ fn test_tasks_filter_by_normal_priority() {
         .stdout(predicate::str::contains("\"total\": 1"))
         .stdout(predicate::str::contains("normal task"));
 }
+
+#[test]
+fn test_tasks_gh_issue_format() {
+    let dir = setup_project(&[("main.rs", "// FIXME: crash on login #42\n")]);
+
+    let output = todo_scan()
+        .args([
+            "tasks",
+            "--dry-run",
+            "--format",
+            "gh-issue",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let issues = json.as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["title"], "Fix crash on login #42");
+    let body = issues[0]["body"].as_str().unwrap();
+    assert!(body.contains("`main.rs:1`"));
+    assert!(body.contains("Issue: #42"));
+    assert_eq!(
+        issues[0]["labels"],
+        serde_json::json!(["fixme", "priority:normal"])
+    );
+}
+
+#[test]
+fn test_tasks_gh_issue_output_writes_markdown_files() {
+    let dir = setup_project(&[("main.rs", "// TODO: first task\n// BUG: second task\n")]);
+    let output_dir = dir.path().join("issues");
+
+    todo_scan()
+        .args([
+            "tasks",
+            "--format",
+            "gh-issue",
+            "--root",
+            dir.path().to_str().unwrap(),
+            "--output",
+            output_dir.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+
+    assert!(!output_dir.join("task-0001.json").exists());
+    let content = fs::read_to_string(output_dir.join("task-0001.md")).unwrap();
+    assert!(content.starts_with(
+        "---\ntitle: \"Fix second task\"\nlabels: [\"bug\", \"priority:normal\"]\n---\n"
+    ));
+    assert!(output_dir.join("task-0002.md").exists());
+}
@@ -292,3 +292,52 @@ fn test_watch_stopped_message() {
     // On Unix, killed processes exit with signal, not success code
     assert!(!status.success() || status.success());
 }
+
+#[test]
+fn test_watch_clear_draws_dashboard() {
+    let dir = setup_project(&[("a.rs", "// BUG: !! crash on start\n// TODO: later\n")]);
+
+    let bin = assert_cmd::cargo_bin!("todo-scan");
+    let mut child = StdCommand::new(bin)
+        .args(["watch", "--clear", "--root", dir.path().to_str().unwrap()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start todo-scan watch");
+
+    let stdout = child.stdout.take().unwrap();
+    let rx = spawn_line_reader(stdout);
+
+    let lines = collect_until(&rx, Duration::from_secs(5), |line| {
+        line.contains("Ctrl+C to stop")
+    });
+
+    child.kill().ok();
+    child.wait().ok();
+
+    let output = lines.join("\n");
+    // Alternate screen, then a cleared screen with the summary
+    assert!(output.contains("\x1b[?1049h"), "output: {}", output);
+    assert!(output.contains("2 items"), "output: {}", output);
+    assert!(output.contains("Top urgent"), "output: {}", output);
+    assert!(output.contains("crash on start"), "output: {}", output);
+    assert!(!output.contains("Initial scan"), "output: {}", output);
+}
+
+#[test]
+fn test_watch_clear_rejects_json_format() {
+    let dir = setup_project(&[("a.rs", "// TODO: test\n")]);
+
+    todo_scan()
+        .args([
+            "watch",
+            "--clear",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--clear"));
+}
@@ -375,6 +375,92 @@ block_tags = ["BUG"]
         .stdout(predicate::str::contains("workspace/block-tag"));
 }
 
+#[test]
+fn check_workspace_default_max_applies_to_unconfigured_packages() {
+    let dir = setup_project(&[
+        (
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["crates/core", "crates/cli"]
+"#,
+        ),
+        (
+            ".todo-scan.toml",
+            r#"
+[workspace]
+default_max = 1
+
+[workspace.packages.core]
+max = 5
+"#,
+        ),
+        (
+            "crates/core/main.rs",
+            "// TODO: first\n// TODO: second\n// TODO: third\n",
+        ),
+        ("crates/cli/main.rs", "// TODO: first\n// TODO: second\n"),
+    ]);
+
+    todo_scan()
+        .args([
+            "check",
+            "--workspace",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "package 'cli' has 2 TODOs (default max: 1)",
+        ))
+        .stdout(predicate::str::contains("package 'core'").not());
+}
+
+#[test]
+fn check_workspace_total_max_exceeded() {
+    let dir = setup_project(&[
+        (
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["crates/core", "crates/cli"]
+"#,
+        ),
+        (
+            ".todo-scan.toml",
+            r#"
+[workspace]
+total_max = 3
+default_max = 2
+"#,
+        ),
+        ("crates/core/main.rs", "// TODO: first\n// TODO: second\n"),
+        ("crates/cli/main.rs", "// TODO: first\n// TODO: second\n"),
+    ]);
+
+    let output = todo_scan()
+        .args([
+            "check",
+            "--workspace",
+            "--format",
+            "json",
+            "--root",
+            dir.path().to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let violations = json["violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0]["rule"], "workspace/total-max");
+    assert_eq!(
+        violations[0]["message"],
+        "workspace has 4 TODOs across 2 packages (total max: 3)"
+    );
+}
+
 // --- error cases ---
 
 #[test]